@@ -37,12 +37,155 @@ pub struct ParsedCommit {
 }
 
 impl ParsedCommit {
+    /// Metadata key of the issues closed by the commit, as comma-separated
+    /// numbers (e.g. `12,34`).
+    pub const CLOSES_METADATA: &'static str = "closes";
+
+    /// Metadata key of the pull request of the commit (e.g. `56` or `#56`).
+    pub const PR_METADATA: &'static str = "pr";
+
+    /// Metadata key of the co-authors of the commit, as newline-separated
+    /// names.
+    pub const CO_AUTHORS_METADATA: &'static str = "co_authors";
+
+    /// Metadata key flagging the commit as a highlight of its release
+    /// (e.g. `true`).
+    pub const HIGHLIGHT_METADATA: &'static str = "highlight";
+
+    /// Metadata key of the GitHub login of the author (e.g. `octocat`).
+    pub const AUTHOR_LOGIN_METADATA: &'static str = "author_login";
+
+    /// Metadata key of the issue tracker tickets of the commit, as
+    /// comma-separated keys (e.g. `PROJ-12,PROJ-34`).
+    pub const TICKET_METADATA: &'static str = "ticket";
+
+    /// Metadata key of the changelog fragment the entry was written in
+    /// (e.g. `123.feat.md`), for entries not coming from a commit.
+    pub const FRAGMENT_METADATA: &'static str = "fragment";
+
+    /// Metadata key of the changelog entry replacing the message of the
+    /// commit, or `skip` to leave the commit out of the changelog.
+    pub const CHANGELOG_METADATA: &'static str = "changelog";
+
+    /// Metadata key of the description of the breaking change of the commit,
+    /// from its `BREAKING CHANGE:` footer.
+    pub const BREAKING_CHANGE_METADATA: &'static str = "breaking_change";
+
     /// Creates a new parsed commit builder.
     #[must_use]
     pub fn builder(hash: impl Into<String>, r#type: impl Into<String>) -> ParsedCommitBuilder {
         ParsedCommitBuilder::new(hash, r#type)
     }
 
+    /// Returns the numbers of the issues closed by this commit.
+    #[must_use]
+    pub fn closed_issues(&self) -> Vec<&str> {
+        self.metadata
+            .get(Self::CLOSES_METADATA)
+            .map(|issues| {
+                issues
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|issue| !issue.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the issue tracker tickets of this commit (e.g. JIRA keys).
+    #[must_use]
+    pub fn tickets(&self) -> Vec<&str> {
+        self.metadata
+            .get(Self::TICKET_METADATA)
+            .map(|tickets| {
+                tickets
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|ticket| !ticket.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of the pull request of this commit.
+    #[must_use]
+    pub fn pull_request(&self) -> Option<&str> {
+        self.metadata
+            .get(Self::PR_METADATA)
+            .map(|pr| pr.trim().trim_start_matches('#'))
+            .filter(|pr| !pr.is_empty())
+    }
+
+    /// Returns the names of the co-authors of this commit.
+    #[must_use]
+    pub fn co_authors(&self) -> Vec<&str> {
+        self.metadata
+            .get(Self::CO_AUTHORS_METADATA)
+            .map(|names| {
+                names
+                    .lines()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the GitHub login of the author, if it has been looked up.
+    #[must_use]
+    pub fn author_login(&self) -> Option<&str> {
+        self.metadata
+            .get(Self::AUTHOR_LOGIN_METADATA)
+            .map(String::as_str)
+    }
+
+    /// Returns true if this commit is flagged as a highlight of its release.
+    #[must_use]
+    pub fn is_highlight(&self) -> bool {
+        self.metadata
+            .get(Self::HIGHLIGHT_METADATA)
+            .is_some_and(|value| !matches!(value.trim(), "" | "false"))
+    }
+
+    /// Returns the text of the changelog entry of this commit: its
+    /// `Changelog:` footer when set, its message otherwise.
+    #[must_use]
+    pub fn changelog_text(&self) -> &str {
+        self.metadata
+            .get(Self::CHANGELOG_METADATA)
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty() && !entry.eq_ignore_ascii_case("skip"))
+            .unwrap_or(&self.message)
+    }
+
+    /// Returns true if this commit is left out of the changelog with
+    /// `Changelog: skip`.
+    #[must_use]
+    pub fn is_changelog_skipped(&self) -> bool {
+        self.metadata
+            .get(Self::CHANGELOG_METADATA)
+            .is_some_and(|entry| entry.trim().eq_ignore_ascii_case("skip"))
+    }
+
+    /// Returns the description of the breaking change of this commit, from
+    /// its `BREAKING CHANGE:` footer.
+    #[must_use]
+    pub fn breaking_change(&self) -> Option<&str> {
+        self.metadata
+            .get(Self::BREAKING_CHANGE_METADATA)
+            .map(|description| description.trim())
+            .filter(|description| !description.is_empty())
+    }
+
+    /// Returns the changelog fragment this entry was written in, if it does
+    /// not come from a commit.
+    #[must_use]
+    pub fn fragment(&self) -> Option<&str> {
+        self.metadata
+            .get(Self::FRAGMENT_METADATA)
+            .map(String::as_str)
+    }
+
     /// Returns true if this commit represents a feature.
     #[must_use]
     pub fn is_feature(&self) -> bool {
@@ -139,6 +282,46 @@ impl ParsedCommitBuilder {
         self
     }
 
+    /// Records the issues closed by the commit, if any.
+    #[must_use]
+    pub fn closed_issues(self, issues: &[String]) -> Self {
+        if issues.is_empty() {
+            return self;
+        }
+        self.metadata(ParsedCommit::CLOSES_METADATA, issues.join(","))
+    }
+
+    /// Records the co-authors of the commit, if any.
+    #[must_use]
+    pub fn co_authors(self, names: &[String]) -> Self {
+        if names.is_empty() {
+            return self;
+        }
+        self.metadata(ParsedCommit::CO_AUTHORS_METADATA, names.join("\n"))
+    }
+
+    /// Records the changelog entry of the commit, if any.
+    #[must_use]
+    pub fn changelog_entry(self, entry: Option<&str>) -> Self {
+        match entry {
+            Some(entry) => self.metadata(ParsedCommit::CHANGELOG_METADATA, entry),
+            None => self,
+        }
+    }
+
+    /// Records the description of the breaking change of the commit, if
+    /// any, marking the commit as breaking.
+    #[must_use]
+    pub fn breaking_change(mut self, description: Option<&str>) -> Self {
+        match description {
+            Some(description) => {
+                self.breaking = true;
+                self.metadata(ParsedCommit::BREAKING_CHANGE_METADATA, description)
+            }
+            None => self,
+        }
+    }
+
     /// Sets the author.
     #[must_use]
     pub fn author(mut self, author: impl Into<String>) -> Self {
@@ -191,6 +374,138 @@ mod tests {
         assert_eq!(commit.emoji, Some("✨".to_string()));
     }
 
+    #[test]
+    fn test_closed_issues() {
+        let commit = ParsedCommit::builder("abc123", "fix")
+            .closed_issues(&["12".to_string(), "34".to_string()])
+            .build();
+        assert_eq!(commit.closed_issues(), vec!["12", "34"]);
+
+        let commit = ParsedCommit::builder("abc123", "fix")
+            .closed_issues(&[])
+            .build();
+        assert!(commit.metadata.is_empty());
+        assert!(commit.closed_issues().is_empty());
+    }
+
+    #[test]
+    fn test_tickets() {
+        let commit = ParsedCommit::builder("abc123", "fix")
+            .metadata(ParsedCommit::TICKET_METADATA, "PROJ-1, PROJ-2")
+            .build();
+        assert_eq!(commit.tickets(), vec!["PROJ-1", "PROJ-2"]);
+
+        let commit = ParsedCommit::builder("abc123", "fix").build();
+        assert!(commit.tickets().is_empty());
+    }
+
+    #[test]
+    fn test_changelog_text() {
+        let commit = ParsedCommit::builder("abc123", "fix")
+            .message("retry on 503")
+            .changelog_entry(Some("Uploads no longer fail when the server is busy"))
+            .build();
+        assert_eq!(
+            commit.changelog_text(),
+            "Uploads no longer fail when the server is busy"
+        );
+        assert!(!commit.is_changelog_skipped());
+
+        let commit = ParsedCommit::builder("abc123", "fix")
+            .message("retry on 503")
+            .changelog_entry(None)
+            .build();
+        assert_eq!(commit.changelog_text(), "retry on 503");
+    }
+
+    #[test]
+    fn test_changelog_skipped() {
+        let commit = ParsedCommit::builder("abc123", "chore")
+            .message("bump deps")
+            .changelog_entry(Some("Skip"))
+            .build();
+        assert!(commit.is_changelog_skipped());
+        assert_eq!(commit.changelog_text(), "bump deps");
+    }
+
+    #[test]
+    fn test_breaking_change() {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .message("new config format")
+            .breaking_change(Some("`unduler.yml` is no longer read"))
+            .build();
+        assert!(commit.breaking);
+        assert_eq!(
+            commit.breaking_change(),
+            Some("`unduler.yml` is no longer read")
+        );
+
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .breaking_change(None)
+            .build();
+        assert!(!commit.breaking);
+        assert!(commit.breaking_change().is_none());
+    }
+
+    #[test]
+    fn test_fragment() {
+        let commit = ParsedCommit::builder("", "feat")
+            .metadata(ParsedCommit::FRAGMENT_METADATA, "123.feat.md")
+            .build();
+        assert_eq!(commit.fragment(), Some("123.feat.md"));
+        assert!(
+            ParsedCommit::builder("abc123", "feat")
+                .build()
+                .fragment()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_co_authors() {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .co_authors(&["Jane Doe".to_string(), "Bob".to_string()])
+            .build();
+        assert_eq!(commit.co_authors(), vec!["Jane Doe", "Bob"]);
+
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .co_authors(&[])
+            .build();
+        assert!(commit.co_authors().is_empty());
+    }
+
+    #[test]
+    fn test_is_highlight() {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .metadata(ParsedCommit::HIGHLIGHT_METADATA, "true")
+            .build();
+        assert!(commit.is_highlight());
+
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .metadata(ParsedCommit::HIGHLIGHT_METADATA, "false")
+            .build();
+        assert!(!commit.is_highlight());
+        assert!(
+            !ParsedCommit::builder("abc123", "feat")
+                .build()
+                .is_highlight()
+        );
+    }
+
+    #[test]
+    fn test_pull_request() {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .metadata("pr", "#56")
+            .build();
+        assert_eq!(commit.pull_request(), Some("56"));
+        assert_eq!(
+            ParsedCommit::builder("abc123", "feat")
+                .build()
+                .pull_request(),
+            None
+        );
+    }
+
     #[test]
     fn test_bump_detection() {
         let breaking = ParsedCommit::builder("abc123", "feat")
@@ -50,18 +50,99 @@ impl RawCommit {
     /// Returns the commit body (everything after the first line).
     #[must_use]
     pub fn body(&self) -> Option<&str> {
-        let mut lines = self.message.lines();
-        lines.next(); // Skip subject
-        lines.next(); // Skip empty line
-
-        let body: String = lines.collect::<Vec<_>>().join("\n");
-        if body.is_empty() {
-            None
-        } else {
-            // This is a bit inefficient but keeps the API simple
-            // In practice, we'd use indices into the original string
-            None
+        let (_, rest) = self.message.split_once('\n')?;
+        let body = rest.trim();
+        (!body.is_empty()).then_some(body)
+    }
+
+    /// Returns the footers of the commit, as `(token, value)` pairs.
+    ///
+    /// Footers are the lines of the last paragraph of the body, written
+    /// `Token: value` or `Token #value` (e.g. `Closes #123`). Lines that are
+    /// not footers continue the value of the footer before them.
+    #[must_use]
+    pub fn footers(&self) -> Vec<(&str, &str)> {
+        let Some(body) = self.body() else {
+            return Vec::new();
+        };
+        let paragraph = body.rsplit("\n\n").next().unwrap_or(body);
+
+        // Tokens with the start and end of their value in the paragraph
+        let mut footers: Vec<(&str, usize, usize)> = Vec::new();
+        let mut offset = 0;
+        for line in paragraph.split_inclusive('\n') {
+            let text = line.trim_end();
+            if let Some((token, value)) = parse_footer(text) {
+                footers.push((
+                    token,
+                    offset + text.len() - value.len(),
+                    offset + text.len(),
+                ));
+            } else if let Some((_, _, end)) = footers.last_mut() {
+                *end = offset + text.len();
+            } else {
+                // A paragraph not starting with a footer is regular text
+                return Vec::new();
+            }
+            offset += line.len();
         }
+
+        footers
+            .into_iter()
+            .map(|(token, start, end)| (token, &paragraph[start..end]))
+            .collect()
+    }
+
+    /// Returns the numbers of the issues closed by the commit, from footers
+    /// such as `Closes #123` or `Fixes: #12, #34`.
+    #[must_use]
+    pub fn closed_issues(&self) -> Vec<String> {
+        self.footers()
+            .into_iter()
+            .filter(|(token, _)| CLOSING_KEYWORDS.contains(&token.to_ascii_lowercase().as_str()))
+            .flat_map(|(_, value)| value.split(|c: char| c == ',' || c.is_whitespace()))
+            .filter_map(|reference| reference.trim().strip_prefix('#'))
+            .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the names of the co-authors of the commit, from
+    /// `Co-authored-by: Name <email>` footers.
+    #[must_use]
+    pub fn co_authors(&self) -> Vec<String> {
+        self.footers()
+            .into_iter()
+            .filter(|(token, _)| token.eq_ignore_ascii_case(CO_AUTHOR_TOKEN))
+            .map(|(_, value)| value.split_once('<').map_or(value, |(name, _)| name).trim())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the changelog entry of the commit, from a `Changelog:` footer
+    /// overriding its generated entry, or `skip` to leave it out.
+    ///
+    /// The last footer wins.
+    #[must_use]
+    pub fn changelog_entry(&self) -> Option<&str> {
+        self.footers()
+            .into_iter()
+            .rev()
+            .find(|(token, _)| token.eq_ignore_ascii_case(CHANGELOG_TOKEN))
+            .map(|(_, value)| value)
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns the description of the breaking change of the commit, from a
+    /// `BREAKING CHANGE:` or `BREAKING-CHANGE:` footer, its lines joined.
+    #[must_use]
+    pub fn breaking_change(&self) -> Option<String> {
+        self.footers()
+            .into_iter()
+            .find(|(token, _)| BREAKING_CHANGE_TOKENS.contains(token))
+            .map(|(_, value)| value.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|value| !value.is_empty())
     }
 
     /// Returns the short hash (first 7 characters).
@@ -71,6 +152,35 @@ impl RawCommit {
     }
 }
 
+/// Footer tokens closing the issues they reference, as recognized by GitHub.
+const CLOSING_KEYWORDS: [&str; 9] = [
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// Footer tokens describing a breaking change, as defined by Conventional
+/// Commits.
+const BREAKING_CHANGE_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// Footer token crediting a co-author.
+const CO_AUTHOR_TOKEN: &str = "Co-authored-by";
+
+/// Footer token overriding the changelog entry of a commit.
+const CHANGELOG_TOKEN: &str = "Changelog";
+
+/// Parses a `Token: value` or `Token #value` footer line.
+fn parse_footer(line: &str) -> Option<(&str, &str)> {
+    let (token, value) = if let Some(footer) = line.split_once(": ") {
+        footer
+    } else {
+        let (token, _) = line.split_once(" #")?;
+        (token, &line[token.len() + 1..])
+    };
+
+    let is_token = token == "BREAKING CHANGE"
+        || (!token.is_empty() && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    is_token.then_some((token, value.trim()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,10 +259,107 @@ mod tests {
     }
 
     #[test]
-    fn test_body_none_with_body_text() {
-        let commit = make_commit("abc123", "subject\n\nbody text");
-        // Current implementation always returns None (as documented)
-        assert!(commit.body().is_none());
+    fn test_body_with_body_text() {
+        let commit = make_commit("abc123", "subject\n\nbody text\n");
+        assert_eq!(commit.body(), Some("body text"));
+    }
+
+    #[test]
+    fn test_footers() {
+        let commit = make_commit(
+            "abc123",
+            "fix: handle timeouts\n\nRetry once.\n\nReviewed-by: Jane\nCloses #12\nBREAKING CHANGE: drop v1\n",
+        );
+        assert_eq!(
+            commit.footers(),
+            vec![
+                ("Reviewed-by", "Jane"),
+                ("Closes", "#12"),
+                ("BREAKING CHANGE", "drop v1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_footers_ignore_text() {
+        let commit = make_commit("abc123", "fix: typo\n\nSee the notes: they explain it.");
+        assert!(commit.footers().is_empty());
+    }
+
+    #[test]
+    fn test_closed_issues() {
+        let commit = make_commit(
+            "abc123",
+            "fix: crash\n\nFixes: #12, #34\nresolves #56\nRefs #78\nCloses org/repo#9",
+        );
+        assert_eq!(commit.closed_issues(), vec!["12", "34", "56"]);
+    }
+
+    #[test]
+    fn test_co_authors() {
+        let commit = make_commit(
+            "abc123",
+            "feat: pair on parser\n\nCo-authored-by: Jane Doe <jane@example.com>\nco-authored-by: Bob <bob@example.com>\nReviewed-by: Alice",
+        );
+        assert_eq!(commit.co_authors(), vec!["Jane Doe", "Bob"]);
+    }
+
+    #[test]
+    fn test_changelog_entry() {
+        let commit = make_commit(
+            "abc123",
+            "fix: retry on 503\n\nChangelog: Uploads no longer fail when the server is busy\nCloses #12",
+        );
+        assert_eq!(
+            commit.changelog_entry(),
+            Some("Uploads no longer fail when the server is busy")
+        );
+
+        let commit = make_commit("abc123", "chore: bump deps\n\nchangelog: skip");
+        assert_eq!(commit.changelog_entry(), Some("skip"));
+
+        assert!(
+            make_commit("abc123", "fix: typo")
+                .changelog_entry()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_breaking_change() {
+        let commit = make_commit(
+            "abc123",
+            "feat: new config format\n\nBREAKING CHANGE: `unduler.yml` is no longer read\nCloses #12",
+        );
+        assert_eq!(
+            commit.breaking_change().as_deref(),
+            Some("`unduler.yml` is no longer read")
+        );
+
+        let commit = make_commit(
+            "abc123",
+            "feat: drop v1\n\nBREAKING-CHANGE: the v1 API is gone",
+        );
+        assert_eq!(
+            commit.breaking_change().as_deref(),
+            Some("the v1 API is gone")
+        );
+
+        let commit = make_commit(
+            "abc123",
+            "feat: drop v1\n\nBREAKING CHANGE: the v1 API is gone,\nuse the v2 API instead\nCloses #12",
+        );
+        assert_eq!(
+            commit.breaking_change().as_deref(),
+            Some("the v1 API is gone, use the v2 API instead")
+        );
+        assert_eq!(commit.closed_issues(), ["12"]);
+
+        assert!(
+            make_commit("abc123", "feat!: drop v1")
+                .breaking_change()
+                .is_none()
+        );
     }
 
     #[test]
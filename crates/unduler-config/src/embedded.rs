@@ -0,0 +1,147 @@
+//! Configuration embedded in package manifests.
+//!
+//! Small projects can keep their configuration in the manifest they already
+//! have, under `[package.metadata.unduler]` in `Cargo.toml` or an `"unduler"`
+//! key in `package.json`.
+
+use std::path::Path;
+
+use toml::Table;
+
+use crate::{ConfigError, ConfigResult};
+
+/// Cargo manifest file name.
+pub const CARGO_MANIFEST: &str = "Cargo.toml";
+
+/// npm manifest file name.
+pub const NPM_MANIFEST: &str = "package.json";
+
+/// Key path of the configuration in a Cargo manifest.
+const CARGO_KEY: [&str; 3] = ["package", "metadata", "unduler"];
+
+/// Key of the configuration in an npm manifest.
+const NPM_KEY: &str = "unduler";
+
+/// Returns true if the file is a manifest that may embed the configuration.
+#[must_use]
+pub(crate) fn is_manifest(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some(CARGO_MANIFEST | NPM_MANIFEST)
+    )
+}
+
+/// Returns the dotted key prefix of the configuration in a file, e.g.
+/// `package.metadata.unduler` for a Cargo manifest.
+#[must_use]
+pub fn config_key_prefix(path: &Path) -> Option<String> {
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(CARGO_MANIFEST) => Some(CARGO_KEY.join(".")),
+        _ => None,
+    }
+}
+
+/// Extracts the configuration embedded in a manifest, if any.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be parsed or the configuration
+/// is not a table.
+pub(crate) fn embedded_table(path: &Path, content: &str) -> ConfigResult<Option<Table>> {
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(CARGO_MANIFEST) => cargo_table(path, content),
+        Some(NPM_MANIFEST) => npm_table(content),
+        _ => Ok(None),
+    }
+}
+
+fn cargo_table(path: &Path, content: &str) -> ConfigResult<Option<Table>> {
+    let manifest: Table = toml::from_str(content).map_err(|source| ConfigError::Syntax {
+        path: path.to_path_buf(),
+        content: content.to_string(),
+        source: Box::new(source),
+    })?;
+
+    let mut value = manifest.get(CARGO_KEY[0]);
+    for key in &CARGO_KEY[1..] {
+        value = value.and_then(|value| value.get(key));
+    }
+    match value {
+        None => Ok(None),
+        Some(toml::Value::Table(table)) => Ok(Some(table.clone())),
+        Some(_) => Err(ConfigError::Invalid(format!(
+            "{} must be a table in {CARGO_MANIFEST}",
+            CARGO_KEY.join(".")
+        ))),
+    }
+}
+
+fn npm_table(content: &str) -> ConfigResult<Option<Table>> {
+    let manifest: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| ConfigError::Invalid(format!("invalid {NPM_MANIFEST}: {e}")))?;
+
+    match manifest.get(NPM_KEY) {
+        None => Ok(None),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| {
+                ConfigError::Invalid(format!("invalid \"{NPM_KEY}\" in {NPM_MANIFEST}: {e}"))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_table() {
+        let content = r#"
+[package]
+name = "demo"
+
+[package.metadata.unduler.version]
+files = ["Cargo.toml"]
+"#;
+        let table = embedded_table(Path::new(CARGO_MANIFEST), content)
+            .unwrap()
+            .unwrap();
+        assert_eq!(table["version"]["files"][0].as_str(), Some("Cargo.toml"));
+    }
+
+    #[test]
+    fn test_cargo_without_metadata() {
+        let content = "[package]\nname = \"demo\"\n";
+        let table = embedded_table(Path::new(CARGO_MANIFEST), content).unwrap();
+        assert!(table.is_none());
+    }
+
+    #[test]
+    fn test_npm_table() {
+        let content = r#"{
+  "name": "demo",
+  "unduler": { "version": { "files": ["package.json"], "tag_prefix": "v" } }
+}"#;
+        let table = embedded_table(Path::new(NPM_MANIFEST), content)
+            .unwrap()
+            .unwrap();
+        assert_eq!(table["version"]["tag_prefix"].as_str(), Some("v"));
+    }
+
+    #[test]
+    fn test_npm_not_an_object() {
+        let content = r#"{ "unduler": true }"#;
+        let result = embedded_table(Path::new(NPM_MANIFEST), content);
+        assert!(matches!(result, Err(ConfigError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_config_key_prefix() {
+        assert_eq!(
+            config_key_prefix(Path::new("/repo/Cargo.toml")).as_deref(),
+            Some("package.metadata.unduler")
+        );
+        assert_eq!(config_key_prefix(Path::new("/repo/package.json")), None);
+        assert_eq!(config_key_prefix(Path::new("/repo/unduler.toml")), None);
+    }
+}
@@ -13,6 +13,22 @@ pub enum ConfigError {
     #[error("invalid TOML: {0}")]
     InvalidToml(#[from] toml::de::Error),
 
+    /// A configuration file could not be parsed.
+    #[error("invalid configuration file {}", path.display())]
+    Syntax {
+        /// Path of the configuration file.
+        path: std::path::PathBuf,
+        /// Content of the configuration file.
+        content: String,
+        /// Parse error, spanning the offending part of the content.
+        #[source]
+        source: Box<toml::de::Error>,
+    },
+
+    /// The selected profile is not defined.
+    #[error("profile '{0}' is not defined")]
+    ProfileNotFound(String),
+
     /// Invalid configuration value.
     #[error("invalid configuration: {0}")]
     Invalid(String),
@@ -0,0 +1,321 @@
+//! Hook entries of the `[hooks]` stages.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::schema::branch_matches;
+
+/// A hook run at a stage, e.g. `[[hooks.post_tag]]`.
+///
+/// Written either as the name of the hook, or as a table:
+///
+/// ```toml
+/// [[hooks.post_tag]]
+/// name = "github-release"
+/// if = "!prerelease && branch == 'main'"
+/// continue_on_error = true
+/// config = { draft = true }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookEntry {
+    /// Name of the hook plugin.
+    pub name: String,
+
+    /// Condition under which the hook runs.
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
+    pub condition: Option<HookCondition>,
+
+    /// Continue the release when the hook fails.
+    pub continue_on_error: bool,
+
+    /// Configuration passed to the hook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
+impl HookEntry {
+    /// Creates an entry running a hook unconditionally.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            condition: None,
+            continue_on_error: false,
+            config: None,
+        }
+    }
+
+    /// Returns true if the hook runs for a release, i.e. it has no
+    /// condition or its condition holds.
+    #[must_use]
+    pub fn applies(&self, prerelease: bool, dry_run: bool, branch: Option<&str>) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.matches(prerelease, dry_run, branch))
+    }
+}
+
+/// Table form of a hook entry.
+#[derive(Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "HookEntry")]
+struct HookTable {
+    /// Name of the hook plugin.
+    name: String,
+
+    /// Condition under which the hook runs: `prerelease`, `dry_run`,
+    /// `branch == 'pattern'` and `branch != 'pattern'` terms, negated with
+    /// `!` and joined with `&&`.
+    #[serde(rename = "if")]
+    #[schemars(with = "Option<String>")]
+    condition: Option<HookCondition>,
+
+    /// Continue the release when the hook fails.
+    #[serde(default)]
+    continue_on_error: bool,
+
+    /// Configuration passed to the hook.
+    config: Option<serde_json::Value>,
+}
+
+impl From<HookTable> for HookEntry {
+    fn from(table: HookTable) -> Self {
+        Self {
+            name: table.name,
+            condition: table.condition,
+            continue_on_error: table.continue_on_error,
+            config: table.config,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HookEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = HookEntry;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hook name or a table with a `name` key")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<HookEntry, E> {
+                Ok(HookEntry::new(name))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<HookEntry, A::Error> {
+                HookTable::deserialize(de::value::MapAccessDeserializer::new(map)).map(Into::into)
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+impl JsonSchema for HookEntry {
+    fn schema_name() -> Cow<'static, str> {
+        "HookEntry".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let table = generator.subschema_for::<HookTable>();
+        json_schema!({
+            "description": "A hook name, or a table with its condition and configuration.",
+            "anyOf": [{ "type": "string" }, table],
+        })
+    }
+}
+
+/// Condition of a hook entry, e.g. `!prerelease && branch == 'main'`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HookCondition {
+    /// Source of the condition.
+    source: String,
+    /// Terms, all of which must hold.
+    terms: Vec<Term>,
+}
+
+/// Term of a hook condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Term {
+    /// The release is a prerelease, or not.
+    Prerelease(bool),
+    /// The release is a dry run, or not.
+    DryRun(bool),
+    /// The current branch matches a glob pattern, or not.
+    Branch {
+        /// Glob pattern, as in `version.release_branches`.
+        pattern: String,
+        /// Whether the branch must match the pattern.
+        matches: bool,
+    },
+}
+
+impl HookCondition {
+    /// Returns true if the condition holds for a release. Branch terms do
+    /// not hold without a current branch.
+    #[must_use]
+    pub fn matches(&self, prerelease: bool, dry_run: bool, branch: Option<&str>) -> bool {
+        self.terms.iter().all(|term| match term {
+            Term::Prerelease(expected) => prerelease == *expected,
+            Term::DryRun(expected) => dry_run == *expected,
+            Term::Branch { pattern, matches } => {
+                branch.is_some_and(|branch| branch_matches(pattern, branch) == *matches)
+            }
+        })
+    }
+}
+
+/// Parses a term of a hook condition.
+fn parse_term(term: &str) -> Result<Term, String> {
+    if let Some((left, right)) = term.split_once("==").or_else(|| term.split_once("!=")) {
+        if left.trim() != "branch" {
+            return Err(format!("unknown variable '{}'", left.trim()));
+        }
+        let pattern = right.trim();
+        let pattern = pattern
+            .strip_prefix('\'')
+            .and_then(|p| p.strip_suffix('\''))
+            .or_else(|| pattern.strip_prefix('"').and_then(|p| p.strip_suffix('"')))
+            .unwrap_or(pattern);
+        if pattern.is_empty() {
+            return Err("missing branch pattern".to_string());
+        }
+        return Ok(Term::Branch {
+            pattern: pattern.to_string(),
+            matches: term.contains("=="),
+        });
+    }
+
+    let (negated, name) = match term.strip_prefix('!') {
+        Some(name) => (true, name.trim()),
+        None => (false, term),
+    };
+    match name {
+        "prerelease" => Ok(Term::Prerelease(!negated)),
+        "dry_run" => Ok(Term::DryRun(!negated)),
+        "" => Err("empty term".to_string()),
+        name => Err(format!("unknown variable '{name}'")),
+    }
+}
+
+impl TryFrom<String> for HookCondition {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        let terms = source
+            .split("&&")
+            .map(|term| parse_term(term.trim()))
+            .collect::<Result<_, _>>()
+            .map_err(|reason| format!("invalid hook condition '{source}': {reason}"))?;
+        Ok(Self { source, terms })
+    }
+}
+
+impl From<HookCondition> for String {
+    fn from(condition: HookCondition) -> Self {
+        condition.source
+    }
+}
+
+impl fmt::Display for HookCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn condition(source: &str) -> HookCondition {
+        HookCondition::try_from(source.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        #[derive(Deserialize)]
+        struct Stage {
+            hooks: Vec<HookEntry>,
+        }
+
+        let stage: Stage = toml::from_str(
+            r#"
+            hooks = [
+                "notify",
+                { name = "github-release", if = "!prerelease", continue_on_error = true, config = { draft = true } },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(stage.hooks[0], HookEntry::new("notify"));
+        let entry = &stage.hooks[1];
+        assert_eq!(entry.name, "github-release");
+        assert_eq!(entry.condition, Some(condition("!prerelease")));
+        assert!(entry.continue_on_error);
+        assert_eq!(entry.config, Some(serde_json::json!({ "draft": true })));
+    }
+
+    #[test]
+    fn test_invalid_condition() {
+        let error = toml::from_str::<HashMap<String, Vec<HookEntry>>>(
+            r#"hooks = [{ name = "notify", if = "friday" }]"#,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("invalid hook condition 'friday': unknown variable 'friday'")
+        );
+    }
+
+    #[test]
+    fn test_condition_terms() {
+        let stable_main = condition("!prerelease && branch == 'main'");
+        assert!(stable_main.matches(false, false, Some("main")));
+        assert!(!stable_main.matches(true, false, Some("main")));
+        assert!(!stable_main.matches(false, false, Some("develop")));
+        assert!(!stable_main.matches(false, false, None));
+
+        let dry_run = condition("dry_run");
+        assert!(dry_run.matches(false, true, None));
+        assert!(!dry_run.matches(false, false, None));
+
+        let not_release = condition(r#"branch != "release/*""#);
+        assert!(not_release.matches(false, false, Some("main")));
+        assert!(!not_release.matches(false, false, Some("release/1.x")));
+    }
+
+    #[test]
+    fn test_condition_errors() {
+        for source in ["", "prerelease &&", "tag == 'v1'", "branch == ''"] {
+            assert!(
+                HookCondition::try_from(source.to_string()).is_err(),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_applies() {
+        let mut entry = HookEntry::new("notify");
+        assert!(entry.applies(true, true, None));
+
+        entry.condition = Some(condition("!dry_run"));
+        assert!(entry.applies(false, false, None));
+        assert!(!entry.applies(false, true, None));
+    }
+
+    #[test]
+    fn test_condition_display() {
+        assert_eq!(condition("!prerelease").to_string(), "!prerelease");
+    }
+}
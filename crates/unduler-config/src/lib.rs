@@ -1,14 +1,29 @@
 //! Configuration management for Unduler.
 //!
-//! This crate handles loading and validating the `unduler.toml` configuration file.
+//! This crate handles loading and validating the `unduler.toml` configuration file,
+//! or the configuration embedded in `Cargo.toml` or `package.json`.
 
+mod embedded;
 mod error;
+mod hooks;
 mod loader;
 mod schema;
+mod span;
 
+pub use embedded::{CARGO_MANIFEST, NPM_MANIFEST, config_key_prefix};
 pub use error::{ConfigError, ConfigResult};
-pub use loader::{CONFIG_FILE_NAME, find_and_load_config, find_and_load_config_from, load_config};
+pub use hooks::{HookCondition, HookEntry};
+pub use loader::{
+    CONFIG_FILE_NAME, USER_CONFIG_FILE, find_and_load_config, find_and_load_config_from,
+    find_config_file, load_config, load_merged_config, load_profile_config, user_config_path,
+};
 pub use schema::{
-    ChangelogConfig, Config, FormatterPluginConfig, HooksConfig, ParserConfig, PluginsConfig,
-    VersionConfig,
+    BumpRule, ChangelogConfig, ChangelogInsertConfig, ChangelogOutputConfig, ChannelConfig, Config,
+    DebianFormatterConfig, FormatterPluginConfig, FormatterSectionConfig, HooksConfig,
+    InsertFormat, JiraPluginConfig, JvmBuildTool, JvmPluginConfig, ListBullet, MessageCase,
+    MilestoneForge, MilestonePluginConfig, NormalizeConfig, NotifyChannelConfig, NotifyChannelKind,
+    NotifyPluginConfig, ParserConfig, PluginsConfig, ReleaseFilesConfig, SandboxConfig, SbomFormat,
+    SbomPluginConfig, SentryPluginConfig, TrailingPunctuation, VersionConfig, VersionFileConfig,
+    WasmConfig, WebhookPluginConfig, WebhookStage, WebhookTargetConfig,
 };
+pub use span::value_span;
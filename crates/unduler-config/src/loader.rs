@@ -1,16 +1,28 @@
 //! Configuration loader.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use toml::Table;
 use tracing::debug;
 
+use crate::embedded::{self, CARGO_MANIFEST, NPM_MANIFEST};
 use crate::{Config, ConfigError, ConfigResult};
 
 /// Default configuration file name.
 pub const CONFIG_FILE_NAME: &str = "unduler.toml";
 
+/// Path of the user configuration, relative to the user configuration
+/// directory.
+pub const USER_CONFIG_FILE: &str = "unduler/config.toml";
+
+/// Key of the table holding configuration profiles.
+pub(crate) const PROFILE_KEY: &str = "profile";
+
 /// Loads configuration from the given path.
 ///
+/// The path is either a configuration file or a manifest embedding the
+/// configuration (see [`find_config_file`]).
+///
 /// # Errors
 ///
 /// Returns an error if the file cannot be read or parsed.
@@ -21,13 +33,145 @@ pub fn load_config(path: impl AsRef<Path>) -> ConfigResult<Config> {
     if !path.exists() {
         return Err(ConfigError::NotFound(path.to_path_buf()));
     }
+    if embedded::is_manifest(path) {
+        return validated(toml::Value::Table(read_table(path)?).try_into()?);
+    }
 
     let content = std::fs::read_to_string(path)?;
-    let config: Config = toml::from_str(&content)?;
+    match toml::from_str(&content) {
+        Ok(config) => validated(config),
+        Err(source) => Err(ConfigError::Syntax {
+            path: path.to_path_buf(),
+            content,
+            source: Box::new(source),
+        }),
+    }
+}
 
+/// Returns the configuration once [`Config::validate`] accepts it.
+fn validated(config: Config) -> ConfigResult<Config> {
+    config.validate()?;
     Ok(config)
 }
 
+/// Loads a repository configuration on top of a user configuration.
+///
+/// Values of the repository configuration take precedence. Tables are
+/// merged key by key, while arrays and other values are replaced as a whole.
+/// A missing user configuration is ignored.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or parsed.
+pub fn load_merged_config(
+    user_path: Option<&Path>,
+    path: impl AsRef<Path>,
+) -> ConfigResult<Config> {
+    let table = merged_table(user_path, path.as_ref())?;
+    validated(toml::Value::Table(table).try_into()?)
+}
+
+/// Loads a configuration like [`load_merged_config`], then applies a profile.
+///
+/// The `[profile.<name>]` table overrides the rest of the configuration,
+/// merged the same way.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or parsed, or the profile is
+/// not defined.
+pub fn load_profile_config(
+    user_path: Option<&Path>,
+    path: impl AsRef<Path>,
+    profile: &str,
+) -> ConfigResult<Config> {
+    let mut table = merged_table(user_path, path.as_ref())?;
+    let overrides = match table
+        .get(PROFILE_KEY)
+        .and_then(|profiles| profiles.get(profile))
+    {
+        Some(toml::Value::Table(overrides)) => overrides.clone(),
+        _ => return Err(ConfigError::ProfileNotFound(profile.to_string())),
+    };
+    debug!(profile, "applying configuration profile");
+    merge_tables(&mut table, overrides);
+
+    validated(toml::Value::Table(table).try_into()?)
+}
+
+/// Reads a repository configuration on top of a user configuration.
+fn merged_table(user_path: Option<&Path>, path: &Path) -> ConfigResult<Table> {
+    let mut table = match user_path {
+        Some(user_path) if user_path.exists() => {
+            debug!(path = ?user_path, "loading user configuration");
+            read_table(user_path)?
+        }
+        _ => Table::new(),
+    };
+
+    if !path.exists() {
+        return Err(ConfigError::NotFound(path.to_path_buf()));
+    }
+    debug!(?path, "loading configuration");
+    merge_tables(&mut table, read_table(path)?);
+
+    Ok(table)
+}
+
+/// Returns the path of the user configuration,
+/// `$XDG_CONFIG_HOME/unduler/config.toml` or `~/.config/unduler/config.toml`.
+#[must_use]
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join(USER_CONFIG_FILE))
+}
+
+/// Reads a configuration file as a TOML table.
+fn read_table(path: &Path) -> ConfigResult<Table> {
+    let content = std::fs::read_to_string(path)?;
+    if embedded::is_manifest(path) {
+        return embedded::embedded_table(path, &content)?.ok_or_else(|| {
+            ConfigError::Invalid(format!("{} has no configuration", path.display()))
+        });
+    }
+
+    // Check the file on its own first, so errors point at it
+    toml::from_str::<Config>(&content).map_err(|source| ConfigError::Syntax {
+        path: path.to_path_buf(),
+        content: content.clone(),
+        source: Box::new(source),
+    })?;
+    Ok(toml::from_str(&content)?)
+}
+
+/// Returns true if a manifest exists and embeds a configuration.
+///
+/// Manifests that cannot be parsed are skipped.
+fn embeds_config(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .is_ok_and(|content| matches!(embedded::embedded_table(path, &content), Ok(Some(_))))
+}
+
+/// Merges the values of `overlay` into `base`.
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match value {
+            toml::Value::Table(overlay) => match base.get_mut(&key) {
+                Some(toml::Value::Table(table)) => merge_tables(table, overlay),
+                _ => {
+                    base.insert(key, toml::Value::Table(overlay));
+                }
+            },
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Finds and loads configuration from the current directory or parents.
 ///
 /// # Errors
@@ -40,19 +184,41 @@ pub fn find_and_load_config() -> ConfigResult<Config> {
 
 /// Finds and loads configuration starting from the given directory.
 ///
-/// Walks up the directory tree until a configuration file is found.
+/// Walks up the directory tree until a configuration file is found, then
+/// merges it over the user configuration (see [`user_config_path`]).
 ///
 /// # Errors
 ///
 /// Returns an error if no configuration file is found or it cannot be parsed.
 pub fn find_and_load_config_from(start_dir: impl AsRef<Path>) -> ConfigResult<Config> {
+    load_merged_config(user_config_path().as_deref(), find_config_file(start_dir)?)
+}
+
+/// Finds the configuration file in the given directory or its parents.
+///
+/// The nearest directory with a configuration wins. In a directory,
+/// `unduler.toml` takes precedence over `[package.metadata.unduler]` in
+/// `Cargo.toml`, which takes precedence over an `"unduler"` key in
+/// `package.json`. Only the first configuration found is used.
+///
+/// # Errors
+///
+/// Returns an error if no configuration file is found.
+pub fn find_config_file(start_dir: impl AsRef<Path>) -> ConfigResult<PathBuf> {
     let start_dir = start_dir.as_ref();
     let mut dir = start_dir;
 
     loop {
         let config_path = dir.join(CONFIG_FILE_NAME);
         if config_path.exists() {
-            return load_config(config_path);
+            return Ok(config_path);
+        }
+
+        for manifest in [CARGO_MANIFEST, NPM_MANIFEST] {
+            let manifest_path = dir.join(manifest);
+            if embeds_config(&manifest_path) {
+                return Ok(manifest_path);
+            }
         }
 
         match dir.parent() {
@@ -129,6 +295,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_load_config_syntax_error_span() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        let content = "[parser]\nname = \n";
+        fs::write(&config_path, content).unwrap();
+
+        let Err(ConfigError::Syntax { path, source, .. }) = load_config(&config_path) else {
+            panic!("expected a syntax error");
+        };
+        assert_eq!(path, config_path);
+        let span = source.span().unwrap();
+        assert!(span.start >= "[parser]\n".len(), "{span:?}");
+    }
+
     #[test]
     fn test_load_config_custom_values() {
         let temp_dir = TempDir::new().unwrap();
@@ -199,4 +380,179 @@ mod tests {
         let config = result.unwrap();
         assert_eq!(config.parser.name, "parent-parser");
     }
+
+    #[test]
+    fn test_load_merged_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let user_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &user_path,
+            r#"
+            [formatter]
+            name = "custom"
+
+            [version]
+            tag_prefix = "release-"
+            release_branches = ["main"]
+
+            [sandbox.lint]
+            network = false
+        "#,
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        fs::write(
+            &config_path,
+            r#"
+            [version]
+            files = ["Cargo.toml"]
+            release_branches = ["release/*"]
+        "#,
+        )
+        .unwrap();
+
+        let config = load_merged_config(Some(&user_path), &config_path).unwrap();
+        assert_eq!(config.formatter.name, "custom");
+        assert_eq!(config.version.tag_prefix, "release-");
+        assert_eq!(config.version.files, ["Cargo.toml"]);
+        assert_eq!(config.version.release_branches, ["release/*"]);
+        assert!(!config.sandbox["lint"].network);
+    }
+
+    #[test]
+    fn test_load_merged_config_without_user_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        fs::write(&config_path, "[parser]\nname = \"gitmoji\"\n").unwrap();
+
+        let user_path = temp_dir.path().join("missing.toml");
+        let config = load_merged_config(Some(&user_path), &config_path).unwrap();
+        assert_eq!(config.parser.name, "gitmoji");
+    }
+
+    #[test]
+    fn test_load_merged_config_user_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let user_path = temp_dir.path().join("config.toml");
+        fs::write(&user_path, "[version]\ntag_prefix = 1\n").unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let result = load_merged_config(Some(&user_path), &config_path);
+        assert!(matches!(result, Err(ConfigError::Syntax { path, .. }) if path == user_path));
+    }
+
+    #[test]
+    fn test_load_merged_config_not_found() {
+        let result = load_merged_config(None, "/nonexistent/path/unduler.toml");
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_load_profile_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        fs::write(
+            &config_path,
+            r#"
+            [version]
+            files = ["Cargo.toml"]
+            tag_prefix = "v"
+
+            [changelog]
+            output = "CHANGELOG.md"
+
+            [profile.nightly.version]
+            tag_prefix = "nightly-"
+
+            [profile.nightly.changelog]
+            output = "NIGHTLY.md"
+        "#,
+        )
+        .unwrap();
+
+        let config = load_profile_config(None, &config_path, "nightly").unwrap();
+        assert_eq!(config.version.tag_prefix, "nightly-");
+        assert_eq!(config.version.files, ["Cargo.toml"]);
+        assert_eq!(config.changelog.output, "NIGHTLY.md");
+
+        let config = load_merged_config(None, &config_path).unwrap();
+        assert_eq!(config.version.tag_prefix, "v");
+    }
+
+    #[test]
+    fn test_load_profile_config_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("unduler.toml");
+        fs::write(
+            &config_path,
+            "[profile.nightly.version]\ntag_prefix = \"n\"\n",
+        )
+        .unwrap();
+
+        let result = load_profile_config(None, &config_path, "stable");
+        assert!(matches!(result, Err(ConfigError::ProfileNotFound(name)) if name == "stable"));
+    }
+
+    #[test]
+    fn test_find_config_in_cargo_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"
+            [package]
+            name = "demo"
+
+            [package.metadata.unduler.version]
+            tag_prefix = "demo-v"
+        "#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{ "unduler": { "version": { "tag_prefix": "js-v" } } }"#,
+        )
+        .unwrap();
+
+        let path = find_config_file(temp_dir.path()).unwrap();
+        assert!(path.ends_with("Cargo.toml"));
+
+        let config = find_and_load_config_from(temp_dir.path()).unwrap();
+        assert_eq!(config.version.tag_prefix, "demo-v");
+    }
+
+    #[test]
+    fn test_find_config_in_npm_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{ "name": "demo", "unduler": { "parser": { "name": "gitmoji" } } }"#,
+        )
+        .unwrap();
+
+        let path = find_config_file(temp_dir.path()).unwrap();
+        assert!(path.ends_with("package.json"));
+
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.parser.name, "gitmoji");
+    }
+
+    #[test]
+    fn test_config_file_takes_precedence_over_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("unduler.toml"), "").unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package.metadata.unduler.version]\ntag_prefix = \"demo-v\"\n",
+        )
+        .unwrap();
+
+        let path = find_config_file(temp_dir.path()).unwrap();
+        assert!(path.ends_with("unduler.toml"));
+    }
 }
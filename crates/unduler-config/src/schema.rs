@@ -2,10 +2,15 @@
 
 use std::collections::HashMap;
 
+use schemars::{JsonSchema, Schema, json_schema, schema_for};
 use serde::{Deserialize, Serialize};
 
+use crate::hooks::HookEntry;
+use crate::loader::PROFILE_KEY;
+use crate::{ConfigError, ConfigResult};
+
 /// Main configuration structure.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Config {
     /// Parser configuration.
     #[serde(default)]
@@ -34,15 +39,66 @@ pub struct Config {
     /// Plugin-specific configuration.
     #[serde(default)]
     pub plugins: PluginsConfig,
+
+    /// Sandbox configuration for WASM plugins, keyed by plugin name.
+    #[serde(default)]
+    pub sandbox: HashMap<String, SandboxConfig>,
+
+    /// WASM runtime limits.
+    #[serde(default)]
+    pub wasm: WasmConfig,
+}
+
+impl Config {
+    /// Returns the JSON Schema of `unduler.toml`, derived from these types.
+    #[must_use]
+    pub fn json_schema() -> Schema {
+        let mut schema = schema_for!(Config);
+
+        // Profiles are applied by the loader, and may override any key
+        let profile = json_schema!({
+            "description": "Configuration profiles, keyed by name, selected with `--profile`.",
+            "type": "object",
+            "additionalProperties": { "$ref": "#" },
+        });
+        if let Some(properties) = schema
+            .get_mut("properties")
+            .and_then(|properties| properties.as_object_mut())
+        {
+            properties.insert(PROFILE_KEY.to_string(), profile.to_value());
+        }
+        schema
+    }
+
+    /// Checks settings that are valid on their own but not together.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag template uses `{package}` without
+    /// `version.package`.
+    pub fn validate(&self) -> ConfigResult<()> {
+        if self.version.package.is_none() && self.version.tag_template().contains("{package}") {
+            return Err(ConfigError::Invalid(
+                "version.tag_format uses {package} but version.package is not set".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Parser configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ParserConfig {
     /// Parser plugin name.
     #[serde(default = "default_parser")]
     pub name: String,
 
+    /// Parser plugin names tried in order for each commit.
+    ///
+    /// Takes precedence over `name` when not empty.
+    #[serde(default)]
+    pub names: Vec<String>,
+
     /// Gitmoji-specific options.
     #[serde(default, rename = "conventional-gitmoji")]
     pub conventional_gitmoji: ConventionalGitmojiConfig,
@@ -50,14 +106,25 @@ pub struct ParserConfig {
     /// Regex-specific options.
     #[serde(default)]
     pub regex: RegexParserConfig,
+
+    /// JIRA-specific options.
+    #[serde(default)]
+    pub jira: JiraParserConfig,
+
+    /// Rewriting of commit messages before parsing.
+    #[serde(default)]
+    pub normalize: NormalizeConfig,
 }
 
 impl Default for ParserConfig {
     fn default() -> Self {
         Self {
             name: default_parser(),
+            names: Vec::new(),
             conventional_gitmoji: ConventionalGitmojiConfig::default(),
             regex: RegexParserConfig::default(),
+            jira: JiraParserConfig::default(),
+            normalize: NormalizeConfig::default(),
         }
     }
 }
@@ -67,7 +134,7 @@ fn default_parser() -> String {
 }
 
 /// Conventional + Gitmoji parser options.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ConventionalGitmojiConfig {
     /// Infer type from emoji if not explicitly provided.
     #[serde(default = "default_true")]
@@ -76,6 +143,11 @@ pub struct ConventionalGitmojiConfig {
     /// Reject commits with unknown emojis.
     #[serde(default)]
     pub strict_emoji: bool,
+
+    /// Commit types keyed by emoji, overriding or extending the built-in
+    /// gitmojis.
+    #[serde(default)]
+    pub mappings: HashMap<String, String>,
 }
 
 fn default_true() -> bool {
@@ -83,7 +155,7 @@ fn default_true() -> bool {
 }
 
 /// Regex parser options.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct RegexParserConfig {
     /// The regex pattern.
     pub pattern: Option<String>,
@@ -97,117 +169,799 @@ pub struct RegexParserConfig {
     pub validation: HashMap<String, Vec<String>>,
 }
 
+/// JIRA parser options.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JiraParserConfig {
+    /// Project keys accepted in ticket ids, any project when empty.
+    #[serde(default)]
+    pub projects: Vec<String>,
+
+    /// Commit types keyed by the first word of the message, in lowercase.
+    #[serde(default)]
+    pub types: HashMap<String, String>,
+
+    /// Commit type of messages without an embedded or inferred type.
+    #[serde(default = "default_jira_type")]
+    pub default_type: String,
+}
+
+impl Default for JiraParserConfig {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            types: HashMap::new(),
+            default_type: default_jira_type(),
+        }
+    }
+}
+
+fn default_jira_type() -> String {
+    "chore".to_string()
+}
+
+/// Rewriting of commit messages before parsing, so that commits the parsers
+/// would skip are parsed by the message they stand for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NormalizeConfig {
+    /// Parse `Merge pull request #12 from ...` and GitLab merge request
+    /// merges by the title in their body.
+    #[serde(default)]
+    pub merges: bool,
+
+    /// Parse `Squashed commit of the following:` commits by the message of
+    /// the newest squashed commit.
+    #[serde(default)]
+    pub squashes: bool,
+
+    /// Parse `Revert "<subject>"` commits as `revert: <subject>`.
+    #[serde(default)]
+    pub reverts: bool,
+}
+
 /// Bumper configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BumperConfig {
     /// Bumper plugin name.
     #[serde(default = "default_bumper")]
     pub name: String,
+
+    /// Use `0.x` semantics while the major version is 0: breaking changes
+    /// bump the minor and features bump the patch.
+    #[serde(default)]
+    pub zero_ver: bool,
+
+    /// Bump rules keyed by commit type or `type(scope)`.
+    #[serde(default)]
+    pub rules: HashMap<String, BumpRule>,
 }
 
 impl Default for BumperConfig {
     fn default() -> Self {
         Self {
             name: default_bumper(),
+            zero_ver: false,
+            rules: HashMap::new(),
         }
     }
 }
 
+/// Version bump triggered by a bump rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpRule {
+    /// Major version bump.
+    Major,
+    /// Minor version bump.
+    Minor,
+    /// Patch version bump.
+    Patch,
+    /// No version bump.
+    None,
+}
+
 fn default_bumper() -> String {
     "semver".to_string()
 }
 
 /// Formatter configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FormatterPluginConfig {
     /// Formatter plugin name.
     #[serde(default = "default_formatter")]
     pub name: String,
+
+    /// Link `#123` references and pull requests when the repository is on GitHub.
+    #[serde(default = "default_true")]
+    pub link_references: bool,
+
+    /// List the issues closed by each release, from `Closes #123` footers.
+    #[serde(default = "default_true")]
+    pub closed_issues: bool,
+
+    /// Credit the author of each entry.
+    #[serde(default)]
+    pub include_authors: bool,
+
+    /// Credit authors by their GitHub login, looked up from their commit email.
+    #[serde(default)]
+    pub github_authors: bool,
+
+    /// List the unique authors and co-authors of each release in a
+    /// Contributors section.
+    #[serde(default)]
+    pub contributors: bool,
+
+    /// Open each release with the number of commits, contributors and
+    /// breaking changes.
+    #[serde(default)]
+    pub summary: bool,
+
+    /// Promote breaking changes and highlighted commits to a Highlights
+    /// section at the top of each release.
+    #[serde(default)]
+    pub highlights: bool,
+
+    /// Marker flagging a commit as a highlight when found in its body.
+    #[serde(default = "default_highlight_marker")]
+    pub highlight_marker: String,
+
+    /// List breaking changes of any type in a Breaking Changes section, with
+    /// the description of their `BREAKING CHANGE:` footer.
+    #[serde(default)]
+    pub breaking_changes: bool,
+
+    /// Prefix entries with their gitmoji and section headings with the
+    /// emoji of their type.
+    #[serde(default)]
+    pub show_emoji: bool,
+
+    /// Collapse entries with the same type, scope and message, such as
+    /// cherry-picks, listing the hashes of all their commits.
+    #[serde(default)]
+    pub deduplicate: bool,
+
+    /// Maximum width of list items, wrapped at spaces when longer.
+    #[serde(default)]
+    pub line_width: Option<usize>,
+
+    /// Case of entry messages.
+    #[serde(default)]
+    pub message_case: MessageCase,
+
+    /// Trailing punctuation of entries.
+    #[serde(default)]
+    pub trailing_punctuation: TrailingPunctuation,
+
+    /// Bullet of list items, `-` or `*`.
+    #[serde(default)]
+    pub bullet: ListBullet,
+
+    /// Language of the section headings, e.g. `fr`. Built-in translations
+    /// exist for `fr`, `de` and `es`.
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// Custom section headings, keyed by commit type or by `highlights`,
+    /// `closed_issues` and `contributors`. They take precedence over the
+    /// translations of `locale`.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+
+    /// Sections in display order, each listing the commits of some types
+    /// under a title. Replaces the default section per type when not empty.
+    #[serde(default)]
+    pub sections: Vec<FormatterSectionConfig>,
+
+    /// Commit types left out of the changelog sections.
+    #[serde(default)]
+    pub hide_types: Vec<String>,
+
+    /// Options of the `debian` formatter.
+    #[serde(default)]
+    pub debian: DebianFormatterConfig,
 }
 
 impl Default for FormatterPluginConfig {
     fn default() -> Self {
         Self {
             name: default_formatter(),
+            link_references: true,
+            closed_issues: true,
+            include_authors: false,
+            github_authors: false,
+            contributors: false,
+            summary: false,
+            highlights: false,
+            highlight_marker: default_highlight_marker(),
+            breaking_changes: false,
+            show_emoji: false,
+            deduplicate: false,
+            line_width: None,
+            message_case: MessageCase::default(),
+            trailing_punctuation: TrailingPunctuation::default(),
+            bullet: ListBullet::default(),
+            locale: None,
+            labels: HashMap::new(),
+            sections: Vec::new(),
+            hide_types: Vec::new(),
+            debian: DebianFormatterConfig::default(),
+        }
+    }
+}
+
+/// Debian changelog formatter options.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DebianFormatterConfig {
+    /// Name of the source package (default: the `Source` field of
+    /// `debian/control`).
+    #[serde(default)]
+    pub package: Option<String>,
+
+    /// Maintainer of the package, as `Name <email>` (default: from
+    /// `DEBFULLNAME` and `DEBEMAIL`, or the git identity).
+    #[serde(default)]
+    pub maintainer: Option<String>,
+
+    /// Distribution the package is uploaded to.
+    #[serde(default = "default_debian_distribution")]
+    pub distribution: String,
+
+    /// Urgency of the upload.
+    #[serde(default = "default_debian_urgency")]
+    pub urgency: String,
+
+    /// Debian revision appended to the version, e.g. `1` for `1.2.0-1`.
+    /// Native packages have none.
+    #[serde(default)]
+    pub revision: Option<String>,
+}
+
+impl Default for DebianFormatterConfig {
+    fn default() -> Self {
+        Self {
+            package: None,
+            maintainer: None,
+            distribution: default_debian_distribution(),
+            urgency: default_debian_urgency(),
+            revision: None,
         }
     }
 }
 
+fn default_debian_distribution() -> String {
+    "unstable".to_string()
+}
+
+fn default_debian_urgency() -> String {
+    "medium".to_string()
+}
+
+/// Case of changelog entry messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageCase {
+    /// Keep messages as written.
+    #[default]
+    Preserve,
+    /// Capitalize the first letter of messages.
+    Sentence,
+}
+
+/// Trailing punctuation of changelog entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingPunctuation {
+    /// Keep entries as written.
+    #[default]
+    Preserve,
+    /// Remove trailing periods.
+    Strip,
+    /// End entries with a period.
+    Period,
+}
+
+/// Bullet of changelog list items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ListBullet {
+    /// `-`
+    #[default]
+    #[serde(rename = "-")]
+    Dash,
+    /// `*`
+    #[serde(rename = "*")]
+    Asterisk,
+}
+
+/// A changelog section.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FormatterSectionConfig {
+    /// Commit types listed in the section.
+    pub types: Vec<String>,
+
+    /// Title of the section.
+    pub title: String,
+}
+
 fn default_formatter() -> String {
     "keepachangelog".to_string()
 }
 
+fn default_highlight_marker() -> String {
+    "[highlight]".to_string()
+}
+
 /// Hooks configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// Each stage lists the hooks to run at it, in order. Hooks not listed at any
+/// stage run at every stage after the listed ones.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HooksConfig {
+    /// Hooks to run once the release is planned, able to veto it.
+    #[serde(default)]
+    pub pre_release: Vec<HookEntry>,
+
     /// Hooks to run before bump.
     #[serde(default)]
-    pub pre_bump: Vec<String>,
+    pub pre_bump: Vec<HookEntry>,
 
     /// Hooks to run after bump.
     #[serde(default)]
-    pub post_bump: Vec<String>,
+    pub post_bump: Vec<HookEntry>,
 
     /// Hooks to run before commit.
     #[serde(default)]
-    pub pre_commit: Vec<String>,
+    pub pre_commit: Vec<HookEntry>,
 
     /// Hooks to run before tag.
     #[serde(default)]
-    pub pre_tag: Vec<String>,
+    pub pre_tag: Vec<HookEntry>,
 
     /// Hooks to run after tag.
     #[serde(default)]
-    pub post_tag: Vec<String>,
+    pub post_tag: Vec<HookEntry>,
+
+    /// Hooks to run after the release is pushed.
+    #[serde(default)]
+    pub post_release: Vec<HookEntry>,
+
+    /// Hooks to run when a release step fails.
+    #[serde(default)]
+    pub failure: Vec<HookEntry>,
+
+    /// Maximum number of asynchronous hooks running at the same time.
+    #[serde(default = "default_hook_concurrency")]
+    pub concurrency: usize,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            pre_release: Vec::new(),
+            pre_bump: Vec::new(),
+            post_bump: Vec::new(),
+            pre_commit: Vec::new(),
+            pre_tag: Vec::new(),
+            post_tag: Vec::new(),
+            post_release: Vec::new(),
+            failure: Vec::new(),
+            concurrency: default_hook_concurrency(),
+        }
+    }
+}
+
+fn default_hook_concurrency() -> usize {
+    4
 }
 
 /// Version configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VersionConfig {
     /// Files containing version information.
     #[serde(default)]
     pub files: Vec<String>,
 
+    /// Files with a custom version location (`[[version.file]]`).
+    #[serde(default, rename = "file")]
+    pub custom_files: Vec<VersionFileConfig>,
+
+    /// Also bump version requirements of path dependencies in Cargo.toml files.
+    #[serde(default)]
+    pub update_path_dependencies: bool,
+
+    /// Only count commits touching these paths (e.g., `["crates/foo/**"]`).
+    ///
+    /// Paths are git pathspecs relative to the repository root. An empty
+    /// list counts every commit.
+    #[serde(default)]
+    pub paths: Vec<String>,
+
+    /// Only follow the first parent of merge commits when collecting commits.
+    #[serde(default)]
+    pub first_parent: bool,
+
+    /// Ignore merge commits when collecting commits.
+    #[serde(default)]
+    pub skip_merges: bool,
+
+    /// Maximum number of commits collected since the last release.
+    pub max_commits: Option<usize>,
+
+    /// Cache the commits parsed since the last release in `.unduler/cache`.
+    #[serde(default = "default_true")]
+    pub cache: bool,
+
+    /// Fetch the full history and tags from `remote` when the repository is
+    /// a shallow clone, instead of failing.
+    #[serde(default)]
+    pub unshallow: bool,
+
     /// Tag prefix (e.g., "v").
     #[serde(default = "default_tag_prefix")]
     pub tag_prefix: String,
+
+    /// Tag name template (e.g., "v{version}" or "{package}@{version}").
+    ///
+    /// Supports `{version}`, `{major}`, `{minor}`, `{patch}` and `{package}`
+    /// placeholders. Falls back to `tag_prefix` when not set.
+    pub tag_format: Option<String>,
+
+    /// Package name used for the `{package}` tag placeholder.
+    pub package: Option<String>,
+
+    /// Release commit message template.
+    ///
+    /// Supports `{version}`, `{tag}` and `{date}` placeholders.
+    #[serde(default = "default_commit_message")]
+    pub commit_message: String,
+
+    /// Author and committer of the release commit, and tagger of annotated
+    /// release tags, written `Name <email>` (default: `user.name` and
+    /// `user.email` from the git configuration), e.g. in CI containers
+    /// without git identity.
+    pub commit_author: Option<String>,
+
+    /// Only commit the files changed by the release: the version files, the
+    /// changelog and the changelog fragments, instead of every change.
+    #[serde(default)]
+    pub commit_release_files: bool,
+
+    /// Run the `pre-commit` and `commit-msg` hooks of the release commit.
+    #[serde(default)]
+    pub verify: bool,
+
+    /// Create lightweight release tags instead of annotated ones.
+    ///
+    /// Lightweight tags have no message, so `tag_message` is ignored.
+    #[serde(default)]
+    pub lightweight_tags: bool,
+
+    /// Annotated release tag message template.
+    ///
+    /// Supports `{version}`, `{tag}`, `{date}` and `{changelog}` placeholders,
+    /// `{changelog}` being the changelog section of the release.
+    #[serde(default = "default_tag_message")]
+    pub tag_message: String,
+
+    /// Push the release commit and tag after a release.
+    #[serde(default)]
+    pub push: bool,
+
+    /// Remote to push releases to.
+    #[serde(default = "default_remote")]
+    pub remote: String,
+
+    /// Branch to push releases to (default: current branch).
+    pub branch: Option<String>,
+
+    /// Branches releases are allowed from (e.g., `["main", "release/*"]`).
+    ///
+    /// Supports `*` (within a path segment) and `**` wildcards. An empty list
+    /// allows every branch.
+    #[serde(default)]
+    pub release_branches: Vec<String>,
+
+    /// Prerelease channels (`[[version.channel]]`).
+    #[serde(default, rename = "channel")]
+    pub channels: Vec<ChannelConfig>,
+}
+
+impl VersionConfig {
+    /// Returns the tag name template, derived from `tag_prefix` if not set.
+    #[must_use]
+    pub fn tag_template(&self) -> String {
+        self.tag_format
+            .clone()
+            .unwrap_or_else(|| format!("{}{{version}}", self.tag_prefix))
+    }
+
+    /// Returns true if releases are allowed from the given branch.
+    #[must_use]
+    pub fn is_release_branch(&self, branch: &str) -> bool {
+        self.release_branches.is_empty()
+            || self
+                .release_branches
+                .iter()
+                .any(|pattern| branch_matches(pattern, branch))
+    }
+
+    /// Returns the prerelease channel of the given branch, if any.
+    #[must_use]
+    pub fn channel_for(&self, branch: &str) -> Option<&ChannelConfig> {
+        self.channels
+            .iter()
+            .find(|channel| branch_matches(&channel.branch, branch))
+    }
+}
+
+/// Matches a branch name against a glob pattern.
+///
+/// `*` matches any characters except `/`, `**` matches any characters.
+pub(crate) fn branch_matches(pattern: &str, branch: &str) -> bool {
+    match pattern.strip_prefix("**") {
+        Some(rest) => (0..=branch.len())
+            .filter(|&i| branch.is_char_boundary(i))
+            .any(|i| branch_matches(rest, &branch[i..])),
+        None => match pattern.strip_prefix('*') {
+            Some(rest) => (0..=branch.len())
+                .filter(|&i| branch.is_char_boundary(i))
+                .take_while(|&i| !branch[..i].contains('/'))
+                .any(|i| branch_matches(rest, &branch[i..])),
+            None => match (pattern.chars().next(), branch.chars().next()) {
+                (Some(p), Some(b)) if p == b => {
+                    branch_matches(&pattern[p.len_utf8()..], &branch[b.len_utf8()..])
+                }
+                (None, None) => true,
+                _ => false,
+            },
+        },
+    }
 }
 
 impl Default for VersionConfig {
     fn default() -> Self {
         Self {
             files: vec!["Cargo.toml".to_string()],
+            custom_files: Vec::new(),
+            update_path_dependencies: false,
+            paths: Vec::new(),
+            first_parent: false,
+            skip_merges: false,
+            max_commits: None,
+            cache: true,
+            unshallow: false,
             tag_prefix: default_tag_prefix(),
+            tag_format: None,
+            package: None,
+            commit_message: default_commit_message(),
+            commit_author: None,
+            commit_release_files: false,
+            verify: false,
+            lightweight_tags: false,
+            tag_message: default_tag_message(),
+            push: false,
+            remote: default_remote(),
+            branch: None,
+            release_branches: Vec::new(),
+            channels: Vec::new(),
         }
     }
 }
 
+fn default_remote() -> String {
+    "origin".to_string()
+}
+
 fn default_tag_prefix() -> String {
     "v".to_string()
 }
 
+fn default_commit_message() -> String {
+    "chore(release): {version}".to_string()
+}
+
+fn default_tag_message() -> String {
+    "Release {version}".to_string()
+}
+
+/// A file with a custom version location.
+///
+/// Without `pattern` or `key`, the file is updated based on its type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VersionFileConfig {
+    /// Path to the file.
+    pub path: String,
+
+    /// Regex locating the version, with a `{version}` placeholder
+    /// (e.g., `__version__ = "{version}"`).
+    pub pattern: Option<String>,
+
+    /// Dot-separated YAML key holding the version (e.g., `appVersion`).
+    pub key: Option<String>,
+}
+
+/// Prerelease channel tied to a branch.
+///
+/// Releases from a matching branch are prereleases (e.g., `1.5.0-rc.1`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelConfig {
+    /// Branch name or glob pattern (e.g., `next` or `beta/*`).
+    pub branch: String,
+
+    /// Prerelease identifier (e.g., `rc`).
+    pub prerelease: String,
+}
+
 /// Changelog configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ChangelogConfig {
     /// Output file path.
+    ///
+    /// `none` writes no changelog file, the release notes only going to the
+    /// tag message and the forge release.
     #[serde(default = "default_changelog_output")]
     pub output: String,
+
+    /// Header written at the top of a new changelog, replacing the Keep a
+    /// Changelog preamble. An empty header writes none.
+    pub header: Option<String>,
+
+    /// File holding the header of a new changelog, taking precedence over
+    /// `header`.
+    pub header_file: Option<String>,
+
+    /// Repository web URL used for comparison links.
+    ///
+    /// Derived from the `origin` remote when not set.
+    pub repository_url: Option<String>,
+
+    /// Directory of changelog fragments, e.g. `changelog.d`.
+    ///
+    /// Fragments named `<name>.<type>.md` are merged into the next release
+    /// and removed once the changelog is written.
+    pub fragments: Option<String>,
+
+    /// Other files the release section is inserted into, at a marker.
+    #[serde(default)]
+    pub inserts: Vec<ChangelogInsertConfig>,
+
+    /// Files holding one release each, with an index.
+    pub releases: Option<ReleaseFilesConfig>,
+
+    /// Other files the release is rendered to, each with its own formatter.
+    #[serde(default)]
+    pub outputs: Vec<ChangelogOutputConfig>,
+
+    /// Canonical scopes keyed by alias, e.g. `ui = "frontend"`.
+    #[serde(default)]
+    pub scope_aliases: HashMap<String, String>,
+
+    /// Lowercase scopes, matching aliases and `scopes` regardless of case.
+    #[serde(default)]
+    pub scope_case_fold: bool,
+
+    /// Known scopes, others being replaced by `unknown_scope`.
+    ///
+    /// Any scope is kept when empty.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// Scope replacing the scopes missing from `scopes`.
+    #[serde(default = "default_unknown_scope")]
+    pub unknown_scope: String,
 }
 
 impl Default for ChangelogConfig {
     fn default() -> Self {
         Self {
             output: default_changelog_output(),
+            header: None,
+            header_file: None,
+            repository_url: None,
+            fragments: None,
+            inserts: Vec::new(),
+            releases: None,
+            outputs: Vec::new(),
+            scope_aliases: HashMap::new(),
+            scope_case_fold: false,
+            scopes: Vec::new(),
+            unknown_scope: default_unknown_scope(),
         }
     }
 }
 
+impl ChangelogConfig {
+    /// Value of `output` writing no changelog file.
+    pub const NO_OUTPUT: &'static str = "none";
+
+    /// Returns the changelog file path, `None` when `output` is `none`.
+    #[must_use]
+    pub fn output_file(&self) -> Option<&str> {
+        (self.output != Self::NO_OUTPUT).then_some(self.output.as_str())
+    }
+}
+
 fn default_changelog_output() -> String {
     "CHANGELOG.md".to_string()
 }
 
+fn default_unknown_scope() -> String {
+    "misc".to_string()
+}
+
+/// Changelog files holding one release each.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReleaseFilesConfig {
+    /// Directory of the release files (e.g., `changelogs`).
+    pub dir: String,
+
+    /// File name template of a release, with a `{version}` placeholder.
+    #[serde(default = "default_release_file_name")]
+    pub file_name: String,
+
+    /// File name of the index listing the releases, in `dir`.
+    #[serde(default = "default_release_index")]
+    pub index: String,
+
+    /// Write only the release files, not the `output` changelog.
+    #[serde(default)]
+    pub skip_output: bool,
+}
+
+fn default_release_file_name() -> String {
+    "v{version}.md".to_string()
+}
+
+fn default_release_index() -> String {
+    "index.md".to_string()
+}
+
+/// File a release is rendered to by a formatter other than the changelog's.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChangelogOutputConfig {
+    /// Formatter name (e.g., `json`).
+    pub formatter: String,
+
+    /// File path (e.g., `dist/release.json`).
+    pub path: String,
+}
+
+/// File the release section is inserted into, at a marker comment.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChangelogInsertConfig {
+    /// File path (e.g., `docs/releases.md`).
+    pub path: String,
+
+    /// Marker the section is inserted after.
+    #[serde(default = "default_insert_marker")]
+    pub marker: String,
+
+    /// Marker ending a block replaced by the latest release.
+    ///
+    /// Without it, releases accumulate after `marker`, newest first.
+    pub end_marker: Option<String>,
+
+    /// Part of the release section rendered.
+    #[serde(default)]
+    pub format: InsertFormat,
+}
+
+fn default_insert_marker() -> String {
+    "<!-- unduler:insert -->".to_string()
+}
+
+/// Part of the release section inserted into a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InsertFormat {
+    /// Whole section, with its heading.
+    #[default]
+    Section,
+    /// Section without its heading.
+    Body,
+}
+
 /// Plugin-specific configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct PluginsConfig {
     /// Cargo hook configuration.
     #[serde(default)]
@@ -220,10 +974,56 @@ pub struct PluginsConfig {
     /// GitHub Release hook configuration.
     #[serde(default, rename = "github-release")]
     pub github_release: GithubReleasePluginConfig,
+
+    /// Chat notification hook configuration.
+    #[serde(default)]
+    pub notify: NotifyPluginConfig,
+
+    /// Generic webhook hook configuration.
+    #[serde(default)]
+    pub webhook: WebhookPluginConfig,
+
+    /// Maven and Gradle hook configuration.
+    #[serde(default)]
+    pub jvm: JvmPluginConfig,
+
+    /// OCI artifact hook configuration.
+    #[serde(default)]
+    pub oci: OciPluginConfig,
+
+    /// JIRA hook configuration.
+    #[serde(default)]
+    pub jira: JiraPluginConfig,
+
+    /// Sentry hook configuration.
+    #[serde(default)]
+    pub sentry: SentryPluginConfig,
+
+    /// Milestone hook configuration.
+    #[serde(default)]
+    pub milestone: MilestonePluginConfig,
+
+    /// SBOM and provenance hook configuration.
+    #[serde(default)]
+    pub sbom: SbomPluginConfig,
+
+    /// Configuration of other plugins, by plugin name, delivered to WASM
+    /// plugins when they are loaded.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+impl PluginsConfig {
+    /// Returns the `[plugins.<name>]` table of a plugin without built-in
+    /// configuration.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&serde_json::Value> {
+        self.other.get(name)
+    }
 }
 
 /// Cargo plugin configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct CargoPluginConfig {
     /// Publish to crates.io after release.
     #[serde(default)]
@@ -234,7 +1034,7 @@ pub struct CargoPluginConfig {
 }
 
 /// npm plugin configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct NpmPluginConfig {
     /// Publish to npm after release.
     #[serde(default)]
@@ -245,7 +1045,7 @@ pub struct NpmPluginConfig {
 }
 
 /// GitHub Release plugin configuration.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct GithubReleasePluginConfig {
     /// Create release as draft.
     #[serde(default)]
@@ -258,16 +1058,533 @@ pub struct GithubReleasePluginConfig {
     /// Assets to upload.
     #[serde(default)]
     pub assets: Vec<String>,
+
+    /// Command printing the GitHub token, e.g. `gh auth token`.
+    pub token_command: Option<String>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Chat notification plugin configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyPluginConfig {
+    /// Message template of channels without their own, with `{version}`,
+    /// `{previous_version}`, `{tag}`, `{highlights}` and `{changelog_url}`
+    /// placeholders.
+    pub template: Option<String>,
 
-    #[test]
-    fn test_default_config() {
-        let config = Config::default();
-        assert_eq!(config.parser.name, "conventional");
+    /// Link to the changelog, with `{version}` and `{tag}` placeholders.
+    pub changelog_url: Option<String>,
+
+    /// Webhooks notified after a release.
+    #[serde(default)]
+    pub channels: Vec<NotifyChannelConfig>,
+
+    /// Also announce failed releases, with the error of the failed step.
+    #[serde(default)]
+    pub failures: bool,
+}
+
+/// A webhook notified after a release.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyChannelConfig {
+    /// Chat service of the webhook.
+    pub kind: NotifyChannelKind,
+
+    /// Webhook URL.
+    pub webhook_url: Option<String>,
+
+    /// Environment variable holding the webhook URL, to keep it out of the
+    /// configuration.
+    pub webhook_url_env: Option<String>,
+
+    /// Message template overriding `plugins.notify.template`.
+    pub template: Option<String>,
+}
+
+/// Chat service of a notification webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyChannelKind {
+    /// Slack incoming webhook.
+    Slack,
+    /// Discord webhook.
+    Discord,
+    /// Microsoft Teams incoming webhook.
+    Teams,
+}
+
+/// Generic webhook plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookPluginConfig {
+    /// Number of retries of a failed request.
+    #[serde(default = "default_webhook_retries")]
+    pub retries: u32,
+
+    /// Delay before the first retry, in seconds, doubled on each retry.
+    #[serde(default = "default_webhook_backoff")]
+    pub backoff: u64,
+
+    /// Webhooks to call.
+    #[serde(default)]
+    pub targets: Vec<WebhookTargetConfig>,
+}
+
+impl Default for WebhookPluginConfig {
+    fn default() -> Self {
+        Self {
+            retries: default_webhook_retries(),
+            backoff: default_webhook_backoff(),
+            targets: Vec::new(),
+        }
+    }
+}
+
+fn default_webhook_retries() -> u32 {
+    3
+}
+
+fn default_webhook_backoff() -> u64 {
+    1
+}
+
+/// A URL receiving the release as JSON.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookTargetConfig {
+    /// URL the body is posted to.
+    pub url: String,
+
+    /// Stages at which the webhook is called.
+    #[serde(default = "default_webhook_stages")]
+    pub stages: Vec<WebhookStage>,
+
+    /// Request headers.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Request headers read from environment variables, keyed by header name.
+    #[serde(default)]
+    pub secret_headers: HashMap<String, String>,
+
+    /// Body template, with `{version}`, `{previous_version}`, `{tag}`,
+    /// `{stage}`, `{changelog}` and `{commits}` placeholders.
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_webhook_stages() -> Vec<WebhookStage> {
+    vec![WebhookStage::PostTag]
+}
+
+/// Release stage at which a webhook is called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookStage {
+    /// Once the release is planned, before anything is modified. A client
+    /// error response vetoes the release.
+    PreRelease,
+    /// Before version files are modified.
+    PreBump,
+    /// After version files are modified.
+    PostBump,
+    /// Before the release commit is created.
+    PreCommit,
+    /// Before the git tag is created.
+    PreTag,
+    /// After the git tag is created.
+    PostTag,
+    /// After the release is pushed.
+    PostRelease,
+    /// After a step of the release failed.
+    Failure,
+}
+
+/// Maven and Gradle plugin configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct JvmPluginConfig {
+    /// Publish the artifacts after release.
+    #[serde(default)]
+    pub publish: bool,
+
+    /// Build tool, detected from the project files when unset.
+    pub tool: Option<JvmBuildTool>,
+
+    /// Tasks (Gradle) or goals (Maven) to run, `publish` or `deploy` by default.
+    #[serde(default)]
+    pub tasks: Vec<String>,
+
+    /// Maven profiles to activate.
+    #[serde(default)]
+    pub profiles: Vec<String>,
+
+    /// Additional arguments.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Environment variables of the build.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Build tool of a JVM project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum JvmBuildTool {
+    /// Gradle.
+    Gradle,
+    /// Maven.
+    Maven,
+}
+
+/// OCI artifact plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OciPluginConfig {
+    /// Repository the artifact is pushed to, e.g. `ghcr.io/acme/modules/vpc`.
+    /// The artifact is only pushed when set.
+    pub repository: Option<String>,
+
+    /// Directory packaged into the artifact, relative to the repository root.
+    #[serde(default = "default_oci_path")]
+    pub path: String,
+
+    /// Tags of the artifact, with `{version}` and `{tag}` placeholders.
+    #[serde(default = "default_oci_tags")]
+    pub tags: Vec<String>,
+
+    /// Artifact type of the manifest.
+    pub artifact_type: Option<String>,
+
+    /// Media type of the packaged directory.
+    pub media_type: Option<String>,
+}
+
+impl Default for OciPluginConfig {
+    fn default() -> Self {
+        Self {
+            repository: None,
+            path: default_oci_path(),
+            tags: default_oci_tags(),
+            artifact_type: None,
+            media_type: None,
+        }
+    }
+}
+
+fn default_oci_path() -> String {
+    ".".to_string()
+}
+
+fn default_oci_tags() -> Vec<String> {
+    vec!["{version}".to_string()]
+}
+
+/// JIRA plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JiraPluginConfig {
+    /// Base URL of the JIRA instance, e.g. `https://acme.atlassian.net`.
+    /// Issues are only updated when set.
+    pub base_url: Option<String>,
+
+    /// Email of the account, for JIRA Cloud. The token is sent as a bearer
+    /// token when unset.
+    pub email: Option<String>,
+
+    /// Environment variable holding the API token.
+    #[serde(default = "default_jira_token_env")]
+    pub token_env: String,
+
+    /// Comment added to the issues, with `{version}` and `{tag}` placeholders.
+    pub comment: Option<String>,
+
+    /// Name of the transition applied to the issues, e.g. `Released`.
+    pub transition: Option<String>,
+}
+
+impl Default for JiraPluginConfig {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            email: None,
+            token_env: default_jira_token_env(),
+            comment: None,
+            transition: None,
+        }
+    }
+}
+
+fn default_jira_token_env() -> String {
+    "JIRA_API_TOKEN".to_string()
+}
+
+/// Sentry plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SentryPluginConfig {
+    /// Organization slug. Releases are only registered when set.
+    pub org: Option<String>,
+
+    /// Project slugs the release belongs to.
+    #[serde(default)]
+    pub projects: Vec<String>,
+
+    /// Base URL of a self-hosted Sentry instance.
+    pub url: Option<String>,
+
+    /// Environment variable holding the authentication token.
+    #[serde(default = "default_sentry_token_env")]
+    pub token_env: String,
+
+    /// Release name, with `{version}` and `{tag}` placeholders.
+    #[serde(default = "default_sentry_release")]
+    pub release: String,
+
+    /// Environment of the deploy marked after the release. An empty string
+    /// disables the deploy.
+    #[serde(default = "default_sentry_environment")]
+    pub environment: String,
+}
+
+impl Default for SentryPluginConfig {
+    fn default() -> Self {
+        Self {
+            org: None,
+            projects: Vec::new(),
+            url: None,
+            token_env: default_sentry_token_env(),
+            release: default_sentry_release(),
+            environment: default_sentry_environment(),
+        }
+    }
+}
+
+fn default_sentry_token_env() -> String {
+    "SENTRY_AUTH_TOKEN".to_string()
+}
+
+fn default_sentry_release() -> String {
+    "{version}".to_string()
+}
+
+fn default_sentry_environment() -> String {
+    "production".to_string()
+}
+
+/// Forge hosting the milestones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneForge {
+    /// GitHub.
+    #[default]
+    GitHub,
+    /// GitLab.
+    GitLab,
+}
+
+/// Milestone plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MilestonePluginConfig {
+    /// Forge hosting the repository.
+    #[serde(default)]
+    pub forge: MilestoneForge,
+
+    /// Repository (`owner/repo`) or GitLab project path. Milestones are only
+    /// managed when set.
+    pub repository: Option<String>,
+
+    /// API URL of a self-hosted forge.
+    pub api_url: Option<String>,
+
+    /// Environment variable holding the API token, `GITHUB_TOKEN` or
+    /// `GITLAB_TOKEN` by default.
+    pub token_env: Option<String>,
+
+    /// Milestone title, with `{version}` and `{tag}` placeholders.
+    #[serde(default = "default_milestone_title")]
+    pub title: String,
+
+    /// Bump from the released version to the next milestone. `none` disables
+    /// the next milestone.
+    #[serde(default = "default_milestone_next")]
+    pub next: BumpRule,
+}
+
+impl Default for MilestonePluginConfig {
+    fn default() -> Self {
+        Self {
+            forge: MilestoneForge::default(),
+            repository: None,
+            api_url: None,
+            token_env: None,
+            title: default_milestone_title(),
+            next: default_milestone_next(),
+        }
+    }
+}
+
+fn default_milestone_title() -> String {
+    "{version}".to_string()
+}
+
+fn default_milestone_next() -> BumpRule {
+    BumpRule::Minor
+}
+
+/// Format of a software bill of materials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SbomFormat {
+    /// `CycloneDX` JSON.
+    CycloneDx,
+    /// SPDX JSON.
+    Spdx,
+}
+
+/// SBOM and provenance plugin configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SbomPluginConfig {
+    /// Generate the SBOM and provenance of each release.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Source scanned by `syft`, e.g. `dir:.` or `file:target/release/app`.
+    #[serde(default = "default_sbom_source")]
+    pub source: String,
+
+    /// Formats of the generated SBOMs.
+    #[serde(default = "default_sbom_formats")]
+    pub formats: Vec<SbomFormat>,
+
+    /// Artifacts described by the provenance, `plugins.github-release.assets`
+    /// by default.
+    pub artifacts: Option<Vec<String>>,
+
+    /// Directory the files are written to.
+    #[serde(default = "default_sbom_output_dir")]
+    pub output_dir: String,
+
+    /// Generate a SLSA provenance statement of the artifacts.
+    #[serde(default = "default_true")]
+    pub provenance: bool,
+
+    /// Identifier of the builder recorded in the provenance.
+    pub builder_id: Option<String>,
+
+    /// URI of the source repository recorded in the provenance.
+    pub source_uri: Option<String>,
+}
+
+impl Default for SbomPluginConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: default_sbom_source(),
+            formats: default_sbom_formats(),
+            artifacts: None,
+            output_dir: default_sbom_output_dir(),
+            provenance: true,
+            builder_id: None,
+            source_uri: None,
+        }
+    }
+}
+
+fn default_sbom_source() -> String {
+    "dir:.".to_string()
+}
+
+fn default_sbom_formats() -> Vec<SbomFormat> {
+    vec![SbomFormat::CycloneDx]
+}
+
+fn default_sbom_output_dir() -> String {
+    "target/sbom".to_string()
+}
+
+/// Sandbox configuration for a WASM plugin.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SandboxConfig {
+    /// Commands the plugin may execute (defaults to the built-in whitelist).
+    pub allowed_commands: Option<Vec<String>>,
+
+    /// Paths, relative to the repository root, the plugin may write to.
+    /// An empty list allows writing anywhere inside the repository.
+    #[serde(default)]
+    pub allowed_paths: Vec<String>,
+
+    /// Allow commands that access the network.
+    #[serde(default = "default_true")]
+    pub network: bool,
+
+    /// Hosts the plugin may send HTTP requests to, e.g. `api.github.com`,
+    /// or `*.slack.com` for any subdomain. An empty list denies HTTP
+    /// requests.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// Maximum execution time of a single command, in seconds.
+    pub timeout: Option<u64>,
+
+    /// Give the plugin read-only WASI access to the repository.
+    #[serde(default)]
+    pub filesystem: bool,
+
+    /// Give the plugin the WASI clocks (frozen at the Unix epoch otherwise).
+    #[serde(default)]
+    pub clocks: bool,
+
+    /// Give the plugin WASI randomness (its calls trap otherwise).
+    #[serde(default)]
+    pub random: bool,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            allowed_commands: None,
+            allowed_paths: Vec::new(),
+            network: true,
+            allowed_hosts: Vec::new(),
+            timeout: None,
+            filesystem: false,
+            clocks: false,
+            random: false,
+        }
+    }
+}
+
+/// WASM runtime limits.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WasmConfig {
+    /// Maximum duration of a single plugin call, in seconds (0 disables the limit).
+    #[serde(default = "default_wasm_timeout")]
+    pub timeout: u64,
+
+    /// Maximum memory of a plugin instance, in `MiB` (0 disables the limit).
+    #[serde(default = "default_wasm_max_memory")]
+    pub max_memory: u64,
+}
+
+impl Default for WasmConfig {
+    fn default() -> Self {
+        Self {
+            timeout: default_wasm_timeout(),
+            max_memory: default_wasm_max_memory(),
+        }
+    }
+}
+
+fn default_wasm_timeout() -> u64 {
+    30
+}
+
+fn default_wasm_max_memory() -> u64 {
+    256
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.parser.name, "conventional");
         assert_eq!(config.bumper.name, "semver");
         assert_eq!(config.formatter.name, "keepachangelog");
         assert_eq!(config.version.tag_prefix, "v");
@@ -287,35 +1604,322 @@ mod tests {
     fn test_default_bumper_config() {
         let config = BumperConfig::default();
         assert_eq!(config.name, "semver");
+        assert!(!config.zero_ver);
+    }
+
+    #[test]
+    fn test_deserialize_zero_ver() {
+        let config: Config = toml::from_str("[bumper]\nzero_ver = true").unwrap();
+        assert!(config.bumper.zero_ver);
+        assert_eq!(config.bumper.name, "semver");
+    }
+
+    #[test]
+    fn test_deserialize_bump_rules() {
+        let toml = r#"
+            [bumper.rules]
+            feat = "minor"
+            deps = "patch"
+            docs = "none"
+            "refactor(core)" = "minor"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let rules = &config.bumper.rules;
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules["feat"], BumpRule::Minor);
+        assert_eq!(rules["deps"], BumpRule::Patch);
+        assert_eq!(rules["docs"], BumpRule::None);
+        assert_eq!(rules["refactor(core)"], BumpRule::Minor);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_bump_rule() {
+        let result: Result<Config, _> = toml::from_str("[bumper.rules]\nfeat = \"huge\"");
+        assert!(result.is_err());
     }
 
     #[test]
     fn test_default_formatter_config() {
         let config = FormatterPluginConfig::default();
         assert_eq!(config.name, "keepachangelog");
+        assert!(config.link_references);
+        assert!(config.closed_issues);
+        assert!(!config.include_authors);
+        assert!(!config.github_authors);
+        assert!(!config.contributors);
+        assert!(!config.summary);
+        assert!(!config.highlights);
+        assert_eq!(config.highlight_marker, "[highlight]");
+        assert!(!config.breaking_changes);
+        assert!(!config.show_emoji);
+        assert!(!config.deduplicate);
+        assert!(config.line_width.is_none());
+        assert_eq!(config.message_case, MessageCase::Preserve);
+        assert_eq!(config.trailing_punctuation, TrailingPunctuation::Preserve);
+        assert_eq!(config.bullet, ListBullet::Dash);
+        assert!(config.locale.is_none());
+        assert!(config.labels.is_empty());
+        assert!(config.sections.is_empty());
+        assert!(config.hide_types.is_empty());
+        assert_eq!(config.debian.distribution, "unstable");
+    }
+
+    #[test]
+    fn test_deserialize_formatter_style() {
+        let config: FormatterPluginConfig = toml::from_str(
+            r#"
+            line_width = 80
+            message_case = "sentence"
+            trailing_punctuation = "strip"
+            bullet = "*"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.line_width, Some(80));
+        assert_eq!(config.message_case, MessageCase::Sentence);
+        assert_eq!(config.trailing_punctuation, TrailingPunctuation::Strip);
+        assert_eq!(config.bullet, ListBullet::Asterisk);
+    }
+
+    #[test]
+    fn test_deserialize_formatter_sections() {
+        let config: FormatterPluginConfig = toml::from_str(
+            r#"
+            sections = [
+                { types = ["feat"], title = "Features" },
+                { types = ["build", "ci"], title = "Build System" },
+            ]
+            hide_types = ["chore"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.sections.len(), 2);
+        assert_eq!(config.sections[1].types, ["build", "ci"]);
+        assert_eq!(config.sections[1].title, "Build System");
+        assert_eq!(config.hide_types, ["chore"]);
+    }
+
+    #[test]
+    fn test_deserialize_formatter_locale() {
+        let config: FormatterPluginConfig = toml::from_str(
+            r#"
+            locale = "fr"
+
+            [labels]
+            chore = "Entretien"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.locale.as_deref(), Some("fr"));
+        assert_eq!(config.labels["chore"], "Entretien");
+    }
+
+    #[test]
+    fn test_deserialize_formatter_debian() {
+        let config: FormatterPluginConfig = toml::from_str(
+            r#"
+            name = "debian"
+
+            [debian]
+            package = "app"
+            distribution = "noble"
+            revision = "1"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.name, "debian");
+        assert_eq!(config.debian.package.as_deref(), Some("app"));
+        assert!(config.debian.maintainer.is_none());
+        assert_eq!(config.debian.distribution, "noble");
+        assert_eq!(config.debian.urgency, "medium");
+        assert_eq!(config.debian.revision.as_deref(), Some("1"));
     }
 
     #[test]
     fn test_default_version_config() {
         let config = VersionConfig::default();
         assert_eq!(config.tag_prefix, "v");
+        assert_eq!(config.commit_message, "chore(release): {version}");
+        assert!(config.commit_author.is_none());
+        assert!(!config.commit_release_files);
+        assert!(!config.verify);
+        assert!(!config.lightweight_tags);
+        assert_eq!(config.tag_message, "Release {version}");
+        assert!(!config.update_path_dependencies);
+        assert!(config.paths.is_empty());
+        assert!(!config.first_parent);
+        assert!(!config.skip_merges);
+        assert!(config.max_commits.is_none());
+        assert!(config.cache);
+        assert!(!config.unshallow);
+        assert!(!config.push);
+        assert_eq!(config.remote, "origin");
+        assert!(config.branch.is_none());
+        assert!(config.release_branches.is_empty());
+        assert!(config.channels.is_empty());
         assert_eq!(config.files, vec!["Cargo.toml".to_string()]);
     }
 
+    #[test]
+    fn test_deserialize_commit_options() {
+        let config: Config = toml::from_str(
+            r#"
+            [version]
+            commit_author = "release-bot <bot@ci>"
+            commit_release_files = true
+            verify = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.version.commit_author.as_deref(),
+            Some("release-bot <bot@ci>")
+        );
+        assert!(config.version.commit_release_files);
+        assert!(config.version.verify);
+    }
+
+    #[test]
+    fn test_deserialize_tag_options() {
+        let config: Config = toml::from_str(
+            r#"
+            [version]
+            lightweight_tags = true
+            tag_message = "{tag}\n\n{changelog}"
+            "#,
+        )
+        .unwrap();
+        assert!(config.version.lightweight_tags);
+        assert_eq!(config.version.tag_message, "{tag}\n\n{changelog}");
+    }
+
+    #[test]
+    fn test_tag_template() {
+        let mut config = VersionConfig::default();
+        assert_eq!(config.tag_template(), "v{version}");
+
+        config.tag_prefix = "release-".to_string();
+        assert_eq!(config.tag_template(), "release-{version}");
+
+        config.tag_format = Some("{package}@{version}".to_string());
+        assert_eq!(config.tag_template(), "{package}@{version}");
+    }
+
+    #[test]
+    fn test_validate_package_placeholder() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        config.version.tag_format = Some("{package}@{version}".to_string());
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid(_))));
+
+        config.version.package = Some("app".to_string());
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_default_changelog_config() {
         let config = ChangelogConfig::default();
         assert_eq!(config.output, "CHANGELOG.md");
+        assert!(config.fragments.is_none());
+        assert!(config.inserts.is_empty());
+        assert!(config.releases.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_changelog_releases() {
+        let config: Config =
+            toml::from_str("[changelog.releases]\ndir = \"changelogs\"\nskip_output = true\n")
+                .unwrap();
+
+        let releases = config.changelog.releases.unwrap();
+        assert_eq!(releases.dir, "changelogs");
+        assert_eq!(releases.file_name, "v{version}.md");
+        assert_eq!(releases.index, "index.md");
+        assert!(releases.skip_output);
+    }
+
+    #[test]
+    fn test_deserialize_changelog_inserts() {
+        let config: Config = toml::from_str(
+            r#"
+[[changelog.inserts]]
+path = "docs/releases.md"
+
+[[changelog.inserts]]
+path = "README.md"
+marker = "<!-- latest-release -->"
+end_marker = "<!-- /latest-release -->"
+format = "body"
+"#,
+        )
+        .unwrap();
+
+        let inserts = &config.changelog.inserts;
+        assert_eq!(inserts.len(), 2);
+        assert_eq!(inserts[0].marker, "<!-- unduler:insert -->");
+        assert!(inserts[0].end_marker.is_none());
+        assert_eq!(inserts[0].format, InsertFormat::Section);
+        assert_eq!(
+            inserts[1].end_marker.as_deref(),
+            Some("<!-- /latest-release -->")
+        );
+        assert_eq!(inserts[1].format, InsertFormat::Body);
+    }
+
+    #[test]
+    fn test_deserialize_changelog_fragments() {
+        let config: Config = toml::from_str("[changelog]\nfragments = \"changelog.d\"\n").unwrap();
+        assert_eq!(config.changelog.fragments.as_deref(), Some("changelog.d"));
     }
 
     #[test]
     fn test_default_hooks_config() {
         let config = HooksConfig::default();
+        assert!(config.pre_release.is_empty());
         assert!(config.pre_bump.is_empty());
         assert!(config.post_bump.is_empty());
         assert!(config.pre_commit.is_empty());
         assert!(config.pre_tag.is_empty());
         assert!(config.post_tag.is_empty());
+        assert!(config.post_release.is_empty());
+        assert!(config.failure.is_empty());
+        assert_eq!(config.concurrency, 4);
+    }
+
+    #[test]
+    fn test_parse_hook_concurrency() {
+        let config: Config = toml::from_str("[hooks]\nconcurrency = 8\n").unwrap();
+        assert_eq!(config.hooks.concurrency, 8);
+
+        let config: Config = toml::from_str("[hooks]\npost_tag = [\"notify\"]\n").unwrap();
+        assert_eq!(config.hooks.concurrency, 4);
+    }
+
+    #[test]
+    fn test_parse_hook_entries() {
+        let config: Config = toml::from_str(
+            r#"
+            [[hooks.post_tag]]
+            name = "github-release"
+            if = "!prerelease"
+            continue_on_error = true
+            config = { draft = true }
+
+            [[hooks.post_tag]]
+            name = "notify"
+            "#,
+        )
+        .unwrap();
+
+        let entries = &config.hooks.post_tag;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "github-release");
+        assert!(entries[0].continue_on_error);
+        assert!(!entries[0].applies(true, false, Some("main")));
+        assert!(entries[0].applies(false, false, Some("main")));
+        assert_eq!(entries[1], HookEntry::new("notify"));
     }
 
     #[test]
@@ -328,6 +1932,225 @@ mod tests {
         assert!(!config.github_release.draft);
         assert!(!config.github_release.prerelease);
         assert!(config.github_release.assets.is_empty());
+        assert!(config.notify.channels.is_empty());
+        assert_eq!(config.webhook.retries, 3);
+        assert!(config.webhook.targets.is_empty());
+        assert!(!config.jvm.publish);
+        assert!(config.jvm.tool.is_none());
+        assert!(config.oci.repository.is_none());
+        assert_eq!(config.oci.path, ".");
+        assert_eq!(config.oci.tags, vec!["{version}"]);
+        assert!(config.jira.base_url.is_none());
+        assert_eq!(config.jira.token_env, "JIRA_API_TOKEN");
+        assert!(config.sentry.org.is_none());
+        assert_eq!(config.sentry.environment, "production");
+        assert!(config.milestone.repository.is_none());
+        assert_eq!(config.milestone.forge, MilestoneForge::GitHub);
+        assert_eq!(config.milestone.next, BumpRule::Minor);
+        assert!(!config.sbom.enabled);
+        assert_eq!(config.sbom.formats, vec![SbomFormat::CycloneDx]);
+        assert!(config.sbom.provenance);
+        assert!(config.other.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_other_plugin_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [plugins.cargo]
+            publish = true
+
+            [plugins.acme]
+            endpoint = "https://acme.test"
+            retries = 2
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.plugins.cargo.publish);
+        assert!(config.plugins.get("cargo").is_none());
+        assert_eq!(
+            config.plugins.get("acme"),
+            Some(&serde_json::json!({ "endpoint": "https://acme.test", "retries": 2 }))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_sbom_config() {
+        let toml = r#"
+            [plugins.sbom]
+            enabled = true
+            source = "file:target/release/app"
+            formats = ["cyclonedx", "spdx"]
+            artifacts = ["target/release/app"]
+            source_uri = "git+https://github.com/acme/app"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let sbom = &config.plugins.sbom;
+        assert!(sbom.enabled);
+        assert_eq!(sbom.source, "file:target/release/app");
+        assert_eq!(sbom.formats, vec![SbomFormat::CycloneDx, SbomFormat::Spdx]);
+        assert_eq!(sbom.artifacts, Some(vec!["target/release/app".to_string()]));
+        assert_eq!(sbom.output_dir, "target/sbom");
+        assert!(sbom.provenance);
+    }
+
+    #[test]
+    fn test_deserialize_milestone_config() {
+        let toml = r#"
+            [plugins.milestone]
+            forge = "gitlab"
+            repository = "acme/platform/app"
+            title = "Release {tag}"
+            next = "patch"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let milestone = &config.plugins.milestone;
+        assert_eq!(milestone.forge, MilestoneForge::GitLab);
+        assert_eq!(milestone.repository.as_deref(), Some("acme/platform/app"));
+        assert!(milestone.token_env.is_none());
+        assert_eq!(milestone.title, "Release {tag}");
+        assert_eq!(milestone.next, BumpRule::Patch);
+    }
+
+    #[test]
+    fn test_deserialize_sentry_config() {
+        let toml = r#"
+            [plugins.sentry]
+            org = "acme"
+            projects = ["web", "api"]
+            release = "web@{version}"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let sentry = &config.plugins.sentry;
+        assert_eq!(sentry.org.as_deref(), Some("acme"));
+        assert_eq!(sentry.projects, vec!["web", "api"]);
+        assert_eq!(sentry.token_env, "SENTRY_AUTH_TOKEN");
+        assert_eq!(sentry.release, "web@{version}");
+        assert_eq!(sentry.environment, "production");
+    }
+
+    #[test]
+    fn test_deserialize_jira_config() {
+        let toml = r#"
+            [plugins.jira]
+            base_url = "https://acme.atlassian.net"
+            email = "release-bot@acme.com"
+            transition = "Released"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let jira = &config.plugins.jira;
+        assert_eq!(jira.base_url.as_deref(), Some("https://acme.atlassian.net"));
+        assert_eq!(jira.email.as_deref(), Some("release-bot@acme.com"));
+        assert_eq!(jira.token_env, "JIRA_API_TOKEN");
+        assert!(jira.comment.is_none());
+        assert_eq!(jira.transition.as_deref(), Some("Released"));
+    }
+
+    #[test]
+    fn test_deserialize_oci_config() {
+        let toml = r#"
+            [plugins.oci]
+            repository = "ghcr.io/acme/modules/vpc"
+            path = "modules/vpc"
+            tags = ["{version}", "latest"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let oci = &config.plugins.oci;
+        assert_eq!(oci.repository.as_deref(), Some("ghcr.io/acme/modules/vpc"));
+        assert_eq!(oci.path, "modules/vpc");
+        assert_eq!(oci.tags, vec!["{version}", "latest"]);
+        assert!(oci.artifact_type.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_jvm_config() {
+        let toml = r#"
+            [plugins.jvm]
+            publish = true
+            tool = "maven"
+            tasks = ["clean", "deploy"]
+            profiles = ["release"]
+            env = { MAVEN_OPTS = "-Xmx1g" }
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let jvm = &config.plugins.jvm;
+        assert!(jvm.publish);
+        assert_eq!(jvm.tool, Some(JvmBuildTool::Maven));
+        assert_eq!(jvm.tasks, vec!["clean", "deploy"]);
+        assert_eq!(jvm.profiles, vec!["release"]);
+        assert_eq!(jvm.env.get("MAVEN_OPTS"), Some(&"-Xmx1g".to_string()));
+    }
+
+    #[test]
+    fn test_deserialize_webhook_config() {
+        let toml = r#"
+            [plugins.webhook]
+            retries = 5
+
+            [[plugins.webhook.targets]]
+            url = "https://example.com/release"
+            secret_headers = { Authorization = "WEBHOOK_TOKEN" }
+            body = { text = "Released {tag}", commits = "{commits}" }
+
+            [[plugins.webhook.targets]]
+            url = "https://example.com/audit"
+            stages = ["pre_tag", "post_tag"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let webhook = &config.plugins.webhook;
+        assert_eq!(webhook.retries, 5);
+        assert_eq!(webhook.backoff, 1);
+        assert_eq!(webhook.targets[0].stages, vec![WebhookStage::PostTag]);
+        assert_eq!(
+            webhook.targets[0].secret_headers.get("Authorization"),
+            Some(&"WEBHOOK_TOKEN".to_string())
+        );
+        let body = webhook.targets[0].body.as_ref().unwrap();
+        assert_eq!(body["text"], "Released {tag}");
+        assert_eq!(
+            webhook.targets[1].stages,
+            vec![WebhookStage::PreTag, WebhookStage::PostTag]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_notify_config() {
+        let toml = r#"
+            [plugins.notify]
+            changelog_url = "https://github.com/acme/app/releases/tag/{tag}"
+
+            [[plugins.notify.channels]]
+            kind = "slack"
+            webhook_url_env = "SLACK_WEBHOOK_URL"
+
+            [[plugins.notify.channels]]
+            kind = "discord"
+            webhook_url = "https://discord.com/api/webhooks/1/abc"
+            template = "{tag} is out!"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let notify = &config.plugins.notify;
+        assert!(notify.template.is_none());
+        assert_eq!(notify.channels.len(), 2);
+        assert_eq!(notify.channels[0].kind, NotifyChannelKind::Slack);
+        assert_eq!(
+            notify.channels[0].webhook_url_env.as_deref(),
+            Some("SLACK_WEBHOOK_URL")
+        );
+        assert_eq!(notify.channels[1].kind, NotifyChannelKind::Discord);
+        assert_eq!(
+            notify.channels[1].template.as_deref(),
+            Some("{tag} is out!")
+        );
     }
 
     #[test]
@@ -342,6 +2165,166 @@ mod tests {
         assert_eq!(config.bumper.name, "semver"); // default
     }
 
+    #[test]
+    fn test_deserialize_parser_names() {
+        let toml = r#"
+            [parser]
+            names = ["gitmoji", "conventional", "regex"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.parser.names, ["gitmoji", "conventional", "regex"]);
+        assert_eq!(config.parser.name, "conventional"); // default
+    }
+
+    #[test]
+    fn test_deserialize_version_paths() {
+        let toml = r#"
+            [version]
+            paths = ["crates/foo/**", "Cargo.lock"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.version.paths, ["crates/foo/**", "Cargo.lock"]);
+    }
+
+    #[test]
+    fn test_deserialize_commit_traversal() {
+        let toml = r"
+            [version]
+            first_parent = true
+            skip_merges = true
+            max_commits = 500
+            cache = false
+        ";
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.version.first_parent);
+        assert!(config.version.skip_merges);
+        assert_eq!(config.version.max_commits, Some(500));
+        assert!(!config.version.cache);
+    }
+
+    #[test]
+    fn test_deserialize_push() {
+        let toml = r#"
+            [version]
+            push = true
+            remote = "upstream"
+            branch = "main"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.version.push);
+        assert_eq!(config.version.remote, "upstream");
+        assert_eq!(config.version.branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_deserialize_release_branches() {
+        let toml = r#"
+            [version]
+            release_branches = ["main", "release/*"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.version.release_branches, ["main", "release/*"]);
+    }
+
+    #[test]
+    fn test_branch_matches_exact() {
+        assert!(branch_matches("main", "main"));
+        assert!(!branch_matches("main", "main2"));
+        assert!(!branch_matches("main", "mai"));
+    }
+
+    #[test]
+    fn test_branch_matches_single_star() {
+        assert!(branch_matches("release/*", "release/1.x"));
+        assert!(branch_matches("release/*", "release/"));
+        assert!(!branch_matches("release/*", "release/1.x/hotfix"));
+        assert!(!branch_matches("release/*", "main"));
+        assert!(branch_matches("*-stable", "1.x-stable"));
+    }
+
+    #[test]
+    fn test_branch_matches_double_star() {
+        assert!(branch_matches("release/**", "release/1.x/hotfix"));
+        assert!(branch_matches("**", "feature/a/b"));
+        assert!(!branch_matches("release/**", "hotfix/1.x"));
+    }
+
+    #[test]
+    fn test_is_release_branch() {
+        let mut config = VersionConfig::default();
+        assert!(config.is_release_branch("anything"));
+
+        config.release_branches = vec!["main".to_string(), "release/*".to_string()];
+        assert!(config.is_release_branch("main"));
+        assert!(config.is_release_branch("release/1.x"));
+        assert!(!config.is_release_branch("feature/foo"));
+    }
+
+    #[test]
+    fn test_deserialize_channels() {
+        let toml = r#"
+            [[version.channel]]
+            branch = "next"
+            prerelease = "rc"
+
+            [[version.channel]]
+            branch = "beta/*"
+            prerelease = "beta"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.version.channels.len(), 2);
+
+        let channel = config.version.channel_for("next").unwrap();
+        assert_eq!(channel.prerelease, "rc");
+        let channel = config.version.channel_for("beta/2.0").unwrap();
+        assert_eq!(channel.prerelease, "beta");
+        assert!(config.version.channel_for("main").is_none());
+    }
+
+    #[test]
+    fn test_deserialize_custom_version_files() {
+        let toml = r#"
+            [version]
+            files = []
+
+            [[version.file]]
+            path = "src/version.py"
+            pattern = '__version__ = "{version}"'
+
+            [[version.file]]
+            path = "VERSION"
+            pattern = "^{version}$"
+
+            [[version.file]]
+            path = "chart/Chart.yaml"
+            key = "appVersion"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.version.files.is_empty());
+        assert_eq!(config.version.custom_files.len(), 3);
+        assert_eq!(config.version.custom_files[0].path, "src/version.py");
+        assert_eq!(
+            config.version.custom_files[0].pattern.as_deref(),
+            Some(r#"__version__ = "{version}""#)
+        );
+        assert_eq!(
+            config.version.custom_files[1].pattern.as_deref(),
+            Some("^{version}$")
+        );
+        assert_eq!(
+            config.version.custom_files[2].key.as_deref(),
+            Some("appVersion")
+        );
+        assert!(config.version.custom_files[2].pattern.is_none());
+    }
+
     #[test]
     fn test_deserialize_empty() {
         let toml = "";
@@ -427,6 +2410,122 @@ mod tests {
         assert!(config.parser.conventional_gitmoji.strict_emoji);
     }
 
+    #[test]
+    fn test_deserialize_gitmoji_mappings() {
+        let toml = r#"
+            [parser.conventional-gitmoji.mappings]
+            "🧿" = "fix"
+            "🎉" = "chore"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let mappings = &config.parser.conventional_gitmoji.mappings;
+        assert_eq!(mappings.get("🧿").map(String::as_str), Some("fix"));
+        assert_eq!(mappings.get("🎉").map(String::as_str), Some("chore"));
+        assert!(config.parser.conventional_gitmoji.infer_type_from_emoji);
+    }
+
+    #[test]
+    fn test_deserialize_jira_parser_config() {
+        let toml = r#"
+            [parser]
+            name = "jira"
+
+            [parser.jira]
+            projects = ["PROJ"]
+
+            [parser.jira.types]
+            fix = "fix"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.parser.jira.projects, vec!["PROJ"]);
+        assert_eq!(config.parser.jira.types["fix"], "fix");
+        assert_eq!(config.parser.jira.default_type, "chore");
+    }
+
+    #[test]
+    fn test_deserialize_normalize_config() {
+        let toml = r"
+            [parser.normalize]
+            merges = true
+            reverts = true
+        ";
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.parser.normalize.merges);
+        assert!(!config.parser.normalize.squashes);
+        assert!(config.parser.normalize.reverts);
+    }
+
+    #[test]
+    fn test_deserialize_scope_config() {
+        let toml = r#"
+            [changelog]
+            scope_case_fold = true
+            scopes = ["api", "frontend"]
+
+            [changelog.scope_aliases]
+            ui = "frontend"
+            webapp = "frontend"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.changelog.scope_aliases["ui"], "frontend");
+        assert!(config.changelog.scope_case_fold);
+        assert_eq!(config.changelog.scopes, vec!["api", "frontend"]);
+        assert_eq!(config.changelog.unknown_scope, "misc");
+    }
+
+    #[test]
+    fn test_deserialize_changelog_header() {
+        let toml = r##"
+            [changelog]
+            header = "# Release History\n"
+            header_file = ".github/changelog-header.md"
+        "##;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.changelog.header.as_deref(),
+            Some("# Release History\n")
+        );
+        assert_eq!(
+            config.changelog.header_file.as_deref(),
+            Some(".github/changelog-header.md")
+        );
+    }
+
+    #[test]
+    fn test_changelog_output_none() {
+        assert_eq!(
+            ChangelogConfig::default().output_file(),
+            Some("CHANGELOG.md")
+        );
+
+        let config: Config = toml::from_str("[changelog]\noutput = \"none\"\n").unwrap();
+        assert_eq!(config.changelog.output_file(), None);
+    }
+
+    #[test]
+    fn test_deserialize_changelog_outputs() {
+        let toml = r#"
+            [[changelog.outputs]]
+            formatter = "keepachangelog"
+            path = "CHANGELOG.md"
+
+            [[changelog.outputs]]
+            formatter = "json"
+            path = "dist/release.json"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.changelog.outputs.len(), 2);
+        assert_eq!(config.changelog.outputs[1].formatter, "json");
+        assert_eq!(config.changelog.outputs[1].path, "dist/release.json");
+        assert!(ChangelogConfig::default().outputs.is_empty());
+    }
+
     #[test]
     fn test_deserialize_regex_config() {
         let toml = r#"
@@ -454,6 +2553,55 @@ mod tests {
         assert_eq!(config.parser.regex.validation.get("type").unwrap().len(), 3);
     }
 
+    #[test]
+    fn test_deserialize_sandbox_config() {
+        let toml = r#"
+            [sandbox.my-hook]
+            allowed_commands = ["cargo"]
+            allowed_paths = ["dist", "CHANGELOG.md"]
+            network = false
+            timeout = 300
+            filesystem = true
+            allowed_hosts = ["api.github.com"]
+
+            [sandbox.other-hook]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let sandbox = config.sandbox.get("my-hook").unwrap();
+        assert_eq!(sandbox.allowed_commands, Some(vec!["cargo".to_string()]));
+        assert_eq!(sandbox.allowed_paths.len(), 2);
+        assert!(!sandbox.network);
+        assert_eq!(sandbox.timeout, Some(300));
+        assert!(sandbox.filesystem);
+        assert!(!sandbox.clocks);
+        assert_eq!(sandbox.allowed_hosts, ["api.github.com"]);
+
+        let other = config.sandbox.get("other-hook").unwrap();
+        assert!(other.allowed_commands.is_none());
+        assert!(other.network);
+        assert!(other.timeout.is_none());
+        assert!(!other.filesystem && !other.clocks && !other.random);
+        assert!(other.allowed_hosts.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_wasm_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.wasm.timeout, 30);
+        assert_eq!(config.wasm.max_memory, 256);
+
+        let toml = r"
+            [wasm]
+            timeout = 5
+            max_memory = 0
+        ";
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.wasm.timeout, 5);
+        assert_eq!(config.wasm.max_memory, 0);
+    }
+
     #[test]
     fn test_serialize_config() {
         let config = Config::default();
@@ -476,4 +2624,24 @@ mod tests {
         assert!(debug.contains("Config"));
         assert!(debug.contains("parser"));
     }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(Config::json_schema()).unwrap();
+        let properties = &schema["properties"];
+
+        for key in ["parser", "bumper", "hooks", "version", "changelog", "wasm"] {
+            assert!(properties.get(key).is_some(), "missing {key}");
+        }
+        assert_eq!(
+            schema["$defs"]["VersionConfig"]["properties"]["tag_prefix"]["default"],
+            "v"
+        );
+        assert!(schema["$defs"]["VersionConfig"]["properties"]["file"].is_object());
+        assert!(schema["$defs"]["ParserConfig"]["properties"]["conventional-gitmoji"].is_object());
+        assert_eq!(
+            schema["properties"]["profile"]["additionalProperties"]["$ref"],
+            "#"
+        );
+    }
 }
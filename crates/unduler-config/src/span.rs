@@ -0,0 +1,111 @@
+//! Source locations of configuration values.
+
+use std::ops::Range;
+
+/// Returns the byte range of the value of a dotted key in a configuration
+/// file, e.g. `parser.regex.pattern`.
+///
+/// Keys are looked up under their table header (`[parser.regex]`) or as
+/// dotted keys of a parent table. Values spanning several lines and inline
+/// tables are not looked into.
+#[must_use]
+pub fn value_span(content: &str, key: &str) -> Option<Range<usize>> {
+    let mut table = String::new();
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            table = normalize_key(trimmed.trim_matches(|c| c == '[' || c == ']'));
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let name = normalize_key(name);
+        let full_key = if table.is_empty() {
+            name
+        } else {
+            format!("{table}.{name}")
+        };
+        if full_key != key {
+            continue;
+        }
+
+        let value_start = start + line.len() - value.len();
+        let leading = value.len() - value.trim_start().len();
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        return Some(value_start + leading..value_start + leading + value.len());
+    }
+
+    None
+}
+
+/// Removes whitespace and quotes around the parts of a dotted key.
+fn normalize_key(key: &str) -> String {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"'))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[parser]
+name = "regex"
+
+[parser.regex]
+pattern = "^(?P<type>\w+"
+
+[bumper]
+rules.feat = "minor"
+"#;
+
+    fn value_at<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+        value_span(content, key).map(|span| &content[span])
+    }
+
+    #[test]
+    fn test_value_under_table() {
+        assert_eq!(value_at(CONFIG, "parser.name"), Some(r#""regex""#));
+        assert_eq!(
+            value_at(CONFIG, "parser.regex.pattern"),
+            Some(r#""^(?P<type>\w+""#)
+        );
+    }
+
+    #[test]
+    fn test_dotted_key() {
+        assert_eq!(value_at(CONFIG, "bumper.rules.feat"), Some(r#""minor""#));
+    }
+
+    #[test]
+    fn test_root_key() {
+        assert_eq!(value_at("version = 1\n", "version"), Some("1"));
+    }
+
+    #[test]
+    fn test_missing_key() {
+        assert_eq!(value_span(CONFIG, "parser.pattern"), None);
+        assert_eq!(value_span(CONFIG, "changelog.output"), None);
+    }
+
+    #[test]
+    fn test_quoted_table() {
+        let content = "[ plugins.\"github-release\" ]\ndraft = true\n";
+        assert_eq!(
+            value_at(content, "plugins.github-release.draft"),
+            Some("true")
+        );
+    }
+}
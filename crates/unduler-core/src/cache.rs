@@ -0,0 +1,243 @@
+//! On-disk cache of the commits parsed since the latest tag.
+//!
+//! Parsing the history since the latest release is the slowest part of
+//! `next`, `bump` and `release` on large repositories. The parsed commits
+//! are cached under `.unduler/cache/`, keyed by the HEAD and tag commits, by
+//! a fingerprint of the configuration and by the parsing inputs of the
+//! pipeline, so any new commit, tag, configuration or parser change
+//! invalidates the cache. Commands changing nothing only read it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use unduler_config::Config;
+
+use crate::release::ParsedRange;
+
+/// Directory of the cache, relative to the repository root.
+pub const CACHE_DIR: &str = ".unduler/cache";
+
+/// Cache file of the parsed commits.
+const COMMITS_FILE: &str = "commits.json";
+
+/// Cached commits of a range, with the key they were parsed for.
+#[derive(Serialize, Deserialize)]
+struct Entry<R> {
+    key: String,
+    range: R,
+}
+
+/// On-disk cache of the commits parsed since a tag.
+///
+/// Only the latest range is kept, so the cache does not grow over time.
+#[derive(Debug, Clone)]
+pub struct CommitCache {
+    dir: PathBuf,
+    fingerprint: String,
+    read_only: bool,
+}
+
+impl CommitCache {
+    /// Creates a cache in `dir`, whose entries are only valid for the given
+    /// fingerprint of the parsing configuration.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>, fingerprint: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            fingerprint: fingerprint.into(),
+            read_only: false,
+        }
+    }
+
+    /// Sets whether the cache is only read, leaving the repository
+    /// untouched.
+    #[must_use]
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Creates the cache of a repository, invalidated when the configuration
+    /// or the version of unduler changes.
+    #[must_use]
+    pub fn for_config(root: &Path, config: &Config) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION"));
+        // Maps are hashed by sorted keys, their serialization order is not stable
+        hash_value(
+            &mut hasher,
+            &serde_json::to_value(config).unwrap_or_default(),
+        );
+        Self::new(root.join(CACHE_DIR), format!("{:x}", hasher.finalize()))
+    }
+
+    /// Returns the directory of the cache.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the commits parsed between a tag and HEAD by a pipeline with
+    /// the given parsing inputs, if cached.
+    pub(crate) fn load(&self, head: &str, tag: Option<&str>, parsing: &str) -> Option<ParsedRange> {
+        let content = fs::read_to_string(self.dir.join(COMMITS_FILE)).ok()?;
+        let entry: Entry<ParsedRange> = serde_json::from_str(&content)
+            .inspect_err(|e| debug!("ignoring invalid commit cache: {e}"))
+            .ok()?;
+        (entry.key == self.key(head, tag, parsing)).then_some(entry.range)
+    }
+
+    /// Caches the commits parsed between a tag and HEAD, replacing the
+    /// cached range, unless the cache is read-only.
+    ///
+    /// The cache directory ignores its own content, so it is neither reported
+    /// as a change nor included in release commits.
+    pub(crate) fn store(&self, head: &str, tag: Option<&str>, parsing: &str, range: &ParsedRange) {
+        if self.read_only {
+            return;
+        }
+        let entry = Entry {
+            key: self.key(head, tag, parsing),
+            range,
+        };
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|()| {
+                let gitignore = self.dir.join(".gitignore");
+                if gitignore.exists() {
+                    Ok(())
+                } else {
+                    fs::write(gitignore, "*\n")
+                }
+            })
+            .and_then(|()| {
+                let content = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+                fs::write(self.dir.join(COMMITS_FILE), content)
+            });
+        if let Err(e) = result {
+            debug!("failed to write commit cache: {e}");
+        }
+    }
+
+    /// Returns the key of the commits between a tag and HEAD.
+    fn key(&self, head: &str, tag: Option<&str>, parsing: &str) -> String {
+        let mut hasher = Sha256::new();
+        for part in [self.fingerprint.as_str(), head, tag.unwrap_or(""), parsing] {
+            hasher.update(part);
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Hashes a JSON value, object keys in sorted order.
+fn hash_value(hasher: &mut Sha256, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            hasher.update("{");
+            for (key, value) in entries {
+                hasher.update(key);
+                hasher.update(":");
+                hash_value(hasher, value);
+                hasher.update(",");
+            }
+            hasher.update("}");
+        }
+        Value::Array(values) => {
+            hasher.update("[");
+            for value in values {
+                hash_value(hasher, value);
+                hasher.update(",");
+            }
+            hasher.update("]");
+        }
+        value => hasher.update(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use unduler_commit::{ParsedCommit, RawCommit};
+
+    fn range() -> ParsedRange {
+        let mut range = ParsedRange::default();
+        range.push(
+            &RawCommit::new("aaa", "feat: add", "author", "a@x.org", Utc::now()),
+            Some(ParsedCommit::builder("aaa", "feat").build()),
+        );
+        range
+    }
+
+    #[test]
+    fn test_load_stored_range() {
+        let temp = TempDir::new().unwrap();
+        let cache = CommitCache::new(temp.path().join("cache"), "config");
+        assert!(cache.load("head", Some("v1.0.0"), "parser").is_none());
+
+        cache.store("head", Some("v1.0.0"), "parser", &range());
+        let cached = cache.load("head", Some("v1.0.0"), "parser").unwrap();
+        assert_eq!(cached.commits[0].hash, "aaa");
+        assert_eq!(cached.raw_count, 1);
+        assert!(cache.dir().join(".gitignore").exists());
+    }
+
+    #[test]
+    fn test_read_only_cache() {
+        let temp = TempDir::new().unwrap();
+        let cache = CommitCache::new(temp.path().join("cache"), "config");
+        cache
+            .clone()
+            .with_read_only(true)
+            .store("head", None, "parser", &range());
+        assert!(!cache.dir().exists());
+
+        cache.store("head", None, "parser", &range());
+        let read_only = cache.with_read_only(true);
+        assert!(read_only.load("head", None, "parser").is_some());
+    }
+
+    #[test]
+    fn test_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let cache = CommitCache::new(temp.path(), "config");
+        cache.store("head", Some("v1.0.0"), "parser", &range());
+
+        assert!(cache.load("new-head", Some("v1.0.0"), "parser").is_none());
+        assert!(cache.load("head", Some("v1.1.0"), "parser").is_none());
+        assert!(cache.load("head", None, "parser").is_none());
+        let other = CommitCache::new(temp.path(), "other config");
+        assert!(other.load("head", Some("v1.0.0"), "parser").is_none());
+        assert!(
+            cache
+                .load("head", Some("v1.0.0"), "highlighting parser")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_follows_config() {
+        let root = Path::new("/repo");
+        let config = Config::default();
+        let cache = CommitCache::for_config(root, &config);
+        assert_eq!(cache.dir(), Path::new("/repo/.unduler/cache"));
+        assert_eq!(
+            cache.fingerprint,
+            CommitCache::for_config(root, &config).fingerprint
+        );
+
+        let mut changed = config.clone();
+        changed.version.skip_merges = !changed.version.skip_merges;
+        assert_ne!(
+            cache.fingerprint,
+            CommitCache::for_config(root, &changed).fingerprint
+        );
+    }
+}
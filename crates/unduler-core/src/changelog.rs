@@ -0,0 +1,1172 @@
+//! Changelog file management.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use semver::Version;
+use unduler_config::{ChangelogInsertConfig, Config, InsertFormat, ReleaseFilesConfig};
+use unduler_plugin::{
+    FormatterConfig, FormatterSection, ListBullet, MessageCase, Release, TrailingPunctuation,
+};
+
+use crate::locale::locale_labels;
+use crate::{ChangelogOutput, CoreError, CoreResult, FileChange};
+
+/// Header written at the top of a new changelog.
+pub const CHANGELOG_HEADER: &str = "# Changelog\n\n\
+     All notable changes to this project will be documented in this file.\n\n\
+     The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
+     and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n";
+
+/// Returns the header written at the top of a new changelog: the content of
+/// `header_file`, the `header` text, or [`CHANGELOG_HEADER`].
+///
+/// A custom header is separated from the first release by a blank line, an
+/// empty one writing no header.
+///
+/// # Errors
+///
+/// Returns an error if the header file cannot be read.
+pub fn changelog_header(header: Option<&str>, header_file: Option<&Path>) -> CoreResult<String> {
+    let header = match (header_file, header) {
+        (Some(path), _) => {
+            fs::read_to_string(path).map_err(|source| CoreError::ChangelogHeader {
+                path: path.to_path_buf(),
+                source,
+            })?
+        }
+        (None, Some(header)) => header.to_string(),
+        (None, None) => return Ok(CHANGELOG_HEADER.to_string()),
+    };
+
+    let header = header.trim_end();
+    if header.is_empty() {
+        Ok(String::new())
+    } else {
+        Ok(format!("{header}\n\n"))
+    }
+}
+
+/// Title of the section holding unreleased changes.
+const UNRELEASED: &str = "Unreleased";
+
+/// Returns the regex matching the heading of a `debian/changelog` entry,
+/// capturing its version, e.g. `app (1.2.0-1) unstable; urgency=medium`.
+fn debian_heading_regex() -> Regex {
+    Regex::new(r"(?m)^[a-z0-9][a-z0-9+.-]+ \(([^)\s]+)\) ").expect("invalid regex")
+}
+
+/// A `## ` section of a changelog.
+struct Section {
+    /// Title of the section, without brackets (e.g. `1.2.3` or `Unreleased`).
+    title: String,
+    /// Byte range of the section, from its heading to the next one.
+    range: Range<usize>,
+}
+
+impl Section {
+    /// Returns whether this section documents the given version.
+    fn is_version(&self, version: &Version) -> bool {
+        let title = self.title.strip_prefix('v').unwrap_or(&self.title);
+        Version::parse(title).is_ok_and(|title| title == *version)
+    }
+
+    /// Returns whether this section holds unreleased changes.
+    fn is_unreleased(&self) -> bool {
+        self.title.eq_ignore_ascii_case(UNRELEASED)
+    }
+}
+
+/// Returns the `## ` sections of a changelog.
+fn sections(content: &str) -> Vec<Section> {
+    let heading_regex = Regex::new(r"(?m)^## +\[?([^\]\s]+)\]?").expect("invalid regex");
+
+    let headings: Vec<_> = heading_regex
+        .captures_iter(content)
+        .map(|captures| {
+            let start = captures.get(0).expect("group 0 always matches").start();
+            (captures[1].to_string(), start)
+        })
+        .collect();
+
+    headings
+        .iter()
+        .enumerate()
+        .map(|(index, (title, start))| {
+            let end = headings
+                .get(index + 1)
+                .map_or(content.len(), |(_, next)| *next);
+            Section {
+                title: title.clone(),
+                range: *start..end,
+            }
+        })
+        .collect()
+}
+
+/// Replaces a range of the changelog with a section.
+fn replace_section(existing: &str, range: Range<usize>, section: &str) -> String {
+    let separator = if range.end == existing.len() {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!(
+        "{}{section}{separator}{}",
+        &existing[..range.start],
+        &existing[range.end..]
+    )
+}
+
+/// Inserts an entry into `debian/changelog` content.
+///
+/// An entry for the same Debian version is replaced, otherwise the entry is
+/// inserted above the latest one. Debian changelogs have no header.
+fn insert_debian_entry(existing: &str, entry: &str, version: &str) -> String {
+    let entry = entry.trim_end();
+    let heading_regex = debian_heading_regex();
+    let headings: Vec<_> = heading_regex
+        .captures_iter(existing)
+        .map(|captures| {
+            let start = captures.get(0).expect("group 0 always matches").start();
+            (captures[1].to_string(), start)
+        })
+        .collect();
+
+    let current = headings.iter().position(|(v, _)| v == version);
+    match current {
+        Some(index) => {
+            let start = headings[index].1;
+            let end = headings
+                .get(index + 1)
+                .map_or(existing.len(), |(_, next)| *next);
+            replace_section(existing, start..end, entry)
+        }
+        None if existing.trim().is_empty() => format!("{entry}\n"),
+        None => format!("{entry}\n\n{}", existing.trim_start()),
+    }
+}
+
+/// Inserts a release section into changelog content.
+///
+/// If a section for the same version already exists, it is replaced, so
+/// that running a release again does not duplicate entries. Otherwise the
+/// section replaces the `Unreleased` section, whose changes are now
+/// released, or is inserted above the latest release. The header is added
+/// to empty changelogs, content above the first release, such as a custom
+/// header, being kept otherwise.
+///
+/// Sections in the `debian/changelog` format are inserted as Debian entries
+/// instead, without header.
+#[must_use]
+pub fn insert_release_section(
+    existing: &str,
+    section: &str,
+    version: &Version,
+    header: &str,
+) -> String {
+    let heading_regex = debian_heading_regex();
+    if let Some(captures) = heading_regex.captures(section)
+        && captures.get(0).is_some_and(|heading| heading.start() == 0)
+    {
+        return insert_debian_entry(existing, section, &captures[1]);
+    }
+
+    if existing.trim().is_empty() {
+        return format!("{header}{section}");
+    }
+
+    let sections = sections(existing);
+    let section = section.trim_end();
+
+    if let Some(current) = sections.iter().find(|s| s.is_version(version)) {
+        let range = current.range.clone();
+        let unreleased = sections
+            .iter()
+            .find(|s| s.is_unreleased())
+            .map(|s| s.range.clone());
+        let content = replace_section(existing, range, section);
+
+        // Drop a stale Unreleased section above the replaced release
+        return match unreleased {
+            Some(unreleased) if unreleased.end <= current.range.start => {
+                format!(
+                    "{}{}",
+                    &content[..unreleased.start],
+                    &content[unreleased.end..]
+                )
+            }
+            _ => content,
+        };
+    }
+
+    if let Some(unreleased) = sections.iter().find(|s| s.is_unreleased()) {
+        return replace_section(existing, unreleased.range.clone(), section);
+    }
+
+    if let Some(latest) = sections.first() {
+        let (before, after) = existing.split_at(latest.range.start);
+        return format!("{before}{section}\n\n{after}");
+    }
+
+    format!("{}\n\n{section}\n", existing.trim_end())
+}
+
+/// Returns the regex matching the `## ` heading of a release section.
+fn release_heading_regex() -> Regex {
+    Regex::new(r"(?m)^## .*$").expect("invalid regex")
+}
+
+/// Turns a rendered release section into an `Unreleased` section.
+///
+/// The release heading is replaced by `## [Unreleased]`, and a comparison
+/// link is appended when a URL is given (e.g. `.../compare/v1.0.0...HEAD`).
+#[must_use]
+pub fn unreleased_section(section: &str, compare_url: Option<&str>) -> String {
+    let body = release_heading_regex()
+        .find(section)
+        .map_or(section, |heading| &section[heading.end()..]);
+
+    let mut output = format!("## [{UNRELEASED}]{}", body.trim_end());
+    if let Some(url) = compare_url {
+        let _ = write!(output, "\n\n[{UNRELEASED}]: {url}");
+    }
+    output.push('\n');
+    output
+}
+
+/// Inserts an `Unreleased` section into changelog content.
+///
+/// An existing `Unreleased` section is replaced, otherwise the section is
+/// inserted above the latest release. Released sections are never modified.
+/// The header is added to empty changelogs.
+#[must_use]
+pub fn insert_unreleased_section(existing: &str, section: &str, header: &str) -> String {
+    if existing.trim().is_empty() {
+        return format!("{header}{section}");
+    }
+
+    let sections = sections(existing);
+    let section = section.trim_end();
+
+    if let Some(unreleased) = sections.iter().find(|s| s.is_unreleased()) {
+        return replace_section(existing, unreleased.range.clone(), section);
+    }
+
+    if let Some(latest) = sections.first() {
+        let (before, after) = existing.split_at(latest.range.start);
+        return format!("{before}{section}\n\n{after}");
+    }
+
+    format!("{}\n\n{section}\n", existing.trim_end())
+}
+
+/// Reads a changelog file, treating a missing file as empty.
+fn read_changelog(path: &Path) -> CoreResult<String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes a release section to a changelog file.
+///
+/// The file is created with the header if it does not exist. See
+/// [`insert_release_section`] for how existing content is handled.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or written.
+pub fn update_changelog(
+    path: &Path,
+    section: &str,
+    version: &Version,
+    header: &str,
+) -> CoreResult<()> {
+    changelog_change(path, section, version, header)?.write()?;
+    Ok(())
+}
+
+/// Returns the change of a changelog file writing a release section,
+/// without writing it.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn changelog_change(
+    path: &Path,
+    section: &str,
+    version: &Version,
+    header: &str,
+) -> CoreResult<FileChange> {
+    let existing = read_changelog(path)?;
+    let after = insert_release_section(&existing, section, version, header);
+    Ok(FileChange::read(path, after)?)
+}
+
+/// Writes an `Unreleased` section to a changelog file.
+///
+/// The file is created with the header if it does not exist. See
+/// [`insert_unreleased_section`] for how existing content is handled.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or written.
+pub fn update_unreleased_changelog(path: &Path, section: &str, header: &str) -> CoreResult<()> {
+    let existing = read_changelog(path)?;
+    fs::write(path, insert_unreleased_section(&existing, section, header))?;
+    Ok(())
+}
+
+/// Returns a release section without its `## ` heading.
+#[must_use]
+pub fn section_body(section: &str) -> &str {
+    match section.strip_prefix("## ") {
+        Some(rest) => rest
+            .split_once('\n')
+            .map_or("", |(_, body)| body.trim_start()),
+        None => section,
+    }
+}
+
+/// Inserts a release section after a marker.
+///
+/// A section for the same version after the marker is replaced, otherwise
+/// the section is inserted right after the marker, above older releases.
+/// Returns `None` when the marker is missing.
+#[must_use]
+pub fn insert_at_marker(
+    existing: &str,
+    marker: &str,
+    section: &str,
+    version: &Version,
+) -> Option<String> {
+    let marker_end = existing.find(marker)? + marker.len();
+    let (head, tail) = existing.split_at(marker_end);
+    let section = section.trim_end();
+
+    if let Some(current) = sections(tail).iter().find(|s| s.is_version(version)) {
+        return Some(format!(
+            "{head}{}",
+            replace_section(tail, current.range.clone(), section)
+        ));
+    }
+
+    let tail = tail.trim_start();
+    if tail.is_empty() {
+        Some(format!("{head}\n\n{section}\n"))
+    } else {
+        Some(format!("{head}\n\n{section}\n\n{tail}"))
+    }
+}
+
+/// Replaces the content between two markers.
+///
+/// Returns `None` when a marker is missing.
+#[must_use]
+pub fn replace_between_markers(
+    existing: &str,
+    start_marker: &str,
+    end_marker: &str,
+    content: &str,
+) -> Option<String> {
+    let start = existing.find(start_marker)? + start_marker.len();
+    let end = start + existing[start..].find(end_marker)?;
+    Some(format!(
+        "{}\n\n{}\n\n{}",
+        &existing[..start],
+        content.trim(),
+        &existing[end..]
+    ))
+}
+
+/// Inserts a release section into the files listed in
+/// `changelog.inserts`.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or written, or lacks its
+/// markers.
+pub fn update_inserts(
+    inserts: &[ChangelogInsertConfig],
+    section: &str,
+    version: &Version,
+) -> CoreResult<()> {
+    for change in insert_changes(inserts, section, version)? {
+        change.write()?;
+    }
+    Ok(())
+}
+
+/// Returns the changes of the files of changelog outputs rendering a
+/// release with their formatters, without writing them.
+///
+/// Markdown and Debian outputs get the release inserted like the changelog,
+/// under the header when created, other outputs, such as JSON, are replaced
+/// by the rendered release.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read.
+pub fn output_changes(
+    outputs: &[ChangelogOutput],
+    release: &Release,
+    config: &FormatterConfig,
+    header: &str,
+) -> CoreResult<Vec<FileChange>> {
+    outputs
+        .iter()
+        .map(|output| {
+            let rendered = output.formatter.format(release, config);
+            match output.formatter.extension() {
+                "md" | "" => changelog_change(&output.path, &rendered, &release.version, header),
+                _ => Ok(FileChange::read(&output.path, rendered)?),
+            }
+        })
+        .collect()
+}
+
+/// Returns the changes of the files listed in `changelog.inserts` inserting
+/// a release section, without writing them.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or lacks its markers.
+pub fn insert_changes(
+    inserts: &[ChangelogInsertConfig],
+    section: &str,
+    version: &Version,
+) -> CoreResult<Vec<FileChange>> {
+    let mut changes = Vec::new();
+    for insert in inserts {
+        let path = Path::new(&insert.path);
+        let existing = fs::read_to_string(path)?;
+        let content = match insert.format {
+            InsertFormat::Section => section,
+            InsertFormat::Body => section_body(section),
+        };
+
+        let updated =
+            match &insert.end_marker {
+                Some(end_marker) => {
+                    replace_between_markers(&existing, &insert.marker, end_marker, content)
+                        .ok_or_else(|| CoreError::MarkerNotFound {
+                            path: path.to_path_buf(),
+                            marker: if existing.contains(&insert.marker) {
+                                end_marker.clone()
+                            } else {
+                                insert.marker.clone()
+                            },
+                        })?
+                }
+                None => insert_at_marker(&existing, &insert.marker, content, version).ok_or_else(
+                    || CoreError::MarkerNotFound {
+                        path: path.to_path_buf(),
+                        marker: insert.marker.clone(),
+                    },
+                )?,
+            };
+        changes.push(FileChange {
+            path: path.to_path_buf(),
+            before: Some(existing),
+            after: updated,
+        });
+    }
+    Ok(changes)
+}
+
+/// Returns the path of the file of a release in `changelog.releases`.
+#[must_use]
+pub fn release_file_path(config: &ReleaseFilesConfig, version: &Version) -> PathBuf {
+    Path::new(&config.dir).join(config.file_name.replace("{version}", &version.to_string()))
+}
+
+/// Renders the index of release files, newest first.
+///
+/// File names not matching the `file_name` template are skipped.
+fn release_index<'a>(
+    config: &ReleaseFilesConfig,
+    file_names: impl Iterator<Item = &'a str>,
+) -> String {
+    let (prefix, suffix) = config
+        .file_name
+        .split_once("{version}")
+        .unwrap_or((&config.file_name, ""));
+    let mut releases: Vec<_> = file_names
+        .filter_map(|name| {
+            let version = name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((Version::parse(version).ok()?, name))
+        })
+        .collect();
+    releases.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut index = String::from("# Releases\n\n");
+    for (version, name) in releases {
+        let _ = writeln!(index, "- [{version}]({name})");
+    }
+    index
+}
+
+/// Writes a release section to its own file, replacing the file of the
+/// same version, and regenerates the index of the release files.
+///
+/// Returns the path of the release file.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or the files cannot be
+/// written.
+pub fn write_release_file(
+    config: &ReleaseFilesConfig,
+    section: &str,
+    version: &Version,
+) -> CoreResult<PathBuf> {
+    for change in release_file_changes(config, section, version)? {
+        change.write()?;
+    }
+    Ok(release_file_path(config, version))
+}
+
+/// Returns the changes of the release file and of the index of the release
+/// files, without writing them.
+///
+/// # Errors
+///
+/// Returns an error if the directory or the files cannot be read.
+pub fn release_file_changes(
+    config: &ReleaseFilesConfig,
+    section: &str,
+    version: &Version,
+) -> CoreResult<Vec<FileChange>> {
+    let path = release_file_path(config, version);
+    let release = FileChange::read(&path, format!("{}\n", section.trim_end()))?;
+
+    let mut file_names = Vec::new();
+    match fs::read_dir(&config.dir) {
+        Ok(entries) => {
+            for entry in entries {
+                file_names.push(entry?.file_name().to_string_lossy().into_owned());
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    if let Some(name) = path.file_name().map(|name| name.to_string_lossy())
+        && !file_names.iter().any(|file_name| *file_name == name)
+    {
+        file_names.push(name.into_owned());
+    }
+    let index = release_index(config, file_names.iter().map(String::as_str));
+    let index = FileChange::read(Path::new(&config.dir).join(&config.index), index)?;
+
+    Ok(vec![release, index])
+}
+
+/// Returns the options passed to the changelog formatter.
+///
+/// Section headings come from the built-in translations of
+/// `formatter.locale`, overridden by `formatter.labels`.
+#[must_use]
+pub fn formatter_config(config: &Config) -> FormatterConfig {
+    let mut type_labels: HashMap<String, String> = config
+        .formatter
+        .locale
+        .as_deref()
+        .and_then(locale_labels)
+        .unwrap_or_default()
+        .iter()
+        .map(|(key, label)| ((*key).to_string(), (*label).to_string()))
+        .collect();
+    type_labels.extend(config.formatter.labels.clone());
+
+    FormatterConfig {
+        link_references: config.formatter.link_references,
+        closed_issues: config.formatter.closed_issues,
+        include_authors: config.formatter.include_authors,
+        contributors: config.formatter.contributors,
+        summary: config.formatter.summary,
+        highlights: config.formatter.highlights,
+        breaking_changes: config.formatter.breaking_changes,
+        show_emoji: config.formatter.show_emoji,
+        deduplicate: config.formatter.deduplicate,
+        line_width: config.formatter.line_width,
+        message_case: match config.formatter.message_case {
+            unduler_config::MessageCase::Preserve => MessageCase::Preserve,
+            unduler_config::MessageCase::Sentence => MessageCase::Sentence,
+        },
+        trailing_punctuation: match config.formatter.trailing_punctuation {
+            unduler_config::TrailingPunctuation::Preserve => TrailingPunctuation::Preserve,
+            unduler_config::TrailingPunctuation::Strip => TrailingPunctuation::Strip,
+            unduler_config::TrailingPunctuation::Period => TrailingPunctuation::Period,
+        },
+        bullet: match config.formatter.bullet {
+            unduler_config::ListBullet::Dash => ListBullet::Dash,
+            unduler_config::ListBullet::Asterisk => ListBullet::Asterisk,
+        },
+        type_labels,
+        sections: config
+            .formatter
+            .sections
+            .iter()
+            .map(|section| FormatterSection {
+                types: section.types.clone(),
+                title: section.title.clone(),
+            })
+            .collect(),
+        hide_types: config.formatter.hide_types.clone(),
+        ..FormatterConfig::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_config::FormatterSectionConfig;
+
+    use super::*;
+
+    const SECTION_1_1_0: &str = "## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n\n";
+
+    fn changelog(sections: &str) -> String {
+        format!("{CHANGELOG_HEADER}{sections}")
+    }
+
+    #[test]
+    fn test_formatter_config() {
+        let mut config = Config::default();
+        let formatter = formatter_config(&config);
+        assert!(formatter.link_references);
+        assert!(formatter.closed_issues);
+
+        config.formatter.closed_issues = false;
+        assert!(!formatter_config(&config).closed_issues);
+    }
+
+    #[test]
+    fn test_formatter_config_locale() {
+        let mut config = Config::default();
+        assert!(formatter_config(&config).type_labels.is_empty());
+
+        config.formatter.locale = Some("fr".to_string());
+        config
+            .formatter
+            .labels
+            .insert("chore".to_string(), "Entretien".to_string());
+        let formatter = formatter_config(&config);
+        assert_eq!(formatter.type_labels["feat"], "Ajouté");
+        assert_eq!(formatter.type_labels["closed_issues"], "Tickets fermés");
+        assert_eq!(formatter.type_labels["chore"], "Entretien");
+    }
+
+    #[test]
+    fn test_formatter_config_style() {
+        let mut config = Config::default();
+        config.formatter.line_width = Some(80);
+        config.formatter.message_case = unduler_config::MessageCase::Sentence;
+        config.formatter.bullet = unduler_config::ListBullet::Asterisk;
+
+        let formatter = formatter_config(&config);
+        assert_eq!(formatter.line_width, Some(80));
+        assert_eq!(formatter.message_case, MessageCase::Sentence);
+        assert_eq!(
+            formatter.trailing_punctuation,
+            TrailingPunctuation::Preserve
+        );
+        assert_eq!(formatter.bullet, ListBullet::Asterisk);
+    }
+
+    #[test]
+    fn test_formatter_config_sections() {
+        let mut config = Config::default();
+        config.formatter.sections = vec![FormatterSectionConfig {
+            types: vec!["build".to_string(), "ci".to_string()],
+            title: "Build System".to_string(),
+        }];
+        config.formatter.hide_types = vec!["chore".to_string()];
+
+        let formatter = formatter_config(&config);
+        assert_eq!(
+            formatter.sections,
+            [FormatterSection {
+                types: vec!["build".to_string(), "ci".to_string()],
+                title: "Build System".to_string(),
+            }]
+        );
+        assert_eq!(formatter.hide_types, ["chore"]);
+    }
+
+    #[test]
+    fn test_insert_into_empty_changelog() {
+        let content =
+            insert_release_section("", SECTION_1_1_0, &Version::new(1, 1, 0), CHANGELOG_HEADER);
+        assert_eq!(content, changelog(SECTION_1_1_0));
+    }
+
+    #[test]
+    fn test_insert_above_latest_release() {
+        let existing = changelog("## [1.0.0] - 2024-01-01\n\n### Added\n\n- initial\n");
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert_eq!(
+            content,
+            changelog(
+                "## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n\n\
+                 ## [1.0.0] - 2024-01-01\n\n### Added\n\n- initial\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_insert_with_custom_header() {
+        let header = "# History\n\n";
+        let content = insert_release_section("", SECTION_1_1_0, &Version::new(1, 1, 0), header);
+        assert_eq!(content, format!("{header}{SECTION_1_1_0}"));
+
+        let content = insert_unreleased_section("", "## [Unreleased]\n", "");
+        assert_eq!(content, "## [Unreleased]\n");
+    }
+
+    #[test]
+    fn test_insert_keeps_existing_header() {
+        let existing = "# Release Notes\n\nSee the docs for upgrade guides.\n\n\
+                        ## [1.0.0] - 2024-01-01\n\n- initial\n";
+
+        let content = insert_release_section(
+            existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+        let content = insert_release_section(
+            &content,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert!(
+            content
+                .starts_with("# Release Notes\n\nSee the docs for upgrade guides.\n\n## [1.1.0]")
+        );
+        assert!(!content.contains("Keep a Changelog"));
+    }
+
+    #[test]
+    fn test_changelog_header() {
+        assert_eq!(changelog_header(None, None).unwrap(), CHANGELOG_HEADER);
+        assert_eq!(
+            changelog_header(Some("# History\n"), None).unwrap(),
+            "# History\n\n"
+        );
+        assert_eq!(changelog_header(Some(""), None).unwrap(), "");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("header.md");
+        fs::write(&path, "# Changes\n\nAll releases.\n").unwrap();
+        assert_eq!(
+            changelog_header(Some("# History"), Some(&path)).unwrap(),
+            "# Changes\n\nAll releases.\n\n"
+        );
+
+        let missing = dir.path().join("missing.md");
+        assert!(matches!(
+            changelog_header(None, Some(&missing)),
+            Err(CoreError::ChangelogHeader { .. })
+        ));
+    }
+
+    #[test]
+    fn test_insert_is_idempotent() {
+        let existing = changelog("## [1.0.0] - 2024-01-01\n\n### Added\n\n- initial\n");
+        let version = Version::new(1, 1, 0);
+
+        let once = insert_release_section(&existing, SECTION_1_1_0, &version, CHANGELOG_HEADER);
+        let twice = insert_release_section(&once, SECTION_1_1_0, &version, CHANGELOG_HEADER);
+
+        assert_eq!(once, twice);
+        assert_eq!(twice.matches("## [1.1.0]").count(), 1);
+    }
+
+    #[test]
+    fn test_replace_existing_version_section() {
+        let existing = changelog(
+            "## [1.1.0] - 2024-01-31\n\n### Fixed\n\n- partial release\n\n\
+             ## [1.0.0] - 2024-01-01\n\n- initial\n",
+        );
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert!(!content.contains("partial release"));
+        assert!(content.contains("- new feature\n\n## [1.0.0]"));
+        assert!(content.ends_with("- initial\n"));
+    }
+
+    #[test]
+    fn test_replace_last_section() {
+        let existing = changelog("## [1.1.0] - 2024-01-31\n\n- partial release\n");
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert_eq!(
+            content,
+            changelog("## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n")
+        );
+    }
+
+    #[test]
+    fn test_release_replaces_unreleased_section() {
+        let existing = changelog(
+            "## [Unreleased]\n\n- work in progress\n\n[Unreleased]: https://example.com/compare/v1.0.0...HEAD\n\n\
+             ## [1.0.0] - 2024-01-01\n\n- initial\n",
+        );
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert_eq!(
+            content,
+            changelog(
+                "## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n\n\
+                 ## [1.0.0] - 2024-01-01\n\n- initial\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_release_replaces_unreleased_only_section() {
+        let existing = changelog("## [Unreleased]\n\n- work in progress\n");
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert_eq!(
+            content,
+            changelog("## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n")
+        );
+    }
+
+    #[test]
+    fn test_release_rerun_drops_stale_unreleased_section() {
+        let existing =
+            changelog("## [Unreleased]\n\n- new feature\n\n## [1.1.0] - 2024-01-31\n\n- partial\n");
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert_eq!(
+            content,
+            changelog("## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n")
+        );
+    }
+
+    #[test]
+    fn test_unreleased_section() {
+        let section = unreleased_section(
+            SECTION_1_1_0,
+            Some("https://github.com/owner/repo/compare/v1.0.0...HEAD"),
+        );
+        assert_eq!(
+            section,
+            "## [Unreleased]\n\n### Added\n\n- new feature\n\n\
+             [Unreleased]: https://github.com/owner/repo/compare/v1.0.0...HEAD\n"
+        );
+
+        let section = unreleased_section(SECTION_1_1_0, None);
+        assert_eq!(section, "## [Unreleased]\n\n### Added\n\n- new feature\n");
+    }
+
+    #[test]
+    fn test_insert_unreleased_section() {
+        let existing = changelog("## [1.0.0] - 2024-01-01\n\n- initial\n");
+        let section = "## [Unreleased]\n\n- first\n";
+
+        let content = insert_unreleased_section(&existing, section, CHANGELOG_HEADER);
+        assert_eq!(
+            content,
+            changelog("## [Unreleased]\n\n- first\n\n## [1.0.0] - 2024-01-01\n\n- initial\n")
+        );
+
+        // Only the Unreleased section is replaced on subsequent runs
+        let content =
+            insert_unreleased_section(&content, "## [Unreleased]\n\n- second\n", CHANGELOG_HEADER);
+        assert_eq!(
+            content,
+            changelog("## [Unreleased]\n\n- second\n\n## [1.0.0] - 2024-01-01\n\n- initial\n")
+        );
+    }
+
+    #[test]
+    fn test_insert_unreleased_into_empty_changelog() {
+        let section = "## [Unreleased]\n\n- first\n";
+        let content = insert_unreleased_section("", section, CHANGELOG_HEADER);
+        assert_eq!(content, changelog(section));
+    }
+
+    #[test]
+    fn test_unbracketed_and_prefixed_headings() {
+        let existing = changelog("## v1.1.0\n\n- old\n\n## 1.0.0\n\n- initial\n");
+
+        let content = insert_release_section(
+            &existing,
+            SECTION_1_1_0,
+            &Version::new(1, 1, 0),
+            CHANGELOG_HEADER,
+        );
+
+        assert!(!content.contains("- old"));
+        assert!(content.contains("## 1.0.0"));
+    }
+
+    #[test]
+    fn test_insert_debian_entries() {
+        let first = "app (1.0.0) unstable; urgency=medium\n\n  * initial\n\n -- Jane <j@x.org>  Thu, 01 Jan 2026 10:00:00 +0000\n";
+        let second = "app (1.1.0) unstable; urgency=medium\n\n  * fix\n\n -- Jane <j@x.org>  Fri, 02 Jan 2026 10:00:00 +0000\n";
+
+        let content = insert_release_section("", first, &Version::new(1, 0, 0), CHANGELOG_HEADER);
+        assert_eq!(content, first);
+
+        let content =
+            insert_release_section(&content, second, &Version::new(1, 1, 0), CHANGELOG_HEADER);
+        assert_eq!(content, format!("{second}\n{first}"));
+
+        // Running the release again replaces its entry
+        let content =
+            insert_release_section(&content, second, &Version::new(1, 1, 0), CHANGELOG_HEADER);
+        assert_eq!(content, format!("{second}\n{first}"));
+        assert!(!content.contains("# Changelog"));
+    }
+
+    #[test]
+    fn test_section_body() {
+        assert_eq!(
+            section_body(SECTION_1_1_0),
+            "### Added\n\n- new feature\n\n"
+        );
+        assert_eq!(section_body("- entry\n"), "- entry\n");
+    }
+
+    #[test]
+    fn test_insert_at_marker() {
+        let existing = "# Releases\n\n<!-- unduler:insert -->\n";
+        let version = Version::new(1, 1, 0);
+
+        let content =
+            insert_at_marker(existing, "<!-- unduler:insert -->", SECTION_1_1_0, &version).unwrap();
+        assert_eq!(
+            content,
+            "# Releases\n\n<!-- unduler:insert -->\n\n## [1.1.0] - 2024-02-01\n\n### Added\n\n- new feature\n"
+        );
+
+        // Running the release again replaces its section
+        let again =
+            insert_at_marker(&content, "<!-- unduler:insert -->", SECTION_1_1_0, &version).unwrap();
+        assert_eq!(again, content);
+
+        // Newer releases go above older ones
+        let newer = "## [1.2.0] - 2024-03-01\n\n- newer\n";
+        let content = insert_at_marker(
+            &content,
+            "<!-- unduler:insert -->",
+            newer,
+            &Version::new(1, 2, 0),
+        )
+        .unwrap();
+        assert!(content.find("## [1.2.0]").unwrap() < content.find("## [1.1.0]").unwrap());
+
+        assert!(
+            insert_at_marker("# Releases\n", "<!-- unduler:insert -->", newer, &version).is_none()
+        );
+    }
+
+    #[test]
+    fn test_replace_between_markers() {
+        let existing =
+            "# App\n\n## Latest release\n\n<!-- start -->\nold\n<!-- end -->\n\n## Usage\n";
+
+        let content =
+            replace_between_markers(existing, "<!-- start -->", "<!-- end -->", "- new\n").unwrap();
+        assert_eq!(
+            content,
+            "# App\n\n## Latest release\n\n<!-- start -->\n\n- new\n\n<!-- end -->\n\n## Usage\n"
+        );
+        assert!(replace_between_markers(existing, "<!-- start -->", "<!-- stop -->", "").is_none());
+    }
+
+    #[test]
+    fn test_update_inserts() {
+        let dir = tempfile::tempdir().unwrap();
+        let releases = dir.path().join("releases.md");
+        let readme = dir.path().join("README.md");
+        fs::write(&releases, "<!-- unduler:insert -->\n").unwrap();
+        fs::write(&readme, "<!-- start --><!-- end -->\n").unwrap();
+
+        let inserts = [
+            ChangelogInsertConfig {
+                path: releases.display().to_string(),
+                marker: "<!-- unduler:insert -->".to_string(),
+                end_marker: None,
+                format: InsertFormat::Section,
+            },
+            ChangelogInsertConfig {
+                path: readme.display().to_string(),
+                marker: "<!-- start -->".to_string(),
+                end_marker: Some("<!-- end -->".to_string()),
+                format: InsertFormat::Body,
+            },
+        ];
+        update_inserts(&inserts, SECTION_1_1_0, &Version::new(1, 1, 0)).unwrap();
+
+        assert!(
+            fs::read_to_string(&releases)
+                .unwrap()
+                .contains("## [1.1.0]")
+        );
+        let readme = fs::read_to_string(&readme).unwrap();
+        assert!(readme.contains("### Added"));
+        assert!(!readme.contains("## [1.1.0]"));
+    }
+
+    #[test]
+    fn test_update_inserts_missing_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("README.md");
+        fs::write(&path, "# App\n").unwrap();
+
+        let inserts = [ChangelogInsertConfig {
+            path: path.display().to_string(),
+            marker: "<!-- unduler:insert -->".to_string(),
+            end_marker: None,
+            format: InsertFormat::Section,
+        }];
+        assert!(matches!(
+            update_inserts(&inserts, SECTION_1_1_0, &Version::new(1, 1, 0)),
+            Err(CoreError::MarkerNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_write_release_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ReleaseFilesConfig {
+            dir: dir.path().join("changelogs").display().to_string(),
+            file_name: "v{version}.md".to_string(),
+            index: "index.md".to_string(),
+            skip_output: false,
+        };
+
+        write_release_file(&config, SECTION_1_1_0, &Version::new(1, 1, 0)).unwrap();
+        let path = write_release_file(&config, "## [1.10.0]\n", &Version::new(1, 10, 0)).unwrap();
+        write_release_file(&config, SECTION_1_1_0, &Version::new(1, 1, 0)).unwrap();
+
+        assert_eq!(path, dir.path().join("changelogs/v1.10.0.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "## [1.10.0]\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("changelogs/index.md")).unwrap(),
+            "# Releases\n\n- [1.10.0](v1.10.0.md)\n- [1.1.0](v1.1.0.md)\n"
+        );
+    }
+
+    struct VersionFormatter(&'static str);
+
+    impl unduler_plugin::Plugin for VersionFormatter {
+        fn name(&self) -> &'static str {
+            "version"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl unduler_plugin::ChangelogFormatter for VersionFormatter {
+        fn format(&self, release: &Release, _config: &FormatterConfig) -> String {
+            match self.0 {
+                "json" => format!("{{\"version\":\"{}\"}}\n", release.version),
+                _ => format!("## [{}]\n\n", release.version),
+            }
+        }
+
+        fn extension(&self) -> &'static str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_output_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("dist/release.json");
+        let markdown = dir.path().join("docs/CHANGELOG.md");
+        fs::create_dir_all(json.parent().unwrap()).unwrap();
+        fs::write(&json, "{\"version\":\"1.0.0\"}\n").unwrap();
+        let outputs = [
+            ChangelogOutput {
+                path: json.clone(),
+                formatter: Box::new(VersionFormatter("json")),
+            },
+            ChangelogOutput {
+                path: markdown.clone(),
+                formatter: Box::new(VersionFormatter("md")),
+            },
+        ];
+        let release = Release::new(Version::new(1, 1, 0), chrono::Utc::now(), Vec::new());
+
+        let changes = output_changes(
+            &outputs,
+            &release,
+            &FormatterConfig::default(),
+            CHANGELOG_HEADER,
+        )
+        .unwrap();
+        assert_eq!(changes[0].path, json);
+        assert_eq!(changes[0].after, "{\"version\":\"1.1.0\"}\n");
+        assert_eq!(changes[1].path, markdown);
+        assert_eq!(changes[1].after, changelog("## [1.1.0]\n\n"));
+        assert!(!markdown.exists());
+    }
+
+    #[test]
+    fn test_update_changelog_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        let version = Version::new(1, 1, 0);
+
+        update_changelog(&path, SECTION_1_1_0, &version, CHANGELOG_HEADER).unwrap();
+        update_changelog(&path, SECTION_1_1_0, &version, CHANGELOG_HEADER).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.matches("## [1.1.0]").count(), 1);
+        assert!(content.starts_with("# Changelog"));
+    }
+}
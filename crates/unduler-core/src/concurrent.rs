@@ -0,0 +1,135 @@
+//! Bounded concurrent execution of futures.
+
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::task::Poll;
+
+/// Runs futures concurrently, at most `limit` at a time, and collects their
+/// results in order.
+///
+/// Futures are started in order as earlier ones complete. The first error
+/// is returned and the futures still running are dropped. A `limit` of 0 is
+/// treated as 1.
+///
+/// This does not spawn tasks, so it works with any executor and with futures
+/// borrowing local data, e.g. uploading several release assets from a hook.
+///
+/// # Errors
+///
+/// Returns the first error returned by a future.
+pub async fn try_join_bounded<I, F, T, E>(futures: I, limit: usize) -> Result<Vec<T>, E>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<T, E>>,
+{
+    let limit = limit.max(1);
+    let mut pending = futures.into_iter().enumerate();
+    let mut running: Vec<(usize, Pin<Box<F>>)> = Vec::new();
+    let mut results: Vec<Option<T>> = Vec::new();
+
+    poll_fn(|cx| {
+        loop {
+            while running.len() < limit
+                && let Some((index, future)) = pending.next()
+            {
+                results.push(None);
+                running.push((index, Box::pin(future)));
+            }
+
+            if running.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut completed = false;
+            let mut i = 0;
+            while i < running.len() {
+                match running[i].1.as_mut().poll(cx) {
+                    Poll::Ready(Ok(value)) => {
+                        let (index, _) = running.swap_remove(i);
+                        results[index] = Some(value);
+                        completed = true;
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => i += 1,
+                }
+            }
+
+            // Start the next futures in place of the completed ones
+            if !completed {
+                return Poll::Pending;
+            }
+        }
+    })
+    .await?;
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A future completing after yielding to the executor once.
+    async fn yield_now() {
+        let mut yielded = false;
+        poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_results_in_order() {
+        let futures = (0..5).map(|i| async move {
+            for _ in 0..(5 - i) {
+                yield_now().await;
+            }
+            Ok::<_, ()>(i)
+        });
+
+        let results = try_join_bounded(futures, 2).await.unwrap();
+        assert_eq!(results, [0, 1, 2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let running = &AtomicUsize::new(0);
+        let max_running = &AtomicUsize::new(0);
+
+        let futures = (0..6).map(move |_| async move {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now, Ordering::SeqCst);
+            yield_now().await;
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, ()>(())
+        });
+
+        try_join_bounded(futures, 3).await.unwrap();
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_first_error() {
+        let futures = (0..4).map(|i| async move { if i == 2 { Err(i) } else { Ok(i) } });
+        assert_eq!(try_join_bounded(futures, 4).await, Err(2));
+    }
+
+    #[tokio::test]
+    async fn test_zero_limit() {
+        let futures = (0..3).map(|i| async move { Ok::<_, ()>(i) });
+        assert_eq!(try_join_bounded(futures, 0).await.unwrap(), [0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_empty() {
+        let futures = std::iter::empty::<std::future::Ready<Result<(), ()>>>();
+        assert!(try_join_bounded(futures, 2).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+//! Changes of the files written by a release, previewed by dry runs.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+
+/// Content of a file before and after a release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Content before the release, `None` if the release creates the file.
+    pub before: Option<String>,
+    /// Content after the release.
+    pub after: String,
+}
+
+impl FileChange {
+    /// Creates the change of a file to new content, reading its current
+    /// content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    pub fn read(path: impl Into<PathBuf>, after: String) -> io::Result<Self> {
+        let path = path.into();
+        let before = match fs::read_to_string(&path) {
+            Ok(before) => Some(before),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            before,
+            after,
+        })
+    }
+
+    /// Returns true if the content of the file changes.
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.before.as_deref() != Some(self.after.as_str())
+    }
+
+    /// Writes the new content, creating the parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, &self.after)
+    }
+
+    /// Returns the unified diff of the change, empty when the content is
+    /// unchanged.
+    #[must_use]
+    pub fn unified_diff(&self) -> String {
+        let old = match self.before {
+            Some(_) => format!("a/{}", display(&self.path)),
+            None => "/dev/null".to_string(),
+        };
+        let new = format!("b/{}", display(&self.path));
+
+        TextDiff::from_lines(
+            self.before.as_deref().unwrap_or_default(),
+            self.after.as_str(),
+        )
+        .unified_diff()
+        .context_radius(3)
+        .header(&old, &new)
+        .to_string()
+    }
+}
+
+/// Displays a path with `/` separators, as in git diffs.
+fn display(path: &Path) -> String {
+    path.display().to_string().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let change = FileChange {
+            path: PathBuf::from("Cargo.toml"),
+            before: Some("[package]\nname = \"app\"\nversion = \"1.0.0\"\n".to_string()),
+            after: "[package]\nname = \"app\"\nversion = \"1.1.0\"\n".to_string(),
+        };
+
+        assert!(change.is_changed());
+        assert_eq!(
+            change.unified_diff(),
+            "--- a/Cargo.toml\n\
+             +++ b/Cargo.toml\n\
+             @@ -1,3 +1,3 @@\n \
+             [package]\n \
+             name = \"app\"\n\
+             -version = \"1.0.0\"\n\
+             +version = \"1.1.0\"\n"
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_new_file() {
+        let change = FileChange {
+            path: PathBuf::from("CHANGELOG.md"),
+            before: None,
+            after: "# Changelog\n".to_string(),
+        };
+
+        assert_eq!(
+            change.unified_diff(),
+            "--- /dev/null\n+++ b/CHANGELOG.md\n@@ -0,0 +1 @@\n+# Changelog\n"
+        );
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let change = FileChange {
+            path: PathBuf::from("VERSION"),
+            before: Some("1.0.0\n".to_string()),
+            after: "1.0.0\n".to_string(),
+        };
+
+        assert!(!change.is_changed());
+        assert_eq!(change.unified_diff(), "");
+    }
+}
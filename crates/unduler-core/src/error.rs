@@ -25,9 +25,65 @@ pub enum CoreError {
     #[error("no commits found since last release")]
     NoCommits,
 
+    /// None of the commits since the last release could be parsed.
+    #[error("no parseable commits found")]
+    NoParseableCommits,
+
+    /// The release tag already exists.
+    #[error("tag {0} already exists")]
+    TagExists(String),
+
+    /// The new version is not greater than the latest tag of its channel.
+    #[error("version {version} is not greater than the latest tag {latest_tag}")]
+    VersionNotGreater {
+        /// Version to release.
+        version: semver::Version,
+        /// Latest tag of the channel.
+        latest_tag: String,
+    },
+
+    /// The current branch cannot be determined.
+    #[error("cannot determine the current branch from a detached HEAD, set version.branch")]
+    DetachedHead,
+
+    /// Releases are not allowed from the current branch.
+    #[error("releases are not allowed from branch '{branch}' (allowed: {})", allowed.join(", "))]
+    BranchNotAllowed {
+        /// Current branch.
+        branch: String,
+        /// Allowed branch patterns.
+        allowed: Vec<String>,
+    },
+
+    /// A changelog insert marker is missing from its file.
+    #[error("marker '{marker}' not found in {}", path.display())]
+    MarkerNotFound {
+        /// File path.
+        path: std::path::PathBuf,
+        /// Missing marker.
+        marker: String,
+    },
+
+    /// The `changelog.header_file` cannot be read.
+    #[error("failed to read changelog header from {}: {source}", path.display())]
+    ChangelogHeader {
+        /// File path.
+        path: std::path::PathBuf,
+        /// Read error.
+        source: std::io::Error,
+    },
+
+    /// Version file update error.
+    #[error("file update error: {0}")]
+    File(#[from] crate::FileUpdateError),
+
     /// IO error.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// The progress of a release cannot be recorded.
+    #[error("failed to record release progress: {0}")]
+    Progress(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 /// Result type for core operations.
@@ -2,10 +2,16 @@
 //!
 //! Supports updating version numbers in various file formats:
 //! - Cargo.toml (TOML)
-//! - package.json (JSON)
+//! - package.json and composer.json (JSON)
+//! - pyproject.toml (`[project]` or `[tool.poetry]` table)
+//! - setup.cfg (`[metadata]` section)
+//! - gradle.properties and build.gradle(.kts) (Gradle)
+//! - pom.xml (project-level `<version>`)
+//! - pubspec.yaml and other YAML files (`version:` or a configured key)
 //! - Generic files via regex pattern
 
 use std::fs;
+use std::ops::Range;
 use std::path::Path;
 
 use semver::Version;
@@ -33,6 +39,10 @@ pub enum FileUpdateError {
     /// Unsupported file type.
     #[error("unsupported file type: {0}")]
     UnsupportedFileType(String),
+
+    /// Invalid version pattern.
+    #[error("invalid version pattern '{pattern}': {reason}")]
+    InvalidPattern { pattern: String, reason: String },
 }
 
 /// Result type for file operations.
@@ -48,19 +58,43 @@ pub type FileResult<T> = Result<T, FileUpdateError>;
 /// - The version field is not found in the file
 /// - The file cannot be read or written
 pub fn update_version_file(path: &Path, new_version: &Version, dry_run: bool) -> FileResult<()> {
+    let content = bump_version_file(path, new_version)?;
+    if !dry_run {
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+/// Returns the content of a version file with the new version, without
+/// writing it.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file does not exist
+/// - The file type is not supported
+/// - The version field is not found in the file
+/// - The file cannot be read
+pub fn bump_version_file(path: &Path, new_version: &Version) -> FileResult<String> {
     let filename = path
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or_default();
 
     match filename {
-        "Cargo.toml" => update_cargo_toml(path, new_version, dry_run),
-        "package.json" => update_package_json(path, new_version, dry_run),
+        "Cargo.toml" => bump_cargo_toml(path, new_version),
+        "package.json" | "composer.json" => bump_package_json(path, new_version),
+        "pyproject.toml" => bump_pyproject_toml(path, new_version),
+        "setup.cfg" => bump_setup_cfg(path, new_version),
+        "gradle.properties" => bump_gradle_properties(path, new_version),
+        "build.gradle" | "build.gradle.kts" => bump_build_gradle(path, new_version),
+        "pom.xml" => bump_pom_xml(path, new_version),
         _ => {
             // Try to detect by extension
             match path.extension().and_then(|e| e.to_str()) {
-                Some("toml") => update_cargo_toml(path, new_version, dry_run),
-                Some("json") => update_package_json(path, new_version, dry_run),
+                Some("toml") => bump_cargo_toml(path, new_version),
+                Some("json") => bump_package_json(path, new_version),
+                Some("yaml" | "yml") => bump_yaml_version(path, YAML_VERSION_KEY, new_version),
                 _ => Err(FileUpdateError::UnsupportedFileType(
                     path.display().to_string(),
                 )),
@@ -69,35 +103,16 @@ pub fn update_version_file(path: &Path, new_version: &Version, dry_run: bool) ->
     }
 }
 
-/// Updates version in a Cargo.toml file.
-fn update_cargo_toml(path: &Path, new_version: &Version, dry_run: bool) -> FileResult<()> {
-    if !path.exists() {
-        return Err(FileUpdateError::NotFound(path.display().to_string()));
-    }
-
-    let content = fs::read_to_string(path)?;
-
-    // Use regex to update version while preserving formatting
-    let version_regex =
-        regex::Regex::new(r#"(?m)^(\s*version\s*=\s*)"([^"]+)"#).expect("invalid regex");
-
-    if !version_regex.is_match(&content) {
-        return Err(FileUpdateError::VersionNotFound(path.display().to_string()));
-    }
-
-    let new_content = version_regex
-        .replace(&content, format!(r#"$1"{new_version}""#))
-        .to_string();
-
-    if !dry_run {
-        fs::write(path, new_content)?;
-    }
-
-    Ok(())
+/// Bumps the version of a Cargo.toml file.
+///
+/// Only the version of the `[package]` or `[workspace.package]` table is
+/// updated, leaving dependency versions untouched.
+fn bump_cargo_toml(path: &Path, new_version: &Version) -> FileResult<String> {
+    bump_located_version(path, new_version, find_cargo_version)
 }
 
-/// Updates version in a package.json file.
-fn update_package_json(path: &Path, new_version: &Version, dry_run: bool) -> FileResult<()> {
+/// Bumps the version of a package.json or composer.json file.
+fn bump_package_json(path: &Path, new_version: &Version) -> FileResult<String> {
     if !path.exists() {
         return Err(FileUpdateError::NotFound(path.display().to_string()));
     }
@@ -127,19 +142,594 @@ fn update_package_json(path: &Path, new_version: &Version, dry_run: bool) -> Fil
         });
     }
 
+    // Pretty formatting and trailing newline
+    let new_content =
+        serde_json::to_string_pretty(&json).map_err(|e| FileUpdateError::ParseError {
+            file: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+    Ok(format!("{new_content}\n"))
+}
+
+/// Tables of a pyproject.toml that may hold the version, in order of precedence.
+const PYPROJECT_SECTIONS: &[&str] = &["project", "tool.poetry"];
+
+/// Section of a setup.cfg that holds the version.
+const SETUP_CFG_SECTIONS: &[&str] = &["metadata"];
+
+/// Returns the regex matching a quoted TOML version value.
+fn toml_version_regex() -> regex::Regex {
+    regex::Regex::new(r#"(?m)^\s*version\s*=\s*["']([^"']+)["']"#).expect("invalid regex")
+}
+
+/// Returns the regex matching an unquoted INI version value.
+///
+/// The value must start with a digit so that directives such as
+/// `attr: package.__version__` are not mistaken for a version.
+fn ini_version_regex() -> regex::Regex {
+    regex::Regex::new(r"(?m)^\s*version\s*=\s*(\d\S*)\s*$").expect("invalid regex")
+}
+
+/// Returns the regex matching INI/TOML section headers.
+fn section_header_regex() -> regex::Regex {
+    regex::Regex::new(r"(?m)^\s*\[\[?([^\]]+)\]\]?\s*(?:#.*)?$").expect("invalid regex")
+}
+
+/// Returns the sections of an INI/TOML document.
+///
+/// Each section is returned as its name and the byte range of its body,
+/// spanning from the end of the `[name]` header to the next header.
+fn sections(content: &str) -> Vec<(&str, Range<usize>)> {
+    let headers: Vec<_> = section_header_regex()
+        .captures_iter(content)
+        .map(|captures| {
+            let header = captures.get(0).expect("group 0 always matches");
+            let name = captures.get(1).expect("group 1 always matches");
+            (name.as_str().trim(), header.range())
+        })
+        .collect();
+
+    headers
+        .iter()
+        .enumerate()
+        .map(|(index, (name, header))| {
+            let end = headers
+                .get(index + 1)
+                .map_or(content.len(), |(_, next)| next.start);
+            (*name, header.end..end)
+        })
+        .collect()
+}
+
+/// Returns the byte range of the body of an INI/TOML section.
+fn section_range(content: &str, name: &str) -> Option<Range<usize>> {
+    sections(content)
+        .into_iter()
+        .find(|(section, _)| *section == name)
+        .map(|(_, range)| range)
+}
+
+/// Locates a version value inside the first section that contains one.
+///
+/// Returns the byte range of the version value within `content`.
+fn find_section_version(
+    content: &str,
+    sections: &[&str],
+    value_regex: &regex::Regex,
+) -> Option<Range<usize>> {
+    sections.iter().find_map(|name| {
+        let section = section_range(content, name)?;
+        let value = value_regex.captures(&content[section.clone()])?.get(1)?;
+        Some(section.start + value.start()..section.start + value.end())
+    })
+}
+
+/// Bumps a version value found by a locator.
+///
+/// The locator returns the byte range of the version value within the file
+/// content, so that the rest of the file is left untouched.
+fn bump_located_version(
+    path: &Path,
+    new_version: &Version,
+    locate: impl Fn(&str) -> Option<Range<usize>>,
+) -> FileResult<String> {
+    if !path.exists() {
+        return Err(FileUpdateError::NotFound(path.display().to_string()));
+    }
+
+    let mut content = fs::read_to_string(path)?;
+
+    let range = locate(&content)
+        .ok_or_else(|| FileUpdateError::VersionNotFound(path.display().to_string()))?;
+
+    content.replace_range(range, &new_version.to_string());
+    Ok(content)
+}
+
+/// Reads a version value found by a locator.
+fn read_located_version(
+    path: &Path,
+    locate: impl Fn(&str) -> Option<Range<usize>>,
+) -> FileResult<Version> {
+    if !path.exists() {
+        return Err(FileUpdateError::NotFound(path.display().to_string()));
+    }
+
+    let content = fs::read_to_string(path)?;
+
+    let range = locate(&content)
+        .ok_or_else(|| FileUpdateError::VersionNotFound(path.display().to_string()))?;
+
+    Version::parse(content[range].trim()).map_err(|e| FileUpdateError::ParseError {
+        file: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Locates the first capture group of a regex.
+fn find_regex_version(content: &str, value_regex: &regex::Regex) -> Option<Range<usize>> {
+    Some(value_regex.captures(content)?.get(1)?.range())
+}
+
+/// Locates the project-level `<version>` of a pom.xml.
+///
+/// Only a `<version>` element that is a direct child of `<project>` is
+/// considered, so the versions of `<parent>`, dependencies and plugins are
+/// skipped. Property references such as `${revision}` are not versions.
+fn find_pom_version(content: &str) -> Option<Range<usize>> {
+    let tag_regex =
+        regex::Regex::new(r"<!--[\s\S]*?-->|<[?!][^>]*>|<(/?)([A-Za-z_][\w:.-]*)[^>]*?(/?)>")
+            .expect("invalid regex");
+
+    let mut depth = 0usize;
+    for captures in tag_regex.captures_iter(content) {
+        // Comments, declarations and processing instructions
+        let Some(name) = captures.get(2) else {
+            continue;
+        };
+
+        if !captures[1].is_empty() {
+            depth = depth.saturating_sub(1);
+            continue;
+        }
+        if !captures[3].is_empty() {
+            continue;
+        }
+
+        if depth == 1 && name.as_str() == "version" {
+            let start = captures.get(0).expect("group 0 always matches").end();
+            let end = start + content[start..].find("</version>")?;
+            let value = &content[start..end];
+            let trimmed = value.trim_start();
+            let start = start + value.len() - trimmed.len();
+            let end = start + trimmed.trim_end().len();
+
+            return content[start..end]
+                .starts_with(|c: char| c.is_ascii_digit())
+                .then_some(start..end);
+        }
+
+        depth += 1;
+    }
+
+    None
+}
+
+/// Tables of a Cargo.toml that may hold the version, in order of precedence.
+const CARGO_SECTIONS: &[&str] = &["package", "workspace.package"];
+
+/// Locates the version of a Cargo.toml.
+///
+/// Falls back to a `version` key in the root table for other TOML files.
+fn find_cargo_version(content: &str) -> Option<Range<usize>> {
+    let value_regex = toml_version_regex();
+
+    find_section_version(content, CARGO_SECTIONS, &value_regex).or_else(|| {
+        let root_end = section_header_regex()
+            .find(content)
+            .map_or(content.len(), |header| header.start());
+        find_regex_version(&content[..root_end], &value_regex)
+    })
+}
+
+/// Tables of a Cargo.toml that declare dependencies.
+const CARGO_DEPENDENCY_TABLES: &[&str] =
+    &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Returns whether a Cargo.toml table declares dependencies.
+///
+/// Covers `[dependencies]`, `[workspace.dependencies]` and
+/// `[target.'cfg(...)'.dependencies]` along with their dev and build variants.
+fn is_cargo_dependency_table(name: &str) -> bool {
+    CARGO_DEPENDENCY_TABLES
+        .iter()
+        .any(|table| name == *table || name.ends_with(&format!(".{table}")))
+}
+
+/// Locates the version requirements of path dependencies in a Cargo.toml.
+///
+/// Both inline tables (`foo = { path = "../foo", version = "1.0.0" }`) and
+/// dependency tables (`[dependencies.foo]`) are supported. Comparison
+/// operators such as `=` or `^` are excluded from the returned ranges.
+fn find_cargo_path_dependencies(content: &str) -> Vec<Range<usize>> {
+    let inline_regex = regex::Regex::new(r"(?m)^[ \t]*[\w.-]+[ \t]*=[ \t]*\{.*\bpath[ \t]*=.*\}")
+        .expect("invalid regex");
+    let path_regex = regex::Regex::new(r"(?m)^[ \t]*path[ \t]*=").expect("invalid regex");
+    let requirement_regex =
+        regex::Regex::new(r#"\bversion\s*=\s*"[=^~<>\s]*(\d[^"]*)""#).expect("invalid regex");
+
+    let mut ranges = Vec::new();
+
+    for (name, body) in sections(content) {
+        let section = &content[body.clone()];
+
+        if is_cargo_dependency_table(name) {
+            for dependency in inline_regex.find_iter(section) {
+                if let Some(version) = find_regex_version(dependency.as_str(), &requirement_regex) {
+                    let start = body.start + dependency.start();
+                    ranges.push(start + version.start..start + version.end);
+                }
+            }
+        } else if name
+            .rsplit_once('.')
+            .is_some_and(|(parent, _)| is_cargo_dependency_table(parent))
+            && path_regex.is_match(section)
+            && let Some(version) = find_regex_version(section, &requirement_regex)
+        {
+            ranges.push(body.start + version.start..body.start + version.end);
+        }
+    }
+
+    ranges
+}
+
+/// Updates the version requirements of path dependencies in a Cargo.toml.
+///
+/// Path dependencies usually point to other members of the same workspace,
+/// whose requirements must follow the released version. Dependencies
+/// without a version requirement are left untouched.
+///
+/// Returns the number of updated requirements.
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be read or written.
+pub fn update_cargo_path_dependencies(
+    path: &Path,
+    new_version: &Version,
+    dry_run: bool,
+) -> FileResult<usize> {
+    if !path.exists() {
+        return Err(FileUpdateError::NotFound(path.display().to_string()));
+    }
+
+    let content = fs::read_to_string(path)?;
+    let (content, count) = bump_cargo_path_dependencies(&content, new_version);
+
+    if !dry_run && count > 0 {
+        fs::write(path, content)?;
+    }
+
+    Ok(count)
+}
+
+/// Bumps the version requirements of path dependencies in the content of a
+/// Cargo.toml.
+///
+/// Returns the new content and the number of updated requirements.
+#[must_use]
+pub fn bump_cargo_path_dependencies(content: &str, new_version: &Version) -> (String, usize) {
+    let mut content = content.to_string();
+    let ranges = find_cargo_path_dependencies(&content);
+
+    // Replace from the end so earlier ranges stay valid
+    for range in ranges.iter().rev() {
+        content.replace_range(range.clone(), &new_version.to_string());
+    }
+
+    (content, ranges.len())
+}
+
+/// Returns the regex matching a version in a gradle.properties file.
+fn gradle_properties_regex() -> regex::Regex {
+    regex::Regex::new(r"(?m)^\s*version\s*[=:]\s*(\d\S*)\s*$").expect("invalid regex")
+}
+
+/// Returns the regex matching a version in a build.gradle(.kts) file.
+///
+/// Matches both the Groovy (`version '1.2.3'`) and the Kotlin
+/// (`version = "1.2.3"`) syntaxes.
+fn build_gradle_regex() -> regex::Regex {
+    regex::Regex::new(r#"(?m)^\s*version\s*=?\s*["'](\d[^"']*)["']"#).expect("invalid regex")
+}
+
+/// Bumps the version of a pyproject.toml file.
+fn bump_pyproject_toml(path: &Path, new_version: &Version) -> FileResult<String> {
+    let value_regex = toml_version_regex();
+    bump_located_version(path, new_version, |content| {
+        find_section_version(content, PYPROJECT_SECTIONS, &value_regex)
+    })
+}
+
+/// Bumps the version of a setup.cfg file.
+fn bump_setup_cfg(path: &Path, new_version: &Version) -> FileResult<String> {
+    let value_regex = ini_version_regex();
+    bump_located_version(path, new_version, |content| {
+        find_section_version(content, SETUP_CFG_SECTIONS, &value_regex)
+    })
+}
+
+/// Bumps the version of a gradle.properties file.
+fn bump_gradle_properties(path: &Path, new_version: &Version) -> FileResult<String> {
+    let value_regex = gradle_properties_regex();
+    bump_located_version(path, new_version, |content| {
+        find_regex_version(content, &value_regex)
+    })
+}
+
+/// Bumps the version of a build.gradle or build.gradle.kts file.
+fn bump_build_gradle(path: &Path, new_version: &Version) -> FileResult<String> {
+    let value_regex = build_gradle_regex();
+    bump_located_version(path, new_version, |content| {
+        find_regex_version(content, &value_regex)
+    })
+}
+
+/// Bumps the version of a pom.xml file.
+fn bump_pom_xml(path: &Path, new_version: &Version) -> FileResult<String> {
+    bump_located_version(path, new_version, find_pom_version)
+}
+
+/// Key holding the version in YAML files such as pubspec.yaml or Chart.yaml.
+const YAML_VERSION_KEY: &str = "version";
+
+/// Locates the scalar value of a key in a YAML document.
+///
+/// The key is a dot-separated path through nested mappings (e.g.
+/// `image.tag`). Quotes and trailing comments are excluded from the returned
+/// range, and values not starting with a digit are not considered versions.
+fn find_yaml_version(content: &str, key: &str) -> Option<Range<usize>> {
+    let line_regex = regex::Regex::new(
+        r#"^( *)(?:"([^"]*)"|'([^']*)'|([^\s#'"\-][^:#]*?))\s*:(?:\s+(.*?))?\s*$"#,
+    )
+    .expect("invalid regex");
+
+    let target: Vec<&str> = key.split('.').collect();
+    // Enclosing mappings as (indentation, key) pairs
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let Some(captures) = line_regex.captures(line.trim_end_matches(['\r', '\n'])) else {
+            continue;
+        };
+
+        let indent = captures[1].len();
+        let name = captures
+            .get(2)
+            .or_else(|| captures.get(3))
+            .or_else(|| captures.get(4))
+            .map(|m| m.as_str())?;
+
+        while parents.last().is_some_and(|&(parent, _)| parent >= indent) {
+            parents.pop();
+        }
+
+        let Some(value) = captures.get(5).filter(|value| !value.is_empty()) else {
+            parents.push((indent, name));
+            continue;
+        };
+
+        let is_target = parents.len() + 1 == target.len()
+            && parents
+                .iter()
+                .map(|&(_, key)| key)
+                .eq(target[..parents.len()].iter().copied())
+            && target[parents.len()] == name;
+        if !is_target {
+            continue;
+        }
+
+        let text = value.as_str();
+        let (start, end) =
+            if let Some(quote) = text.chars().next().filter(|&c| matches!(c, '"' | '\'')) {
+                (1, text[1..].find(quote)? + 1)
+            } else {
+                // Strip a trailing comment from plain scalars
+                let end = text
+                    .find(" #")
+                    .map_or(text.len(), |comment| text[..comment].trim_end().len());
+                (0, end)
+            };
+
+        let range = line_start + value.start() + start..line_start + value.start() + end;
+        return content[range.clone()]
+            .starts_with(|c: char| c.is_ascii_digit())
+            .then_some(range);
+    }
+
+    None
+}
+
+/// Updates a version stored under a key in a YAML file.
+///
+/// The key is a dot-separated path through nested mappings, such as
+/// `version` in pubspec.yaml or `appVersion` in a Helm Chart.yaml. Only the
+/// value is rewritten, preserving comments and formatting, and the build
+/// metadata of the current version (the `+3` build number of a pubspec.yaml)
+/// is kept unless the new version has its own.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file does not exist
+/// - The key is not found or does not hold a version
+/// - The file cannot be read or written
+pub fn update_yaml_version(
+    path: &Path,
+    key: &str,
+    new_version: &Version,
+    dry_run: bool,
+) -> FileResult<()> {
+    let content = bump_yaml_version(path, key, new_version)?;
     if !dry_run {
-        // Write with pretty formatting and trailing newline
-        let new_content =
-            serde_json::to_string_pretty(&json).map_err(|e| FileUpdateError::ParseError {
-                file: path.display().to_string(),
-                reason: e.to_string(),
-            })?;
-        fs::write(path, format!("{new_content}\n"))?;
+        fs::write(path, content)?;
     }
+    Ok(())
+}
+
+/// Returns the content of a YAML file with the new version under a key,
+/// without writing it.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file does not exist
+/// - The key is not found or does not hold a version
+/// - The file cannot be read
+pub fn bump_yaml_version(path: &Path, key: &str, new_version: &Version) -> FileResult<String> {
+    bump_located_version(path, new_version, |content| {
+        let range = find_yaml_version(content, key)?;
+        match content[range.clone()].find('+') {
+            Some(build) if new_version.build.is_empty() => Some(range.start..range.start + build),
+            _ => Some(range),
+        }
+    })
+}
+
+/// Reads a version stored under a key in a YAML file.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file does not exist
+/// - The key is not found or does not hold a version
+/// - The version string is not valid semver
+pub fn read_yaml_version(path: &Path, key: &str) -> FileResult<Version> {
+    read_located_version(path, |content| find_yaml_version(content, key))
+}
+
+/// Pattern matching a semantic version inside a version pattern.
+const VERSION_PATTERN: &str = r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?";
+
+/// Placeholder marking the version in a version pattern.
+const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// Builds the regex for a version pattern.
+///
+/// The pattern is a regex in which `{version}` marks the version to replace,
+/// e.g. `__version__ = "{version}"`. The text around the version is captured
+/// so it can be preserved on replacement.
+fn build_pattern_regex(pattern: &str) -> FileResult<regex::Regex> {
+    let Some((before, after)) = pattern.split_once(VERSION_PLACEHOLDER) else {
+        return Err(FileUpdateError::InvalidPattern {
+            pattern: pattern.to_string(),
+            reason: format!("missing {VERSION_PLACEHOLDER} placeholder"),
+        });
+    };
+
+    // Named groups, as the pattern may hold groups of its own
+    regex::Regex::new(&format!(
+        "(?m)(?P<before>{before})(?P<version>{VERSION_PATTERN})(?P<after>{after})"
+    ))
+    .map_err(|e| FileUpdateError::InvalidPattern {
+        pattern: pattern.to_string(),
+        reason: e.to_string(),
+    })
+}
 
+/// Updates version in any file using a version pattern.
+///
+/// Every match of the pattern is replaced, leaving the surrounding text
+/// untouched.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The pattern is invalid or lacks the `{version}` placeholder
+/// - The file does not exist
+/// - The pattern does not match the file
+/// - The file cannot be read or written
+pub fn update_version_with_pattern(
+    path: &Path,
+    pattern: &str,
+    new_version: &Version,
+    dry_run: bool,
+) -> FileResult<()> {
+    let content = bump_version_with_pattern(path, pattern, new_version)?;
+    if !dry_run {
+        fs::write(path, content)?;
+    }
     Ok(())
 }
 
+/// Returns the content of a file with every match of a version pattern
+/// replaced, without writing it.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The pattern is invalid or lacks the `{version}` placeholder
+/// - The file does not exist
+/// - The pattern does not match the file
+/// - The file cannot be read
+pub fn bump_version_with_pattern(
+    path: &Path,
+    pattern: &str,
+    new_version: &Version,
+) -> FileResult<String> {
+    let regex = build_pattern_regex(pattern)?;
+
+    if !path.exists() {
+        return Err(FileUpdateError::NotFound(path.display().to_string()));
+    }
+
+    let content = fs::read_to_string(path)?;
+
+    if !regex.is_match(&content) {
+        return Err(FileUpdateError::VersionNotFound(path.display().to_string()));
+    }
+
+    Ok(regex
+        .replace_all(&content, |caps: &regex::Captures<'_>| {
+            format!("{}{new_version}{}", &caps["before"], &caps["after"])
+        })
+        .to_string())
+}
+
+/// Reads the current version from any file using a version pattern.
+///
+/// The first match of the pattern is used.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The pattern is invalid or lacks the `{version}` placeholder
+/// - The file does not exist
+/// - The pattern does not match the file
+/// - The version string is not valid semver
+pub fn read_version_with_pattern(path: &Path, pattern: &str) -> FileResult<Version> {
+    let regex = build_pattern_regex(pattern)?;
+
+    if !path.exists() {
+        return Err(FileUpdateError::NotFound(path.display().to_string()));
+    }
+
+    let content = fs::read_to_string(path)?;
+
+    let captures = regex
+        .captures(&content)
+        .ok_or_else(|| FileUpdateError::VersionNotFound(path.display().to_string()))?;
+
+    Version::parse(&captures["version"]).map_err(|e| FileUpdateError::ParseError {
+        file: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
 /// Reads the current version from a file.
 ///
 /// # Errors
@@ -157,10 +747,16 @@ pub fn read_version_from_file(path: &Path) -> FileResult<Version> {
 
     match filename {
         "Cargo.toml" => read_cargo_toml_version(path),
-        "package.json" => read_package_json_version(path),
+        "package.json" | "composer.json" => read_package_json_version(path),
+        "pyproject.toml" => read_pyproject_toml_version(path),
+        "setup.cfg" => read_setup_cfg_version(path),
+        "gradle.properties" => read_gradle_properties_version(path),
+        "build.gradle" | "build.gradle.kts" => read_build_gradle_version(path),
+        "pom.xml" => read_pom_xml_version(path),
         _ => match path.extension().and_then(|e| e.to_str()) {
             Some("toml") => read_cargo_toml_version(path),
             Some("json") => read_package_json_version(path),
+            Some("yaml" | "yml") => read_yaml_version(path, YAML_VERSION_KEY),
             _ => Err(FileUpdateError::UnsupportedFileType(
                 path.display().to_string(),
             )),
@@ -170,28 +766,10 @@ pub fn read_version_from_file(path: &Path) -> FileResult<Version> {
 
 /// Reads version from a Cargo.toml file.
 fn read_cargo_toml_version(path: &Path) -> FileResult<Version> {
-    if !path.exists() {
-        return Err(FileUpdateError::NotFound(path.display().to_string()));
-    }
-
-    let content = fs::read_to_string(path)?;
-
-    let version_regex =
-        regex::Regex::new(r#"(?m)^\s*version\s*=\s*"([^"]+)""#).expect("invalid regex");
-
-    let captures = version_regex
-        .captures(&content)
-        .ok_or_else(|| FileUpdateError::VersionNotFound(path.display().to_string()))?;
-
-    let version_str = captures.get(1).map(|m| m.as_str()).unwrap_or_default();
-
-    Version::parse(version_str).map_err(|e| FileUpdateError::ParseError {
-        file: path.display().to_string(),
-        reason: e.to_string(),
-    })
+    read_located_version(path, find_cargo_version)
 }
 
-/// Reads version from a package.json file.
+/// Reads version from a package.json or composer.json file.
 fn read_package_json_version(path: &Path) -> FileResult<Version> {
     if !path.exists() {
         return Err(FileUpdateError::NotFound(path.display().to_string()));
@@ -216,6 +794,39 @@ fn read_package_json_version(path: &Path) -> FileResult<Version> {
     })
 }
 
+/// Reads version from a pyproject.toml file.
+fn read_pyproject_toml_version(path: &Path) -> FileResult<Version> {
+    let value_regex = toml_version_regex();
+    read_located_version(path, |content| {
+        find_section_version(content, PYPROJECT_SECTIONS, &value_regex)
+    })
+}
+
+/// Reads version from a setup.cfg file.
+fn read_setup_cfg_version(path: &Path) -> FileResult<Version> {
+    let value_regex = ini_version_regex();
+    read_located_version(path, |content| {
+        find_section_version(content, SETUP_CFG_SECTIONS, &value_regex)
+    })
+}
+
+/// Reads version from a gradle.properties file.
+fn read_gradle_properties_version(path: &Path) -> FileResult<Version> {
+    let value_regex = gradle_properties_regex();
+    read_located_version(path, |content| find_regex_version(content, &value_regex))
+}
+
+/// Reads version from a build.gradle or build.gradle.kts file.
+fn read_build_gradle_version(path: &Path) -> FileResult<Version> {
+    let value_regex = build_gradle_regex();
+    read_located_version(path, |content| find_regex_version(content, &value_regex))
+}
+
+/// Reads version from a pom.xml file.
+fn read_pom_xml_version(path: &Path) -> FileResult<Version> {
+    read_located_version(path, find_pom_version)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,7 +848,7 @@ version = "1.0.0"
         .unwrap();
 
         let version = Version::new(2, 0, 0);
-        update_cargo_toml(file.path(), &version, false).unwrap();
+        update_version_file(file.path(), &version, false).unwrap();
 
         let content = fs::read_to_string(file.path()).unwrap();
         assert!(content.contains(r#"version = "2.0.0""#));
@@ -256,12 +867,24 @@ version = "1.0.0"
         .unwrap();
 
         let version = Version::new(2, 0, 0);
-        update_cargo_toml(file.path(), &version, true).unwrap();
+        update_version_file(file.path(), &version, true).unwrap();
 
         let content = fs::read_to_string(file.path()).unwrap();
         assert!(content.contains(r#"version = "1.0.0""#)); // Unchanged
     }
 
+    #[test]
+    fn test_bump_version_file() {
+        let (_dir, path) = named_file("Cargo.toml", "[package]\nversion = \"1.0.0\"\n");
+
+        let content = bump_version_file(&path, &Version::new(1, 1, 0)).unwrap();
+        assert_eq!(content, "[package]\nversion = \"1.1.0\"\n");
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[package]\nversion = \"1.0.0\"\n"
+        );
+    }
+
     #[test]
     fn test_update_package_json() {
         let mut file = NamedTempFile::with_suffix(".json").unwrap();
@@ -275,7 +898,7 @@ version = "1.0.0"
         .unwrap();
 
         let version = Version::new(2, 0, 0);
-        update_package_json(file.path(), &version, false).unwrap();
+        update_version_file(file.path(), &version, false).unwrap();
 
         let content = fs::read_to_string(file.path()).unwrap();
         assert!(content.contains(r#""version": "2.0.0""#));
@@ -380,6 +1003,583 @@ name = "test"
         ));
     }
 
+    const WORKSPACE_CARGO_TOML: &str = r#"[workspace]
+members = ["crates/*"]
+
+[workspace.dependencies.serde]
+version = "1.0.200"
+features = ["derive"]
+
+[workspace.dependencies]
+app-core = { path = "crates/app-core", version = "0.1.0" }
+regex = { version = "1.10" }
+
+[workspace.package]
+version = "0.1.0"
+edition = "2024"
+"#;
+
+    #[test]
+    fn test_update_cargo_toml_workspace() {
+        let (_dir, path) = named_file("Cargo.toml", WORKSPACE_CARGO_TOML);
+
+        update_version_file(&path, &Version::new(0, 2, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            WORKSPACE_CARGO_TOML.replace(
+                "version = \"0.1.0\"\nedition",
+                "version = \"0.2.0\"\nedition"
+            )
+        );
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(0, 2, 0)
+        );
+    }
+
+    #[test]
+    fn test_update_cargo_toml_ignores_dependency_tables() {
+        let content = r#"[dependencies.serde]
+version = "1.0.200"
+
+[package]
+name = "test"
+version = "1.0.0"
+"#;
+        let (_dir, path) = named_file("Cargo.toml", content);
+
+        update_version_file(&path, &Version::new(2, 0, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("version = \"1.0.200\""));
+        assert!(content.contains("version = \"2.0.0\""));
+    }
+
+    #[test]
+    fn test_update_cargo_toml_inherited_version() {
+        let content = "[package]\nname = \"member\"\nversion.workspace = true\n";
+        let (_dir, path) = named_file("Cargo.toml", content);
+
+        let result = update_version_file(&path, &Version::new(2, 0, 0), false);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_update_cargo_path_dependencies() {
+        let content = r#"[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+app-core = { path = "../app-core", version = "=0.1.0" }
+app-git = { path = "../app-git" }
+serde = { version = "1.0", features = ["derive"] }
+
+[dependencies.app-config]
+path = "../app-config"
+version = "0.1.0"
+
+[target.'cfg(unix)'.dev-dependencies]
+app-test = { version = "^0.1.0", path = "../app-test" }
+"#;
+        let (_dir, path) = named_file("Cargo.toml", content);
+
+        let updated = update_cargo_path_dependencies(&path, &Version::new(0, 2, 0), false);
+        assert_eq!(updated.unwrap(), 3);
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#"app-core = { path = "../app-core", version = "=0.2.0" }"#));
+        assert!(content.contains(r#"app-git = { path = "../app-git" }"#));
+        assert!(content.contains(r#"serde = { version = "1.0", features = ["derive"] }"#));
+        assert!(content.contains("path = \"../app-config\"\nversion = \"0.2.0\""));
+        assert!(content.contains(r#"app-test = { version = "^0.2.0", path = "../app-test" }"#));
+        // The package version is handled by update_version_file
+        assert!(content.contains("name = \"app\"\nversion = \"0.1.0\""));
+    }
+
+    #[test]
+    fn test_update_cargo_path_dependencies_workspace() {
+        let (_dir, path) = named_file("Cargo.toml", WORKSPACE_CARGO_TOML);
+
+        let updated = update_cargo_path_dependencies(&path, &Version::new(0, 2, 0), true);
+        assert_eq!(updated.unwrap(), 1);
+
+        // Dry run leaves the file untouched
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, WORKSPACE_CARGO_TOML);
+    }
+
+    /// Writes content to a file with the given name in a temporary directory.
+    fn named_file(name: &str, content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_update_composer_json() {
+        let (_dir, path) = named_file(
+            "composer.json",
+            "{\n  \"name\": \"acme/app\",\n  \"version\": \"0.1.0\"\n}\n",
+        );
+
+        update_version_file(&path, &Version::new(0, 2, 0), false).unwrap();
+
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(0, 2, 0)
+        );
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"name\": \"acme/app\""));
+    }
+
+    const PYPROJECT: &str = r#"[build-system]
+requires = ["hatchling"]
+
+[project]
+name = "app"
+version = "1.0.0"
+dependencies = ["requests"]
+
+[tool.black]
+target-version = ["py311"]
+"#;
+
+    #[test]
+    fn test_update_pyproject_toml() {
+        let (_dir, path) = named_file("pyproject.toml", PYPROJECT);
+
+        update_version_file(&path, &Version::new(2, 0, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, PYPROJECT.replace("1.0.0", "2.0.0"));
+    }
+
+    #[test]
+    fn test_update_pyproject_toml_ignores_other_tables() {
+        let content = r#"[tool.other]
+version = "9.9.9"
+
+[project]
+name = "app"
+version = "1.0.0"
+"#;
+        let (_dir, path) = named_file("pyproject.toml", content);
+
+        update_version_file(&path, &Version::new(2, 0, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#"version = "9.9.9""#));
+        assert!(content.contains(r#"version = "2.0.0""#));
+    }
+
+    #[test]
+    fn test_update_pyproject_toml_poetry() {
+        let content = r#"[tool.poetry]
+name = "app"
+version = "0.3.0"
+"#;
+        let (_dir, path) = named_file("pyproject.toml", content);
+
+        update_version_file(&path, &Version::new(0, 4, 0), false).unwrap();
+
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(0, 4, 0)
+        );
+    }
+
+    #[test]
+    fn test_pyproject_toml_dynamic_version() {
+        let content = r#"[project]
+name = "app"
+dynamic = ["version"]
+"#;
+        let (_dir, path) = named_file("pyproject.toml", content);
+
+        let result = update_version_file(&path, &Version::new(2, 0, 0), false);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_read_pyproject_toml_version() {
+        let (_dir, path) = named_file("pyproject.toml", PYPROJECT);
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(1, 0, 0)
+        );
+    }
+
+    const SETUP_CFG: &str = "[metadata]
+name = app
+version = 1.0.0
+
+[options]
+python_requires = >=3.9
+";
+
+    #[test]
+    fn test_update_setup_cfg() {
+        let (_dir, path) = named_file("setup.cfg", SETUP_CFG);
+
+        update_version_file(&path, &Version::new(1, 1, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, SETUP_CFG.replace("1.0.0", "1.1.0"));
+    }
+
+    #[test]
+    fn test_setup_cfg_attr_version() {
+        let content = "[metadata]
+name = app
+version = attr: app.__version__
+";
+        let (_dir, path) = named_file("setup.cfg", content);
+
+        let result = update_version_file(&path, &Version::new(1, 1, 0), false);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_read_setup_cfg_version() {
+        let (_dir, path) = named_file("setup.cfg", SETUP_CFG);
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_update_gradle_properties() {
+        let content = "group=com.example\nversion=1.0.0\nkotlin.code.style=official\n";
+        let (_dir, path) = named_file("gradle.properties", content);
+
+        update_version_file(&path, &Version::new(1, 0, 1), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "group=com.example\nversion=1.0.1\nkotlin.code.style=official\n"
+        );
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(1, 0, 1)
+        );
+    }
+
+    #[test]
+    fn test_update_build_gradle() {
+        let content = "plugins {\n    id 'java'\n    id 'org.example.tool' version '0.9.0'\n}\n\ngroup 'com.example'\nversion '1.0.0'\n";
+        let (_dir, path) = named_file("build.gradle", content);
+
+        update_version_file(&path, &Version::new(1, 1, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("version '0.9.0'"));
+        assert!(content.contains("\nversion '1.1.0'\n"));
+    }
+
+    #[test]
+    fn test_update_build_gradle_kts() {
+        let content =
+            "plugins {\n    kotlin(\"jvm\") version \"1.9.0\"\n}\n\nversion = \"1.0.0\"\n";
+        let (_dir, path) = named_file("build.gradle.kts", content);
+
+        update_version_file(&path, &Version::new(2, 0, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("kotlin(\"jvm\") version \"1.9.0\""));
+        assert!(content.contains("version = \"2.0.0\""));
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(2, 0, 0)
+        );
+    }
+
+    const POM_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <!-- <version>0.0.0</version> -->
+  <parent>
+    <groupId>com.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>5.0.0</version>
+    <relativePath/>
+  </parent>
+  <artifactId>app</artifactId>
+  <version>1.0.0</version>
+  <dependencies>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>lib</artifactId>
+      <version>3.2.1</version>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+    #[test]
+    fn test_update_pom_xml() {
+        let (_dir, path) = named_file("pom.xml", POM_XML);
+
+        update_version_file(&path, &Version::new(1, 1, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            POM_XML.replace("<version>1.0.0</version>", "<version>1.1.0</version>")
+        );
+    }
+
+    #[test]
+    fn test_read_pom_xml_version() {
+        let (_dir, path) = named_file("pom.xml", POM_XML);
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::new(1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_pom_xml_inherited_version() {
+        let content = "<project>\n  <parent>\n    <version>5.0.0</version>\n  </parent>\n  <artifactId>app</artifactId>\n</project>\n";
+        let (_dir, path) = named_file("pom.xml", content);
+
+        let result = update_version_file(&path, &Version::new(1, 0, 0), false);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_pom_xml_property_version() {
+        let content = "<project>\n  <version>${revision}</version>\n</project>\n";
+        let (_dir, path) = named_file("pom.xml", content);
+
+        let result = read_version_from_file(&path);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    const PUBSPEC_YAML: &str = "name: app
+description: A Flutter app.
+version: 1.0.0+3 # build number
+
+environment:
+  sdk: \">=3.0.0 <4.0.0\"
+
+dependencies:
+  http: ^1.1.0
+";
+
+    #[test]
+    fn test_update_pubspec_yaml() {
+        let (_dir, path) = named_file("pubspec.yaml", PUBSPEC_YAML);
+
+        update_version_file(&path, &Version::new(1, 1, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, PUBSPEC_YAML.replace("1.0.0+3 #", "1.1.0+3 #"));
+
+        update_version_file(&path, &Version::parse("1.2.0+4").unwrap(), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, PUBSPEC_YAML.replace("1.0.0+3 #", "1.2.0+4 #"));
+    }
+
+    #[test]
+    fn test_read_pubspec_yaml_version() {
+        let (_dir, path) = named_file("pubspec.yaml", PUBSPEC_YAML);
+        assert_eq!(
+            read_version_from_file(&path).unwrap(),
+            Version::parse("1.0.0+3").unwrap()
+        );
+    }
+
+    const CHART_YAML: &str = "apiVersion: v2
+name: app
+version: 0.1.0
+appVersion: \"1.0.0\"
+";
+
+    #[test]
+    fn test_update_yaml_key() {
+        let (_dir, path) = named_file("Chart.yaml", CHART_YAML);
+
+        update_yaml_version(&path, "appVersion", &Version::new(1, 2, 0), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, CHART_YAML.replace("\"1.0.0\"", "\"1.2.0\""));
+        assert_eq!(
+            read_yaml_version(&path, "version").unwrap(),
+            Version::new(0, 1, 0)
+        );
+    }
+
+    #[test]
+    fn test_update_yaml_nested_key() {
+        let content = "image:
+  repository: example/app
+  tag: '1.0.0'
+sidecar:
+  tag: 2.0.0
+tag: 3.0.0
+";
+        let (_dir, path) = named_file("values.yaml", content);
+
+        update_yaml_version(&path, "image.tag", &Version::new(1, 0, 1), false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("  tag: '1.0.1'\n"));
+        assert!(content.contains("  tag: 2.0.0\n"));
+        assert!(content.contains("\ntag: 3.0.0\n"));
+    }
+
+    #[test]
+    fn test_yaml_key_not_found() {
+        let (_dir, path) = named_file("Chart.yaml", CHART_YAML);
+
+        let result = update_yaml_version(&path, "image.tag", &Version::new(1, 0, 0), false);
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_yaml_key_without_version() {
+        let (_dir, path) = named_file("Chart.yaml", CHART_YAML);
+
+        let result = read_yaml_version(&path, "name");
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    fn update_with_pattern(content: &str, suffix: &str, pattern: &str) -> String {
+        let mut file = NamedTempFile::with_suffix(suffix).unwrap();
+        write!(file, "{content}").unwrap();
+
+        update_version_with_pattern(file.path(), pattern, &Version::new(2, 0, 0), false).unwrap();
+        fs::read_to_string(file.path()).unwrap()
+    }
+
+    #[test]
+    fn test_pattern_python_module() {
+        let content = update_with_pattern(
+            "\"\"\"Package.\"\"\"\n\n__version__ = \"1.0.0\"\n__author__ = \"me\"\n",
+            ".py",
+            r#"__version__ = "{version}""#,
+        );
+        assert_eq!(
+            content,
+            "\"\"\"Package.\"\"\"\n\n__version__ = \"2.0.0\"\n__author__ = \"me\"\n"
+        );
+    }
+
+    #[test]
+    fn test_pattern_plain_version_file() {
+        let content = update_with_pattern("1.0.0\n", "", "^{version}$");
+        assert_eq!(content, "2.0.0\n");
+    }
+
+    #[test]
+    fn test_pattern_pyproject_toml() {
+        let content = update_with_pattern(
+            "[project]\nname = \"app\"\nversion = \"1.0.0\"\n\n[tool.black]\ntarget-version = \"py311\"\n",
+            ".toml",
+            r#"^version = "{version}""#,
+        );
+        assert!(content.contains("version = \"2.0.0\""));
+        assert!(content.contains("target-version = \"py311\""));
+    }
+
+    #[test]
+    fn test_pattern_build_gradle() {
+        let content = update_with_pattern(
+            "plugins {\n    id 'java'\n}\n\nversion '1.0.0-SNAPSHOT'\n",
+            ".gradle",
+            r"version '{version}'",
+        );
+        assert!(content.contains("version '2.0.0'"));
+    }
+
+    #[test]
+    fn test_pattern_helm_chart() {
+        let content = update_with_pattern(
+            "apiVersion: v2\nname: app\nversion: 1.0.0\nappVersion: \"1.0.0\"\n",
+            ".yaml",
+            r#"appVersion: "{version}""#,
+        );
+        assert!(content.contains("\nversion: 1.0.0\n"));
+        assert!(content.contains(r#"appVersion: "2.0.0""#));
+    }
+
+    #[test]
+    fn test_pattern_replaces_all_matches() {
+        let content = update_with_pattern(
+            "image: app:1.0.0\nsidecar: app:1.0.0\n",
+            ".yaml",
+            "app:{version}",
+        );
+        assert_eq!(content, "image: app:2.0.0\nsidecar: app:2.0.0\n");
+    }
+
+    #[test]
+    fn test_pattern_dry_run() {
+        let mut file = NamedTempFile::with_suffix(".py").unwrap();
+        write!(file, r#"__version__ = "1.0.0""#).unwrap();
+
+        let pattern = r#"__version__ = "{version}""#;
+        update_version_with_pattern(file.path(), pattern, &Version::new(2, 0, 0), true).unwrap();
+
+        let content = fs::read_to_string(file.path()).unwrap();
+        assert_eq!(content, r#"__version__ = "1.0.0""#);
+    }
+
+    #[test]
+    fn test_read_version_with_pattern() {
+        let mut file = NamedTempFile::with_suffix(".py").unwrap();
+        writeln!(file, "__version__ = \"1.2.3-rc.1\"").unwrap();
+
+        let version = read_version_with_pattern(file.path(), r#"__version__ = "{version}""#);
+        assert_eq!(version.unwrap(), Version::parse("1.2.3-rc.1").unwrap());
+    }
+
+    #[test]
+    fn test_pattern_with_groups() {
+        let content = update_with_pattern(
+            "version = \"1.0.0\"\nrelease = \"1.0.0\"\n",
+            ".toml",
+            r#"^(version|release) = "{version}"(\s*#.*)?"#,
+        );
+        assert_eq!(content, "version = \"2.0.0\"\nrelease = \"2.0.0\"\n");
+
+        let mut file = NamedTempFile::with_suffix(".toml").unwrap();
+        writeln!(file, "release = \"1.2.3\"").unwrap();
+        let version = read_version_with_pattern(file.path(), r#"^(version|release) = "{version}""#);
+        assert_eq!(version.unwrap(), Version::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_pattern_not_matching() {
+        let mut file = NamedTempFile::with_suffix(".py").unwrap();
+        writeln!(file, "VERSION = \"1.0.0\"").unwrap();
+
+        let result = update_version_with_pattern(
+            file.path(),
+            r#"__version__ = "{version}""#,
+            &Version::new(2, 0, 0),
+            false,
+        );
+        assert!(matches!(result, Err(FileUpdateError::VersionNotFound(_))));
+    }
+
+    #[test]
+    fn test_pattern_without_placeholder() {
+        let file = NamedTempFile::with_suffix(".py").unwrap();
+        let result = read_version_with_pattern(file.path(), "__version__");
+        assert!(matches!(
+            result,
+            Err(FileUpdateError::InvalidPattern { .. })
+        ));
+    }
+
     #[test]
     fn test_error_display() {
         let err = FileUpdateError::NotFound("test.toml".to_string());
@@ -396,5 +1596,14 @@ name = "test"
             reason: "invalid JSON".to_string(),
         };
         assert_eq!(err.to_string(), "failed to parse test.json: invalid JSON");
+
+        let err = FileUpdateError::InvalidPattern {
+            pattern: "version".to_string(),
+            reason: "missing {version} placeholder".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "invalid version pattern 'version': missing {version} placeholder"
+        );
     }
 }
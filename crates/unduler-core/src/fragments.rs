@@ -0,0 +1,154 @@
+//! Changelog fragments written by hand.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use unduler_commit::ParsedCommit;
+
+/// Extension of fragment files.
+const FRAGMENT_EXTENSION: &str = ".md";
+
+/// A changelog entry written in a fragment file, named `<name>.<type>.md`
+/// (e.g. `123.feat.md`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// Path of the fragment file.
+    pub path: PathBuf,
+    /// Type of the entry, like a commit type (e.g. `feat`).
+    pub r#type: String,
+    /// Text of the entry.
+    pub text: String,
+}
+
+impl Fragment {
+    /// Parses a fragment file, returning `None` for files not named
+    /// `<name>.<type>.md`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        let Some(r#type) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(FRAGMENT_EXTENSION))
+            .and_then(|stem| stem.rsplit_once('.'))
+            .filter(|(name, r#type)| !name.is_empty() && !r#type.is_empty())
+            .map(|(_, r#type)| r#type.to_string())
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            r#type,
+            text: fs::read_to_string(path)?.trim().to_string(),
+        }))
+    }
+
+    /// Returns the changelog entry of the fragment, as a commit without hash
+    /// or author.
+    ///
+    /// Fragments of type `breaking` are breaking changes.
+    #[must_use]
+    pub fn to_commit(&self) -> ParsedCommit {
+        let file_name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        ParsedCommit::builder("", &self.r#type)
+            .message(&self.text)
+            .breaking(self.r#type == "breaking")
+            .metadata(ParsedCommit::FRAGMENT_METADATA, file_name)
+            .build()
+    }
+}
+
+/// Reads the fragments of a directory, in file name order.
+///
+/// A missing directory has no fragments. Files not named `<name>.<type>.md`,
+/// like a `README.md`, and empty fragments are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a fragment cannot be read.
+pub fn read_fragments(dir: &Path) -> io::Result<Vec<Fragment>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut paths = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+
+    let mut fragments = Vec::new();
+    for path in paths.iter().filter(|path| path.is_file()) {
+        if let Some(fragment) = Fragment::read(path)?
+            && !fragment.text.is_empty()
+        {
+            fragments.push(fragment);
+        }
+    }
+    Ok(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn fragments_dir(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_read_fragments() {
+        let dir = fragments_dir(&[
+            ("README.md", "Add a fragment per user-facing change."),
+            ("42.fix.md", "Fix the login redirect.\n"),
+            ("12.feat.md", "Add single sign-on."),
+            ("empty.fix.md", "\n"),
+            ("notes.feat.txt", "Not a fragment."),
+        ]);
+
+        let fragments = read_fragments(dir.path()).unwrap();
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[0].r#type, "feat");
+        assert_eq!(fragments[0].text, "Add single sign-on.");
+        assert_eq!(fragments[1].path, dir.path().join("42.fix.md"));
+        assert_eq!(fragments[1].text, "Fix the login redirect.");
+    }
+
+    #[test]
+    fn test_read_fragments_missing_dir() {
+        let dir = TempDir::new().unwrap();
+        assert!(
+            read_fragments(&dir.path().join("changelog.d"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_to_commit() {
+        let dir = fragments_dir(&[("api.breaking.md", "Remove the v1 API.")]);
+        let fragments = read_fragments(dir.path()).unwrap();
+
+        let commit = fragments[0].to_commit();
+        assert_eq!(commit.r#type, "breaking");
+        assert_eq!(commit.message, "Remove the v1 API.");
+        assert!(commit.breaking);
+        assert!(commit.hash.is_empty());
+        assert_eq!(commit.fragment(), Some("api.breaking.md"));
+    }
+}
@@ -3,14 +3,48 @@
 //! This crate provides the main orchestration logic for version management
 //! and changelog generation.
 
+mod cache;
+mod changelog;
+mod concurrent;
+mod diff;
 mod error;
 mod files;
+mod fragments;
+mod locale;
+mod normalize;
 mod pipeline;
+mod progress;
 mod release;
+mod scopes;
+mod template;
 mod version;
 
+pub use cache::{CACHE_DIR, CommitCache};
+pub use changelog::{
+    CHANGELOG_HEADER, changelog_change, changelog_header, formatter_config, insert_at_marker,
+    insert_changes, insert_release_section, insert_unreleased_section, output_changes,
+    release_file_changes, release_file_path, replace_between_markers, section_body,
+    unreleased_section, update_changelog, update_inserts, update_unreleased_changelog,
+    write_release_file,
+};
+pub use concurrent::try_join_bounded;
+pub use diff::FileChange;
 pub use error::{CoreError, CoreResult};
-pub use files::{FileResult, FileUpdateError, read_version_from_file, update_version_file};
-pub use pipeline::Pipeline;
-pub use release::ReleaseManager;
+pub use files::{
+    FileResult, FileUpdateError, bump_cargo_path_dependencies, bump_version_file,
+    bump_version_with_pattern, bump_yaml_version, read_version_from_file,
+    read_version_with_pattern, read_yaml_version, update_cargo_path_dependencies,
+    update_version_file, update_version_with_pattern, update_yaml_version,
+};
+pub use fragments::{Fragment, read_fragments};
+pub use locale::locale_labels;
+pub use normalize::{NormalizedCommit, Normalizer};
+pub use pipeline::{ChangelogOutput, DEFAULT_HOOK_CONCURRENCY, Pipeline, mark_highlight};
+pub use progress::{ReleaseProgress, ReleaseStep};
+pub use release::{
+    CommitRange, FileUpdate, FileVersion, ReleaseManager, ReleaseOptions, ReleaseOutcome,
+    ReleasePlan, SkippedCommit,
+};
+pub use scopes::ScopeNormalizer;
+pub use template::{render_commit_message, render_tag_message};
 pub use version::VersionManager;
@@ -0,0 +1,121 @@
+//! Built-in translations of the changelog section headings.
+
+/// Section headings in French.
+const FR: &[(&str, &str)] = &[
+    ("feat", "Ajouté"),
+    ("fix", "Corrigé"),
+    ("docs", "Documentation"),
+    ("style", "Style"),
+    ("refactor", "Modifié"),
+    ("perf", "Performances"),
+    ("test", "Tests"),
+    ("build", "Compilation"),
+    ("ci", "Compilation"),
+    ("chore", "Maintenance"),
+    ("revert", "Annulé"),
+    ("deps", "Dépendances"),
+    ("security", "Sécurité"),
+    ("breaking", "Changements incompatibles"),
+    ("highlights", "Points forts"),
+    ("closed_issues", "Tickets fermés"),
+    ("contributors", "Contributeurs"),
+];
+
+/// Section headings in German.
+const DE: &[(&str, &str)] = &[
+    ("feat", "Hinzugefügt"),
+    ("fix", "Behoben"),
+    ("docs", "Dokumentation"),
+    ("style", "Formatierung"),
+    ("refactor", "Geändert"),
+    ("perf", "Leistung"),
+    ("test", "Tests"),
+    ("build", "Build"),
+    ("ci", "Build"),
+    ("chore", "Wartung"),
+    ("revert", "Zurückgenommen"),
+    ("deps", "Abhängigkeiten"),
+    ("security", "Sicherheit"),
+    ("breaking", "Inkompatible Änderungen"),
+    ("highlights", "Höhepunkte"),
+    ("closed_issues", "Geschlossene Issues"),
+    ("contributors", "Mitwirkende"),
+];
+
+/// Section headings in Spanish.
+const ES: &[(&str, &str)] = &[
+    ("feat", "Añadido"),
+    ("fix", "Corregido"),
+    ("docs", "Documentación"),
+    ("style", "Estilo"),
+    ("refactor", "Cambiado"),
+    ("perf", "Rendimiento"),
+    ("test", "Pruebas"),
+    ("build", "Compilación"),
+    ("ci", "Compilación"),
+    ("chore", "Mantenimiento"),
+    ("revert", "Revertido"),
+    ("deps", "Dependencias"),
+    ("security", "Seguridad"),
+    ("breaking", "Cambios incompatibles"),
+    ("highlights", "Destacados"),
+    ("closed_issues", "Incidencias cerradas"),
+    ("contributors", "Colaboradores"),
+];
+
+/// Returns the built-in section headings of a locale, keyed by commit type
+/// or by `highlights`, `closed_issues` and `contributors`.
+///
+/// Regional variants use the headings of their language, so `fr-CA` gets the
+/// French headings. Returns `None` for locales without built-in headings.
+#[must_use]
+pub fn locale_labels(locale: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    let language = locale.split(['-', '_']).next().unwrap_or(locale);
+    match language.to_ascii_lowercase().as_str() {
+        "fr" => Some(FR),
+        "de" => Some(DE),
+        "es" => Some(ES),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(locale: &str, key: &str) -> Option<&'static str> {
+        locale_labels(locale)?
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, label)| *label)
+    }
+
+    #[test]
+    fn test_locale_labels() {
+        assert_eq!(label("fr", "feat"), Some("Ajouté"));
+        assert_eq!(label("de", "fix"), Some("Behoben"));
+        assert_eq!(label("es", "contributors"), Some("Colaboradores"));
+    }
+
+    #[test]
+    fn test_regional_locale() {
+        assert_eq!(label("fr-CA", "feat"), Some("Ajouté"));
+        assert_eq!(label("DE_at", "feat"), Some("Hinzugefügt"));
+    }
+
+    #[test]
+    fn test_unknown_locale() {
+        assert!(locale_labels("en").is_none());
+        assert!(locale_labels("it").is_none());
+    }
+
+    #[test]
+    fn test_locales_cover_same_keys() {
+        fn keys(labels: &[(&'static str, &str)]) -> Vec<&'static str> {
+            labels.iter().map(|(k, _)| *k).collect()
+        }
+
+        assert_eq!(keys(FR), keys(DE));
+        assert_eq!(keys(FR), keys(ES));
+    }
+}
@@ -0,0 +1,342 @@
+//! Normalization of commit messages before parsing.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unduler_commit::{ParsedCommit, RawCommit};
+use unduler_config::NormalizeConfig;
+
+/// Subject of GitHub pull request merges, e.g.
+/// `Merge pull request #12 from owner/branch`.
+static GITHUB_MERGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^Merge pull request #(?P<pr>\d+) from \S+$").expect("invalid regex")
+});
+
+/// Trailer of GitLab merge request merges, e.g.
+/// `See merge request group/project!12`.
+static GITLAB_MERGE_REQUEST_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^See merge request \S+!(?P<pr>\d+)\s*$").expect("invalid regex")
+});
+
+/// Subject of `git merge --squash` commits.
+const SQUASHED_SUBJECT: &str = "Squashed commit of the following:";
+
+/// Subject of reverts, e.g. `Revert "feat: add cache"`.
+static REVERT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^Revert "(?P<subject>.+)"$"#).expect("invalid regex"));
+
+/// Rewrites commit messages that parsers do not recognize, such as merge
+/// commits, into the message they stand for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    merges: bool,
+    squashes: bool,
+    reverts: bool,
+}
+
+impl Normalizer {
+    /// Creates a normalizer leaving every message unchanged.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the normalizer enabled by `[parser.normalize]`.
+    #[must_use]
+    pub fn from_config(config: &NormalizeConfig) -> Self {
+        Self::new()
+            .with_merges(config.merges)
+            .with_squashes(config.squashes)
+            .with_reverts(config.reverts)
+    }
+
+    /// Replaces the message of pull and merge request merges with the
+    /// title and description in their body.
+    #[must_use]
+    pub fn with_merges(mut self, merges: bool) -> Self {
+        self.merges = merges;
+        self
+    }
+
+    /// Replaces the message of `Squashed commit of the following:` commits
+    /// with the message of the newest squashed commit.
+    #[must_use]
+    pub fn with_squashes(mut self, squashes: bool) -> Self {
+        self.squashes = squashes;
+        self
+    }
+
+    /// Rewrites `Revert "<subject>"` subjects as `revert: <subject>`.
+    #[must_use]
+    pub fn with_reverts(mut self, reverts: bool) -> Self {
+        self.reverts = reverts;
+        self
+    }
+
+    /// Returns true if some messages are rewritten.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.merges || self.squashes || self.reverts
+    }
+
+    /// Rewrites the message of a commit, returning `None` if it is left
+    /// unchanged.
+    #[must_use]
+    pub fn normalize(&self, raw: &RawCommit) -> Option<NormalizedCommit> {
+        let mut message = None;
+        let mut pull_request = None;
+
+        if self.merges
+            && let Some((body, pr)) = merge_message(&raw.message)
+        {
+            message = Some(body);
+            pull_request = Some(pr);
+        }
+
+        let current = message.as_deref().unwrap_or(&raw.message);
+        if self.squashes
+            && let Some(squashed) = squashed_message(current)
+        {
+            message = Some(squashed);
+        }
+
+        let current = message.as_deref().unwrap_or(&raw.message);
+        if self.reverts
+            && let Some(revert) = revert_message(current)
+        {
+            message = Some(revert);
+        }
+
+        message.map(|message| NormalizedCommit {
+            commit: RawCommit::new(&raw.hash, message, &raw.author, &raw.email, raw.date),
+            pull_request,
+        })
+    }
+}
+
+/// A commit whose message was rewritten by a [`Normalizer`].
+#[derive(Debug, Clone)]
+pub struct NormalizedCommit {
+    /// The commit with its rewritten message.
+    pub commit: RawCommit,
+    /// The number of the pull or merge request the commit merged.
+    pub pull_request: Option<String>,
+}
+
+impl NormalizedCommit {
+    /// Records the merged pull request in a commit parsed from the
+    /// rewritten message, unless the parser found one.
+    #[must_use]
+    pub fn annotate(&self, mut commit: ParsedCommit) -> ParsedCommit {
+        if let Some(pr) = &self.pull_request {
+            commit
+                .metadata
+                .entry(ParsedCommit::PR_METADATA.to_string())
+                .or_insert_with(|| pr.clone());
+        }
+        commit
+    }
+}
+
+/// Returns the body of a pull or merge request merge, without the GitLab
+/// trailer, and the number of the request.
+fn merge_message(message: &str) -> Option<(String, String)> {
+    let (subject, body) = split_message(message);
+    let body = body.trim();
+
+    if let Some(captures) = GITHUB_MERGE_RE.captures(subject) {
+        return (!body.is_empty()).then(|| (body.to_string(), captures["pr"].to_string()));
+    }
+
+    if subject.starts_with("Merge branch ")
+        && let Some(captures) = GITLAB_MERGE_REQUEST_RE.captures(body)
+    {
+        let body = GITLAB_MERGE_REQUEST_RE.replace(body, "");
+        let body = body.trim();
+        return (!body.is_empty()).then(|| (body.to_string(), captures["pr"].to_string()));
+    }
+
+    None
+}
+
+/// Returns the message of the newest commit listed by a `git merge --squash`
+/// commit, indented by four spaces after its `commit`, `Author` and `Date`
+/// lines.
+fn squashed_message(message: &str) -> Option<String> {
+    let (subject, body) = split_message(message);
+    if subject != SQUASHED_SUBJECT {
+        return None;
+    }
+
+    let lines: Vec<_> = body
+        .lines()
+        .skip_while(|line| !line.starts_with("commit "))
+        .skip(1)
+        .skip_while(|line| !line.starts_with("    "))
+        .take_while(|line| !line.starts_with("commit "))
+        .map(|line| line.strip_prefix("    ").unwrap_or(line))
+        .collect();
+    let squashed = lines.join("\n");
+    let squashed = squashed.trim();
+    (!squashed.is_empty()).then(|| squashed.to_string())
+}
+
+/// Rewrites a `Revert "<subject>"` subject as `revert: <subject>`.
+fn revert_message(message: &str) -> Option<String> {
+    let (subject, _) = split_message(message);
+    let captures = REVERT_RE.captures(subject)?;
+    Some(format!(
+        "revert: {}{}",
+        &captures["subject"],
+        &message[subject.len()..]
+    ))
+}
+
+/// Splits a message into its subject and the rest.
+fn split_message(message: &str) -> (&str, &str) {
+    message.split_once('\n').unwrap_or((message, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn make_raw(message: &str) -> RawCommit {
+        RawCommit::new("abc123", message, "Test", "test@test.com", Utc::now())
+    }
+
+    fn normalize(normalizer: Normalizer, message: &str) -> Option<String> {
+        normalizer
+            .normalize(&make_raw(message))
+            .map(|normalized| normalized.commit.message)
+    }
+
+    fn all() -> Normalizer {
+        Normalizer::new()
+            .with_merges(true)
+            .with_squashes(true)
+            .with_reverts(true)
+    }
+
+    #[test]
+    fn test_disabled() {
+        let normalizer = Normalizer::new();
+        assert!(!normalizer.is_enabled());
+        assert_eq!(
+            normalize(
+                normalizer,
+                "Merge pull request #12 from owner/branch\n\nfeat: add cache"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_github_merge() {
+        let normalized = all()
+            .normalize(&make_raw(
+                "Merge pull request #12 from owner/feature/cache\n\nfeat: add cache\n\nCaches the parsed commits.",
+            ))
+            .unwrap();
+        assert_eq!(
+            normalized.commit.message,
+            "feat: add cache\n\nCaches the parsed commits."
+        );
+        assert_eq!(normalized.pull_request.as_deref(), Some("12"));
+    }
+
+    #[test]
+    fn test_github_merge_without_title() {
+        assert_eq!(
+            normalize(all(), "Merge pull request #12 from owner/branch"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_gitlab_merge() {
+        let normalized = all()
+            .normalize(&make_raw(
+                "Merge branch 'cache' into 'main'\n\nfeat: add cache\n\nSee merge request group/project!34",
+            ))
+            .unwrap();
+        assert_eq!(normalized.commit.message, "feat: add cache");
+        assert_eq!(normalized.pull_request.as_deref(), Some("34"));
+    }
+
+    #[test]
+    fn test_plain_merge_unchanged() {
+        assert_eq!(normalize(all(), "Merge branch 'main' into feature"), None);
+    }
+
+    #[test]
+    fn test_squashed_commit() {
+        let message = "Squashed commit of the following:\n\n\
+            commit 1111111111111111111111111111111111111111\n\
+            Author: Jane Doe <jane@example.com>\n\
+            Date:   Mon Mar 4 10:00:00 2024 +0100\n\n    \
+            feat(api): add pagination\n\n    \
+            Pages hold 50 items.\n\n\
+            commit 2222222222222222222222222222222222222222\n\
+            Author: Jane Doe <jane@example.com>\n\
+            Date:   Mon Mar 4 09:00:00 2024 +0100\n\n    \
+            wip\n";
+        assert_eq!(
+            normalize(all(), message).as_deref(),
+            Some("feat(api): add pagination\n\nPages hold 50 items.")
+        );
+    }
+
+    #[test]
+    fn test_revert() {
+        assert_eq!(
+            normalize(
+                all(),
+                "Revert \"feat: add cache\"\n\nThis reverts commit 1111111."
+            )
+            .as_deref(),
+            Some("revert: feat: add cache\n\nThis reverts commit 1111111.")
+        );
+    }
+
+    #[test]
+    fn test_merged_revert() {
+        let normalized = all()
+            .normalize(&make_raw(
+                "Merge pull request #13 from owner/revert-12-cache\n\nRevert \"feat: add cache\"",
+            ))
+            .unwrap();
+        assert_eq!(normalized.commit.message, "revert: feat: add cache");
+        assert_eq!(normalized.pull_request.as_deref(), Some("13"));
+    }
+
+    #[test]
+    fn test_only_enabled_rules() {
+        let normalizer = Normalizer::new().with_reverts(true);
+        assert_eq!(
+            normalize(
+                normalizer,
+                "Merge pull request #12 from owner/branch\n\nfeat: add cache"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_annotate_keeps_parsed_pull_request() {
+        let normalized = all()
+            .normalize(&make_raw(
+                "Merge pull request #12 from owner/branch\n\nfeat: add cache",
+            ))
+            .unwrap();
+
+        let commit = ParsedCommit::builder("abc123", "feat").build();
+        assert_eq!(normalized.annotate(commit).pull_request(), Some("12"));
+
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .metadata(ParsedCommit::PR_METADATA, "7")
+            .build();
+        assert_eq!(normalized.annotate(commit).pull_request(), Some("7"));
+    }
+}
@@ -1,8 +1,32 @@
 //! Plugin pipeline execution.
 
-use tracing::info;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use semver::Version;
+use tracing::{Instrument, debug, info, info_span, instrument, warn};
 use unduler_commit::{ParsedCommit, RawCommit};
-use unduler_plugin::{BumpStrategy, BumpType, ChangelogFormatter, CommitParser, ReleaseHook};
+use unduler_config::HookEntry;
+use unduler_plugin::{
+    AsyncReleaseHook, BumpReason, BumpStrategy, BumpType, ChangelogFormatter, CommitParser,
+    HookStage, Plugin, PluginResult, ReleaseContext, ReleaseHook,
+};
+
+use crate::normalize::{NormalizedCommit, Normalizer};
+use crate::scopes::ScopeNormalizer;
+use crate::try_join_bounded;
+
+/// Default number of asynchronous hooks running at the same time.
+pub const DEFAULT_HOOK_CONCURRENCY: usize = 4;
+
+/// A file a release is rendered to by a formatter of its own, besides the
+/// changelog.
+pub struct ChangelogOutput {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Formatter rendering the release.
+    pub formatter: Box<dyn ChangelogFormatter>,
+}
 
 /// Orchestrates plugin execution.
 pub struct Pipeline {
@@ -10,6 +34,13 @@ pub struct Pipeline {
     bumper: Box<dyn BumpStrategy>,
     formatter: Box<dyn ChangelogFormatter>,
     hooks: Vec<Box<dyn ReleaseHook>>,
+    async_hooks: Vec<Box<dyn AsyncReleaseHook>>,
+    hook_entries: HashMap<HookStage, Vec<HookEntry>>,
+    concurrency: usize,
+    highlight_marker: Option<String>,
+    normalizer: Normalizer,
+    scopes: ScopeNormalizer,
+    outputs: Vec<ChangelogOutput>,
 }
 
 impl Pipeline {
@@ -25,6 +56,13 @@ impl Pipeline {
             bumper,
             formatter,
             hooks: Vec::new(),
+            async_hooks: Vec::new(),
+            hook_entries: HashMap::new(),
+            concurrency: DEFAULT_HOOK_CONCURRENCY,
+            highlight_marker: None,
+            normalizer: Normalizer::new(),
+            scopes: ScopeNormalizer::new(),
+            outputs: Vec::new(),
         }
     }
 
@@ -35,45 +73,337 @@ impl Pipeline {
         self
     }
 
+    /// Adds an asynchronous release hook.
+    #[must_use]
+    pub fn with_async_hook(mut self, hook: Box<dyn AsyncReleaseHook>) -> Self {
+        self.async_hooks.push(hook);
+        self
+    }
+
+    /// Sets the hooks listed at a stage, from `[hooks]`.
+    ///
+    /// Hooks listed at some stage only run at the stages listing them, in
+    /// list order, when their condition holds.
+    #[must_use]
+    pub fn with_hook_entries(mut self, stage: HookStage, entries: Vec<HookEntry>) -> Self {
+        self.hook_entries.insert(stage, entries);
+        self
+    }
+
+    /// Sets how many asynchronous hooks run at the same time.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets the marker flagging commits as highlights when found in their
+    /// body (e.g. `[highlight]`).
+    #[must_use]
+    pub fn with_highlight_marker(mut self, marker: impl Into<String>) -> Self {
+        self.highlight_marker = Some(marker.into());
+        self
+    }
+
+    /// Sets the normalizer rewriting commit messages before parsing, e.g.
+    /// merge commits into the pull request they merged.
+    #[must_use]
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Sets the normalizer rewriting the scopes of parsed commits into
+    /// canonical scopes, seen by the bumper and the formatter.
+    #[must_use]
+    pub fn with_scope_normalizer(mut self, scopes: ScopeNormalizer) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Adds a file the release is rendered to by another formatter, e.g. a
+    /// JSON summary of the release.
+    #[must_use]
+    pub fn with_output(
+        mut self,
+        path: impl Into<PathBuf>,
+        formatter: Box<dyn ChangelogFormatter>,
+    ) -> Self {
+        self.outputs.push(ChangelogOutput {
+            path: path.into(),
+            formatter,
+        });
+        self
+    }
+
     /// Parses raw commits using the configured parser.
+    #[instrument(
+        skip_all,
+        fields(parser = self.parser.name(), count = raw_commits.len())
+    )]
     pub fn parse_commits(&self, raw_commits: &[RawCommit]) -> Vec<ParsedCommit> {
-        raw_commits
+        self.parse_batch(raw_commits)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Parses raw commits in a single batch of the configured parser,
+    /// returning the result for each of them, in order.
+    pub fn parse_batch(&self, raw_commits: &[RawCommit]) -> Vec<Option<ParsedCommit>> {
+        let normalized: Vec<_> = raw_commits
             .iter()
-            .filter_map(|raw| {
-                let parsed = self.parser.parse(raw);
-                if parsed.is_none() {
-                    info!(
-                        hash = %raw.short_hash(),
-                        subject = %raw.subject(),
-                        "skipping unparseable commit"
-                    );
-                }
-                parsed
+            .map(|raw| self.normalizer.normalize(raw))
+            .collect();
+        let messages: Vec<RawCommit> = raw_commits
+            .iter()
+            .zip(&normalized)
+            .map(|(raw, normalized)| {
+                normalized
+                    .as_ref()
+                    .map_or_else(|| raw.clone(), |normalized| normalized.commit.clone())
             })
+            .collect();
+        raw_commits
+            .iter()
+            .zip(normalized)
+            .zip(self.parser.parse_batch(&messages))
+            .map(|((raw, normalized), parsed)| self.finish(raw, normalized.as_ref(), parsed))
             .collect()
     }
 
+    /// Parses a single raw commit using the configured parser.
+    pub fn parse_commit(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+        let normalized = self.normalizer.normalize(raw);
+        let message = normalized
+            .as_ref()
+            .map_or(raw, |normalized| &normalized.commit);
+        let parsed = self.parser.parse(message);
+        self.finish(raw, normalized.as_ref(), parsed)
+    }
+
+    /// Completes a commit parsed from its normalized message.
+    fn finish(
+        &self,
+        raw: &RawCommit,
+        normalized: Option<&NormalizedCommit>,
+        parsed: Option<ParsedCommit>,
+    ) -> Option<ParsedCommit> {
+        let parsed = match normalized {
+            Some(normalized) => {
+                debug!(
+                    hash = %raw.short_hash(),
+                    subject = %normalized.commit.subject(),
+                    "normalized commit message"
+                );
+                parsed.map(|commit| normalized.annotate(commit))
+            }
+            None => parsed,
+        };
+        let parsed = parsed.map(|commit| self.scopes.apply(commit));
+        if parsed.is_none() {
+            info!(
+                hash = %raw.short_hash(),
+                subject = %raw.subject(),
+                "skipping unparseable commit"
+            );
+        }
+        match &self.highlight_marker {
+            Some(marker) => parsed.map(|commit| mark_highlight(commit, raw, marker)),
+            None => parsed,
+        }
+    }
+
+    /// Rewrites the scope of a commit not parsed by the pipeline, like a
+    /// changelog fragment, into its canonical scope.
+    #[must_use]
+    pub fn normalize_scope(&self, commit: ParsedCommit) -> ParsedCommit {
+        self.scopes.apply(commit)
+    }
+
+    /// Returns the inputs of [`parse_commit`](Self::parse_commit), so that
+    /// commits parsed by one pipeline are not reused by another parsing them
+    /// differently.
+    pub(crate) fn parsing_key(&self) -> String {
+        format!(
+            "{}\0{}\0{:?}\0{:?}\0{:?}",
+            self.parser.name(),
+            self.parser.version(),
+            self.highlight_marker,
+            self.normalizer,
+            self.scopes
+        )
+    }
+
     /// Determines the bump type using the configured bumper.
+    #[instrument(skip_all, fields(bumper = self.bumper.name()))]
     pub fn determine_bump(&self, commits: &[ParsedCommit]) -> BumpType {
         self.bumper.determine(commits)
     }
 
+    /// Explains the bump triggered by each commit on top of the current
+    /// version, using the configured bumper.
+    pub fn explain_bump(&self, current: &Version, commits: &[ParsedCommit]) -> Vec<BumpReason> {
+        self.bumper.explain(current, commits)
+    }
+
+    /// Returns the next version computed by the configured bumper, if any.
+    ///
+    /// When `None`, the version is bumped according to [`determine_bump`](Self::determine_bump).
+    #[instrument(skip_all, fields(bumper = self.bumper.name(), current = %current))]
+    pub fn next_version(&self, current: &Version, commits: &[ParsedCommit]) -> Option<Version> {
+        self.bumper.next_version(current, commits)
+    }
+
     /// Returns a reference to the formatter.
     pub fn formatter(&self) -> &dyn ChangelogFormatter {
         self.formatter.as_ref()
     }
 
+    /// Returns the files the release is rendered to besides the changelog.
+    pub fn outputs(&self) -> &[ChangelogOutput] {
+        &self.outputs
+    }
+
     /// Returns a reference to the hooks.
     pub fn hooks(&self) -> &[Box<dyn ReleaseHook>] {
         &self.hooks
     }
+
+    /// Returns a reference to the asynchronous hooks.
+    pub fn async_hooks(&self) -> &[Box<dyn AsyncReleaseHook>] {
+        &self.async_hooks
+    }
+
+    /// Returns how many asynchronous hooks run at the same time.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Returns the hooks listed at a stage.
+    pub fn hook_entries(&self, stage: HookStage) -> &[HookEntry] {
+        self.hook_entries.get(&stage).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the hooks to run at a stage, with their entry: the hooks
+    /// listed at the stage whose condition holds, in list order, then the
+    /// hooks listed at no stage.
+    ///
+    /// Entries naming none of `hooks` are skipped, as they name a hook of
+    /// the other kind: names matching no hook at all are rejected when the
+    /// pipeline is created from the configuration.
+    fn schedule<'a, H: Plugin + ?Sized>(
+        &'a self,
+        hooks: &'a [Box<H>],
+        stage: HookStage,
+        ctx: &ReleaseContext,
+    ) -> Vec<(&'a H, Option<&'a HookEntry>)> {
+        let listed = self
+            .hook_entries(stage)
+            .iter()
+            .filter(|entry| {
+                let applies =
+                    entry.applies(ctx.is_prerelease(), ctx.dry_run, ctx.branch.as_deref());
+                if !applies {
+                    debug!(%stage, hook = %entry.name, "skipping hook, condition does not hold");
+                }
+                applies
+            })
+            .filter_map(|entry| {
+                let hook = hooks.iter().find(|hook| hook.name() == entry.name)?;
+                Some((hook.as_ref(), Some(entry)))
+            });
+        let unlisted = hooks
+            .iter()
+            .filter(|hook| {
+                !self
+                    .hook_entries
+                    .values()
+                    .flatten()
+                    .any(|entry| entry.name == hook.name())
+            })
+            .map(|hook| (hook.as_ref(), None));
+        listed.chain(unlisted).collect()
+    }
+
+    /// Runs the hooks of a stage.
+    ///
+    /// Hooks run in order, then asynchronous hooks run concurrently, at most
+    /// [`concurrency`](Self::concurrency) at a time. Each hook sees its
+    /// `config` table in [`ReleaseContext::config`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a hook not set to continue on error.
+    #[instrument(skip_all, fields(stage = %stage))]
+    pub async fn run_hooks(&self, stage: HookStage, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        for (hook, entry) in self.schedule(&self.hooks, stage, ctx) {
+            let _span = info_span!("hook", name = hook.name()).entered();
+            debug!(%stage, "running hook");
+            ctx.config = entry.and_then(|entry| entry.config.clone());
+            let result = stage.run(hook, ctx);
+            ctx.config = None;
+            tolerate(result, entry)?;
+        }
+
+        let ctx = &*ctx;
+        let futures = self
+            .schedule(&self.async_hooks, stage, ctx)
+            .into_iter()
+            .map(|(hook, entry)| {
+                let span = info_span!("hook", name = hook.name());
+                span.in_scope(|| debug!(%stage, "running async hook"));
+                async move {
+                    let configured = entry.and_then(|entry| entry.config.clone()).map(|config| {
+                        let mut configured = ctx.clone();
+                        configured.config = Some(config);
+                        configured
+                    });
+                    let result = stage
+                        .run_async(hook, configured.as_ref().unwrap_or(ctx))
+                        .await;
+                    tolerate(result, entry)
+                }
+                .instrument(span)
+            });
+        try_join_bounded(futures, self.concurrency).await?;
+
+        Ok(())
+    }
+}
+
+/// Ignores the error of a hook set to continue on error, logging it.
+fn tolerate(result: PluginResult<()>, entry: Option<&HookEntry>) -> PluginResult<()> {
+    match result {
+        Err(error) if entry.is_some_and(|entry| entry.continue_on_error) => {
+            warn!(%error, "hook failed, continuing");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// Flags a commit as a highlight when the body of its raw commit contains
+/// a marker.
+#[must_use]
+pub fn mark_highlight(mut commit: ParsedCommit, raw: &RawCommit, marker: &str) -> ParsedCommit {
+    if !marker.is_empty() && raw.body().is_some_and(|body| body.contains(marker)) {
+        commit.metadata.insert(
+            ParsedCommit::HIGHLIGHT_METADATA.to_string(),
+            "true".to_string(),
+        );
+    }
+    commit
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
-    use unduler_plugin::{FormatterConfig, Plugin, PluginResult, Release, ReleaseContext};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use unduler_config::HookCondition;
+    use unduler_plugin::{BoxFuture, FormatterConfig, PluginError, Release};
 
     fn make_raw(hash: &str, message: &str) -> RawCommit {
         RawCommit::new(hash, message, "Test Author", "test@example.com", Utc::now())
@@ -107,6 +437,52 @@ mod tests {
         }
     }
 
+    // Mock parser using the message as the scope of a feature
+    struct ScopedParser;
+
+    impl Plugin for ScopedParser {
+        fn name(&self) -> &'static str {
+            "scoped-parser"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl CommitParser for ScopedParser {
+        fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+            Some(
+                ParsedCommit::builder(&raw.hash, "feat")
+                    .scope(&raw.message)
+                    .build(),
+            )
+        }
+    }
+
+    /// Parses commits as `batch` commits only through `parse_batch`.
+    struct BatchParser;
+
+    impl Plugin for BatchParser {
+        fn name(&self) -> &'static str {
+            "batch-parser"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl CommitParser for BatchParser {
+        fn parse(&self, _raw: &RawCommit) -> Option<ParsedCommit> {
+            None
+        }
+
+        fn parse_batch(&self, raws: &[RawCommit]) -> Vec<Option<ParsedCommit>> {
+            raws.iter()
+                .map(|raw| Some(ParsedCommit::builder(&raw.hash, "batch").build()))
+                .collect()
+        }
+    }
+
     // Mock bumper that returns Minor for feat, Patch for fix
     struct MockBumper;
 
@@ -134,6 +510,30 @@ mod tests {
         }
     }
 
+    // Mock bumper computing a build-number version
+    struct MockVersionBumper;
+
+    impl Plugin for MockVersionBumper {
+        fn name(&self) -> &'static str {
+            "mock-version-bumper"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl BumpStrategy for MockVersionBumper {
+        fn determine(&self, _commits: &[ParsedCommit]) -> BumpType {
+            BumpType::Patch
+        }
+
+        fn next_version(&self, current: &Version, commits: &[ParsedCommit]) -> Option<Version> {
+            let mut next = current.clone();
+            next.patch += commits.len() as u64;
+            Some(next)
+        }
+    }
+
     // Mock formatter
     struct MockFormatter;
 
@@ -171,11 +571,102 @@ mod tests {
     }
 
     impl ReleaseHook for MockHook {
-        fn on_pre_bump(&self, _ctx: &mut ReleaseContext) -> PluginResult<()> {
+        fn on_pre_bump(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+            ctx.set_metadata(self.name, serde_json::Value::Bool(true));
             Ok(())
         }
     }
 
+    // Mock async hook requiring the hooks to have run first
+    struct MockAsyncHook<'a> {
+        calls: &'a AtomicUsize,
+    }
+
+    impl Plugin for MockAsyncHook<'_> {
+        fn name(&self) -> &'static str {
+            "mock-async-hook"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl AsyncReleaseHook for MockAsyncHook<'_> {
+        fn on_pre_bump<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+            Box::pin(async move {
+                if ctx.get_metadata("hook1").is_none() {
+                    return Err(PluginError::ExecutionFailed("hook1 did not run".into()));
+                }
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    // Mock hook recording the order of the hooks and their configuration
+    struct RecordingHook {
+        name: &'static str,
+        fail: bool,
+    }
+
+    impl Plugin for RecordingHook {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl ReleaseHook for RecordingHook {
+        fn on_post_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+            let mut order = ctx.get_metadata("order").cloned().unwrap_or(json!([]));
+            order.as_array_mut().unwrap().push(json!(self.name));
+            ctx.set_metadata("order", order);
+            if let Some(config) = ctx.config.clone() {
+                ctx.set_metadata(self.name, config);
+            }
+            if self.fail {
+                return Err(PluginError::ExecutionFailed(format!(
+                    "{} failed",
+                    self.name
+                )));
+            }
+            Ok(())
+        }
+    }
+
+    fn recording_pipeline(entries: Vec<HookEntry>) -> Pipeline {
+        Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_hook(Box::new(RecordingHook {
+            name: "unlisted",
+            fail: false,
+        }))
+        .with_hook(Box::new(RecordingHook {
+            name: "notify",
+            fail: false,
+        }))
+        .with_hook(Box::new(RecordingHook {
+            name: "github-release",
+            fail: true,
+        }))
+        .with_hook_entries(HookStage::PostTag, entries)
+    }
+
+    fn make_context() -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+    }
+
     #[test]
     fn test_new() {
         let pipeline = Pipeline::new(
@@ -238,6 +729,81 @@ mod tests {
         assert_eq!(parsed.len(), 2);
     }
 
+    #[test]
+    fn test_parse_commits_marks_highlights() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_highlight_marker("[highlight]");
+
+        let raw_commits = vec![
+            make_raw("abc1234", "feat: new engine\n\n[highlight] Twice as fast."),
+            make_raw("def5678", "fix: typo"),
+        ];
+
+        let parsed = pipeline.parse_commits(&raw_commits);
+        assert!(parsed[0].is_highlight());
+        assert!(!parsed[1].is_highlight());
+    }
+
+    #[test]
+    fn test_parse_commits_normalizes_merges() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_normalizer(Normalizer::new().with_merges(true));
+
+        let raw_commits = vec![
+            make_raw(
+                "abc1234",
+                "Merge pull request #12 from owner/cache\n\nfeat: add cache",
+            ),
+            make_raw("def5678", "Merge branch 'main' into cache"),
+        ];
+
+        let parsed = pipeline.parse_commits(&raw_commits);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].hash, "abc1234");
+        assert_eq!(parsed[0].r#type, "feat");
+        assert_eq!(parsed[0].pull_request(), Some("12"));
+    }
+
+    #[test]
+    fn test_parse_commits_normalizes_scopes() {
+        let pipeline = Pipeline::new(
+            Box::new(ScopedParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_scope_normalizer(ScopeNormalizer::new().with_alias("ui", "frontend"));
+
+        let parsed = pipeline.parse_commits(&[make_raw("abc1234", "ui")]);
+        assert_eq!(parsed[0].scope.as_deref(), Some("frontend"));
+    }
+
+    #[test]
+    fn test_normalize_scope() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_scope_normalizer(ScopeNormalizer::new().with_alias("ui", "frontend"));
+
+        let commit = ParsedCommit::builder("", "feat")
+            .scope("ui")
+            .message("dark mode")
+            .build();
+        assert_eq!(
+            pipeline.normalize_scope(commit).scope.as_deref(),
+            Some("frontend")
+        );
+    }
+
     #[test]
     fn test_parse_commits_empty() {
         let pipeline = Pipeline::new(
@@ -251,6 +817,30 @@ mod tests {
         assert!(parsed.is_empty());
     }
 
+    #[test]
+    fn test_parse_commits_in_batch() {
+        let pipeline = Pipeline::new(
+            Box::new(BatchParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_scope_normalizer(ScopeNormalizer::new());
+
+        let parsed = pipeline.parse_commits(&[make_raw("abc1234", "a"), make_raw("def5678", "b")]);
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.iter().all(|commit| commit.r#type == "batch"));
+    }
+
+    #[test]
+    fn test_parsing_key_follows_parser_version() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        );
+        assert!(pipeline.parsing_key().starts_with("mock-parser\x001.0.0\0"));
+    }
+
     #[test]
     fn test_determine_bump() {
         let pipeline = Pipeline::new(
@@ -290,6 +880,40 @@ mod tests {
         assert_eq!(bump, BumpType::Patch);
     }
 
+    #[test]
+    fn test_next_version_defaults_to_none() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        );
+
+        let commits = vec![ParsedCommit::builder("abc123", "feat").build()];
+        assert!(
+            pipeline
+                .next_version(&Version::new(1, 0, 0), &commits)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_next_version_from_bumper() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockVersionBumper),
+            Box::new(MockFormatter),
+        );
+
+        let commits = vec![
+            ParsedCommit::builder("abc123", "feat").build(),
+            ParsedCommit::builder("def456", "fix").build(),
+        ];
+        assert_eq!(
+            pipeline.next_version(&Version::new(1, 0, 0), &commits),
+            Some(Version::new(1, 0, 2))
+        );
+    }
+
     #[test]
     fn test_formatter() {
         let pipeline = Pipeline::new(
@@ -312,4 +936,116 @@ mod tests {
 
         assert!(pipeline.hooks().is_empty());
     }
+
+    #[test]
+    fn test_default_concurrency() {
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        );
+
+        assert!(pipeline.async_hooks().is_empty());
+        assert_eq!(pipeline.concurrency(), DEFAULT_HOOK_CONCURRENCY);
+        assert_eq!(pipeline.with_concurrency(8).concurrency(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_hook(Box::new(MockHook { name: "hook1" }))
+        .with_async_hook(Box::new(MockAsyncHook { calls: &CALLS }))
+        .with_async_hook(Box::new(MockAsyncHook { calls: &CALLS }));
+
+        let mut ctx = make_context();
+        pipeline
+            .run_hooks(HookStage::PreBump, &mut ctx)
+            .await
+            .unwrap();
+
+        assert!(ctx.get_metadata("hook1").is_some());
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks_async_error() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        // Without hook1, the async hook fails
+        let pipeline = Pipeline::new(
+            Box::new(MockParser),
+            Box::new(MockBumper),
+            Box::new(MockFormatter),
+        )
+        .with_async_hook(Box::new(MockAsyncHook { calls: &CALLS }));
+
+        let mut ctx = make_context();
+        let result = pipeline.run_hooks(HookStage::PreBump, &mut ctx).await;
+        assert!(result.is_err());
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks_in_entry_order() {
+        let mut release = HookEntry::new("github-release");
+        release.continue_on_error = true;
+        release.config = Some(json!({ "draft": true }));
+        let pipeline = recording_pipeline(vec![release, HookEntry::new("notify")]);
+
+        let mut ctx = make_context();
+        pipeline
+            .run_hooks(HookStage::PostTag, &mut ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ctx.get_metadata("order"),
+            Some(&json!(["github-release", "notify", "unlisted"]))
+        );
+        assert_eq!(
+            ctx.get_metadata("github-release"),
+            Some(&json!({ "draft": true }))
+        );
+        assert!(ctx.get_metadata("notify").is_none());
+        assert!(ctx.config.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks_skips_unmet_conditions() {
+        let mut notify = HookEntry::new("notify");
+        notify.condition = Some(HookCondition::try_from("!prerelease".to_string()).unwrap());
+        let pipeline = recording_pipeline(vec![notify, HookEntry::new("github-release")]);
+
+        let mut ctx = make_context();
+        ctx.next_version = Version::parse("1.1.0-rc.1").unwrap();
+        let result = pipeline.run_hooks(HookStage::PostTag, &mut ctx).await;
+
+        assert!(result.is_err());
+        assert_eq!(ctx.get_metadata("order"), Some(&json!(["github-release"])));
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks_listed_at_other_stage() {
+        let pipeline = recording_pipeline(Vec::new())
+            .with_hook_entries(HookStage::PreTag, vec![HookEntry::new("github-release")]);
+
+        let mut ctx = make_context();
+        pipeline
+            .run_hooks(HookStage::PostTag, &mut ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ctx.get_metadata("order"),
+            Some(&json!(["unlisted", "notify"]))
+        );
+        assert_eq!(pipeline.hook_entries(HookStage::PreTag).len(), 1);
+        assert!(pipeline.hook_entries(HookStage::PostTag).is_empty());
+    }
 }
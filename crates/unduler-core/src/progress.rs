@@ -0,0 +1,49 @@
+//! Progress of a release, reported between its steps.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CoreResult, ReleaseOutcome};
+
+/// Step of a release, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseStep {
+    /// Version files updated.
+    VersionFiles,
+    /// Changelog written.
+    Changelog,
+    /// Release commit created.
+    Commit,
+    /// Release tag created.
+    Tag,
+    /// Release commit and tag pushed.
+    Push,
+}
+
+/// Observer of the steps of a release.
+///
+/// Callers report the steps as they run, and can resume a failed release
+/// by marking the steps it completed as done: they are skipped.
+pub trait ReleaseProgress {
+    /// Returns true if a step was completed by an earlier attempt of the
+    /// release, so that it is skipped.
+    fn is_done(&self, _step: ReleaseStep) -> bool {
+        false
+    }
+
+    /// Called before a step runs.
+    fn started(&mut self, _step: ReleaseStep) {}
+
+    /// Called once a step completed, with the outcome of the release so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error to abort the release, e.g. if the progress cannot
+    /// be saved.
+    fn completed(&mut self, _step: ReleaseStep, _outcome: &ReleaseOutcome) -> CoreResult<()> {
+        Ok(())
+    }
+}
+
+/// Runs every step, reporting nothing.
+impl ReleaseProgress for () {}
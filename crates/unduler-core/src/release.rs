@@ -1,153 +1,1926 @@
 //! Release orchestration.
 
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
 use semver::Version;
-use tracing::{debug, info};
-use unduler_git::Repository;
-use unduler_plugin::{FormatterConfig, Release, ReleaseContext};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument, warn};
+use unduler_commit::{ParsedCommit, RawCommit};
+use unduler_config::{
+    ChangelogConfig, ChangelogInsertConfig, ChannelConfig, Config, ReleaseFilesConfig,
+    VersionConfig, VersionFileConfig,
+};
+use unduler_git::{CommitOptions, GitResult, Identity, NewCommitOptions, Repository, TagFormat};
+use unduler_plugin::{BumpType, FormatterConfig, HookStage, Release, ReleaseContext};
+
+use crate::{
+    CommitCache, CoreError, CoreResult, FileChange, FileResult, Fragment, Pipeline,
+    ReleaseProgress, ReleaseStep, VersionManager, bump_cargo_path_dependencies, bump_version_file,
+    bump_version_with_pattern, bump_yaml_version, changelog_change, changelog_header,
+    formatter_config, insert_changes, output_changes, read_fragments, read_version_from_file,
+    read_version_with_pattern, read_yaml_version, release_file_changes, release_file_path,
+    render_commit_message, render_tag_message,
+};
+
+/// Options controlling a release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseOptions {
+    dry_run: bool,
+    bump_type: Option<BumpType>,
+    changelog: bool,
+    commit: bool,
+    tag: bool,
+    push: bool,
+    allow_branch: bool,
+    allow_same_version: bool,
+    no_verify: bool,
+    tag_message: Option<String>,
+    changelog_section: Option<String>,
+}
+
+impl Default for ReleaseOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            bump_type: None,
+            changelog: true,
+            commit: true,
+            tag: true,
+            push: false,
+            allow_branch: false,
+            allow_same_version: false,
+            no_verify: false,
+            tag_message: None,
+            changelog_section: None,
+        }
+    }
+}
+
+impl ReleaseOptions {
+    /// Creates options for a full release: changelog, commit and tag, without push.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports what would be done without changing anything.
+    #[must_use]
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Forces the bump type instead of determining it from commits.
+    #[must_use]
+    pub fn with_bump_type(mut self, bump_type: Option<BumpType>) -> Self {
+        self.bump_type = bump_type;
+        self
+    }
+
+    /// Writes the changelog.
+    #[must_use]
+    pub fn with_changelog(mut self, changelog: bool) -> Self {
+        self.changelog = changelog;
+        self
+    }
+
+    /// Creates the release commit.
+    #[must_use]
+    pub fn with_commit(mut self, commit: bool) -> Self {
+        self.commit = commit;
+        self
+    }
+
+    /// Creates the release tag.
+    #[must_use]
+    pub fn with_tag(mut self, tag: bool) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    /// Pushes the release commit and tag.
+    #[must_use]
+    pub fn with_push(mut self, push: bool) -> Self {
+        self.push = push;
+        self
+    }
+
+    /// Skips the release branch check.
+    #[must_use]
+    pub fn with_allow_branch(mut self, allow_branch: bool) -> Self {
+        self.allow_branch = allow_branch;
+        self
+    }
+
+    /// Allows releasing the same version as the latest tag.
+    #[must_use]
+    pub fn with_allow_same_version(mut self, allow_same_version: bool) -> Self {
+        self.allow_same_version = allow_same_version;
+        self
+    }
+
+    /// Skips the commit hooks of the release commit.
+    #[must_use]
+    pub fn with_no_verify(mut self, no_verify: bool) -> Self {
+        self.no_verify = no_verify;
+        self
+    }
+
+    /// Sets the annotated tag message, instead of rendering
+    /// `version.tag_message`.
+    #[must_use]
+    pub fn with_tag_message(mut self, tag_message: Option<String>) -> Self {
+        self.tag_message = tag_message;
+        self
+    }
+
+    /// Sets the changelog section of the release, e.g. edited by the user,
+    /// instead of formatting it.
+    #[must_use]
+    pub fn with_changelog_section(mut self, section: Option<String>) -> Self {
+        self.changelog_section = section;
+        self
+    }
+
+    /// Returns whether this is a dry run.
+    #[must_use]
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Returns the forced bump type.
+    #[must_use]
+    pub fn bump_type(&self) -> Option<BumpType> {
+        self.bump_type
+    }
+
+    /// Returns whether the changelog is written.
+    #[must_use]
+    pub fn changelog(&self) -> bool {
+        self.changelog
+    }
+
+    /// Returns whether the release commit is created.
+    #[must_use]
+    pub fn commit(&self) -> bool {
+        self.commit
+    }
+
+    /// Returns whether the release tag is created.
+    #[must_use]
+    pub fn tag(&self) -> bool {
+        self.tag
+    }
+
+    /// Returns whether the release is pushed.
+    #[must_use]
+    pub fn push(&self) -> bool {
+        self.push
+    }
+
+    /// Returns whether the release branch check is skipped.
+    #[must_use]
+    pub fn allow_branch(&self) -> bool {
+        self.allow_branch
+    }
+
+    /// Returns whether the same version as the latest tag can be released.
+    #[must_use]
+    pub fn allow_same_version(&self) -> bool {
+        self.allow_same_version
+    }
+
+    /// Returns whether the commit hooks are skipped.
+    #[must_use]
+    pub fn no_verify(&self) -> bool {
+        self.no_verify
+    }
+
+    /// Returns the annotated tag message set instead of the template.
+    #[must_use]
+    pub fn tag_message(&self) -> Option<&str> {
+        self.tag_message.as_deref()
+    }
+
+    /// Returns the changelog section set instead of the formatted one.
+    #[must_use]
+    pub fn changelog_section(&self) -> Option<&str> {
+        self.changelog_section.as_deref()
+    }
+}
+
+/// Version and commits of an upcoming release.
+#[derive(Debug, Clone)]
+pub struct ReleasePlan {
+    /// Latest release tag, `None` for a first release.
+    pub latest_tag: Option<String>,
+    /// Version of the latest release, `0.0.0` for a first release.
+    pub previous_version: Version,
+    /// Version to release.
+    pub new_version: Version,
+    /// Bump type applied.
+    pub bump_type: BumpType,
+    /// Number of commits since the latest release, parseable or not.
+    pub raw_commit_count: usize,
+    /// Commits since the latest release, followed by the entries of the
+    /// changelog fragments.
+    pub commits: Vec<ParsedCommit>,
+    /// Changelog fragment files, removed once the changelog is written.
+    pub fragments: Vec<PathBuf>,
+    /// Commits since the latest release the parser could not parse.
+    pub skipped: Vec<SkippedCommit>,
+    /// Commit HEAD pointed to when the release was planned, `None` on an
+    /// unborn branch.
+    pub head: Option<String>,
+}
+
+impl ReleasePlan {
+    /// Returns true if there is no previous release.
+    #[must_use]
+    pub fn is_first_release(&self) -> bool {
+        self.latest_tag.is_none()
+    }
+
+    /// Returns the commits the release was planned from, to plan it again
+    /// with [`ReleaseManager::plan_version`].
+    #[must_use]
+    pub fn commit_range(&self) -> CommitRange {
+        CommitRange {
+            latest_tag: self.latest_tag.clone(),
+            head: self.head.clone(),
+        }
+    }
+}
+
+/// Commits a release was planned from: the commits after its latest tag, up
+/// to HEAD at the time.
+///
+/// Resuming a release from its range leaves out the commits it created
+/// since, e.g. its release commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitRange {
+    /// Latest release tag, `None` for a first release.
+    pub latest_tag: Option<String>,
+    /// Last commit of the release, `None` on an unborn branch.
+    pub head: Option<String>,
+}
+
+/// Commit the parser could not parse, kept to be reported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedCommit {
+    /// Short commit hash.
+    pub hash: String,
+    /// First line of the commit message.
+    pub subject: String,
+}
 
-use crate::{CoreError, CoreResult, Pipeline, VersionManager};
+impl From<&RawCommit> for SkippedCommit {
+    fn from(commit: &RawCommit) -> Self {
+        Self {
+            hash: commit.short_hash().to_string(),
+            subject: commit.subject().to_string(),
+        }
+    }
+}
+
+/// Result of updating a version file.
+#[derive(Debug)]
+pub struct FileUpdate {
+    /// File path, as configured.
+    pub path: String,
+    /// Change of the file, also computed by dry runs.
+    pub result: FileResult<FileChange>,
+}
+
+/// Version read from a version file.
+#[derive(Debug)]
+pub struct FileVersion {
+    /// File path, as configured.
+    pub path: String,
+    /// Version read from the file.
+    pub result: FileResult<Version>,
+}
+
+/// Result of a release.
+#[derive(Debug, Clone)]
+pub struct ReleaseOutcome {
+    /// Version before the release.
+    pub previous_version: Version,
+    /// Released version.
+    pub new_version: Version,
+    /// Bump type applied.
+    pub bump_type: BumpType,
+    /// Version files updated.
+    pub files_updated: Vec<String>,
+    /// Files changed by the release: version files and changelog.
+    pub changes: Vec<FileChange>,
+    /// Changelog file written.
+    pub changelog: Option<PathBuf>,
+    /// Release commit message.
+    pub commit: Option<String>,
+    /// Release tag name.
+    pub tag: Option<String>,
+    /// Message of the annotated release tag.
+    pub tag_message: Option<String>,
+    /// Refspecs pushed, or to push in a dry run.
+    pub refspecs: Vec<String>,
+    /// Whether the release was pushed.
+    pub pushed: bool,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+}
 
 /// Manages the release process.
+///
+/// [`release`](Self::release) runs a whole release. The individual steps are
+/// also exposed for callers reporting progress between them.
 pub struct ReleaseManager {
     repo: Repository,
     version_manager: VersionManager,
-    tag_prefix: String,
+    tag_format: TagFormat,
+    commit_options: CommitOptions,
+    version: VersionConfig,
+    changelog_path: PathBuf,
+    changelog_file: bool,
+    changelog_header: Option<String>,
+    changelog_header_file: Option<PathBuf>,
+    formatter_config: FormatterConfig,
+    repository_url: Option<String>,
+    fragments_dir: Option<PathBuf>,
+    inserts: Vec<ChangelogInsertConfig>,
+    release_files: Option<ReleaseFilesConfig>,
+    commit_cache: Option<CommitCache>,
 }
 
 impl ReleaseManager {
-    /// Creates a new release manager.
+    /// Creates a new release manager for tags made of a prefix and the version.
     #[must_use]
     pub fn new(repo: Repository, tag_prefix: impl Into<String>) -> Self {
         Self {
             repo,
             version_manager: VersionManager::new(),
-            tag_prefix: tag_prefix.into(),
+            tag_format: TagFormat::from_prefix(&tag_prefix.into()),
+            commit_options: CommitOptions::default(),
+            version: VersionConfig::default(),
+            changelog_path: PathBuf::from(ChangelogConfig::default().output),
+            changelog_file: true,
+            changelog_header: None,
+            changelog_header_file: None,
+            formatter_config: FormatterConfig::default(),
+            repository_url: None,
+            fragments_dir: None,
+            inserts: Vec::new(),
+            release_files: None,
+            commit_cache: None,
         }
     }
 
-    /// Executes a release with the given pipeline.
+    /// Creates a release manager from the project configuration.
+    #[must_use]
+    pub fn from_config(mut repo: Repository, config: &Config) -> Self {
+        let version = &config.version;
+
+        // Tags are created by the commit author too; an invalid author is
+        // reported by `check_identity` and the commit
+        if let Some(Ok(identity)) = version.commit_author.as_deref().map(str::parse::<Identity>) {
+            repo = repo.with_identity(identity);
+        }
+
+        let tag_format = TagFormat::new(version.tag_template());
+        let tag_format = match &version.package {
+            Some(package) => tag_format.with_package(package),
+            None => tag_format,
+        };
+
+        let commit_options = CommitOptions::new()
+            .with_paths(version.paths.iter().cloned())
+            .with_first_parent(version.first_parent)
+            .with_skip_merges(version.skip_merges)
+            .with_max_count(version.max_commits);
+
+        let repository_url = match &config.changelog.repository_url {
+            Some(url) => Some(url.trim_end_matches('/').to_string()),
+            None => repo.remote_web_url(&version.remote).unwrap_or_else(|e| {
+                debug!("failed to read {} remote: {e}", version.remote);
+                None
+            }),
+        };
+
+        let commit_cache = version
+            .cache
+            .then(|| CommitCache::for_config(repo.path(), config));
+        let mut manager = Self::new(repo, "")
+            .with_tag_format(tag_format)
+            .with_commit_options(commit_options)
+            .with_version_config(version.clone())
+            .with_changelog_path(&config.changelog.output)
+            .with_changelog_file(config.changelog.output_file().is_some())
+            .with_formatter_config(formatter_config(config))
+            .with_inserts(config.changelog.inserts.clone())
+            .with_release_files(config.changelog.releases.clone());
+        if let Some(cache) = commit_cache {
+            manager = manager.with_commit_cache(cache);
+        }
+        if let Some(dir) = &config.changelog.fragments {
+            manager = manager.with_fragments_dir(dir);
+        }
+        if let Some(header) = &config.changelog.header {
+            manager = manager.with_changelog_header(header);
+        }
+        if let Some(path) = &config.changelog.header_file {
+            manager = manager.with_changelog_header_file(path);
+        }
+        match repository_url {
+            Some(url) => manager.with_repository_url(url),
+            None => manager,
+        }
+    }
+
+    /// Sets the tag name template.
+    #[must_use]
+    pub fn with_tag_format(mut self, tag_format: TagFormat) -> Self {
+        self.tag_format = tag_format;
+        self
+    }
+
+    /// Sets the options used to collect commits.
+    #[must_use]
+    pub fn with_commit_options(mut self, commit_options: CommitOptions) -> Self {
+        self.commit_options = commit_options;
+        self
+    }
+
+    /// Sets the version configuration: version files, commit message,
+    /// channels, release branches and push settings.
+    ///
+    /// The tag format and commit options are set separately.
+    #[must_use]
+    pub fn with_version_config(mut self, version: VersionConfig) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the cache of the commits parsed since the latest tag.
+    #[must_use]
+    pub fn with_commit_cache(mut self, cache: CommitCache) -> Self {
+        self.commit_cache = Some(cache);
+        self
+    }
+
+    /// Sets whether the commit cache is only read, e.g. by commands that
+    /// change nothing.
+    #[must_use]
+    pub fn with_read_only_cache(mut self, read_only: bool) -> Self {
+        self.commit_cache = self
+            .commit_cache
+            .map(|cache| cache.with_read_only(read_only));
+        self
+    }
+
+    /// Sets the changelog file path.
+    #[must_use]
+    pub fn with_changelog_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.changelog_path = path.into();
+        self
+    }
+
+    /// Sets whether the changelog file is written.
+    ///
+    /// Without it, the changelog section of a release only goes to the tag
+    /// message, the hooks, the release files and the `changelog.inserts`
+    /// files.
+    #[must_use]
+    pub fn with_changelog_file(mut self, changelog_file: bool) -> Self {
+        self.changelog_file = changelog_file;
+        self
+    }
+
+    /// Sets the header written at the top of a new changelog, replacing the
+    /// Keep a Changelog preamble.
+    #[must_use]
+    pub fn with_changelog_header(mut self, header: impl Into<String>) -> Self {
+        self.changelog_header = Some(header.into());
+        self
+    }
+
+    /// Sets the file holding the header written at the top of a new
+    /// changelog, read when writing it.
+    #[must_use]
+    pub fn with_changelog_header_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.changelog_header_file = Some(path.into());
+        self
+    }
+
+    /// Sets the options passed to the changelog formatter.
+    #[must_use]
+    pub fn with_formatter_config(mut self, config: FormatterConfig) -> Self {
+        self.formatter_config = config;
+        self
+    }
+
+    /// Sets the repository web URL used for changelog links.
+    #[must_use]
+    pub fn with_repository_url(mut self, url: impl Into<String>) -> Self {
+        self.repository_url = Some(url.into());
+        self
+    }
+
+    /// Sets the directory of the changelog fragments merged into releases.
+    #[must_use]
+    pub fn with_fragments_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.fragments_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the other files the release section is inserted into.
+    #[must_use]
+    pub fn with_inserts(mut self, inserts: Vec<ChangelogInsertConfig>) -> Self {
+        self.inserts = inserts;
+        self
+    }
+
+    /// Sets the files holding one release each, written with or instead of
+    /// the changelog.
+    #[must_use]
+    pub fn with_release_files(mut self, release_files: Option<ReleaseFilesConfig>) -> Self {
+        self.release_files = release_files;
+        self
+    }
+
+    /// Returns the repository.
+    #[must_use]
+    pub fn repository(&self) -> &Repository {
+        &self.repo
+    }
+
+    /// Returns the tag name template.
+    #[must_use]
+    pub fn tag_format(&self) -> &TagFormat {
+        &self.tag_format
+    }
+
+    /// Returns the changelog file path.
+    #[must_use]
+    pub fn changelog_path(&self) -> &Path {
+        &self.changelog_path
+    }
+
+    /// Returns the repository web URL used for changelog links.
+    #[must_use]
+    pub fn repository_url(&self) -> Option<&str> {
+        self.repository_url.as_deref()
+    }
+
+    /// Returns the current branch, falling back to the configured branch on
+    /// a detached HEAD.
     ///
     /// # Errors
     ///
-    /// Returns an error if the release fails.
-    pub fn release(&self, pipeline: &Pipeline, dry_run: bool) -> CoreResult<Version> {
-        info!("starting release process");
+    /// Returns an error if HEAD cannot be read.
+    pub fn current_branch(&self) -> CoreResult<Option<String>> {
+        let branch = self.repo.current_branch()?;
+        Ok(branch.or_else(|| self.version.branch.clone()))
+    }
 
-        // Get previous version
-        let previous_version = self.get_previous_version()?;
-        debug!(?previous_version, "found previous version");
+    /// Checks that releases are allowed from the current branch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::BranchNotAllowed`] if the branch is not a release
+    /// branch, or [`CoreError::DetachedHead`] if it cannot be determined.
+    pub fn check_branch(&self) -> CoreResult<()> {
+        if self.version.release_branches.is_empty() {
+            return Ok(());
+        }
 
-        // Get commits since last release
-        let tag = previous_version
-            .as_ref()
-            .map(|v| format!("{}{v}", self.tag_prefix));
-        let raw_commits = self.repo.commits_since(tag.as_deref())?;
+        // Detached HEADs (e.g. CI checkouts) fall back to the configured branch
+        let branch = self.current_branch()?.ok_or(CoreError::DetachedHead)?;
+        if self.version.is_release_branch(&branch) {
+            return Ok(());
+        }
 
-        if raw_commits.is_empty() {
-            return Err(CoreError::NoCommits);
+        Err(CoreError::BranchNotAllowed {
+            branch,
+            allowed: self.version.release_branches.clone(),
+        })
+    }
+
+    /// Returns the prerelease channel of the current branch, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if HEAD cannot be read.
+    pub fn channel(&self) -> CoreResult<Option<&ChannelConfig>> {
+        if self.version.channels.is_empty() {
+            return Ok(None);
         }
 
-        info!(
-            count = raw_commits.len(),
-            "found commits since last release"
-        );
+        let branch = self.current_branch()?;
+        Ok(branch.and_then(|branch| self.version.channel_for(&branch)))
+    }
+
+    /// Returns the latest release tag along with its version.
+    ///
+    /// Prerelease tags are ignored when channels are configured. A shallow
+    /// clone is first deepened when `version.unshallow` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags cannot be read or the history of a shallow
+    /// clone cannot be fetched.
+    pub fn latest_tag(&self) -> CoreResult<Option<(String, Version)>> {
+        // Tags and history missing from a shallow clone would skew the range
+        if self.version.unshallow && self.repo.is_shallow() {
+            info!(remote = %self.version.remote, "fetching the full history of a shallow clone");
+            self.repo.unshallow(&self.version.remote)?;
+        }
+
+        let latest = if self.version.channels.is_empty() {
+            self.repo.latest_tag_matching(&self.tag_format)?
+        } else {
+            self.repo.latest_tag_in_channel(&self.tag_format, None)?
+        };
+        Ok(latest)
+    }
 
-        // Parse commits
-        let parsed_commits = pipeline.parse_commits(&raw_commits);
+    /// Returns the commits since a tag, or all commits without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if commits cannot be read.
+    pub fn commits_since(&self, tag: Option<&str>) -> CoreResult<Vec<RawCommit>> {
+        Ok(self.repo.commits_since_with(tag, &self.commit_options)?)
+    }
+
+    /// Computes the next release from the commits since the latest tag.
+    ///
+    /// The bump type is forced when given, and determined by the pipeline
+    /// otherwise. A first release is `0.1.0`, and on a prerelease channel the
+    /// version becomes a prerelease (e.g. `1.5.0-rc.1`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::NoCommits`] or [`CoreError::NoParseableCommits`]
+    /// when there is nothing to release, or an error if the repository cannot
+    /// be read.
+    #[instrument(skip_all)]
+    pub fn plan(
+        &self,
+        pipeline: &Pipeline,
+        bump_type: Option<BumpType>,
+    ) -> CoreResult<ReleasePlan> {
+        let latest = self.latest_tag()?;
+        let (latest_tag, latest_version) = latest.unzip();
+        debug!(tag = ?latest_tag, "found latest version tag");
+
+        let head = self.repo.head_id()?;
+        let ParsedRange {
+            mut commits,
+            skipped,
+            raw_count,
+        } = self.parse_commits(pipeline, latest_tag.as_deref(), head.as_deref())?;
         debug!(
-            parsed = parsed_commits.len(),
-            skipped = raw_commits.len() - parsed_commits.len(),
+            parsed = commits.len(),
+            skipped = skipped.len(),
             "parsed commits"
         );
 
-        // Determine bump type
-        let bump_type = pipeline.determine_bump(&parsed_commits);
-        info!(%bump_type, "determined bump type");
+        let fragments = self.fragments()?;
+        debug!(count = fragments.len(), "read changelog fragments");
+        commits.extend(
+            fragments
+                .iter()
+                .map(|fragment| pipeline.normalize_scope(fragment.to_commit())),
+        );
+
+        if bump_type.is_none() {
+            if raw_count == 0 {
+                return Err(CoreError::NoCommits);
+            }
+            if commits.is_empty() {
+                return Err(CoreError::NoParseableCommits);
+            }
+        }
+
+        let (bump_type, new_version) = if let Some(forced) = bump_type {
+            info!(bump_type = %forced, "using forced bump type");
+            (forced, self.bump(latest_version.as_ref(), forced))
+        } else {
+            self.next_version(pipeline, latest_version.as_ref(), &commits)
+        };
+
+        // Prerelease channel: e.g. 1.5.0 -> 1.5.0-rc.1
+        let new_version = match self.channel()? {
+            Some(channel) => self.next_prerelease(channel, &new_version)?,
+            None => new_version,
+        };
+
+        Ok(ReleasePlan {
+            latest_tag,
+            previous_version: latest_version.unwrap_or_else(|| Version::new(0, 0, 0)),
+            new_version,
+            bump_type,
+            raw_commit_count: raw_count,
+            commits,
+            fragments: fragments
+                .into_iter()
+                .map(|fragment| fragment.path)
+                .collect(),
+            skipped,
+            head,
+        })
+    }
 
-        // Calculate new version
-        let base_version = previous_version.unwrap_or_else(|| Version::new(0, 0, 0));
-        let next_version = self.version_manager.bump(&base_version, bump_type);
+    /// Parses the commits after a tag, or all commits without one, up to
+    /// `head`, as they are read, so that only the parsed commits are kept in
+    /// memory. There are no commits without `head`, on an unborn branch.
+    ///
+    /// With a commit cache, the commits are only parsed again when `head`,
+    /// the tag, the configuration or the parsing inputs of the pipeline
+    /// changed.
+    fn parse_commits(
+        &self,
+        pipeline: &Pipeline,
+        tag: Option<&str>,
+        head: Option<&str>,
+    ) -> CoreResult<ParsedRange> {
+        let Some(head) = head else {
+            return Ok(ParsedRange::default());
+        };
+        // Cached commits are keyed by the head and tag commits
+        let tag_id = tag.and_then(|tag| self.repo.commit_id(tag).ok());
+        let cache = self
+            .commit_cache
+            .as_ref()
+            .filter(|_| tag.is_none() || tag_id.is_some());
+        parse_cached(
+            pipeline,
+            head,
+            cache.map(|cache| (cache, tag_id.as_deref())),
+            |f| {
+                self.repo
+                    .for_each_commit_since_to(tag, head, &self.commit_options, f)
+            },
+        )
+    }
+
+    /// Parses the commits reachable from `to` but not from `from`, as they
+    /// are read, returning the number of commits read and the parsed ones.
+    ///
+    /// Like the commits of a release, they are only parsed again when the
+    /// range, the configuration or the parsing inputs of the pipeline
+    /// changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a revision cannot be resolved or commits cannot be
+    /// read.
+    pub fn parse_commits_between(
+        &self,
+        pipeline: &Pipeline,
+        from: Option<&str>,
+        to: &str,
+    ) -> CoreResult<(usize, Vec<ParsedCommit>)> {
+        let head = self.repo.commit_id(to)?;
+        let from_id = from.map(|from| self.repo.commit_id(from)).transpose()?;
+        let cache = self.commit_cache.as_ref();
+        let range = parse_cached(
+            pipeline,
+            &head,
+            cache.map(|cache| (cache, from_id.as_deref())),
+            |f| {
+                self.repo
+                    .for_each_commit_between(from, to, &self.commit_options, f)
+            },
+        )?;
+        Ok((range.raw_count, range.commits))
+    }
+
+    /// Plans the release of an already computed version, e.g. to resume it.
+    ///
+    /// The commits of the release are read from `range`, the commit range of
+    /// its first plan, so that the commits it created since are left out.
+    /// Without a range, they are read from the latest tag up to HEAD.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be read.
+    pub fn plan_version(
+        &self,
+        pipeline: &Pipeline,
+        previous_version: Version,
+        new_version: Version,
+        bump_type: BumpType,
+        range: Option<CommitRange>,
+    ) -> CoreResult<ReleasePlan> {
+        let CommitRange { latest_tag, head } = match range {
+            Some(range) => range,
+            None => CommitRange {
+                latest_tag: self.latest_tag()?.map(|(tag, _)| tag),
+                head: self.repo.head_id()?,
+            },
+        };
+        let ParsedRange {
+            mut commits,
+            skipped,
+            raw_count,
+        } = self.parse_commits(pipeline, latest_tag.as_deref(), head.as_deref())?;
+        let fragments = self.fragments()?;
+        commits.extend(
+            fragments
+                .iter()
+                .map(|fragment| pipeline.normalize_scope(fragment.to_commit())),
+        );
+
+        Ok(ReleasePlan {
+            latest_tag,
+            previous_version,
+            new_version,
+            bump_type,
+            raw_commit_count: raw_count,
+            commits,
+            fragments: fragments
+                .into_iter()
+                .map(|fragment| fragment.path)
+                .collect(),
+            skipped,
+            head,
+        })
+    }
+
+    /// Reads the changelog fragments, if a fragments directory is set.
+    fn fragments(&self) -> CoreResult<Vec<Fragment>> {
+        match &self.fragments_dir {
+            Some(dir) => Ok(read_fragments(dir)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the bump type and version following `current` for commits:
+    /// the version computed by the bumper when it computes one, `current`
+    /// bumped according to the commits otherwise, `0.1.0` for a first
+    /// release.
+    #[must_use]
+    pub fn next_version(
+        &self,
+        pipeline: &Pipeline,
+        current: Option<&Version>,
+        commits: &[ParsedCommit],
+    ) -> (BumpType, Version) {
+        if let Some(current) = current
+            && let Some(next) = pipeline.next_version(current, commits)
+        {
+            info!(%next, "next version computed by the bumper");
+            return (bump_between(current, &next), next);
+        }
+
+        let bump_type = pipeline.determine_bump(commits);
+        info!(%bump_type, "determined bump type from commits");
+        (bump_type, self.bump(current, bump_type))
+    }
+
+    /// Bumps the latest version, a first release being `0.1.0`.
+    fn bump(&self, current: Option<&Version>, bump_type: BumpType) -> Version {
+        match current {
+            // A release bumps at least the patch version
+            Some(current) => self
+                .version_manager
+                .bump(current, bump_type.max(BumpType::Patch)),
+            None => Version::new(0, 1, 0),
+        }
+    }
+
+    /// Returns the next prerelease of a version on a channel.
+    fn next_prerelease(&self, channel: &ChannelConfig, version: &Version) -> CoreResult<Version> {
+        let latest = self
+            .repo
+            .latest_tag_in_channel(&self.tag_format, Some(&channel.prerelease))?;
+
+        Ok(self.version_manager.prerelease(
+            version,
+            &channel.prerelease,
+            latest.map(|(_, v)| v).as_ref(),
+        )?)
+    }
+
+    /// Checks that a version moves the release history forward.
+    ///
+    /// The tag of the version must not exist yet, and the version must be
+    /// greater than the latest tag of its own channel: stable versions are
+    /// compared with stable tags, `rc` prereleases with `rc` tags.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::TagExists`] or [`CoreError::VersionNotGreater`]
+    /// if the version cannot be released.
+    pub fn check_version(&self, version: &Version, options: &ReleaseOptions) -> CoreResult<()> {
+        let tag_name = self.tag_format.format(version);
+        if options.tag() && self.repo.tag_exists(&tag_name)? {
+            return Err(CoreError::TagExists(tag_name));
+        }
+
+        let channel = (!version.pre.is_empty())
+            .then(|| version.pre.as_str().split('.').next())
+            .flatten();
+        let latest = self.repo.latest_tag_in_channel(&self.tag_format, channel)?;
+
+        if let Some((latest_tag, latest_version)) = latest {
+            let allowed = if options.allow_same_version() {
+                *version >= latest_version
+            } else {
+                *version > latest_version
+            };
+            if !allowed {
+                return Err(CoreError::VersionNotGreater {
+                    version: version.clone(),
+                    latest_tag,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Updates the configured version files.
+    ///
+    /// Every change is computed before any file is written, so a file that
+    /// cannot be updated leaves all of them untouched. Dry runs only compute
+    /// the changes.
+    pub fn update_version_files(&self, version: &Version, dry_run: bool) -> Vec<FileUpdate> {
+        self.update_files(version, dry_run, |_| true)
+    }
+
+    /// Updates the configured version files among a list of paths.
+    ///
+    /// Paths are compared to the configured ones, e.g. to fix only the files
+    /// whose version diverges. As with [`Self::update_version_files`], files
+    /// are only written when all of them can be updated.
+    pub fn update_listed_version_files(
+        &self,
+        paths: &[String],
+        version: &Version,
+        dry_run: bool,
+    ) -> Vec<FileUpdate> {
+        self.update_files(version, dry_run, |path| paths.iter().any(|p| p == path))
+    }
+
+    /// Reads the version of each configured version file.
+    pub fn read_version_files(&self) -> Vec<FileVersion> {
+        self.version_files()
+            .map(|(path, custom_file)| {
+                let result = match custom_file {
+                    Some(file) => read_custom_file(file),
+                    None => read_version_from_file(Path::new(path)),
+                };
+                FileVersion {
+                    path: path.clone(),
+                    result,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the configured version files, with their custom location.
+    fn version_files(&self) -> impl Iterator<Item = (&String, Option<&VersionFileConfig>)> {
+        let files = self.version.files.iter().map(|path| (path, None));
+        let custom_files = self
+            .version
+            .custom_files
+            .iter()
+            .map(|file| (&file.path, Some(file)));
+        files.chain(custom_files)
+    }
+
+    #[instrument(
+        name = "version_files",
+        skip_all,
+        fields(version = %version, dry_run = dry_run)
+    )]
+    fn update_files(
+        &self,
+        version: &Version,
+        dry_run: bool,
+        filter: impl Fn(&str) -> bool,
+    ) -> Vec<FileUpdate> {
+        let mut updates: Vec<FileUpdate> = self
+            .version_files()
+            .filter(|(path, _)| filter(path))
+            .map(|(path, custom_file)| FileUpdate {
+                path: path.clone(),
+                result: self.bump_file(Path::new(path), custom_file, version),
+            })
+            .collect();
+
+        if !dry_run && updates.iter().all(|update| update.result.is_ok()) {
+            for update in &mut updates {
+                if let Ok(change) = &update.result
+                    && let Err(e) = change.write()
+                {
+                    update.result = Err(e.into());
+                }
+            }
+        }
+        updates
+    }
+
+    /// Returns the change of a version file, located by `[[version.file]]`
+    /// or based on its type.
+    fn bump_file(
+        &self,
+        path: &Path,
+        custom_file: Option<&VersionFileConfig>,
+        version: &Version,
+    ) -> FileResult<FileChange> {
+        let mut content = match custom_file {
+            Some(file) => bump_custom_file(file, version)?,
+            None => bump_version_file(path, version)?,
+        };
+
+        if custom_file.is_none()
+            && self.version.update_path_dependencies
+            && path.file_name().is_some_and(|name| name == "Cargo.toml")
+        {
+            content = bump_cargo_path_dependencies(&content, version).0;
+        }
+
+        Ok(FileChange::read(path, content)?)
+    }
+
+    /// Formats the changelog section of a release and writes it to the
+    /// changelog, the release files and the `changelog.inserts` files,
+    /// removing the changelog fragments it consumed.
+    ///
+    /// Returns the section. Dry runs only format it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the changelog cannot be written.
+    #[instrument(
+        name = "changelog",
+        skip_all,
+        fields(formatter = pipeline.formatter().name(), version = %plan.new_version)
+    )]
+    pub fn write_changelog(
+        &self,
+        pipeline: &Pipeline,
+        plan: &ReleasePlan,
+        dry_run: bool,
+    ) -> CoreResult<String> {
+        let section = self.format_changelog(pipeline, plan);
+        if !dry_run {
+            for change in self.output_changes(pipeline, plan)? {
+                change.write()?;
+                debug!(path = %change.path.display(), "wrote changelog output");
+            }
+            self.write_changelog_section(plan, &section)?;
+        }
+        Ok(section)
+    }
+
+    /// Formats the changelog section of a release, without writing it.
+    #[must_use]
+    pub fn format_changelog(&self, pipeline: &Pipeline, plan: &ReleasePlan) -> String {
+        let section = pipeline
+            .formatter()
+            .format(&self.release_of(plan), &self.formatter_config);
+        debug!(changelog_len = section.len(), "generated changelog");
+        section
+    }
+
+    /// Returns the changes of the `changelog.outputs` files rendering a
+    /// release with their own formatters, without writing them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read.
+    pub fn output_changes(
+        &self,
+        pipeline: &Pipeline,
+        plan: &ReleasePlan,
+    ) -> CoreResult<Vec<FileChange>> {
+        output_changes(
+            pipeline.outputs(),
+            &self.release_of(plan),
+            &self.formatter_config,
+            &self.changelog_header()?,
+        )
+    }
+
+    /// Returns the header written at the top of a new changelog.
+    fn changelog_header(&self) -> CoreResult<String> {
+        changelog_header(
+            self.changelog_header.as_deref(),
+            self.changelog_header_file.as_deref(),
+        )
+    }
+
+    /// Returns the release formatters render for a plan.
+    fn release_of(&self, plan: &ReleasePlan) -> Release {
+        let mut release = Release::new(plan.new_version.clone(), Utc::now(), plan.commits.clone());
+        if !plan.is_first_release() {
+            release = release.with_previous_version(plan.previous_version.clone());
+        }
+        if let Some(url) = &self.repository_url {
+            release = release.with_repository_url(url);
+        }
+        release
+    }
+
+    /// Writes a changelog section of a release, e.g. edited by the user, to
+    /// the changelog, the release files and the `changelog.inserts` files,
+    /// removing the changelog fragments of the release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the changelog cannot be written.
+    pub fn write_changelog_section(&self, plan: &ReleasePlan, section: &str) -> CoreResult<()> {
+        self.write_release_section(section, &plan.new_version)?;
+        remove_fragments(plan)
+    }
+
+    /// Writes the changelog section of a version to the changelog, the
+    /// release files and the `changelog.inserts` files.
+    ///
+    /// Returns the file holding the section, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, lacks its markers or
+    /// cannot be written.
+    pub fn write_release_section(
+        &self,
+        section: &str,
+        version: &Version,
+    ) -> CoreResult<Option<PathBuf>> {
+        for change in self.changelog_changes(section, version)? {
+            change.write()?;
+            debug!(path = %change.path.display(), "wrote changelog");
+        }
+        Ok(self.release_changelog(version))
+    }
+
+    /// Returns the files a release writes: the version files it changes and,
+    /// with a changelog section, the changelog, release and insert files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a changelog file cannot be read or lacks its
+    /// markers.
+    pub fn planned_files(
+        &self,
+        version: &Version,
+        section: Option<&str>,
+    ) -> CoreResult<Vec<String>> {
+        let mut files: Vec<String> = self
+            .update_version_files(version, true)
+            .into_iter()
+            .filter(|update| update.result.as_ref().is_ok_and(FileChange::is_changed))
+            .map(|update| update.path)
+            .collect();
+        if let Some(section) = section {
+            files.extend(
+                self.changelog_changes(section, version)?
+                    .iter()
+                    .filter(|change| change.is_changed())
+                    .map(|change| change.path.display().to_string()),
+            );
+        }
+        Ok(files)
+    }
+
+    /// Returns the changes of the changelog, the release files and the
+    /// `changelog.inserts` files writing a release section, without writing
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read or lacks its markers.
+    pub fn changelog_changes(
+        &self,
+        section: &str,
+        version: &Version,
+    ) -> CoreResult<Vec<FileChange>> {
+        let mut changes = Vec::new();
+        let skip_output = self
+            .release_files
+            .as_ref()
+            .is_some_and(|release_files| release_files.skip_output);
+        if self.changelog_file && !skip_output {
+            changes.push(changelog_change(
+                &self.changelog_path,
+                section,
+                version,
+                &self.changelog_header()?,
+            )?);
+        }
+        if let Some(release_files) = &self.release_files {
+            changes.extend(release_file_changes(release_files, section, version)?);
+        }
+        changes.extend(insert_changes(&self.inserts, section, version)?);
+        Ok(changes)
+    }
+
+    /// Returns the file holding a release section: the release file when
+    /// the changelog is skipped or disabled, the changelog otherwise.
+    fn release_changelog(&self, version: &Version) -> Option<PathBuf> {
+        match &self.release_files {
+            Some(release_files) if release_files.skip_output || !self.changelog_file => {
+                Some(release_file_path(release_files, version))
+            }
+            _ => self.changelog_file.then(|| self.changelog_path.clone()),
+        }
+    }
+
+    /// Creates the release commit, staging every change unless
+    /// `version.commit_release_files` is set.
+    ///
+    /// The commit hooks only run with `version.verify`, unless `no_verify`
+    /// is set.
+    ///
+    /// Returns the commit message. Dry runs only render it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `version.commit_author` is invalid or the commit
+    /// cannot be created.
+    pub fn commit(&self, version: &Version, no_verify: bool, dry_run: bool) -> CoreResult<String> {
+        self.commit_with_files(version, &[], no_verify, dry_run)
+    }
+
+    /// Creates the release commit like [`commit`](Self::commit), also
+    /// staging extra files added by hooks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `version.commit_author` is invalid or the commit
+    /// cannot be created.
+    #[instrument(name = "commit", skip_all, fields(version = %version))]
+    pub fn commit_with_files(
+        &self,
+        version: &Version,
+        files: &[String],
+        no_verify: bool,
+        dry_run: bool,
+    ) -> CoreResult<String> {
+        let message = render_commit_message(
+            &self.version.commit_message,
+            version,
+            &self.tag_format.format(version),
+            Utc::now().date_naive(),
+        );
+        let identity = self
+            .version
+            .commit_author
+            .as_deref()
+            .map(str::parse::<Identity>)
+            .transpose()?;
+        // Without release files, every change is staged, extra files included
+        let mut paths = self.release_files();
+        if !paths.is_empty() {
+            paths.extend_from_slice(files);
+        }
+        let options = NewCommitOptions::new()
+            .with_paths(paths)
+            .with_identity(identity)
+            .with_verify(self.version.verify && !no_verify);
+
+        if !dry_run {
+            self.repo.commit_with(&message, &options)?;
+            info!(%message, "created commit");
+        }
+        Ok(message)
+    }
+
+    /// Returns the pathspecs staged in the release commit, or none to stage
+    /// every change.
+    fn release_files(&self) -> Vec<String> {
+        if !self.version.commit_release_files {
+            return Vec::new();
+        }
+
+        let mut paths: Vec<String> = self.version_files().map(|(path, _)| path.clone()).collect();
+        if self.changelog_file {
+            paths.push(self.changelog_path.display().to_string());
+        }
+        if let Some(release_files) = &self.release_files {
+            paths.push(release_files.dir.clone());
+        }
+        paths.extend(self.inserts.iter().map(|insert| insert.path.clone()));
+        if let Some(dir) = &self.fragments_dir {
+            paths.push(dir.display().to_string());
+        }
+        paths
+    }
+
+    /// Renders the annotated tag message of a release from
+    /// `version.tag_message`.
+    ///
+    /// `changelog` is the changelog section of the release; `{changelog}` is
+    /// left empty without it.
+    #[must_use]
+    pub fn tag_message(&self, version: &Version, changelog: Option<&str>) -> String {
+        render_tag_message(
+            &self.version.tag_message,
+            version,
+            &self.tag_format.format(version),
+            Utc::now().date_naive(),
+            changelog.unwrap_or_default(),
+        )
+    }
+
+    /// Creates the release tag on HEAD, annotated with `message` unless
+    /// `version.lightweight_tags` is set.
+    ///
+    /// Returns the tag name. Dry runs only format it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tag cannot be created.
+    #[instrument(skip_all, fields(version = %version))]
+    pub fn tag(&self, version: &Version, message: &str, dry_run: bool) -> CoreResult<String> {
+        let tag_name = self.tag_format.format(version);
+
+        if !dry_run {
+            if self.version.lightweight_tags {
+                self.repo.create_lightweight_tag(&tag_name)?;
+            } else {
+                self.repo.create_tag(&tag_name, message)?;
+            }
+            info!(%tag_name, "created tag");
+        }
+        Ok(tag_name)
+    }
+
+    /// Checks a git identity is configured for the release commit and tag,
+    /// before anything is changed.
+    ///
+    /// Releases authored by `version.commit_author` use it for both. Dry
+    /// runs, and releases without commit or annotated tag, need none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `version.commit_author` is invalid, or
+    /// [`GitError::MissingIdentity`](unduler_git::GitError::MissingIdentity)
+    /// if no name or email is configured.
+    pub fn check_identity(&self, options: &ReleaseOptions) -> CoreResult<()> {
+        if let Some(author) = &self.version.commit_author {
+            author.parse::<Identity>()?;
+            return Ok(());
+        }
+        let tag = options.tag() && !self.version.lightweight_tags;
+        if !options.dry_run() && (options.commit() || tag) {
+            self.repo.identity()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the refspecs pushed after a release.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CoreError::DetachedHead`] if the commit is pushed from a
+    /// detached HEAD without a configured branch.
+    pub fn push_refspecs(&self, tag_name: Option<&str>, commit: bool) -> CoreResult<Vec<String>> {
+        let mut refspecs = Vec::new();
+
+        if commit {
+            let branch = match &self.version.branch {
+                Some(branch) => branch.clone(),
+                None => self.repo.current_branch()?.ok_or(CoreError::DetachedHead)?,
+            };
+            refspecs.push(format!("HEAD:refs/heads/{branch}"));
+        }
+
+        if let Some(tag_name) = tag_name {
+            refspecs.push(format!("refs/tags/{tag_name}:refs/tags/{tag_name}"));
+        }
+
+        Ok(refspecs)
+    }
+
+    /// Pushes refspecs to the configured remote.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the push fails or is rejected.
+    #[instrument(skip_all)]
+    pub fn push(&self, refspecs: &[String], dry_run: bool) -> CoreResult<()> {
+        if !dry_run {
+            self.repo.push(&self.version.remote, refspecs)?;
+            info!(remote = %self.version.remote, "pushed release");
+        }
+        Ok(())
+    }
+
+    /// Returns the remote releases are pushed to.
+    #[must_use]
+    pub fn remote(&self) -> &str {
+        &self.version.remote
+    }
+
+    /// Executes a release with the given pipeline.
+    ///
+    /// Runs every step in order, calling the pipeline hooks between them.
+    /// The `pre_release` hooks get the planned release, with its changelog
+    /// section and files, before anything is modified, and can veto it. Once
+    /// the release is pushed, the `post_release` hooks run; if a step fails,
+    /// the `failure` hooks run with its error instead.
+    /// Asynchronous hooks run on a runtime created for the release, so this
+    /// must not be called from asynchronous code: use
+    /// [`release_async`](Self::release_async) there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub fn release(
+        &self,
+        pipeline: &Pipeline,
+        options: &ReleaseOptions,
+    ) -> CoreResult<ReleaseOutcome> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(self.release_async(pipeline, options))
+    }
+
+    /// Executes a release with the given pipeline, from asynchronous code.
+    ///
+    /// See [`release`](Self::release).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub async fn release_async(
+        &self,
+        pipeline: &Pipeline,
+        options: &ReleaseOptions,
+    ) -> CoreResult<ReleaseOutcome> {
+        info!("starting release process");
+
+        if !options.allow_branch() {
+            self.check_branch()?;
+        }
+        self.check_identity(options)?;
+
+        let plan = self.plan(pipeline, options.bump_type())?;
+        self.check_version(&plan.new_version, options)?;
         info!(
-            previous = %base_version,
-            next = %next_version,
+            previous = %plan.previous_version,
+            next = %plan.new_version,
             "calculated new version"
         );
 
+        self.release_plan_async(pipeline, plan, options, &mut ())
+            .await
+    }
+
+    /// Executes a planned release, reporting its steps to `progress`.
+    ///
+    /// Runs the steps and hooks of [`release`](Self::release), for callers
+    /// that check the plan themselves. Steps `progress` reports as done are
+    /// skipped, to resume a failed release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub fn release_plan(
+        &self,
+        pipeline: &Pipeline,
+        plan: ReleasePlan,
+        options: &ReleaseOptions,
+        progress: &mut dyn ReleaseProgress,
+    ) -> CoreResult<ReleaseOutcome> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(self.release_plan_async(pipeline, plan, options, progress))
+    }
+
+    /// Executes a planned release from asynchronous code.
+    ///
+    /// See [`release_plan`](Self::release_plan).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub async fn release_plan_async(
+        &self,
+        pipeline: &Pipeline,
+        mut plan: ReleasePlan,
+        options: &ReleaseOptions,
+        progress: &mut dyn ReleaseProgress,
+    ) -> CoreResult<ReleaseOutcome> {
+        let dry_run = options.dry_run();
+        let mut outcome = ReleaseOutcome {
+            previous_version: plan.previous_version.clone(),
+            new_version: plan.new_version.clone(),
+            bump_type: plan.bump_type,
+            files_updated: Vec::new(),
+            changes: Vec::new(),
+            changelog: None,
+            commit: None,
+            tag: None,
+            tag_message: None,
+            refspecs: Vec::new(),
+            pushed: false,
+            dry_run,
+        };
+
         // Create release context
         let mut ctx = ReleaseContext::new(
             self.repo.path(),
-            base_version.clone(),
-            next_version.clone(),
-            bump_type,
-            parsed_commits.clone(),
+            plan.previous_version.clone(),
+            plan.new_version.clone(),
+            plan.bump_type,
+            plan.commits.clone(),
         )
+        .with_tag_name(self.tag_format.format(&plan.new_version))
+        .with_package(self.tag_format.package().map(String::from))
+        .with_repository_url(self.repository_url.clone())
+        .with_branch(self.current_branch().ok().flatten())
         .dry_run(dry_run);
 
-        // Run pre_bump hooks
-        for hook in pipeline.hooks() {
-            debug!(hook = hook.name(), "running pre_bump hook");
-            hook.on_pre_bump(&mut ctx)?;
+        if let Err(error) = self
+            .release_steps(
+                pipeline,
+                options,
+                &mut plan,
+                &mut ctx,
+                &mut outcome,
+                progress,
+            )
+            .await
+        {
+            ctx.error = Some(error.to_string());
+            if let Err(hook_error) = pipeline.run_hooks(HookStage::Failure, &mut ctx).await {
+                warn!(error = %hook_error, "failure hook failed");
+            }
+            return Err(error);
         }
 
-        if !dry_run {
-            // TODO: Update version files
+        pipeline.run_hooks(HookStage::PostRelease, &mut ctx).await?;
+
+        info!(version = %plan.new_version, "release completed");
+        Ok(outcome)
+    }
+
+    /// Runs the steps of a planned release, from the `pre_release` hooks to
+    /// the push.
+    ///
+    /// Reads back the changes hooks make to the context: the version after
+    /// the `pre_bump` hooks, the changelog section after the `post_bump`
+    /// hooks and the extra commit files after the `pre_commit` hooks.
+    async fn release_steps(
+        &self,
+        pipeline: &Pipeline,
+        options: &ReleaseOptions,
+        plan: &mut ReleasePlan,
+        ctx: &mut ReleaseContext,
+        outcome: &mut ReleaseOutcome,
+        progress: &mut dyn ReleaseProgress,
+    ) -> CoreResult<()> {
+        let dry_run = options.dry_run();
+        ctx.changelog = self.release_section(pipeline, plan, options);
+        ctx.files = self.planned_release_files(pipeline, plan, ctx.changelog.as_deref())?;
+
+        pipeline.run_hooks(HookStage::PreRelease, ctx).await?;
+        pipeline.run_hooks(HookStage::PreBump, ctx).await?;
+
+        if ctx.next_version != plan.new_version {
+            self.check_version(&ctx.next_version, options)?;
+            info!(
+                planned = %plan.new_version,
+                next = %ctx.next_version,
+                "hooks changed the version"
+            );
+            plan.new_version = ctx.next_version.clone();
+            outcome.new_version = plan.new_version.clone();
+            ctx.tag_name = Some(self.tag_format.format(&plan.new_version));
+            ctx.changelog = self.release_section(pipeline, plan, options);
+            ctx.files = self.planned_release_files(pipeline, plan, ctx.changelog.as_deref())?;
         }
 
-        // Run post_bump hooks
-        for hook in pipeline.hooks() {
-            debug!(hook = hook.name(), "running post_bump hook");
-            hook.on_post_bump(&mut ctx)?;
+        let has_version_files =
+            !self.version.files.is_empty() || !self.version.custom_files.is_empty();
+        if has_version_files && !progress.is_done(ReleaseStep::VersionFiles) {
+            progress.started(ReleaseStep::VersionFiles);
+            for update in self.update_version_files(&plan.new_version, dry_run) {
+                outcome.changes.push(update.result?);
+                outcome.files_updated.push(update.path);
+            }
+            progress.completed(ReleaseStep::VersionFiles, outcome)?;
         }
 
-        // Generate changelog
-        let release = Release::new(next_version.clone(), chrono::Utc::now(), parsed_commits)
-            .with_previous_version(base_version);
+        pipeline.run_hooks(HookStage::PostBump, ctx).await?;
 
-        let changelog = pipeline
-            .formatter()
-            .format(&release, &FormatterConfig::default());
-        ctx.changelog = Some(changelog.clone());
+        if let Some(section) = &ctx.changelog
+            && !progress.is_done(ReleaseStep::Changelog)
+        {
+            progress.started(ReleaseStep::Changelog);
+            let mut changes = self.changelog_changes(section, &plan.new_version)?;
+            changes.extend(self.output_changes(pipeline, plan)?);
+            changes.retain(FileChange::is_changed);
+            if !dry_run {
+                for change in &changes {
+                    change.write()?;
+                    debug!(path = %change.path.display(), "wrote changelog");
+                }
+                remove_fragments(plan)?;
+            }
+            outcome.changes.extend(changes);
+            outcome.changelog = self.release_changelog(&plan.new_version);
+            progress.completed(ReleaseStep::Changelog, outcome)?;
+        }
 
-        debug!(changelog_len = changelog.len(), "generated changelog");
+        pipeline.run_hooks(HookStage::PreCommit, ctx).await?;
 
-        // Run pre_commit hooks
-        for hook in pipeline.hooks() {
-            debug!(hook = hook.name(), "running pre_commit hook");
-            hook.on_pre_commit(&mut ctx)?;
+        if options.commit() && !progress.is_done(ReleaseStep::Commit) {
+            progress.started(ReleaseStep::Commit);
+            let mut files = ctx.commit_files.clone();
+            if ctx.changelog.is_some() {
+                files.extend(
+                    pipeline
+                        .outputs()
+                        .iter()
+                        .map(|output| output.path.display().to_string()),
+                );
+            }
+            outcome.commit = Some(self.commit_with_files(
+                &plan.new_version,
+                &files,
+                options.no_verify(),
+                dry_run,
+            )?);
+            progress.completed(ReleaseStep::Commit, outcome)?;
         }
 
-        if !dry_run {
-            // TODO: Commit changes
+        pipeline.run_hooks(HookStage::PreTag, ctx).await?;
+
+        if options.tag() && progress.is_done(ReleaseStep::Tag) {
+            outcome.tag = Some(self.tag_format.format(&plan.new_version));
+        } else if options.tag() {
+            progress.started(ReleaseStep::Tag);
+            let message = match options.tag_message() {
+                Some(message) => message.to_string(),
+                None => self.tag_message(&plan.new_version, ctx.changelog.as_deref()),
+            };
+            outcome.tag = Some(self.tag(&plan.new_version, &message, dry_run)?);
+            outcome.tag_message = (!self.version.lightweight_tags).then_some(message);
+            progress.completed(ReleaseStep::Tag, outcome)?;
         }
 
-        // Run pre_tag hooks
-        for hook in pipeline.hooks() {
-            debug!(hook = hook.name(), "running pre_tag hook");
-            hook.on_pre_tag(&mut ctx)?;
+        pipeline.run_hooks(HookStage::PostTag, ctx).await?;
+
+        if options.push() {
+            progress.started(ReleaseStep::Push);
+            outcome.refspecs = self.push_refspecs(outcome.tag.as_deref(), options.commit())?;
+            self.push(&outcome.refspecs, dry_run)?;
+            outcome.pushed = !dry_run;
+            progress.completed(ReleaseStep::Push, outcome)?;
         }
 
-        if !dry_run {
-            // Create tag
-            let tag_name = format!("{}{next_version}", self.tag_prefix);
-            self.repo
-                .create_tag(&tag_name, &format!("Release {next_version}"))?;
-            info!(%tag_name, "created tag");
+        Ok(())
+    }
+
+    /// Returns the files a release writes, like
+    /// [`planned_files`](Self::planned_files), along with the changed
+    /// `changelog.outputs` files when it has a changelog section.
+    fn planned_release_files(
+        &self,
+        pipeline: &Pipeline,
+        plan: &ReleasePlan,
+        section: Option<&str>,
+    ) -> CoreResult<Vec<String>> {
+        let mut files = self.planned_files(&plan.new_version, section)?;
+        if section.is_some() {
+            files.extend(
+                self.output_changes(pipeline, plan)?
+                    .iter()
+                    .filter(|change| change.is_changed())
+                    .map(|change| change.path.display().to_string()),
+            );
         }
+        Ok(files)
+    }
 
-        // Run post_tag hooks
-        for hook in pipeline.hooks() {
-            debug!(hook = hook.name(), "running post_tag hook");
-            hook.on_post_tag(&mut ctx)?;
+    /// Returns the changelog section of a release: the section of the
+    /// options, or the formatted one. `None` without a changelog.
+    fn release_section(
+        &self,
+        pipeline: &Pipeline,
+        plan: &ReleasePlan,
+        options: &ReleaseOptions,
+    ) -> Option<String> {
+        if !options.changelog() {
+            return None;
         }
+        Some(match options.changelog_section() {
+            Some(section) => section.to_string(),
+            None => self.format_changelog(pipeline, plan),
+        })
+    }
+}
 
-        info!(version = %next_version, "release completed");
-        Ok(next_version)
+/// Removes the changelog fragments of a release.
+fn remove_fragments(plan: &ReleasePlan) -> CoreResult<()> {
+    for fragment in &plan.fragments {
+        fs::remove_file(fragment)?;
+        debug!(fragment = %fragment.display(), "removed changelog fragment");
     }
+    Ok(())
+}
 
-    /// Gets the previous version from the latest tag.
-    fn get_previous_version(&self) -> CoreResult<Option<Version>> {
-        let tag = self.repo.latest_version_tag(&self.tag_prefix)?;
+/// Bumps the version of a file configured with `[[version.file]]`.
+fn bump_custom_file(file: &VersionFileConfig, version: &Version) -> FileResult<String> {
+    let path = Path::new(&file.path);
+    match (&file.pattern, &file.key) {
+        (Some(pattern), _) => bump_version_with_pattern(path, pattern, version),
+        (None, Some(key)) => bump_yaml_version(path, key, version),
+        (None, None) => bump_version_file(path, version),
+    }
+}
+
+/// Reads a file configured with `[[version.file]]`.
+fn read_custom_file(file: &VersionFileConfig) -> FileResult<Version> {
+    let path = Path::new(&file.path);
+    match (&file.pattern, &file.key) {
+        (Some(pattern), _) => read_version_with_pattern(path, pattern),
+        (None, Some(key)) => read_yaml_version(path, key),
+        (None, None) => read_version_from_file(path),
+    }
+}
+
+/// Number of commits parsed in a single call to the parser.
+const PARSE_BATCH_SIZE: usize = 256;
+
+/// Commits of a range, parsed as they are read.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ParsedRange {
+    /// Parsed commits.
+    pub(crate) commits: Vec<ParsedCommit>,
+    /// Commits the parser could not parse.
+    pub(crate) skipped: Vec<SkippedCommit>,
+    /// Number of commits in the range.
+    pub(crate) raw_count: usize,
+}
+
+impl ParsedRange {
+    /// Adds a commit of the range, with its parsed form if the parser could
+    /// parse it.
+    pub(crate) fn push(&mut self, raw: &RawCommit, parsed: Option<ParsedCommit>) {
+        self.raw_count += 1;
+        match parsed {
+            Some(commit) => self.commits.push(commit),
+            None => self.skipped.push(SkippedCommit::from(raw)),
+        }
+    }
+
+    /// Parses a batch of commits of the range, emptying it.
+    fn parse_batch(&mut self, pipeline: &Pipeline, batch: &mut Vec<RawCommit>) {
+        let parsed = pipeline.parse_batch(batch);
+        for (raw, parsed) in batch.iter().zip(parsed) {
+            self.push(raw, parsed);
+        }
+        batch.clear();
+    }
+}
+
+/// Parses the commits `read` walks, or loads them from a cache, keyed
+/// by `head` and the start commit of the range, if any.
+fn parse_cached(
+    pipeline: &Pipeline,
+    head: &str,
+    cached: Option<(&CommitCache, Option<&str>)>,
+    read: impl FnOnce(&mut dyn FnMut(RawCommit)) -> GitResult<()>,
+) -> CoreResult<ParsedRange> {
+    // Pipelines parsing differently, e.g. without highlights, do not share
+    // their commits
+    let parsing = pipeline.parsing_key();
+    if let Some((cache, start)) = &cached
+        && let Some(range) = cache.load(head, *start, &parsing)
+    {
+        debug!(count = range.raw_count, "using cached commits");
+        return Ok(range);
+    }
+
+    // Commits are parsed in batches as they are read, so only the parsed
+    // ones and a batch of raw ones are kept in memory
+    let mut range = ParsedRange::default();
+    let mut batch = Vec::with_capacity(PARSE_BATCH_SIZE);
+    read(&mut |raw| {
+        batch.push(raw);
+        if batch.len() == PARSE_BATCH_SIZE {
+            range.parse_batch(pipeline, &mut batch);
+        }
+    })?;
+    range.parse_batch(pipeline, &mut batch);
+
+    if let Some((cache, start)) = &cached {
+        cache.store(head, *start, &parsing, &range);
+    }
+    Ok(range)
+}
+
+/// Returns the bump type leading from one version to the next.
+fn bump_between(current: &Version, next: &Version) -> BumpType {
+    if next.major != current.major {
+        BumpType::Major
+    } else if next.minor != current.minor {
+        BumpType::Minor
+    } else if next.patch != current.patch {
+        BumpType::Patch
+    } else {
+        BumpType::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = ReleaseOptions::new();
+        assert!(!options.dry_run());
+        assert!(options.bump_type().is_none());
+        assert!(options.changelog());
+        assert!(options.commit());
+        assert!(options.tag());
+        assert!(!options.push());
+        assert!(!options.allow_branch());
+        assert!(!options.allow_same_version());
+        assert!(!options.no_verify());
+        assert!(options.tag_message().is_none());
+    }
+
+    #[test]
+    fn test_options_builder() {
+        let options = ReleaseOptions::new()
+            .with_dry_run(true)
+            .with_bump_type(Some(BumpType::Minor))
+            .with_changelog(false)
+            .with_commit(false)
+            .with_tag(false)
+            .with_push(true)
+            .with_allow_branch(true)
+            .with_allow_same_version(true)
+            .with_no_verify(true)
+            .with_tag_message(Some("Release notes".to_string()))
+            .with_changelog_section(Some("## [1.0.0]".to_string()));
+
+        assert!(options.dry_run());
+        assert_eq!(options.bump_type(), Some(BumpType::Minor));
+        assert!(!options.changelog());
+        assert!(!options.commit());
+        assert!(!options.tag());
+        assert!(options.push());
+        assert!(options.allow_branch());
+        assert!(options.allow_same_version());
+        assert!(options.no_verify());
+        assert_eq!(options.tag_message(), Some("Release notes"));
+        assert_eq!(options.changelog_section(), Some("## [1.0.0]"));
+    }
+
+    #[test]
+    fn test_bump_between() {
+        let current = Version::new(1, 2, 3);
+        assert_eq!(
+            bump_between(&current, &Version::new(2, 0, 0)),
+            BumpType::Major
+        );
+        assert_eq!(
+            bump_between(&current, &Version::new(1, 3, 0)),
+            BumpType::Minor
+        );
+        assert_eq!(
+            bump_between(&current, &Version::new(1, 2, 4)),
+            BumpType::Patch
+        );
+        assert_eq!(bump_between(&current, &current), BumpType::None);
+    }
+
+    #[test]
+    fn test_parsed_range() {
+        let raw =
+            |hash: &str| RawCommit::new(hash, "message\n\nbody", "author", "a@x.org", Utc::now());
+        let mut range = ParsedRange::default();
+        range.push(&raw("aaaaaaaaaa"), None);
+        range.push(
+            &raw("bbb"),
+            Some(ParsedCommit::builder("bbb", "feat").build()),
+        );
+        range.push(&raw("ccc"), None);
 
-        Ok(tag.and_then(|t| self.version_manager.from_tag(&t, &self.tag_prefix)))
+        let skipped: Vec<_> = range
+            .skipped
+            .iter()
+            .map(|commit| commit.hash.as_str())
+            .collect();
+        assert_eq!(skipped, ["aaaaaaa", "ccc"]);
+        assert_eq!(range.skipped[0].subject, "message");
+        assert_eq!(range.commits.len(), 1);
+        assert_eq!(range.raw_count, 3);
     }
 }
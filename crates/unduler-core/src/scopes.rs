@@ -0,0 +1,195 @@
+//! Normalization of commit scopes before grouping.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use unduler_commit::ParsedCommit;
+use unduler_config::ChangelogConfig;
+
+/// Rewrites the scopes of parsed commits into canonical scopes, so that
+/// bump rules and formatters see a single name for each scope.
+///
+/// Scopes are lowercased first when case folding is enabled, then replaced
+/// by their alias target, and finally replaced by the unknown scope when an
+/// allow-list is set and does not contain them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeNormalizer {
+    aliases: BTreeMap<String, String>,
+    case_fold: bool,
+    allowed: BTreeSet<String>,
+    unknown: String,
+}
+
+impl ScopeNormalizer {
+    /// Creates a normalizer leaving every scope unchanged.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the normalizer described by the `changelog.scope_*` settings.
+    #[must_use]
+    pub fn from_config(config: &ChangelogConfig) -> Self {
+        let normalizer = config
+            .scope_aliases
+            .iter()
+            .fold(Self::new(), |normalizer, (alias, scope)| {
+                normalizer.with_alias(alias, scope)
+            })
+            .with_case_fold(config.scope_case_fold);
+        if config.scopes.is_empty() {
+            normalizer
+        } else {
+            normalizer.with_allowed(&config.scopes, &config.unknown_scope)
+        }
+    }
+
+    /// Replaces the scope `alias` with `scope`.
+    #[must_use]
+    pub fn with_alias(mut self, alias: impl Into<String>, scope: impl Into<String>) -> Self {
+        self.aliases.insert(alias.into(), scope.into());
+        self
+    }
+
+    /// Lowercases scopes, and matches aliases and allowed scopes regardless
+    /// of their case.
+    #[must_use]
+    pub fn with_case_fold(mut self, case_fold: bool) -> Self {
+        self.case_fold = case_fold;
+        self
+    }
+
+    /// Replaces the scopes that are neither in `allowed` nor the target of
+    /// an alias with `unknown`.
+    #[must_use]
+    pub fn with_allowed(
+        mut self,
+        allowed: impl IntoIterator<Item = impl Into<String>>,
+        unknown: impl Into<String>,
+    ) -> Self {
+        self.allowed = allowed.into_iter().map(Into::into).collect();
+        self.unknown = unknown.into();
+        self
+    }
+
+    /// Returns true if some scopes are rewritten.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        !self.aliases.is_empty() || self.case_fold || !self.allowed.is_empty()
+    }
+
+    /// Returns the canonical scope of a scope.
+    #[must_use]
+    pub fn normalize(&self, scope: &str) -> String {
+        let folded = if self.case_fold {
+            scope.to_lowercase()
+        } else {
+            scope.to_string()
+        };
+
+        let aliased = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| self.matches(alias, &folded))
+            .map(|(_, target)| target.clone());
+        let is_alias = aliased.is_some();
+        let scope = aliased.unwrap_or(folded);
+
+        if is_alias
+            || self.allowed.is_empty()
+            || self
+                .allowed
+                .iter()
+                .any(|allowed| self.matches(allowed, &scope))
+        {
+            scope
+        } else {
+            self.unknown.clone()
+        }
+    }
+
+    /// Rewrites the scope of a parsed commit, leaving commits without a
+    /// scope unchanged.
+    #[must_use]
+    pub fn apply(&self, mut commit: ParsedCommit) -> ParsedCommit {
+        if let Some(scope) = commit.scope.as_deref().filter(|scope| !scope.is_empty()) {
+            commit.scope = Some(self.normalize(scope));
+        }
+        commit
+    }
+
+    /// Compares a configured scope with a scope, ignoring case when folding.
+    fn matches(&self, configured: &str, scope: &str) -> bool {
+        if self.case_fold {
+            configured.to_lowercase() == scope
+        } else {
+            configured == scope
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_unchanged() {
+        let normalizer = ScopeNormalizer::new();
+        assert!(!normalizer.is_enabled());
+        assert_eq!(normalizer.normalize("UI"), "UI");
+    }
+
+    #[test]
+    fn test_aliases() {
+        let normalizer = ScopeNormalizer::new()
+            .with_alias("ui", "frontend")
+            .with_alias("webapp", "frontend");
+        assert_eq!(normalizer.normalize("ui"), "frontend");
+        assert_eq!(normalizer.normalize("webapp"), "frontend");
+        assert_eq!(normalizer.normalize("api"), "api");
+        assert_eq!(normalizer.normalize("UI"), "UI");
+    }
+
+    #[test]
+    fn test_case_fold() {
+        let normalizer = ScopeNormalizer::new()
+            .with_case_fold(true)
+            .with_alias("UI", "frontend");
+        assert_eq!(normalizer.normalize("Ui"), "frontend");
+        assert_eq!(normalizer.normalize("API"), "api");
+    }
+
+    #[test]
+    fn test_allowed() {
+        let normalizer = ScopeNormalizer::new()
+            .with_alias("ui", "frontend")
+            .with_allowed(["api", "cli"], "misc");
+        assert_eq!(normalizer.normalize("api"), "api");
+        assert_eq!(normalizer.normalize("ui"), "frontend");
+        assert_eq!(normalizer.normalize("deps"), "misc");
+    }
+
+    #[test]
+    fn test_apply() {
+        let normalizer = ScopeNormalizer::new().with_alias("ui", "frontend");
+
+        let commit = ParsedCommit::builder("abc", "feat").scope("ui").build();
+        assert_eq!(normalizer.apply(commit).scope.as_deref(), Some("frontend"));
+
+        let commit = ParsedCommit::builder("abc", "feat").build();
+        assert_eq!(normalizer.apply(commit).scope, None);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = ChangelogConfig {
+            scope_aliases: [("webapp".to_string(), "frontend".to_string())].into(),
+            scope_case_fold: true,
+            scopes: vec!["api".to_string()],
+            ..ChangelogConfig::default()
+        };
+        let normalizer = ScopeNormalizer::from_config(&config);
+        assert_eq!(normalizer.normalize("WebApp"), "frontend");
+        assert_eq!(normalizer.normalize("API"), "api");
+        assert_eq!(normalizer.normalize("docs"), "misc");
+    }
+}
@@ -0,0 +1,109 @@
+//! Message templates.
+
+use chrono::NaiveDate;
+use semver::Version;
+
+/// Renders a release commit message from a template.
+///
+/// Supported placeholders:
+/// - `{version}`: the released version (e.g. `1.2.3`)
+/// - `{tag}`: the release tag name (e.g. `v1.2.3`)
+/// - `{date}`: the release date (e.g. `2024-01-15`)
+#[must_use]
+pub fn render_commit_message(
+    template: &str,
+    version: &Version,
+    tag: &str,
+    date: NaiveDate,
+) -> String {
+    template
+        .replace("{version}", &version.to_string())
+        .replace("{tag}", tag)
+        .replace("{date}", &date.format("%Y-%m-%d").to_string())
+}
+
+/// Renders an annotated release tag message from a template.
+///
+/// Supports the placeholders of [`render_commit_message`], and `{changelog}`
+/// for the changelog section of the release, trimmed. The message is trimmed
+/// so an empty changelog leaves no trailing blank lines.
+#[must_use]
+pub fn render_tag_message(
+    template: &str,
+    version: &Version,
+    tag: &str,
+    date: NaiveDate,
+    changelog: &str,
+) -> String {
+    render_commit_message(template, version, tag, date)
+        .replace("{changelog}", changelog.trim())
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        let message = render_commit_message(
+            "chore(release): {version}",
+            &Version::new(1, 2, 3),
+            "v1.2.3",
+            date(),
+        );
+        assert_eq!(message, "chore(release): 1.2.3");
+    }
+
+    #[test]
+    fn test_render_all_placeholders() {
+        let message = render_commit_message(
+            "release: {tag} ({version}) on {date} [skip ci]",
+            &Version::new(2, 0, 0),
+            "app@2.0.0",
+            date(),
+        );
+        assert_eq!(
+            message,
+            "release: app@2.0.0 (2.0.0) on 2024-01-15 [skip ci]"
+        );
+    }
+
+    #[test]
+    fn test_render_without_placeholders() {
+        let message = render_commit_message("release", &Version::new(1, 0, 0), "v1.0.0", date());
+        assert_eq!(message, "release");
+    }
+
+    #[test]
+    fn test_render_tag_message() {
+        let message = render_tag_message(
+            "Release {version}\n\n{changelog}",
+            &Version::new(1, 2, 0),
+            "v1.2.0",
+            date(),
+            "## [1.2.0]\n\n### Added\n\n- Login\n",
+        );
+        assert_eq!(
+            message,
+            "Release 1.2.0\n\n## [1.2.0]\n\n### Added\n\n- Login"
+        );
+    }
+
+    #[test]
+    fn test_render_tag_message_without_changelog() {
+        let message = render_tag_message(
+            "{tag}\n\n{changelog}",
+            &Version::new(1, 0, 0),
+            "v1.0.0",
+            date(),
+            "",
+        );
+        assert_eq!(message, "v1.0.0");
+    }
+}
@@ -40,6 +40,42 @@ impl VersionManager {
         new_version
     }
 
+    /// Returns the next prerelease of a version on a release channel.
+    ///
+    /// The prerelease number continues from `latest` when it is a prerelease
+    /// of the same version on the same channel (`1.5.0-rc.2` gives
+    /// `1.5.0-rc.3`), and starts at 1 otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the channel is not a valid prerelease identifier.
+    pub fn prerelease(
+        &self,
+        version: &Version,
+        channel: &str,
+        latest: Option<&Version>,
+    ) -> Result<Version, semver::Error> {
+        let number = latest
+            .filter(|latest| {
+                (latest.major, latest.minor, latest.patch)
+                    == (version.major, version.minor, version.patch)
+            })
+            .and_then(|latest| {
+                latest
+                    .pre
+                    .as_str()
+                    .strip_prefix(channel)?
+                    .strip_prefix('.')?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .map_or(1, |number| number + 1);
+
+        let mut next = Version::new(version.major, version.minor, version.patch);
+        next.pre = semver::Prerelease::new(&format!("{channel}.{number}"))?;
+        Ok(next)
+    }
+
     /// Parses a version string.
     ///
     /// # Errors
@@ -91,6 +127,48 @@ mod tests {
         assert_eq!(bumped, Version::new(1, 2, 4));
     }
 
+    #[test]
+    fn test_prerelease_first() {
+        let vm = VersionManager::new();
+        let version = Version::new(1, 5, 0);
+        let next = vm.prerelease(&version, "rc", None).unwrap();
+        assert_eq!(next, Version::parse("1.5.0-rc.1").unwrap());
+    }
+
+    #[test]
+    fn test_prerelease_increments() {
+        let vm = VersionManager::new();
+        let version = Version::new(1, 5, 0);
+        let latest = Version::parse("1.5.0-rc.2").unwrap();
+        let next = vm.prerelease(&version, "rc", Some(&latest)).unwrap();
+        assert_eq!(next, Version::parse("1.5.0-rc.3").unwrap());
+    }
+
+    #[test]
+    fn test_prerelease_restarts_for_new_version() {
+        let vm = VersionManager::new();
+        let version = Version::new(2, 0, 0);
+        let latest = Version::parse("1.5.0-rc.2").unwrap();
+        let next = vm.prerelease(&version, "rc", Some(&latest)).unwrap();
+        assert_eq!(next, Version::parse("2.0.0-rc.1").unwrap());
+    }
+
+    #[test]
+    fn test_prerelease_ignores_other_channels() {
+        let vm = VersionManager::new();
+        let version = Version::new(1, 5, 0);
+        let latest = Version::parse("1.5.0-beta.4").unwrap();
+        let next = vm.prerelease(&version, "rc", Some(&latest)).unwrap();
+        assert_eq!(next, Version::parse("1.5.0-rc.1").unwrap());
+    }
+
+    #[test]
+    fn test_prerelease_invalid_channel() {
+        let vm = VersionManager::new();
+        let version = Version::new(1, 5, 0);
+        assert!(vm.prerelease(&version, "release candidate", None).is_err());
+    }
+
     #[test]
     fn test_from_tag() {
         let vm = VersionManager::new();
@@ -0,0 +1,111 @@
+//! Commit collection options.
+
+/// Options controlling which commits are collected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitOptions {
+    paths: Vec<String>,
+    first_parent: bool,
+    skip_merges: bool,
+    max_count: Option<usize>,
+}
+
+impl CommitOptions {
+    /// Creates options collecting every commit.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only collects commits touching paths matching these pathspecs.
+    ///
+    /// Pathspecs are relative to the repository root and support glob
+    /// patterns (e.g. `crates/foo/**`). An empty list matches every commit.
+    #[must_use]
+    pub fn with_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only follows the first parent of merge commits (`--first-parent`).
+    ///
+    /// Commits brought by merged branches are then skipped, leaving the
+    /// merge commits themselves.
+    #[must_use]
+    pub fn with_first_parent(mut self, first_parent: bool) -> Self {
+        self.first_parent = first_parent;
+        self
+    }
+
+    /// Skips merge commits (`--no-merges`).
+    #[must_use]
+    pub fn with_skip_merges(mut self, skip_merges: bool) -> Self {
+        self.skip_merges = skip_merges;
+        self
+    }
+
+    /// Limits the number of collected commits (`--max-count`).
+    #[must_use]
+    pub fn with_max_count(mut self, max_count: Option<usize>) -> Self {
+        self.max_count = max_count;
+        self
+    }
+
+    /// Returns the path filters.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Returns whether only first parents are followed.
+    #[must_use]
+    pub fn first_parent(&self) -> bool {
+        self.first_parent
+    }
+
+    /// Returns whether merge commits are skipped.
+    #[must_use]
+    pub fn skip_merges(&self) -> bool {
+        self.skip_merges
+    }
+
+    /// Returns the maximum number of collected commits.
+    #[must_use]
+    pub fn max_count(&self) -> Option<usize> {
+        self.max_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = CommitOptions::new();
+        assert!(options.paths().is_empty());
+        assert!(!options.first_parent());
+        assert!(!options.skip_merges());
+        assert!(options.max_count().is_none());
+    }
+
+    #[test]
+    fn test_with_paths() {
+        let options = CommitOptions::new().with_paths(["crates/foo/**", "Cargo.toml"]);
+        assert_eq!(options.paths(), ["crates/foo/**", "Cargo.toml"]);
+    }
+
+    #[test]
+    fn test_with_traversal_options() {
+        let options = CommitOptions::new()
+            .with_first_parent(true)
+            .with_skip_merges(true)
+            .with_max_count(Some(10));
+        assert!(options.first_parent());
+        assert!(options.skip_merges());
+        assert_eq!(options.max_count(), Some(10));
+    }
+}
@@ -17,6 +17,62 @@ pub enum GitError {
     #[error("tag not found: {0}")]
     TagNotFound(String),
 
+    /// Tag already exists.
+    #[error("tag already exists: {0}")]
+    TagExists(String),
+
+    /// Revision not found.
+    #[error("revision not found: {0}")]
+    RevisionNotFound(String),
+
+    /// Remote not found.
+    #[error("remote not found: {0}")]
+    RemoteNotFound(String),
+
+    /// Push to a remote failed.
+    #[error("failed to push to {remote}: {reason}")]
+    PushFailed {
+        /// Remote name.
+        remote: String,
+        /// Failure reason.
+        reason: String,
+    },
+
+    /// Push of a reference rejected by the remote.
+    #[error("push of {reference} rejected: {reason}")]
+    PushRejected {
+        /// Rejected reference.
+        reference: String,
+        /// Rejection reason reported by the remote.
+        reason: String,
+    },
+
+    /// History truncated by a shallow clone.
+    #[error(
+        "history is truncated in this shallow clone; run `git fetch --unshallow --tags`, \
+         or check out the full history in CI (e.g. `fetch-depth: 0`)"
+    )]
+    ShallowClone,
+
+    /// No name or email configured for commits and tags.
+    #[error("no git identity configured, user.name and user.email must be set")]
+    MissingIdentity,
+
+    /// Identity not written `Name <email>`.
+    #[error("invalid identity `{0}`, expected `Name <email>`")]
+    InvalidIdentity(String),
+
+    /// Commit hook exited with a failure.
+    #[error("{hook} hook failed with {status}: {output}")]
+    HookFailed {
+        /// Hook name.
+        hook: String,
+        /// Exit status of the hook.
+        status: std::process::ExitStatus,
+        /// Captured stdout and stderr of the hook.
+        output: String,
+    },
+
     /// No commits found.
     #[error("no commits found")]
     NoCommits,
@@ -56,6 +112,51 @@ mod tests {
         assert_eq!(err.to_string(), "tag not found: v1.0.0");
     }
 
+    #[test]
+    fn test_tag_exists_display() {
+        let err = GitError::TagExists("v1.0.0".to_string());
+        assert_eq!(err.to_string(), "tag already exists: v1.0.0");
+    }
+
+    #[test]
+    fn test_revision_not_found_display() {
+        let err = GitError::RevisionNotFound("main~3".to_string());
+        assert_eq!(err.to_string(), "revision not found: main~3");
+    }
+
+    #[test]
+    fn test_push_rejected_display() {
+        let err = GitError::PushRejected {
+            reference: "refs/heads/main".to_string(),
+            reason: "non-fast-forward".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "push of refs/heads/main rejected: non-fast-forward"
+        );
+    }
+
+    #[test]
+    fn test_shallow_clone_display() {
+        let err = GitError::ShallowClone;
+        assert!(err.to_string().contains("git fetch --unshallow"));
+    }
+
+    #[test]
+    fn test_missing_identity_display() {
+        let err = GitError::MissingIdentity;
+        assert!(err.to_string().contains("user.name and user.email"));
+    }
+
+    #[test]
+    fn test_invalid_identity_display() {
+        let err = GitError::InvalidIdentity("release-bot".to_string());
+        assert_eq!(
+            err.to_string(),
+            "invalid identity `release-bot`, expected `Name <email>`"
+        );
+    }
+
     #[test]
     fn test_no_commits_display() {
         let err = GitError::NoCommits;
@@ -0,0 +1,87 @@
+//! Commit author identities.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::GitError;
+
+/// Name and email of a commit author, like `release-bot <bot@example.com>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    name: String,
+    email: String,
+}
+
+impl Identity {
+    /// Creates an identity.
+    #[must_use]
+    pub fn new(name: impl Into<String>, email: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            email: email.into(),
+        }
+    }
+
+    /// Returns the name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the email.
+    #[must_use]
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+}
+
+impl FromStr for Identity {
+    type Err = GitError;
+
+    /// Parses an identity written `Name <email>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || GitError::InvalidIdentity(s.to_string());
+        let (name, rest) = s.split_once('<').ok_or_else(invalid)?;
+        let email = rest.strip_suffix('>').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() || email.is_empty() || email.contains(['<', '>']) {
+            return Err(invalid());
+        }
+        Ok(Self::new(name, email))
+    }
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let identity: Identity = "release-bot <bot@ci>".parse().unwrap();
+        assert_eq!(identity.name(), "release-bot");
+        assert_eq!(identity.email(), "bot@ci");
+        assert_eq!(identity.to_string(), "release-bot <bot@ci>");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for s in [
+            "release-bot",
+            "<bot@ci>",
+            "release-bot <>",
+            "bot <bot@ci",
+            "a <b> c",
+        ] {
+            assert!(
+                matches!(s.parse::<Identity>(), Err(GitError::InvalidIdentity(_))),
+                "{s}"
+            );
+        }
+    }
+}
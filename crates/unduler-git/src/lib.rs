@@ -2,11 +2,24 @@
 //!
 //! This crate provides Git operations:
 //! - Repository management
-//! - Commit retrieval
+//! - Commit retrieval and filtering
+//! - Commit creation
 //! - Tag management
+//! - Tag name templates
+//! - Working tree status
 
+mod commit_options;
 mod error;
+mod identity;
+mod new_commit_options;
 mod repository;
+mod status;
+mod tag_format;
 
+pub use commit_options::CommitOptions;
 pub use error::{GitError, GitResult};
+pub use identity::Identity;
+pub use new_commit_options::NewCommitOptions;
 pub use repository::Repository;
+pub use status::{FileChange, FileStatus};
+pub use tag_format::TagFormat;
@@ -0,0 +1,93 @@
+//! Options for creating commits.
+
+use crate::Identity;
+
+/// Options controlling how [`Repository::commit_with`](crate::Repository::commit_with)
+/// stages changes and creates the commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewCommitOptions {
+    paths: Vec<String>,
+    identity: Option<Identity>,
+    verify: bool,
+}
+
+impl NewCommitOptions {
+    /// Creates options staging every change, with the identity of the git
+    /// configuration, and without the commit hooks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only commits changes matching these pathspecs.
+    ///
+    /// Pathspecs are relative to the repository root. Changes staged outside
+    /// them stay staged and out of the commit. An empty list commits every
+    /// change.
+    #[must_use]
+    pub fn with_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the author and committer, instead of `user.name` and
+    /// `user.email` from the git configuration.
+    #[must_use]
+    pub fn with_identity(mut self, identity: Option<Identity>) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    /// Runs the `pre-commit` and `commit-msg` hooks.
+    #[must_use]
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Returns the staged pathspecs.
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// Returns the author and committer set instead of the git configuration.
+    #[must_use]
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Returns whether the commit hooks run.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        self.verify
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = NewCommitOptions::new();
+        assert!(options.paths().is_empty());
+        assert!(options.identity().is_none());
+        assert!(!options.verify());
+    }
+
+    #[test]
+    fn test_options_builder() {
+        let options = NewCommitOptions::new()
+            .with_paths(["Cargo.toml", "CHANGELOG.md"])
+            .with_identity(Some(Identity::new("release-bot", "bot@ci")))
+            .with_verify(true);
+        assert_eq!(options.paths(), ["Cargo.toml", "CHANGELOG.md"]);
+        assert_eq!(options.identity().unwrap().name(), "release-bot");
+        assert!(options.verify());
+    }
+}
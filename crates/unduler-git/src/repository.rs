@@ -1,19 +1,43 @@
 //! Git repository wrapper.
 
-use std::path::Path;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use chrono::{TimeZone, Utc};
 use git2::Repository as Git2Repo;
+use tracing::{debug, instrument};
 use unduler_commit::RawCommit;
 
-use crate::{GitError, GitResult};
+use crate::{
+    CommitOptions, FileStatus, GitError, GitResult, Identity, NewCommitOptions, TagFormat,
+};
+
+/// Fetch depth deepening a shallow clone to its full history, as defined by
+/// libgit2 (`GIT_FETCH_DEPTH_UNSHALLOW`).
+const UNSHALLOW_DEPTH: i32 = i32::MAX;
 
 /// A Git repository wrapper.
 pub struct Repository {
     inner: Git2Repo,
+    /// Tag names, read once and kept up to date by [`Self::create_tag`].
+    tags: RefCell<Option<Vec<String>>>,
+    /// Identity used instead of `user.name` and `user.email` for commits
+    /// and tags.
+    identity: Option<Identity>,
 }
 
 impl Repository {
+    fn from_inner(inner: Git2Repo) -> Self {
+        Self {
+            inner,
+            tags: RefCell::new(None),
+            identity: None,
+        }
+    }
+
     /// Opens a repository at the given path.
     ///
     /// # Errors
@@ -22,17 +46,61 @@ impl Repository {
     pub fn open(path: impl AsRef<Path>) -> GitResult<Self> {
         let path = path.as_ref();
         let inner = Git2Repo::open(path).map_err(|_| GitError::NotARepo(path.to_path_buf()))?;
-        Ok(Self { inner })
+        Ok(Self::from_inner(inner))
     }
 
     /// Discovers the repository from the current directory.
     ///
     /// # Errors
     ///
-    /// Returns an error if no repository is found.
+    /// Returns [`GitError::RepoNotFound`] if no repository is found.
     pub fn discover() -> GitResult<Self> {
-        let inner = Git2Repo::discover(".")?;
-        Ok(Self { inner })
+        let inner = Git2Repo::discover(".").map_err(|e| {
+            if e.code() == git2::ErrorCode::NotFound {
+                GitError::RepoNotFound(std::env::current_dir().unwrap_or_default())
+            } else {
+                e.into()
+            }
+        })?;
+        Ok(Self::from_inner(inner))
+    }
+
+    /// Sets the identity of commits and tags, instead of `user.name` and
+    /// `user.email` from the git configuration.
+    #[must_use]
+    pub fn with_identity(mut self, identity: Identity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Returns the identity of commits and tags, set on the repository or
+    /// else from the git configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::MissingIdentity`] if no name or email is
+    /// configured, or an error if the git configuration cannot be read.
+    pub fn identity(&self) -> GitResult<Identity> {
+        if let Some(identity) = &self.identity {
+            return Ok(identity.clone());
+        }
+        let config = self.inner.config()?;
+        let value = |key: &str| match config.get_string(key) {
+            Ok(value) if !value.is_empty() => Ok(value),
+            Ok(_) => Err(GitError::MissingIdentity),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Err(GitError::MissingIdentity),
+            Err(e) => Err(e.into()),
+        };
+        Ok(Identity::new(value("user.name")?, value("user.email")?))
+    }
+
+    /// Returns a signature of `identity`, or of [`Self::identity`].
+    fn signature(&self, identity: Option<&Identity>) -> GitResult<git2::Signature<'static>> {
+        if let Some(identity) = identity {
+            return Ok(git2::Signature::now(identity.name(), identity.email())?);
+        }
+        let identity = self.identity()?;
+        Ok(git2::Signature::now(identity.name(), identity.email())?)
     }
 
     /// Returns the repository root path.
@@ -41,26 +109,200 @@ impl Repository {
         self.inner.workdir().unwrap_or_else(|| self.inner.path())
     }
 
+    /// Returns true if the repository is a shallow clone.
+    #[must_use]
+    pub fn is_shallow(&self) -> bool {
+        self.inner.is_shallow()
+    }
+
+    /// Fetches the full history and the tags of a shallow clone from a
+    /// remote (`git fetch --unshallow --tags`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::RemoteNotFound`] if the remote does not exist, or
+    /// an error if the fetch fails.
+    #[instrument(skip(self))]
+    pub fn unshallow(&self, remote: &str) -> GitResult<()> {
+        let mut git_remote = match self.inner.find_remote(remote) {
+            Ok(git_remote) => git_remote,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                return Err(GitError::RemoteNotFound(remote.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let config = self.inner.config()?;
+        let mut credentials = Credentials::default();
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(|url, username, allowed| {
+            credentials.next(&config, url, username, allowed)
+        });
+
+        let mut options = git2::FetchOptions::new();
+        options
+            .remote_callbacks(callbacks)
+            .download_tags(git2::AutotagOption::All)
+            .depth(UNSHALLOW_DEPTH);
+        git_remote.fetch::<&str>(&[], Some(&mut options), None)?;
+
+        *self.tags.borrow_mut() = None;
+        Ok(())
+    }
+
+    /// Returns the commit checked out, or `None` on an unborn branch of a
+    /// repository without commits.
+    fn head_commit(&self) -> GitResult<Option<git2::Commit<'_>>> {
+        match self.inner.head() {
+            Ok(head) => Ok(Some(head.peel_to_commit()?)),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the ids of the commits whose parents a shallow clone lacks.
+    fn shallow_boundary(&self) -> GitResult<HashSet<git2::Oid>> {
+        if !self.is_shallow() {
+            return Ok(HashSet::new());
+        }
+        let content = match fs::read_to_string(self.inner.path().join("shallow")) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content
+            .lines()
+            .filter_map(|line| git2::Oid::from_str(line.trim()).ok())
+            .collect())
+    }
+
     /// Returns all tags in the repository.
     ///
+    /// Tags are read once and cached, so that looking up the tags of many
+    /// packages lists them a single time.
+    ///
     /// # Errors
     ///
     /// Returns an error if tags cannot be read.
     pub fn tags(&self) -> GitResult<Vec<String>> {
-        let tags = self.inner.tag_names(None)?;
-        Ok(tags.iter().flatten().map(String::from).collect())
+        let mut cache = self.tags.borrow_mut();
+        if let Some(ref tags) = *cache {
+            return Ok(tags.clone());
+        }
+        let tags: Vec<String> = self
+            .inner
+            .tag_names(None)?
+            .iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        *cache = Some(tags.clone());
+        Ok(tags)
+    }
+
+    /// Returns the tags matching a glob pattern, where `*` matches any
+    /// characters and `?` a single one (e.g. `pkg-a-v*`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags cannot be read.
+    pub fn tags_matching(&self, glob: &str) -> GitResult<Vec<String>> {
+        let mut tags = self.tags()?;
+        tags.retain(|tag| glob_matches(glob, tag));
+        Ok(tags)
     }
 
     /// Returns commits since the given tag.
     ///
-    /// If tag is `None`, returns all commits.
+    /// If tag is `None`, returns all commits. An unborn branch has no
+    /// commits.
     ///
     /// # Errors
     ///
-    /// Returns an error if commits cannot be read.
+    /// Returns [`GitError::ShallowClone`] if the commits since the tag go
+    /// past the history of a shallow clone, or an error if commits cannot be
+    /// read.
     pub fn commits_since(&self, tag: Option<&str>) -> GitResult<Vec<RawCommit>> {
+        self.commits_since_with(tag, &CommitOptions::default())
+    }
+
+    /// Returns commits since the given tag, filtered by the given options.
+    ///
+    /// If tag is `None`, returns all matching commits. An unborn branch has
+    /// no commits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::ShallowClone`] if the commits since the tag go
+    /// past the history of a shallow clone, or an error if commits cannot be
+    /// read.
+    #[instrument(skip(self, options))]
+    pub fn commits_since_with(
+        &self,
+        tag: Option<&str>,
+        options: &CommitOptions,
+    ) -> GitResult<Vec<RawCommit>> {
+        let mut commits = Vec::new();
+        self.for_each_commit_since(tag, options, |commit| commits.push(commit))?;
+        Ok(commits)
+    }
+
+    /// Calls `f` with each commit since the given tag, filtered by the given
+    /// options, newest first.
+    ///
+    /// Unlike [`commits_since_with`](Self::commits_since_with), commits are
+    /// not collected, so long histories can be processed in constant memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::ShallowClone`] if the commits since the tag go
+    /// past the history of a shallow clone, or an error if commits cannot be
+    /// read.
+    #[instrument(skip(self, options, f))]
+    pub fn for_each_commit_since(
+        &self,
+        tag: Option<&str>,
+        options: &CommitOptions,
+        f: impl FnMut(RawCommit),
+    ) -> GitResult<()> {
+        let Some(head) = self.head_commit()? else {
+            return Ok(());
+        };
+        self.walk_commits_since(tag, head.id(), options, f)
+    }
+
+    /// Calls `f` with each commit since the given tag up to `head`, a
+    /// revision, filtered by the given options, newest first.
+    ///
+    /// Like [`for_each_commit_since`](Self::for_each_commit_since), from
+    /// `head` rather than HEAD.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::ShallowClone`] if the commits since the tag go
+    /// past the history of a shallow clone, or an error if `head` cannot be
+    /// resolved or commits cannot be read.
+    #[instrument(skip(self, options, f))]
+    pub fn for_each_commit_since_to(
+        &self,
+        tag: Option<&str>,
+        head: &str,
+        options: &CommitOptions,
+        f: impl FnMut(RawCommit),
+    ) -> GitResult<()> {
+        self.walk_commits_since(tag, self.resolve_commit(head)?, options, f)
+    }
+
+    /// Calls `f` with each commit since the given tag up to `head`.
+    fn walk_commits_since(
+        &self,
+        tag: Option<&str>,
+        head: git2::Oid,
+        options: &CommitOptions,
+        f: impl FnMut(RawCommit),
+    ) -> GitResult<()> {
         let mut revwalk = self.inner.revwalk()?;
-        revwalk.push_head()?;
+        revwalk.push(head)?;
 
         // If we have a tag, stop at it
         if let Some(tag_name) = tag {
@@ -71,32 +313,159 @@ impl Repository {
             let tag_oid = tag_ref
                 .target()
                 .ok_or_else(|| GitError::TagNotFound(tag_name.to_string()))?;
-            revwalk.hide(tag_oid)?;
+            // The tagged commit of a shallow clone may not have been fetched
+            revwalk.hide(tag_oid).map_err(|e| {
+                if self.is_shallow() && e.code() == git2::ErrorCode::NotFound {
+                    GitError::ShallowClone
+                } else {
+                    e.into()
+                }
+            })?;
         }
 
+        self.walk_commits(revwalk, options, f)
+    }
+
+    /// Returns commits reachable from `to` but not from `from`.
+    ///
+    /// Both ends accept any revision (tag, branch, hash or `HEAD`). If `from`
+    /// is `None`, returns all commits reachable from `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a revision cannot be resolved or commits cannot be read.
+    #[instrument(skip(self, options))]
+    pub fn commits_between(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        options: &CommitOptions,
+    ) -> GitResult<Vec<RawCommit>> {
         let mut commits = Vec::new();
+        self.for_each_commit_between(from, to, options, |commit| commits.push(commit))?;
+        Ok(commits)
+    }
+
+    /// Calls `f` with each commit reachable from `to` but not from `from`,
+    /// newest first, without collecting them.
+    ///
+    /// See [`commits_between`](Self::commits_between).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a revision cannot be resolved or commits cannot be read.
+    #[instrument(skip(self, options, f))]
+    pub fn for_each_commit_between(
+        &self,
+        from: Option<&str>,
+        to: &str,
+        options: &CommitOptions,
+        f: impl FnMut(RawCommit),
+    ) -> GitResult<()> {
+        let mut revwalk = self.inner.revwalk()?;
+        revwalk.push(self.resolve_commit(to)?)?;
+
+        if let Some(from) = from {
+            revwalk.hide(self.resolve_commit(from)?)?;
+        }
+
+        self.walk_commits(revwalk, options, f)
+    }
+
+    /// Returns the id of the HEAD commit, `None` on an unborn branch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if HEAD cannot be read.
+    pub fn head_id(&self) -> GitResult<Option<String>> {
+        Ok(self.head_commit()?.map(|commit| commit.id().to_string()))
+    }
+
+    /// Returns the id of the commit a revision (tag, branch, hash or
+    /// `HEAD`) points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::RevisionNotFound`] if the revision does not
+    /// resolve to a commit.
+    pub fn commit_id(&self, revision: &str) -> GitResult<String> {
+        Ok(self.resolve_commit(revision)?.to_string())
+    }
+
+    /// Resolves a revision to a commit id.
+    fn resolve_commit(&self, revision: &str) -> GitResult<git2::Oid> {
+        let commit = self
+            .inner
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| GitError::RevisionNotFound(revision.to_string()))?;
+        Ok(commit.id())
+    }
+
+    /// Calls `f` with the commits of a revision walk, filtered by the given
+    /// options.
+    fn walk_commits(
+        &self,
+        mut revwalk: git2::Revwalk<'_>,
+        options: &CommitOptions,
+        mut f: impl FnMut(RawCommit),
+    ) -> GitResult<()> {
+        if options.first_parent() {
+            revwalk.simplify_first_parent()?;
+        }
+
+        let boundary = self.shallow_boundary()?;
+        let mut count = 0;
         for oid in revwalk {
+            if options.max_count().is_some_and(|max| count >= max) {
+                break;
+            }
+
+            // Commits past the boundary are missing, so the range is incomplete
             let oid = oid?;
+            if boundary.contains(&oid) {
+                return Err(GitError::ShallowClone);
+            }
             let commit = self.inner.find_commit(oid)?;
 
-            let message = commit.message().unwrap_or("").to_string();
-            let author = commit.author();
-            let time = commit.time();
-
-            let raw = RawCommit::new(
-                oid.to_string(),
-                message,
-                author.name().unwrap_or("Unknown"),
-                author.email().unwrap_or(""),
-                Utc.timestamp_opt(time.seconds(), 0)
-                    .single()
-                    .unwrap_or_else(Utc::now),
-            );
-
-            commits.push(raw);
+            if options.skip_merges() && commit.parent_count() > 1 {
+                continue;
+            }
+
+            if !options.paths().is_empty() && !self.touches_paths(&commit, options.paths())? {
+                continue;
+            }
+
+            f(raw_commit(&commit));
+            count += 1;
         }
 
-        Ok(commits)
+        Ok(())
+    }
+
+    /// Returns whether a commit changes files matching the given pathspecs.
+    ///
+    /// Commits are compared to their first parent, so merge commits count
+    /// the changes brought by the merged branch.
+    fn touches_paths(&self, commit: &git2::Commit<'_>, paths: &[String]) -> GitResult<bool> {
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+
+        let mut diff_options = git2::DiffOptions::new();
+        for path in paths {
+            diff_options.pathspec(path);
+        }
+
+        let diff = self.inner.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&tree),
+            Some(&mut diff_options),
+        )?;
+
+        Ok(diff.deltas().len() > 0)
     }
 
     /// Returns the latest tag matching a version pattern.
@@ -105,87 +474,606 @@ impl Repository {
     ///
     /// Returns an error if tags cannot be read.
     pub fn latest_version_tag(&self, prefix: &str) -> GitResult<Option<String>> {
+        let latest = self.latest_tag_matching(&TagFormat::from_prefix(prefix))?;
+        Ok(latest.map(|(tag, _)| tag))
+    }
+
+    /// Returns the latest tag matching a tag format, along with its version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags cannot be read.
+    pub fn latest_tag_matching(
+        &self,
+        format: &TagFormat,
+    ) -> GitResult<Option<(String, semver::Version)>> {
         let tags = self.tags()?;
 
-        // Find tags matching the prefix and parse as semver
+        // Find tags matching the format and extract their version
         let mut version_tags: Vec<_> = tags
             .into_iter()
-            .filter(|t| t.starts_with(prefix))
-            .filter_map(|t| {
-                let version_str = t.strip_prefix(prefix)?;
-                semver::Version::parse(version_str).ok().map(|v| (t, v))
-            })
+            .filter_map(|t| format.parse(&t).map(|v| (t, v)))
             .collect();
 
         // Sort by version descending
         version_tags.sort_by(|a, b| b.1.cmp(&a.1));
 
-        Ok(version_tags.into_iter().next().map(|(tag, _)| tag))
+        Ok(version_tags.into_iter().next())
     }
 
-    /// Creates a new tag.
+    /// Returns the latest tag matching a tag format on a commit before
+    /// `revision`, i.e. reachable from it, along with its version.
+    ///
+    /// Tags on the commit of `revision` itself are left out, so a version tag
+    /// leads to the previous one.
     ///
     /// # Errors
     ///
-    /// Returns an error if the tag cannot be created.
+    /// Returns an error if `revision` cannot be resolved or tags cannot be
+    /// read.
+    pub fn latest_tag_before(
+        &self,
+        format: &TagFormat,
+        revision: &str,
+    ) -> GitResult<Option<(String, semver::Version)>> {
+        let target = self.resolve_commit(revision)?;
+        let mut latest: Option<(String, semver::Version)> = None;
+        for tag in self.tags()? {
+            let Some(version) = format.parse(&tag) else {
+                continue;
+            };
+            if latest
+                .as_ref()
+                .is_some_and(|(_, latest)| *latest >= version)
+            {
+                continue;
+            }
+            let commit = self.resolve_commit(&tag)?;
+            if self.inner.graph_descendant_of(target, commit)? {
+                latest = Some((tag, version));
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Returns the latest tag of a package, along with its version, for a
+    /// tag format with a `{package}` placeholder (e.g. `{package}-v{version}`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags cannot be read.
+    pub fn latest_tag_for(
+        &self,
+        format: &TagFormat,
+        package: &str,
+    ) -> GitResult<Option<(String, semver::Version)>> {
+        self.latest_tag_matching(&format.clone().with_package(package))
+    }
+
+    /// Returns the latest tag of a release channel, along with its version.
+    ///
+    /// With `None`, only stable versions are considered. With a prerelease
+    /// identifier (e.g. `rc`), only versions like `1.5.0-rc.2` are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags cannot be read.
+    pub fn latest_tag_in_channel(
+        &self,
+        format: &TagFormat,
+        channel: Option<&str>,
+    ) -> GitResult<Option<(String, semver::Version)>> {
+        let in_channel = |version: &semver::Version| match channel {
+            Some(channel) => version.pre.as_str().split('.').next() == Some(channel),
+            None => version.pre.is_empty(),
+        };
+
+        let latest = self
+            .tags()?
+            .into_iter()
+            .filter_map(|t| format.parse(&t).map(|v| (t, v)))
+            .filter(|(_, v)| in_channel(v))
+            .max_by(|a, b| a.1.cmp(&b.1));
+
+        Ok(latest)
+    }
+
+    /// Returns true if a tag with this name exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if references cannot be read.
+    pub fn tag_exists(&self, name: &str) -> GitResult<bool> {
+        match self.inner.find_reference(&format!("refs/tags/{name}")) {
+            Ok(_) => Ok(true),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Creates a new annotated tag on HEAD.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::TagExists`] if the tag already exists,
+    /// [`GitError::NoCommits`] on an unborn branch, or an error if the tag
+    /// cannot be created.
+    #[instrument(skip(self, message))]
     pub fn create_tag(&self, name: &str, message: &str) -> GitResult<()> {
-        let head = self.inner.head()?;
-        let commit = head.peel_to_commit()?;
-        let sig = self.inner.signature()?;
+        let commit = self.tag_target(name)?;
+        let sig = self.signature(None)?;
 
         self.inner
             .tag(name, commit.as_object(), &sig, message, false)?;
+        self.cache_tag(name);
+
+        Ok(())
+    }
+
+    /// Creates a new lightweight tag on HEAD, without message or tagger.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::TagExists`] if the tag already exists,
+    /// [`GitError::NoCommits`] on an unborn branch, or an error if the tag
+    /// cannot be created.
+    #[instrument(skip(self))]
+    pub fn create_lightweight_tag(&self, name: &str) -> GitResult<()> {
+        let commit = self.tag_target(name)?;
+
+        self.inner
+            .tag_lightweight(name, commit.as_object(), false)?;
+        self.cache_tag(name);
 
         Ok(())
     }
 
+    /// Returns the commit a new tag points to, checking the tag does not
+    /// exist yet.
+    fn tag_target(&self, name: &str) -> GitResult<git2::Commit<'_>> {
+        if self.tag_exists(name)? {
+            return Err(GitError::TagExists(name.to_string()));
+        }
+        self.head_commit()?.ok_or(GitError::NoCommits)
+    }
+
+    /// Adds a created tag to the cached tag names.
+    fn cache_tag(&self, name: &str) {
+        if let Some(ref mut tags) = *self.tags.borrow_mut() {
+            tags.push(name.to_string());
+        }
+    }
+
     /// Stages all modified and new files and creates a commit.
     ///
     /// # Errors
     ///
     /// Returns an error if the commit cannot be created.
     pub fn commit(&self, message: &str) -> GitResult<git2::Oid> {
-        let sig = self.inner.signature()?;
+        self.commit_with(message, &NewCommitOptions::new())
+    }
 
-        // Add all changes to index
+    /// Stages the changes selected by `options` and creates a commit.
+    ///
+    /// With pathspecs, only their changes are committed: changes staged
+    /// beforehand elsewhere stay staged. When enabled, the `pre-commit` hook
+    /// runs once the changes are staged, and the `commit-msg` hook can then
+    /// rewrite the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GitError::HookFailed`] if a hook fails, or an error if the
+    /// commit cannot be created.
+    #[instrument(skip_all)]
+    pub fn commit_with(&self, message: &str, options: &NewCommitOptions) -> GitResult<git2::Oid> {
+        let sig = self.signature(options.identity())?;
+
+        // Add changes to index, including removed files
+        let only_paths = !options.paths().is_empty();
+        let pathspecs = if only_paths {
+            options.paths().to_vec()
+        } else {
+            vec![".".to_string()]
+        };
         let mut index = self.inner.index()?;
-        index.add_all(["."], git2::IndexAddOption::DEFAULT, None)?;
+        index.add_all(&pathspecs, git2::IndexAddOption::DEFAULT, None)?;
+        index.update_all(&pathspecs, None)?;
         index.write()?;
 
-        let tree_id = index.write_tree()?;
+        let mut message = message.to_string();
+        if options.verify() {
+            self.run_hook("pre-commit", &[])?;
+            // The hook may have staged more changes
+            index.read(true)?;
+
+            let message_file = self.inner.path().join("COMMIT_EDITMSG");
+            fs::write(&message_file, &message)?;
+            if self.run_hook("commit-msg", &[message_file.as_path()])? {
+                message = fs::read_to_string(&message_file)?;
+            }
+        }
+
+        let tree_id = if only_paths {
+            self.write_pathspec_tree(&index, &pathspecs)?
+        } else {
+            index.write_tree()?
+        };
         let tree = self.inner.find_tree(tree_id)?;
 
-        let parent = self.inner.head().ok().and_then(|h| h.peel_to_commit().ok());
+        // The first commit of an unborn branch has no parent
+        let parent = self.head_commit()?;
         let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
 
         let oid = self
             .inner
-            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+            .commit(Some("HEAD"), &sig, &sig, &message, &tree, &parents)?;
 
         Ok(oid)
     }
 
+    /// Writes the tree of HEAD updated with the index entries matching
+    /// `pathspecs`, leaving out the other staged changes.
+    fn write_pathspec_tree(
+        &self,
+        index: &git2::Index,
+        pathspecs: &[String],
+    ) -> GitResult<git2::Oid> {
+        let pathspec = git2::Pathspec::new(pathspecs)?;
+        let matches =
+            |path: &[u8]| pathspec.matches_path(&bytes_path(path), git2::PathspecFlags::DEFAULT);
+
+        let mut tree_index = git2::Index::new()?;
+        if let Some(head) = self.head_commit()? {
+            tree_index.read_tree(&head.tree()?)?;
+        }
+
+        // Files removed by the staged changes
+        let removed: Vec<Vec<u8>> = tree_index
+            .iter()
+            .filter(|entry| matches(&entry.path))
+            .filter(|entry| index.get_path(&bytes_path(&entry.path), 0).is_none())
+            .map(|entry| entry.path)
+            .collect();
+        for path in removed {
+            tree_index.remove_path(&bytes_path(&path))?;
+        }
+        for entry in index.iter().filter(|entry| matches(&entry.path)) {
+            tree_index.add(&entry)?;
+        }
+
+        Ok(tree_index.write_tree_to(&self.inner)?)
+    }
+
+    /// Runs a hook from `core.hooksPath` or `.git/hooks`, if it exists.
+    ///
+    /// Its output is captured, so it never mixes with ours.
+    ///
+    /// Returns whether the hook ran.
+    fn run_hook(&self, name: &str, args: &[&Path]) -> GitResult<bool> {
+        let hook = self.hooks_dir()?.join(name);
+        if !is_executable(&hook) {
+            return Ok(false);
+        }
+
+        let output = Command::new(&hook)
+            .args(args)
+            .current_dir(self.path())
+            .output()?;
+        let captured = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let captured = captured.trim();
+        debug!(hook = name, output = captured, "ran hook");
+        if !output.status.success() {
+            return Err(GitError::HookFailed {
+                hook: name.to_string(),
+                status: output.status,
+                output: captured.to_string(),
+            });
+        }
+        Ok(true)
+    }
+
+    /// Returns the directory of the hooks.
+    fn hooks_dir(&self) -> GitResult<PathBuf> {
+        match self.inner.config()?.get_path("core.hooksPath") {
+            Ok(dir) => Ok(self.path().join(dir)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                Ok(self.inner.commondir().join("hooks"))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Returns the files with uncommitted changes, including untracked files.
+    ///
+    /// Ignored files are skipped. An empty list means the working tree is clean.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the status cannot be read.
+    pub fn status(&self) -> GitResult<Vec<FileStatus>> {
+        let mut options = git2::StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+
+        let statuses = self.inner.statuses(Some(&mut options))?;
+
+        Ok(statuses
+            .iter()
+            .filter_map(|entry| {
+                let path = entry.path()?;
+                FileStatus::from_git2(path, entry.status())
+            })
+            .collect())
+    }
+
+    /// Returns the name of the current branch, or `None` on a detached HEAD.
+    ///
+    /// On an unborn branch, returns the branch the first commit will create.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if HEAD cannot be read.
+    pub fn current_branch(&self) -> GitResult<Option<String>> {
+        let head = match self.inner.head() {
+            Ok(head) => head,
+            // HEAD names the branch its first commit will create
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                let head = self.inner.find_reference("HEAD")?;
+                return Ok(head
+                    .symbolic_target()
+                    .and_then(|target| target.strip_prefix("refs/heads/"))
+                    .map(ToString::to_string));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if !head.is_branch() {
+            return Ok(None);
+        }
+        Ok(head.shorthand().map(ToString::to_string))
+    }
+
+    /// Pushes references to a remote.
+    ///
+    /// Credentials come from the SSH agent for SSH remotes and from the
+    /// configured git credential helpers for HTTPS remotes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remote does not exist, the push fails, or the
+    /// remote rejects a reference.
+    #[instrument(skip(self))]
+    pub fn push(&self, remote: &str, refspecs: &[String]) -> GitResult<()> {
+        let mut git_remote = match self.inner.find_remote(remote) {
+            Ok(git_remote) => git_remote,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                return Err(GitError::RemoteNotFound(remote.to_string()));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let config = self.inner.config()?;
+        let mut credentials = Credentials::default();
+        let mut rejected = Vec::new();
+
+        {
+            let mut callbacks = git2::RemoteCallbacks::new();
+            callbacks.credentials(|url, username, allowed| {
+                credentials.next(&config, url, username, allowed)
+            });
+            callbacks.push_update_reference(|reference, status| {
+                if let Some(reason) = status {
+                    rejected.push((reference.to_string(), reason.to_string()));
+                }
+                Ok(())
+            });
+
+            let mut options = git2::PushOptions::new();
+            options.remote_callbacks(callbacks);
+
+            git_remote
+                .push(refspecs, Some(&mut options))
+                .map_err(|e| GitError::PushFailed {
+                    remote: remote.to_string(),
+                    reason: e.message().to_string(),
+                })?;
+        }
+
+        if let Some((reference, reason)) = rejected.into_iter().next() {
+            return Err(GitError::PushRejected { reference, reason });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the web URL of a remote (e.g. `https://github.com/owner/repo`).
+    ///
+    /// Returns `None` if the remote does not exist or its URL cannot be
+    /// mapped to a web URL, such as local paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remote cannot be read.
+    pub fn remote_web_url(&self, name: &str) -> GitResult<Option<String>> {
+        let remote = match self.inner.find_remote(name) {
+            Ok(remote) => remote,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(remote.url().and_then(web_url))
+    }
+
     /// Returns the latest commit.
     ///
     /// # Errors
     ///
     /// Returns an error if no commits exist.
     pub fn latest_commit(&self) -> GitResult<RawCommit> {
-        let head = self.inner.head()?;
-        let commit = head.peel_to_commit()?;
-
-        let author = commit.author();
-        let time = commit.time();
-
-        Ok(RawCommit::new(
-            commit.id().to_string(),
-            commit.message().unwrap_or("").to_string(),
-            author.name().unwrap_or("Unknown"),
-            author.email().unwrap_or(""),
-            Utc.timestamp_opt(time.seconds(), 0)
-                .single()
-                .unwrap_or_else(Utc::now),
-        ))
+        let commit = self.head_commit()?.ok_or(GitError::NoCommits)?;
+        Ok(raw_commit(&commit))
+    }
+
+    /// Returns the commit with a hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hash is invalid or the commit does not exist.
+    pub fn find_commit(&self, hash: &str) -> GitResult<RawCommit> {
+        let oid = git2::Oid::from_str(hash)?;
+        Ok(raw_commit(&self.inner.find_commit(oid)?))
+    }
+}
+
+/// Converts a git2 commit.
+fn raw_commit(commit: &git2::Commit<'_>) -> RawCommit {
+    let author = commit.author();
+    let time = commit.time();
+
+    RawCommit::new(
+        commit.id().to_string(),
+        commit.message().unwrap_or("").to_string(),
+        author.name().unwrap_or("Unknown"),
+        author.email().unwrap_or(""),
+        Utc.timestamp_opt(time.seconds(), 0)
+            .single()
+            .unwrap_or_else(Utc::now),
+    )
+}
+
+/// Credential lookup for remote operations, trying each method once.
+///
+/// libgit2 calls the credentials callback again after a failed attempt, so
+/// each method is only offered once to avoid looping forever.
+#[derive(Debug, Default)]
+struct Credentials {
+    tried_ssh_agent: bool,
+    tried_helper: bool,
+    tried_default: bool,
+}
+
+impl Credentials {
+    /// Returns the next credentials to try.
+    fn next(
+        &mut self,
+        config: &git2::Config,
+        url: &str,
+        username: Option<&str>,
+        allowed: git2::CredentialType,
+    ) -> Result<git2::Cred, git2::Error> {
+        if allowed.contains(git2::CredentialType::USERNAME) {
+            return git2::Cred::username(username.unwrap_or("git"));
+        }
+
+        if allowed.contains(git2::CredentialType::SSH_KEY) && !self.tried_ssh_agent {
+            self.tried_ssh_agent = true;
+            return git2::Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+
+        if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) && !self.tried_helper {
+            self.tried_helper = true;
+            return git2::Cred::credential_helper(config, url, username);
+        }
+
+        if allowed.contains(git2::CredentialType::DEFAULT) && !self.tried_default {
+            self.tried_default = true;
+            return git2::Cred::default();
+        }
+
+        Err(git2::Error::from_str(&format!(
+            "no valid credentials for {url}"
+        )))
+    }
+}
+
+/// Converts a remote URL into a web URL.
+///
+/// Supports SCP-like (`git@host:owner/repo.git`), `ssh://` and `http(s)://`
+/// remotes.
+fn web_url(remote: &str) -> Option<String> {
+    let (host, path) = if let Some(rest) = remote
+        .strip_prefix("https://")
+        .or_else(|| remote.strip_prefix("http://"))
+        .or_else(|| remote.strip_prefix("ssh://"))
+        .or_else(|| remote.strip_prefix("git://"))
+    {
+        let (authority, path) = rest.split_once('/')?;
+        // Drop credentials and port
+        let host = authority.rsplit('@').next()?;
+        let host = host.split(':').next()?;
+        (host, path)
+    } else if !remote.contains("://") {
+        let (authority, path) = remote.split_once(':')?;
+        let host = authority.rsplit('@').next()?;
+        (host, path)
+    } else {
+        return None;
+    };
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+
+    if host.is_empty() || path.is_empty() || host.contains('/') {
+        return None;
+    }
+
+    Some(format!("https://{host}/{path}"))
+}
+
+/// Returns true if `text` matches a glob pattern with `*` and `?` wildcards.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text it was matched against
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                // Let the last `*` match one more character
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Converts a path of an index entry.
+fn bytes_path(path: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(path).into_owned())
+}
+
+/// Returns whether a hook file can be run, like git skipping hooks that are
+/// not executable.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path)
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
     }
 }
 
@@ -204,7 +1092,7 @@ mod tests {
         config.set_str("user.name", "Test User").unwrap();
         config.set_str("user.email", "test@example.com").unwrap();
 
-        let repo = Repository { inner: git2_repo };
+        let repo = Repository::from_inner(git2_repo);
         (temp_dir, repo)
     }
 
@@ -219,92 +1107,389 @@ mod tests {
         let parent = repo.inner.head().ok().and_then(|h| h.peel_to_commit().ok());
         let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
 
-        repo.inner
-            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
-            .unwrap()
+        repo.inner
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_open_valid_repo() {
+        let (temp_dir, _repo) = create_test_repo();
+        let result = Repository::open(temp_dir.path());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_open_invalid_path() {
+        let result = Repository::open("/nonexistent/path/to/repo");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_open_not_a_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = Repository::open(temp_dir.path());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_discover_from_unduler_repo() {
+        // This test runs from within the unduler repo
+        let result = Repository::discover();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_path() {
+        let (temp_dir, repo) = create_test_repo();
+        let path = repo.path();
+        // Use canonicalize to resolve symlinks (macOS /var -> /private/var)
+        let expected = temp_dir.path().canonicalize().unwrap();
+        let actual = path.canonicalize().unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_tags_empty() {
+        let (_temp_dir, repo) = create_test_repo();
+        let tags = repo.tags().unwrap();
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn test_tags_with_tags() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+
+        repo.create_tag("v1.0.0", "First release").unwrap();
+        repo.create_tag("v1.1.0", "Second release").unwrap();
+
+        let tags = repo.tags().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&"v1.0.0".to_string()));
+        assert!(tags.contains(&"v1.1.0".to_string()));
+    }
+
+    #[test]
+    fn test_find_commit() {
+        let (_temp_dir, repo) = create_test_repo();
+        let oid = create_commit(&repo, "feat: first");
+
+        let commit = repo.find_commit(&oid.to_string()).unwrap();
+        assert_eq!(commit.message, "feat: first");
+        assert_eq!(commit.email, "test@example.com");
+
+        assert!(repo.find_commit("not-a-hash").is_err());
+    }
+
+    #[test]
+    fn test_commits_since_none() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "First commit");
+        create_commit(&repo, "Second commit");
+        create_commit(&repo, "Third commit");
+
+        let commits = repo.commits_since(None).unwrap();
+        assert_eq!(commits.len(), 3);
+    }
+
+    #[test]
+    fn test_commits_since_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "First commit");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        create_commit(&repo, "Second commit");
+        create_commit(&repo, "Third commit");
+
+        let commits = repo.commits_since(Some("v1.0.0")).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject(), "Third commit");
+        assert_eq!(commits[1].subject(), "Second commit");
+    }
+
+    /// Creates a commit writing the given file.
+    fn commit_file(repo: &Repository, path: &str, message: &str) -> git2::Oid {
+        let full_path = repo.path().join(path);
+        std::fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        std::fs::write(&full_path, message).unwrap();
+
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = {
+            let mut index = repo.inner.index().unwrap();
+            index.add_path(Path::new(path)).unwrap();
+            index.write().unwrap();
+            index.write_tree().unwrap()
+        };
+        let tree = repo.inner.find_tree(tree_id).unwrap();
+
+        let parent = repo.inner.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+
+        repo.inner
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_commits_since_with_paths() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "crates/foo/src/lib.rs", "feat(foo): add foo");
+        commit_file(&repo, "crates/bar/src/lib.rs", "feat(bar): add bar");
+        commit_file(&repo, "crates/foo/README.md", "docs(foo): document foo");
+        commit_file(&repo, "README.md", "docs: update readme");
+
+        let options = CommitOptions::new().with_paths(["crates/foo/**"]);
+        let commits = repo.commits_since_with(None, &options).unwrap();
+
+        let subjects: Vec<_> = commits.iter().map(RawCommit::subject).collect();
+        assert_eq!(subjects, ["docs(foo): document foo", "feat(foo): add foo"]);
+    }
+
+    #[test]
+    fn test_commits_since_with_multiple_paths() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "crates/foo/src/lib.rs", "feat(foo): add foo");
+        commit_file(&repo, "crates/bar/src/lib.rs", "feat(bar): add bar");
+        commit_file(&repo, "README.md", "docs: update readme");
+
+        let options = CommitOptions::new().with_paths(["crates/bar/**", "README.md"]);
+        let commits = repo.commits_since_with(None, &options).unwrap();
+        assert_eq!(commits.len(), 2);
+    }
+
+    #[test]
+    fn test_commits_since_with_paths_and_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "crates/foo/src/lib.rs", "feat(foo): add foo");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        commit_file(&repo, "crates/bar/src/lib.rs", "feat(bar): add bar");
+        commit_file(&repo, "crates/foo/src/main.rs", "fix(foo): fix foo");
+
+        let options = CommitOptions::new().with_paths(["crates/foo/**"]);
+        let commits = repo.commits_since_with(Some("v1.0.0"), &options).unwrap();
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject(), "fix(foo): fix foo");
+    }
+
+    /// Creates a history with a merged feature branch:
+    ///
+    /// ```text
+    /// initial -- main fix -- merge
+    ///        \             /
+    ///         part 1 -- part 2
+    /// ```
+    fn create_merge_history(repo: &Repository) {
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let base = create_commit(repo, "chore: initial commit");
+        let base = repo.inner.find_commit(base).unwrap();
+        let tree = base.tree().unwrap();
+
+        let part1 = repo
+            .inner
+            .commit(None, &sig, &sig, "feat: feature part 1", &tree, &[&base])
+            .unwrap();
+        let part1 = repo.inner.find_commit(part1).unwrap();
+        let part2 = repo
+            .inner
+            .commit(None, &sig, &sig, "feat: feature part 2", &tree, &[&part1])
+            .unwrap();
+        let part2 = repo.inner.find_commit(part2).unwrap();
+
+        let main = create_commit(repo, "fix: main fix");
+        let main = repo.inner.find_commit(main).unwrap();
+        repo.inner
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge branch 'feature'",
+                &tree,
+                &[&main, &part2],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_commits_since_all_parents() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_merge_history(&repo);
+
+        let commits = repo.commits_since(None).unwrap();
+        assert_eq!(commits.len(), 5);
+    }
+
+    #[test]
+    fn test_commits_since_first_parent() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_merge_history(&repo);
+
+        let options = CommitOptions::new().with_first_parent(true);
+        let commits = repo.commits_since_with(None, &options).unwrap();
+
+        let subjects: Vec<_> = commits.iter().map(RawCommit::subject).collect();
+        assert_eq!(
+            subjects,
+            [
+                "Merge branch 'feature'",
+                "fix: main fix",
+                "chore: initial commit"
+            ]
+        );
     }
 
     #[test]
-    fn test_open_valid_repo() {
-        let (temp_dir, _repo) = create_test_repo();
-        let result = Repository::open(temp_dir.path());
-        assert!(result.is_ok());
+    fn test_commits_since_skip_merges() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_merge_history(&repo);
+
+        let options = CommitOptions::new().with_skip_merges(true);
+        let commits = repo.commits_since_with(None, &options).unwrap();
+
+        assert_eq!(commits.len(), 4);
+        assert!(commits.iter().all(|c| !c.subject().starts_with("Merge")));
     }
 
     #[test]
-    fn test_open_invalid_path() {
-        let result = Repository::open("/nonexistent/path/to/repo");
-        assert!(result.is_err());
+    fn test_commits_since_first_parent_skip_merges() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_merge_history(&repo);
+
+        let options = CommitOptions::new()
+            .with_first_parent(true)
+            .with_skip_merges(true);
+        let commits = repo.commits_since_with(None, &options).unwrap();
+
+        let subjects: Vec<_> = commits.iter().map(RawCommit::subject).collect();
+        assert_eq!(subjects, ["fix: main fix", "chore: initial commit"]);
     }
 
     #[test]
-    fn test_open_not_a_repo() {
-        let temp_dir = TempDir::new().unwrap();
-        let result = Repository::open(temp_dir.path());
-        assert!(result.is_err());
+    fn test_commits_since_max_count() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: first");
+        create_commit(&repo, "feat: second");
+        create_commit(&repo, "feat: third");
+
+        let options = CommitOptions::new().with_max_count(Some(2));
+        let commits = repo.commits_since_with(None, &options).unwrap();
+
+        let subjects: Vec<_> = commits.iter().map(RawCommit::subject).collect();
+        assert_eq!(subjects, ["feat: third", "feat: second"]);
     }
 
     #[test]
-    fn test_discover_from_unduler_repo() {
-        // This test runs from within the unduler repo
-        let result = Repository::discover();
-        assert!(result.is_ok());
+    fn test_head_and_commit_ids() {
+        let (_temp_dir, repo) = create_test_repo();
+        assert!(repo.head_id().unwrap().is_none());
+
+        let oid = create_commit(&repo, "feat: first");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        assert_eq!(repo.head_id().unwrap(), Some(oid.to_string()));
+        assert_eq!(repo.commit_id("v1.0.0").unwrap(), oid.to_string());
+        assert!(matches!(
+            repo.commit_id("v2.0.0"),
+            Err(GitError::RevisionNotFound(_))
+        ));
     }
 
     #[test]
-    fn test_path() {
-        let (temp_dir, repo) = create_test_repo();
-        let path = repo.path();
-        // Use canonicalize to resolve symlinks (macOS /var -> /private/var)
-        let expected = temp_dir.path().canonicalize().unwrap();
-        let actual = path.canonicalize().unwrap();
-        assert_eq!(actual, expected);
+    fn test_for_each_commit_since() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: first");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        create_commit(&repo, "feat: second");
+        create_commit(&repo, "fix: third");
+
+        let mut subjects = Vec::new();
+        repo.for_each_commit_since(Some("v1.0.0"), &CommitOptions::default(), |commit| {
+            subjects.push(commit.subject().to_string());
+        })
+        .unwrap();
+        assert_eq!(subjects, ["fix: third", "feat: second"]);
+
+        let mut count = 0;
+        repo.for_each_commit_between(None, "v1.0.0", &CommitOptions::default(), |_| {
+            count += 1;
+        })
+        .unwrap();
+        assert_eq!(count, 1);
     }
 
     #[test]
-    fn test_tags_empty() {
+    fn test_for_each_commit_since_to() {
         let (_temp_dir, repo) = create_test_repo();
-        let tags = repo.tags().unwrap();
-        assert!(tags.is_empty());
+        create_commit(&repo, "feat: first");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        let second = create_commit(&repo, "feat: second");
+        create_commit(&repo, "fix: third");
+
+        let mut subjects = Vec::new();
+        repo.for_each_commit_since_to(
+            Some("v1.0.0"),
+            &second.to_string(),
+            &CommitOptions::default(),
+            |commit| subjects.push(commit.subject().to_string()),
+        )
+        .unwrap();
+        assert_eq!(subjects, ["feat: second"]);
     }
 
     #[test]
-    fn test_tags_with_tags() {
+    fn test_commits_between_tags() {
         let (_temp_dir, repo) = create_test_repo();
-        create_commit(&repo, "Initial commit");
+        create_commit(&repo, "feat: initial");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        create_commit(&repo, "feat: second");
+        create_commit(&repo, "fix: third");
+        repo.create_tag("v1.1.0", "Release 1.1.0").unwrap();
+        create_commit(&repo, "feat: fourth");
 
-        repo.create_tag("v1.0.0", "First release").unwrap();
-        repo.create_tag("v1.1.0", "Second release").unwrap();
+        let commits = repo
+            .commits_between(Some("v1.0.0"), "v1.1.0", &CommitOptions::default())
+            .unwrap();
 
-        let tags = repo.tags().unwrap();
-        assert_eq!(tags.len(), 2);
-        assert!(tags.contains(&"v1.0.0".to_string()));
-        assert!(tags.contains(&"v1.1.0".to_string()));
+        let subjects: Vec<_> = commits.iter().map(RawCommit::subject).collect();
+        assert_eq!(subjects, ["fix: third", "feat: second"]);
     }
 
     #[test]
-    fn test_commits_since_none() {
+    fn test_commits_between_hash_and_head() {
         let (_temp_dir, repo) = create_test_repo();
-        create_commit(&repo, "First commit");
-        create_commit(&repo, "Second commit");
-        create_commit(&repo, "Third commit");
+        create_commit(&repo, "feat: initial");
+        let second = create_commit(&repo, "feat: second");
+        create_commit(&repo, "fix: third");
 
-        let commits = repo.commits_since(None).unwrap();
-        assert_eq!(commits.len(), 3);
+        let commits = repo
+            .commits_between(Some(&second.to_string()), "HEAD", &CommitOptions::default())
+            .unwrap();
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject(), "fix: third");
     }
 
     #[test]
-    fn test_commits_since_tag() {
+    fn test_commits_between_without_from() {
         let (_temp_dir, repo) = create_test_repo();
-        create_commit(&repo, "First commit");
+        create_commit(&repo, "feat: initial");
         repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
-        create_commit(&repo, "Second commit");
-        create_commit(&repo, "Third commit");
+        create_commit(&repo, "feat: second");
 
-        let commits = repo.commits_since(Some("v1.0.0")).unwrap();
-        assert_eq!(commits.len(), 2);
-        assert_eq!(commits[0].subject(), "Third commit");
-        assert_eq!(commits[1].subject(), "Second commit");
+        let commits = repo
+            .commits_between(None, "v1.0.0", &CommitOptions::default())
+            .unwrap();
+
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].subject(), "feat: initial");
+    }
+
+    #[test]
+    fn test_commits_between_invalid_revision() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: initial");
+
+        let result = repo.commits_between(Some("v9.9.9"), "HEAD", &CommitOptions::default());
+        assert!(matches!(result, Err(GitError::RevisionNotFound(_))));
     }
 
     #[test]
@@ -402,6 +1587,195 @@ mod tests {
         assert_eq!(result, Some("v1.0.0".to_string()));
     }
 
+    #[test]
+    fn test_latest_tag_matching_template() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        repo.create_tag("app@1.0.0", "Release").unwrap();
+        repo.create_tag("lib@3.0.0", "Release").unwrap();
+        create_commit(&repo, "Another commit");
+        repo.create_tag("app@1.2.0", "Release").unwrap();
+        repo.create_tag("release/2.0.0", "Release").unwrap();
+
+        let format = TagFormat::new("{package}@{version}").with_package("app");
+        let result = repo.latest_tag_matching(&format).unwrap();
+        assert_eq!(
+            result,
+            Some(("app@1.2.0".to_string(), semver::Version::new(1, 2, 0)))
+        );
+
+        let format = TagFormat::new("release/{version}");
+        let result = repo.latest_tag_matching(&format).unwrap();
+        assert_eq!(
+            result.map(|(tag, _)| tag),
+            Some("release/2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unborn_branch() {
+        let (_temp_dir, repo) = create_test_repo();
+
+        assert!(repo.commits_since(None).unwrap().is_empty());
+        assert!(repo.current_branch().unwrap().is_some());
+        assert!(matches!(repo.latest_commit(), Err(GitError::NoCommits)));
+        assert!(matches!(
+            repo.create_tag("v1.0.0", "Release"),
+            Err(GitError::NoCommits)
+        ));
+    }
+
+    #[test]
+    fn test_detached_head() {
+        let (_temp_dir, repo) = create_test_repo();
+        let first = create_commit(&repo, "feat: first");
+        create_commit(&repo, "feat: second");
+        repo.inner.set_head_detached(first).unwrap();
+
+        assert!(repo.current_branch().unwrap().is_none());
+        let commits = repo.commits_since(None).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "feat: first");
+    }
+
+    #[test]
+    fn test_shallow_clone() {
+        let (temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: first");
+        let boundary = create_commit(&repo, "feat: second");
+        create_commit(&repo, "feat: third");
+
+        // Simulate a clone with `--depth 2`
+        fs::write(
+            temp_dir.path().join(".git/shallow"),
+            format!("{boundary}\n"),
+        )
+        .unwrap();
+        assert!(repo.is_shallow());
+
+        assert!(matches!(
+            repo.commits_since(None),
+            Err(GitError::ShallowClone)
+        ));
+
+        // Commits within the fetched history can still be read
+        repo.inner
+            .tag_lightweight(
+                "v1.0.0",
+                &repo.inner.find_object(boundary, None).unwrap(),
+                false,
+            )
+            .unwrap();
+        let commits = repo.commits_since(Some("v1.0.0")).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "feat: third");
+    }
+
+    #[test]
+    fn test_unshallow_unknown_remote() {
+        let (_temp_dir, repo) = create_test_repo();
+        assert!(matches!(
+            repo.unshallow("origin"),
+            Err(GitError::RemoteNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_tags_matching() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        repo.create_tag("pkg-a-v1.0.0", "Release").unwrap();
+        repo.create_tag("pkg-a-v1.1.0", "Release").unwrap();
+        repo.create_tag("pkg-b-v2.0.0", "Release").unwrap();
+
+        let mut tags = repo.tags_matching("pkg-a-v*").unwrap();
+        tags.sort();
+        assert_eq!(tags, ["pkg-a-v1.0.0", "pkg-a-v1.1.0"]);
+        assert_eq!(repo.tags_matching("pkg-?-v2.*").unwrap(), ["pkg-b-v2.0.0"]);
+        assert!(repo.tags_matching("pkg-c-*").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_latest_tag_for() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        repo.create_tag("pkg-a-v1.2.3", "Release").unwrap();
+        repo.create_tag("pkg-a-b-v9.0.0", "Release").unwrap();
+        repo.create_tag("pkg-b-v2.0.0", "Release").unwrap();
+
+        let format = TagFormat::new("{package}-v{version}");
+        let latest = repo.latest_tag_for(&format, "pkg-a").unwrap();
+        assert_eq!(
+            latest,
+            Some(("pkg-a-v1.2.3".to_string(), semver::Version::new(1, 2, 3)))
+        );
+        assert!(repo.latest_tag_for(&format, "pkg-c").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tags_cache_sees_created_tags() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        assert!(repo.tags().unwrap().is_empty());
+
+        repo.create_tag("v1.0.0", "Release").unwrap();
+        assert_eq!(repo.tags().unwrap(), ["v1.0.0"]);
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("v*", "v1.0.0"));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("a*b*c", "axxbyyc"));
+        assert!(glob_matches("v?.0", "v1.0"));
+        assert!(!glob_matches("v?.0", "v10.0"));
+        assert!(!glob_matches("pkg-a-*", "pkg-b-v1"));
+    }
+
+    #[test]
+    fn test_latest_tag_before() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        repo.create_tag("v1.0.0", "Release").unwrap();
+        create_commit(&repo, "Second commit");
+        repo.create_tag("v1.1.0", "Release").unwrap();
+        create_commit(&repo, "Third commit");
+        repo.create_tag("v2.0.0", "Release").unwrap();
+
+        let format = TagFormat::from_prefix("v");
+        let before = |revision| {
+            repo.latest_tag_before(&format, revision)
+                .unwrap()
+                .map(|(tag, _)| tag)
+        };
+        assert_eq!(before("v2.0.0"), Some("v1.1.0".to_string()));
+        assert_eq!(before("HEAD~1"), Some("v1.0.0".to_string()));
+        assert_eq!(before("v1.0.0"), None);
+        assert!(repo.latest_tag_before(&format, "missing").is_err());
+    }
+
+    #[test]
+    fn test_latest_tag_in_channel() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        repo.create_tag("v1.4.0", "Release").unwrap();
+        create_commit(&repo, "Another commit");
+        repo.create_tag("v1.5.0-rc.1", "Release").unwrap();
+        repo.create_tag("v1.5.0-rc.2", "Release").unwrap();
+        repo.create_tag("v1.5.0-beta.3", "Release").unwrap();
+
+        let format = TagFormat::from_prefix("v");
+
+        let stable = repo.latest_tag_in_channel(&format, None).unwrap();
+        assert_eq!(stable.map(|(tag, _)| tag), Some("v1.4.0".to_string()));
+
+        let rc = repo.latest_tag_in_channel(&format, Some("rc")).unwrap();
+        assert_eq!(rc.map(|(tag, _)| tag), Some("v1.5.0-rc.2".to_string()));
+
+        let alpha = repo.latest_tag_in_channel(&format, Some("alpha")).unwrap();
+        assert!(alpha.is_none());
+    }
+
     #[test]
     fn test_create_tag() {
         let (_temp_dir, repo) = create_test_repo();
@@ -414,6 +1788,23 @@ mod tests {
         assert!(tags.contains(&"v1.0.0".to_string()));
     }
 
+    #[test]
+    fn test_create_lightweight_tag() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+        assert!(repo.tags().unwrap().is_empty());
+
+        repo.create_lightweight_tag("v1.0.0").unwrap();
+        assert_eq!(repo.tags().unwrap(), ["v1.0.0"]);
+
+        let reference = repo.inner.find_reference("refs/tags/v1.0.0").unwrap();
+        assert!(reference.peel_to_tag().is_err());
+        assert!(matches!(
+            repo.create_lightweight_tag("v1.0.0"),
+            Err(GitError::TagExists(_))
+        ));
+    }
+
     #[test]
     fn test_create_tag_duplicate() {
         let (_temp_dir, repo) = create_test_repo();
@@ -421,6 +1812,280 @@ mod tests {
 
         repo.create_tag("v1.0.0", "First release").unwrap();
         let result = repo.create_tag("v1.0.0", "Duplicate");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(GitError::TagExists(tag)) if tag == "v1.0.0"));
+    }
+
+    #[test]
+    fn test_tag_exists() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "Initial commit");
+
+        assert!(!repo.tag_exists("v1.0.0").unwrap());
+        repo.create_tag("v1.0.0", "First release").unwrap();
+        assert!(repo.tag_exists("v1.0.0").unwrap());
+        assert!(!repo.tag_exists("v1.0").unwrap());
+    }
+
+    #[test]
+    fn test_status_clean() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "src/lib.rs", "feat: add lib");
+
+        assert!(repo.status().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_status_dirty() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "src/lib.rs", "feat: add lib");
+
+        std::fs::write(repo.path().join("src/lib.rs"), "changed").unwrap();
+        std::fs::write(repo.path().join("notes.txt"), "notes").unwrap();
+
+        let status = repo.status().unwrap();
+        assert_eq!(status.len(), 2);
+
+        let lib = status.iter().find(|s| s.path() == "src/lib.rs").unwrap();
+        assert_eq!(lib.change(), crate::FileChange::Modified);
+        assert!(!lib.is_staged());
+
+        let notes = status.iter().find(|s| s.path() == "notes.txt").unwrap();
+        assert_eq!(notes.change(), crate::FileChange::New);
+    }
+
+    #[test]
+    fn test_status_staged() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "src/lib.rs", "feat: add lib");
+
+        std::fs::write(repo.path().join("src/lib.rs"), "changed").unwrap();
+        let mut index = repo.inner.index().unwrap();
+        index.add_path(Path::new("src/lib.rs")).unwrap();
+        index.write().unwrap();
+
+        let status = repo.status().unwrap();
+        assert_eq!(status.len(), 1);
+        assert!(status[0].is_staged());
+    }
+
+    #[test]
+    fn test_commit_stages_removed_files() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "changelog.d/12.feat.md", "feat: add fragment");
+
+        std::fs::remove_file(repo.path().join("changelog.d/12.feat.md")).unwrap();
+        repo.commit("chore: release").unwrap();
+
+        assert!(repo.status().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_commit_with_paths() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "Cargo.toml", "feat: initial");
+        std::fs::write(repo.path().join("Cargo.toml"), "version = \"1.1.0\"").unwrap();
+        std::fs::write(repo.path().join("notes.txt"), "draft").unwrap();
+
+        let options = NewCommitOptions::new().with_paths(["Cargo.toml"]);
+        repo.commit_with("chore: release", &options).unwrap();
+
+        let status = repo.status().unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].path(), "notes.txt");
+    }
+
+    #[test]
+    fn test_commit_with_paths_keeps_staged_changes_out() {
+        let (_temp_dir, repo) = create_test_repo();
+        commit_file(&repo, "Cargo.toml", "feat: initial");
+        std::fs::write(repo.path().join("Cargo.toml"), "version = \"1.1.0\"").unwrap();
+        std::fs::write(repo.path().join("notes.txt"), "draft").unwrap();
+        let mut index = repo.inner.index().unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+
+        let options = NewCommitOptions::new().with_paths(["Cargo.toml"]);
+        let oid = repo.commit_with("chore: release", &options).unwrap();
+
+        let tree = repo.inner.find_commit(oid).unwrap().tree().unwrap();
+        assert!(tree.get_name("Cargo.toml").is_some());
+        assert!(tree.get_name("notes.txt").is_none());
+        let status = repo.status().unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].path(), "notes.txt");
+    }
+
+    #[test]
+    fn test_commit_with_identity() {
+        let (_temp_dir, repo) = create_test_repo();
+        let options =
+            NewCommitOptions::new().with_identity(Some(Identity::new("release-bot", "bot@ci")));
+        let oid = repo.commit_with("chore: release", &options).unwrap();
+
+        let commit = repo.inner.find_commit(oid).unwrap();
+        assert_eq!(commit.author().name(), Some("release-bot"));
+        assert_eq!(commit.committer().email(), Some("bot@ci"));
+    }
+
+    #[test]
+    fn test_identity() {
+        let (_temp_dir, repo) = create_test_repo();
+        let identity = repo.identity().unwrap();
+        assert_eq!(identity.to_string(), "Test User <test@example.com>");
+
+        let repo = repo.with_identity(Identity::new("release-bot", "bot@ci"));
+        assert_eq!(repo.identity().unwrap().to_string(), "release-bot <bot@ci>");
+
+        let oid = repo.commit("chore: release").unwrap();
+        let commit = repo.inner.find_commit(oid).unwrap();
+        assert_eq!(commit.author().name(), Some("release-bot"));
+        assert_eq!(commit.author().email(), Some("bot@ci"));
+    }
+
+    #[test]
+    fn test_missing_identity() {
+        let (_temp_dir, repo) = create_test_repo();
+        let mut config = repo
+            .inner
+            .config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap();
+        config.set_str("user.email", "").unwrap();
+
+        assert!(matches!(repo.identity(), Err(GitError::MissingIdentity)));
+        assert!(matches!(
+            repo.commit("chore: release"),
+            Err(GitError::MissingIdentity)
+        ));
+
+        let repo = repo.with_identity(Identity::new("release-bot", "bot@ci"));
+        assert!(repo.identity().is_ok());
+    }
+
+    #[cfg(unix)]
+    fn write_hook(repo: &Repository, name: &str, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = repo.inner.path().join("hooks").join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_commit_runs_hooks() {
+        let (_temp_dir, repo) = create_test_repo();
+        write_hook(&repo, "pre-commit", "echo 'lint failed' >&2\nexit 1");
+
+        let options = NewCommitOptions::new().with_verify(true);
+        let result = repo.commit_with("chore: release", &options);
+        assert!(matches!(
+            result,
+            Err(GitError::HookFailed { hook, output, .. })
+                if hook == "pre-commit" && output == "lint failed"
+        ));
+
+        assert!(repo.commit("chore: release").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_commit_msg_hook_rewrites_message() {
+        let (_temp_dir, repo) = create_test_repo();
+        write_hook(&repo, "commit-msg", "echo 'Signed-off-by: CI' >> \"$1\"");
+
+        let options = NewCommitOptions::new().with_verify(true);
+        let oid = repo.commit_with("chore: release\n", &options).unwrap();
+        let commit = repo.inner.find_commit(oid).unwrap();
+        assert_eq!(
+            commit.message(),
+            Some("chore: release\nSigned-off-by: CI\n")
+        );
+    }
+
+    #[test]
+    fn test_current_branch() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: initial");
+
+        let branch = repo.current_branch().unwrap().unwrap();
+        assert_eq!(
+            repo.inner.head().unwrap().name(),
+            Some(format!("refs/heads/{branch}").as_str())
+        );
+
+        let head = repo.inner.head().unwrap().target().unwrap();
+        repo.inner.set_head_detached(head).unwrap();
+        assert!(repo.current_branch().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_push_to_local_remote() {
+        let (_temp_dir, repo) = create_test_repo();
+        let remote_dir = TempDir::new().unwrap();
+        let remote = Git2Repository::init_bare(remote_dir.path()).unwrap();
+        repo.inner
+            .remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+
+        create_commit(&repo, "feat: initial");
+        repo.create_tag("v1.0.0", "Release 1.0.0").unwrap();
+        let branch = repo.current_branch().unwrap().unwrap();
+
+        repo.push(
+            "origin",
+            &[
+                format!("refs/heads/{branch}:refs/heads/{branch}"),
+                "refs/tags/v1.0.0:refs/tags/v1.0.0".to_string(),
+            ],
+        )
+        .unwrap();
+
+        assert!(
+            remote
+                .find_reference(&format!("refs/heads/{branch}"))
+                .is_ok()
+        );
+        assert!(remote.find_reference("refs/tags/v1.0.0").is_ok());
+    }
+
+    #[test]
+    fn test_push_unknown_remote() {
+        let (_temp_dir, repo) = create_test_repo();
+        create_commit(&repo, "feat: initial");
+
+        let result = repo.push("upstream", &["refs/heads/main".to_string()]);
+        assert!(matches!(result, Err(GitError::RemoteNotFound(name)) if name == "upstream"));
+    }
+
+    #[test]
+    fn test_web_url() {
+        let expected = Some("https://github.com/owner/repo".to_string());
+        assert_eq!(web_url("git@github.com:owner/repo.git"), expected);
+        assert_eq!(web_url("https://github.com/owner/repo.git"), expected);
+        assert_eq!(web_url("https://token@github.com/owner/repo"), expected);
+        assert_eq!(web_url("ssh://git@github.com:22/owner/repo.git"), expected);
+        assert_eq!(
+            web_url("git@gitlab.com:group/sub/repo.git"),
+            Some("https://gitlab.com/group/sub/repo".to_string())
+        );
+        assert_eq!(web_url("/srv/git/repo.git"), None);
+        assert_eq!(web_url("file:///srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_remote_web_url() {
+        let (_temp_dir, repo) = create_test_repo();
+        assert_eq!(repo.remote_web_url("origin").unwrap(), None);
+
+        repo.inner
+            .remote("origin", "git@github.com:owner/repo.git")
+            .unwrap();
+        assert_eq!(
+            repo.remote_web_url("origin").unwrap(),
+            Some("https://github.com/owner/repo".to_string())
+        );
     }
 }
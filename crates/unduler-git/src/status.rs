@@ -0,0 +1,145 @@
+//! Working tree status.
+
+use std::fmt;
+
+/// Kind of change of a file in the working tree or index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    /// Untracked or newly added file.
+    New,
+    /// Modified file.
+    Modified,
+    /// Deleted file.
+    Deleted,
+    /// Renamed file.
+    Renamed,
+    /// File type changed (e.g. file to symlink).
+    TypeChange,
+    /// File with merge conflicts.
+    Conflicted,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::New => write!(f, "new"),
+            Self::Modified => write!(f, "modified"),
+            Self::Deleted => write!(f, "deleted"),
+            Self::Renamed => write!(f, "renamed"),
+            Self::TypeChange => write!(f, "typechange"),
+            Self::Conflicted => write!(f, "conflicted"),
+        }
+    }
+}
+
+/// Status of a changed file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatus {
+    path: String,
+    change: FileChange,
+    staged: bool,
+}
+
+impl FileStatus {
+    /// Creates a new file status.
+    #[must_use]
+    pub fn new(path: impl Into<String>, change: FileChange, staged: bool) -> Self {
+        Self {
+            path: path.into(),
+            change,
+            staged,
+        }
+    }
+
+    /// Converts a git2 status entry, returning `None` for unchanged files.
+    pub(crate) fn from_git2(path: &str, status: git2::Status) -> Option<Self> {
+        let staged = status.intersects(
+            git2::Status::INDEX_NEW
+                | git2::Status::INDEX_MODIFIED
+                | git2::Status::INDEX_DELETED
+                | git2::Status::INDEX_RENAMED
+                | git2::Status::INDEX_TYPECHANGE,
+        );
+
+        let change = if status.is_conflicted() {
+            FileChange::Conflicted
+        } else if status.intersects(git2::Status::INDEX_NEW | git2::Status::WT_NEW) {
+            FileChange::New
+        } else if status.intersects(git2::Status::INDEX_DELETED | git2::Status::WT_DELETED) {
+            FileChange::Deleted
+        } else if status.intersects(git2::Status::INDEX_RENAMED | git2::Status::WT_RENAMED) {
+            FileChange::Renamed
+        } else if status.intersects(git2::Status::INDEX_TYPECHANGE | git2::Status::WT_TYPECHANGE) {
+            FileChange::TypeChange
+        } else if status.intersects(git2::Status::INDEX_MODIFIED | git2::Status::WT_MODIFIED) {
+            FileChange::Modified
+        } else {
+            return None;
+        };
+
+        Some(Self::new(path, change, staged))
+    }
+
+    /// Returns the file path, relative to the repository root.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the kind of change.
+    #[must_use]
+    pub fn change(&self) -> FileChange {
+        self.change
+    }
+
+    /// Returns true if the change is staged in the index.
+    #[must_use]
+    pub fn is_staged(&self) -> bool {
+        self.staged
+    }
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.staged {
+            write!(f, "{} ({}, staged)", self.path, self.change)
+        } else {
+            write!(f, "{} ({})", self.path, self.change)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_git2_untracked() {
+        let status = FileStatus::from_git2("new.txt", git2::Status::WT_NEW).unwrap();
+        assert_eq!(status.path(), "new.txt");
+        assert_eq!(status.change(), FileChange::New);
+        assert!(!status.is_staged());
+    }
+
+    #[test]
+    fn test_from_git2_staged() {
+        let status = FileStatus::from_git2("lib.rs", git2::Status::INDEX_MODIFIED).unwrap();
+        assert_eq!(status.change(), FileChange::Modified);
+        assert!(status.is_staged());
+    }
+
+    #[test]
+    fn test_from_git2_unchanged() {
+        assert!(FileStatus::from_git2("lib.rs", git2::Status::CURRENT).is_none());
+        assert!(FileStatus::from_git2("target", git2::Status::IGNORED).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let status = FileStatus::new("src/lib.rs", FileChange::Modified, true);
+        assert_eq!(status.to_string(), "src/lib.rs (modified, staged)");
+
+        let status = FileStatus::new("notes.txt", FileChange::New, false);
+        assert_eq!(status.to_string(), "notes.txt (new)");
+    }
+}
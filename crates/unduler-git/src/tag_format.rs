@@ -0,0 +1,231 @@
+//! Tag name templates.
+
+use std::fmt::Write;
+
+use regex::Regex;
+use semver::Version;
+
+/// Pattern matching a full semantic version.
+const VERSION_PATTERN: &str = r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?";
+
+/// A tag name template such as `v{version}` or `{package}@{version}`.
+///
+/// Supported placeholders:
+/// - `{version}`: the full version (e.g. `1.2.3-rc.1`)
+/// - `{major}`, `{minor}`, `{patch}`: individual version components
+/// - `{package}`: the package name
+#[derive(Debug, Clone)]
+pub struct TagFormat {
+    template: String,
+    package: Option<String>,
+    regex: Regex,
+}
+
+impl TagFormat {
+    /// Default tag template.
+    pub const DEFAULT: &'static str = "v{version}";
+
+    /// Creates a tag format from a template.
+    #[must_use]
+    pub fn new(template: impl Into<String>) -> Self {
+        let template = template.into();
+        let regex = build_regex(&template, None);
+        Self {
+            template,
+            package: None,
+            regex,
+        }
+    }
+
+    /// Creates a tag format for tags made of a prefix followed by the version.
+    #[must_use]
+    pub fn from_prefix(prefix: &str) -> Self {
+        Self::new(format!("{prefix}{{version}}"))
+    }
+
+    /// Sets the package name used for the `{package}` placeholder.
+    #[must_use]
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        let package = package.into();
+        self.regex = build_regex(&self.template, Some(&package));
+        self.package = Some(package);
+        self
+    }
+
+    /// Returns the template.
+    #[must_use]
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the package name.
+    #[must_use]
+    pub fn package(&self) -> Option<&str> {
+        self.package.as_deref()
+    }
+
+    /// Formats the tag name for a version.
+    #[must_use]
+    pub fn format(&self, version: &Version) -> String {
+        self.template
+            .replace("{version}", &version.to_string())
+            .replace("{major}", &version.major.to_string())
+            .replace("{minor}", &version.minor.to_string())
+            .replace("{patch}", &version.patch.to_string())
+            .replace("{package}", self.package.as_deref().unwrap_or_default())
+    }
+
+    /// Extracts the version from a tag name matching this format.
+    ///
+    /// Returns `None` if the tag does not match. Templates without
+    /// `{version}` default missing components to zero.
+    #[must_use]
+    pub fn parse(&self, tag: &str) -> Option<Version> {
+        let captures = self.regex.captures(tag)?;
+
+        if let Some(version) = captures.name("version") {
+            return Version::parse(version.as_str()).ok();
+        }
+
+        let component = |name: &str| -> Option<u64> {
+            captures
+                .name(name)
+                .map_or(Some(0), |m| m.as_str().parse().ok())
+        };
+
+        // A template needs at least the major component to identify versions
+        captures.name("major")?;
+        Some(Version::new(
+            component("major")?,
+            component("minor")?,
+            component("patch")?,
+        ))
+    }
+
+    /// Returns whether the tag name matches this format.
+    #[must_use]
+    pub fn matches(&self, tag: &str) -> bool {
+        self.parse(tag).is_some()
+    }
+}
+
+impl Default for TagFormat {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT)
+    }
+}
+
+/// Builds the regex matching tag names for a template.
+fn build_regex(template: &str, package: Option<&str>) -> Regex {
+    let mut pattern = String::from("^");
+    let mut seen = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+
+        let group = match name {
+            "version" => Some(VERSION_PATTERN.to_string()),
+            "major" | "minor" | "patch" => Some(r"\d+".to_string()),
+            "package" => Some(package.map_or_else(|| ".+?".to_string(), regex::escape)),
+            _ => None,
+        };
+
+        pattern.push_str(&regex::escape(&rest[..start]));
+        match group {
+            // Repeated placeholders only need to be captured once
+            Some(group) if seen.contains(&name) => {
+                let _ = write!(pattern, "(?:{group})");
+            }
+            Some(group) => {
+                let _ = write!(pattern, "(?P<{name}>{group})");
+                seen.push(name);
+            }
+            None => pattern.push_str(&regex::escape(&rest[start..=start + len])),
+        }
+
+        rest = &rest[start + len + 1..];
+    }
+
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+
+    Regex::new(&pattern).expect("escaped tag template is a valid regex")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_format() {
+        let format = TagFormat::default();
+        assert_eq!(format.format(&Version::new(1, 2, 3)), "v1.2.3");
+        assert_eq!(format.parse("v1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(format.parse("1.2.3"), None);
+    }
+
+    #[test]
+    fn test_from_prefix() {
+        let format = TagFormat::from_prefix("release-");
+        assert_eq!(format.template(), "release-{version}");
+        assert_eq!(format.parse("release-2.0.0"), Some(Version::new(2, 0, 0)));
+        assert_eq!(format.parse("v2.0.0"), None);
+    }
+
+    #[test]
+    fn test_package_format() {
+        let format = TagFormat::new("{package}@{version}").with_package("app");
+        assert_eq!(format.format(&Version::new(1, 2, 3)), "app@1.2.3");
+        assert_eq!(format.parse("app@1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(format.parse("lib@1.2.3"), None);
+    }
+
+    #[test]
+    fn test_package_format_without_package() {
+        let format = TagFormat::new("{package}@{version}");
+        assert_eq!(format.parse("lib@1.2.3"), Some(Version::new(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_path_like_format() {
+        let format = TagFormat::new("release/{version}");
+        assert_eq!(format.format(&Version::new(1, 2, 3)), "release/1.2.3");
+        assert_eq!(format.parse("release/1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(format.parse("release/x"), None);
+    }
+
+    #[test]
+    fn test_prerelease_version() {
+        let format = TagFormat::default();
+        assert_eq!(
+            format.parse("v1.0.0-rc.1"),
+            Some(Version::parse("1.0.0-rc.1").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_component_placeholders() {
+        let format = TagFormat::new("v{major}.{minor}");
+        assert_eq!(format.format(&Version::new(1, 2, 3)), "v1.2");
+        assert_eq!(format.parse("v1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(format.parse("v1.2.3"), None);
+    }
+
+    #[test]
+    fn test_literal_characters_are_escaped() {
+        let format = TagFormat::new("v{version}+build");
+        assert!(format.matches("v1.0.0+build"));
+        assert!(!format.matches("v1.0.0xbuild"));
+    }
+
+    #[test]
+    fn test_unknown_placeholder_is_literal() {
+        let format = TagFormat::new("{name}-{version}");
+        assert!(format.matches("{name}-1.0.0"));
+        assert!(!format.matches("foo-1.0.0"));
+    }
+}
@@ -0,0 +1,61 @@
+//! Synthetic commit histories shared by the benchmarks.
+
+use chrono::{DateTime, Utc};
+use unduler_commit::RawCommit;
+
+/// Subjects cycled through by the synthetic histories, with a mix of types,
+/// scopes, breaking changes and subjects no parser recognizes.
+const SUBJECTS: &[&str] = &[
+    "feat(api): add pagination to the list endpoints",
+    "fix: handle empty responses from the registry",
+    "docs: document the release workflow",
+    "refactor(core)!: split the release manager",
+    "chore(deps): bump serde from 1.0.200 to 1.0.210",
+    "perf(parser): avoid allocating the subject",
+    "Merge branch 'main' into feature/pagination",
+    "fix(cli): report the failing hook",
+    "test: cover the changelog inserts",
+    "WIP",
+];
+
+/// Gitmoji subjects, ending with the worst cases of the gitmoji parser:
+/// an unknown emoji and no emoji at all scan every known gitmoji.
+const GITMOJI_SUBJECTS: &[&str] = &[
+    "✨ feat(api): add pagination to the list endpoints",
+    ":bug: fix: handle empty responses from the registry",
+    "📝 document the release workflow",
+    ":recycle: refactor(core)!: split the release manager",
+    "🦄 feat: add unicorn support",
+    "fix(cli): report the failing hook",
+];
+
+/// Body of the commits, with footers the parsers extract.
+const BODY: &str = "\n\nLonger description of the change, wrapped over\nseveral lines.\n\nCloses #123\nCo-authored-by: Jane Doe <jane@example.com>\n";
+
+/// Returns `count` commits with the given subjects, newest first.
+fn history(subjects: &[&str], count: usize) -> Vec<RawCommit> {
+    let start = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap_or_default();
+    (0..count)
+        .map(|i| {
+            let subject = subjects[i % subjects.len()];
+            let offset = i64::try_from(i).unwrap_or(i64::MAX);
+            RawCommit::new(
+                format!("{i:040x}"),
+                format!("{subject}{BODY}"),
+                "John Doe",
+                "john@example.com",
+                start - chrono::Duration::seconds(offset),
+            )
+        })
+        .collect()
+}
+
+/// Returns `count` Conventional Commits, a tenth of them unparseable.
+pub fn conventional_history(count: usize) -> Vec<RawCommit> {
+    history(SUBJECTS, count)
+}
+
+/// Returns `count` gitmoji commits.
+pub fn gitmoji_history(count: usize) -> Vec<RawCommit> {
+    history(GITMOJI_SUBJECTS, count)
+}
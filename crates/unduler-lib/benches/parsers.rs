@@ -0,0 +1,64 @@
+//! Benchmarks of the built-in commit parsers.
+
+mod common;
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use unduler_commit::RawCommit;
+use unduler_parser_conventional::ConventionalParser;
+use unduler_parser_gitmoji::ConventionalGitmojiParser;
+use unduler_parser_regex::{FieldMapping, RegexParser, RegexParserConfig};
+use unduler_plugin::CommitParser;
+
+/// Number of commits parsed per iteration.
+const COMMITS: usize = 1_000;
+
+/// Benchmarks a parser over a history.
+fn bench_parser(c: &mut Criterion, name: &str, parser: &dyn CommitParser, commits: &[RawCommit]) {
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Elements(commits.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::new(name, commits.len()),
+        commits,
+        |b, commits| {
+            b.iter(|| {
+                commits
+                    .iter()
+                    .filter_map(|raw| parser.parse(black_box(raw)))
+                    .count()
+            });
+        },
+    );
+    group.finish();
+}
+
+fn conventional(c: &mut Criterion) {
+    let commits = common::conventional_history(COMMITS);
+    bench_parser(c, "conventional", &ConventionalParser::new(), &commits);
+}
+
+fn gitmoji(c: &mut Criterion) {
+    let commits = common::gitmoji_history(COMMITS);
+    bench_parser(c, "gitmoji", &ConventionalGitmojiParser::new(), &commits);
+}
+
+fn regex(c: &mut Criterion) {
+    let parser = RegexParser::new(RegexParserConfig {
+        pattern: r"^(?P<type>\w+)(?:\((?P<scope>[^)]+)\))?!?: (?P<message>.+)$".to_string(),
+        mapping: FieldMapping {
+            r#type: "type".to_string(),
+            scope: Some("scope".to_string()),
+            message: "message".to_string(),
+            metadata: HashMap::new(),
+        },
+        validation: HashMap::new(),
+    })
+    .expect("valid pattern");
+    let commits = common::conventional_history(COMMITS);
+    bench_parser(c, "regex", &parser, &commits);
+}
+
+criterion_group!(benches, conventional, gitmoji, regex);
+criterion_main!(benches);
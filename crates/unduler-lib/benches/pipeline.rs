@@ -0,0 +1,66 @@
+//! End-to-end benchmarks of parsing, bumping and formatting a release.
+
+mod common;
+
+use std::hint::black_box;
+
+use chrono::Utc;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use semver::Version;
+use unduler_bumper_semver::SemverBumper;
+use unduler_core::{Pipeline, VersionManager};
+use unduler_formatter_keepachangelog::KeepAChangelogFormatter;
+use unduler_parser_conventional::ConventionalParser;
+use unduler_parser_gitmoji::ConventionalGitmojiParser;
+use unduler_plugin::{CommitParser, FormatterConfig, Release};
+
+/// Sizes of the synthetic histories.
+const SIZES: &[usize] = &[10_000, 100_000];
+
+/// Creates the pipeline of a parser, with the default bumper and formatter.
+fn pipeline(parser: Box<dyn CommitParser>) -> Pipeline {
+    Pipeline::new(
+        parser,
+        Box::new(SemverBumper::new()),
+        Box::new(KeepAChangelogFormatter::new()),
+    )
+}
+
+/// Parses the commits of a release, determines its bump and formats it.
+fn release(pipeline: &Pipeline, commits: &[unduler_commit::RawCommit]) -> String {
+    let parsed = pipeline.parse_commits(commits);
+    let bump = pipeline.determine_bump(&parsed);
+    let version = VersionManager::new().bump(&Version::new(1, 0, 0), bump);
+    let release = Release::new(version, Utc::now(), parsed);
+    pipeline
+        .formatter()
+        .format(&release, &FormatterConfig::default())
+}
+
+fn end_to_end(c: &mut Criterion) {
+    let mut group = c.benchmark_group("release");
+    group.sample_size(10);
+
+    for &size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+
+        let conventional = pipeline(Box::new(ConventionalParser::new()));
+        let commits = common::conventional_history(size);
+        group.bench_with_input(
+            BenchmarkId::new("conventional", size),
+            &commits,
+            |b, commits| b.iter(|| release(&conventional, black_box(commits))),
+        );
+
+        let gitmoji = pipeline(Box::new(ConventionalGitmojiParser::new()));
+        let commits = common::gitmoji_history(size);
+        group.bench_with_input(BenchmarkId::new("gitmoji", size), &commits, |b, commits| {
+            b.iter(|| release(&gitmoji, black_box(commits)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, end_to_end);
+criterion_main!(benches);
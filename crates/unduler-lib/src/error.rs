@@ -0,0 +1,84 @@
+//! Library error types.
+
+use thiserror::Error;
+
+/// Errors returned by the library API.
+#[derive(Debug, Error)]
+pub enum UndulerError {
+    /// Configuration error.
+    #[error("config error: {0}")]
+    Config(#[from] unduler_config::ConfigError),
+
+    /// Git error.
+    #[error("git error: {0}")]
+    Git(#[from] unduler_git::GitError),
+
+    /// Release error.
+    #[error(transparent)]
+    Core(#[from] unduler_core::CoreError),
+
+    /// A plugin that is not built in could not be loaded.
+    #[error("plugin error: {0}")]
+    Plugin(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// No built-in or installed commit parser has this name.
+    #[error("unknown commit parser `{0}`")]
+    UnknownParser(String),
+
+    /// No built-in or installed bump strategy has this name.
+    #[error("unknown bump strategy `{0}`")]
+    UnknownBumper(String),
+
+    /// No built-in or installed changelog formatter has this name.
+    #[error("unknown changelog formatter `{0}`")]
+    UnknownFormatter(String),
+
+    /// A hook listed in `[hooks]` is neither enabled nor installed.
+    #[error("unknown hook `{0}`: it is neither enabled nor installed")]
+    UnknownHook(String),
+
+    /// The environment variable of a webhook secret header is unset.
+    #[error("secret header `{header}` of webhook {url} needs the `{var}` environment variable")]
+    MissingSecretHeader {
+        /// Webhook URL.
+        url: String,
+        /// Header name.
+        header: String,
+        /// Environment variable holding the header value.
+        var: String,
+    },
+
+    /// The Debian changelog formatter has no package name.
+    #[error("no Debian package name")]
+    MissingDebianPackage,
+
+    /// The Debian changelog formatter has no maintainer.
+    #[error("no Debian package maintainer")]
+    MissingDebianMaintainer,
+}
+
+/// Result type for library operations.
+pub type UndulerResult<T> = Result<T, UndulerError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use unduler_config::ConfigError;
+    use unduler_core::CoreError;
+
+    #[test]
+    fn test_config_error_display() {
+        let err = UndulerError::from(ConfigError::NotFound(PathBuf::from("unduler.toml")));
+        assert_eq!(
+            err.to_string(),
+            "config error: configuration file not found: unduler.toml"
+        );
+    }
+
+    #[test]
+    fn test_core_error_is_transparent() {
+        let err = UndulerError::from(CoreError::NoCommits);
+        assert_eq!(err.to_string(), "no commits found since last release");
+    }
+}
@@ -0,0 +1,46 @@
+//! Programmatic API for Unduler.
+//!
+//! This crate drives releases without the CLI, e.g. from a cargo xtask. It
+//! loads the configuration, selects the built-in plugins and exposes the
+//! release steps of [`unduler_core`].
+//!
+//! ```no_run
+//! use unduler_lib::{Project, ReleaseOptions};
+//!
+//! let project = Project::discover()?;
+//!
+//! let plan = project.plan()?;
+//! println!("next version: {}", plan.new_version);
+//! println!("{}", project.changelog(&plan)?);
+//!
+//! let outcome = project.release(&ReleaseOptions::new().with_push(true))?;
+//! println!("released {}", outcome.new_version);
+//! # Ok::<(), unduler_lib::UndulerError>(())
+//! ```
+
+mod error;
+mod plugins;
+mod project;
+
+pub use error::{UndulerError, UndulerResult};
+pub use plugins::{
+    ExternalPlugins, create_bumper, create_bumper_with, create_formatter, create_gitmoji_parser,
+    create_jira_hook, create_jvm_hook, create_milestone_hook, create_named_formatter,
+    create_named_formatter_with, create_notify_hook, create_oci_hook, create_parser,
+    create_parser_with, create_pipeline, create_pipeline_with, create_sbom_hook,
+    create_sentry_hook, create_webhook_hook,
+};
+pub use project::Project;
+
+pub use semver::Version;
+pub use unduler_commit::{ParsedCommit, RawCommit};
+pub use unduler_config::{Config, find_and_load_config, find_and_load_config_from, load_config};
+pub use unduler_core::{
+    FileUpdate, FileVersion, Pipeline, ReleaseManager, ReleaseOptions, ReleaseOutcome, ReleasePlan,
+    try_join_bounded,
+};
+pub use unduler_git::Repository;
+pub use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, BumpType, ChangelogFormatter, CommitParser, HookStage,
+    ReleaseContext, ReleaseHook,
+};
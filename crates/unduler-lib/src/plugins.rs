@@ -0,0 +1,1126 @@
+//! Built-in plugins selected from the configuration.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tracing::{info, warn};
+use unduler_bumper_semver::SemverBumper;
+use unduler_config::{
+    BumpRule, Config, JvmBuildTool, MilestoneForge, NotifyChannelKind, SbomFormat, WebhookStage,
+};
+use unduler_core::{Normalizer, Pipeline, ScopeNormalizer};
+use unduler_formatter_debian::DebianFormatter;
+use unduler_formatter_keepachangelog::KeepAChangelogFormatter;
+use unduler_git::Repository;
+use unduler_hook_jira::{JiraAuth, JiraHook};
+use unduler_hook_jvm::{BuildTool, JvmHook};
+use unduler_hook_milestone::{Forge, MilestoneHook};
+use unduler_hook_notify::{Channel, ChannelKind, NotifyHook};
+use unduler_hook_oci::OciHook;
+use unduler_hook_sbom::SbomHook;
+use unduler_hook_sentry::SentryHook;
+use unduler_hook_webhook::{Webhook, WebhookHook};
+use unduler_parser_conventional::ConventionalParser;
+use unduler_parser_gitmoji::{ConventionalGitmojiParser, GitmojiParserConfig};
+use unduler_parser_jira::{JiraParser, JiraParserConfig};
+use unduler_parser_regex::{FieldMapping, RegexParser, RegexParserConfig};
+use unduler_plugin::{
+    BumpStrategy, BumpType, ChangelogFormatter, CommitParser, CompositeParser, HookStage,
+    ReleaseHook,
+};
+
+use crate::{UndulerError, UndulerResult};
+
+/// Control file of Debian packages, relative to the current directory.
+const DEBIAN_CONTROL: &str = "debian/control";
+
+/// Plugins that are not built in, such as installed WASM plugins, looked up
+/// by the name the configuration gives them.
+///
+/// Names of built-in plugins are never looked up. Every method returns
+/// `None` by default, so that unknown names fall back to the default
+/// plugins.
+pub trait ExternalPlugins {
+    /// Returns the commit parser named `name`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parser exists but cannot be loaded.
+    fn parser(&self, _name: &str) -> UndulerResult<Option<Box<dyn CommitParser>>> {
+        Ok(None)
+    }
+
+    /// Returns the bump strategy named `name`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bumper exists but cannot be loaded.
+    fn bumper(&self, _name: &str) -> UndulerResult<Option<Box<dyn BumpStrategy>>> {
+        Ok(None)
+    }
+
+    /// Returns the changelog formatter named `name`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the formatter exists but cannot be loaded.
+    fn formatter(&self, _name: &str) -> UndulerResult<Option<Box<dyn ChangelogFormatter>>> {
+        Ok(None)
+    }
+
+    /// Returns the release hook named `name`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hook exists but cannot be loaded.
+    fn hook(&self, _name: &str) -> UndulerResult<Option<Box<dyn ReleaseHook>>> {
+        Ok(None)
+    }
+}
+
+/// No external plugins.
+impl ExternalPlugins for () {}
+
+/// Creates the pipeline of built-in plugins described by the configuration.
+///
+/// The notification and webhook hooks are added when `plugins.notify` has
+/// channels and `plugins.webhook` has targets, the JVM hook when
+/// `plugins.jvm.publish` is set, the SBOM hook when `plugins.sbom.enabled`
+/// is set, and the OCI, JIRA, Sentry and milestone hooks when
+/// `plugins.oci.repository`, `plugins.jira.base_url`, `plugins.sentry.org`
+/// and `plugins.milestone.repository` are set. Other hooks can be added with [`Pipeline::with_hook`] and
+/// [`Pipeline::with_async_hook`].
+///
+/// The hooks listed in `[hooks]` run at their stages, in order.
+///
+/// # Errors
+///
+/// Returns an error if the changelog formatter cannot be created, or a
+/// parser or listed hook is unknown.
+pub fn create_pipeline(config: &Config) -> UndulerResult<Pipeline> {
+    create_pipeline_with(config, &())
+}
+
+/// Creates the pipeline described by the configuration, as
+/// [`create_pipeline`] does, with plugins that are not built in.
+///
+/// Parsers, bumpers and formatters that are not built in are looked up in
+/// `external`, and so are the hooks listed in `[hooks]` that no built-in
+/// hook of the pipeline answers to.
+///
+/// # Errors
+///
+/// Returns an error if a changelog formatter cannot be created, a parser,
+/// bumper, formatter or listed hook is neither built in nor in `external`,
+/// or an external plugin cannot be loaded.
+pub fn create_pipeline_with(
+    config: &Config,
+    external: &dyn ExternalPlugins,
+) -> UndulerResult<Pipeline> {
+    let mut pipeline = Pipeline::new(
+        create_parser_with(config, external)?,
+        create_bumper_with(config, external)?,
+        create_named_formatter_with(&config.formatter.name, config, external)?,
+    )
+    .with_concurrency(config.hooks.concurrency)
+    .with_highlight_marker(&config.formatter.highlight_marker)
+    .with_normalizer(Normalizer::from_config(&config.parser.normalize))
+    .with_scope_normalizer(ScopeNormalizer::from_config(&config.changelog));
+
+    for output in &config.changelog.outputs {
+        // The changelog itself is already rendered by `formatter.name`
+        if output.path == config.changelog.output {
+            if output.formatter != config.formatter.name {
+                warn!(
+                    path = %output.path,
+                    formatter = %output.formatter,
+                    "changelog output is the changelog, rendered by {}",
+                    config.formatter.name
+                );
+            }
+            continue;
+        }
+        let formatter = create_named_formatter_with(&output.formatter, config, external)?;
+        pipeline = pipeline.with_output(&output.path, formatter);
+    }
+
+    let hooks = &config.hooks;
+    let mut hook_names = BTreeSet::new();
+    for (stage, entries) in [
+        (HookStage::PreRelease, &hooks.pre_release),
+        (HookStage::PreBump, &hooks.pre_bump),
+        (HookStage::PostBump, &hooks.post_bump),
+        (HookStage::PreCommit, &hooks.pre_commit),
+        (HookStage::PreTag, &hooks.pre_tag),
+        (HookStage::PostTag, &hooks.post_tag),
+        (HookStage::PostRelease, &hooks.post_release),
+        (HookStage::Failure, &hooks.failure),
+    ] {
+        hook_names.extend(entries.iter().map(|entry| entry.name.as_str()));
+        if !entries.is_empty() {
+            pipeline = pipeline.with_hook_entries(stage, entries.clone());
+        }
+    }
+
+    if !config.plugins.notify.channels.is_empty() {
+        pipeline = pipeline.with_async_hook(Box::new(create_notify_hook(config)));
+    }
+    if !config.plugins.webhook.targets.is_empty() {
+        pipeline = pipeline.with_async_hook(Box::new(create_webhook_hook(config)?));
+    }
+    if config.plugins.jvm.publish {
+        pipeline = pipeline.with_hook(Box::new(create_jvm_hook(config)));
+    }
+    if let Some(hook) = create_oci_hook(config) {
+        pipeline = pipeline.with_hook(Box::new(hook));
+    }
+    if config.plugins.sbom.enabled {
+        pipeline = pipeline.with_hook(Box::new(create_sbom_hook(config)));
+    }
+    if let Some(hook) = create_jira_hook(config) {
+        pipeline = pipeline.with_async_hook(Box::new(hook));
+    }
+    if let Some(hook) = create_sentry_hook(config) {
+        pipeline = pipeline.with_async_hook(Box::new(hook));
+    }
+    if let Some(hook) = create_milestone_hook(config) {
+        pipeline = pipeline.with_async_hook(Box::new(hook));
+    }
+
+    for name in hook_names {
+        let registered = pipeline.hooks().iter().any(|hook| hook.name() == name)
+            || pipeline
+                .async_hooks()
+                .iter()
+                .any(|hook| hook.name() == name);
+        if !registered {
+            let hook = external
+                .hook(name)?
+                .ok_or_else(|| UndulerError::UnknownHook(name.to_string()))?;
+            pipeline = pipeline.with_hook(hook);
+        }
+    }
+    Ok(pipeline)
+}
+
+/// Returns the tag name template of the configuration, as the release tags
+/// are named, for the hooks rendering tags.
+fn tag_template(config: &Config) -> String {
+    let template = config.version.tag_template();
+    match &config.version.package {
+        Some(package) => template.replace("{package}", package),
+        None => template,
+    }
+}
+
+/// Creates the commit parser, chaining parsers when `parser.names` is set.
+///
+/// # Errors
+///
+/// Returns an error if a parser name is unknown.
+pub fn create_parser(config: &Config) -> UndulerResult<Box<dyn CommitParser>> {
+    create_parser_with(config, &())
+}
+
+/// Creates the commit parser as [`create_parser`] does, looking up parsers
+/// with unknown names in `external`.
+///
+/// # Errors
+///
+/// Returns an error if a parser is neither built in nor in `external`, or
+/// an external parser cannot be loaded.
+pub fn create_parser_with(
+    config: &Config,
+    external: &dyn ExternalPlugins,
+) -> UndulerResult<Box<dyn CommitParser>> {
+    if config.parser.names.is_empty() {
+        return create_external_parser(&config.parser.name, config, external);
+    }
+
+    let parsers = config
+        .parser
+        .names
+        .iter()
+        .map(|name| create_external_parser(name, config, external))
+        .collect::<UndulerResult<_>>()?;
+    Ok(Box::new(CompositeParser::new(parsers)))
+}
+
+/// Creates a single parser by name, looking up unknown names in `external`.
+fn create_external_parser(
+    name: &str,
+    config: &Config,
+    external: &dyn ExternalPlugins,
+) -> UndulerResult<Box<dyn CommitParser>> {
+    if let Some(parser) = create_builtin_parser(name, config) {
+        return Ok(parser);
+    }
+    external
+        .parser(name)?
+        .ok_or_else(|| UndulerError::UnknownParser(name.to_string()))
+}
+
+/// Creates a built-in parser by name, if there is one.
+fn create_builtin_parser(name: &str, config: &Config) -> Option<Box<dyn CommitParser>> {
+    match name {
+        "conventional" => Some(Box::new(ConventionalParser::new())),
+        "gitmoji" | "conventional-gitmoji" => Some(Box::new(create_gitmoji_parser(config))),
+        "regex" => Some(create_regex_parser(config)),
+        "jira" => Some(Box::new(create_jira_parser(config))),
+        _ => None,
+    }
+}
+
+/// Creates the gitmoji parser, with the configured emoji mappings.
+#[must_use]
+pub fn create_gitmoji_parser(config: &Config) -> ConventionalGitmojiParser {
+    let gitmoji = &config.parser.conventional_gitmoji;
+    let parser_config = GitmojiParserConfig {
+        infer_type_from_emoji: gitmoji.infer_type_from_emoji,
+        strict_emoji: gitmoji.strict_emoji,
+        mappings: gitmoji.mappings.clone(),
+    };
+    ConventionalGitmojiParser::with_config(parser_config)
+}
+
+fn create_jira_parser(config: &Config) -> JiraParser {
+    let jira = &config.parser.jira;
+    JiraParser::with_config(JiraParserConfig {
+        projects: jira.projects.clone(),
+        types: jira.types.clone(),
+        default_type: jira.default_type.clone(),
+    })
+}
+
+fn create_regex_parser(config: &Config) -> Box<dyn CommitParser> {
+    let regex = &config.parser.regex;
+    let Some(ref pattern) = regex.pattern else {
+        info!("no regex pattern configured, falling back to conventional");
+        return Box::new(ConventionalParser::new());
+    };
+
+    let metadata: HashMap<_, _> = regex
+        .mapping
+        .iter()
+        .filter(|(field, _)| !["type", "scope", "message"].contains(&field.as_str()))
+        .map(|(field, capture)| (field.clone(), capture.clone()))
+        .collect();
+
+    let mapping = FieldMapping {
+        r#type: regex
+            .mapping
+            .get("type")
+            .cloned()
+            .unwrap_or_else(|| "type".to_string()),
+        scope: regex.mapping.get("scope").cloned(),
+        message: regex
+            .mapping
+            .get("message")
+            .cloned()
+            .unwrap_or_else(|| "message".to_string()),
+        metadata,
+    };
+
+    let parser_config = RegexParserConfig {
+        pattern: pattern.clone(),
+        mapping,
+        validation: regex.validation.clone(),
+    };
+
+    match RegexParser::new(parser_config) {
+        Ok(parser) => Box::new(parser),
+        Err(e) => {
+            info!("invalid regex pattern, falling back to conventional: {e}");
+            Box::new(ConventionalParser::new())
+        }
+    }
+}
+
+/// Creates the bump strategy named by `bumper.name`: the built-in `semver`
+/// bumper with the configured rules, or a bumper of `external`.
+///
+/// # Errors
+///
+/// Returns an error if the bumper is neither built in nor in `external`, or
+/// cannot be loaded.
+pub fn create_bumper_with(
+    config: &Config,
+    external: &dyn ExternalPlugins,
+) -> UndulerResult<Box<dyn BumpStrategy>> {
+    let name = &config.bumper.name;
+    if name == "semver" {
+        return Ok(Box::new(create_bumper(config)));
+    }
+    external
+        .bumper(name)?
+        .ok_or_else(|| UndulerError::UnknownBumper(name.clone()))
+}
+
+/// Creates the bump strategy with the configured rules.
+#[must_use]
+pub fn create_bumper(config: &Config) -> SemverBumper {
+    let bumper = SemverBumper::new().with_zero_ver(config.bumper.zero_ver);
+    config
+        .bumper
+        .rules
+        .iter()
+        .fold(bumper, |bumper, (key, rule)| {
+            bumper.with_rule(key.clone(), bump_type(*rule))
+        })
+}
+
+fn bump_type(rule: BumpRule) -> BumpType {
+    match rule {
+        BumpRule::Major => BumpType::Major,
+        BumpRule::Minor => BumpType::Minor,
+        BumpRule::Patch => BumpType::Patch,
+        BumpRule::None => BumpType::None,
+    }
+}
+
+/// Creates the changelog formatter named by `formatter.name`.
+///
+/// # Errors
+///
+/// Returns an error if the name is unknown, or the Debian formatter has
+/// no package name or maintainer.
+pub fn create_formatter(config: &Config) -> UndulerResult<Box<dyn ChangelogFormatter>> {
+    create_named_formatter(&config.formatter.name, config)
+}
+
+/// Creates a built-in changelog formatter by name: `keepachangelog` or
+/// `debian`.
+///
+/// The Debian package defaults to the `Source` field of `debian/control`,
+/// and its maintainer to `DEBFULLNAME` and `DEBEMAIL`, as `dch` does, or to
+/// the git identity.
+///
+/// # Errors
+///
+/// Returns an error if the name is unknown, or the Debian formatter has
+/// no package name or maintainer.
+pub fn create_named_formatter(
+    name: &str,
+    config: &Config,
+) -> UndulerResult<Box<dyn ChangelogFormatter>> {
+    create_named_formatter_with(name, config, &())
+}
+
+/// Creates a changelog formatter by name as [`create_named_formatter`]
+/// does, looking up other names in `external`.
+///
+/// # Errors
+///
+/// Returns an error if no formatter has this name, the Debian formatter
+/// has no package name or maintainer, or an external formatter cannot be
+/// loaded.
+pub fn create_named_formatter_with(
+    name: &str,
+    config: &Config,
+    external: &dyn ExternalPlugins,
+) -> UndulerResult<Box<dyn ChangelogFormatter>> {
+    match name {
+        "debian" => {}
+        "keepachangelog" => return Ok(Box::new(KeepAChangelogFormatter::new())),
+        name => {
+            return external
+                .formatter(name)?
+                .ok_or_else(|| UndulerError::UnknownFormatter(name.to_string()));
+        }
+    }
+
+    let debian = &config.formatter.debian;
+    let package = match &debian.package {
+        Some(package) => package.clone(),
+        None => {
+            control_source(Path::new(DEBIAN_CONTROL)).ok_or(UndulerError::MissingDebianPackage)?
+        }
+    };
+    let maintainer = match &debian.maintainer {
+        Some(maintainer) => maintainer.clone(),
+        None => debian_maintainer().ok_or(UndulerError::MissingDebianMaintainer)?,
+    };
+
+    Ok(Box::new(
+        DebianFormatter::new(package, maintainer)
+            .with_distribution(&debian.distribution)
+            .with_urgency(&debian.urgency)
+            .with_revision(debian.revision.clone()),
+    ))
+}
+
+/// Returns the `Source` field of a `debian/control` file.
+fn control_source(path: &Path) -> Option<String> {
+    let control = fs::read_to_string(path).ok()?;
+    control.lines().find_map(|line| {
+        line.strip_prefix("Source:")
+            .map(|source| source.trim().to_string())
+            .filter(|source| !source.is_empty())
+    })
+}
+
+/// Returns the maintainer of Debian packages from `DEBFULLNAME` and
+/// `DEBEMAIL`, or from the git identity.
+fn debian_maintainer() -> Option<String> {
+    let var = |name| {
+        std::env::var(name)
+            .ok()
+            .filter(|value: &String| !value.is_empty())
+    };
+    if let (Some(name), Some(email)) = (var("DEBFULLNAME"), var("DEBEMAIL")) {
+        return Some(format!("{name} <{email}>"));
+    }
+    let identity = Repository::discover().ok()?.identity().ok()?;
+    Some(identity.to_string())
+}
+
+/// Creates the notification hook, skipping channels without a webhook URL.
+#[must_use]
+pub fn create_notify_hook(config: &Config) -> NotifyHook {
+    let notify = &config.plugins.notify;
+    let mut hook = NotifyHook::new()
+        .with_tag_format(tag_template(config))
+        .with_failures(notify.failures);
+    if let Some(ref template) = notify.template {
+        hook = hook.with_template(template);
+    }
+    if let Some(ref url) = notify.changelog_url {
+        hook = hook.with_changelog_url(url);
+    }
+
+    for channel in &notify.channels {
+        let kind = match channel.kind {
+            NotifyChannelKind::Slack => ChannelKind::Slack,
+            NotifyChannelKind::Discord => ChannelKind::Discord,
+            NotifyChannelKind::Teams => ChannelKind::Teams,
+        };
+        let url = channel.webhook_url.clone().or_else(|| {
+            channel
+                .webhook_url_env
+                .as_ref()
+                .and_then(|var| std::env::var(var).ok())
+        });
+        let Some(url) = url else {
+            warn!(channel = kind.name(), "no webhook URL configured, skipping");
+            continue;
+        };
+
+        let mut target = Channel::new(kind, url);
+        if let Some(ref template) = channel.template {
+            target = target.with_template(template);
+        }
+        hook = hook.with_channel(target);
+    }
+    hook
+}
+
+/// Creates the webhook hook.
+///
+/// # Errors
+///
+/// Returns an error if the environment variable of a secret header is
+/// unset, rather than calling the webhook without it.
+pub fn create_webhook_hook(config: &Config) -> UndulerResult<WebhookHook> {
+    let webhook = &config.plugins.webhook;
+    let hook = WebhookHook::new()
+        .with_tag_format(tag_template(config))
+        .with_retries(webhook.retries)
+        .with_backoff(Duration::from_secs(webhook.backoff));
+
+    webhook.targets.iter().try_fold(hook, |hook, target| {
+        let stages = target
+            .stages
+            .iter()
+            .map(|stage| match stage {
+                WebhookStage::PreRelease => HookStage::PreRelease,
+                WebhookStage::PreBump => HookStage::PreBump,
+                WebhookStage::PostBump => HookStage::PostBump,
+                WebhookStage::PreCommit => HookStage::PreCommit,
+                WebhookStage::PreTag => HookStage::PreTag,
+                WebhookStage::PostTag => HookStage::PostTag,
+                WebhookStage::PostRelease => HookStage::PostRelease,
+                WebhookStage::Failure => HookStage::Failure,
+            })
+            .collect();
+        let mut webhook = Webhook::new(&target.url).with_stages(stages);
+        for (name, value) in &target.headers {
+            webhook = webhook.with_header(name, value);
+        }
+        for (name, var) in &target.secret_headers {
+            let value = std::env::var(var).map_err(|_| UndulerError::MissingSecretHeader {
+                url: target.url.clone(),
+                header: name.clone(),
+                var: var.clone(),
+            })?;
+            webhook = webhook.with_header(name, value);
+        }
+        if let Some(ref body) = target.body {
+            webhook = webhook.with_body(body.clone());
+        }
+        Ok(hook.with_webhook(webhook))
+    })
+}
+
+/// Creates the Maven and Gradle hook.
+#[must_use]
+pub fn create_jvm_hook(config: &Config) -> JvmHook {
+    let jvm = &config.plugins.jvm;
+    let mut hook = JvmHook::new()
+        .with_tasks(jvm.tasks.clone())
+        .with_profiles(jvm.profiles.clone())
+        .with_args(jvm.args.clone());
+    if let Some(tool) = jvm.tool {
+        hook = hook.with_tool(match tool {
+            JvmBuildTool::Gradle => BuildTool::Gradle,
+            JvmBuildTool::Maven => BuildTool::Maven,
+        });
+    }
+    jvm.env
+        .iter()
+        .fold(hook, |hook, (name, value)| hook.with_env(name, value))
+}
+
+/// Creates the OCI artifact hook, if a repository is configured.
+#[must_use]
+pub fn create_oci_hook(config: &Config) -> Option<OciHook> {
+    let oci = &config.plugins.oci;
+    let mut hook = OciHook::new(oci.repository.as_ref()?)
+        .with_path(&oci.path)
+        .with_tags(oci.tags.clone())
+        .with_tag_format(tag_template(config));
+    if let Some(ref artifact_type) = oci.artifact_type {
+        hook = hook.with_artifact_type(artifact_type);
+    }
+    if let Some(ref media_type) = oci.media_type {
+        hook = hook.with_media_type(media_type);
+    }
+    Some(hook)
+}
+
+/// Creates the JIRA hook, if a base URL is configured and the API token is
+/// set.
+#[must_use]
+pub fn create_jira_hook(config: &Config) -> Option<JiraHook> {
+    let jira = &config.plugins.jira;
+    let base_url = jira.base_url.as_ref()?;
+    let Ok(token) = std::env::var(&jira.token_env) else {
+        warn!(var = %jira.token_env, "JIRA API token is unset, skipping JIRA updates");
+        return None;
+    };
+
+    let auth = match jira.email {
+        Some(ref email) => JiraAuth::Basic {
+            email: email.clone(),
+            token,
+        },
+        None => JiraAuth::Bearer(token),
+    };
+    let mut hook = JiraHook::new(base_url, auth).with_tag_format(tag_template(config));
+    if let Some(ref comment) = jira.comment {
+        hook = hook.with_comment(comment);
+    }
+    if let Some(ref transition) = jira.transition {
+        hook = hook.with_transition(transition);
+    }
+    Some(hook)
+}
+
+/// Creates the Sentry hook, if an organization is configured and the
+/// authentication token is set.
+#[must_use]
+pub fn create_sentry_hook(config: &Config) -> Option<SentryHook> {
+    let sentry = &config.plugins.sentry;
+    let org = sentry.org.as_ref()?;
+    let Ok(token) = std::env::var(&sentry.token_env) else {
+        warn!(var = %sentry.token_env, "Sentry token is unset, skipping Sentry release");
+        return None;
+    };
+
+    let environment = Some(sentry.environment.clone()).filter(|env| !env.is_empty());
+    let mut hook = SentryHook::new(org, sentry.projects.clone(), token)
+        .with_release(&sentry.release)
+        .with_environment(environment)
+        .with_tag_format(tag_template(config));
+    if let Some(ref url) = sentry.url {
+        hook = hook.with_url(url);
+    }
+    Some(hook)
+}
+
+/// Creates the SBOM and provenance hook, describing the GitHub release
+/// assets unless artifacts are configured.
+#[must_use]
+pub fn create_sbom_hook(config: &Config) -> SbomHook {
+    let sbom = &config.plugins.sbom;
+    let artifacts = sbom
+        .artifacts
+        .as_ref()
+        .unwrap_or(&config.plugins.github_release.assets)
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    let formats = sbom
+        .formats
+        .iter()
+        .map(|format| match format {
+            SbomFormat::CycloneDx => unduler_hook_sbom::SbomFormat::CycloneDx,
+            SbomFormat::Spdx => unduler_hook_sbom::SbomFormat::Spdx,
+        })
+        .collect();
+
+    let mut hook = SbomHook::new(artifacts)
+        .with_source(&sbom.source)
+        .with_formats(formats)
+        .with_output_dir(&sbom.output_dir)
+        .with_provenance(sbom.provenance)
+        .with_tag_format(tag_template(config));
+    if let Some(ref builder_id) = sbom.builder_id {
+        hook = hook.with_builder_id(builder_id);
+    }
+    if let Some(ref uri) = sbom.source_uri {
+        hook = hook.with_source_uri(uri);
+    }
+    hook
+}
+
+/// Creates the milestone hook, if a repository is configured and the API
+/// token is set.
+#[must_use]
+pub fn create_milestone_hook(config: &Config) -> Option<MilestoneHook> {
+    let milestone = &config.plugins.milestone;
+    let repository = milestone.repository.as_ref()?;
+    let (forge, default_token_env) = match milestone.forge {
+        MilestoneForge::GitHub => (Forge::GitHub, "GITHUB_TOKEN"),
+        MilestoneForge::GitLab => (Forge::GitLab, "GITLAB_TOKEN"),
+    };
+    let token_env = milestone.token_env.as_deref().unwrap_or(default_token_env);
+    let Ok(token) = std::env::var(token_env) else {
+        warn!(var = %token_env, "forge token is unset, skipping milestones");
+        return None;
+    };
+
+    let mut hook = MilestoneHook::new(forge, repository, token)
+        .with_title(&milestone.title)
+        .with_next(bump_type(milestone.next))
+        .with_tag_format(tag_template(config));
+    if let Some(ref url) = milestone.api_url {
+        hook = hook.with_api_url(url);
+    }
+    Some(hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use chrono::Utc;
+    use unduler_commit::RawCommit;
+
+    use super::*;
+
+    fn config(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_default_parser() {
+        let parser = create_parser(&Config::default()).unwrap();
+        assert_eq!(parser.name(), "conventional");
+    }
+
+    #[test]
+    fn test_named_parser() {
+        let parser = create_parser(&config("[parser]\nname = \"gitmoji\"\n")).unwrap();
+        assert_eq!(parser.name(), "gitmoji");
+    }
+
+    #[test]
+    fn test_gitmoji_parser_mappings() {
+        let parser = create_gitmoji_parser(&config(
+            "[parser.conventional-gitmoji.mappings]\n\"🧿\" = \"fix\"\n",
+        ));
+        let raw = RawCommit::new(
+            "abc",
+            "🧿 ward off bugs",
+            "Jane",
+            "jane@example.com",
+            Utc::now(),
+        );
+        assert_eq!(parser.parse(&raw).unwrap().r#type, "fix");
+    }
+
+    #[test]
+    fn test_jira_parser() {
+        let parser = create_parser(&config(
+            "[parser]\nname = \"jira\"\n\n[parser.jira.types]\nfix = \"fix\"\n",
+        ))
+        .unwrap();
+        assert_eq!(parser.name(), "jira");
+        let raw = RawCommit::new(
+            "abc",
+            "PROJ-1: fix login",
+            "Jane",
+            "jane@example.com",
+            Utc::now(),
+        );
+        let parsed = parser.parse(&raw).unwrap();
+        assert_eq!(parsed.r#type, "fix");
+        assert_eq!(parsed.tickets(), vec!["PROJ-1"]);
+    }
+
+    #[test]
+    fn test_parser_chain() {
+        let parser = create_parser(&config(
+            "[parser]\nnames = [\"gitmoji\", \"conventional\"]\n",
+        ))
+        .unwrap();
+        assert_eq!(parser.name(), "composite");
+    }
+
+    #[test]
+    fn test_regex_parser_without_pattern() {
+        let parser = create_parser(&config("[parser]\nname = \"regex\"\n")).unwrap();
+        assert_eq!(parser.name(), "conventional");
+    }
+
+    #[test]
+    fn test_pipeline_without_notify_channels() {
+        let pipeline = create_pipeline(&Config::default()).unwrap();
+        assert!(pipeline.async_hooks().is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_with_notify_channels() {
+        let pipeline = create_pipeline(&config(
+            "[[plugins.notify.channels]]\nkind = \"slack\"\nwebhook_url = \"https://hooks.test\"\n",
+        ))
+        .unwrap();
+        assert_eq!(pipeline.async_hooks().len(), 1);
+        assert_eq!(pipeline.async_hooks()[0].name(), "notify");
+    }
+
+    #[test]
+    fn test_named_formatters() {
+        let config = config(
+            "[formatter.debian]\npackage = \"app\"\nmaintainer = \"Jane <jane@example.com>\"\n",
+        );
+        let formatter = create_named_formatter("debian", &config).unwrap();
+        assert_eq!(formatter.name(), "debian");
+        let formatter = create_named_formatter("keepachangelog", &config).unwrap();
+        assert_eq!(formatter.name(), "keepachangelog");
+        assert!(matches!(
+            create_named_formatter("unknown", &config),
+            Err(UndulerError::UnknownFormatter(name)) if name == "unknown"
+        ));
+    }
+
+    #[test]
+    fn test_pipeline_outputs() {
+        let pipeline = create_pipeline(&config(
+            "[formatter.debian]\npackage = \"app\"\nmaintainer = \"Jane <jane@example.com>\"\n\n\
+             [[changelog.outputs]]\nformatter = \"keepachangelog\"\npath = \"CHANGELOG.md\"\n\n\
+             [[changelog.outputs]]\nformatter = \"debian\"\npath = \"debian/changelog\"\n",
+        ))
+        .unwrap();
+        assert_eq!(pipeline.outputs().len(), 1);
+        assert_eq!(pipeline.outputs()[0].path, Path::new("debian/changelog"));
+        assert_eq!(pipeline.outputs()[0].formatter.name(), "debian");
+
+        let result = create_pipeline(&config(
+            "[[changelog.outputs]]\nformatter = \"markdown\"\npath = \"NEWS.md\"\n",
+        ));
+        assert!(matches!(result, Err(UndulerError::UnknownFormatter(name)) if name == "markdown"));
+    }
+
+    #[test]
+    fn test_pipeline_hook_entries() {
+        let pipeline = create_pipeline(&config(
+            r#"
+            [hooks]
+            pre_tag = ["sbom"]
+
+            [[hooks.post_tag]]
+            name = "notify"
+            if = "!prerelease"
+
+            [plugins.sbom]
+            enabled = true
+
+            [[plugins.notify.channels]]
+            kind = "slack"
+            webhook_url_env = "UNDULER_TEST_UNSET_WEBHOOK"
+            "#,
+        ))
+        .unwrap();
+        assert_eq!(pipeline.hook_entries(HookStage::PreTag)[0].name, "sbom");
+        assert_eq!(pipeline.hook_entries(HookStage::PostTag)[0].name, "notify");
+        assert!(pipeline.hook_entries(HookStage::PostRelease).is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_unknown_hook_entry() {
+        let result = create_pipeline(&config(
+            r#"
+            [[hooks.post_tag]]
+            name = "notfiy"
+            continue_on_error = true
+            "#,
+        ));
+        assert!(matches!(result, Err(UndulerError::UnknownHook(name)) if name == "notfiy"));
+    }
+
+    /// External plugins answering to `audit`, as a parser, a bumper and a
+    /// hook.
+    struct Audit;
+
+    impl unduler_plugin::Plugin for Audit {
+        fn name(&self) -> &'static str {
+            "audit"
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+
+        fn description(&self) -> &'static str {
+            "Audit plugin"
+        }
+    }
+
+    impl CommitParser for Audit {
+        fn parse(&self, _raw: &unduler_commit::RawCommit) -> Option<unduler_commit::ParsedCommit> {
+            None
+        }
+    }
+
+    impl BumpStrategy for Audit {
+        fn determine(&self, _commits: &[unduler_commit::ParsedCommit]) -> BumpType {
+            BumpType::None
+        }
+
+        fn next_version(
+            &self,
+            _current: &semver::Version,
+            _commits: &[unduler_commit::ParsedCommit],
+        ) -> Option<semver::Version> {
+            Some(semver::Version::new(2026, 10, 0))
+        }
+    }
+
+    impl ReleaseHook for Audit {}
+
+    impl ExternalPlugins for Audit {
+        fn parser(&self, name: &str) -> UndulerResult<Option<Box<dyn CommitParser>>> {
+            Ok((name == "audit").then(|| Box::new(Audit) as Box<dyn CommitParser>))
+        }
+
+        fn bumper(&self, name: &str) -> UndulerResult<Option<Box<dyn BumpStrategy>>> {
+            Ok((name == "audit").then(|| Box::new(Audit) as Box<dyn BumpStrategy>))
+        }
+
+        fn hook(&self, name: &str) -> UndulerResult<Option<Box<dyn ReleaseHook>>> {
+            Ok((name == "audit").then(|| Box::new(Audit) as Box<dyn ReleaseHook>))
+        }
+    }
+
+    #[test]
+    fn test_pipeline_with_external_plugins() {
+        let pipeline = create_pipeline_with(
+            &config(
+                r#"
+                [parser]
+                name = "audit"
+
+                [bumper]
+                name = "audit"
+
+                [hooks]
+                pre_tag = ["audit"]
+                "#,
+            ),
+            &Audit,
+        )
+        .unwrap();
+        let names: Vec<_> = pipeline.hooks().iter().map(|hook| hook.name()).collect();
+        assert_eq!(names, ["audit"]);
+        assert_eq!(
+            pipeline.next_version(&semver::Version::new(1, 0, 0), &[]),
+            Some(semver::Version::new(2026, 10, 0))
+        );
+
+        let result = create_pipeline(&config("[bumper]\nname = \"calver\"\n"));
+        assert!(matches!(result, Err(UndulerError::UnknownBumper(name)) if name == "calver"));
+
+        let parser = create_parser_with(&config("[parser]\nname = \"audit\"\n"), &Audit);
+        assert_eq!(parser.unwrap().name(), "audit");
+    }
+
+    #[test]
+    fn test_pipeline_with_unknown_plugins() {
+        let result = create_pipeline_with(
+            &config("[hooks]\npre_tag = [\"audit\", \"missing\"]\n"),
+            &Audit,
+        );
+        assert!(matches!(result, Err(UndulerError::UnknownHook(name)) if name == "missing"));
+
+        // Built-in hooks that are not enabled are unknown too
+        let result = create_pipeline(&config("[hooks]\npre_tag = [\"sbom\"]\n"));
+        assert!(matches!(result, Err(UndulerError::UnknownHook(name)) if name == "sbom"));
+
+        let result = create_parser_with(&config("[parser]\nname = \"missing\"\n"), &Audit);
+        assert!(matches!(result, Err(UndulerError::UnknownParser(name)) if name == "missing"));
+        let result = create_parser(&config("[parser]\nnames = [\"gitmoji\", \"audit\"]\n"));
+        assert!(matches!(result, Err(UndulerError::UnknownParser(name)) if name == "audit"));
+    }
+
+    #[test]
+    fn test_notify_hook_skips_channels_without_url() {
+        let hook = create_notify_hook(&config(
+            "[[plugins.notify.channels]]\nkind = \"teams\"\nwebhook_url_env = \"UNDULER_TEST_UNSET_WEBHOOK\"\n",
+        ));
+        assert!(hook.channels().is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_with_jvm_publish() {
+        let pipeline = create_pipeline(&config("[plugins.jvm]\npublish = true\n")).unwrap();
+        assert_eq!(pipeline.hooks().len(), 1);
+        assert_eq!(pipeline.hooks()[0].name(), "jvm");
+
+        let hook = create_jvm_hook(&config("[plugins.jvm]\ntool = \"maven\"\n"));
+        assert_eq!(hook.tool(), Some(BuildTool::Maven));
+    }
+
+    #[test]
+    fn test_pipeline_with_oci_repository() {
+        assert!(create_oci_hook(&Config::default()).is_none());
+
+        let pipeline = create_pipeline(&config(
+            "[plugins.oci]\nrepository = \"ghcr.io/acme/app\"\n",
+        ))
+        .unwrap();
+        assert_eq!(pipeline.hooks().len(), 1);
+        assert_eq!(pipeline.hooks()[0].name(), "oci");
+    }
+
+    #[test]
+    fn test_hooks_render_the_tag_format() {
+        let config = config(
+            r#"
+            [version]
+            tag_format = "{package}/{major}.{minor}"
+            package = "web"
+
+            [plugins.oci]
+            repository = "ghcr.io/acme/web"
+            tags = ["{tag}"]
+            "#,
+        );
+        assert_eq!(tag_template(&config), "web/{major}.{minor}");
+
+        let ctx = unduler_plugin::ReleaseContext::new(
+            PathBuf::from("."),
+            semver::Version::new(1, 1, 0),
+            semver::Version::new(1, 2, 0),
+            BumpType::Minor,
+            Vec::new(),
+        );
+        let args = create_oci_hook(&config).unwrap().push_args(&ctx).unwrap();
+        assert_eq!(args[1], "ghcr.io/acme/web:web/1.2");
+    }
+
+    #[test]
+    fn test_jira_hook_without_token() {
+        assert!(create_jira_hook(&Config::default()).is_none());
+        assert!(
+            create_jira_hook(&config(
+                "[plugins.jira]\nbase_url = \"https://jira.test\"\ntoken_env = \"UNDULER_TEST_UNSET_TOKEN\"\n",
+            ))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_sentry_hook_without_token() {
+        assert!(create_sentry_hook(&Config::default()).is_none());
+        assert!(
+            create_sentry_hook(&config(
+                "[plugins.sentry]\norg = \"acme\"\ntoken_env = \"UNDULER_TEST_UNSET_TOKEN\"\n",
+            ))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_with_sbom() {
+        let pipeline = create_pipeline(&config("[plugins.sbom]\nenabled = true\n")).unwrap();
+        assert_eq!(pipeline.hooks().len(), 1);
+        assert_eq!(pipeline.hooks()[0].name(), "sbom");
+
+        let hook = create_sbom_hook(&config(
+            "[plugins.sbom]\nformats = [\"spdx\"]\nprovenance = false\n",
+        ));
+        assert_eq!(hook.formats(), [unduler_hook_sbom::SbomFormat::Spdx]);
+        assert!(!hook.provenance());
+    }
+
+    #[test]
+    fn test_milestone_hook_without_token() {
+        assert!(create_milestone_hook(&Config::default()).is_none());
+        assert!(
+            create_milestone_hook(&config(
+                "[plugins.milestone]\nrepository = \"acme/app\"\ntoken_env = \"UNDULER_TEST_UNSET_TOKEN\"\n",
+            ))
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_with_webhook_targets() {
+        let pipeline = create_pipeline(&config(
+            "[[plugins.webhook.targets]]\nurl = \"https://hooks.test\"\n",
+        ))
+        .unwrap();
+        assert_eq!(pipeline.async_hooks().len(), 1);
+        assert_eq!(pipeline.async_hooks()[0].name(), "webhook");
+    }
+
+    #[test]
+    fn test_webhook_hook_stages_and_headers() {
+        let hook = create_webhook_hook(&config(
+            r#"
+            [[plugins.webhook.targets]]
+            url = "https://hooks.test"
+            stages = ["pre_tag"]
+            headers = { X-Source = "unduler" }
+            secret_headers = { Authorization = "PATH" }
+            "#,
+        ))
+        .unwrap();
+        let webhook = &hook.webhooks()[0];
+        assert_eq!(webhook.stages(), &[HookStage::PreTag]);
+        assert_eq!(
+            webhook.headers()[0],
+            ("X-Source".to_string(), "unduler".to_string())
+        );
+        assert_eq!(webhook.headers()[1].0, "Authorization");
+    }
+
+    #[test]
+    fn test_webhook_hook_unset_secret_header() {
+        let result = create_webhook_hook(&config(
+            r#"
+            [[plugins.webhook.targets]]
+            url = "https://hooks.test"
+            secret_headers = { Authorization = "UNDULER_TEST_UNSET_TOKEN" }
+            "#,
+        ));
+        assert!(matches!(
+            result,
+            Err(UndulerError::MissingSecretHeader { header, var, .. })
+                if header == "Authorization" && var == "UNDULER_TEST_UNSET_TOKEN"
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+//! A project released with Unduler.
+
+use std::path::Path;
+
+use semver::Version;
+use unduler_commit::ParsedCommit;
+use unduler_config::{Config, find_and_load_config, find_and_load_config_from};
+use unduler_core::{Pipeline, ReleaseManager, ReleaseOptions, ReleaseOutcome, ReleasePlan};
+use unduler_git::Repository;
+use unduler_plugin::{AsyncReleaseHook, BumpType, ReleaseHook};
+
+use crate::{UndulerResult, create_pipeline};
+
+/// A git repository along with its configuration and plugins.
+///
+/// Version files and the changelog are resolved relative to the current
+/// directory, as with the CLI.
+pub struct Project {
+    config: Config,
+    manager: ReleaseManager,
+    pipeline: Pipeline,
+}
+
+impl Project {
+    /// Opens the project of the current directory.
+    ///
+    /// The repository is discovered from the current directory and the
+    /// configuration is looked up in it or its parents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no repository or configuration is found.
+    pub fn discover() -> UndulerResult<Self> {
+        let config = find_and_load_config()?;
+        let repo = Repository::discover()?;
+        Self::new(repo, config)
+    }
+
+    /// Opens the project at a repository root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a repository or no configuration
+    /// is found.
+    pub fn open(path: impl AsRef<Path>) -> UndulerResult<Self> {
+        let path = path.as_ref();
+        let config = find_and_load_config_from(path)?;
+        let repo = Repository::open(path)?;
+        Self::new(repo, config)
+    }
+
+    /// Creates a project from a repository and a configuration, using the
+    /// built-in plugins the configuration selects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a changelog formatter cannot be created.
+    pub fn new(repo: Repository, config: Config) -> UndulerResult<Self> {
+        let pipeline = create_pipeline(&config)?;
+        let manager = ReleaseManager::from_config(repo, &config);
+        Ok(Self {
+            config,
+            manager,
+            pipeline,
+        })
+    }
+
+    /// Replaces the plugin pipeline.
+    #[must_use]
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Adds a release hook.
+    #[must_use]
+    pub fn with_hook(mut self, hook: Box<dyn ReleaseHook>) -> Self {
+        self.pipeline = self.pipeline.with_hook(hook);
+        self
+    }
+
+    /// Adds an asynchronous release hook.
+    #[must_use]
+    pub fn with_async_hook(mut self, hook: Box<dyn AsyncReleaseHook>) -> Self {
+        self.pipeline = self.pipeline.with_async_hook(hook);
+        self
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the release manager.
+    #[must_use]
+    pub fn manager(&self) -> &ReleaseManager {
+        &self.manager
+    }
+
+    /// Returns the plugin pipeline.
+    #[must_use]
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Returns the commits since the latest release that the parser accepts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tags or commits cannot be read.
+    pub fn commits(&self) -> UndulerResult<Vec<ParsedCommit>> {
+        let latest_tag = self.manager.latest_tag()?.map(|(tag, _)| tag);
+        let raw_commits = self.manager.commits_since(latest_tag.as_deref())?;
+        Ok(self.pipeline.parse_commits(&raw_commits))
+    }
+
+    /// Analyzes the commits since the latest release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is nothing to release or the repository
+    /// cannot be read.
+    pub fn plan(&self) -> UndulerResult<ReleasePlan> {
+        Ok(self.manager.plan(&self.pipeline, None)?)
+    }
+
+    /// Analyzes the commits since the latest release with a forced bump type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be read.
+    pub fn plan_with(&self, bump_type: BumpType) -> UndulerResult<ReleasePlan> {
+        Ok(self.manager.plan(&self.pipeline, Some(bump_type))?)
+    }
+
+    /// Returns the next version.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is nothing to release or the repository
+    /// cannot be read.
+    pub fn next_version(&self) -> UndulerResult<Version> {
+        Ok(self.plan()?.new_version)
+    }
+
+    /// Renders the changelog section of a planned release without writing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section cannot be rendered.
+    pub fn changelog(&self, plan: &ReleasePlan) -> UndulerResult<String> {
+        Ok(self.manager.write_changelog(&self.pipeline, plan, true)?)
+    }
+
+    /// Runs a release, calling the pipeline hooks between steps.
+    ///
+    /// Use [`release_async`](Self::release_async) from asynchronous code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub fn release(&self, options: &ReleaseOptions) -> UndulerResult<ReleaseOutcome> {
+        Ok(self.manager.release(&self.pipeline, options)?)
+    }
+
+    /// Runs a release from asynchronous code.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release fails.
+    pub async fn release_async(&self, options: &ReleaseOptions) -> UndulerResult<ReleaseOutcome> {
+        Ok(self.manager.release_async(&self.pipeline, options).await?)
+    }
+}
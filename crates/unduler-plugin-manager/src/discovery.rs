@@ -3,9 +3,19 @@
 //! Plugins are distributed as:
 //! 1. Rust crates on crates.io (for source and metadata)
 //! 2. Pre-compiled WASM on GitHub Releases (for runtime)
+//!
+//! Plugins can also come from a private registry with the crates.io API, a
+//! static JSON index listing the URLs of their WASM, or a direct URL.
+
+use std::collections::HashMap;
+use std::time::Duration;
 
+use reqwest::header::{AUTHORIZATION, RETRY_AFTER};
 use serde::Deserialize;
 
+use crate::http::HttpOptions;
+use crate::lockfile::{LockedPlugin, digest};
+use crate::source::{CRATES_IO, PluginSource};
 use crate::storage::{PluginStorage, PluginType};
 
 /// Search response from crates.io API.
@@ -24,6 +34,70 @@ struct SearchCrate {
 }
 use crate::{InstalledPlugin, PluginManagerError, PluginManagerResult, PluginRegistry};
 
+/// Longest `Retry-After` delay followed before retrying a request.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Static plugin index.
+///
+/// ```json
+/// {
+///   "plugins": {
+///     "unduler-parser-acme": {
+///       "description": "Parser of the ACME commit convention",
+///       "versions": {
+///         "1.0.0": {
+///           "url": "unduler-parser-acme-1.0.0.wasm",
+///           "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+///         }
+///       }
+///     }
+///   }
+/// }
+/// ```
+///
+/// WASM URLs are relative to the index URL unless absolute. A version can
+/// also map to its URL alone, for local WASM needing no digest.
+#[derive(Debug, Deserialize)]
+struct PluginIndex {
+    #[serde(default)]
+    plugins: HashMap<String, IndexEntry>,
+}
+
+/// Plugin listed in a static index.
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    description: Option<String>,
+    repository: Option<String>,
+    /// WASM by version.
+    #[serde(default)]
+    versions: HashMap<String, IndexArtifact>,
+}
+
+/// WASM of a plugin version listed in a static index.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum IndexArtifact {
+    /// URL of the WASM.
+    Url(String),
+    /// URL of the WASM with its SHA-256 digest.
+    Verified { url: String, sha256: String },
+}
+
+impl IndexArtifact {
+    fn url(&self) -> &str {
+        match self {
+            Self::Url(url) | Self::Verified { url, .. } => url,
+        }
+    }
+
+    fn sha256(&self) -> Option<&str> {
+        match self {
+            Self::Url(_) => None,
+            Self::Verified { sha256, .. } => Some(sha256),
+        }
+    }
+}
+
 /// Crates.io API response for crate metadata.
 #[derive(Debug, Deserialize)]
 struct CratesIoResponse {
@@ -35,8 +109,6 @@ struct CratesIoResponse {
 /// Crate metadata from crates.io.
 #[derive(Debug, Deserialize)]
 struct CrateInfo {
-    #[allow(dead_code)]
-    name: String,
     description: Option<String>,
     repository: Option<String>,
     max_stable_version: Option<String>,
@@ -49,19 +121,30 @@ struct VersionInfo {
     yanked: bool,
 }
 
-/// GitHub release response.
-#[derive(Debug, Deserialize)]
-struct GitHubRelease {
-    #[allow(dead_code)]
-    tag_name: String,
-    assets: Vec<GitHubAsset>,
+/// GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubRelease {
+    /// Tag of the release.
+    pub tag_name: String,
+    /// Files attached to the release.
+    pub assets: Vec<GitHubAsset>,
 }
 
-/// GitHub release asset.
-#[derive(Debug, Deserialize)]
-struct GitHubAsset {
-    name: String,
-    browser_download_url: String,
+impl GitHubRelease {
+    /// Finds an asset by file name.
+    #[must_use]
+    pub fn asset(&self, name: &str) -> Option<&GitHubAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// File attached to a GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubAsset {
+    /// File name.
+    pub name: String,
+    /// Download URL.
+    pub browser_download_url: String,
 }
 
 /// Plugin metadata discovered from crates.io.
@@ -81,59 +164,121 @@ pub struct PluginMetadata {
     pub description: Option<String>,
     /// Repository URL.
     pub repository: Option<String>,
+    /// WASM URLs by version, for plugins listed in a static index.
+    pub artifacts: HashMap<semver::Version, String>,
+    /// SHA-256 digests of the WASM by version, for index entries listing one.
+    pub checksums: HashMap<semver::Version, String>,
 }
 
 /// Plugin discovery and download service.
 pub struct PluginDiscovery {
     client: reqwest::Client,
+    source: PluginSource,
+    http: HttpOptions,
 }
 
 impl PluginDiscovery {
-    /// Creates a new plugin discovery instance.
+    /// Creates a new plugin discovery instance, authenticating GitHub API
+    /// requests with `GITHUB_TOKEN` or `GH_TOKEN`.
     ///
     /// # Panics
     ///
     /// Panics if the HTTP client cannot be built.
     #[must_use]
     pub fn new() -> Self {
-        let client = reqwest::Client::builder()
+        Self::with_http(HttpOptions::from_env()).expect("failed to build HTTP client")
+    }
+
+    /// Creates a plugin discovery instance with HTTP settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the proxy URL is invalid or the HTTP client
+    /// cannot be built.
+    pub fn with_http(http: HttpOptions) -> PluginManagerResult<Self> {
+        let mut builder = reqwest::Client::builder()
             .user_agent(concat!(
                 env!("CARGO_PKG_NAME"),
                 "/",
                 env!("CARGO_PKG_VERSION")
             ))
-            .build()
-            .expect("failed to build HTTP client");
+            .timeout(http.timeout());
+        if let Some(url) = http.proxy() {
+            let proxy = reqwest::Proxy::all(url).map_err(|e| PluginManagerError::InvalidProxy {
+                url: url.to_string(),
+                source: e,
+            })?;
+            builder = builder.proxy(proxy);
+        }
+        let client = builder.build().map_err(PluginManagerError::HttpClient)?;
+
+        Ok(Self {
+            client,
+            source: PluginSource::default(),
+            http,
+        })
+    }
+
+    /// Sets the source plugins are discovered from, instead of crates.io.
+    #[must_use]
+    pub fn with_source(mut self, source: PluginSource) -> Self {
+        self.source = source;
+        self
+    }
 
-        Self { client }
+    /// Returns the source plugins are discovered from.
+    #[must_use]
+    pub fn source(&self) -> &PluginSource {
+        &self.source
     }
 
-    /// Fetches plugin metadata from crates.io.
+    /// Fetches plugin metadata from the source.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - The crate name is not a valid plugin name
-    /// - The crate cannot be found on crates.io
+    /// - The plugin cannot be found in the source
+    /// - The source is a direct URL, which has no metadata
     /// - The network request fails
     pub async fn fetch_metadata(&self, crate_name: &str) -> PluginManagerResult<PluginMetadata> {
+        match &self.source {
+            PluginSource::Registry { url } => self.fetch_registry_metadata(url, crate_name).await,
+            PluginSource::Index { url } => self.fetch_index_metadata(url, crate_name).await,
+            PluginSource::Url { .. } => Err(PluginManagerError::InvalidMetadata {
+                name: crate_name.to_string(),
+                reason: "plugins installed from a URL have no metadata".to_string(),
+            }),
+        }
+    }
+
+    /// Fetches plugin metadata from a registry with the crates.io API.
+    async fn fetch_registry_metadata(
+        &self,
+        registry: &str,
+        crate_name: &str,
+    ) -> PluginManagerResult<PluginMetadata> {
         let (plugin_type, short_name) = PluginStorage::parse_crate_name(crate_name)?;
 
-        let url = format!("https://crates.io/api/v1/crates/{crate_name}");
+        let url = format!("{registry}/api/v1/crates/{crate_name}");
 
-        let response =
-            self.client
-                .get(&url)
-                .send()
-                .await
-                .map_err(|e| PluginManagerError::CratesIoFetch {
-                    name: crate_name.to_string(),
-                    source: e,
-                })?;
+        let response = self.send(self.client.get(&url)).await.map_err(|e| {
+            request_error(e, |e| PluginManagerError::CratesIoFetch {
+                name: crate_name.to_string(),
+                source: e,
+            })
+        })?;
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return Err(PluginManagerError::CrateNotFound {
-                name: crate_name.to_string(),
+            return Err(if registry == CRATES_IO {
+                PluginManagerError::CrateNotFound {
+                    name: crate_name.to_string(),
+                }
+            } else {
+                PluginManagerError::NotFoundIn {
+                    name: crate_name.to_string(),
+                    location: registry.to_string(),
+                }
             });
         }
 
@@ -176,10 +321,144 @@ impl PluginDiscovery {
             versions,
             description: data.krate.description,
             repository: data.krate.repository,
+            artifacts: HashMap::new(),
+            checksums: HashMap::new(),
+        })
+    }
+
+    /// Fetches plugin metadata from a static index.
+    async fn fetch_index_metadata(
+        &self,
+        index_url: &str,
+        crate_name: &str,
+    ) -> PluginManagerResult<PluginMetadata> {
+        let (plugin_type, short_name) = PluginStorage::parse_crate_name(crate_name)?;
+
+        let mut index = self.fetch_index(index_url).await?;
+        let entry =
+            index
+                .plugins
+                .remove(crate_name)
+                .ok_or_else(|| PluginManagerError::NotFoundIn {
+                    name: crate_name.to_string(),
+                    location: index_url.to_string(),
+                })?;
+
+        let mut artifacts = HashMap::new();
+        let mut checksums = HashMap::new();
+        for (version, artifact) in &entry.versions {
+            let Ok(version) = version.parse::<semver::Version>() else {
+                continue;
+            };
+            if let Some(sha256) = artifact.sha256() {
+                checksums.insert(version.clone(), sha256.to_string());
+            }
+            artifacts.insert(version, resolve_url(index_url, artifact.url()));
+        }
+
+        let mut versions: Vec<semver::Version> = artifacts.keys().cloned().collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+
+        let latest_version = versions
+            .iter()
+            .find(|v| v.pre.is_empty())
+            .or_else(|| versions.first())
+            .cloned()
+            .ok_or_else(|| PluginManagerError::InvalidMetadata {
+                name: crate_name.to_string(),
+                reason: "no versions available".to_string(),
+            })?;
+
+        Ok(PluginMetadata {
+            crate_name: crate_name.to_string(),
+            plugin_type,
+            short_name,
+            latest_version,
+            versions,
+            description: entry.description,
+            repository: entry.repository,
+            artifacts,
+            checksums,
+        })
+    }
+
+    /// Reads a static index.
+    async fn fetch_index(&self, index_url: &str) -> PluginManagerResult<PluginIndex> {
+        let bytes = self.fetch("plugin index", index_url).await?;
+        serde_json::from_slice(&bytes).map_err(|e| PluginManagerError::InvalidIndex {
+            url: index_url.to_string(),
+            reason: e.to_string(),
         })
     }
 
-    /// Downloads a plugin WASM from GitHub Releases.
+    /// Downloads a file over HTTPS, or reads it from the local file system
+    /// for paths and `file://` URLs.
+    ///
+    /// `name` identifies the download in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is plain HTTP, the download fails or the
+    /// file cannot be read.
+    pub async fn fetch(&self, name: &str, url: &str) -> PluginManagerResult<Vec<u8>> {
+        if !is_remote(url) {
+            let path = url.strip_prefix("file://").unwrap_or(url);
+            return Ok(std::fs::read(path)?);
+        }
+        if !url.starts_with("https://") {
+            return Err(PluginManagerError::InsecureUrl {
+                url: url.to_string(),
+            });
+        }
+
+        let failed = |source| PluginManagerError::DownloadFailed {
+            name: name.to_string(),
+            url: url.to_string(),
+            source,
+        };
+        let bytes = self
+            .send(self.client.get(url))
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| request_error(e, failed))?
+            .bytes()
+            .await
+            .map_err(|e| request_error(e, failed))?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Sends a request, retrying on network errors, rate limits and server
+    /// errors.
+    ///
+    /// Returns the last response or error once the retries are exhausted.
+    async fn send(&self, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+        let mut attempt = 0;
+        loop {
+            let result = request
+                .try_clone()
+                .expect("requests without streamed body can be cloned")
+                .send()
+                .await;
+
+            let retry_after = match &result {
+                Ok(response) if is_transient(response.status()) => retry_after(response),
+                Err(e) if e.is_connect() || e.is_timeout() => None,
+                _ => return result,
+            };
+            if attempt >= self.http.retries() {
+                return result;
+            }
+
+            let delay = self.http.retry_delay(attempt, retry_after);
+            tracing::debug!("Request failed, retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Downloads a plugin WASM from its index URL, or else from GitHub
+    /// Releases.
     ///
     /// Expects the release to have a `<crate-name>.wasm` asset.
     ///
@@ -195,6 +474,16 @@ impl PluginDiscovery {
         metadata: &PluginMetadata,
         version: &semver::Version,
     ) -> PluginManagerResult<Vec<u8>> {
+        if let Some(url) = metadata.artifacts.get(version) {
+            return self.fetch(&metadata.crate_name, url).await;
+        }
+        if !matches!(self.source, PluginSource::Registry { .. }) {
+            return Err(PluginManagerError::ReleaseNotFound {
+                name: metadata.crate_name.clone(),
+                version: version.to_string(),
+            });
+        }
+
         let repo_url =
             metadata
                 .repository
@@ -225,7 +514,12 @@ impl PluginDiscovery {
                 .await
             {
                 Ok(bytes) => return Ok(bytes),
-                Err(e) => last_error = Some(e),
+                Err(
+                    e @ (PluginManagerError::ReleaseNotFound { .. }
+                    | PluginManagerError::WasmAssetNotFound { .. }),
+                ) => last_error = Some(e),
+                // Other tags fail the same way
+                Err(e) => return Err(e),
             }
         }
 
@@ -237,88 +531,103 @@ impl PluginDiscovery {
         )
     }
 
-    /// Attempts to download a WASM asset from a specific release.
-    async fn try_download_release(
+    /// Fetches a GitHub release by tag, or the latest release without tag.
+    ///
+    /// `name` identifies the release in errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the release cannot be found, the GitHub API rate
+    /// limit is exhausted, or the request fails.
+    pub async fn github_release(
         &self,
+        name: &str,
         owner: &str,
         repo: &str,
-        tag: &str,
-        metadata: &PluginMetadata,
-    ) -> PluginManagerResult<Vec<u8>> {
-        let url = format!("https://api.github.com/repos/{owner}/{repo}/releases/tags/{tag}");
+        tag: Option<&str>,
+    ) -> PluginManagerResult<GitHubRelease> {
+        let url = match tag {
+            Some(tag) => format!("https://api.github.com/repos/{owner}/{repo}/releases/tags/{tag}"),
+            None => format!("https://api.github.com/repos/{owner}/{repo}/releases/latest"),
+        };
 
-        let response = self
+        let mut request = self
             .client
             .get(&url)
-            .header("Accept", "application/vnd.github.v3+json")
-            .send()
-            .await
-            .map_err(|e| PluginManagerError::DownloadFailed {
-                name: metadata.crate_name.clone(),
+            .header("Accept", "application/vnd.github.v3+json");
+        if let Some(token) = self.http.github_token() {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let response = self.send(request).await.map_err(|e| {
+            request_error(e, |e| PluginManagerError::DownloadFailed {
+                name: name.to_string(),
                 url: url.clone(),
                 source: e,
-            })?;
+            })
+        })?;
 
+        if is_rate_limited(&response) {
+            return Err(PluginManagerError::RateLimited { url });
+        }
         if !response.status().is_success() {
             return Err(PluginManagerError::ReleaseNotFound {
-                name: metadata.crate_name.clone(),
-                version: tag.to_string(),
+                name: name.to_string(),
+                version: tag.unwrap_or("latest").to_string(),
             });
         }
 
-        let release: GitHubRelease =
-            response
-                .json()
-                .await
-                .map_err(|e| PluginManagerError::DownloadFailed {
-                    name: metadata.crate_name.clone(),
-                    url: url.clone(),
-                    source: e,
-                })?;
+        response
+            .json()
+            .await
+            .map_err(|e| PluginManagerError::DownloadFailed {
+                name: name.to_string(),
+                url,
+                source: e,
+            })
+    }
+
+    /// Attempts to download a WASM asset from a specific release.
+    async fn try_download_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+        metadata: &PluginMetadata,
+    ) -> PluginManagerResult<Vec<u8>> {
+        let release = self
+            .github_release(&metadata.crate_name, owner, repo, Some(tag))
+            .await?;
 
         // Look for WASM asset
         let wasm_name = format!("{}.wasm", metadata.crate_name);
-        let asset = release
-            .assets
-            .iter()
-            .find(|a| a.name == wasm_name)
-            .ok_or_else(|| PluginManagerError::WasmAssetNotFound {
-                name: metadata.crate_name.clone(),
-                version: tag.to_string(),
-            })?;
+        let asset =
+            release
+                .asset(&wasm_name)
+                .ok_or_else(|| PluginManagerError::WasmAssetNotFound {
+                    name: metadata.crate_name.clone(),
+                    version: tag.to_string(),
+                })?;
 
         // Download the WASM file
-        let bytes = self
-            .client
-            .get(&asset.browser_download_url)
-            .send()
-            .await
-            .map_err(|e| PluginManagerError::DownloadFailed {
-                name: metadata.crate_name.clone(),
-                url: asset.browser_download_url.clone(),
-                source: e,
-            })?
-            .bytes()
+        self.fetch(&metadata.crate_name, &asset.browser_download_url)
             .await
-            .map_err(|e| PluginManagerError::DownloadFailed {
-                name: metadata.crate_name.clone(),
-                url: asset.browser_download_url.clone(),
-                source: e,
-            })?;
-
-        Ok(bytes.to_vec())
     }
 
     /// Installs a plugin.
     ///
+    /// The WASM is checked against `sha256`, or else the digest listed in
+    /// the index. WASM downloaded from an index needs one of them.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the plugin cannot be fetched or installed.
+    /// Returns an error if the plugin cannot be fetched or installed, or its
+    /// WASM does not match the expected digest.
     pub async fn install(
         &self,
         registry: &mut PluginRegistry,
         crate_name: &str,
         version: Option<&semver::Version>,
+        sha256: Option<&str>,
     ) -> PluginManagerResult<InstalledPlugin> {
         let metadata = self.fetch_metadata(crate_name).await?;
 
@@ -339,17 +648,14 @@ impl PluginDiscovery {
         tracing::info!("Downloading {} v{}", crate_name, version);
 
         let wasm_bytes = self.download_wasm(&metadata, &version).await?;
-
-        // Save to storage
-        let storage = registry.storage();
-        storage.save_plugin(
-            &metadata.short_name,
-            metadata.plugin_type,
+        verify_digest(
+            crate_name,
             &version,
+            metadata.artifacts.get(&version).map(String::as_str),
+            sha256.or_else(|| metadata.checksums.get(&version).map(String::as_str)),
             &wasm_bytes,
         )?;
 
-        // Register in registry
         let plugin = InstalledPlugin {
             crate_name: crate_name.to_string(),
             plugin_type: metadata.plugin_type,
@@ -357,10 +663,130 @@ impl PluginDiscovery {
             version,
             description: metadata.description,
             repository: metadata.repository,
+            source: self.source.clone(),
+            installed_at: chrono::Utc::now(),
+        };
+        Self::save(registry, plugin, &wasm_bytes)
+    }
+
+    /// Installs a plugin from the direct URL of its WASM.
+    ///
+    /// The URL has no metadata, so the version is given and the plugin is
+    /// only updated by installing it again. The WASM is checked against
+    /// `sha256`, which is required unless the URL is a local file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crate name is not a valid plugin name, the
+    /// plugin cannot be downloaded or installed, or its WASM does not match
+    /// the expected digest.
+    pub async fn install_from_url(
+        &self,
+        registry: &mut PluginRegistry,
+        crate_name: &str,
+        version: &semver::Version,
+        url: &str,
+        sha256: Option<&str>,
+    ) -> PluginManagerResult<InstalledPlugin> {
+        let (plugin_type, short_name) = PluginStorage::parse_crate_name(crate_name)?;
+
+        if let Some(existing) = registry.get(crate_name)
+            && existing.version == *version
+        {
+            return Err(PluginManagerError::AlreadyInstalled {
+                name: crate_name.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        tracing::info!("Downloading {} v{} from {}", crate_name, version, url);
+
+        let wasm_bytes = self.fetch(crate_name, url).await?;
+        verify_digest(crate_name, version, Some(url), sha256, &wasm_bytes)?;
+
+        let plugin = InstalledPlugin {
+            crate_name: crate_name.to_string(),
+            plugin_type,
+            short_name,
+            version: version.clone(),
+            description: None,
+            repository: None,
+            source: PluginSource::url(url),
+            installed_at: chrono::Utc::now(),
+        };
+        Self::save(registry, plugin, &wasm_bytes)
+    }
+
+    /// Installs a plugin pinned in a lockfile from its locked source,
+    /// whatever the source of this instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin cannot be downloaded or installed, or
+    /// its WASM does not match the locked digest.
+    pub async fn install_locked(
+        &self,
+        registry: &mut PluginRegistry,
+        locked: &LockedPlugin,
+    ) -> PluginManagerResult<InstalledPlugin> {
+        let (plugin_type, short_name) = PluginStorage::parse_crate_name(&locked.name)?;
+
+        tracing::info!(
+            "Downloading {} v{} from {}",
+            locked.name,
+            locked.version,
+            locked.source
+        );
+
+        let (wasm_bytes, description, repository) =
+            if let PluginSource::Url { url } = &locked.source {
+                (self.fetch(&locked.name, url).await?, None, None)
+            } else {
+                let discovery = Self {
+                    client: self.client.clone(),
+                    source: locked.source.clone(),
+                    http: self.http.clone(),
+                };
+                let metadata = discovery.fetch_metadata(&locked.name).await?;
+                let wasm_bytes = discovery.download_wasm(&metadata, &locked.version).await?;
+                (wasm_bytes, metadata.description, metadata.repository)
+            };
+
+        verify_digest(
+            &locked.name,
+            &locked.version,
+            None,
+            Some(&locked.digest),
+            &wasm_bytes,
+        )?;
+
+        let plugin = InstalledPlugin {
+            crate_name: locked.name.clone(),
+            plugin_type,
+            short_name,
+            version: locked.version.clone(),
+            description,
+            repository,
+            source: locked.source.clone(),
             installed_at: chrono::Utc::now(),
         };
+        Self::save(registry, plugin, &wasm_bytes)
+    }
+
+    /// Saves a downloaded plugin to storage and registers it.
+    fn save(
+        registry: &mut PluginRegistry,
+        plugin: InstalledPlugin,
+        wasm_bytes: &[u8],
+    ) -> PluginManagerResult<InstalledPlugin> {
+        registry.storage().save_plugin(
+            &plugin.short_name,
+            plugin.plugin_type,
+            &plugin.version,
+            wasm_bytes,
+        )?;
 
-        if registry.is_installed(crate_name) {
+        if registry.is_installed(&plugin.crate_name) {
             registry.upgrade(plugin.clone())?;
         } else {
             registry.register(plugin.clone())?;
@@ -394,23 +820,30 @@ impl PluginDiscovery {
         Ok(())
     }
 
-    /// Searches for plugins on crates.io.
+    /// Searches for plugins in the source.
     ///
     /// # Errors
     ///
-    /// Returns an error if the search fails.
+    /// Returns an error if the search fails, or the source is a direct URL.
     pub async fn search(&self, query: &str) -> PluginManagerResult<Vec<SearchResult>> {
-        let url = format!("https://crates.io/api/v1/crates?q=unduler-{query}&per_page=20");
+        let registry = match &self.source {
+            PluginSource::Registry { url } => url,
+            PluginSource::Index { url } => return self.search_index(url, query).await,
+            PluginSource::Url { url } => {
+                return Err(PluginManagerError::InvalidIndex {
+                    url: url.clone(),
+                    reason: "a direct URL cannot be searched".to_string(),
+                });
+            }
+        };
+        let url = format!("{registry}/api/v1/crates?q=unduler-{query}&per_page=20");
 
-        let response =
-            self.client
-                .get(&url)
-                .send()
-                .await
-                .map_err(|e| PluginManagerError::CratesIoFetch {
-                    name: query.to_string(),
-                    source: e,
-                })?;
+        let response = self.send(self.client.get(&url)).await.map_err(|e| {
+            request_error(e, |e| PluginManagerError::CratesIoFetch {
+                name: query.to_string(),
+                source: e,
+            })
+        })?;
 
         let data: SearchResponse =
             response
@@ -440,6 +873,41 @@ impl PluginDiscovery {
 
         Ok(results)
     }
+
+    /// Searches for plugins whose name contains the query in a static index.
+    async fn search_index(
+        &self,
+        index_url: &str,
+        query: &str,
+    ) -> PluginManagerResult<Vec<SearchResult>> {
+        let index = self.fetch_index(index_url).await?;
+
+        let mut results: Vec<SearchResult> = index
+            .plugins
+            .into_iter()
+            .filter(|(name, _)| name.contains(query))
+            .filter_map(|(name, entry)| {
+                let (plugin_type, short_name) = PluginStorage::parse_crate_name(&name).ok()?;
+                let latest_version = entry
+                    .versions
+                    .keys()
+                    .filter_map(|v| v.parse::<semver::Version>().ok())
+                    .max()
+                    .map(|v| v.to_string());
+                Some(SearchResult {
+                    crate_name: name,
+                    plugin_type,
+                    short_name,
+                    description: entry.description,
+                    latest_version,
+                    downloads: 0,
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| a.crate_name.cmp(&b.crate_name));
+
+        Ok(results)
+    }
 }
 
 impl Default for PluginDiscovery {
@@ -465,6 +933,101 @@ pub struct SearchResult {
     pub downloads: u64,
 }
 
+/// Returns whether a response status is worth retrying.
+fn is_transient(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Returns the delay a response asks to wait before retrying.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    let delay = Duration::from_secs(seconds.trim().parse().ok()?);
+    Some(delay.min(MAX_RETRY_AFTER))
+}
+
+/// Returns whether a GitHub API response reports an exhausted rate limit.
+fn is_rate_limited(response: &reqwest::Response) -> bool {
+    matches!(
+        response.status(),
+        reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::TOO_MANY_REQUESTS
+    ) && response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .is_some_and(|remaining| remaining == "0")
+}
+
+/// Returns a clear error for a request that did not reach the server in
+/// time, or else the error built by `otherwise`.
+fn request_error(
+    e: reqwest::Error,
+    otherwise: impl FnOnce(reqwest::Error) -> PluginManagerError,
+) -> PluginManagerError {
+    let url = e.url().map_or_else(String::new, ToString::to_string);
+    if e.is_timeout() {
+        PluginManagerError::TimedOut { url }
+    } else if e.is_connect() {
+        PluginManagerError::Offline { url, source: e }
+    } else {
+        otherwise(e)
+    }
+}
+
+/// Returns whether a URL is downloaded over HTTP.
+/// Checks WASM against its expected SHA-256 digest, in hex with or without
+/// the `sha256:` prefix.
+///
+/// WASM downloaded from a remote `url` needs a digest.
+fn verify_digest(
+    name: &str,
+    version: &semver::Version,
+    url: Option<&str>,
+    expected: Option<&str>,
+    wasm_bytes: &[u8],
+) -> PluginManagerResult<()> {
+    let Some(expected) = expected else {
+        return match url {
+            Some(url) if is_remote(url) => Err(PluginManagerError::MissingDigest {
+                name: name.to_string(),
+                url: url.to_string(),
+            }),
+            _ => Ok(()),
+        };
+    };
+
+    let actual = digest(wasm_bytes);
+    let expected = format!(
+        "sha256:{}",
+        expected
+            .strip_prefix("sha256:")
+            .unwrap_or(expected)
+            .to_ascii_lowercase()
+    );
+    if actual != expected {
+        return Err(PluginManagerError::DigestMismatch {
+            name: name.to_string(),
+            version: version.to_string(),
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn is_remote(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Resolves a URL listed in an index relative to the index URL.
+fn resolve_url(index_url: &str, url: &str) -> String {
+    if url.contains("://") || url.starts_with('/') {
+        return url.to_string();
+    }
+    match index_url.rfind('/') {
+        Some(end) => format!("{}{url}", &index_url[..=end]),
+        None => url.to_string(),
+    }
+}
+
 /// Parses a GitHub URL into (owner, repo).
 fn parse_github_url(url: &str) -> Option<(String, String)> {
     let url = url.trim_end_matches('/');
@@ -491,6 +1054,7 @@ fn parse_github_url(url: &str) -> Option<(String, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use unduler_plugin::test_util::poll_once;
 
     #[test]
     fn test_parse_github_url_https() {
@@ -513,6 +1077,218 @@ mod tests {
         assert_eq!(repo, "unduler");
     }
 
+    #[test]
+    fn test_resolve_url() {
+        let index = "https://plugins.example.com/unduler/index.json";
+        assert_eq!(
+            resolve_url(index, "parser-acme.wasm"),
+            "https://plugins.example.com/unduler/parser-acme.wasm"
+        );
+        assert_eq!(
+            resolve_url(index, "https://cdn.example.com/parser-acme.wasm"),
+            "https://cdn.example.com/parser-acme.wasm"
+        );
+        assert_eq!(resolve_url("index.json", "acme.wasm"), "acme.wasm");
+        assert_eq!(
+            resolve_url("/srv/plugins/index.json", "acme.wasm"),
+            "/srv/plugins/acme.wasm"
+        );
+    }
+
+    fn write_index(dir: &std::path::Path) -> String {
+        std::fs::write(dir.join("unduler-parser-acme-1.1.0.wasm"), b"\0asm").unwrap();
+        let index = dir.join("index.json");
+        std::fs::write(
+            &index,
+            r#"{
+                "plugins": {
+                    "unduler-parser-acme": {
+                        "description": "ACME commits",
+                        "versions": {
+                            "1.0.0": "https://cdn.example.com/acme-1.0.0.wasm",
+                            "1.1.0": {
+                                "url": "unduler-parser-acme-1.1.0.wasm",
+                                "sha256": "cd5d4935a48c0672cb06407bb443bc0087aff947c6b864bac886982c73b3027f"
+                            },
+                            "2.0.0-beta.1": "acme-2.0.0-beta.1.wasm"
+                        }
+                    },
+                    "unduler-hook-acme": { "versions": {} }
+                }
+            }"#,
+        )
+        .unwrap();
+        index.display().to_string()
+    }
+
+    #[test]
+    fn test_fetch_index_metadata() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let index = write_index(dir.path());
+        let discovery = PluginDiscovery::new().with_source(PluginSource::index(&index));
+
+        let metadata = poll_once(discovery.fetch_metadata("unduler-parser-acme")).unwrap();
+        assert_eq!(metadata.short_name, "acme");
+        assert_eq!(metadata.latest_version, semver::Version::new(1, 1, 0));
+        assert_eq!(metadata.versions.len(), 3);
+        assert_eq!(metadata.description.as_deref(), Some("ACME commits"));
+
+        assert_eq!(
+            metadata.checksums[&metadata.latest_version],
+            digest(b"\0asm").trim_start_matches("sha256:")
+        );
+
+        let wasm = poll_once(discovery.download_wasm(&metadata, &metadata.latest_version));
+        assert_eq!(wasm.unwrap(), b"\0asm");
+    }
+
+    #[test]
+    fn test_fetch_insecure_url() {
+        let result = poll_once(PluginDiscovery::new().fetch("acme", "http://example.com/a.wasm"));
+        assert!(matches!(
+            result,
+            Err(PluginManagerError::InsecureUrl { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_digest() {
+        let version = semver::Version::new(1, 0, 0);
+        let url = Some("https://example.com/acme.wasm");
+        let sha256 = digest(b"\0asm");
+        let hex = sha256.trim_start_matches("sha256:").to_ascii_uppercase();
+
+        assert!(verify_digest("acme", &version, url, Some(&sha256), b"\0asm").is_ok());
+        assert!(verify_digest("acme", &version, url, Some(&hex), b"\0asm").is_ok());
+        assert!(verify_digest("acme", &version, Some("acme.wasm"), None, b"\0asm").is_ok());
+        assert!(matches!(
+            verify_digest("acme", &version, url, None, b"\0asm"),
+            Err(PluginManagerError::MissingDigest { .. })
+        ));
+        assert!(matches!(
+            verify_digest("acme", &version, url, Some(&sha256), b"other"),
+            Err(PluginManagerError::DigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_install_from_index_checks_digest() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = PluginStorage::with_base_dir(dir.path().join("home")).unwrap();
+        let mut registry = PluginRegistry::new(storage).unwrap();
+        let discovery =
+            PluginDiscovery::new().with_source(PluginSource::index(write_index(dir.path())));
+        let version = semver::Version::new(1, 1, 0);
+
+        let result = poll_once(discovery.install(
+            &mut registry,
+            "unduler-parser-acme",
+            Some(&version),
+            Some(&digest(b"other")),
+        ));
+        assert!(matches!(
+            result,
+            Err(PluginManagerError::DigestMismatch { .. })
+        ));
+
+        let plugin = poll_once(discovery.install(
+            &mut registry,
+            "unduler-parser-acme",
+            Some(&version),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(plugin.version, version);
+    }
+
+    #[test]
+    fn test_fetch_index_metadata_not_found() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let discovery =
+            PluginDiscovery::new().with_source(PluginSource::index(write_index(dir.path())));
+
+        let result = poll_once(discovery.fetch_metadata("unduler-parser-other"));
+        assert!(matches!(result, Err(PluginManagerError::NotFoundIn { .. })));
+
+        let result = poll_once(discovery.fetch_metadata("unduler-hook-acme"));
+        assert!(matches!(
+            result,
+            Err(PluginManagerError::InvalidMetadata { .. })
+        ));
+    }
+
+    #[test]
+    fn test_search_index() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let discovery =
+            PluginDiscovery::new().with_source(PluginSource::index(write_index(dir.path())));
+
+        let results = poll_once(discovery.search("acme")).unwrap();
+        let names: Vec<_> = results.iter().map(|r| r.crate_name.as_str()).collect();
+        assert_eq!(names, ["unduler-hook-acme", "unduler-parser-acme"]);
+        assert_eq!(results[1].latest_version.as_deref(), Some("2.0.0-beta.1"));
+    }
+
+    #[test]
+    fn test_install_locked() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = PluginStorage::with_base_dir(dir.path().join("home")).unwrap();
+        let mut registry = PluginRegistry::new(storage).unwrap();
+        let mut locked = LockedPlugin {
+            name: "unduler-parser-acme".to_string(),
+            version: semver::Version::new(1, 1, 0),
+            source: PluginSource::index(write_index(dir.path())),
+            digest: digest(b"\0asm"),
+        };
+        let discovery = PluginDiscovery::new();
+
+        let plugin = poll_once(discovery.install_locked(&mut registry, &locked)).unwrap();
+        assert_eq!(plugin.version, locked.version);
+        assert_eq!(plugin.source, locked.source);
+        assert_eq!(plugin.description.as_deref(), Some("ACME commits"));
+
+        locked.digest = digest(b"other");
+        let result = poll_once(discovery.install_locked(&mut registry, &locked));
+        assert!(matches!(
+            result,
+            Err(PluginManagerError::DigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_transient(reqwest::StatusCode::NOT_FOUND));
+        assert!(!is_transient(reqwest::StatusCode::OK));
+    }
+
+    #[tokio::test]
+    async fn test_offline_error() {
+        // Nothing listens on port 1, so connections are refused
+        let http = HttpOptions::new()
+            .with_retries(1)
+            .with_backoff(Duration::from_millis(1));
+        let discovery = PluginDiscovery::with_http(http)
+            .unwrap()
+            .with_source(PluginSource::registry("http://127.0.0.1:1"));
+
+        let result = discovery.fetch_metadata("unduler-parser-acme").await;
+        assert!(
+            matches!(result, Err(PluginManagerError::Offline { ref url, .. }) if url.starts_with("http://127.0.0.1:1/")),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_invalid_proxy() {
+        let http = HttpOptions::new().with_proxy(Some("not a url".to_string()));
+        assert!(matches!(
+            PluginDiscovery::with_http(http),
+            Err(PluginManagerError::InvalidProxy { .. })
+        ));
+    }
+
     #[test]
     fn test_parse_github_url_invalid() {
         assert!(parse_github_url("https://gitlab.com/foo/bar").is_none());
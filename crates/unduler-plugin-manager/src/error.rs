@@ -32,10 +32,46 @@ pub enum PluginManagerError {
         source: reqwest::Error,
     },
 
+    /// Invalid proxy URL.
+    #[error("invalid proxy URL: {url}")]
+    InvalidProxy {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// Failed to build the HTTP client.
+    #[error("failed to build HTTP client")]
+    HttpClient(#[source] reqwest::Error),
+
+    /// Server unreachable, e.g. without network.
+    #[error("cannot reach {url}: check the network connection, or the proxy set with HTTPS_PROXY")]
+    Offline {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// Request not answered before the timeout.
+    #[error("request to {url} timed out")]
+    TimedOut { url: String },
+
+    /// GitHub API rate limit exhausted.
+    #[error("GitHub API rate limit exceeded for {url}: set GITHUB_TOKEN to authenticate requests")]
+    RateLimited { url: String },
+
     /// Crate not found on crates.io.
     #[error("crate not found on crates.io: {name}")]
     CrateNotFound { name: String },
 
+    /// Plugin not found in a private registry or a static index.
+    #[error("plugin not found in {location}: {name}")]
+    NotFoundIn { name: String, location: String },
+
+    /// Static plugin index that cannot be parsed.
+    #[error("invalid plugin index {url}: {reason}")]
+    InvalidIndex { url: String, reason: String },
+
     /// Invalid crate metadata (missing required fields).
     #[error("invalid crate metadata for {name}: {reason}")]
     InvalidMetadata { name: String, reason: String },
@@ -49,6 +85,16 @@ pub enum PluginManagerError {
         source: reqwest::Error,
     },
 
+    /// Plugin download over plain HTTP.
+    #[error("refusing to download {url} over plain HTTP: use an https:// URL")]
+    InsecureUrl { url: String },
+
+    /// Remote plugin download without a digest to verify it against.
+    #[error(
+        "no SHA-256 digest to verify {name} downloaded from {url}: list one in the index or pass --sha256"
+    )]
+    MissingDigest { name: String, url: String },
+
     /// GitHub release not found.
     #[error("GitHub release not found for {name} v{version}")]
     ReleaseNotFound { name: String, version: String },
@@ -81,6 +127,44 @@ pub enum PluginManagerError {
     #[error("failed to serialize registry")]
     RegistrySerialize(#[source] toml::ser::Error),
 
+    /// Failed to read the lockfile.
+    #[error("failed to read lockfile {path}")]
+    LockfileRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Failed to write the lockfile.
+    #[error("failed to write lockfile {path}")]
+    LockfileWrite {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Failed to parse the lockfile.
+    #[error("failed to parse lockfile {path}")]
+    LockfileParse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    /// Failed to serialize the lockfile.
+    #[error("failed to serialize lockfile")]
+    LockfileSerialize(#[source] toml::ser::Error),
+
+    /// Downloaded plugin not matching its expected digest, from the
+    /// lockfile, the index or `--sha256`.
+    #[error("digest mismatch for {name} v{version}: expected {expected}, downloaded {actual}")]
+    DigestMismatch {
+        name: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+
     /// Failed to load WASM plugin.
     #[error("failed to load WASM plugin: {name}")]
     WasmLoad {
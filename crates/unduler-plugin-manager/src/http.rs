@@ -0,0 +1,166 @@
+//! HTTP settings of plugin discovery.
+
+use std::time::Duration;
+
+/// Environment variables holding a GitHub token, in lookup order.
+const TOKEN_VARIABLES: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// HTTP settings used to discover and download plugins.
+///
+/// Proxies are read from `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` unless
+/// one is set explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpOptions {
+    timeout: Duration,
+    proxy: Option<String>,
+    retries: u32,
+    backoff: Duration,
+    github_token: Option<String>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            proxy: None,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            github_token: None,
+        }
+    }
+}
+
+impl HttpOptions {
+    /// Creates the default settings, without GitHub token.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the default settings, with the GitHub token of `GITHUB_TOKEN`
+    /// or `GH_TOKEN`.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let token = TOKEN_VARIABLES
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|token| !token.is_empty());
+        Self::new().with_github_token(token)
+    }
+
+    /// Sets the timeout of a request, from connection to the end of the
+    /// response.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the proxy of every request, instead of the proxy variables.
+    #[must_use]
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Sets the number of retries of a request failing on a network error,
+    /// a rate limit or a server error.
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled on each retry.
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets the token authenticating requests to the GitHub API, which
+    /// otherwise share a low anonymous rate limit.
+    #[must_use]
+    pub fn with_github_token(mut self, token: Option<String>) -> Self {
+        self.github_token = token;
+        self
+    }
+
+    /// Returns the timeout of a request.
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the proxy set instead of the proxy variables.
+    #[must_use]
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    /// Returns the number of retries of a failed request.
+    #[must_use]
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the delay before the first retry.
+    #[must_use]
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Returns the GitHub API token.
+    #[must_use]
+    pub fn github_token(&self) -> Option<&str> {
+        self.github_token.as_deref()
+    }
+
+    /// Returns the delay before a retry, following `Retry-After` when the
+    /// server sent one.
+    #[must_use]
+    pub fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        retry_after.unwrap_or_else(|| self.backoff.saturating_mul(2u32.saturating_pow(attempt)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = HttpOptions::new();
+        assert_eq!(options.timeout(), Duration::from_secs(30));
+        assert!(options.proxy().is_none());
+        assert_eq!(options.retries(), 3);
+        assert_eq!(options.backoff(), Duration::from_secs(1));
+        assert!(options.github_token().is_none());
+    }
+
+    #[test]
+    fn test_options_builder() {
+        let options = HttpOptions::new()
+            .with_timeout(Duration::from_secs(5))
+            .with_proxy(Some("http://proxy.example.com:3128".to_string()))
+            .with_retries(0)
+            .with_backoff(Duration::from_millis(100))
+            .with_github_token(Some("token".to_string()));
+        assert_eq!(options.timeout(), Duration::from_secs(5));
+        assert_eq!(options.proxy(), Some("http://proxy.example.com:3128"));
+        assert_eq!(options.retries(), 0);
+        assert_eq!(options.backoff(), Duration::from_millis(100));
+        assert_eq!(options.github_token(), Some("token"));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let options = HttpOptions::new();
+        assert_eq!(options.retry_delay(0, None), Duration::from_secs(1));
+        assert_eq!(options.retry_delay(2, None), Duration::from_secs(4));
+        assert_eq!(
+            options.retry_delay(2, Some(Duration::from_secs(10))),
+            Duration::from_secs(10)
+        );
+    }
+}
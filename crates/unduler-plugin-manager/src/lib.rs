@@ -3,15 +3,26 @@
 //! This crate handles:
 //! - Plugin discovery via crates.io
 //! - Plugin installation from GitHub Releases
-//! - Local plugin storage and registry
+//! - Private registries, static plugin indexes and direct URLs
+//! - Local plugin storage and registry, globally or per project
+//! - Lockfile of the plugins used by a repository
 //! - Plugin loading through the WASM runtime
+//! - GitHub release lookups, shared with the self-update of the CLI
 
 pub mod discovery;
 pub mod error;
+pub mod http;
+pub mod loader;
+pub mod lockfile;
 pub mod registry;
+pub mod source;
 pub mod storage;
 
-pub use discovery::PluginDiscovery;
+pub use discovery::{GitHubAsset, GitHubRelease, PluginDiscovery};
 pub use error::{PluginManagerError, PluginManagerResult};
+pub use http::HttpOptions;
+pub use loader::{PluginLoader, ResolvedPlugin};
+pub use lockfile::{LOCKFILE_NAME, LockMismatch, LockedPlugin, Lockfile};
 pub use registry::{InstalledPlugin, PluginRegistry};
+pub use source::PluginSource;
 pub use storage::PluginStorage;
@@ -0,0 +1,450 @@
+//! Loading of installed plugins through the WASM runtime.
+//!
+//! Plugins installed in a project are preferred over the plugins installed
+//! globally, so each project can pin its own plugin versions.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use unduler_wasm_runtime::{
+    PluginHost, SandboxPolicy, Secrets, SharedPlugin, WasmBumper, WasmFormatter, WasmHook,
+    WasmParser,
+};
+
+use crate::storage::PluginType;
+use crate::{
+    InstalledPlugin, PluginManagerError, PluginManagerResult, PluginRegistry, PluginStorage,
+};
+
+/// Installed plugin resolved by the loader.
+#[derive(Debug, Clone)]
+pub struct ResolvedPlugin<'a> {
+    /// Installed plugin.
+    pub plugin: &'a InstalledPlugin,
+    /// Path of the plugin WASM.
+    pub path: PathBuf,
+    /// Whether the plugin is installed in the project.
+    pub local: bool,
+}
+
+/// Loader of installed plugins, from the project first and globally
+/// otherwise.
+pub struct PluginLoader {
+    global: PluginRegistry,
+    local: Option<PluginRegistry>,
+    configs: HashMap<String, Value>,
+    sandboxes: HashMap<String, SandboxPolicy>,
+}
+
+impl PluginLoader {
+    /// Creates a loader of globally installed plugins.
+    #[must_use]
+    pub fn new(global: PluginRegistry) -> Self {
+        Self {
+            global,
+            local: None,
+            configs: HashMap::new(),
+            sandboxes: HashMap::new(),
+        }
+    }
+
+    /// Sets the registry of the project plugins, preferred over the global
+    /// ones.
+    #[must_use]
+    pub fn with_local(mut self, local: PluginRegistry) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    /// Sets the `[plugins.<name>]` tables of the plugins, by short name
+    /// (e.g. `acme` for `unduler-parser-acme`), delivered to the plugins
+    /// when they are loaded.
+    #[must_use]
+    pub fn with_configs(mut self, configs: HashMap<String, Value>) -> Self {
+        self.configs = configs;
+        self
+    }
+
+    /// Sets the sandbox policies of the plugins, by short name, applied to
+    /// the formatters and hooks when they are loaded.
+    #[must_use]
+    pub fn with_sandboxes(mut self, sandboxes: HashMap<String, SandboxPolicy>) -> Self {
+        self.sandboxes = sandboxes;
+        self
+    }
+
+    /// Returns the registry of globally installed plugins.
+    #[must_use]
+    pub fn global(&self) -> &PluginRegistry {
+        &self.global
+    }
+
+    /// Returns the registry of the project plugins.
+    #[must_use]
+    pub fn local(&self) -> Option<&PluginRegistry> {
+        self.local.as_ref()
+    }
+
+    /// Resolves an installed plugin by crate name, preferring the project
+    /// installation.
+    #[must_use]
+    pub fn resolve(&self, crate_name: &str) -> Option<ResolvedPlugin<'_>> {
+        self.local
+            .as_ref()
+            .and_then(|registry| Self::resolve_in(registry, crate_name, true))
+            .or_else(|| Self::resolve_in(&self.global, crate_name, false))
+    }
+
+    /// Lists the installed plugins, project plugins shadowing the global
+    /// ones of the same name, sorted by crate name.
+    #[must_use]
+    pub fn list(&self) -> Vec<ResolvedPlugin<'_>> {
+        let mut plugins: Vec<_> = self
+            .local
+            .iter()
+            .flat_map(PluginRegistry::list)
+            .chain(self.global.list())
+            .filter_map(|plugin| self.resolve(&plugin.crate_name))
+            .collect();
+        plugins.sort_by(|a, b| a.plugin.crate_name.cmp(&b.plugin.crate_name));
+        plugins.dedup_by(|a, b| a.plugin.crate_name == b.plugin.crate_name);
+        plugins
+    }
+
+    /// Loads an installed parser plugin on a host, delivering its
+    /// configuration when the host loads it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not installed, is not a parser,
+    /// cannot be loaded, or rejects its configuration.
+    pub fn load_parser(
+        &self,
+        host: &PluginHost,
+        crate_name: &str,
+    ) -> PluginManagerResult<SharedPlugin<WasmParser>> {
+        let path = self.resolve_typed(crate_name, PluginType::Parser)?;
+        host.parser(&path, |mut parser| {
+            if let Some(config) = self.config_of(crate_name) {
+                parser.configure(&config)?;
+            }
+            Ok(parser)
+        })
+        .map_err(|source| wasm_load(crate_name, source))
+    }
+
+    /// Loads an installed bumper plugin on a host, delivering its
+    /// configuration when the host loads it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not installed, is not a bumper,
+    /// cannot be loaded, or rejects its configuration.
+    pub fn load_bumper(
+        &self,
+        host: &PluginHost,
+        crate_name: &str,
+    ) -> PluginManagerResult<SharedPlugin<WasmBumper>> {
+        let path = self.resolve_typed(crate_name, PluginType::Bumper)?;
+        host.bumper(&path, |mut bumper| {
+            if let Some(config) = self.config_of(crate_name) {
+                bumper.configure(&config)?;
+            }
+            Ok(bumper)
+        })
+        .map_err(|source| wasm_load(crate_name, source))
+    }
+
+    /// Loads an installed formatter plugin on a host, able to read `workdir`
+    /// when its sandbox policy allows it, delivering its configuration when
+    /// the host loads it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not installed, is not a formatter,
+    /// cannot be loaded, or rejects its configuration.
+    pub fn load_formatter(
+        &self,
+        host: &PluginHost,
+        crate_name: &str,
+        workdir: &Path,
+    ) -> PluginManagerResult<SharedPlugin<WasmFormatter>> {
+        let path = self.resolve_typed(crate_name, PluginType::Formatter)?;
+        host.formatter(
+            &path,
+            workdir,
+            &self.sandbox_of(crate_name),
+            |mut formatter| {
+                if let Some(config) = self.config_of(crate_name) {
+                    formatter.configure(&config)?;
+                }
+                Ok(formatter)
+            },
+        )
+        .map_err(|source| wasm_load(crate_name, source))
+    }
+
+    /// Loads an installed hook plugin on a host, running in `workdir` under
+    /// its sandbox policy, delivering its configuration and the secrets it
+    /// declares when the host loads it first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin is not installed, is not a hook,
+    /// cannot be loaded, or rejects its configuration.
+    pub fn load_hook(
+        &self,
+        host: &PluginHost,
+        crate_name: &str,
+        workdir: &Path,
+    ) -> PluginManagerResult<SharedPlugin<WasmHook>> {
+        let path = self.resolve_typed(crate_name, PluginType::Hook)?;
+        host.hook(&path, workdir, self.sandbox_of(crate_name), |hook| {
+            let mut hook = hook.with_secrets(self.secrets_of(crate_name));
+            if let Some(config) = self.config_of(crate_name) {
+                hook.configure(&config)?;
+            }
+            Ok(hook)
+        })
+        .map_err(|source| wasm_load(crate_name, source))
+    }
+
+    /// Returns the configuration of a plugin, as JSON.
+    fn config_of(&self, crate_name: &str) -> Option<String> {
+        let (_, short_name) = PluginStorage::parse_crate_name(crate_name).ok()?;
+        self.configs.get(&short_name).map(Value::to_string)
+    }
+
+    /// Returns the secrets declared in the configuration of a plugin.
+    fn secrets_of(&self, crate_name: &str) -> Secrets {
+        PluginStorage::parse_crate_name(crate_name)
+            .ok()
+            .and_then(|(_, short_name)| self.configs.get(&short_name).map(Secrets::from_config))
+            .unwrap_or_default()
+    }
+
+    /// Returns the sandbox policy of a plugin, the default one unless set.
+    fn sandbox_of(&self, crate_name: &str) -> SandboxPolicy {
+        PluginStorage::parse_crate_name(crate_name)
+            .ok()
+            .and_then(|(_, short_name)| self.sandboxes.get(&short_name).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Resolves a plugin in a registry.
+    fn resolve_in<'a>(
+        registry: &'a PluginRegistry,
+        crate_name: &str,
+        local: bool,
+    ) -> Option<ResolvedPlugin<'a>> {
+        let plugin = registry.get(crate_name)?;
+        let path =
+            registry
+                .storage()
+                .plugin_path(&plugin.short_name, plugin.plugin_type, &plugin.version);
+        Some(ResolvedPlugin {
+            plugin,
+            path,
+            local,
+        })
+    }
+
+    /// Resolves the WASM path of a plugin of the expected type.
+    fn resolve_typed(
+        &self,
+        crate_name: &str,
+        expected: PluginType,
+    ) -> PluginManagerResult<PathBuf> {
+        let resolved =
+            self.resolve(crate_name)
+                .ok_or_else(|| PluginManagerError::PluginNotFound {
+                    name: crate_name.to_string(),
+                })?;
+        if resolved.plugin.plugin_type != expected {
+            return Err(PluginManagerError::TypeMismatch {
+                name: crate_name.to_string(),
+                expected: format!("{expected:?}").to_lowercase(),
+                actual: format!("{:?}", resolved.plugin.plugin_type).to_lowercase(),
+            });
+        }
+        Ok(resolved.path)
+    }
+}
+
+/// Wraps a WASM error of a plugin.
+fn wasm_load(crate_name: &str, source: unduler_wasm_runtime::WasmError) -> PluginManagerError {
+    PluginManagerError::WasmLoad {
+        name: crate_name.to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use unduler_wasm_runtime::WasmEngine;
+
+    use super::*;
+    use crate::{PluginSource, PluginStorage};
+
+    fn create_registry(dir: &Path) -> PluginRegistry {
+        PluginRegistry::new(PluginStorage::with_base_dir(dir.to_path_buf()).unwrap()).unwrap()
+    }
+
+    fn register(registry: &mut PluginRegistry, crate_name: &str, version: semver::Version) {
+        let (plugin_type, short_name) = PluginStorage::parse_crate_name(crate_name).unwrap();
+        registry
+            .register(InstalledPlugin {
+                crate_name: crate_name.to_string(),
+                plugin_type,
+                short_name,
+                version,
+                description: None,
+                repository: None,
+                source: PluginSource::default(),
+                installed_at: chrono::Utc::now(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_resolve_prefers_local() {
+        let temp = TempDir::new().unwrap();
+        let mut global = create_registry(&temp.path().join("global"));
+        let mut local = create_registry(&temp.path().join("project/.unduler"));
+        register(
+            &mut global,
+            "unduler-parser-acme",
+            semver::Version::new(1, 0, 0),
+        );
+        register(
+            &mut global,
+            "unduler-hook-slack",
+            semver::Version::new(0, 1, 0),
+        );
+        register(
+            &mut local,
+            "unduler-parser-acme",
+            semver::Version::new(2, 0, 0),
+        );
+
+        let loader = PluginLoader::new(global).with_local(local);
+
+        let parser = loader.resolve("unduler-parser-acme").unwrap();
+        assert!(parser.local);
+        assert_eq!(parser.plugin.version, semver::Version::new(2, 0, 0));
+        assert_eq!(
+            parser.path,
+            temp.path()
+                .join("project/.unduler/plugins/parser-acme/2.0.0.wasm")
+        );
+
+        let hook = loader.resolve("unduler-hook-slack").unwrap();
+        assert!(!hook.local);
+
+        assert!(loader.resolve("unduler-bumper-semver").is_none());
+    }
+
+    #[test]
+    fn test_list_shadows_global() {
+        let temp = TempDir::new().unwrap();
+        let mut global = create_registry(&temp.path().join("global"));
+        let mut local = create_registry(&temp.path().join("project/.unduler"));
+        register(
+            &mut global,
+            "unduler-parser-acme",
+            semver::Version::new(1, 0, 0),
+        );
+        register(
+            &mut global,
+            "unduler-hook-slack",
+            semver::Version::new(0, 1, 0),
+        );
+        register(
+            &mut local,
+            "unduler-parser-acme",
+            semver::Version::new(2, 0, 0),
+        );
+
+        let loader = PluginLoader::new(global).with_local(local);
+        let plugins: Vec<_> = loader
+            .list()
+            .into_iter()
+            .map(|p| (p.plugin.crate_name.as_str(), p.local))
+            .collect();
+        assert_eq!(
+            plugins,
+            [("unduler-hook-slack", false), ("unduler-parser-acme", true)]
+        );
+    }
+
+    #[test]
+    fn test_load_type_mismatch() {
+        let temp = TempDir::new().unwrap();
+        let mut global = create_registry(temp.path());
+        register(
+            &mut global,
+            "unduler-hook-slack",
+            semver::Version::new(0, 1, 0),
+        );
+
+        let loader = PluginLoader::new(global);
+        let host = PluginHost::new(WasmEngine::new().unwrap());
+        assert!(matches!(
+            loader.load_parser(&host, "unduler-hook-slack"),
+            Err(PluginManagerError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            loader.load_parser(&host, "unduler-parser-acme"),
+            Err(PluginManagerError::PluginNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_config_of() {
+        let temp = TempDir::new().unwrap();
+        let loader =
+            PluginLoader::new(create_registry(temp.path())).with_configs(HashMap::from([(
+                "acme".to_string(),
+                serde_json::json!({ "strict": true }),
+            )]));
+
+        assert_eq!(
+            loader.config_of("unduler-parser-acme").as_deref(),
+            Some(r#"{"strict":true}"#)
+        );
+        assert!(loader.config_of("unduler-hook-slack").is_none());
+    }
+
+    #[test]
+    fn test_secrets_of() {
+        let temp = TempDir::new().unwrap();
+        let loader =
+            PluginLoader::new(create_registry(temp.path())).with_configs(HashMap::from([(
+                "release".to_string(),
+                serde_json::json!({ "token_env": "RELEASE_TOKEN", "draft": true }),
+            )]));
+
+        let secrets = loader.secrets_of("unduler-hook-release");
+        assert_eq!(secrets.names().collect::<Vec<_>>(), ["token"]);
+        assert_eq!(loader.secrets_of("unduler-hook-slack").names().count(), 0);
+    }
+
+    #[test]
+    fn test_sandbox_of() {
+        let temp = TempDir::new().unwrap();
+        let loader =
+            PluginLoader::new(create_registry(temp.path())).with_sandboxes(HashMap::from([(
+                "templates".to_string(),
+                SandboxPolicy::default().with_filesystem(true),
+            )]));
+
+        assert!(
+            loader
+                .sandbox_of("unduler-formatter-templates")
+                .filesystem()
+        );
+        assert!(!loader.sandbox_of("unduler-hook-slack").wasi());
+    }
+}
@@ -0,0 +1,446 @@
+//! Lockfile of the plugins used by a repository.
+//!
+//! The lockfile is stored as `unduler.lock` next to the project
+//! configuration, and records the exact version, source and digest of each
+//! plugin so that every machine installs the same plugins.
+
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::loader::{PluginLoader, ResolvedPlugin};
+use crate::{PluginManagerError, PluginManagerResult, PluginSource};
+
+/// File name of the lockfile.
+pub const LOCKFILE_NAME: &str = "unduler.lock";
+
+/// Version of the lockfile format.
+const LOCKFILE_VERSION: u32 = 1;
+
+/// Plugin pinned in the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPlugin {
+    /// Full crate name (e.g., "unduler-parser-conventional").
+    pub name: String,
+    /// Locked version.
+    pub version: semver::Version,
+    /// Source the plugin is installed from.
+    pub source: PluginSource,
+    /// Digest of the plugin WASM, as `sha256:<hex>`.
+    pub digest: String,
+}
+
+impl LockedPlugin {
+    /// Locks an installed plugin, hashing its WASM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin WASM cannot be read.
+    pub fn from_resolved(resolved: &ResolvedPlugin<'_>) -> PluginManagerResult<Self> {
+        let wasm_bytes = std::fs::read(&resolved.path)?;
+        Ok(Self {
+            name: resolved.plugin.crate_name.clone(),
+            version: resolved.plugin.version.clone(),
+            source: resolved.plugin.source.clone(),
+            digest: digest(&wasm_bytes),
+        })
+    }
+}
+
+/// Difference between the lockfile and the installed plugins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockMismatch {
+    /// Locked plugin not installed.
+    Missing {
+        /// Crate name.
+        name: String,
+        /// Locked version.
+        version: semver::Version,
+    },
+    /// Locked plugin installed at another version.
+    Version {
+        /// Crate name.
+        name: String,
+        /// Locked version.
+        locked: semver::Version,
+        /// Installed version.
+        installed: semver::Version,
+    },
+    /// Locked plugin installed with another WASM.
+    Digest {
+        /// Crate name.
+        name: String,
+        /// Locked version.
+        version: semver::Version,
+    },
+    /// Loaded plugin missing from the lockfile.
+    Unlocked {
+        /// Crate name.
+        name: String,
+    },
+}
+
+impl LockMismatch {
+    /// Returns the crate name of the plugin.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Missing { name, .. }
+            | Self::Version { name, .. }
+            | Self::Digest { name, .. }
+            | Self::Unlocked { name } => name,
+        }
+    }
+}
+
+impl fmt::Display for LockMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { name, version } => write!(f, "{name} v{version} is not installed"),
+            Self::Version {
+                name,
+                locked,
+                installed,
+            } => write!(
+                f,
+                "{name} is locked to v{locked} but v{installed} is installed"
+            ),
+            Self::Digest { name, version } => {
+                write!(f, "{name} v{version} does not match the locked digest")
+            }
+            Self::Unlocked { name } => write!(f, "{name} is not in the lockfile"),
+        }
+    }
+}
+
+/// Lockfile of the plugins used by a repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Version of the lockfile format.
+    version: u32,
+    /// Locked plugins, sorted by name.
+    #[serde(default, rename = "plugin")]
+    plugins: Vec<LockedPlugin>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Creates an empty lockfile.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks the installed plugins, project plugins taking precedence over
+    /// the global ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM of a plugin cannot be read.
+    pub fn from_loader(loader: &PluginLoader) -> PluginManagerResult<Self> {
+        let mut lockfile = Self::new();
+        for resolved in loader.list() {
+            lockfile.lock(LockedPlugin::from_resolved(&resolved)?);
+        }
+        Ok(lockfile)
+    }
+
+    /// Loads a lockfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> PluginManagerResult<Self> {
+        let path = path.as_ref();
+        let content =
+            std::fs::read_to_string(path).map_err(|source| PluginManagerError::LockfileRead {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        toml::from_str(&content).map_err(|source| PluginManagerError::LockfileParse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Saves the lockfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lockfile cannot be serialized or written.
+    pub fn save(&self, path: impl AsRef<Path>) -> PluginManagerResult<()> {
+        let path = path.as_ref();
+        let content =
+            toml::to_string_pretty(self).map_err(PluginManagerError::LockfileSerialize)?;
+        let content = format!(
+            "# This file is generated by `unduler plugin lock`. Do not edit it by hand.\n\n{content}"
+        );
+        std::fs::write(path, content).map_err(|source| PluginManagerError::LockfileWrite {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Returns the locked plugins, sorted by name.
+    #[must_use]
+    pub fn plugins(&self) -> &[LockedPlugin] {
+        &self.plugins
+    }
+
+    /// Gets a locked plugin by crate name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&LockedPlugin> {
+        self.plugins.iter().find(|p| p.name == name)
+    }
+
+    /// Locks a plugin, replacing the entry of the same name.
+    pub fn lock(&mut self, plugin: LockedPlugin) {
+        match self.plugins.binary_search_by(|p| p.name.cmp(&plugin.name)) {
+            Ok(index) => self.plugins[index] = plugin,
+            Err(index) => self.plugins.insert(index, plugin),
+        }
+    }
+
+    /// Compares the locked plugins with the plugins the loader resolves.
+    ///
+    /// Installed plugins missing from the lockfile are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM of an installed plugin cannot be read.
+    pub fn verify(&self, loader: &PluginLoader) -> PluginManagerResult<Vec<LockMismatch>> {
+        let mut mismatches = Vec::new();
+        for locked in &self.plugins {
+            mismatches.extend(Self::compare(loader, locked)?);
+        }
+        Ok(mismatches)
+    }
+
+    /// Compares the plugins a release loaded with the lockfile.
+    ///
+    /// Locked plugins the release does not load are ignored, and loaded
+    /// plugins missing from the lockfile are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM of a loaded plugin cannot be read.
+    pub fn verify_loaded<'a>(
+        &self,
+        loader: &PluginLoader,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> PluginManagerResult<Vec<LockMismatch>> {
+        let mut mismatches = Vec::new();
+        for name in names {
+            match self.get(name) {
+                Some(locked) => mismatches.extend(Self::compare(loader, locked)?),
+                None => mismatches.push(LockMismatch::Unlocked {
+                    name: name.to_string(),
+                }),
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Compares a locked plugin with the plugin the loader resolves.
+    fn compare(
+        loader: &PluginLoader,
+        locked: &LockedPlugin,
+    ) -> PluginManagerResult<Option<LockMismatch>> {
+        let Some(resolved) = loader.resolve(&locked.name) else {
+            return Ok(Some(LockMismatch::Missing {
+                name: locked.name.clone(),
+                version: locked.version.clone(),
+            }));
+        };
+        if resolved.plugin.version != locked.version {
+            Ok(Some(LockMismatch::Version {
+                name: locked.name.clone(),
+                locked: locked.version.clone(),
+                installed: resolved.plugin.version.clone(),
+            }))
+        } else if LockedPlugin::from_resolved(&resolved)?.digest != locked.digest {
+            Ok(Some(LockMismatch::Digest {
+                name: locked.name.clone(),
+                version: locked.version.clone(),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Returns the digest of plugin WASM, as `sha256:<hex>`.
+#[must_use]
+pub fn digest(wasm_bytes: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(wasm_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::storage::PluginType;
+    use crate::{InstalledPlugin, PluginRegistry, PluginStorage};
+
+    fn create_test_registry() -> (TempDir, PluginRegistry) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = PluginStorage::with_base_dir(temp_dir.path().to_path_buf()).unwrap();
+        let registry = PluginRegistry::new(storage).unwrap();
+        (temp_dir, registry)
+    }
+
+    fn install(registry: &mut PluginRegistry, crate_name: &str, wasm_bytes: &[u8]) {
+        let (plugin_type, short_name) = PluginStorage::parse_crate_name(crate_name).unwrap();
+        let version = semver::Version::new(1, 0, 0);
+        registry
+            .storage()
+            .save_plugin(&short_name, plugin_type, &version, wasm_bytes)
+            .unwrap();
+        registry
+            .register(InstalledPlugin {
+                crate_name: crate_name.to_string(),
+                plugin_type,
+                short_name,
+                version,
+                description: None,
+                repository: None,
+                source: PluginSource::default(),
+                installed_at: chrono::Utc::now(),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_digest() {
+        assert_eq!(
+            digest(b"hello"),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_from_loader() {
+        let (_temp, mut registry) = create_test_registry();
+        install(&mut registry, "unduler-parser-conventional", b"parser");
+        install(&mut registry, "unduler-bumper-semver", b"bumper");
+
+        let lockfile = Lockfile::from_loader(&PluginLoader::new(registry)).unwrap();
+        let names: Vec<_> = lockfile.plugins().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["unduler-bumper-semver", "unduler-parser-conventional"]
+        );
+        assert_eq!(
+            lockfile.get("unduler-bumper-semver").unwrap().digest,
+            digest(b"bumper")
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let (temp, mut registry) = create_test_registry();
+        install(&mut registry, "unduler-parser-conventional", b"parser");
+
+        let lockfile = Lockfile::from_loader(&PluginLoader::new(registry)).unwrap();
+        let path = temp.path().join(LOCKFILE_NAME);
+        lockfile.save(&path).unwrap();
+
+        assert_eq!(Lockfile::load(&path).unwrap(), lockfile);
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let temp = TempDir::new().unwrap();
+        assert!(matches!(
+            Lockfile::load(temp.path().join(LOCKFILE_NAME)),
+            Err(PluginManagerError::LockfileRead { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify() {
+        let (_temp, mut registry) = create_test_registry();
+        install(&mut registry, "unduler-parser-conventional", b"parser");
+        install(&mut registry, "unduler-bumper-semver", b"bumper");
+
+        let loader = PluginLoader::new(registry);
+        let mut lockfile = Lockfile::from_loader(&loader).unwrap();
+        assert!(lockfile.verify(&loader).unwrap().is_empty());
+
+        lockfile.lock(LockedPlugin {
+            name: "unduler-hook-slack".to_string(),
+            version: semver::Version::new(0, 2, 0),
+            source: PluginSource::default(),
+            digest: digest(b"hook"),
+        });
+        let mut semver = lockfile.get("unduler-bumper-semver").unwrap().clone();
+        semver.version = semver::Version::new(2, 0, 0);
+        lockfile.lock(semver);
+        loader
+            .global()
+            .storage()
+            .save_plugin(
+                "conventional",
+                PluginType::Parser,
+                &semver::Version::new(1, 0, 0),
+                b"tampered",
+            )
+            .unwrap();
+
+        let mismatches = lockfile.verify(&loader).unwrap();
+        assert_eq!(mismatches.len(), 3);
+        assert!(matches!(mismatches[0], LockMismatch::Version { .. }));
+        assert!(matches!(mismatches[1], LockMismatch::Missing { .. }));
+        assert!(matches!(mismatches[2], LockMismatch::Digest { .. }));
+        assert_eq!(
+            mismatches[1].to_string(),
+            "unduler-hook-slack v0.2.0 is not installed"
+        );
+    }
+
+    #[test]
+    fn test_verify_loaded() {
+        let (_temp, mut registry) = create_test_registry();
+        install(&mut registry, "unduler-parser-conventional", b"parser");
+        install(&mut registry, "unduler-hook-slack", b"hook");
+
+        let loader = PluginLoader::new(registry);
+        let mut lockfile = Lockfile::from_loader(&loader).unwrap();
+        lockfile.lock(LockedPlugin {
+            name: "unduler-bumper-semver".to_string(),
+            version: semver::Version::new(1, 0, 0),
+            source: PluginSource::default(),
+            digest: digest(b"bumper"),
+        });
+        lockfile.plugins.retain(|p| p.name != "unduler-hook-slack");
+
+        // The locked bumper is not loaded, so its absence is ignored
+        let mismatches = lockfile
+            .verify_loaded(
+                &loader,
+                ["unduler-parser-conventional", "unduler-hook-slack"],
+            )
+            .unwrap();
+        assert_eq!(
+            mismatches,
+            vec![LockMismatch::Unlocked {
+                name: "unduler-hook-slack".to_string()
+            }]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "unduler-hook-slack is not in the lockfile"
+        );
+    }
+}
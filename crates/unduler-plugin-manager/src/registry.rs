@@ -8,7 +8,7 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 
 use crate::storage::{PluginStorage, PluginType};
-use crate::{PluginManagerError, PluginManagerResult};
+use crate::{PluginManagerError, PluginManagerResult, PluginSource};
 
 /// Information about an installed plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +26,10 @@ pub struct InstalledPlugin {
     pub description: Option<String>,
     /// Source repository URL.
     pub repository: Option<String>,
+    /// Source the plugin was installed from, crates.io for plugins
+    /// installed before sources were recorded.
+    #[serde(default)]
+    pub source: PluginSource,
     /// Installation timestamp.
     pub installed_at: chrono::DateTime<chrono::Utc>,
 }
@@ -237,6 +241,7 @@ mod tests {
             version: semver::Version::new(1, 0, 0),
             description: Some("Conventional commits parser".to_string()),
             repository: Some("https://github.com/example/repo".to_string()),
+            source: PluginSource::default(),
             installed_at: chrono::Utc::now(),
         }
     }
@@ -265,6 +270,7 @@ mod tests {
                 version: semver::Version::new(1, 0, 0),
                 description: None,
                 repository: None,
+                source: PluginSource::default(),
                 installed_at: chrono::Utc::now(),
             })
             .unwrap();
@@ -286,6 +292,48 @@ mod tests {
         assert!(!registry.is_installed("unduler-parser-conventional"));
     }
 
+    #[test]
+    fn test_source_persistence() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = PluginSource::index("https://plugins.example.com/index.json");
+
+        {
+            let storage = PluginStorage::with_base_dir(temp_dir.path().to_path_buf()).unwrap();
+            let mut registry = PluginRegistry::new(storage).unwrap();
+            let plugin = InstalledPlugin {
+                source: source.clone(),
+                ..create_test_plugin()
+            };
+            registry.register(plugin).unwrap();
+        }
+
+        let storage = PluginStorage::with_base_dir(temp_dir.path().to_path_buf()).unwrap();
+        let registry = PluginRegistry::new(storage).unwrap();
+        assert_eq!(
+            registry.get("unduler-parser-conventional").unwrap().source,
+            source
+        );
+    }
+
+    #[test]
+    fn test_source_defaults_to_crates_io() {
+        let data: RegistryFile = toml::from_str(
+            r#"
+            [plugins.unduler-parser-conventional]
+            crate_name = "unduler-parser-conventional"
+            plugin_type = "parser"
+            short_name = "conventional"
+            version = "1.0.0"
+            installed_at = "2024-01-15T00:00:00Z"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            data.plugins["unduler-parser-conventional"].source,
+            PluginSource::default()
+        );
+    }
+
     #[test]
     fn test_persistence() {
         let temp_dir = TempDir::new().unwrap();
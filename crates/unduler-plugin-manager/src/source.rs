@@ -0,0 +1,102 @@
+//! Sources plugins are installed from.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// API of crates.io.
+pub const CRATES_IO: &str = "https://crates.io";
+
+/// Source a plugin is discovered and downloaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PluginSource {
+    /// Registry with the crates.io API (crates.io or a private registry),
+    /// the WASM being attached to GitHub Releases of the plugin repository.
+    Registry {
+        /// Base URL of the registry API.
+        url: String,
+    },
+    /// Static JSON index listing the plugins and the URLs of their WASM.
+    Index {
+        /// URL or path of the index file.
+        url: String,
+    },
+    /// WASM downloaded from a direct URL.
+    Url {
+        /// URL or path of the WASM file.
+        url: String,
+    },
+}
+
+impl PluginSource {
+    /// Returns a registry source for a base URL.
+    #[must_use]
+    pub fn registry(url: impl Into<String>) -> Self {
+        Self::Registry {
+            url: url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Returns a static index source.
+    #[must_use]
+    pub fn index(url: impl Into<String>) -> Self {
+        Self::Index { url: url.into() }
+    }
+
+    /// Returns a direct URL source.
+    #[must_use]
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url { url: url.into() }
+    }
+}
+
+impl Default for PluginSource {
+    fn default() -> Self {
+        Self::registry(CRATES_IO)
+    }
+}
+
+impl fmt::Display for PluginSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Registry { url } if url == CRATES_IO => write!(f, "crates.io"),
+            Self::Registry { url } => write!(f, "registry+{url}"),
+            Self::Index { url } => write!(f, "index+{url}"),
+            Self::Url { url } => write!(f, "{url}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_source() {
+        assert_eq!(
+            PluginSource::default(),
+            PluginSource::Registry {
+                url: CRATES_IO.to_string()
+            }
+        );
+        assert_eq!(PluginSource::default().to_string(), "crates.io");
+    }
+
+    #[test]
+    fn test_registry_trims_trailing_slash() {
+        let source = PluginSource::registry("https://registry.example.com/");
+        assert_eq!(source.to_string(), "registry+https://registry.example.com");
+    }
+
+    #[test]
+    fn test_display() {
+        let source = PluginSource::index("https://plugins.example.com/index.json");
+        assert_eq!(
+            source.to_string(),
+            "index+https://plugins.example.com/index.json"
+        );
+        let source = PluginSource::url("https://example.com/plugin.wasm");
+        assert_eq!(source.to_string(), "https://example.com/plugin.wasm");
+    }
+}
@@ -9,13 +9,20 @@
 //! │   ├── bumper-semver/
 //! │   │   └── 0.1.0.wasm
 //! │   └── ...
+//! ├── cache/
 //! └── registry.toml
 //! ```
+//!
+//! Plugins installed for a single project are stored the same way in the
+//! `.unduler/` directory of the project.
 
 use std::path::{Path, PathBuf};
 
 use crate::{PluginManagerError, PluginManagerResult};
 
+/// Directory of project-local plugins, relative to the project root.
+pub const LOCAL_DIR: &str = ".unduler";
+
 /// Plugin type derived from plugin name prefix.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PluginType {
@@ -83,6 +90,34 @@ impl PluginStorage {
         Ok(Self { base_dir })
     }
 
+    /// Creates the plugin storage of a project, in its `.unduler/` directory.
+    ///
+    /// The directory is ignored by git, like the other state unduler keeps
+    /// in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage directory cannot be created.
+    pub fn local(project_dir: &Path) -> PluginManagerResult<Self> {
+        let storage = Self::with_base_dir(project_dir.join(LOCAL_DIR))?;
+
+        let gitignore = storage.base_dir.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(&gitignore, "*\n").map_err(|source| PluginManagerError::SaveFailed {
+                path: gitignore,
+                source,
+            })?;
+        }
+
+        Ok(storage)
+    }
+
+    /// Returns true if a project has local plugin storage.
+    #[must_use]
+    pub fn has_local(project_dir: &Path) -> bool {
+        project_dir.join(LOCAL_DIR).join("registry.toml").exists()
+    }
+
     /// Returns the default base directory (`~/.unduler`).
     fn default_base_dir() -> PluginManagerResult<PathBuf> {
         let home = dirs::home_dir().ok_or_else(|| PluginManagerError::StorageCreation {
@@ -108,6 +143,12 @@ impl PluginStorage {
         self.base_dir.join("plugins")
     }
 
+    /// Returns the directory of compiled plugins.
+    #[must_use]
+    pub fn cache_dir(&self) -> PathBuf {
+        self.base_dir.join("cache")
+    }
+
     /// Returns the registry file path.
     #[must_use]
     pub fn registry_path(&self) -> PathBuf {
@@ -274,4 +315,27 @@ mod tests {
             PathBuf::from("/tmp/unduler-test/plugins/parser-conventional/1.0.0.wasm")
         );
     }
+
+    #[test]
+    fn test_local() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(!PluginStorage::has_local(dir.path()));
+
+        let storage = PluginStorage::local(dir.path()).unwrap();
+        assert_eq!(storage.plugins_dir(), dir.path().join(".unduler/plugins"));
+        assert!(storage.plugins_dir().is_dir());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".unduler/.gitignore")).unwrap(),
+            "*\n"
+        );
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        let storage = PluginStorage::with_base_dir(PathBuf::from("/tmp/unduler-test")).unwrap();
+        assert_eq!(
+            storage.cache_dir(),
+            PathBuf::from("/tmp/unduler-test/cache")
+        );
+    }
 }
@@ -33,6 +33,9 @@
 //! export!(MyParser);
 //! ```
 
+// The generated bindings rebuild the lists they receive from their raw parts
+#![allow(clippy::same_length_and_capacity)]
+
 /// Parser plugin bindings.
 pub mod parser {
     wit_bindgen::generate!({
@@ -46,6 +49,26 @@ pub mod parser {
     pub use self::unduler::plugin::types::*;
 }
 
+/// Bindings for parser plugins that also parse commits in batches.
+///
+/// Plugins implement [`BatchGuest`](batch_parser::BatchGuest) and export it
+/// next to their parser, e.g. `parser::export!(MyParser)` and
+/// `batch_parser::export!(MyParser)`.
+pub mod batch_parser {
+    wit_bindgen::generate!({
+        world: "unduler-batch-parser-export",
+        path: "wit",
+        pub_export_macro: true,
+        export_macro_name: "export",
+        with: {
+            "unduler:plugin/types@0.2.0": crate::parser::unduler::plugin::types,
+        },
+    });
+
+    pub use self::exports::unduler::plugin::batch_parser::Guest as BatchGuest;
+    pub use crate::parser::unduler::plugin::types::*;
+}
+
 /// Bumper plugin bindings.
 pub mod bumper {
     wit_bindgen::generate!({
@@ -59,6 +82,26 @@ pub mod bumper {
     pub use self::unduler::plugin::types::*;
 }
 
+/// Bindings for bumper plugins that also compute the next version.
+///
+/// Plugins implement [`VersionGuest`](version_bumper::VersionGuest) and
+/// export it next to their bumper, e.g. `bumper::export!(MyBumper)` and
+/// `version_bumper::export!(MyBumper)`.
+pub mod version_bumper {
+    wit_bindgen::generate!({
+        world: "unduler-version-bumper-export",
+        path: "wit",
+        pub_export_macro: true,
+        export_macro_name: "export",
+        with: {
+            "unduler:plugin/types@0.2.0": crate::bumper::unduler::plugin::types,
+        },
+    });
+
+    pub use self::exports::unduler::plugin::version_bumper::Guest as VersionGuest;
+    pub use crate::bumper::unduler::plugin::types::*;
+}
+
 /// Formatter plugin bindings.
 pub mod formatter {
     wit_bindgen::generate!({
@@ -73,6 +116,11 @@ pub mod formatter {
 }
 
 /// Hook plugin bindings.
+///
+/// Hooks send HTTP requests through [`http::request`](hook::http::request),
+/// to the hosts allowed by their sandbox, and get the secrets declared in
+/// their configuration through
+/// [`secrets::get_secret`](hook::secrets::get_secret).
 pub mod hook {
     wit_bindgen::generate!({
         world: "unduler-hook",
@@ -82,5 +130,24 @@ pub mod hook {
     });
 
     pub use self::exports::unduler::plugin::hook::Guest;
+    pub use self::unduler::plugin::http;
+    pub use self::unduler::plugin::secrets;
     pub use self::unduler::plugin::types::*;
 }
+
+/// Bindings for plugins accepting configuration.
+///
+/// Plugins implement [`Guest`](config::Guest) and export it next to their
+/// plugin type, e.g. `parser::export!(MyParser)` and
+/// `config::export!(MyParser)`. The configuration is the
+/// `[plugins.<name>]` table of the plugin, as a JSON object.
+pub mod config {
+    wit_bindgen::generate!({
+        world: "unduler-configurable",
+        path: "wit",
+        pub_export_macro: true,
+        export_macro_name: "export",
+    });
+
+    pub use self::exports::unduler::plugin::config::Guest;
+}
@@ -9,7 +9,7 @@ use unduler_commit::ParsedCommit;
 use crate::BumpType;
 
 /// Shared state passed to all hooks during the release process.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReleaseContext {
     /// Path to the repository root.
     pub repo_path: std::path::PathBuf,
@@ -17,7 +17,8 @@ pub struct ReleaseContext {
     /// The previous version (before bump).
     pub previous_version: Version,
 
-    /// The next version (after bump).
+    /// The next version (after bump). `pre_release` and `pre_bump` hooks
+    /// can change it to release another version.
     pub next_version: Version,
 
     /// The determined bump type.
@@ -29,14 +30,47 @@ pub struct ReleaseContext {
     /// The generated changelog (populated after formatter runs).
     pub changelog: Option<String>,
 
+    /// Name of the release tag, e.g. `v1.2.3`, following the tag format of
+    /// the project.
+    pub tag_name: Option<String>,
+
+    /// Package name of the `{package}` tag placeholder, from
+    /// `version.package`.
+    pub package: Option<String>,
+
+    /// Web URL of the repository, e.g. `https://github.com/owner/repo`.
+    pub repository_url: Option<String>,
+
+    /// Name of the current branch, unless the HEAD is detached.
+    pub branch: Option<String>,
+
+    /// Files the release writes, relative to the repository root
+    /// (populated before `pre_release` hooks run).
+    pub files: Vec<String>,
+
+    /// Extra files staged in the release commit, relative to the repository
+    /// root (read back after `pre_commit` hooks run).
+    pub commit_files: Vec<String>,
+
+    /// The error of the failed step (populated before `failure` hooks run).
+    pub error: Option<String>,
+
     /// Whether this is a dry run (no actual changes).
     pub dry_run: bool,
 
     /// Arbitrary metadata for inter-hook communication.
     pub metadata: HashMap<String, Value>,
+
+    /// Configuration of the running hook, from its `config` table in
+    /// `[hooks]`.
+    pub config: Option<Value>,
 }
 
 impl ReleaseContext {
+    /// Metadata key of the files attached to the release, as an array of
+    /// paths relative to the repository root.
+    pub const ASSETS_METADATA: &'static str = "assets";
+
     /// Creates a new release context.
     #[must_use]
     pub fn new(
@@ -53,8 +87,16 @@ impl ReleaseContext {
             bump_type,
             commits,
             changelog: None,
+            tag_name: None,
+            package: None,
+            repository_url: None,
+            branch: None,
+            files: Vec::new(),
+            commit_files: Vec::new(),
+            error: None,
             dry_run: false,
             metadata: HashMap::new(),
+            config: None,
         }
     }
 
@@ -65,6 +107,40 @@ impl ReleaseContext {
         self
     }
 
+    /// Sets the name of the release tag.
+    #[must_use]
+    pub fn with_tag_name(mut self, tag_name: impl Into<String>) -> Self {
+        self.tag_name = Some(tag_name.into());
+        self
+    }
+
+    /// Sets the package name of the `{package}` tag placeholder.
+    #[must_use]
+    pub fn with_package(mut self, package: Option<String>) -> Self {
+        self.package = package;
+        self
+    }
+
+    /// Sets the web URL of the repository.
+    #[must_use]
+    pub fn with_repository_url(mut self, url: Option<String>) -> Self {
+        self.repository_url = url;
+        self
+    }
+
+    /// Sets the name of the current branch.
+    #[must_use]
+    pub fn with_branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Returns true if the next version is a prerelease.
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        !self.next_version.pre.is_empty()
+    }
+
     /// Gets a metadata value.
     #[must_use]
     pub fn get_metadata(&self, key: &str) -> Option<&Value> {
@@ -76,13 +152,55 @@ impl ReleaseContext {
         self.metadata.insert(key.into(), value);
     }
 
-    /// Returns the version tag string (e.g., "v1.2.3").
+    /// Stages an extra file in the release commit.
+    pub fn add_commit_file(&mut self, path: impl Into<String>) {
+        self.commit_files.push(path.into());
+    }
+
+    /// Attaches a file to the release, for the hooks uploading assets.
+    pub fn add_asset(&mut self, path: impl Into<String>) {
+        let assets = self
+            .metadata
+            .entry(Self::ASSETS_METADATA.to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(assets) = assets {
+            assets.push(Value::String(path.into()));
+        }
+    }
+
+    /// Returns the files attached to the release by hooks.
     #[must_use]
-    pub fn tag(&self, prefix: &str) -> String {
-        format!("{prefix}{}", self.next_version)
+    pub fn assets(&self) -> Vec<&str> {
+        self.get_metadata(Self::ASSETS_METADATA)
+            .and_then(Value::as_array)
+            .map(|assets| assets.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the version tag string (e.g., "v1.2.3"): the name of the
+    /// release tag when known, the tag template rendered for the next version
+    /// otherwise.
+    #[must_use]
+    pub fn tag(&self, template: &str) -> String {
+        self.tag_name
+            .clone()
+            .unwrap_or_else(|| format_tag(template, &self.next_version, self.package.as_deref()))
     }
 }
 
+/// Renders a tag name template such as `v{version}` or `{package}@{version}`
+/// for a version, with its `{version}`, `{major}`, `{minor}`, `{patch}` and
+/// `{package}` placeholders. Without a package, `{package}` renders empty.
+#[must_use]
+pub fn format_tag(template: &str, version: &Version, package: Option<&str>) -> String {
+    template
+        .replace("{package}", package.unwrap_or_default())
+        .replace("{version}", &version.to_string())
+        .replace("{major}", &version.major.to_string())
+        .replace("{minor}", &version.minor.to_string())
+        .replace("{patch}", &version.patch.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,22 +290,99 @@ mod tests {
         assert_eq!(ctx.get_metadata("key"), Some(&json!("second")));
     }
 
+    #[test]
+    fn test_assets() {
+        let mut ctx = create_context();
+        assert!(ctx.assets().is_empty());
+
+        ctx.add_asset("target/sbom/sbom.cdx.json");
+        ctx.add_asset("target/sbom/provenance.intoto.jsonl");
+        assert_eq!(
+            ctx.assets(),
+            [
+                "target/sbom/sbom.cdx.json",
+                "target/sbom/provenance.intoto.jsonl"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commit_files() {
+        let mut ctx = create_context();
+        assert!(ctx.commit_files.is_empty());
+
+        ctx.add_commit_file("Cargo.lock");
+        assert_eq!(ctx.commit_files, ["Cargo.lock"]);
+    }
+
     #[test]
     fn test_tag_with_v_prefix() {
         let ctx = create_context();
-        assert_eq!(ctx.tag("v"), "v1.1.0");
+        assert_eq!(ctx.tag("v{version}"), "v1.1.0");
     }
 
     #[test]
     fn test_tag_with_release_prefix() {
         let ctx = create_context();
-        assert_eq!(ctx.tag("release-"), "release-1.1.0");
+        assert_eq!(ctx.tag("release-{version}"), "release-1.1.0");
     }
 
     #[test]
     fn test_tag_with_empty_prefix() {
         let ctx = create_context();
-        assert_eq!(ctx.tag(""), "1.1.0");
+        assert_eq!(ctx.tag("{version}"), "1.1.0");
+    }
+
+    #[test]
+    fn test_tag_with_components() {
+        let ctx = create_context();
+        assert_eq!(ctx.tag("release/{major}.{minor}"), "release/1.1");
+    }
+
+    #[test]
+    fn test_tag_with_package() {
+        let ctx = create_context();
+        assert_eq!(ctx.tag("{package}@{version}"), "@1.1.0");
+        let ctx = ctx.with_package(Some("app".to_string()));
+        assert_eq!(ctx.tag("{package}@{version}"), "app@1.1.0");
+        assert_eq!(
+            format_tag("{package}-v{major}", &Version::new(2, 0, 0), Some("web")),
+            "web-v2"
+        );
+    }
+
+    #[test]
+    fn test_tag_name_overrides_template() {
+        let ctx = create_context().with_tag_name("app@1.1.0");
+        assert_eq!(ctx.tag("v{version}"), "app@1.1.0");
+        assert_eq!(ctx.tag_name.as_deref(), Some("app@1.1.0"));
+    }
+
+    #[test]
+    fn test_repository_url() {
+        let ctx = create_context();
+        assert!(ctx.repository_url.is_none());
+        let ctx = ctx.with_repository_url(Some("https://github.com/owner/repo".to_string()));
+        assert_eq!(
+            ctx.repository_url.as_deref(),
+            Some("https://github.com/owner/repo")
+        );
+    }
+
+    #[test]
+    fn test_branch() {
+        let ctx = create_context();
+        assert!(ctx.branch.is_none());
+        let ctx = ctx.with_branch(Some("main".to_string()));
+        assert_eq!(ctx.branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_is_prerelease() {
+        let mut ctx = create_context();
+        assert!(!ctx.is_prerelease());
+        ctx.next_version = Version::parse("1.1.0-beta.1").unwrap();
+        assert!(ctx.is_prerelease());
     }
 
     #[test]
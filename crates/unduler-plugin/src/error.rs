@@ -17,6 +17,10 @@ pub enum PluginError {
     #[error("plugin execution failed: {0}")]
     ExecutionFailed(String),
 
+    /// A hook rejected the planned release.
+    #[error("release vetoed: {0}")]
+    Vetoed(String),
+
     /// Configuration error.
     #[error("plugin configuration error: {0}")]
     ConfigError(String),
@@ -54,6 +58,12 @@ mod tests {
         assert_eq!(err.to_string(), "plugin execution failed: crash");
     }
 
+    #[test]
+    fn test_vetoed_display() {
+        let err = PluginError::Vetoed("no releases on Fridays".to_string());
+        assert_eq!(err.to_string(), "release vetoed: no releases on Fridays");
+    }
+
     #[test]
     fn test_config_error_display() {
         let err = PluginError::ConfigError("invalid value".to_string());
@@ -6,15 +6,22 @@
 //! - [`BumpStrategy`]: Determines version bump type
 //! - [`ChangelogFormatter`]: Formats changelog output
 //! - [`ReleaseHook`]: Lifecycle hooks during release
+//! - [`AsyncReleaseHook`]: Lifecycle hooks performing asynchronous work
 
 mod context;
 mod error;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod traits;
 
-pub use context::ReleaseContext;
+pub use context::{ReleaseContext, format_tag};
 pub use error::{PluginError, PluginResult};
 pub use traits::Plugin;
-pub use traits::bumper::{BumpStrategy, BumpType};
-pub use traits::formatter::{ChangelogFormatter, FormatterConfig, Release};
-pub use traits::hook::ReleaseHook;
-pub use traits::parser::CommitParser;
+pub use traits::async_hook::{AsyncReleaseHook, BoxFuture};
+pub use traits::bumper::{BumpReason, BumpStrategy, BumpType};
+pub use traits::formatter::{
+    ChangelogFormatter, FormatterConfig, FormatterSection, ListBullet, MessageCase, Release,
+    TrailingPunctuation, wrap_line,
+};
+pub use traits::hook::{HookStage, ReleaseHook};
+pub use traits::parser::{CommitParser, CompositeParser};
@@ -0,0 +1,22 @@
+//! Helpers for testing plugins, enabled by the `test-util` feature.
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// Polls a future once and returns its output, for futures completing
+/// without awaiting anything, such as hooks skipping their work.
+///
+/// # Panics
+///
+/// Panics if the future is pending.
+pub fn poll_once<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
@@ -0,0 +1,161 @@
+//! Asynchronous release hook trait.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use super::Plugin;
+use super::hook::HookStage;
+use crate::{PluginResult, ReleaseContext};
+
+/// A boxed future, as returned by [`AsyncReleaseHook`] methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Lifecycle hooks performing asynchronous work, such as network calls.
+///
+/// Asynchronous hooks run at the same stages as
+/// [`ReleaseHook`](super::hook::ReleaseHook)s, after them. The asynchronous
+/// hooks of a stage run concurrently, so they only get shared access to the
+/// context and cannot modify the release.
+pub trait AsyncReleaseHook: Plugin {
+    /// Called once the release is planned, before anything is modified.
+    ///
+    /// Return [`PluginError::Vetoed`](crate::PluginError::Vetoed) to reject
+    /// the release, e.g. while the CI checks of the commit are failing.
+    fn on_pre_release<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called before version files are modified.
+    fn on_pre_bump<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called after version files are modified.
+    fn on_post_bump<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called before the release commit is created.
+    fn on_pre_commit<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called before the git tag is created.
+    fn on_pre_tag<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called after the git tag is created.
+    ///
+    /// Use this for publishing, e.g. uploading release assets.
+    fn on_post_tag<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called after the release is pushed, once every step succeeded.
+    ///
+    /// Use this for announcements.
+    fn on_post_release<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Called after a step of the release failed, with its error in
+    /// [`ReleaseContext::error`].
+    fn on_failure<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl HookStage {
+    /// Returns the future of an asynchronous hook matching this stage.
+    pub fn run_async<'a>(
+        self,
+        hook: &'a dyn AsyncReleaseHook,
+        ctx: &'a ReleaseContext,
+    ) -> BoxFuture<'a, PluginResult<()>> {
+        match self {
+            Self::PreRelease => hook.on_pre_release(ctx),
+            Self::PreBump => hook.on_pre_bump(ctx),
+            Self::PostBump => hook.on_post_bump(ctx),
+            Self::PreCommit => hook.on_pre_commit(ctx),
+            Self::PreTag => hook.on_pre_tag(ctx),
+            Self::PostTag => hook.on_post_tag(ctx),
+            Self::PostRelease => hook.on_post_release(ctx),
+            Self::Failure => hook.on_failure(ctx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::poll_once;
+    use crate::{BumpType, PluginError};
+    use semver::Version;
+
+    struct MinimalHook;
+
+    impl Plugin for MinimalHook {
+        fn name(&self) -> &'static str {
+            "minimal-async-hook"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl AsyncReleaseHook for MinimalHook {}
+
+    struct FailingHook;
+
+    impl Plugin for FailingHook {
+        fn name(&self) -> &'static str {
+            "failing-async-hook"
+        }
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl AsyncReleaseHook for FailingHook {
+        fn on_post_tag<'a>(&'a self, _ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+            Box::pin(async { Err(PluginError::ExecutionFailed("upload failed".into())) })
+        }
+    }
+
+    fn create_test_context() -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_defaults() {
+        let hook = MinimalHook;
+        let ctx = create_test_context();
+        for stage in [
+            HookStage::PreRelease,
+            HookStage::PreBump,
+            HookStage::PostBump,
+            HookStage::PreCommit,
+            HookStage::PreTag,
+            HookStage::PostTag,
+            HookStage::PostRelease,
+            HookStage::Failure,
+        ] {
+            assert!(poll_once(stage.run_async(&hook, &ctx)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_run_async_dispatches_stage() {
+        let hook = FailingHook;
+        let ctx = create_test_context();
+        assert!(poll_once(HookStage::PreTag.run_async(&hook, &ctx)).is_ok());
+        assert!(poll_once(HookStage::PostTag.run_async(&hook, &ctx)).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Bump strategy trait.
 
+use semver::Version;
 use serde::{Deserialize, Serialize};
 use unduler_commit::ParsedCommit;
 
@@ -52,10 +53,46 @@ impl std::fmt::Display for BumpType {
     }
 }
 
+/// Bump triggered by a commit, with the rule that fired.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BumpReason {
+    /// Hash of the commit.
+    pub hash: String,
+    /// Bump triggered by the commit.
+    pub bump: BumpType,
+    /// Rule that fired (e.g., "breaking change" or "feat is a minor type").
+    pub rule: String,
+}
+
 /// Determines version bump type from parsed commits.
 pub trait BumpStrategy: Plugin {
     /// Determines the bump type based on the given commits.
     fn determine(&self, commits: &[ParsedCommit]) -> BumpType;
+
+    /// Computes the next version directly, bypassing the bump type.
+    ///
+    /// Strategies with their own versioning scheme (calendar versioning,
+    /// build numbers...) override this. Returning `None`, the default, applies
+    /// the bump type returned by [`determine`](Self::determine).
+    fn next_version(&self, _current: &Version, _commits: &[ParsedCommit]) -> Option<Version> {
+        None
+    }
+
+    /// Explains the bump triggered by each commit on top of the current
+    /// version.
+    ///
+    /// The default bumps each commit on its own, without naming a rule.
+    /// Strategies override this to report which rule fired.
+    fn explain(&self, _current: &Version, commits: &[ParsedCommit]) -> Vec<BumpReason> {
+        commits
+            .iter()
+            .map(|commit| BumpReason {
+                hash: commit.hash.clone(),
+                bump: self.determine(std::slice::from_ref(commit)),
+                rule: format!("{} strategy", self.name()),
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
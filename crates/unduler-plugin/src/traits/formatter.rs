@@ -1,5 +1,7 @@
 //! Changelog formatter trait.
 
+use std::fmt::Write;
+
 use chrono::{DateTime, Utc};
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -54,6 +56,59 @@ impl Release {
     }
 }
 
+/// A changelog section listing the commits of some types under a title.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FormatterSection {
+    /// Commit types listed in the section (e.g., `["build", "ci"]`).
+    pub types: Vec<String>,
+
+    /// Title of the section (e.g., "Features").
+    pub title: String,
+}
+
+/// Case of the messages of changelog entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageCase {
+    /// Keep messages as written.
+    #[default]
+    Preserve,
+    /// Capitalize the first letter of messages.
+    Sentence,
+}
+
+/// Trailing punctuation of changelog entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrailingPunctuation {
+    /// Keep entries as written.
+    #[default]
+    Preserve,
+    /// Remove trailing periods.
+    Strip,
+    /// End entries with a period, unless they end with `.`, `!` or `?`.
+    Period,
+}
+
+/// Bullet of changelog list items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListBullet {
+    /// `-`
+    #[default]
+    Dash,
+    /// `*`
+    Asterisk,
+}
+
+impl ListBullet {
+    /// Returns the Markdown marker of the bullet.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Dash => "-",
+            Self::Asterisk => "*",
+        }
+    }
+}
+
 /// Configuration for the changelog formatter.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[allow(clippy::struct_excessive_bools)]
@@ -70,8 +125,133 @@ pub struct FormatterConfig {
     /// Include commit authors in output.
     pub include_authors: bool,
 
+    /// Link `#123` references and pull requests to a GitHub repository.
+    pub link_references: bool,
+
+    /// List the issues closed by the release, from `Closes #123` footers.
+    pub closed_issues: bool,
+
+    /// List the unique authors of the release in a Contributors section.
+    pub contributors: bool,
+
+    /// Open the release with a summary of its commits, contributors and
+    /// breaking changes.
+    pub summary: bool,
+
+    /// List breaking changes and highlighted commits in a Highlights section
+    /// before the per-type sections.
+    pub highlights: bool,
+
+    /// List breaking changes of any type in a Breaking Changes section, with
+    /// the description of their `BREAKING CHANGE:` footer.
+    pub breaking_changes: bool,
+
+    /// Prefix entries with their gitmoji and section headings with the
+    /// emoji of their type.
+    pub show_emoji: bool,
+
+    /// Collapse entries with the same type, scope and message, such as
+    /// cherry-picks, listing the hashes of all their commits.
+    pub deduplicate: bool,
+
+    /// Maximum width of list items, wrapped at spaces when longer.
+    pub line_width: Option<usize>,
+
+    /// Case of entry messages.
+    pub message_case: MessageCase,
+
+    /// Trailing punctuation of entries.
+    pub trailing_punctuation: TrailingPunctuation,
+
+    /// Bullet of list items.
+    pub bullet: ListBullet,
+
     /// Custom type labels (e.g., "feat" -> "Features").
     pub type_labels: std::collections::HashMap<String, String>,
+
+    /// Sections in display order, replacing the default section per type
+    /// when not empty.
+    pub sections: Vec<FormatterSection>,
+
+    /// Commit types left out of the sections.
+    pub hide_types: Vec<String>,
+}
+
+impl FormatterConfig {
+    /// Applies the case and trailing punctuation options to the text of an
+    /// entry.
+    #[must_use]
+    pub fn normalize(&self, text: &str) -> String {
+        let mut text = text.to_string();
+
+        if self.message_case == MessageCase::Sentence
+            && let Some(first) = text.chars().next()
+            && first.is_lowercase()
+        {
+            text = first.to_uppercase().chain(text.chars().skip(1)).collect();
+        }
+
+        match self.trailing_punctuation {
+            TrailingPunctuation::Preserve => {}
+            TrailingPunctuation::Strip => {
+                let len = text.trim_end_matches('.').len();
+                text.truncate(len);
+            }
+            TrailingPunctuation::Period => {
+                if !text.is_empty() && !text.ends_with(['.', '!', '?']) {
+                    text.push('.');
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Wraps a line of a list item at spaces to fit in `width` characters,
+/// starting the lines it continues on with `indent`.
+///
+/// The leading spaces and list marker (`-`, `*` or `+`) stay on the first
+/// line. Words longer than the width are kept whole, and no line is started
+/// with a word read as a list marker or heading.
+#[must_use]
+pub fn wrap_line(line: &str, width: usize, indent: &str) -> String {
+    if line.chars().count() <= width {
+        return line.to_string();
+    }
+
+    let body = line.trim_start_matches(' ');
+    let body = ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|marker| body.strip_prefix(marker))
+        .unwrap_or(body);
+    let mut wrapped = line[..line.len() - body.len()].to_string();
+    let mut current_width = wrapped.chars().count();
+    let mut first = true;
+    for word in body.split(' ') {
+        let word_width = word.chars().count();
+        if !first && current_width + 1 + word_width > width && can_start_line(word) {
+            _ = write!(wrapped, "\n{indent}{word}");
+            current_width = indent.chars().count() + word_width;
+        } else {
+            if !first {
+                wrapped.push(' ');
+                current_width += 1;
+            }
+            wrapped.push_str(word);
+            current_width += word_width;
+        }
+        first = false;
+    }
+    wrapped
+}
+
+/// Returns true if a wrapped line can start with `word`, which is not a list
+/// marker (`-`, `*`, `+`, `1.`) or a heading (`#`).
+fn can_start_line(word: &str) -> bool {
+    let ordered = word
+        .strip_suffix(['.', ')'])
+        .is_some_and(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+    !(matches!(word, "-" | "*" | "+" | "") || ordered || word.chars().all(|c| c == '#'))
 }
 
 /// Formats changelog output.
@@ -84,3 +264,46 @@ pub trait ChangelogFormatter: Plugin {
         "md"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let mut config = FormatterConfig::default();
+        assert_eq!(config.normalize("add login."), "add login.");
+
+        config.message_case = MessageCase::Sentence;
+        config.trailing_punctuation = TrailingPunctuation::Strip;
+        assert_eq!(config.normalize("add login."), "Add login");
+        assert_eq!(config.normalize("`api` is faster"), "`api` is faster");
+
+        config.trailing_punctuation = TrailingPunctuation::Period;
+        assert_eq!(
+            config.normalize("éviter les doublons"),
+            "Éviter les doublons."
+        );
+        assert_eq!(config.normalize("it works!"), "It works!");
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        assert_eq!(wrap_line("- short entry", 20, "  "), "- short entry");
+        assert_eq!(
+            wrap_line("- add a rather long entry to the changelog", 20, "  "),
+            "- add a rather long\n  entry to the\n  changelog"
+        );
+        assert_eq!(
+            wrap_line("- see https://example.com/a/very/long/path", 20, "  "),
+            "- see\n  https://example.com/a/very/long/path"
+        );
+        // A line starting with `-` would become a nested list
+        assert_eq!(wrap_line("- keep a - b", 9, "  "), "- keep a -\n  b");
+        // The marker stays with the first word
+        assert_eq!(
+            wrap_line("  * https://example.com/long", 10, "    "),
+            "  * https://example.com/long"
+        );
+    }
+}
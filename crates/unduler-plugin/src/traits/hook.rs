@@ -1,18 +1,109 @@
 //! Release hook trait.
 
+use std::fmt;
+
 use super::Plugin;
 use crate::{PluginResult, ReleaseContext};
 
+/// Point of the release process at which hooks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookStage {
+    /// Once the release is planned, before anything is modified.
+    PreRelease,
+    /// Before version files are modified.
+    PreBump,
+    /// After version files are modified.
+    PostBump,
+    /// Before the release commit is created.
+    PreCommit,
+    /// Before the git tag is created.
+    PreTag,
+    /// After the git tag is created.
+    PostTag,
+    /// After the release is pushed.
+    PostRelease,
+    /// After a step of the release failed.
+    Failure,
+}
+
+impl HookStage {
+    /// Runs the method of a hook matching this stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the hook.
+    pub fn run(self, hook: &dyn ReleaseHook, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        match self {
+            Self::PreRelease => hook.on_pre_release(ctx),
+            Self::PreBump => hook.on_pre_bump(ctx),
+            Self::PostBump => hook.on_post_bump(ctx),
+            Self::PreCommit => hook.on_pre_commit(ctx),
+            Self::PreTag => hook.on_pre_tag(ctx),
+            Self::PostTag => hook.on_post_tag(ctx),
+            Self::PostRelease => hook.on_post_release(ctx),
+            Self::Failure => hook.on_failure(ctx),
+        }
+    }
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PreRelease => write!(f, "pre_release"),
+            Self::PreBump => write!(f, "pre_bump"),
+            Self::PostBump => write!(f, "post_bump"),
+            Self::PreCommit => write!(f, "pre_commit"),
+            Self::PreTag => write!(f, "pre_tag"),
+            Self::PostTag => write!(f, "post_tag"),
+            Self::PostRelease => write!(f, "post_release"),
+            Self::Failure => write!(f, "failure"),
+        }
+    }
+}
+
 /// Lifecycle hooks during the release process.
 ///
 /// Hooks are executed at specific points in the release pipeline:
-/// 1. `pre_bump` - Before version files are modified
-/// 2. `post_bump` - After version files are modified
-/// 3. `pre_commit` - Before the release commit is created
-/// 4. `pre_tag` - Before the git tag is created
-/// 5. `post_tag` - After the git tag is created
+/// 1. `pre_release` - Once the release is planned, before anything is modified
+/// 2. `pre_bump` - Before version files are modified
+/// 3. `post_bump` - After version files are modified
+/// 4. `pre_commit` - Before the release commit is created
+/// 5. `pre_tag` - Before the git tag is created
+/// 6. `post_tag` - After the git tag is created
+/// 7. `post_release` - After the release is pushed
+///
+/// If a step fails once the release is planned, `failure` hooks run with
+/// the error instead of the remaining stages.
+///
+/// # Modifying the release
+///
+/// Hooks get mutable access to the context, and the pipeline reads back:
+/// - `next_version` after the `pre_release` and `pre_bump` hooks, to
+///   release another version, e.g. for odd/even versioning schemes. The
+///   changelog section is then formatted again for that version;
+/// - `changelog` after the `post_bump` hooks, written as the changelog
+///   section;
+/// - `commit_files` after the `pre_commit` hooks, staged in the release
+///   commit;
+/// - `metadata`, passed on to later hooks.
+///
+/// Other fields only describe the release.
 #[allow(unused_variables)]
 pub trait ReleaseHook: Plugin {
+    /// Called once the release is planned, before anything is modified.
+    ///
+    /// The context holds the new version, the changelog section and the
+    /// files the release writes. Use this to approve the release, e.g.
+    /// rejecting releases on Fridays or while CI checks are failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PluginError::Vetoed`](crate::PluginError::Vetoed) with the
+    /// reason to reject the release, which then changes nothing.
+    fn on_pre_release(&self, _ctx: &mut ReleaseContext) -> PluginResult<()> {
+        Ok(())
+    }
+
     /// Called before version files are modified.
     ///
     /// Use this for validation or preparation.
@@ -67,6 +158,30 @@ pub trait ReleaseHook: Plugin {
     fn on_post_tag(&self, _ctx: &mut ReleaseContext) -> PluginResult<()> {
         Ok(())
     }
+
+    /// Called after the release is pushed, once every step succeeded.
+    ///
+    /// Use this to announce the release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if post-release operations fail.
+    fn on_post_release(&self, _ctx: &mut ReleaseContext) -> PluginResult<()> {
+        Ok(())
+    }
+
+    /// Called after a step of the release failed, with its error in
+    /// [`ReleaseContext::error`].
+    ///
+    /// Use this to report the failure. Errors of failure hooks are logged,
+    /// the release failing with the error of the step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the failure cannot be reported.
+    fn on_failure(&self, _ctx: &mut ReleaseContext) -> PluginResult<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +214,13 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_default_pre_release() {
+        let hook = MinimalHook;
+        let mut ctx = create_test_context();
+        assert!(hook.on_pre_release(&mut ctx).is_ok());
+    }
+
     #[test]
     fn test_default_pre_bump() {
         let hook = MinimalHook;
@@ -133,4 +255,35 @@ mod tests {
         let mut ctx = create_test_context();
         assert!(hook.on_post_tag(&mut ctx).is_ok());
     }
+
+    #[test]
+    fn test_default_post_release() {
+        let hook = MinimalHook;
+        let mut ctx = create_test_context();
+        assert!(hook.on_post_release(&mut ctx).is_ok());
+    }
+
+    #[test]
+    fn test_default_failure() {
+        let hook = MinimalHook;
+        let mut ctx = create_test_context();
+        ctx.error = Some("push rejected".to_string());
+        assert!(hook.on_failure(&mut ctx).is_ok());
+    }
+
+    #[test]
+    fn test_stage_display() {
+        assert_eq!(HookStage::PreRelease.to_string(), "pre_release");
+        assert_eq!(HookStage::PreBump.to_string(), "pre_bump");
+        assert_eq!(HookStage::PostTag.to_string(), "post_tag");
+        assert_eq!(HookStage::PostRelease.to_string(), "post_release");
+        assert_eq!(HookStage::Failure.to_string(), "failure");
+    }
+
+    #[test]
+    fn test_stage_run() {
+        let hook = MinimalHook;
+        let mut ctx = create_test_context();
+        assert!(HookStage::PreCommit.run(&hook, &mut ctx).is_ok());
+    }
 }
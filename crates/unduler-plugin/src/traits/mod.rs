@@ -1,5 +1,6 @@
 //! Plugin traits.
 
+pub mod async_hook;
 pub mod bumper;
 pub mod formatter;
 pub mod hook;
@@ -11,6 +11,15 @@ pub trait CommitParser: Plugin {
     /// Returns `None` if the commit message doesn't match the expected format.
     fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit>;
 
+    /// Parses raw commits, returning the result of [`parse`](Self::parse)
+    /// for each of them, in order.
+    ///
+    /// Parsers with a costly call per commit, like WASM plugins, parse them
+    /// in fewer calls. Default implementation parses them one by one.
+    fn parse_batch(&self, raws: &[RawCommit]) -> Vec<Option<ParsedCommit>> {
+        raws.iter().map(|raw| self.parse(raw)).collect()
+    }
+
     /// Returns whether this parser can handle the given commit.
     ///
     /// This is a quick check that can be used before attempting to parse.
@@ -19,3 +28,201 @@ pub trait CommitParser: Plugin {
         self.parse(raw).is_some()
     }
 }
+
+/// Tries several parsers in order for each commit.
+///
+/// The first parser that parses a commit wins, which allows mixing
+/// commit conventions in the same history.
+pub struct CompositeParser {
+    parsers: Vec<Box<dyn CommitParser>>,
+}
+
+impl CompositeParser {
+    /// Creates a composite parser from parsers in priority order.
+    #[must_use]
+    pub fn new(parsers: Vec<Box<dyn CommitParser>>) -> Self {
+        Self { parsers }
+    }
+
+    /// Appends a parser with the lowest priority.
+    #[must_use]
+    pub fn with_parser(mut self, parser: Box<dyn CommitParser>) -> Self {
+        self.parsers.push(parser);
+        self
+    }
+
+    /// Returns the parsers in priority order.
+    #[must_use]
+    pub fn parsers(&self) -> &[Box<dyn CommitParser>] {
+        &self.parsers
+    }
+}
+
+impl Plugin for CompositeParser {
+    fn name(&self) -> &'static str {
+        "composite"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Tries several parsers in order"
+    }
+}
+
+impl CommitParser for CompositeParser {
+    fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+        self.parsers.iter().find_map(|parser| parser.parse(raw))
+    }
+
+    /// Parses the commits in a batch per parser, each parser getting the
+    /// commits the previous ones left unparsed.
+    fn parse_batch(&self, raws: &[RawCommit]) -> Vec<Option<ParsedCommit>> {
+        let mut parsed: Vec<Option<ParsedCommit>> = raws.iter().map(|_| None).collect();
+        for parser in &self.parsers {
+            let pending: Vec<usize> = (0..raws.len()).filter(|&i| parsed[i].is_none()).collect();
+            if pending.is_empty() {
+                break;
+            }
+            let commits: Vec<RawCommit> = pending.iter().map(|&i| raws[i].clone()).collect();
+            for (i, commit) in pending.into_iter().zip(parser.parse_batch(&commits)) {
+                parsed[i] = commit;
+            }
+        }
+        parsed
+    }
+
+    fn can_parse(&self, raw: &RawCommit) -> bool {
+        self.parsers.iter().any(|parser| parser.can_parse(raw))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use chrono::Utc;
+
+    /// Parses commits starting with the given prefix as the given type.
+    struct PrefixParser {
+        prefix: &'static str,
+        r#type: &'static str,
+    }
+
+    impl Plugin for PrefixParser {
+        fn name(&self) -> &'static str {
+            self.r#type
+        }
+
+        fn version(&self) -> &'static str {
+            "1.0.0"
+        }
+    }
+
+    impl CommitParser for PrefixParser {
+        fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+            let message = raw.message.strip_prefix(self.prefix)?;
+            Some(
+                ParsedCommit::builder(&raw.hash, self.r#type)
+                    .message(message.trim())
+                    .build(),
+            )
+        }
+    }
+
+    /// Counts the parse calls of the parser it wraps.
+    struct CountingParser {
+        inner: PrefixParser,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Plugin for CountingParser {
+        fn name(&self) -> &'static str {
+            self.inner.name()
+        }
+
+        fn version(&self) -> &'static str {
+            self.inner.version()
+        }
+    }
+
+    impl CommitParser for CountingParser {
+        fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.inner.parse(raw)
+        }
+    }
+
+    fn raw(message: &str) -> RawCommit {
+        RawCommit::new("abc123", message, "Test", "test@test.com", Utc::now())
+    }
+
+    fn composite() -> CompositeParser {
+        CompositeParser::new(vec![Box::new(PrefixParser {
+            prefix: "✨",
+            r#type: "feat",
+        })])
+        .with_parser(Box::new(PrefixParser {
+            prefix: "fix:",
+            r#type: "fix",
+        }))
+    }
+
+    #[test]
+    fn test_composite_first_match_wins() {
+        let parser = composite();
+        let parsed = parser.parse(&raw("✨ add feature")).unwrap();
+        assert_eq!(parsed.r#type, "feat");
+        assert_eq!(parsed.message, "add feature");
+    }
+
+    #[test]
+    fn test_composite_falls_back() {
+        let parser = composite();
+        let parsed = parser.parse(&raw("fix: bug")).unwrap();
+        assert_eq!(parsed.r#type, "fix");
+    }
+
+    #[test]
+    fn test_composite_no_match() {
+        let parser = composite();
+        assert!(parser.parse(&raw("random message")).is_none());
+        assert!(!parser.can_parse(&raw("random message")));
+    }
+
+    #[test]
+    fn test_composite_empty() {
+        let parser = CompositeParser::new(Vec::new());
+        assert!(parser.parsers().is_empty());
+        assert!(parser.parse(&raw("fix: bug")).is_none());
+    }
+
+    #[test]
+    fn test_composite_parses_each_commit_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let parser = CompositeParser::new(vec![Box::new(CountingParser {
+            inner: PrefixParser {
+                prefix: "fix:",
+                r#type: "fix",
+            },
+            calls: Arc::clone(&calls),
+        })]);
+
+        assert!(parser.parse(&raw("fix: bug")).is_some());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_composite_parse_batch() {
+        let parser = composite();
+        let parsed = parser.parse_batch(&[raw("fix: bug"), raw("random"), raw("✨ feature")]);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].as_ref().unwrap().r#type, "fix");
+        assert!(parsed[1].is_none());
+        assert_eq!(parsed[2].as_ref().unwrap().r#type, "feat");
+    }
+}
@@ -0,0 +1,517 @@
+//! WASM plugins behind the native plugin traits, so that a release pipeline
+//! runs them like built-in plugins.
+//!
+//! Each adapter is named after the plugin name of the configuration, which
+//! selects it in the pipeline. The native traits return `'static` metadata:
+//! plugins are loaded once per run, so their metadata is leaked.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use semver::Version;
+use serde_json::Value;
+use unduler_commit::{ParsedCommit, RawCommit};
+use unduler_plugin::{
+    BumpStrategy, BumpType, ChangelogFormatter, CommitParser, FormatterConfig, Plugin, PluginError,
+    PluginResult, Release, ReleaseContext, ReleaseHook,
+};
+
+use crate::hook::{self, ActionResults, HookResult};
+use crate::{SharedPlugin, WasmBumper, WasmFormatter, WasmHook, WasmParser, WasmResult};
+
+/// Leaks a string, for the `'static` metadata of the native traits.
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// Locks a plugin instance, recovering it from a panicked call.
+pub(crate) fn lock<T>(plugin: &Mutex<T>) -> MutexGuard<'_, T> {
+    plugin
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Metadata of a WASM plugin.
+struct Metadata {
+    name: &'static str,
+    version: &'static str,
+    description: &'static str,
+}
+
+impl Metadata {
+    /// Creates the metadata of a plugin named `name`, with its reported
+    /// version and description.
+    fn new(name: &str, version: String, description: String) -> Self {
+        Self {
+            name: leak(name.to_string()),
+            version: leak(version),
+            description: leak(description),
+        }
+    }
+}
+
+/// Parser plugin running as a [`CommitParser`].
+pub struct WasmCommitParser {
+    metadata: Metadata,
+    parser: SharedPlugin<WasmParser>,
+}
+
+impl WasmCommitParser {
+    /// Wraps a parser plugin, named `name` in the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn new(name: &str, parser: WasmParser) -> WasmResult<Self> {
+        Self::from_shared(name, Arc::new(Mutex::new(parser)))
+    }
+
+    /// Wraps a parser plugin shared with other users, e.g. loaded by a
+    /// [`PluginHost`](crate::PluginHost), named `name` in the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn from_shared(name: &str, parser: SharedPlugin<WasmParser>) -> WasmResult<Self> {
+        let info = lock(&parser).info()?;
+        Ok(Self {
+            metadata: Metadata::new(name, info.version, info.description),
+            parser,
+        })
+    }
+}
+
+impl Plugin for WasmCommitParser {
+    fn name(&self) -> &'static str {
+        self.metadata.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.metadata.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.metadata.description
+    }
+}
+
+impl CommitParser for WasmCommitParser {
+    /// Parses a commit, skipping it when the plugin call fails.
+    fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+        match lock(&self.parser).parse(&raw.into()) {
+            Ok(parsed) => parsed.map(Into::into),
+            Err(error) => {
+                tracing::warn!(plugin = self.name(), %error, "failed to parse commit {}", raw.hash);
+                None
+            }
+        }
+    }
+
+    /// Parses commits through the `batch-parser` interface when the plugin
+    /// exports it, skipping them all when the plugin call fails.
+    fn parse_batch(&self, raws: &[RawCommit]) -> Vec<Option<ParsedCommit>> {
+        let commits: Vec<_> = raws.iter().map(Into::into).collect();
+        match lock(&self.parser).parse_batch(&commits) {
+            Ok(parsed) => parsed
+                .into_iter()
+                .map(|commit| commit.map(Into::into))
+                .collect(),
+            Err(error) => {
+                tracing::warn!(plugin = self.name(), %error, "failed to parse {} commits", raws.len());
+                raws.iter().map(|_| None).collect()
+            }
+        }
+    }
+}
+
+/// Bumper plugin running as a [`BumpStrategy`].
+///
+/// The next version comes from the `version-bumper` interface when the
+/// plugin exports it, and from the bump type otherwise.
+pub struct WasmBumpStrategy {
+    metadata: Metadata,
+    bumper: SharedPlugin<WasmBumper>,
+}
+
+impl WasmBumpStrategy {
+    /// Wraps a bumper plugin, named `name` in the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn new(name: &str, bumper: WasmBumper) -> WasmResult<Self> {
+        Self::from_shared(name, Arc::new(Mutex::new(bumper)))
+    }
+
+    /// Wraps a bumper plugin shared with other users, named `name` in the
+    /// pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn from_shared(name: &str, bumper: SharedPlugin<WasmBumper>) -> WasmResult<Self> {
+        let info = lock(&bumper).info()?;
+        Ok(Self {
+            metadata: Metadata::new(name, info.version, info.description),
+            bumper,
+        })
+    }
+}
+
+impl Plugin for WasmBumpStrategy {
+    fn name(&self) -> &'static str {
+        self.metadata.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.metadata.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.metadata.description
+    }
+}
+
+impl BumpStrategy for WasmBumpStrategy {
+    /// Determines the bump type, logging an error and bumping nothing when
+    /// the plugin call fails.
+    fn determine(&self, commits: &[ParsedCommit]) -> BumpType {
+        let commits: Vec<_> = commits.iter().map(Into::into).collect();
+        match lock(&self.bumper).determine(&commits) {
+            Ok(bump) => bump.into(),
+            Err(error) => {
+                tracing::error!(plugin = self.name(), %error, "failed to determine the bump");
+                BumpType::None
+            }
+        }
+    }
+
+    /// Computes the next version, logging an error and deferring to the bump
+    /// type when the plugin call fails or returns an invalid version.
+    fn next_version(&self, current: &Version, commits: &[ParsedCommit]) -> Option<Version> {
+        let mut bumper = lock(&self.bumper);
+        if !bumper.has_next_version() {
+            return None;
+        }
+
+        let commits: Vec<_> = commits.iter().map(Into::into).collect();
+        let next = bumper
+            .next_version(&current.into(), &commits)
+            .map_err(|error| error.to_string())
+            .and_then(|next| {
+                next.map(Version::try_from)
+                    .transpose()
+                    .map_err(|e| e.to_string())
+            });
+        next.unwrap_or_else(|error| {
+            tracing::error!(plugin = self.name(), %error, "failed to compute the next version");
+            None
+        })
+    }
+}
+
+/// Formatter plugin running as a [`ChangelogFormatter`].
+pub struct WasmChangelogFormatter {
+    metadata: Metadata,
+    extension: &'static str,
+    formatter: SharedPlugin<WasmFormatter>,
+}
+
+impl WasmChangelogFormatter {
+    /// Wraps a formatter plugin, named `name` in the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information or file extension cannot
+    /// be read.
+    pub fn new(name: &str, formatter: WasmFormatter) -> WasmResult<Self> {
+        Self::from_shared(name, Arc::new(Mutex::new(formatter)))
+    }
+
+    /// Wraps a formatter plugin shared with other users, named `name` in
+    /// the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information or file extension cannot
+    /// be read.
+    pub fn from_shared(name: &str, formatter: SharedPlugin<WasmFormatter>) -> WasmResult<Self> {
+        let (info, extension) = {
+            let mut plugin = lock(&formatter);
+            (plugin.info()?, leak(plugin.extension()?))
+        };
+        Ok(Self {
+            metadata: Metadata::new(name, info.version, info.description),
+            extension,
+            formatter,
+        })
+    }
+}
+
+impl Plugin for WasmChangelogFormatter {
+    fn name(&self) -> &'static str {
+        self.metadata.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.metadata.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.metadata.description
+    }
+}
+
+impl ChangelogFormatter for WasmChangelogFormatter {
+    /// Formats a release, logging an error and returning an empty section
+    /// when the plugin call fails.
+    fn format(&self, release: &Release, config: &FormatterConfig) -> String {
+        lock(&self.formatter)
+            .format(&release.into(), &config.into())
+            .unwrap_or_else(|error| {
+                tracing::error!(plugin = self.name(), %error, "failed to format release");
+                String::new()
+            })
+    }
+
+    fn extension(&self) -> &'static str {
+        self.extension
+    }
+}
+
+/// Stage function of a hook plugin.
+type Stage = fn(&mut WasmHook, &hook::ReleaseContext) -> WasmResult<(HookResult, ActionResults)>;
+
+/// Hook plugin running as a [`ReleaseHook`].
+///
+/// Metadata updates of the plugin are stored in the release context, as
+/// strings, and so are the version, changelog section and commit files the
+/// plugin returns. An unsuccessful result fails the stage, or vetoes the
+/// release from `on_pre_release`, and so does an action that failed or was
+/// rejected by the sandbox policy.
+pub struct WasmReleaseHook {
+    metadata: Metadata,
+    hook: SharedPlugin<WasmHook>,
+}
+
+impl WasmReleaseHook {
+    /// Wraps a hook plugin, named `name` in the pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn new(name: &str, hook: WasmHook) -> WasmResult<Self> {
+        Self::from_shared(name, Arc::new(Mutex::new(hook)))
+    }
+
+    /// Wraps a hook plugin shared with other users, named `name` in the
+    /// pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin information cannot be read.
+    pub fn from_shared(name: &str, hook: SharedPlugin<WasmHook>) -> WasmResult<Self> {
+        let info = lock(&hook).info()?;
+        Ok(Self {
+            metadata: Metadata::new(name, info.version, info.description),
+            hook,
+        })
+    }
+
+    /// Runs a stage of the plugin, returning the reason of an unsuccessful
+    /// result.
+    fn run(&self, stage: Stage, ctx: &mut ReleaseContext) -> PluginResult<Option<String>> {
+        let (mut result, actions) = stage(&mut lock(&self.hook), &(&*ctx).into())
+            .map_err(|error| PluginError::ExecutionFailed(error.to_string()))?;
+
+        update_context(self.name(), &mut result, ctx)?;
+        if !result.success {
+            return Ok(Some(
+                result
+                    .error_message
+                    .unwrap_or_else(|| format!("hook '{}' failed", self.name())),
+            ));
+        }
+
+        if let Some(violation) = actions.violations.first() {
+            return Err(PluginError::ExecutionFailed(violation.to_string()));
+        }
+        if let Some(error) = actions.errors.first() {
+            return Err(PluginError::ExecutionFailed(error.clone()));
+        }
+        if let Some(output) = actions
+            .command_outputs
+            .iter()
+            .find(|output| output.exit_code != 0)
+        {
+            return Err(PluginError::ExecutionFailed(format!(
+                "command exited with code {}: {}",
+                output.exit_code,
+                output.stderr.trim()
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Runs a stage of the plugin, failing on an unsuccessful result.
+    fn run_stage(&self, stage: Stage, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        match self.run(stage, ctx)? {
+            Some(reason) => Err(PluginError::ExecutionFailed(reason)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Stores the metadata updates, version, changelog section and commit files
+/// of a hook result in the release context.
+fn update_context(
+    hook: &str,
+    result: &mut HookResult,
+    ctx: &mut ReleaseContext,
+) -> PluginResult<()> {
+    for (key, value) in result.metadata_updates.drain(..) {
+        ctx.metadata.insert(key, Value::String(value));
+    }
+    if let Some(version) = result.next_version.take() {
+        ctx.next_version = version.try_into().map_err(|error| {
+            PluginError::ExecutionFailed(format!(
+                "hook '{hook}' returned an invalid version: {error}"
+            ))
+        })?;
+    }
+    if let Some(changelog) = result.changelog.take() {
+        ctx.changelog = Some(changelog);
+    }
+    ctx.commit_files.append(&mut result.commit_files);
+    Ok(())
+}
+
+impl Plugin for WasmReleaseHook {
+    fn name(&self) -> &'static str {
+        self.metadata.name
+    }
+
+    fn version(&self) -> &'static str {
+        self.metadata.version
+    }
+
+    fn description(&self) -> &'static str {
+        self.metadata.description
+    }
+}
+
+impl ReleaseHook for WasmReleaseHook {
+    fn on_pre_release(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        match self.run(WasmHook::on_pre_release, ctx)? {
+            Some(reason) => Err(PluginError::Vetoed(reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn on_pre_bump(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_pre_bump, ctx)
+    }
+
+    fn on_post_bump(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_post_bump, ctx)
+    }
+
+    fn on_pre_commit(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_pre_commit, ctx)
+    }
+
+    fn on_pre_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_pre_tag, ctx)
+    }
+
+    fn on_post_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_post_tag, ctx)
+    }
+
+    fn on_post_release(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_post_release, ctx)
+    }
+
+    fn on_failure(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        self.run_stage(WasmHook::on_failure, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_result() -> HookResult {
+        HookResult {
+            success: true,
+            error_message: None,
+            metadata_updates: vec![("ticket".to_string(), "PROJ-1".to_string())],
+            actions: vec![],
+            next_version: None,
+            changelog: None,
+            commit_files: vec![],
+        }
+    }
+
+    fn create_context() -> ReleaseContext {
+        ReleaseContext::new(
+            "/repo",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_update_context() {
+        let mut ctx = create_context();
+        let mut result = create_result();
+        result.next_version = Some(hook::Version {
+            major: 1,
+            minor: 2,
+            patch: 0,
+            pre: None,
+            build: None,
+        });
+        result.changelog = Some("### Added\n\n- Feature".to_string());
+        result.commit_files = vec!["Cargo.lock".to_string()];
+
+        update_context("audit", &mut result, &mut ctx).unwrap();
+        assert_eq!(ctx.next_version, Version::new(1, 2, 0));
+        assert_eq!(ctx.changelog.as_deref(), Some("### Added\n\n- Feature"));
+        assert_eq!(ctx.commit_files, ["Cargo.lock"]);
+        assert_eq!(
+            ctx.get_metadata("ticket"),
+            Some(&Value::String("PROJ-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_update_context_keeps_unset_fields() {
+        let mut ctx = create_context();
+        ctx.changelog = Some("generated".to_string());
+
+        update_context("audit", &mut create_result(), &mut ctx).unwrap();
+        assert_eq!(ctx.next_version, Version::new(1, 1, 0));
+        assert_eq!(ctx.changelog.as_deref(), Some("generated"));
+        assert!(ctx.commit_files.is_empty());
+    }
+
+    #[test]
+    fn test_update_context_invalid_version() {
+        let mut result = create_result();
+        result.next_version = Some(hook::Version {
+            major: 1,
+            minor: 2,
+            patch: 0,
+            pre: Some("not valid!".to_string()),
+            build: None,
+        });
+
+        let error = update_context("audit", &mut result, &mut create_context()).unwrap_err();
+        assert!(error.to_string().contains("invalid version"), "{error}");
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::path::Path;
 
-use wasmtime::Store;
 use wasmtime::component::{Component, Linker};
+use wasmtime::{Store, StoreLimits};
 
+use crate::config::{self, UndulerConfigurable};
+use crate::limits::{self, LimitedState};
 use crate::{WasmEngine, WasmError, WasmResult};
 
 // Generate bindings from WIT
@@ -13,13 +15,36 @@ wasmtime::component::bindgen!({
     path: "../unduler-plugin/wit",
 });
 
+/// Bindings for bumpers that also export the `version-bumper` interface.
+mod versioned {
+    wasmtime::component::bindgen!({
+        world: "unduler-version-bumper",
+        path: "../unduler-plugin/wit",
+        with: {
+            "unduler:plugin/types": crate::bumper::unduler::plugin::types,
+        },
+    });
+}
+
 /// Store state for bumper plugins (no WASI needed).
-pub struct BumperState;
+pub struct BumperState {
+    limits: StoreLimits,
+}
+
+impl LimitedState for BumperState {
+    fn store_limits(&mut self) -> &mut StoreLimits {
+        &mut self.limits
+    }
+}
 
 /// WASM bumper plugin wrapper.
 pub struct WasmBumper {
     store: Store<BumperState>,
     instance: UndulerBumper,
+    versioned: Option<versioned::UndulerVersionBumper>,
+    configurable: Option<UndulerConfigurable>,
+    name: String,
+    deadline: u64,
 }
 
 impl WasmBumper {
@@ -27,25 +52,63 @@ impl WasmBumper {
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be instantiated.
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
     pub fn from_component(engine: &WasmEngine, component: &Component) -> WasmResult<Self> {
-        let mut store = Store::new(engine.inner(), BumperState);
+        Self::instantiate(engine, component, limits::COMPONENT_NAME.to_string())
+    }
+
+    /// Creates a new WASM bumper from a component, named `name` until the
+    /// component reports its own name.
+    pub(crate) fn instantiate(
+        engine: &WasmEngine,
+        component: &Component,
+        name: String,
+    ) -> WasmResult<Self> {
+        let state = BumperState {
+            limits: engine.limits().store_limits(),
+        };
+        let mut store = limits::new_store(engine, state);
         let linker = Linker::new(engine.inner());
 
-        let instance = UndulerBumper::instantiate(&mut store, component, &linker)
+        let instance = linker
+            .instantiate(&mut store, component)
             .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        let bumper = UndulerBumper::new(&mut store, &instance)
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        // `version-bumper` is optional, older bumpers only export `bumper`
+        let versioned = versioned::UndulerVersionBumper::new(&mut store, &instance).ok();
+        let configurable = config::lookup(&mut store, instance);
+
+        let mut bumper = Self {
+            store,
+            instance: bumper,
+            versioned,
+            configurable,
+            name,
+            deadline: engine.limits().deadline_ticks(),
+        };
+        // Until it answers, a failed call names the plugin after its file
+        bumper.name = bumper.info()?.name;
 
-        Ok(Self { store, instance })
+        Ok(bumper)
     }
 
     /// Creates a new WASM bumper from a file path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be loaded or instantiated.
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
     pub fn from_file(engine: &WasmEngine, path: &Path) -> WasmResult<Self> {
         let component = engine.load_component(path)?;
-        Self::from_component(engine, &component)
+        Self::instantiate(engine, &component, limits::file_name(path))
+    }
+
+    /// Returns the plugin name reported by the component.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Gets plugin information.
@@ -54,13 +117,34 @@ impl WasmBumper {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn info(&mut self) -> WasmResult<PluginInfo> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_bumper()
             .call_info(&mut self.store)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "info".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "info", &e))
+    }
+
+    /// Returns true if the plugin exports the `config` interface.
+    #[must_use]
+    pub fn is_configurable(&self) -> bool {
+        self.configurable.is_some()
+    }
+
+    /// Delivers the `[plugins.<name>]` configuration of the plugin, as a
+    /// JSON object. Plugins not exporting the `config` interface ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin rejects the configuration or the WASM
+    /// function call fails.
+    pub fn configure(&mut self, config: &str) -> WasmResult<()> {
+        self.store.set_epoch_deadline(self.deadline);
+        config::configure(
+            &mut self.store,
+            self.configurable.as_ref(),
+            &self.name,
+            config,
+        )
     }
 
     /// Determines bump type from parsed commits.
@@ -69,15 +153,43 @@ impl WasmBumper {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn determine(&mut self, commits: &[ParsedCommit]) -> WasmResult<BumpType> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_bumper()
             .call_determine(&mut self.store, commits)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "determine".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "determine", &e))
+    }
+
+    /// Returns true if the plugin exports the `version-bumper` interface.
+    #[must_use]
+    pub fn has_next_version(&self) -> bool {
+        self.versioned.is_some()
+    }
+
+    /// Computes the next version, if the plugin supports it.
+    ///
+    /// Returns `None` when the plugin does not export the `version-bumper`
+    /// interface, or when it defers to the bump type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM function call fails.
+    pub fn next_version(
+        &mut self,
+        current: &Version,
+        commits: &[ParsedCommit],
+    ) -> WasmResult<Option<Version>> {
+        let Some(versioned) = &self.versioned else {
+            return Ok(None);
+        };
+
+        self.store.set_epoch_deadline(self.deadline);
+        versioned
+            .unduler_plugin_version_bumper()
+            .call_next_version(&mut self.store, current, commits)
+            .map_err(|e| limits::call_error(&self.name, "next-version", &e))
     }
 }
 
 // Re-export generated types
-pub use unduler::plugin::types::{BumpType, ParsedCommit, PluginInfo, PluginType};
+pub use unduler::plugin::types::{BumpType, ParsedCommit, PluginInfo, PluginType, Version};
@@ -0,0 +1,47 @@
+//! Configuration of WASM plugins exporting the `config` interface.
+
+use wasmtime::Store;
+use wasmtime::component::Instance;
+
+use crate::limits;
+use crate::{WasmError, WasmResult};
+
+// Generate bindings from WIT
+wasmtime::component::bindgen!({
+    world: "unduler-configurable",
+    path: "../unduler-plugin/wit",
+});
+
+/// Returns the `config` export of a plugin instance. The interface is
+/// optional, plugins without configuration do not export it.
+pub(crate) fn lookup<T: 'static>(
+    store: &mut Store<T>,
+    instance: Instance,
+) -> Option<UndulerConfigurable> {
+    UndulerConfigurable::new(store, &instance).ok()
+}
+
+/// Delivers its configuration to a plugin, as JSON.
+///
+/// Plugins not exporting the `config` interface ignore the configuration,
+/// with a warning.
+pub(crate) fn configure<T: 'static>(
+    store: &mut Store<T>,
+    configurable: Option<&UndulerConfigurable>,
+    plugin: &str,
+    config: &str,
+) -> WasmResult<()> {
+    let Some(configurable) = configurable else {
+        tracing::warn!(plugin, "plugin does not accept configuration, ignoring it");
+        return Ok(());
+    };
+
+    configurable
+        .unduler_plugin_config()
+        .call_configure(store, config)
+        .map_err(|e| limits::call_error(plugin, "configure", &e))?
+        .map_err(|reason| WasmError::Configuration {
+            plugin: plugin.to_string(),
+            reason,
+        })
+}
@@ -0,0 +1,458 @@
+//! Conversions between native Unduler types and the generated WIT types.
+//!
+//! Each plugin world generates its own copy of the shared WIT types, so the
+//! conversions are implemented per world.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use serde_json::Value;
+
+use crate::{bumper, formatter, hook, parser};
+
+/// Converts a Unix timestamp into a UTC date, falling back to now.
+fn timestamp_to_date(timestamp: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+/// Converts a metadata map into a list of key/value pairs sorted by key.
+fn metadata_to_list(metadata: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut list: Vec<_> = metadata
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    list.sort();
+    list
+}
+
+/// Converts a semver component into a WIT `u32`, saturating on overflow.
+fn version_part(part: u64) -> u32 {
+    u32::try_from(part).unwrap_or(u32::MAX)
+}
+
+/// Returns `None` for an empty string.
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+impl From<&unduler_commit::RawCommit> for parser::RawCommit {
+    fn from(raw: &unduler_commit::RawCommit) -> Self {
+        Self {
+            hash: raw.hash.clone(),
+            message: raw.message.clone(),
+            author: raw.author.clone(),
+            email: raw.email.clone(),
+            timestamp: raw.date.timestamp(),
+        }
+    }
+}
+
+impl From<parser::ParsedCommit> for unduler_commit::ParsedCommit {
+    fn from(parsed: parser::ParsedCommit) -> Self {
+        let mut builder = Self::builder(parsed.hash, parsed.commit_type)
+            .message(parsed.message)
+            .breaking(parsed.breaking)
+            .author(parsed.author)
+            .date(timestamp_to_date(parsed.timestamp));
+
+        if let Some(scope) = parsed.scope {
+            builder = builder.scope(scope);
+        }
+        if let Some(emoji) = parsed.emoji {
+            builder = builder.emoji(emoji);
+        }
+        for (key, value) in parsed.metadata {
+            builder = builder.metadata(key, value);
+        }
+
+        builder.build()
+    }
+}
+
+impl From<&unduler_commit::ParsedCommit> for bumper::ParsedCommit {
+    fn from(commit: &unduler_commit::ParsedCommit) -> Self {
+        Self {
+            hash: commit.hash.clone(),
+            commit_type: commit.r#type.clone(),
+            scope: commit.scope.clone(),
+            message: commit.message.clone(),
+            breaking: commit.breaking,
+            emoji: commit.emoji.clone(),
+            metadata: metadata_to_list(&commit.metadata),
+            author: commit.author.clone(),
+            timestamp: commit.date.timestamp(),
+        }
+    }
+}
+
+impl From<bumper::BumpType> for unduler_plugin::BumpType {
+    fn from(bump: bumper::BumpType) -> Self {
+        match bump {
+            bumper::BumpType::Major => Self::Major,
+            bumper::BumpType::Minor => Self::Minor,
+            bumper::BumpType::Patch => Self::Patch,
+            bumper::BumpType::None => Self::None,
+        }
+    }
+}
+
+impl From<&semver::Version> for bumper::Version {
+    fn from(version: &semver::Version) -> Self {
+        Self {
+            major: version_part(version.major),
+            minor: version_part(version.minor),
+            patch: version_part(version.patch),
+            pre: non_empty(version.pre.as_str()),
+            build: non_empty(version.build.as_str()),
+        }
+    }
+}
+
+impl TryFrom<bumper::Version> for semver::Version {
+    type Error = semver::Error;
+
+    fn try_from(version: bumper::Version) -> Result<Self, Self::Error> {
+        to_semver(
+            version.major,
+            version.minor,
+            version.patch,
+            version.pre,
+            version.build,
+        )
+    }
+}
+
+/// Builds a version from the fields of a WIT version.
+fn to_semver(
+    major: u32,
+    minor: u32,
+    patch: u32,
+    pre: Option<String>,
+    build: Option<String>,
+) -> Result<semver::Version, semver::Error> {
+    let mut result = semver::Version::new(major.into(), minor.into(), patch.into());
+    if let Some(pre) = pre {
+        result.pre = semver::Prerelease::new(&pre)?;
+    }
+    if let Some(build) = build {
+        result.build = semver::BuildMetadata::new(&build)?;
+    }
+    Ok(result)
+}
+
+impl From<&unduler_commit::ParsedCommit> for formatter::ParsedCommit {
+    fn from(commit: &unduler_commit::ParsedCommit) -> Self {
+        Self {
+            hash: commit.hash.clone(),
+            commit_type: commit.r#type.clone(),
+            scope: commit.scope.clone(),
+            message: commit.message.clone(),
+            breaking: commit.breaking,
+            emoji: commit.emoji.clone(),
+            metadata: metadata_to_list(&commit.metadata),
+            author: commit.author.clone(),
+            timestamp: commit.date.timestamp(),
+        }
+    }
+}
+
+impl From<&semver::Version> for formatter::Version {
+    fn from(version: &semver::Version) -> Self {
+        Self {
+            major: version_part(version.major),
+            minor: version_part(version.minor),
+            patch: version_part(version.patch),
+            pre: non_empty(version.pre.as_str()),
+            build: non_empty(version.build.as_str()),
+        }
+    }
+}
+
+impl From<&unduler_plugin::Release> for formatter::Release {
+    fn from(release: &unduler_plugin::Release) -> Self {
+        Self {
+            version: (&release.version).into(),
+            date: release.date.to_rfc3339(),
+            commits: release.commits.iter().map(Into::into).collect(),
+            previous_version: release.previous_version.as_ref().map(Into::into),
+            repository_url: release.repository_url.clone(),
+        }
+    }
+}
+
+impl From<&unduler_plugin::FormatterSection> for formatter::FormatterSection {
+    fn from(section: &unduler_plugin::FormatterSection) -> Self {
+        Self {
+            types: section.types.clone(),
+            title: section.title.clone(),
+        }
+    }
+}
+
+impl From<unduler_plugin::MessageCase> for formatter::MessageCase {
+    fn from(case: unduler_plugin::MessageCase) -> Self {
+        match case {
+            unduler_plugin::MessageCase::Preserve => Self::Preserve,
+            unduler_plugin::MessageCase::Sentence => Self::Sentence,
+        }
+    }
+}
+
+impl From<unduler_plugin::TrailingPunctuation> for formatter::TrailingPunctuation {
+    fn from(punctuation: unduler_plugin::TrailingPunctuation) -> Self {
+        match punctuation {
+            unduler_plugin::TrailingPunctuation::Preserve => Self::Preserve,
+            unduler_plugin::TrailingPunctuation::Strip => Self::Strip,
+            unduler_plugin::TrailingPunctuation::Period => Self::Period,
+        }
+    }
+}
+
+impl From<unduler_plugin::ListBullet> for formatter::ListBullet {
+    fn from(bullet: unduler_plugin::ListBullet) -> Self {
+        match bullet {
+            unduler_plugin::ListBullet::Dash => Self::Dash,
+            unduler_plugin::ListBullet::Asterisk => Self::Asterisk,
+        }
+    }
+}
+
+impl From<&unduler_plugin::FormatterConfig> for formatter::FormatterConfig {
+    fn from(config: &unduler_plugin::FormatterConfig) -> Self {
+        Self {
+            group_by_type: config.group_by_type,
+            group_by_scope: config.group_by_scope,
+            include_hashes: config.include_hashes,
+            include_authors: config.include_authors,
+            link_references: config.link_references,
+            closed_issues: config.closed_issues,
+            contributors: config.contributors,
+            summary: config.summary,
+            highlights: config.highlights,
+            breaking_changes: config.breaking_changes,
+            show_emoji: config.show_emoji,
+            deduplicate: config.deduplicate,
+            line_width: config
+                .line_width
+                .map(|width| u32::try_from(width).unwrap_or(u32::MAX)),
+            message_case: config.message_case.into(),
+            trailing_punctuation: config.trailing_punctuation.into(),
+            bullet: config.bullet.into(),
+            type_labels: metadata_to_list(&config.type_labels),
+            sections: config.sections.iter().map(Into::into).collect(),
+            hide_types: config.hide_types.clone(),
+        }
+    }
+}
+
+impl From<&unduler_commit::ParsedCommit> for hook::ParsedCommit {
+    fn from(commit: &unduler_commit::ParsedCommit) -> Self {
+        Self {
+            hash: commit.hash.clone(),
+            commit_type: commit.r#type.clone(),
+            scope: commit.scope.clone(),
+            message: commit.message.clone(),
+            breaking: commit.breaking,
+            emoji: commit.emoji.clone(),
+            metadata: metadata_to_list(&commit.metadata),
+            author: commit.author.clone(),
+            timestamp: commit.date.timestamp(),
+        }
+    }
+}
+
+impl From<&semver::Version> for hook::Version {
+    fn from(version: &semver::Version) -> Self {
+        Self {
+            major: version_part(version.major),
+            minor: version_part(version.minor),
+            patch: version_part(version.patch),
+            pre: non_empty(version.pre.as_str()),
+            build: non_empty(version.build.as_str()),
+        }
+    }
+}
+
+impl TryFrom<hook::Version> for semver::Version {
+    type Error = semver::Error;
+
+    fn try_from(version: hook::Version) -> Result<Self, Self::Error> {
+        to_semver(
+            version.major,
+            version.minor,
+            version.patch,
+            version.pre,
+            version.build,
+        )
+    }
+}
+
+impl From<unduler_plugin::BumpType> for hook::BumpType {
+    fn from(bump: unduler_plugin::BumpType) -> Self {
+        match bump {
+            unduler_plugin::BumpType::Major => Self::Major,
+            unduler_plugin::BumpType::Minor => Self::Minor,
+            unduler_plugin::BumpType::Patch => Self::Patch,
+            unduler_plugin::BumpType::None => Self::None,
+        }
+    }
+}
+
+impl From<&unduler_plugin::ReleaseContext> for hook::ReleaseContext {
+    fn from(ctx: &unduler_plugin::ReleaseContext) -> Self {
+        // String values are passed as is, other values as JSON
+        let mut metadata: Vec<_> = ctx
+            .metadata
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(value) => (key.clone(), value.clone()),
+                value => (key.clone(), value.to_string()),
+            })
+            .collect();
+        metadata.sort();
+
+        Self {
+            repo_path: ctx.repo_path.to_string_lossy().into_owned(),
+            previous_version: (&ctx.previous_version).into(),
+            next_version: (&ctx.next_version).into(),
+            bump_type: ctx.bump_type.into(),
+            commits: ctx.commits.iter().map(Into::into).collect(),
+            changelog: ctx.changelog.clone(),
+            tag_name: ctx.tag_name.clone(),
+            repository_url: ctx.repository_url.clone(),
+            branch: ctx.branch.clone(),
+            files: ctx.files.clone(),
+            error: ctx.error.clone(),
+            dry_run: ctx.dry_run,
+            metadata,
+            hook_config: ctx.config.as_ref().map(Value::to_string),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_commit_to_wit() {
+        let date = timestamp_to_date(1_234_567_890);
+        let raw =
+            unduler_commit::RawCommit::new("abc123", "feat: x", "Test", "test@test.com", date);
+
+        let wit = parser::RawCommit::from(&raw);
+        assert_eq!(wit.hash, "abc123");
+        assert_eq!(wit.message, "feat: x");
+        assert_eq!(wit.timestamp, 1_234_567_890);
+    }
+
+    #[test]
+    fn test_parsed_commit_from_wit() {
+        let wit = parser::ParsedCommit {
+            hash: "abc123".to_string(),
+            commit_type: "feat".to_string(),
+            scope: Some("api".to_string()),
+            message: "add endpoint".to_string(),
+            breaking: true,
+            emoji: None,
+            metadata: vec![("ticket".to_string(), "PROJ-1".to_string())],
+            author: "Test".to_string(),
+            timestamp: 0,
+        };
+
+        let parsed = unduler_commit::ParsedCommit::from(wit);
+        assert_eq!(parsed.r#type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert!(parsed.breaking);
+        assert_eq!(
+            parsed.metadata.get("ticket").map(String::as_str),
+            Some("PROJ-1")
+        );
+    }
+
+    #[test]
+    fn test_bump_type_from_wit() {
+        assert_eq!(
+            unduler_plugin::BumpType::from(bumper::BumpType::Major),
+            unduler_plugin::BumpType::Major
+        );
+        assert_eq!(
+            unduler_plugin::BumpType::from(bumper::BumpType::None),
+            unduler_plugin::BumpType::None
+        );
+    }
+
+    #[test]
+    fn test_version_round_trip() {
+        let version = semver::Version::parse("2.0.0-beta.1+build.5").unwrap();
+
+        let wit = bumper::Version::from(&version);
+        assert_eq!(wit.major, 2);
+        assert_eq!(wit.pre.as_deref(), Some("beta.1"));
+        assert_eq!(wit.build.as_deref(), Some("build.5"));
+        assert_eq!(semver::Version::try_from(wit).unwrap(), version);
+    }
+
+    #[test]
+    fn test_invalid_version_from_wit() {
+        let wit = bumper::Version {
+            major: 1,
+            minor: 0,
+            patch: 0,
+            pre: Some("not valid!".to_string()),
+            build: None,
+        };
+        assert!(semver::Version::try_from(wit).is_err());
+    }
+
+    #[test]
+    fn test_release_to_wit() {
+        let version = semver::Version::parse("1.2.0-rc.1").unwrap();
+        let release = unduler_plugin::Release::new(version, Utc::now(), vec![])
+            .with_previous_version(semver::Version::new(1, 1, 0));
+
+        let wit = formatter::Release::from(&release);
+        assert_eq!(wit.version.minor, 2);
+        assert_eq!(wit.version.pre.as_deref(), Some("rc.1"));
+        assert!(wit.version.build.is_none());
+        assert_eq!(wit.previous_version.map(|v| v.minor), Some(1));
+    }
+
+    #[test]
+    fn test_release_context_to_wit() {
+        let mut ctx = unduler_plugin::ReleaseContext::new(
+            "/repo",
+            semver::Version::new(1, 0, 0),
+            semver::Version::new(1, 1, 0),
+            unduler_plugin::BumpType::Minor,
+            vec![],
+        )
+        .dry_run(true);
+        ctx.metadata
+            .insert("ticket".to_string(), Value::String("PROJ-1".to_string()));
+        ctx.metadata
+            .insert("assets".to_string(), serde_json::json!(["dist/app"]));
+        ctx.config = Some(serde_json::json!({ "draft": true }));
+
+        let wit = hook::ReleaseContext::from(&ctx);
+        assert_eq!(wit.repo_path, "/repo");
+        assert_eq!(wit.next_version.minor, 1);
+        assert!(matches!(wit.bump_type, hook::BumpType::Minor));
+        assert!(wit.dry_run);
+        assert_eq!(
+            wit.metadata,
+            [
+                ("assets".to_string(), r#"["dist/app"]"#.to_string()),
+                ("ticket".to_string(), "PROJ-1".to_string()),
+            ]
+        );
+        assert_eq!(wit.hook_config.as_deref(), Some(r#"{"draft":true}"#));
+    }
+}
@@ -1,29 +1,90 @@
 //! WASM engine setup and management.
 
 use std::path::Path;
+use std::thread;
 
-use wasmtime::{Config, Engine, component::Component};
+use wasmtime::{Cache, CacheConfig, Config, Engine, component::Component};
 
-use crate::{WasmError, WasmResult};
+use crate::limits::EPOCH_TICK;
+use crate::{WasmError, WasmLimits, WasmResult};
 
 /// WASM engine for loading and executing plugin components.
 pub struct WasmEngine {
     inner: Engine,
+    limits: WasmLimits,
 }
 
 impl WasmEngine {
     /// Creates a new WASM engine with Component Model support.
     ///
+    /// Plugin calls are interrupted once they exceed the timeout of the
+    /// engine limits (see [`WasmEngine::with_limits`]).
+    ///
     /// # Errors
     ///
     /// Returns an error if the engine cannot be created.
     pub fn new() -> WasmResult<Self> {
+        Self::build(None)
+    }
+
+    /// Creates a new WASM engine caching compiled components in a directory.
+    ///
+    /// Compiled components are keyed by a digest of their bytes and of the
+    /// engine configuration, so a plugin is only compiled again after it
+    /// changes or wasmtime is upgraded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine or the cache cannot be created.
+    pub fn with_cache_dir(dir: impl AsRef<Path>) -> WasmResult<Self> {
+        Self::build(Some(dir.as_ref()))
+    }
+
+    fn build(cache_dir: Option<&Path>) -> WasmResult<Self> {
         let mut config = Config::new();
         config.wasm_component_model(true);
+        config.epoch_interruption(true);
+
+        if let Some(dir) = cache_dir {
+            let mut cache_config = CacheConfig::new();
+            cache_config.with_directory(dir);
+            let cache =
+                Cache::new(cache_config).map_err(|e| WasmError::EngineCreation(e.to_string()))?;
+            config.cache(Some(cache));
+        }
 
         let inner = Engine::new(&config).map_err(|e| WasmError::EngineCreation(e.to_string()))?;
 
-        Ok(Self { inner })
+        // Advance the epoch until the engine is dropped
+        let weak = inner.weak();
+        thread::Builder::new()
+            .name("unduler-wasm-epoch".to_string())
+            .spawn(move || {
+                while let Some(engine) = weak.upgrade() {
+                    engine.increment_epoch();
+                    drop(engine);
+                    thread::sleep(EPOCH_TICK);
+                }
+            })
+            .map_err(|e| WasmError::EngineCreation(e.to_string()))?;
+
+        Ok(Self {
+            inner,
+            limits: WasmLimits::default(),
+        })
+    }
+
+    /// Sets the limits applied to plugins instantiated by this engine.
+    #[must_use]
+    pub fn with_limits(mut self, limits: WasmLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the limits applied to plugins instantiated by this engine.
+    #[must_use]
+    pub fn limits(&self) -> &WasmLimits {
+        &self.limits
     }
 
     /// Returns a reference to the inner wasmtime engine.
@@ -46,6 +107,18 @@ impl WasmEngine {
         })
     }
 
+    /// Compiles a WASM component ahead of its first use.
+    ///
+    /// With a cache directory (see [`WasmEngine::with_cache_dir`]), this
+    /// stores the compiled component so later loads skip compilation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded.
+    pub fn precompile(&self, path: &Path) -> WasmResult<()> {
+        self.load_component(path).map(drop)
+    }
+
     /// Loads a WASM component from bytes.
     ///
     /// # Errors
@@ -79,6 +152,28 @@ mod tests {
         let _engine = WasmEngine::default();
     }
 
+    #[test]
+    fn test_engine_with_limits() {
+        let engine = WasmEngine::new()
+            .unwrap()
+            .with_limits(WasmLimits::unlimited());
+        assert!(engine.limits().timeout().is_none());
+    }
+
+    #[test]
+    fn test_engine_with_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = WasmEngine::with_cache_dir(dir.path());
+        assert!(engine.is_ok());
+    }
+
+    #[test]
+    fn test_precompile_not_found() {
+        let engine = WasmEngine::new().unwrap();
+        let result = engine.precompile(Path::new("/nonexistent/plugin.wasm"));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_load_component_not_found() {
         let engine = WasmEngine::new().unwrap();
@@ -21,6 +21,14 @@ pub enum WasmError {
     #[error("failed to call WASM function '{name}': {reason}")]
     FunctionCall { name: String, reason: String },
 
+    /// A plugin call exceeded its time limit.
+    #[error("plugin '{plugin}' timed out in '{function}'")]
+    Timeout { plugin: String, function: String },
+
+    /// A plugin rejected its configuration.
+    #[error("plugin '{plugin}' rejected its configuration: {reason}")]
+    Configuration { plugin: String, reason: String },
+
     /// Plugin type mismatch.
     #[error("plugin type mismatch: expected {expected}, got {actual}")]
     TypeMismatch { expected: String, actual: String },
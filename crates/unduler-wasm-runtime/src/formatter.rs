@@ -2,10 +2,14 @@
 
 use std::path::Path;
 
-use wasmtime::Store;
-use wasmtime::component::{Component, Linker};
+use wasmtime::component::Component;
+use wasmtime::{Store, StoreLimits};
+use wasmtime_wasi::{WasiCtxView, WasiView};
 
-use crate::{WasmEngine, WasmError, WasmResult};
+use crate::config::{self, UndulerConfigurable};
+use crate::limits::{self, LimitedState};
+use crate::wasi::{self, WasiState};
+use crate::{SandboxPolicy, WasmEngine, WasmError, WasmResult};
 
 // Generate bindings from WIT
 wasmtime::component::bindgen!({
@@ -13,39 +17,140 @@ wasmtime::component::bindgen!({
     path: "../unduler-plugin/wit",
 });
 
-/// Store state for formatter plugins (no WASI needed).
-pub struct FormatterState;
+/// Store state for formatter plugins.
+pub struct FormatterState {
+    limits: StoreLimits,
+    wasi: WasiState,
+}
+
+impl LimitedState for FormatterState {
+    fn store_limits(&mut self) -> &mut StoreLimits {
+        &mut self.limits
+    }
+}
+
+impl WasiView for FormatterState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        self.wasi.view()
+    }
+}
 
 /// WASM formatter plugin wrapper.
 pub struct WasmFormatter {
     store: Store<FormatterState>,
     instance: UndulerFormatter,
+    configurable: Option<UndulerConfigurable>,
+    name: String,
+    deadline: u64,
 }
 
 impl WasmFormatter {
-    /// Creates a new WASM formatter from a component.
+    /// Creates a new WASM formatter from a component, without WASI.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be instantiated.
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
     pub fn from_component(engine: &WasmEngine, component: &Component) -> WasmResult<Self> {
-        let mut store = Store::new(engine.inner(), FormatterState);
-        let linker = Linker::new(engine.inner());
+        Self::from_component_sandboxed(engine, component, Path::new("."), &SandboxPolicy::default())
+    }
 
-        let instance = UndulerFormatter::instantiate(&mut store, component, &linker)
+    /// Creates a new WASM formatter from a component, granted the WASI
+    /// capabilities of a sandbox policy. With the filesystem capability, the
+    /// plugin can read `workdir`, e.g. to load templates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
+    pub fn from_component_sandboxed(
+        engine: &WasmEngine,
+        component: &Component,
+        workdir: &Path,
+        sandbox: &SandboxPolicy,
+    ) -> WasmResult<Self> {
+        Self::instantiate(
+            engine,
+            component,
+            workdir,
+            sandbox,
+            limits::COMPONENT_NAME.to_string(),
+        )
+    }
+
+    /// Creates a new WASM formatter from a component, named `name` until the
+    /// component reports its own name.
+    pub(crate) fn instantiate(
+        engine: &WasmEngine,
+        component: &Component,
+        workdir: &Path,
+        sandbox: &SandboxPolicy,
+        name: String,
+    ) -> WasmResult<Self> {
+        let state = FormatterState {
+            limits: engine.limits().store_limits(),
+            wasi: WasiState::new(sandbox, workdir)?,
+        };
+        let mut store = limits::new_store(engine, state);
+        let linker = wasi::linker(engine, sandbox)?;
+
+        let instance = linker
+            .instantiate(&mut store, component)
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        let configurable = config::lookup(&mut store, instance);
+        let instance = UndulerFormatter::new(&mut store, &instance)
             .map_err(|e| WasmError::Instantiation(e.to_string()))?;
 
-        Ok(Self { store, instance })
+        let mut formatter = Self {
+            store,
+            instance,
+            configurable,
+            name,
+            deadline: engine.limits().deadline_ticks(),
+        };
+        // Until it answers, a failed call names the plugin after its file
+        formatter.name = formatter.info()?.name;
+
+        Ok(formatter)
     }
 
     /// Creates a new WASM formatter from a file path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be loaded or instantiated.
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
     pub fn from_file(engine: &WasmEngine, path: &Path) -> WasmResult<Self> {
+        Self::from_file_sandboxed(engine, path, Path::new("."), &SandboxPolicy::default())
+    }
+
+    /// Creates a new WASM formatter from a file path, granted the WASI
+    /// capabilities of a sandbox policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
+    pub fn from_file_sandboxed(
+        engine: &WasmEngine,
+        path: &Path,
+        workdir: &Path,
+        sandbox: &SandboxPolicy,
+    ) -> WasmResult<Self> {
         let component = engine.load_component(path)?;
-        Self::from_component(engine, &component)
+        Self::instantiate(
+            engine,
+            &component,
+            workdir,
+            sandbox,
+            limits::file_name(path),
+        )
+    }
+
+    /// Returns the plugin name reported by the component.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Gets plugin information.
@@ -54,13 +159,34 @@ impl WasmFormatter {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn info(&mut self) -> WasmResult<PluginInfo> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_formatter()
             .call_info(&mut self.store)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "info".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "info", &e))
+    }
+
+    /// Returns true if the plugin exports the `config` interface.
+    #[must_use]
+    pub fn is_configurable(&self) -> bool {
+        self.configurable.is_some()
+    }
+
+    /// Delivers the `[plugins.<name>]` configuration of the plugin, as a
+    /// JSON object. Plugins not exporting the `config` interface ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin rejects the configuration or the WASM
+    /// function call fails.
+    pub fn configure(&mut self, config: &str) -> WasmResult<()> {
+        self.store.set_epoch_deadline(self.deadline);
+        config::configure(
+            &mut self.store,
+            self.configurable.as_ref(),
+            &self.name,
+            config,
+        )
     }
 
     /// Formats a release into a changelog string.
@@ -69,13 +195,11 @@ impl WasmFormatter {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn format(&mut self, release: &Release, config: &FormatterConfig) -> WasmResult<String> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_formatter()
             .call_format(&mut self.store, release, config)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "format".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "format", &e))
     }
 
     /// Gets the file extension for output.
@@ -84,15 +208,16 @@ impl WasmFormatter {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn extension(&mut self) -> WasmResult<String> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_formatter()
             .call_extension(&mut self.store)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "extension".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "extension", &e))
     }
 }
 
 // Re-export generated types
-pub use unduler::plugin::types::{FormatterConfig, PluginInfo, PluginType, Release};
+pub use unduler::plugin::types::{
+    FormatterConfig, FormatterSection, ListBullet, MessageCase, ParsedCommit, PluginInfo,
+    PluginType, Release, TrailingPunctuation, Version,
+};
@@ -1,11 +1,20 @@
 //! WASM hook plugin wrapper with action execution.
 
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-
-use wasmtime::Store;
-use wasmtime::component::{Component, Linker};
-
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use wasmtime::component::{Component, HasSelf};
+use wasmtime::{Store, StoreLimits};
+use wasmtime_wasi::{WasiCtxView, WasiView};
+
+use crate::config::{self, UndulerConfigurable};
+use crate::limits::{self, LimitedState};
+use crate::sandbox::{DEFAULT_ALLOWED_COMMANDS, SandboxPolicy, SandboxViolation};
+use crate::secrets::Secrets;
+use crate::wasi::{self, WasiState};
 use crate::{WasmEngine, WasmError, WasmResult};
 
 // Generate bindings from WIT
@@ -14,20 +23,74 @@ wasmtime::component::bindgen!({
     path: "../unduler-plugin/wit",
 });
 
-/// Whitelisted commands that hooks are allowed to execute.
-const ALLOWED_COMMANDS: &[&str] = &["cargo", "npm", "yarn", "pnpm", "gh", "git"];
+/// Interval at which running commands are polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Timeout of HTTP requests when the sandbox policy sets none.
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Store state for hook plugins.
 pub struct HookState {
-    /// Working directory for the hook (repository root).
-    #[allow(dead_code)]
-    workdir: PathBuf,
+    /// Resource limits of the plugin instance.
+    limits: StoreLimits,
+    /// WASI capabilities of the plugin instance.
+    wasi: WasiState,
+    /// Sandbox policy applied to the HTTP requests of the plugin.
+    sandbox: SandboxPolicy,
+    /// Secrets the plugin may get.
+    secrets: Secrets,
 }
 
 impl HookState {
     /// Creates a new hook state.
-    fn new(workdir: PathBuf) -> Self {
-        Self { workdir }
+    fn new(limits: StoreLimits, wasi: WasiState, sandbox: SandboxPolicy) -> Self {
+        Self {
+            limits,
+            wasi,
+            sandbox,
+            secrets: Secrets::default(),
+        }
+    }
+}
+
+impl unduler::plugin::types::Host for HookState {}
+
+impl unduler::plugin::secrets::Host for HookState {
+    fn get_secret(&mut self, name: String) -> Option<String> {
+        tracing::debug!("Plugin requested secret '{name}'");
+        self.secrets.get(&name)
+    }
+}
+
+impl unduler::plugin::http::Host for HookState {
+    fn request(&mut self, request: HttpRequest) -> Result<HttpResponse, String> {
+        if let Err(violation) = self.sandbox.check_request(&request.url) {
+            tracing::warn!("Sandbox violation: {violation}");
+            return Err(violation.to_string());
+        }
+
+        tracing::debug!("Sending HTTP request: {} {}", request.method, request.url);
+
+        let timeout = self.sandbox.timeout().unwrap_or(DEFAULT_HTTP_TIMEOUT);
+        // The blocking client must not run on the thread of an async runtime
+        thread::scope(|scope| {
+            scope
+                .spawn(|| send_request(&request, timeout))
+                .join()
+                .unwrap_or_else(|_| Err("HTTP request panicked".to_string()))
+        })
+    }
+}
+
+impl LimitedState for HookState {
+    fn store_limits(&mut self) -> &mut StoreLimits {
+        &mut self.limits
+    }
+}
+
+impl WasiView for HookState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        self.wasi.view()
     }
 }
 
@@ -51,13 +114,31 @@ pub struct ActionResults {
     pub files_written: Vec<PathBuf>,
     /// Any errors that occurred.
     pub errors: Vec<String>,
+    /// Actions rejected by the sandbox policy.
+    pub violations: Vec<SandboxViolation>,
 }
 
 impl ActionResults {
     /// Returns true if all actions succeeded.
     #[must_use]
     pub fn success(&self) -> bool {
-        self.errors.is_empty() && self.command_outputs.iter().all(|o| o.exit_code == 0)
+        self.errors.is_empty()
+            && self.violations.is_empty()
+            && self.command_outputs.iter().all(|o| o.exit_code == 0)
+    }
+}
+
+/// Error raised while executing a single hook action.
+enum ActionError {
+    /// The action was rejected by the sandbox policy.
+    Violation(SandboxViolation),
+    /// The action failed to execute.
+    Failed(String),
+}
+
+impl From<SandboxViolation> for ActionError {
+    fn from(violation: SandboxViolation) -> Self {
+        Self::Violation(violation)
     }
 }
 
@@ -65,41 +146,137 @@ impl ActionResults {
 pub struct WasmHook {
     store: Store<HookState>,
     instance: UndulerHook,
+    configurable: Option<UndulerConfigurable>,
     workdir: PathBuf,
+    sandbox: SandboxPolicy,
+    name: String,
+    deadline: u64,
 }
 
 impl WasmHook {
-    /// Creates a new WASM hook from a component with a working directory.
+    /// Creates a new WASM hook from a component with a working directory,
+    /// under the default sandbox policy.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be instantiated.
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
     pub fn from_component(
         engine: &WasmEngine,
         component: &Component,
         workdir: PathBuf,
     ) -> WasmResult<Self> {
-        let mut store = Store::new(engine.inner(), HookState::new(workdir.clone()));
-        let linker = Linker::new(engine.inner());
+        Self::from_component_sandboxed(engine, component, workdir, SandboxPolicy::default())
+    }
+
+    /// Creates a new WASM hook from a component with a working directory,
+    /// under a sandbox policy. The WASI capabilities of the policy are
+    /// granted at instantiation, with read-only access to `workdir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
+    pub fn from_component_sandboxed(
+        engine: &WasmEngine,
+        component: &Component,
+        workdir: PathBuf,
+        sandbox: SandboxPolicy,
+    ) -> WasmResult<Self> {
+        Self::instantiate(
+            engine,
+            component,
+            workdir,
+            sandbox,
+            limits::COMPONENT_NAME.to_string(),
+        )
+    }
 
-        let instance = UndulerHook::instantiate(&mut store, component, &linker)
+    /// Creates a new WASM hook from a component, named `name` until the
+    /// component reports its own name.
+    pub(crate) fn instantiate(
+        engine: &WasmEngine,
+        component: &Component,
+        workdir: PathBuf,
+        sandbox: SandboxPolicy,
+        name: String,
+    ) -> WasmResult<Self> {
+        let wasi_state = WasiState::new(&sandbox, &workdir)?;
+        let state = HookState::new(engine.limits().store_limits(), wasi_state, sandbox.clone());
+        let mut store = limits::new_store(engine, state);
+        let mut linker = wasi::linker(engine, &sandbox)?;
+        UndulerHook::add_to_linker::<_, HasSelf<_>>(&mut linker, |state| state)
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+
+        let instance = linker
+            .instantiate(&mut store, component)
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        let configurable = config::lookup(&mut store, instance);
+        let instance = UndulerHook::new(&mut store, &instance)
             .map_err(|e| WasmError::Instantiation(e.to_string()))?;
 
-        Ok(Self {
+        let mut hook = Self {
             store,
             instance,
+            configurable,
             workdir,
-        })
+            sandbox,
+            name,
+            deadline: engine.limits().deadline_ticks(),
+        };
+        // Until it answers, a failed call names the plugin after its file
+        hook.name = hook.info()?.name;
+
+        Ok(hook)
     }
 
     /// Creates a new WASM hook from a file path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be loaded or instantiated.
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
     pub fn from_file(engine: &WasmEngine, path: &Path, workdir: PathBuf) -> WasmResult<Self> {
+        Self::from_file_sandboxed(engine, path, workdir, SandboxPolicy::default())
+    }
+
+    /// Creates a new WASM hook from a file path, under a sandbox policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
+    pub fn from_file_sandboxed(
+        engine: &WasmEngine,
+        path: &Path,
+        workdir: PathBuf,
+        sandbox: SandboxPolicy,
+    ) -> WasmResult<Self> {
         let component = engine.load_component(path)?;
-        Self::from_component(engine, &component, workdir)
+        Self::instantiate(
+            engine,
+            &component,
+            workdir,
+            sandbox,
+            limits::file_name(path),
+        )
+    }
+
+    /// Sets the sandbox policy applied to the actions and HTTP requests of
+    /// this hook. WASI capabilities are granted at instantiation, see
+    /// [`from_component_sandboxed`](Self::from_component_sandboxed).
+    #[must_use]
+    pub fn with_sandbox(mut self, sandbox: SandboxPolicy) -> Self {
+        self.store.data_mut().sandbox = sandbox.clone();
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Sets the secrets this hook may get.
+    #[must_use]
+    pub fn with_secrets(mut self, secrets: Secrets) -> Self {
+        self.store.data_mut().secrets = secrets;
+        self
     }
 
     /// Returns the working directory for this hook.
@@ -108,19 +285,74 @@ impl WasmHook {
         &self.workdir
     }
 
+    /// Returns the plugin name reported by the component.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the sandbox policy applied to this hook.
+    #[must_use]
+    pub fn sandbox(&self) -> &SandboxPolicy {
+        &self.sandbox
+    }
+
     /// Gets plugin information.
     ///
     /// # Errors
     ///
     /// Returns an error if the WASM function call fails.
     pub fn info(&mut self) -> WasmResult<PluginInfo> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_hook()
             .call_info(&mut self.store)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "info".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "info", &e))
+    }
+
+    /// Returns true if the plugin exports the `config` interface.
+    #[must_use]
+    pub fn is_configurable(&self) -> bool {
+        self.configurable.is_some()
+    }
+
+    /// Delivers the `[plugins.<name>]` configuration of the plugin, as a
+    /// JSON object. Plugins not exporting the `config` interface ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin rejects the configuration or the WASM
+    /// function call fails.
+    pub fn configure(&mut self, config: &str) -> WasmResult<()> {
+        self.store.set_epoch_deadline(self.deadline);
+        config::configure(
+            &mut self.store,
+            self.configurable.as_ref(),
+            &self.name,
+            config,
+        )
+    }
+
+    /// Called once the release is planned, before anything is modified.
+    /// An unsuccessful result vetoes the release.
+    /// Executes any actions returned by the hook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM function call fails.
+    pub fn on_pre_release(
+        &mut self,
+        ctx: &ReleaseContext,
+    ) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
+        let result = self
+            .instance
+            .unduler_plugin_hook()
+            .call_on_pre_release(&mut self.store, ctx)
+            .map_err(|e| limits::call_error(&self.name, "on_pre_release", &e))?;
+
+        let actions = self.execute_actions(&result.actions);
+        Ok((result, actions))
     }
 
     /// Called before version files are modified.
@@ -130,14 +362,12 @@ impl WasmHook {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn on_pre_bump(&mut self, ctx: &ReleaseContext) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
         let result = self
             .instance
             .unduler_plugin_hook()
             .call_on_pre_bump(&mut self.store, ctx)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "on_pre_bump".to_string(),
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| limits::call_error(&self.name, "on_pre_bump", &e))?;
 
         let actions = self.execute_actions(&result.actions);
         Ok((result, actions))
@@ -153,14 +383,12 @@ impl WasmHook {
         &mut self,
         ctx: &ReleaseContext,
     ) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
         let result = self
             .instance
             .unduler_plugin_hook()
             .call_on_post_bump(&mut self.store, ctx)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "on_post_bump".to_string(),
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| limits::call_error(&self.name, "on_post_bump", &e))?;
 
         let actions = self.execute_actions(&result.actions);
         Ok((result, actions))
@@ -176,14 +404,12 @@ impl WasmHook {
         &mut self,
         ctx: &ReleaseContext,
     ) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
         let result = self
             .instance
             .unduler_plugin_hook()
             .call_on_pre_commit(&mut self.store, ctx)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "on_pre_commit".to_string(),
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| limits::call_error(&self.name, "on_pre_commit", &e))?;
 
         let actions = self.execute_actions(&result.actions);
         Ok((result, actions))
@@ -196,14 +422,12 @@ impl WasmHook {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn on_pre_tag(&mut self, ctx: &ReleaseContext) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
         let result = self
             .instance
             .unduler_plugin_hook()
             .call_on_pre_tag(&mut self.store, ctx)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "on_pre_tag".to_string(),
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| limits::call_error(&self.name, "on_pre_tag", &e))?;
 
         let actions = self.execute_actions(&result.actions);
         Ok((result, actions))
@@ -216,14 +440,51 @@ impl WasmHook {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn on_post_tag(&mut self, ctx: &ReleaseContext) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
         let result = self
             .instance
             .unduler_plugin_hook()
             .call_on_post_tag(&mut self.store, ctx)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "on_post_tag".to_string(),
-                reason: e.to_string(),
-            })?;
+            .map_err(|e| limits::call_error(&self.name, "on_post_tag", &e))?;
+
+        let actions = self.execute_actions(&result.actions);
+        Ok((result, actions))
+    }
+
+    /// Called after the release is pushed.
+    /// Executes any actions returned by the hook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM function call fails.
+    pub fn on_post_release(
+        &mut self,
+        ctx: &ReleaseContext,
+    ) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
+        let result = self
+            .instance
+            .unduler_plugin_hook()
+            .call_on_post_release(&mut self.store, ctx)
+            .map_err(|e| limits::call_error(&self.name, "on_post_release", &e))?;
+
+        let actions = self.execute_actions(&result.actions);
+        Ok((result, actions))
+    }
+
+    /// Called after a step of the release failed, with its error in the context.
+    /// Executes any actions returned by the hook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WASM function call fails.
+    pub fn on_failure(&mut self, ctx: &ReleaseContext) -> WasmResult<(HookResult, ActionResults)> {
+        self.store.set_epoch_deadline(self.deadline);
+        let result = self
+            .instance
+            .unduler_plugin_hook()
+            .call_on_failure(&mut self.store, ctx)
+            .map_err(|e| limits::call_error(&self.name, "on_failure", &e))?;
 
         let actions = self.execute_actions(&result.actions);
         Ok((result, actions))
@@ -234,18 +495,26 @@ impl WasmHook {
         let mut results = ActionResults::default();
 
         for action in actions {
-            match action {
-                HookAction::RunCommand(req) => match self.execute_command(req) {
-                    Ok(output) => results.command_outputs.push(output),
-                    Err(e) => results.errors.push(e),
-                },
-                HookAction::WriteFile(req) => match self.write_file(req) {
-                    Ok(path) => results.files_written.push(path),
-                    Err(e) => results.errors.push(e),
-                },
+            let outcome = match action {
+                HookAction::RunCommand(req) => self
+                    .execute_command(req)
+                    .map(|output| results.command_outputs.push(output)),
+                HookAction::WriteFile(req) => self
+                    .write_file(req)
+                    .map(|path| results.files_written.push(path)),
                 HookAction::LogMessage(req) => {
                     Self::log_message(req);
+                    Ok(())
+                }
+            };
+
+            match outcome {
+                Ok(()) => {}
+                Err(ActionError::Violation(violation)) => {
+                    tracing::warn!("Sandbox violation: {violation}");
+                    results.violations.push(violation);
                 }
+                Err(ActionError::Failed(e)) => results.errors.push(e),
             }
         }
 
@@ -253,15 +522,9 @@ impl WasmHook {
     }
 
     /// Executes a command action.
-    fn execute_command(&self, req: &CommandRequest) -> Result<CommandOutput, String> {
-        // Validate command is whitelisted
-        if !ALLOWED_COMMANDS.contains(&req.command.as_str()) {
-            return Err(format!(
-                "command '{}' is not allowed. Allowed commands: {}",
-                req.command,
-                ALLOWED_COMMANDS.join(", ")
-            ));
-        }
+    fn execute_command(&self, req: &CommandRequest) -> Result<CommandOutput, ActionError> {
+        // Validate command against the sandbox policy
+        self.sandbox.check_command(&req.command, &req.args)?;
 
         // Determine working directory
         let cwd = match &req.workdir {
@@ -283,18 +546,7 @@ impl WasmHook {
             cwd
         );
 
-        // Execute command
-        let output = Command::new(&req.command)
-            .args(&req.args)
-            .current_dir(&cwd)
-            .output()
-            .map_err(|e| format!("failed to execute command '{}': {}", req.command, e))?;
-
-        let result = CommandOutput {
-            exit_code: output.status.code().unwrap_or(-1),
-            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
-            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
-        };
+        let result = self.run_command(req, &cwd)?;
 
         if result.exit_code != 0 {
             tracing::warn!(
@@ -308,18 +560,70 @@ impl WasmHook {
         Ok(result)
     }
 
+    /// Runs a command, killing it if it exceeds the sandbox timeout.
+    fn run_command(&self, req: &CommandRequest, cwd: &Path) -> Result<CommandOutput, ActionError> {
+        let mut child = Command::new(&req.command)
+            .args(&req.args)
+            .current_dir(cwd)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                ActionError::Failed(format!("failed to execute command '{}': {e}", req.command))
+            })?;
+
+        // Drain pipes on background threads so the child never blocks on a full pipe
+        let stdout = child.stdout.take().map(spawn_reader);
+        let stderr = child.stderr.take().map(spawn_reader);
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(|e| {
+                ActionError::Failed(format!("failed to wait for command '{}': {e}", req.command))
+            })? {
+                break status;
+            }
+
+            if let Some(timeout) = self.sandbox.timeout()
+                && started.elapsed() >= timeout
+            {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ActionError::Violation(SandboxViolation::Timeout {
+                    command: req.command.clone(),
+                    seconds: timeout.as_secs(),
+                }));
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        };
+
+        let collect = |handle: Option<thread::JoinHandle<String>>| {
+            handle.and_then(|h| h.join().ok()).unwrap_or_default()
+        };
+
+        Ok(CommandOutput {
+            exit_code: status.code().unwrap_or(-1),
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        })
+    }
+
     /// Writes a file action.
-    fn write_file(&self, req: &FileWriteRequest) -> Result<PathBuf, String> {
-        let path = self.resolve_path(&req.path)?;
+    fn write_file(&self, req: &FileWriteRequest) -> Result<PathBuf, ActionError> {
+        let path = self.resolve_path(&req.path).map_err(ActionError::Failed)?;
+        self.sandbox
+            .check_write(&normalize_path(&path), &normalize_path(&self.workdir))?;
 
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)
-                .map_err(|e| format!("failed to create directory: {e}"))?;
+                .map_err(|e| ActionError::Failed(format!("failed to create directory: {e}")))?;
         }
 
-        std::fs::write(&path, &req.content)
-            .map_err(|e| format!("failed to write file '{}': {e}", path.display()))?;
+        std::fs::write(&path, &req.content).map_err(|e| {
+            ActionError::Failed(format!("failed to write file '{}': {e}", path.display()))
+        })?;
 
         tracing::debug!("Wrote file: {}", path.display());
 
@@ -363,6 +667,59 @@ impl WasmHook {
     }
 }
 
+/// Reads a child pipe to completion on a background thread.
+fn spawn_reader(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+/// Sends an HTTP request of a plugin, without following redirects.
+fn send_request(request: &HttpRequest, timeout: Duration) -> Result<HttpResponse, String> {
+    let method = reqwest::Method::from_bytes(request.method.to_ascii_uppercase().as_bytes())
+        .map_err(|_| format!("invalid HTTP method '{}'", request.method))?;
+    let client = reqwest::blocking::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(timeout)
+        .user_agent(concat!("unduler/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("failed to create HTTP client: {e}"))?;
+
+    let mut builder = client.request(method, &request.url);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = &request.body {
+        builder = builder.body(body.clone());
+    }
+
+    let response = builder
+        .send()
+        .map_err(|e| format!("HTTP request to '{}' failed: {e}", request.url))?;
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), value.to_string()))
+        })
+        .collect();
+    let body = response
+        .text()
+        .map_err(|e| format!("failed to read HTTP response from '{}': {e}", request.url))?;
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
 /// Normalizes a path by resolving . and .. components without requiring the path to exist.
 fn normalize_path(path: &Path) -> PathBuf {
     let mut components = Vec::new();
@@ -382,14 +739,16 @@ fn normalize_path(path: &Path) -> PathBuf {
 
 // Re-export generated types
 pub use unduler::plugin::types::{
-    CommandRequest, FileWriteRequest, HookAction, HookResult, LogLevel, LogRequest, PluginInfo,
-    PluginType, ReleaseContext,
+    BumpType, CommandRequest, FileWriteRequest, HookAction, HookResult, LogLevel, LogRequest,
+    ParsedCommit, PluginInfo, PluginType, ReleaseContext, Version,
 };
 
-/// Returns the list of allowed commands for hooks.
+pub use unduler::plugin::http::{HttpRequest, HttpResponse};
+
+/// Returns the list of commands hooks may execute under the default sandbox policy.
 #[must_use]
 pub fn allowed_commands() -> &'static [&'static str] {
-    ALLOWED_COMMANDS
+    DEFAULT_ALLOWED_COMMANDS
 }
 
 #[cfg(test)]
@@ -405,6 +764,8 @@ mod tests {
         assert!(commands.contains(&"pnpm"));
         assert!(commands.contains(&"gh"));
         assert!(commands.contains(&"git"));
+        assert!(commands.contains(&"mvn"));
+        assert!(!commands.contains(&"./gradlew"));
         assert!(!commands.contains(&"rm"));
         assert!(!commands.contains(&"sudo"));
     }
@@ -0,0 +1,223 @@
+//! Shared host for the WASM plugins of a release.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use wasmtime::component::Component;
+
+use crate::adapter::lock;
+use crate::limits;
+use crate::{
+    SandboxPolicy, WasmBumper, WasmEngine, WasmFormatter, WasmHook, WasmParser, WasmResult,
+};
+
+/// Plugin instance shared by every user of a [`PluginHost`].
+pub type SharedPlugin<T> = Arc<Mutex<T>>;
+
+/// Instances of one plugin type, by component path.
+type Instances<T> = Mutex<HashMap<PathBuf, SharedPlugin<T>>>;
+
+/// Runs the WASM plugins of a release on a single engine.
+///
+/// Plugins are loaded from their path on first use, then the same instance
+/// serves every later call, e.g. the parse call of each commit, and every
+/// pipeline stage asking for the same plugin. A component loaded for several
+/// plugin types is compiled only once.
+pub struct PluginHost {
+    engine: WasmEngine,
+    components: Mutex<HashMap<PathBuf, Component>>,
+    parsers: Instances<WasmParser>,
+    bumpers: Instances<WasmBumper>,
+    formatters: Instances<WasmFormatter>,
+    hooks: Instances<WasmHook>,
+}
+
+impl PluginHost {
+    /// Creates a host running plugins on an engine.
+    #[must_use]
+    pub fn new(engine: WasmEngine) -> Self {
+        Self {
+            engine,
+            components: Mutex::new(HashMap::new()),
+            parsers: Mutex::new(HashMap::new()),
+            bumpers: Mutex::new(HashMap::new()),
+            formatters: Mutex::new(HashMap::new()),
+            hooks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the engine shared by the plugins.
+    #[must_use]
+    pub fn engine(&self) -> &WasmEngine {
+        &self.engine
+    }
+
+    /// Returns true if the component at a path has been loaded.
+    #[must_use]
+    pub fn is_loaded(&self, path: &Path) -> bool {
+        lock(&self.components).contains_key(path)
+    }
+
+    /// Returns the component at a path, compiling it on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded.
+    pub fn component(&self, path: &Path) -> WasmResult<Component> {
+        let mut components = lock(&self.components);
+        if let Some(component) = components.get(path) {
+            return Ok(component.clone());
+        }
+
+        let component = self.engine.load_component(path)?;
+        components.insert(path.to_path_buf(), component.clone());
+        Ok(component)
+    }
+
+    /// Returns the parser plugin at a path, loading it on first use and
+    /// passing the new instance through `init`, e.g. to configure it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated,
+    /// if its plugin information cannot be read, or if `init` fails.
+    pub fn parser(
+        &self,
+        path: &Path,
+        init: impl FnOnce(WasmParser) -> WasmResult<WasmParser>,
+    ) -> WasmResult<SharedPlugin<WasmParser>> {
+        shared(&self.parsers, path, || {
+            let component = self.component(path)?;
+            init(WasmParser::instantiate(
+                &self.engine,
+                &component,
+                limits::file_name(path),
+            )?)
+        })
+    }
+
+    /// Returns the bumper plugin at a path, loading it on first use and
+    /// passing the new instance through `init`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated,
+    /// if its plugin information cannot be read, or if `init` fails.
+    pub fn bumper(
+        &self,
+        path: &Path,
+        init: impl FnOnce(WasmBumper) -> WasmResult<WasmBumper>,
+    ) -> WasmResult<SharedPlugin<WasmBumper>> {
+        shared(&self.bumpers, path, || {
+            let component = self.component(path)?;
+            init(WasmBumper::instantiate(
+                &self.engine,
+                &component,
+                limits::file_name(path),
+            )?)
+        })
+    }
+
+    /// Returns the formatter plugin at a path, loading it on first use with
+    /// the WASI capabilities of a sandbox policy, and passing the new
+    /// instance through `init`.
+    ///
+    /// The working directory and sandbox policy only apply when the
+    /// formatter is loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated,
+    /// if its plugin information cannot be read, or if `init` fails.
+    pub fn formatter(
+        &self,
+        path: &Path,
+        workdir: &Path,
+        sandbox: &SandboxPolicy,
+        init: impl FnOnce(WasmFormatter) -> WasmResult<WasmFormatter>,
+    ) -> WasmResult<SharedPlugin<WasmFormatter>> {
+        shared(&self.formatters, path, || {
+            let component = self.component(path)?;
+            init(WasmFormatter::instantiate(
+                &self.engine,
+                &component,
+                workdir,
+                sandbox,
+                limits::file_name(path),
+            )?)
+        })
+    }
+
+    /// Returns the hook plugin at a path, loading it on first use to run in
+    /// `workdir` under a sandbox policy, and passing the new instance
+    /// through `init`, e.g. to give it its secrets.
+    ///
+    /// The working directory and sandbox policy only apply when the hook is
+    /// loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be loaded or instantiated,
+    /// if its plugin information cannot be read, or if `init` fails.
+    pub fn hook(
+        &self,
+        path: &Path,
+        workdir: &Path,
+        sandbox: SandboxPolicy,
+        init: impl FnOnce(WasmHook) -> WasmResult<WasmHook>,
+    ) -> WasmResult<SharedPlugin<WasmHook>> {
+        shared(&self.hooks, path, || {
+            let component = self.component(path)?;
+            init(WasmHook::instantiate(
+                &self.engine,
+                &component,
+                workdir.to_path_buf(),
+                sandbox,
+                limits::file_name(path),
+            )?)
+        })
+    }
+}
+
+/// Returns the instance of the plugin at a path, loading it on first use.
+fn shared<T>(
+    instances: &Instances<T>,
+    path: &Path,
+    load: impl FnOnce() -> WasmResult<T>,
+) -> WasmResult<SharedPlugin<T>> {
+    let mut instances = lock(instances);
+    if let Some(instance) = instances.get(path) {
+        return Ok(Arc::clone(instance));
+    }
+
+    let instance = Arc::new(Mutex::new(load()?));
+    instances.insert(path.to_path_buf(), Arc::clone(&instance));
+    Ok(instance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_not_found() {
+        let host = PluginHost::new(WasmEngine::new().unwrap());
+        let path = Path::new("/nonexistent/plugin.wasm");
+
+        assert!(host.parser(path, Ok).is_err());
+        assert!(!host.is_loaded(path));
+    }
+
+    #[test]
+    fn test_hook_not_found() {
+        let host = PluginHost::new(WasmEngine::new().unwrap());
+        let path = Path::new("/nonexistent/plugin.wasm");
+
+        assert!(
+            host.hook(path, Path::new("/tmp"), SandboxPolicy::default(), Ok)
+                .is_err()
+        );
+        assert!(!host.is_loaded(path));
+    }
+}
@@ -3,16 +3,29 @@
 //! This crate provides the infrastructure to load and execute WASM plugins
 //! using the wasmtime runtime with Component Model support.
 
+pub mod adapter;
 pub mod bumper;
+mod config;
+mod convert;
 pub mod engine;
 pub mod error;
 pub mod formatter;
 pub mod hook;
+pub mod host;
+pub mod limits;
 pub mod parser;
+pub mod sandbox;
+pub mod secrets;
+mod wasi;
 
+pub use adapter::{WasmBumpStrategy, WasmChangelogFormatter, WasmCommitParser, WasmReleaseHook};
 pub use bumper::WasmBumper;
 pub use engine::WasmEngine;
 pub use error::{WasmError, WasmResult};
 pub use formatter::WasmFormatter;
 pub use hook::WasmHook;
+pub use host::{PluginHost, SharedPlugin};
+pub use limits::WasmLimits;
 pub use parser::WasmParser;
+pub use sandbox::{SandboxPolicy, SandboxViolation};
+pub use secrets::Secrets;
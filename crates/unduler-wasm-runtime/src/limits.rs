@@ -0,0 +1,207 @@
+//! Execution limits for WASM plugins.
+//!
+//! Calls into a plugin are bounded in time using wasmtime epoch interruption,
+//! and linear memory growth is capped per plugin instance.
+
+use std::path::Path;
+use std::time::Duration;
+
+use unduler_config::WasmConfig;
+use wasmtime::{Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+use crate::{WasmEngine, WasmError};
+
+/// Interval at which the engine epoch is incremented.
+pub(crate) const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Default maximum duration of a single plugin call.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default maximum linear memory of a plugin instance, in bytes.
+pub const DEFAULT_MAX_MEMORY: usize = 256 * 1024 * 1024;
+
+/// Resource limits applied to plugin instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    timeout: Option<Duration>,
+    max_memory: Option<usize>,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_TIMEOUT),
+            max_memory: Some(DEFAULT_MAX_MEMORY),
+        }
+    }
+}
+
+impl WasmLimits {
+    /// Creates limits without any timeout or memory cap.
+    #[must_use]
+    pub fn unlimited() -> Self {
+        Self {
+            timeout: None,
+            max_memory: None,
+        }
+    }
+
+    /// Creates limits from the `[wasm]` configuration section.
+    #[must_use]
+    pub fn from_config(config: &WasmConfig) -> Self {
+        Self {
+            timeout: (config.timeout > 0).then(|| Duration::from_secs(config.timeout)),
+            max_memory: (config.max_memory > 0).then(|| {
+                usize::try_from(config.max_memory.saturating_mul(1024 * 1024)).unwrap_or(usize::MAX)
+            }),
+        }
+    }
+
+    /// Sets the maximum duration of a single plugin call.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum linear memory of a plugin instance, in bytes.
+    #[must_use]
+    pub fn with_max_memory(mut self, max_memory: Option<usize>) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Returns the maximum duration of a single plugin call.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the maximum linear memory of a plugin instance, in bytes.
+    #[must_use]
+    pub fn max_memory(&self) -> Option<usize> {
+        self.max_memory
+    }
+
+    /// Returns the number of epoch ticks a call may run for.
+    pub(crate) fn deadline_ticks(&self) -> u64 {
+        match self.timeout {
+            // Far enough in the future to never trigger, without overflowing
+            None => u64::MAX / 2,
+            Some(timeout) => {
+                let ticks = timeout.as_millis() / EPOCH_TICK.as_millis();
+                u64::try_from(ticks).unwrap_or(u64::MAX / 2).max(1)
+            }
+        }
+    }
+
+    /// Builds the wasmtime store limits.
+    pub(crate) fn store_limits(&self) -> StoreLimits {
+        let mut builder = StoreLimitsBuilder::new().trap_on_grow_failure(true);
+        if let Some(max_memory) = self.max_memory {
+            builder = builder.memory_size(max_memory);
+        }
+        builder.build()
+    }
+}
+
+/// Store state that carries resource limits.
+pub(crate) trait LimitedState: Send + 'static {
+    /// Returns the store limits of this state.
+    fn store_limits(&mut self) -> &mut StoreLimits;
+}
+
+/// Creates a store with the engine limits applied.
+pub(crate) fn new_store<T: LimitedState>(engine: &WasmEngine, state: T) -> Store<T> {
+    let mut store = Store::new(engine.inner(), state);
+    store.limiter(|state| state.store_limits());
+    store.set_epoch_deadline(engine.limits().deadline_ticks());
+    store
+}
+
+/// Name of a plugin instantiated from a component, until it reports its
+/// own.
+pub(crate) const COMPONENT_NAME: &str = "component";
+
+/// Name of a plugin loaded from a file, until it reports its own: its file
+/// name.
+pub(crate) fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Converts a failed plugin call into a [`WasmError`].
+pub(crate) fn call_error(plugin: &str, function: &str, error: &wasmtime::Error) -> WasmError {
+    if matches!(error.downcast_ref::<Trap>(), Some(Trap::Interrupt)) {
+        WasmError::Timeout {
+            plugin: plugin.to_string(),
+            function: function.to_string(),
+        }
+    } else {
+        WasmError::FunctionCall {
+            name: function.to_string(),
+            reason: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_limits() {
+        let limits = WasmLimits::default();
+        assert_eq!(limits.timeout(), Some(DEFAULT_TIMEOUT));
+        assert_eq!(limits.max_memory(), Some(DEFAULT_MAX_MEMORY));
+        assert_eq!(limits.deadline_ticks(), 3000);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let limits = WasmLimits::unlimited();
+        assert!(limits.timeout().is_none());
+        assert!(limits.max_memory().is_none());
+        assert_eq!(limits.deadline_ticks(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_short_timeout_has_at_least_one_tick() {
+        let limits = WasmLimits::default().with_timeout(Some(Duration::from_millis(1)));
+        assert_eq!(limits.deadline_ticks(), 1);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = WasmConfig {
+            timeout: 5,
+            max_memory: 64,
+        };
+        let limits = WasmLimits::from_config(&config);
+        assert_eq!(limits.timeout(), Some(Duration::from_secs(5)));
+        assert_eq!(limits.max_memory(), Some(64 * 1024 * 1024));
+
+        let config = WasmConfig {
+            timeout: 0,
+            max_memory: 0,
+        };
+        assert_eq!(WasmLimits::from_config(&config), WasmLimits::unlimited());
+    }
+
+    #[test]
+    fn test_call_error_maps_interrupt_to_timeout() {
+        let error = wasmtime::Error::new(Trap::Interrupt);
+        let err = call_error("conventional", "parse", &error);
+        assert!(matches!(err, WasmError::Timeout { .. }));
+        assert_eq!(
+            err.to_string(),
+            "plugin 'conventional' timed out in 'parse'"
+        );
+
+        let error = wasmtime::Error::msg("boom");
+        let err = call_error("conventional", "parse", &error);
+        assert!(matches!(err, WasmError::FunctionCall { .. }));
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::path::Path;
 
-use wasmtime::Store;
 use wasmtime::component::{Component, Linker};
+use wasmtime::{Store, StoreLimits};
 
+use crate::config::{self, UndulerConfigurable};
+use crate::limits::{self, LimitedState};
 use crate::{WasmEngine, WasmError, WasmResult};
 
 // Generate bindings from WIT
@@ -13,13 +15,39 @@ wasmtime::component::bindgen!({
     path: "../unduler-plugin/wit",
 });
 
+/// Bindings for parsers that also export the `batch-parser` interface.
+mod batched {
+    wasmtime::component::bindgen!({
+        world: "unduler-batch-parser",
+        path: "../unduler-plugin/wit",
+        with: {
+            "unduler:plugin/types": crate::parser::unduler::plugin::types,
+        },
+    });
+}
+
+/// Maximum number of commits sent to a plugin in a single `parse-batch` call.
+pub const BATCH_SIZE: usize = 256;
+
 /// Store state for parser plugins (no WASI needed).
-pub struct ParserState;
+pub struct ParserState {
+    limits: StoreLimits,
+}
+
+impl LimitedState for ParserState {
+    fn store_limits(&mut self) -> &mut StoreLimits {
+        &mut self.limits
+    }
+}
 
 /// WASM parser plugin wrapper.
 pub struct WasmParser {
     store: Store<ParserState>,
     instance: UndulerParser,
+    batched: Option<batched::UndulerBatchParser>,
+    configurable: Option<UndulerConfigurable>,
+    name: String,
+    deadline: u64,
 }
 
 impl WasmParser {
@@ -27,25 +55,63 @@ impl WasmParser {
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be instantiated.
+    /// Returns an error if the component cannot be instantiated, or if its
+    /// plugin information cannot be read.
     pub fn from_component(engine: &WasmEngine, component: &Component) -> WasmResult<Self> {
-        let mut store = Store::new(engine.inner(), ParserState);
+        Self::instantiate(engine, component, limits::COMPONENT_NAME.to_string())
+    }
+
+    /// Creates a new WASM parser from a component, named `name` until the
+    /// component reports its own name.
+    pub(crate) fn instantiate(
+        engine: &WasmEngine,
+        component: &Component,
+        name: String,
+    ) -> WasmResult<Self> {
+        let state = ParserState {
+            limits: engine.limits().store_limits(),
+        };
+        let mut store = limits::new_store(engine, state);
         let linker = Linker::new(engine.inner());
 
-        let instance = UndulerParser::instantiate(&mut store, component, &linker)
+        let instance = linker
+            .instantiate(&mut store, component)
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        let configurable = config::lookup(&mut store, instance);
+        let parser = UndulerParser::new(&mut store, &instance)
             .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+        // `batch-parser` is optional, older parsers only export `parser`
+        let batched = batched::UndulerBatchParser::new(&mut store, &instance).ok();
 
-        Ok(Self { store, instance })
+        let mut parser = Self {
+            store,
+            instance: parser,
+            batched,
+            configurable,
+            name,
+            deadline: engine.limits().deadline_ticks(),
+        };
+        // Until it answers, a failed call names the plugin after its file
+        parser.name = parser.info()?.name;
+
+        Ok(parser)
     }
 
     /// Creates a new WASM parser from a file path.
     ///
     /// # Errors
     ///
-    /// Returns an error if the component cannot be loaded or instantiated.
+    /// Returns an error if the component cannot be loaded or instantiated, or
+    /// if its plugin information cannot be read.
     pub fn from_file(engine: &WasmEngine, path: &Path) -> WasmResult<Self> {
         let component = engine.load_component(path)?;
-        Self::from_component(engine, &component)
+        Self::instantiate(engine, &component, limits::file_name(path))
+    }
+
+    /// Returns the plugin name reported by the component.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
     }
 
     /// Gets plugin information.
@@ -54,13 +120,34 @@ impl WasmParser {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn info(&mut self) -> WasmResult<PluginInfo> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_parser()
             .call_info(&mut self.store)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "info".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "info", &e))
+    }
+
+    /// Returns true if the plugin exports the `config` interface.
+    #[must_use]
+    pub fn is_configurable(&self) -> bool {
+        self.configurable.is_some()
+    }
+
+    /// Delivers the `[plugins.<name>]` configuration of the plugin, as a
+    /// JSON object. Plugins not exporting the `config` interface ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plugin rejects the configuration or the WASM
+    /// function call fails.
+    pub fn configure(&mut self, config: &str) -> WasmResult<()> {
+        self.store.set_epoch_deadline(self.deadline);
+        config::configure(
+            &mut self.store,
+            self.configurable.as_ref(),
+            &self.name,
+            config,
+        )
     }
 
     /// Parses a raw commit.
@@ -69,13 +156,56 @@ impl WasmParser {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn parse(&mut self, commit: &RawCommit) -> WasmResult<Option<ParsedCommit>> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_parser()
             .call_parse(&mut self.store, commit)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "parse".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "parse", &e))
+    }
+
+    /// Returns true if the plugin exports the `batch-parser` interface.
+    #[must_use]
+    pub fn has_parse_batch(&self) -> bool {
+        self.batched.is_some()
+    }
+
+    /// Parses raw commits, returning the result for each of them in order.
+    ///
+    /// Commits are sent in chunks of [`BATCH_SIZE`] when the plugin exports
+    /// the `batch-parser` interface, one by one otherwise. Each chunk gets
+    /// the time limit of a single call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a WASM function call fails, or if the plugin
+    /// returns a result count different from the commit count.
+    pub fn parse_batch(&mut self, commits: &[RawCommit]) -> WasmResult<Vec<Option<ParsedCommit>>> {
+        let Some(batched) = &self.batched else {
+            return commits.iter().map(|commit| self.parse(commit)).collect();
+        };
+
+        let mut parsed = Vec::with_capacity(commits.len());
+        for chunk in commits.chunks(BATCH_SIZE) {
+            self.store.set_epoch_deadline(self.deadline);
+            let results = batched
+                .unduler_plugin_batch_parser()
+                .call_parse_batch(&mut self.store, chunk)
+                .map_err(|e| limits::call_error(&self.name, "parse-batch", &e))?;
+            if results.len() != chunk.len() {
+                return Err(WasmError::FunctionCall {
+                    name: "parse-batch".to_string(),
+                    reason: format!(
+                        "plugin '{}' returned {} results for {} commits",
+                        self.name,
+                        results.len(),
+                        chunk.len()
+                    ),
+                });
+            }
+            parsed.extend(results);
+        }
+
+        Ok(parsed)
     }
 
     /// Checks if this parser can handle the commit.
@@ -84,13 +214,11 @@ impl WasmParser {
     ///
     /// Returns an error if the WASM function call fails.
     pub fn can_parse(&mut self, commit: &RawCommit) -> WasmResult<bool> {
+        self.store.set_epoch_deadline(self.deadline);
         self.instance
             .unduler_plugin_parser()
             .call_can_parse(&mut self.store, commit)
-            .map_err(|e| WasmError::FunctionCall {
-                name: "can_parse".to_string(),
-                reason: e.to_string(),
-            })
+            .map_err(|e| limits::call_error(&self.name, "can_parse", &e))
     }
 }
 
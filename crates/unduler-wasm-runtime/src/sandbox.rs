@@ -0,0 +1,539 @@
+//! Sandbox policy for actions requested by hook plugins, and for the WASI
+//! capabilities of plugins.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use thiserror::Error;
+use unduler_config::SandboxConfig;
+
+/// Commands hooks are allowed to execute when no explicit list is configured.
+///
+/// The `./mvnw` and `./gradlew` wrappers, scripts of the repository, are left
+/// out: a plugin is granted them through its `allowed_commands`.
+pub const DEFAULT_ALLOWED_COMMANDS: &[&str] =
+    &["cargo", "npm", "yarn", "pnpm", "gh", "git", "mvn", "gradle"];
+
+/// Subcommands known to access the network, per command.
+///
+/// An empty list means every invocation of the command is considered networked.
+const NETWORK_COMMANDS: &[(&str, &[&str])] = &[
+    ("curl", &[]),
+    ("wget", &[]),
+    ("ssh", &[]),
+    ("scp", &[]),
+    ("gh", &[]),
+    ("git", &["clone", "fetch", "ls-remote", "pull", "push"]),
+    (
+        "cargo",
+        &[
+            "fetch", "install", "login", "owner", "publish", "search", "update", "yank",
+        ],
+    ),
+    (
+        "npm",
+        &[
+            "ci",
+            "dist-tag",
+            "install",
+            "login",
+            "publish",
+            "unpublish",
+            "update",
+            "view",
+        ],
+    ),
+    ("yarn", &["add", "install", "npm", "publish", "upgrade"]),
+    ("pnpm", &["add", "install", "publish", "update"]),
+    ("mvn", &["deploy", "release:perform"]),
+    ("./mvnw", &["deploy", "release:perform"]),
+    ("gradle", &["publish"]),
+    ("./gradlew", &["publish"]),
+];
+
+/// A sandbox rule broken by a hook action.
+#[derive(Debug, Error)]
+pub enum SandboxViolation {
+    /// The command is not in the allowed list.
+    #[error("command '{command}' is not allowed. Allowed commands: {allowed}")]
+    CommandNotAllowed { command: String, allowed: String },
+
+    /// The command requires network access, which is disabled.
+    #[error("command '{0}' requires network access, which is disabled for this plugin")]
+    NetworkDisabled(String),
+
+    /// The path is outside the allowed write paths.
+    #[error("writing to '{0}' is not allowed")]
+    WriteNotAllowed(String),
+
+    /// The command exceeded the maximum execution time.
+    #[error("command '{command}' timed out after {seconds}s")]
+    Timeout { command: String, seconds: u64 },
+
+    /// The HTTP request targets a host outside the allowed hosts, is not an
+    /// HTTP request, or network access is disabled.
+    #[error("request to '{0}' is not allowed")]
+    RequestNotAllowed(String),
+}
+
+/// Limits applied to the actions a hook plugin requests, and WASI
+/// capabilities granted to a plugin.
+#[derive(Debug, Clone)]
+pub struct SandboxPolicy {
+    allowed_commands: Vec<String>,
+    allowed_paths: Vec<PathBuf>,
+    network: bool,
+    allowed_hosts: Vec<String>,
+    timeout: Option<Duration>,
+    filesystem: bool,
+    clocks: bool,
+    random: bool,
+}
+
+impl Default for SandboxPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_commands: DEFAULT_ALLOWED_COMMANDS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            allowed_paths: Vec::new(),
+            network: true,
+            allowed_hosts: Vec::new(),
+            timeout: None,
+            filesystem: false,
+            clocks: false,
+            random: false,
+        }
+    }
+}
+
+impl SandboxPolicy {
+    /// Creates a policy from a plugin's sandbox configuration.
+    #[must_use]
+    pub fn from_config(config: &SandboxConfig) -> Self {
+        let mut policy = Self::default()
+            .with_allowed_paths(config.allowed_paths.iter().map(PathBuf::from))
+            .with_network(config.network)
+            .with_allowed_hosts(config.allowed_hosts.iter().cloned())
+            .with_filesystem(config.filesystem)
+            .with_clocks(config.clocks)
+            .with_random(config.random);
+
+        if let Some(commands) = &config.allowed_commands {
+            policy = policy.with_allowed_commands(commands.iter().cloned());
+        }
+        if let Some(seconds) = config.timeout {
+            policy = policy.with_timeout(Duration::from_secs(seconds));
+        }
+
+        policy
+    }
+
+    /// Sets the commands the plugin may execute.
+    #[must_use]
+    pub fn with_allowed_commands(mut self, commands: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_commands = commands.into_iter().collect();
+        self
+    }
+
+    /// Sets the paths, relative to the working directory, the plugin may write to.
+    ///
+    /// An empty list allows writing anywhere inside the working directory.
+    #[must_use]
+    pub fn with_allowed_paths(mut self, paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.allowed_paths = paths.into_iter().collect();
+        self
+    }
+
+    /// Enables or disables commands that access the network.
+    #[must_use]
+    pub fn with_network(mut self, network: bool) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Sets the hosts the plugin may send HTTP requests to: exact host names,
+    /// or `*.domain` for any subdomain of `domain`.
+    ///
+    /// An empty list denies HTTP requests.
+    #[must_use]
+    pub fn with_allowed_hosts(mut self, hosts: impl IntoIterator<Item = String>) -> Self {
+        self.allowed_hosts = hosts.into_iter().collect();
+        self
+    }
+
+    /// Sets the maximum execution time of a single command.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Grants or denies read-only WASI access to the repository.
+    #[must_use]
+    pub fn with_filesystem(mut self, filesystem: bool) -> Self {
+        self.filesystem = filesystem;
+        self
+    }
+
+    /// Grants or denies the WASI wall and monotonic clocks.
+    #[must_use]
+    pub fn with_clocks(mut self, clocks: bool) -> Self {
+        self.clocks = clocks;
+        self
+    }
+
+    /// Grants or denies WASI randomness.
+    #[must_use]
+    pub fn with_random(mut self, random: bool) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Returns the commands the plugin may execute.
+    #[must_use]
+    pub fn allowed_commands(&self) -> &[String] {
+        &self.allowed_commands
+    }
+
+    /// Returns whether network access is allowed.
+    #[must_use]
+    pub fn network(&self) -> bool {
+        self.network
+    }
+
+    /// Returns the hosts the plugin may send HTTP requests to.
+    #[must_use]
+    pub fn allowed_hosts(&self) -> &[String] {
+        &self.allowed_hosts
+    }
+
+    /// Returns the maximum execution time of a single command.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns whether the plugin may read the repository through WASI.
+    #[must_use]
+    pub fn filesystem(&self) -> bool {
+        self.filesystem
+    }
+
+    /// Returns whether the plugin may read the clocks through WASI.
+    #[must_use]
+    pub fn clocks(&self) -> bool {
+        self.clocks
+    }
+
+    /// Returns whether the plugin may get random bytes through WASI.
+    #[must_use]
+    pub fn random(&self) -> bool {
+        self.random
+    }
+
+    /// Returns true if the policy grants any WASI capability, so the plugin
+    /// is linked with WASI.
+    #[must_use]
+    pub fn wasi(&self) -> bool {
+        self.filesystem || self.clocks || self.random
+    }
+
+    /// Checks that a command invocation is allowed.
+    ///
+    /// Network detection is best-effort: it only knows the subcommands of the
+    /// default package managers and tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns a violation if the command is not allowed or requires network
+    /// access while it is disabled.
+    pub fn check_command(&self, command: &str, args: &[String]) -> Result<(), SandboxViolation> {
+        if !self.allowed_commands.iter().any(|c| c == command) {
+            return Err(SandboxViolation::CommandNotAllowed {
+                command: command.to_string(),
+                allowed: self.allowed_commands.join(", "),
+            });
+        }
+
+        if !self.network && requires_network(command, args) {
+            return Err(SandboxViolation::NetworkDisabled(command.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a normalized path inside `workdir` may be written.
+    ///
+    /// # Errors
+    ///
+    /// Returns a violation if the path is outside the allowed write paths.
+    pub fn check_write(&self, path: &Path, workdir: &Path) -> Result<(), SandboxViolation> {
+        if self.allowed_paths.is_empty() {
+            return Ok(());
+        }
+
+        let allowed = self
+            .allowed_paths
+            .iter()
+            .any(|allowed| path.starts_with(workdir.join(allowed)));
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(SandboxViolation::WriteNotAllowed(
+                path.display().to_string(),
+            ))
+        }
+    }
+
+    /// Checks that an HTTP request to `url` is allowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a violation if network access is disabled, the URL is not an
+    /// `http` or `https` URL, or its host is not allowed.
+    pub fn check_request(&self, url: &str) -> Result<(), SandboxViolation> {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+
+        match host {
+            Some(host)
+                if self.network && self.allowed_hosts.iter().any(|a| host_matches(a, &host)) =>
+            {
+                Ok(())
+            }
+            _ => Err(SandboxViolation::RequestNotAllowed(url.to_string())),
+        }
+    }
+}
+
+/// Returns true if `host` matches an allowed host, where `*.domain` matches
+/// any subdomain of `domain`.
+fn host_matches(allowed: &str, host: &str) -> bool {
+    let allowed = allowed.to_ascii_lowercase();
+    match allowed.strip_prefix("*.") {
+        Some(domain) => host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+        None => allowed == host,
+    }
+}
+
+/// Returns true if the command invocation is known to access the network.
+///
+/// Every word of the invocation is checked, so that a networked command run
+/// through another one, as in `env curl` or `sh -c "git push"`, is found. The
+/// subcommand of a command is the first word after it that is not an option,
+/// so that `mvn -Prelease deploy` is recognized.
+fn requires_network(command: &str, args: &[String]) -> bool {
+    let words: Vec<&str> = std::iter::once(command)
+        .chain(
+            args.iter()
+                .flat_map(|arg| arg.split(|c: char| c.is_whitespace() || ";&|()`'\"".contains(c))),
+        )
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words.iter().enumerate().any(|(index, word)| {
+        NETWORK_COMMANDS
+            .iter()
+            .find(|(name, _)| is_program(word, name))
+            .is_some_and(|(_, subcommands)| {
+                subcommands.is_empty()
+                    || words[index + 1..]
+                        .iter()
+                        .find(|word| !word.starts_with('-'))
+                        .is_some_and(|word| subcommands.contains(word))
+            })
+    })
+}
+
+/// Returns true if a word runs the program `name`, by name or by path.
+fn is_program(word: &str, name: &str) -> bool {
+    word == name || (!name.starts_with("./") && word.ends_with(&format!("/{name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = SandboxPolicy::default();
+        assert!(policy.check_command("cargo", &args(&["publish"])).is_ok());
+        assert!(matches!(
+            policy.check_command("rm", &args(&["-rf", "/"])),
+            Err(SandboxViolation::CommandNotAllowed { .. })
+        ));
+        assert!(policy.network());
+        assert!(policy.timeout().is_none());
+        assert!(!policy.wasi());
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let policy = SandboxPolicy::default().with_allowed_commands(["make".to_string()]);
+        assert!(policy.check_command("make", &[]).is_ok());
+        assert!(policy.check_command("cargo", &[]).is_err());
+    }
+
+    #[test]
+    fn test_network_disabled() {
+        let policy = SandboxPolicy::default().with_network(false);
+        assert!(matches!(
+            policy.check_command("cargo", &args(&["publish"])),
+            Err(SandboxViolation::NetworkDisabled(_))
+        ));
+        assert!(policy.check_command("gh", &args(&["release"])).is_err());
+        assert!(policy.check_command("cargo", &args(&["build"])).is_ok());
+        assert!(policy.check_command("git", &args(&["add", "."])).is_ok());
+    }
+
+    #[test]
+    fn test_network_disabled_through_other_commands() {
+        let policy = SandboxPolicy::default()
+            .with_allowed_commands(["sh".to_string(), "env".to_string()])
+            .with_network(false);
+        for (command, arguments) in [
+            ("env", &["curl", "https://example.com"][..]),
+            ("env", &["FOO=1", "/usr/bin/wget", "https://example.com"]),
+            ("sh", &["-c", "curl -fsS https://example.com"]),
+            ("sh", &["-c", "cargo build && git push origin main"]),
+            ("sh", &["-c", "cargo build;cargo publish"]),
+        ] {
+            assert!(
+                matches!(
+                    policy.check_command(command, &args(arguments)),
+                    Err(SandboxViolation::NetworkDisabled(_))
+                ),
+                "{command} {arguments:?}"
+            );
+        }
+        assert!(
+            policy
+                .check_command("sh", &args(&["-c", "cargo build && git tag v1.0.0"]))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_jvm_commands() {
+        let policy = SandboxPolicy::default();
+        assert!(policy.check_command("mvn", &args(&["deploy"])).is_ok());
+        assert!(matches!(
+            policy.check_command("./gradlew", &args(&["publish"])),
+            Err(SandboxViolation::CommandNotAllowed { .. })
+        ));
+
+        let policy = policy.with_allowed_commands(
+            ["mvn", "./mvnw", "gradle", "./gradlew"].map(ToString::to_string),
+        );
+        assert!(
+            policy
+                .check_command("./gradlew", &args(&["publish"]))
+                .is_ok()
+        );
+
+        let policy = policy.with_network(false);
+        assert!(matches!(
+            policy.check_command("mvn", &args(&["-B", "-Prelease", "deploy"])),
+            Err(SandboxViolation::NetworkDisabled(_))
+        ));
+        assert!(
+            policy
+                .check_command("./gradlew", &args(&["publish"]))
+                .is_err()
+        );
+        assert!(policy.check_command("gradle", &args(&["build"])).is_ok());
+        assert!(
+            policy
+                .check_command("./mvnw", &args(&["-B", "verify"]))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_check_write() {
+        let workdir = Path::new("/repo");
+        let policy = SandboxPolicy::default();
+        assert!(
+            policy
+                .check_write(Path::new("/repo/any/file"), workdir)
+                .is_ok()
+        );
+
+        let policy = policy.with_allowed_paths([PathBuf::from("dist")]);
+        assert!(
+            policy
+                .check_write(Path::new("/repo/dist/a.zip"), workdir)
+                .is_ok()
+        );
+        assert!(matches!(
+            policy.check_write(Path::new("/repo/src/main.rs"), workdir),
+            Err(SandboxViolation::WriteNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_request() {
+        let policy = SandboxPolicy::default();
+        assert!(matches!(
+            policy.check_request("https://api.github.com/repos"),
+            Err(SandboxViolation::RequestNotAllowed(_))
+        ));
+
+        let policy =
+            policy.with_allowed_hosts(["api.github.com".to_string(), "*.slack.com".to_string()]);
+        assert!(policy.check_request("https://api.github.com/repos").is_ok());
+        assert!(policy.check_request("https://API.GitHub.com/").is_ok());
+        assert!(
+            policy
+                .check_request("https://hooks.slack.com/services/x")
+                .is_ok()
+        );
+        assert!(policy.check_request("https://slack.com/").is_err());
+        assert!(policy.check_request("https://evilslack.com/").is_err());
+        assert!(policy.check_request("https://github.com/").is_err());
+        assert!(policy.check_request("file:///etc/passwd").is_err());
+        assert!(policy.check_request("not a url").is_err());
+
+        let policy = policy.with_network(false);
+        assert!(
+            policy
+                .check_request("https://api.github.com/repos")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = SandboxConfig {
+            allowed_commands: Some(vec!["make".to_string()]),
+            allowed_paths: vec!["dist".to_string()],
+            network: false,
+            allowed_hosts: vec!["api.github.com".to_string()],
+            timeout: Some(30),
+            filesystem: true,
+            clocks: false,
+            random: true,
+        };
+
+        let policy = SandboxPolicy::from_config(&config);
+        assert_eq!(policy.allowed_commands(), ["make".to_string()]);
+        assert!(!policy.network());
+        assert_eq!(policy.allowed_hosts(), ["api.github.com".to_string()]);
+        assert_eq!(policy.timeout(), Some(Duration::from_secs(30)));
+        assert!(policy.filesystem());
+        assert!(!policy.clocks());
+        assert!(policy.random());
+        assert!(policy.wasi());
+    }
+}
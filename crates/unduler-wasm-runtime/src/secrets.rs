@@ -0,0 +1,103 @@
+//! Secrets provided to hook plugins.
+//!
+//! A plugin declares its secrets in its `[plugins.<name>]` table by the
+//! environment variable holding them, e.g. `token_env = "GITHUB_TOKEN"` for
+//! the `token` secret. Variables are read when the plugin asks for a secret,
+//! so their values are never stored, logged or written to the registry.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+/// Suffix of the configuration keys declaring a secret.
+const SECRET_SUFFIX: &str = "_env";
+
+/// Secrets a plugin may get, by name, with the environment variable holding
+/// each of them.
+#[derive(Debug, Clone, Default)]
+pub struct Secrets {
+    variables: BTreeMap<String, String>,
+}
+
+impl Secrets {
+    /// Creates the secrets declared in the configuration of a plugin, by its
+    /// string `<secret>_env` keys.
+    #[must_use]
+    pub fn from_config(config: &Value) -> Self {
+        let variables = config
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| {
+                let name = key.strip_suffix(SECRET_SUFFIX)?;
+                let variable = value.as_str()?;
+                (!name.is_empty()).then(|| (name.to_string(), variable.to_string()))
+            })
+            .collect();
+        Self { variables }
+    }
+
+    /// Declares a secret held by an environment variable.
+    #[must_use]
+    pub fn with_secret(mut self, name: impl Into<String>, variable: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), variable.into());
+        self
+    }
+
+    /// Returns the names of the declared secrets.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.variables.keys().map(String::as_str)
+    }
+
+    /// Returns the value of a secret, if it is declared and its variable is
+    /// set.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.get_with(name, |variable| std::env::var(variable).ok())
+    }
+
+    /// Returns the value of a secret, reading variables with `var`.
+    fn get_with(&self, name: &str, var: impl Fn(&str) -> Option<String>) -> Option<String> {
+        let variable = self.variables.get(name)?;
+        let value = var(variable).filter(|value| !value.is_empty());
+        if value.is_none() {
+            tracing::warn!(secret = name, variable, "secret variable is not set");
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn env(name: &str) -> Option<String> {
+        (name == "GITHUB_TOKEN").then(|| "ghp_secret".to_string())
+    }
+
+    #[test]
+    fn test_from_config() {
+        let secrets = Secrets::from_config(&json!({
+            "token_env": "GITHUB_TOKEN",
+            "webhook_env": "SLACK_WEBHOOK",
+            "draft": true,
+            "_env": "IGNORED",
+            "count_env": 3,
+        }));
+        assert_eq!(secrets.names().collect::<Vec<_>>(), ["token", "webhook"]);
+    }
+
+    #[test]
+    fn test_get() {
+        let secrets = Secrets::default()
+            .with_secret("token", "GITHUB_TOKEN")
+            .with_secret("webhook", "SLACK_WEBHOOK");
+        assert_eq!(
+            secrets.get_with("token", env).as_deref(),
+            Some("ghp_secret")
+        );
+        assert!(secrets.get_with("webhook", env).is_none());
+        assert!(secrets.get_with("password", env).is_none());
+    }
+}
@@ -0,0 +1,195 @@
+//! Capability-scoped WASI for plugins whose sandbox policy grants it.
+//!
+//! Plugins only get WASI imports when their policy grants a capability:
+//! read-only access to the repository, clocks or randomness. Denied clocks
+//! are frozen at the Unix epoch and denied randomness traps, never handing
+//! out predictable bytes, so the other capabilities can be granted alone.
+
+use std::path::Path;
+use std::time::Duration;
+
+use wasmtime::component::{Linker, ResourceTable};
+use wasmtime_wasi::{
+    DirPerms, FilePerms, HostMonotonicClock, HostWallClock, WasiCtx, WasiCtxBuilder, WasiCtxView,
+    WasiView,
+};
+
+use crate::sandbox::SandboxPolicy;
+
+/// Version of the WASI interfaces linked by `wasmtime-wasi`.
+const WASI_VERSION: &str = "0.2.6";
+use crate::{WasmEngine, WasmError, WasmResult};
+
+/// WASI state of a plugin instance.
+pub struct WasiState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl WasiState {
+    /// Creates the WASI state granting the capabilities of a sandbox policy.
+    ///
+    /// With the filesystem capability, `workdir` is preopened read-only as
+    /// the current directory of the plugin.
+    pub(crate) fn new(sandbox: &SandboxPolicy, workdir: &Path) -> WasmResult<Self> {
+        let mut builder = WasiCtxBuilder::new();
+        if sandbox.filesystem() {
+            builder
+                .preopened_dir(workdir, ".", DirPerms::READ, FilePerms::READ)
+                .map_err(|e| {
+                    WasmError::Instantiation(format!(
+                        "failed to open {} for the plugin: {e}",
+                        workdir.display()
+                    ))
+                })?;
+        }
+        if !sandbox.clocks() {
+            builder.wall_clock(FrozenClock).monotonic_clock(FrozenClock);
+        }
+
+        Ok(Self {
+            ctx: builder.build(),
+            table: ResourceTable::new(),
+        })
+    }
+
+    /// Returns the view of the state used by the WASI implementation.
+    pub(crate) fn view(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.ctx,
+            table: &mut self.table,
+        }
+    }
+}
+
+impl WasiView for WasiState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        self.view()
+    }
+}
+
+/// Creates the linker of a plugin, with the WASI imports when its sandbox
+/// policy grants any WASI capability.
+pub(crate) fn linker<T: WasiView + 'static>(
+    engine: &WasmEngine,
+    sandbox: &SandboxPolicy,
+) -> WasmResult<Linker<T>> {
+    let mut linker = Linker::new(engine.inner());
+    if sandbox.wasi() {
+        wasmtime_wasi::p2::add_to_linker_sync(&mut linker)
+            .and_then(|()| {
+                if sandbox.random() {
+                    Ok(())
+                } else {
+                    deny_random(&mut linker)
+                }
+            })
+            .map_err(|e| WasmError::Instantiation(e.to_string()))?;
+    }
+    Ok(linker)
+}
+
+/// Replaces the WASI randomness functions with functions trapping.
+fn deny_random<T: 'static>(linker: &mut Linker<T>) -> wasmtime::Result<()> {
+    fn denied<R>() -> wasmtime::Result<R> {
+        Err(wasmtime::Error::msg(
+            "randomness is denied by the sandbox policy",
+        ))
+    }
+
+    linker.allow_shadowing(true);
+    let mut random = linker.instance(&format!("wasi:random/random@{WASI_VERSION}"))?;
+    random.func_wrap("get-random-bytes", |_, (_,): (u64,)| denied::<(Vec<u8>,)>())?;
+    random.func_wrap("get-random-u64", |_, ()| denied::<(u64,)>())?;
+    let mut insecure = linker.instance(&format!("wasi:random/insecure@{WASI_VERSION}"))?;
+    insecure.func_wrap("get-insecure-random-bytes", |_, (_,): (u64,)| {
+        denied::<(Vec<u8>,)>()
+    })?;
+    insecure.func_wrap("get-insecure-random-u64", |_, ()| denied::<(u64,)>())?;
+    let mut seed = linker.instance(&format!("wasi:random/insecure-seed@{WASI_VERSION}"))?;
+    seed.func_wrap("insecure-seed", |_, ()| denied::<((u64, u64),)>())?;
+    linker.allow_shadowing(false);
+    Ok(())
+}
+
+/// Clock frozen at the Unix epoch, for plugins denied clocks.
+struct FrozenClock;
+
+impl HostWallClock for FrozenClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    fn now(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+impl HostMonotonicClock for FrozenClock {
+    fn resolution(&self) -> u64 {
+        1_000_000_000
+    }
+
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linker_without_capabilities() {
+        let engine = WasmEngine::new().unwrap();
+        let sandbox = SandboxPolicy::default();
+        assert!(!sandbox.wasi());
+        assert!(linker::<WasiState>(&engine, &sandbox).is_ok());
+    }
+
+    #[test]
+    fn test_state_with_filesystem() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let sandbox = SandboxPolicy::default().with_filesystem(true);
+        assert!(WasiState::new(&sandbox, temp.path()).is_ok());
+        assert!(WasiState::new(&sandbox, &temp.path().join("missing")).is_err());
+    }
+
+    /// Component exporting `run`, returning a random number from WASI.
+    const RANDOM_COMPONENT: &str = r#"(component
+      (import "wasi:random/random@0.2.6" (instance $random
+        (export "get-random-u64" (func (result u64)))))
+      (core func $get (canon lower (func $random "get-random-u64")))
+      (core module $module
+        (import "random" "get" (func $get (result i64)))
+        (func (export "run") (result i64) (call $get)))
+      (core instance $instance (instantiate $module
+        (with "random" (instance (export "get" (func $get))))))
+      (func (export "run") (result u64) (canon lift (core func $instance "run"))))"#;
+
+    fn run_random(sandbox: &SandboxPolicy) -> wasmtime::Result<u64> {
+        let engine = WasmEngine::new().unwrap();
+        let component =
+            wasmtime::component::Component::new(engine.inner(), RANDOM_COMPONENT).unwrap();
+        let linker = linker::<WasiState>(&engine, sandbox).unwrap();
+        let state = WasiState::new(sandbox, Path::new(".")).unwrap();
+        let mut store = wasmtime::Store::new(engine.inner(), state);
+        store.set_epoch_deadline(1_000);
+        let instance = linker.instantiate(&mut store, &component)?;
+        let run = instance.get_typed_func::<(), (u64,)>(&mut store, "run")?;
+        Ok(run.call(&mut store, ())?.0)
+    }
+
+    #[test]
+    fn test_denied_random_traps() {
+        let sandbox = SandboxPolicy::default().with_clocks(true);
+        let err = run_random(&sandbox).unwrap_err();
+        assert!(
+            format!("{err:?}").contains("randomness is denied"),
+            "{err:?}"
+        );
+
+        let sandbox = sandbox.with_random(true);
+        assert!(run_random(&sandbox).is_ok());
+    }
+}
@@ -2,7 +2,8 @@
 
 use std::path::PathBuf;
 
-use unduler_wasm_runtime::{WasmBumper, WasmEngine};
+use unduler_plugin::{BumpStrategy, BumpType};
+use unduler_wasm_runtime::{WasmBumpStrategy, WasmBumper, WasmEngine};
 
 fn test_plugin_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -173,3 +174,48 @@ fn test_bumper_empty_commits() {
     let bump = bumper.determine(&commits).expect("determine failed");
     assert!(matches!(bump, unduler_wasm_runtime::bumper::BumpType::None));
 }
+
+#[test]
+fn test_bumper_without_next_version() {
+    let path = test_plugin_path();
+    if !path.exists() {
+        return;
+    }
+
+    let engine = WasmEngine::new().expect("Failed to create engine");
+    let mut bumper = WasmBumper::from_file(&engine, &path).expect("Failed to load bumper");
+    assert!(!bumper.has_next_version());
+
+    let current = unduler_wasm_runtime::bumper::Version {
+        major: 1,
+        minor: 0,
+        patch: 0,
+        pre: None,
+        build: None,
+    };
+    let next = bumper
+        .next_version(&current, &[])
+        .expect("next_version failed");
+    assert!(next.is_none());
+}
+
+#[test]
+fn test_bump_strategy_adapter() {
+    let path = test_plugin_path();
+    if !path.exists() {
+        return;
+    }
+
+    let engine = WasmEngine::new().expect("Failed to create engine");
+    let bumper = WasmBumper::from_file(&engine, &path).expect("Failed to load bumper");
+    let strategy = WasmBumpStrategy::new("semver", bumper).expect("Failed to wrap bumper");
+
+    let commits = vec![unduler_commit::ParsedCommit::builder("abc123", "feat").build()];
+    assert_eq!(strategy.determine(&commits), BumpType::Minor);
+    // Without `version-bumper`, the bump type applies
+    assert!(
+        strategy
+            .next_version(&semver::Version::new(1, 0, 0), &commits)
+            .is_none()
+    );
+}
@@ -1,8 +1,15 @@
 //! Integration tests for WASM parser plugins.
 
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use unduler_wasm_runtime::{WasmEngine, WasmParser};
+use chrono::Utc;
+use unduler_commit::RawCommit;
+use unduler_plugin::CommitParser;
+use unduler_wasm_runtime::{
+    PluginHost, WasmCommitParser, WasmEngine, WasmError, WasmLimits, WasmParser,
+};
 
 fn test_plugin_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -13,6 +20,110 @@ fn test_plugin_path() -> PathBuf {
         .join("test-plugins/parser-conventional.wasm")
 }
 
+/// Writes a component of a plugin `world` from a core module in WAT.
+fn write_component(path: &Path, world: &str, wat: &str) {
+    let mut resolve = wit_parser::Resolve::default();
+    resolve
+        .push_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../unduler-plugin/wit"))
+        .expect("failed to parse the plugin WIT");
+    let world = resolve
+        .select_world(resolve.packages.iter().next().unwrap().0, Some(world))
+        .expect("failed to select the plugin world");
+
+    let mut module = wat::parse_str(wat).expect("failed to parse the module");
+    wit_component::embed_component_metadata(
+        &mut module,
+        &resolve,
+        world,
+        wit_component::StringEncoding::UTF8,
+    )
+    .expect("failed to embed the component metadata");
+    let component = wit_component::ComponentEncoder::default()
+        .module(&module)
+        .and_then(|encoder| encoder.validate(true).encode())
+        .expect("failed to encode the component");
+    fs::write(path, component).expect("failed to write the component");
+}
+
+/// Writes a parser component whose `info` call never returns.
+fn write_hanging_parser(path: &Path) {
+    write_component(
+        path,
+        "unduler-parser",
+        r#"(module
+          (memory (export "memory") 1)
+          (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (i32.const 1024))
+          (func (export "unduler:plugin/parser@0.2.0#info") (result i32)
+            (loop $hang (br $hang))
+            (unreachable))
+          (func (export "unduler:plugin/parser@0.2.0#parse")
+            (param i32 i32 i32 i32 i32 i32 i32 i32 i64) (result i32)
+            (i32.const 0))
+          (func (export "unduler:plugin/parser@0.2.0#can-parse")
+            (param i32 i32 i32 i32 i32 i32 i32 i32 i64) (result i32)
+            (i32.const 0)))"#,
+    );
+}
+
+/// Writes a parser component exporting `batch-parser`, whose `parse` never
+/// parses a commit while `parse-batch` parses every commit as a `batch`
+/// commit.
+fn write_batch_parser(path: &Path) {
+    write_component(
+        path,
+        "unduler-batch-parser",
+        r#"(module
+          (memory (export "memory") 1)
+          ;; "batch", then the plugin info: name, version and description
+          ;; all "batch", and the parser type
+          (data (i32.const 100) "batch")
+          (data (i32.const 200)
+            "\64\00\00\00\05\00\00\00\64\00\00\00\05\00\00\00\64\00\00\00\05\00\00\00\00")
+          (global $heap (mut i32) (i32.const 1024))
+          (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr
+              (i32.and
+                (i32.add (global.get $heap) (i32.sub (local.get 2) (i32.const 1)))
+                (i32.sub (i32.const 0) (local.get 2))))
+            (global.set $heap (i32.add (local.get $ptr) (local.get 3)))
+            (local.get $ptr))
+          (func (export "unduler:plugin/parser@0.2.0#info") (result i32)
+            (i32.const 200))
+          (func (export "unduler:plugin/parser@0.2.0#parse")
+            (param i32 i32 i32 i32 i32 i32 i32 i32 i64) (result i32)
+            (i32.const 0))
+          (func (export "unduler:plugin/parser@0.2.0#can-parse")
+            (param i32 i32 i32 i32 i32 i32 i32 i32 i64) (result i32)
+            (i32.const 0))
+          ;; Returns `some` of an 88-byte `option<parsed-commit>` per commit
+          (func (export "unduler:plugin/batch-parser@0.2.0#parse-batch")
+            (param $commits i32) (param $len i32) (result i32)
+            (local $i i32) (local $item i32)
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                (local.set $item
+                  (i32.add (i32.const 32768) (i32.mul (local.get $i) (i32.const 88))))
+                (i32.store8 (local.get $item) (i32.const 1))
+                ;; hash, commit-type, message and author
+                (i32.store offset=8 (local.get $item) (i32.const 100))
+                (i32.store offset=12 (local.get $item) (i32.const 5))
+                (i32.store offset=16 (local.get $item) (i32.const 100))
+                (i32.store offset=20 (local.get $item) (i32.const 5))
+                (i32.store offset=36 (local.get $item) (i32.const 100))
+                (i32.store offset=40 (local.get $item) (i32.const 5))
+                (i32.store offset=68 (local.get $item) (i32.const 100))
+                (i32.store offset=72 (local.get $item) (i32.const 5))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (i32.store (i32.const 16) (i32.const 32768))
+            (i32.store (i32.const 20) (local.get $len))
+            (i32.const 16)))"#,
+    );
+}
+
 #[test]
 fn test_load_parser_plugin() {
     let path = test_plugin_path();
@@ -123,3 +234,112 @@ fn test_parser_breaking_change() {
     assert_eq!(parsed.commit_type, "feat");
     assert!(parsed.breaking);
 }
+
+#[test]
+fn test_parse_batch_without_interface() {
+    let path = test_plugin_path();
+    if !path.exists() {
+        return;
+    }
+
+    let engine = WasmEngine::new().expect("Failed to create engine");
+    let mut parser = WasmParser::from_file(&engine, &path).expect("Failed to load parser");
+    assert!(!parser.has_parse_batch());
+
+    let commits: Vec<_> = [
+        "feat: add feature",
+        "random commit message",
+        "fix(api): bug",
+    ]
+    .iter()
+    .map(|message| unduler_wasm_runtime::parser::RawCommit {
+        hash: "abc123".to_string(),
+        message: (*message).to_string(),
+        author: "Test".to_string(),
+        email: "test@test.com".to_string(),
+        timestamp: 0,
+    })
+    .collect();
+
+    let parsed = parser.parse_batch(&commits).expect("parse_batch failed");
+    assert_eq!(parsed.len(), 3);
+    assert_eq!(parsed[0].as_ref().unwrap().commit_type, "feat");
+    assert!(parsed[1].is_none());
+    assert_eq!(parsed[2].as_ref().unwrap().scope.as_deref(), Some("api"));
+}
+
+#[test]
+fn test_host_reuses_parser() {
+    let path = test_plugin_path();
+    if !path.exists() {
+        return;
+    }
+
+    let host = PluginHost::new(WasmEngine::new().expect("Failed to create engine"));
+    assert!(!host.is_loaded(&path));
+
+    let mut loads = 0;
+    for i in 0..100 {
+        let commit = unduler_wasm_runtime::parser::RawCommit {
+            hash: format!("{i:07}"),
+            message: format!("fix: bug {i}"),
+            author: "Test".to_string(),
+            email: "test@test.com".to_string(),
+            timestamp: 0,
+        };
+        let parser = host
+            .parser(&path, |parser| {
+                loads += 1;
+                Ok(parser)
+            })
+            .expect("Failed to load parser");
+        let parsed = parser.lock().unwrap().parse(&commit).expect("parse failed");
+        assert!(parsed.is_some());
+    }
+
+    assert!(host.is_loaded(&path));
+    assert_eq!(loads, 1, "the parser should be instantiated once");
+}
+
+#[test]
+fn test_hanging_info_names_the_plugin_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hanging.wasm");
+    write_hanging_parser(&path);
+
+    let limits = WasmLimits::default().with_timeout(Some(Duration::from_millis(50)));
+    let engine = WasmEngine::new()
+        .expect("Failed to create engine")
+        .with_limits(limits);
+    let Err(err) = WasmParser::from_file(&engine, &path) else {
+        panic!("loaded a parser whose info call hangs");
+    };
+    assert!(matches!(err, WasmError::Timeout { .. }));
+    assert_eq!(err.to_string(), "plugin 'hanging.wasm' timed out in 'info'");
+}
+
+#[test]
+fn test_commit_parser_uses_batch_interface() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("batch.wasm");
+    write_batch_parser(&path);
+
+    let engine = WasmEngine::new().expect("Failed to create engine");
+    let parser = WasmParser::from_file(&engine, &path).expect("Failed to load parser");
+    assert!(parser.has_parse_batch());
+    let parser = WasmCommitParser::new("batch", parser).expect("Failed to wrap parser");
+
+    let commits: Vec<_> = ["feat: add feature", "fix: bug"]
+        .iter()
+        .map(|message| RawCommit::new("abc123", *message, "Test", "test@test.com", Utc::now()))
+        .collect();
+    assert!(parser.parse(&commits[0]).is_none());
+
+    let parsed = parser.parse_batch(&commits);
+    assert_eq!(parsed.len(), 2);
+    assert!(parsed.iter().all(|commit| {
+        commit
+            .as_ref()
+            .is_some_and(|commit| commit.r#type == "batch")
+    }));
+}
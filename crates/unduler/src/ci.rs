@@ -0,0 +1,116 @@
+//! CI integration.
+
+use chrono::Utc;
+
+/// CI provider, used to group log output into collapsible sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions (`GITHUB_ACTIONS=true`).
+    GitHubActions,
+    /// GitLab CI (`GITLAB_CI` set).
+    GitLab,
+    /// Any other environment, without log grouping.
+    Other,
+}
+
+impl CiProvider {
+    /// Detects the CI provider from the environment.
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Detects the CI provider using the given environment lookup.
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        if var("GITHUB_ACTIONS").is_some_and(|value| value == "true") {
+            Self::GitHubActions
+        } else if var("GITLAB_CI").is_some() {
+            Self::GitLab
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Returns the line opening a log group, if supported.
+    #[must_use]
+    pub fn group_start(self, name: &str, title: &str) -> Option<String> {
+        match self {
+            Self::GitHubActions => Some(format!("::group::{title}")),
+            Self::GitLab => Some(format!(
+                "\x1b[0Ksection_start:{}:{name}\r\x1b[0K{title}",
+                Utc::now().timestamp()
+            )),
+            Self::Other => None,
+        }
+    }
+
+    /// Returns the line closing a log group, if supported.
+    #[must_use]
+    pub fn group_end(self, name: &str) -> Option<String> {
+        match self {
+            Self::GitHubActions => Some("::endgroup::".to_string()),
+            Self::GitLab => Some(format!(
+                "\x1b[0Ksection_end:{}:{name}\r\x1b[0K",
+                Utc::now().timestamp()
+            )),
+            Self::Other => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        move |name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(
+            CiProvider::from_env(env(&[("GITHUB_ACTIONS", "true")])),
+            CiProvider::GitHubActions
+        );
+        assert_eq!(
+            CiProvider::from_env(env(&[("GITLAB_CI", "true")])),
+            CiProvider::GitLab
+        );
+        assert_eq!(
+            CiProvider::from_env(env(&[("CI", "true")])),
+            CiProvider::Other
+        );
+    }
+
+    #[test]
+    fn test_github_groups() {
+        let provider = CiProvider::GitHubActions;
+        assert_eq!(
+            provider.group_start("tag", "Creating git tag").as_deref(),
+            Some("::group::Creating git tag")
+        );
+        assert_eq!(provider.group_end("tag").as_deref(), Some("::endgroup::"));
+    }
+
+    #[test]
+    fn test_gitlab_groups() {
+        let provider = CiProvider::GitLab;
+        let start = provider.group_start("tag", "Creating git tag").unwrap();
+        assert!(start.contains("section_start:"));
+        assert!(start.ends_with(":tag\r\x1b[0KCreating git tag"));
+
+        let end = provider.group_end("tag").unwrap();
+        assert!(end.contains("section_end:"));
+        assert!(end.ends_with(":tag\r\x1b[0K"));
+    }
+
+    #[test]
+    fn test_other_has_no_groups() {
+        assert!(CiProvider::Other.group_start("tag", "Tag").is_none());
+        assert!(CiProvider::Other.group_end("tag").is_none());
+    }
+}
@@ -1,18 +1,34 @@
 //! CLI definition.
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use clap::{ArgAction, Parser, Subcommand};
 
 use crate::commands;
+use crate::output::{Output, OutputArg};
 
 /// Automate version management and changelog generation for Git-based projects.
 #[derive(Debug, Parser)]
 #[command(name = "unduler")]
 #[command(version, about, long_about = None)]
 pub struct Cli {
-    /// Enable verbose output
+    /// Log more: `-v` for debug logs, `-vv` for trace logs
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only log warnings and errors
     #[arg(short, long, global = true)]
-    pub verbose: bool,
+    pub quiet: bool,
+
+    /// Also write logs to a file, at least at debug level
+    #[arg(long, global = true, value_name = "PATH", env = "UNDULER_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Output format for command results: `text` or `json`. Other values
+    /// are the changelog file of `changelog`, as with its `--file`
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "text")]
+    pub output: OutputArg,
 
     #[command(subcommand)]
     pub command: Commands,
@@ -29,22 +45,97 @@ pub enum Commands {
     /// Generate changelog
     Changelog(commands::changelog::ChangelogArgs),
 
+    /// Print the next version without changing anything
+    Next(commands::next::NextArgs),
+
+    /// Check that the version files match the latest tag
+    Check(commands::check::CheckArgs),
+
     /// Run a full release (bump + changelog + tag)
     Release(commands::release::ReleaseArgs),
 
     /// Manage plugins (install, remove, list, search)
     Plugin(commands::plugin::PluginArgs),
+
+    /// Inspect the configuration (schema)
+    Config(commands::config::ConfigArgs),
+
+    /// Inspect the gitmoji mappings of the parser
+    Gitmoji(commands::gitmoji::GitmojiArgs),
+
+    /// Generate CI pipelines running releases
+    Ci(commands::ci::CiArgs),
+
+    /// Update unduler to the latest release
+    SelfUpdate(commands::self_update::SelfUpdateArgs),
 }
 
 impl Cli {
+    /// Returns true if running in non-interactive CI mode.
+    #[must_use]
+    pub fn is_ci(&self) -> bool {
+        matches!(&self.command, Commands::Release(args) if args.ci)
+    }
+
+    /// Returns the output of the command, handing a changelog file given
+    /// with `--output` to the changelog command.
+    fn take_output(&mut self) -> Result<Output> {
+        let file = match &mut self.output {
+            OutputArg::Format(format) => return Ok(Output::new(*format)),
+            OutputArg::File(file) => std::mem::take(file),
+        };
+        match &mut self.command {
+            Commands::Changelog(args) if args.file.is_none() => {
+                args.file = Some(file);
+                Ok(Output::default())
+            }
+            Commands::Changelog(_) => bail!("`--output` and `--file` both set the changelog file"),
+            _ => bail!("invalid output format `{file}`: expected `text` or `json`"),
+        }
+    }
+
     /// Runs the CLI command.
-    pub fn run(self) -> Result<()> {
+    pub fn run(mut self) -> Result<()> {
+        let output = self.take_output()?;
         match self.command {
             Commands::Init(args) => commands::init::run(args),
-            Commands::Bump(args) => commands::bump::run(args),
-            Commands::Changelog(args) => commands::changelog::run(args),
-            Commands::Release(args) => commands::release::run(args),
+            Commands::Bump(args) => commands::bump::run(&args, output),
+            Commands::Changelog(args) => commands::changelog::run(args, output),
+            Commands::Next(args) => commands::next::run(&args, output),
+            Commands::Check(args) => commands::check::run(&args, output),
+            Commands::Release(args) => commands::release::run(&args, output),
             Commands::Plugin(args) => commands::plugin::run(args),
+            Commands::Config(args) => commands::config::run(args),
+            Commands::Gitmoji(args) => commands::gitmoji::run(args, output),
+            Commands::Ci(args) => commands::ci::run(args),
+            Commands::SelfUpdate(args) => commands::self_update::run(&args, output),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_changelog_output_file() {
+        let mut cli = Cli::parse_from(["unduler", "changelog", "--output", "NEWS.md"]);
+        assert!(!cli.take_output().unwrap().is_json());
+        assert!(matches!(
+            cli.command,
+            Commands::Changelog(args) if args.file.as_deref() == Some("NEWS.md")
+        ));
+
+        let mut cli = Cli::parse_from(["unduler", "changelog", "--output", "json"]);
+        assert!(cli.take_output().unwrap().is_json());
+
+        let mut cli = Cli::parse_from(["unduler", "bump", "--output", "NEWS.md"]);
+        assert!(cli.take_output().is_err());
+    }
+}
@@ -1,7 +1,6 @@
 //! Changelog command.
 
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
@@ -9,244 +8,309 @@ use clap::Args;
 use semver::Version;
 use tracing::info;
 
-use unduler_bumper_semver::SemverBumper;
-use unduler_commit::{ParsedCommit, RawCommit};
-use unduler_config::{Config, find_and_load_config};
-use unduler_formatter_keepachangelog::KeepAChangelogFormatter;
-use unduler_git::Repository;
-use unduler_parser_conventional::ConventionalParser;
-use unduler_parser_gitmoji::{ConventionalGitmojiParser, GitmojiParserConfig};
-use unduler_parser_regex::{FieldMapping, RegexParser, RegexParserConfig};
-use unduler_plugin::{
-    BumpStrategy, BumpType, ChangelogFormatter, CommitParser, FormatterConfig, Release,
+use unduler_commit::ParsedCommit;
+use unduler_config::ChangelogConfig;
+use unduler_core::{
+    Pipeline, ReleaseManager, changelog_header, formatter_config, output_changes,
+    unreleased_section, update_unreleased_changelog,
 };
+use unduler_git::{Repository, TagFormat};
+use unduler_plugin::{ChangelogFormatter, FormatterConfig, Release};
+
+use crate::github;
+use crate::output::{Output, Summary};
 
 /// Arguments for the changelog command.
 #[derive(Debug, Args)]
 pub struct ChangelogArgs {
-    /// Output file (default: from config or CHANGELOG.md)
-    #[arg(short, long)]
-    pub output: Option<String>,
+    /// Changelog file (default: from config or CHANGELOG.md), also set by
+    /// `-o` or `--output <FILE>`
+    #[arg(short, long, short_alias = 'o')]
+    pub file: Option<String>,
 
-    /// Generate changelog for unreleased changes only
+    /// Render pending changes under an Unreleased section
     #[arg(short, long)]
     pub unreleased: bool,
 
+    /// Start of the commit range, exclusive (default: latest version tag)
+    #[arg(long, value_name = "REV")]
+    pub from: Option<String>,
+
+    /// End of the commit range: tag, hash or HEAD (default: HEAD)
+    #[arg(long, value_name = "REV")]
+    pub to: Option<String>,
+
     /// Print to stdout instead of writing to file
     #[arg(long)]
     pub dry_run: bool,
-}
-
-/// Creates the appropriate parser based on configuration.
-fn create_parser(config: &Config) -> Box<dyn CommitParser> {
-    match config.parser.name.as_str() {
-        "gitmoji" | "conventional-gitmoji" => create_gitmoji_parser(config),
-        "regex" => create_regex_parser(config),
-        _ => Box::new(ConventionalParser::new()),
-    }
-}
 
-fn create_gitmoji_parser(config: &Config) -> Box<dyn CommitParser> {
-    let parser_config = GitmojiParserConfig {
-        infer_type_from_emoji: config.parser.conventional_gitmoji.infer_type_from_emoji,
-        strict_emoji: config.parser.conventional_gitmoji.strict_emoji,
-    };
-    Box::new(ConventionalGitmojiParser::with_config(parser_config))
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
 }
 
-fn create_regex_parser(config: &Config) -> Box<dyn CommitParser> {
-    let Some(ref pattern) = config.parser.regex.pattern else {
-        info!("no regex pattern configured, falling back to conventional");
-        return Box::new(ConventionalParser::new());
-    };
-
-    // Build field mapping from config
-    let mut metadata_mapping = std::collections::HashMap::new();
-    for (field, capture) in &config.parser.regex.mapping {
-        if !["type", "scope", "message"].contains(&field.as_str()) {
-            metadata_mapping.insert(field.clone(), capture.clone());
+/// Returns the version documented by the commits of a range ending at `to`.
+fn release_version(
+    manager: &ReleaseManager,
+    pipeline: &Pipeline,
+    to: Option<&str>,
+    previous_version: Option<&Version>,
+    parsed_commits: &[ParsedCommit],
+) -> Version {
+    // A range ending at a version tag documents that version
+    match to.and_then(|to| manager.tag_format().parse(to)) {
+        Some(tagged_version) => tagged_version,
+        None => {
+            manager
+                .next_version(pipeline, previous_version, parsed_commits)
+                .1
         }
     }
+}
 
-    let mapping = FieldMapping {
-        r#type: config
-            .parser
-            .regex
-            .mapping
-            .get("type")
-            .cloned()
-            .unwrap_or_else(|| "type".to_string()),
-        scope: config.parser.regex.mapping.get("scope").cloned(),
-        message: config
-            .parser
-            .regex
-            .mapping
-            .get("message")
-            .cloned()
-            .unwrap_or_else(|| "message".to_string()),
-        metadata: metadata_mapping,
-    };
-
-    let parser_config = RegexParserConfig {
-        pattern: pattern.clone(),
-        mapping,
-        validation: config.parser.regex.validation.clone(),
-    };
+/// Returns the header of a new changelog, from `changelog.header_file` or
+/// `changelog.header`.
+fn header(config: &ChangelogConfig) -> Result<String> {
+    let header_file = config.header_file.as_deref().map(Path::new);
+    changelog_header(config.header.as_deref(), header_file)
+        .context("failed to read changelog header")
+}
 
-    match RegexParser::new(parser_config) {
-        Ok(parser) => Box::new(parser),
-        Err(e) => {
-            info!("invalid regex pattern, falling back to conventional: {e}");
-            Box::new(ConventionalParser::new())
-        }
+/// Writes the changelog to a file, merging with existing content, to the
+/// release files and to the `changelog.inserts` files.
+///
+/// Without a changelog file, i.e. with `changelog.output = "none"`, the
+/// changelog is printed unless written to a release file.
+///
+/// Returns the path of the written changelog.
+fn write_changelog(
+    manager: &ReleaseManager,
+    changelog: &str,
+    version: &Version,
+    output: Output,
+) -> Result<Option<PathBuf>> {
+    let written = manager
+        .write_release_section(changelog, version)
+        .context("failed to write changelog")?;
+
+    match &written {
+        Some(path) => output.text(format!(
+            "Changelog updated for version {version}: {}",
+            path.display()
+        )),
+        None => output.text(changelog),
     }
-}
 
-/// Parses raw commits using the given parser.
-fn parse_commits(parser: &dyn CommitParser, raw_commits: &[RawCommit]) -> Vec<ParsedCommit> {
-    raw_commits
-        .iter()
-        .filter_map(|raw| {
-            let parsed = parser.parse(raw);
-            if parsed.is_none() {
-                info!(
-                    hash = %raw.short_hash(),
-                    subject = %raw.subject(),
-                    "skipping unparseable commit"
-                );
-            }
-            parsed
-        })
-        .collect()
+    Ok(written)
 }
 
-/// Determines the next version based on commits and current version.
-fn determine_next_version(current_version: &Version, parsed_commits: &[ParsedCommit]) -> Version {
-    let bumper = SemverBumper::new();
-    let bump_type = bumper.determine(parsed_commits);
-
-    match bump_type {
-        BumpType::Major => Version::new(current_version.major + 1, 0, 0),
-        BumpType::Minor => Version::new(current_version.major, current_version.minor + 1, 0),
-        BumpType::Patch | BumpType::None => Version::new(
-            current_version.major,
-            current_version.minor,
-            current_version.patch + 1,
-        ),
+/// Renders the pending changes since `from` as an `Unreleased` section.
+fn render_unreleased(
+    formatter: &dyn ChangelogFormatter,
+    formatter_config: &FormatterConfig,
+    commits: Vec<ParsedCommit>,
+    repository_url: Option<&str>,
+    from: Option<&str>,
+) -> String {
+    let mut release = Release::new(Version::new(0, 0, 0), Utc::now(), commits);
+    if let Some(url) = repository_url {
+        release = release.with_repository_url(url);
     }
+    let rendered = formatter.format(&release, formatter_config);
+
+    let compare_url = from
+        .zip(repository_url)
+        .map(|(tag, url)| format!("{url}/compare/{tag}...HEAD"));
+    unreleased_section(&rendered, compare_url.as_deref())
 }
 
-/// Writes the changelog to a file, merging with existing content.
-fn write_changelog(
+/// Writes an `Unreleased` section to the changelog file.
+fn write_unreleased(
     changelog: &str,
-    output_path: &PathBuf,
-    version: &Version,
-    unreleased: bool,
+    output_path: &Path,
+    config: &ChangelogConfig,
+    output: Output,
 ) -> Result<()> {
-    let existing = fs::read_to_string(output_path).unwrap_or_default();
-
-    let new_content = if existing.is_empty() {
-        format!(
-            "# Changelog\n\n\
-             All notable changes to this project will be documented in this file.\n\n\
-             The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
-             and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n\
-             {changelog}"
-        )
-    } else if let Some(pos) = existing.find("\n## ") {
-        let (header, rest) = existing.split_at(pos + 1);
-        format!("{header}{changelog}{rest}")
-    } else {
-        format!("{existing}\n{changelog}")
-    };
-
-    fs::write(output_path, new_content)
+    update_unreleased_changelog(output_path, changelog, &header(config)?)
         .with_context(|| format!("failed to write changelog to {}", output_path.display()))?;
+    output.text(format!(
+        "Changelog updated with unreleased changes: {}",
+        output_path.display()
+    ));
+    Ok(())
+}
 
-    if unreleased {
-        println!(
-            "Changelog updated with unreleased changes: {}",
-            output_path.display()
-        );
-    } else {
-        println!(
-            "Changelog updated for version {version}: {}",
-            output_path.display()
-        );
+/// Renders the release to the `changelog.outputs` files, printing their
+/// diffs instead of writing them in a dry run.
+fn write_outputs(
+    pipeline: &Pipeline,
+    config: &ChangelogConfig,
+    release: &Release,
+    formatter_config: &FormatterConfig,
+    dry_run: bool,
+    output: Output,
+) -> Result<()> {
+    let header = header(config)?;
+    let changes = output_changes(pipeline.outputs(), release, formatter_config, &header)
+        .context("failed to render changelog outputs")?;
+    for change in changes.iter().filter(|change| change.is_changed()) {
+        if dry_run {
+            super::print_diff(output, change);
+        } else {
+            change.write().with_context(|| {
+                format!("failed to write changelog to {}", change.path.display())
+            })?;
+            output.text(format!(
+                "Changelog output written: {}",
+                change.path.display()
+            ));
+        }
     }
-
     Ok(())
 }
 
+/// Returns the start of the commit range, with the version preceding it:
+/// the explicit start, or the latest version tag before the end of the
+/// range.
+fn range_start(
+    repo: &Repository,
+    tag_format: &TagFormat,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<(Option<String>, Option<Version>)> {
+    let latest = match to {
+        Some(to) => repo.latest_tag_before(tag_format, to),
+        None => repo.latest_tag_matching(tag_format),
+    }
+    .context("failed to get latest version tag")?;
+    let (latest_tag, latest_version) = latest.unzip();
+
+    // An explicit range start replaces the latest tag, which still gives the
+    // previous version when the start is not a version tag
+    if let Some(from) = from {
+        let previous_version = tag_format.parse(from).or(latest_version);
+        return Ok((Some(from.to_string()), previous_version));
+    }
+
+    info!(tag = ?latest_tag, "found latest version tag");
+    Ok((latest_tag, latest_version))
+}
+
 /// Runs the changelog command.
-pub fn run(args: ChangelogArgs) -> Result<()> {
-    let config = find_and_load_config().context("failed to load configuration")?;
+pub fn run(args: ChangelogArgs, output: Output) -> Result<()> {
+    let config = super::load_config(args.profile.as_deref())?;
     let repo = Repository::discover().context("failed to open git repository")?;
-    let tag_prefix = &config.version.tag_prefix;
+    // Dry runs leave the commit cache untouched
+    let mut manager = ReleaseManager::from_config(repo, &config).with_read_only_cache(args.dry_run);
+    if let Some(file) = &args.file {
+        manager = manager.with_changelog_path(file).with_changelog_file(true);
+    }
+    let repo = manager.repository();
+    let tag_format = manager.tag_format();
 
-    let latest_tag = repo
-        .latest_version_tag(tag_prefix)
-        .context("failed to get latest version tag")?;
+    let (from, previous_version) =
+        range_start(repo, tag_format, args.from.as_deref(), args.to.as_deref())?;
+    let to = args.to.as_deref().unwrap_or("HEAD");
 
-    info!(tag = ?latest_tag, "found latest version tag");
+    let pipeline = super::create_pipeline(&config)?;
+    info!(from = ?from, to, "parsing commits");
 
-    let raw_commits = repo
-        .commits_since(latest_tag.as_deref())
+    let (raw_count, mut parsed_commits) = manager
+        .parse_commits_between(&pipeline, from.as_deref(), to)
         .context("failed to get commits")?;
 
-    if raw_commits.is_empty() {
-        println!("No commits found since last release");
-        return Ok(());
-    }
-
-    info!(count = raw_commits.len(), "found commits to process");
+    let mut summary = Summary {
+        previous_version: previous_version.as_ref().map(ToString::to_string),
+        dry_run: args.dry_run,
+        ..Summary::default()
+    };
 
-    let parser = create_parser(&config);
-    info!(parser = parser.name(), "using parser");
+    if raw_count == 0 {
+        if args.from.is_some() || args.to.is_some() {
+            output.text("No commits found in range");
+        } else {
+            output.text("No commits found since last release");
+        }
+        return output.summary(&summary);
+    }
 
-    let parsed_commits = parse_commits(parser.as_ref(), &raw_commits);
+    info!(count = raw_count, "found commits to process");
 
     if parsed_commits.is_empty() {
-        println!("No parseable commits found");
-        return Ok(());
+        output.text("No parseable commits found");
+        return output.summary(&summary);
     }
 
     info!(count = parsed_commits.len(), "parsed commits");
 
-    let version = if args.unreleased {
-        Version::new(0, 0, 0)
-    } else if let Some(current_version) = latest_tag
-        .as_ref()
-        .and_then(|tag| tag.strip_prefix(tag_prefix))
-        .and_then(|v| Version::parse(v).ok())
-    {
-        // Tag exists: bump based on commits
-        determine_next_version(&current_version, &parsed_commits)
-    } else {
-        // No tag: first release is 0.1.0 (standard SemVer convention)
-        Version::new(0, 1, 0)
-    };
+    let formatter = pipeline.formatter();
+    let formatter_config = formatter_config(&config);
+    let repository_url = manager.repository_url().map(String::from);
+    github::resolve_author_logins(
+        &config,
+        repo,
+        repository_url.as_deref(),
+        &mut parsed_commits,
+    );
+    let output_path = args
+        .file
+        .map(PathBuf::from)
+        .or_else(|| config.changelog.output_file().map(PathBuf::from));
+
+    if args.unreleased {
+        let changelog = render_unreleased(
+            formatter,
+            &formatter_config,
+            parsed_commits,
+            repository_url.as_deref(),
+            from.as_deref(),
+        );
+
+        if let Some(output_path) = output_path.filter(|_| !args.dry_run) {
+            write_unreleased(&changelog, &output_path, &config.changelog, output)?;
+            summary.changelog = Some(output_path.display().to_string());
+        } else {
+            output.text(changelog);
+        }
+
+        return output.summary(&summary);
+    }
+
+    let version = release_version(
+        &manager,
+        &pipeline,
+        args.to.as_deref(),
+        previous_version.as_ref(),
+        &parsed_commits,
+    );
 
     let mut release = Release::new(version.clone(), Utc::now(), parsed_commits);
 
-    if let Some(ref tag) = latest_tag
-        && let Some(prev_version) = tag.strip_prefix(tag_prefix)
-        && let Ok(v) = Version::parse(prev_version)
-    {
-        release = release.with_previous_version(v);
+    if let Some(previous_version) = previous_version {
+        release = release.with_previous_version(previous_version);
+    }
+    if let Some(url) = &repository_url {
+        release = release.with_repository_url(url);
     }
 
-    let formatter = KeepAChangelogFormatter::new();
-    let changelog = formatter.format(&release, &FormatterConfig::default());
+    let changelog = formatter.format(&release, &formatter_config);
+
+    summary.new_version = Some(version.to_string());
 
     if args.dry_run {
-        println!("{changelog}");
+        output.text(changelog);
     } else {
-        let output_path = args
-            .output
-            .map_or_else(|| PathBuf::from(&config.changelog.output), PathBuf::from);
-        write_changelog(&changelog, &output_path, &version, args.unreleased)?;
+        let written = write_changelog(&manager, &changelog, &version, output)?;
+        summary.changelog = written.map(|path| path.display().to_string());
     }
-
-    Ok(())
+    write_outputs(
+        &pipeline,
+        &config.changelog,
+        &release,
+        &formatter_config,
+        args.dry_run,
+        output,
+    )?;
+
+    output.summary(&summary)
 }
@@ -0,0 +1,110 @@
+//! Check command.
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use unduler_core::ReleaseManager;
+use unduler_git::Repository;
+
+use crate::diagnostic::Diagnostic;
+use crate::output::{Output, Summary};
+
+/// Arguments for the check command.
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Rewrite the diverging version files to match the latest tag
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
+}
+
+/// Runs the check command.
+///
+/// Compares the version of the latest tag with the versions of the
+/// configured version files, and fails when they diverge.
+pub fn run(args: &CheckArgs, output: Output) -> Result<()> {
+    let config = super::load_config(args.profile.as_deref())?;
+    let repo = Repository::discover().context("failed to open git repository")?;
+    let manager = ReleaseManager::from_config(repo, &config);
+
+    let Some((tag, tag_version)) = manager.latest_tag()? else {
+        return Err(Diagnostic::new("no release tag found")
+            .with_help("versions are checked against the latest tag, run `unduler release` first")
+            .into());
+    };
+
+    output.text(format!("Latest tag: {tag} ({tag_version})\n"));
+
+    if config.version.files.is_empty() && config.version.custom_files.is_empty() {
+        output.text("No version files configured");
+        return Ok(());
+    }
+
+    let mut diverging = Vec::new();
+    let mut error_count = 0;
+
+    for file in manager.read_version_files() {
+        match file.result {
+            Ok(version) if version == tag_version => {
+                output.text(format!("  ok        {}: {version}", file.path));
+            }
+            Ok(version) => {
+                output.text(format!("  diverges  {}: {version}", file.path));
+                diverging.push(file.path);
+            }
+            Err(e) => {
+                eprintln!("Failed to read {}: {e}", file.path);
+                error_count += 1;
+            }
+        }
+    }
+
+    let mut summary = Summary {
+        previous_version: Some(tag_version.to_string()),
+        ..Summary::default()
+    };
+
+    if args.fix && !diverging.is_empty() {
+        let updates = manager.update_listed_version_files(&diverging, &tag_version, false);
+        let failed = updates.iter().filter(|u| u.result.is_err()).count();
+        for update in updates {
+            match update.result {
+                Ok(_) if failed == 0 => {
+                    output.text(format!("Updated {} to version {tag_version}", update.path));
+                    summary.files_updated.push(update.path);
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to update {}: {e}", update.path),
+            }
+        }
+        error_count += failed;
+        diverging.clear();
+    }
+
+    output.summary(&summary)?;
+
+    if !diverging.is_empty() {
+        return Err(Diagnostic::new(format!(
+            "{} version file(s) diverge from {tag}",
+            diverging.len()
+        ))
+        .with_help("run `unduler check --fix` to update them to the tagged version")
+        .into());
+    }
+    if error_count > 0 {
+        anyhow::bail!("{error_count} version file(s) could not be checked");
+    }
+
+    if summary.files_updated.is_empty() {
+        output.text(format!("\nVersion files match {tag}"));
+    } else {
+        output.text(format!(
+            "\nUpdated {} file(s) to match {tag}",
+            summary.files_updated.len()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,592 @@
+//! CI commands.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+
+use unduler_config::{Config, MilestoneForge};
+use unduler_plugin_manager::{LOCKFILE_NAME, PluginStorage};
+
+use crate::diagnostic::Diagnostic;
+
+/// URL of the latest Linux binary of unduler, statically linked so that it
+/// runs in any image.
+const UNDULER_URL: &str = "https://github.com/jdevelop-io/unduler/releases/latest/download/unduler-x86_64-unknown-linux-musl";
+
+/// Default npm registry, authenticated with `NODE_AUTH_TOKEN`.
+const NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// CI commands.
+#[derive(Debug, Args)]
+pub struct CiArgs {
+    #[command(subcommand)]
+    pub command: CiCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CiCommand {
+    /// Generate a CI pipeline releasing on pushes to the release branches
+    Init(CiInitArgs),
+}
+
+/// CI provider of a generated pipeline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    /// GitHub Actions workflow
+    #[default]
+    Github,
+    /// GitLab CI pipeline
+    Gitlab,
+}
+
+impl CiProvider {
+    /// Returns the path the pipeline is written to by default.
+    fn default_path(self) -> &'static str {
+        match self {
+            Self::Github => ".github/workflows/release.yml",
+            Self::Gitlab => ".gitlab-ci.yml",
+        }
+    }
+}
+
+/// Arguments for the `ci init` command.
+#[derive(Debug, Args)]
+pub struct CiInitArgs {
+    /// CI provider of the pipeline
+    #[arg(long, value_enum, default_value_t)]
+    pub provider: CiProvider,
+
+    /// Branches releasing on push (default: `version.release_branches`, or
+    /// main)
+    #[arg(short, long = "branch")]
+    pub branches: Vec<String>,
+
+    /// Write the pipeline to this file instead of the provider default
+    #[arg(short = 'o', long = "out")]
+    pub out: Option<PathBuf>,
+
+    /// Force overwrite an existing pipeline
+    #[arg(short, long)]
+    pub force: bool,
+
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
+}
+
+/// What the release job needs, derived from the configuration.
+#[derive(Debug, Default)]
+struct Requirements {
+    /// Environment variables of the release, keyed by name, with the name of
+    /// the CI secret holding them.
+    secrets: BTreeMap<String, String>,
+    /// Registry of the npm hook, when publishing to npm.
+    npm_registry: Option<String>,
+    /// Whether the cargo hook publishes crates.
+    cargo: bool,
+    /// Whether the milestone hook manages GitHub milestones.
+    github_issues: bool,
+    /// Whether the OCI hook pushes to the GitHub Container Registry.
+    github_packages: bool,
+    /// Whether the git identity of release commits is configured.
+    git_user: bool,
+}
+
+impl Requirements {
+    /// Collects the requirements of the release from the configuration.
+    fn from_config(config: &Config, provider: CiProvider) -> Self {
+        let plugins = &config.plugins;
+        let mut requirements = Self {
+            git_user: config.version.commit_author.is_some(),
+            ..Self::default()
+        };
+
+        match provider {
+            CiProvider::Github => requirements.secret("GITHUB_TOKEN", "GITHUB_TOKEN"),
+            // The job token cannot push, the release is pushed with a project
+            // access token
+            CiProvider::Gitlab => requirements.secret("GITLAB_TOKEN", "GITLAB_TOKEN"),
+        }
+        if plugins.cargo.publish {
+            requirements.cargo = true;
+            requirements.secret("CARGO_REGISTRY_TOKEN", "CARGO_REGISTRY_TOKEN");
+        }
+        if plugins.npm.publish {
+            requirements.npm_registry = Some(
+                plugins
+                    .npm
+                    .registry
+                    .clone()
+                    .unwrap_or_else(|| NPM_REGISTRY.to_string()),
+            );
+            requirements.secret("NODE_AUTH_TOKEN", "NPM_TOKEN");
+        }
+        if plugins.jira.base_url.is_some() {
+            requirements.secret(&plugins.jira.token_env, &plugins.jira.token_env);
+        }
+        if plugins.sentry.org.is_some() {
+            requirements.secret(&plugins.sentry.token_env, &plugins.sentry.token_env);
+        }
+        if plugins.milestone.repository.is_some() {
+            let token_env =
+                plugins
+                    .milestone
+                    .token_env
+                    .as_deref()
+                    .unwrap_or(match plugins.milestone.forge {
+                        MilestoneForge::GitHub => "GITHUB_TOKEN",
+                        MilestoneForge::GitLab => "GITLAB_TOKEN",
+                    });
+            requirements.secret(token_env, token_env);
+            requirements.github_issues = plugins.milestone.forge == MilestoneForge::GitHub;
+        }
+        requirements.github_packages = plugins
+            .oci
+            .repository
+            .as_deref()
+            .is_some_and(|repository| repository.starts_with("ghcr.io/"));
+        for channel in &plugins.notify.channels {
+            if let Some(env) = &channel.webhook_url_env {
+                requirements.secret(env, env);
+            }
+        }
+        for target in &plugins.webhook.targets {
+            for env in target.secret_headers.values() {
+                requirements.secret(env, env);
+            }
+        }
+
+        requirements
+    }
+
+    fn secret(&mut self, env: &str, secret: &str) {
+        self.secrets.insert(env.to_string(), secret.to_string());
+    }
+
+    /// Returns the names of the CI secrets to configure, sorted.
+    fn secret_names(&self, provider: CiProvider) -> Vec<&str> {
+        let mut names: Vec<_> = self
+            .secrets
+            .values()
+            .map(String::as_str)
+            // GitHub provides its token to every workflow
+            .filter(|name| provider != CiProvider::Github || *name != "GITHUB_TOKEN")
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+/// Release job of a generated pipeline.
+#[derive(Debug)]
+struct ReleaseJob<'a> {
+    /// Branches releasing on push.
+    branches: &'a [String],
+    /// Configuration profile of the release.
+    profile: Option<&'a str>,
+    /// Whether the project has an unduler.lock.
+    locked: bool,
+    /// Whether the project installs its plugins in the project.
+    local_plugins: bool,
+}
+
+impl ReleaseJob<'_> {
+    /// Returns the command running the release, succeeding when there is
+    /// nothing to release.
+    fn release_command(&self) -> String {
+        let mut command = "unduler release --ci --push".to_string();
+        if self.locked {
+            command.push_str(" --locked");
+        }
+        if let Some(profile) = self.profile {
+            let _ = write!(command, " --profile {profile}");
+        }
+        command.push_str(" || [ $? -eq 2 ]");
+        command
+    }
+
+    /// Returns the command installing the locked plugins.
+    fn sync_command(&self) -> &'static str {
+        if self.local_plugins {
+            "unduler plugin sync --local"
+        } else {
+            "unduler plugin sync"
+        }
+    }
+}
+
+/// Generates a GitHub Actions workflow.
+fn github_workflow(job: &ReleaseJob<'_>, requirements: &Requirements) -> String {
+    let mut workflow = String::new();
+
+    workflow.push_str("# Generated by `unduler ci init`.\n");
+    let secrets = requirements.secret_names(CiProvider::Github);
+    if !secrets.is_empty() {
+        workflow.push_str("#\n# Repository secrets used by the release:\n");
+        for secret in secrets {
+            let _ = writeln!(workflow, "#   {secret}");
+        }
+    }
+
+    workflow.push_str("\nname: Release\n\non:\n  push:\n    branches:\n");
+    for branch in job.branches {
+        let _ = writeln!(workflow, "      - '{branch}'");
+    }
+
+    workflow.push_str("\npermissions:\n  contents: write\n");
+    if requirements.github_issues {
+        workflow.push_str("  issues: write\n");
+    }
+    if requirements.github_packages {
+        workflow.push_str("  packages: write\n");
+    }
+
+    workflow.push_str(
+        "\nconcurrency:\n  group: release\n  cancel-in-progress: false\n\n\
+         jobs:\n  release:\n    name: Release\n    runs-on: ubuntu-latest\n    steps:\n",
+    );
+
+    // Releases are computed from the whole history and tags
+    workflow
+        .push_str("      - uses: actions/checkout@v6\n        with:\n          fetch-depth: 0\n");
+
+    if !requirements.git_user {
+        workflow.push_str(
+            "\n      - name: Configure git\n        run: |\n          \
+             git config user.name \"github-actions[bot]\"\n          \
+             git config user.email \"41898282+github-actions[bot]@users.noreply.github.com\"\n",
+        );
+    }
+
+    if let Some(registry) = &requirements.npm_registry {
+        let _ = write!(
+            workflow,
+            "\n      - name: Set up Node.js\n        uses: actions/setup-node@v4\n        with:\n          \
+             node-version: lts/*\n          registry-url: {registry}\n"
+        );
+    }
+
+    let key = if job.locked {
+        format!("unduler-${{{{ runner.os }}}}-${{{{ hashFiles('{LOCKFILE_NAME}') }}}}")
+    } else {
+        "unduler-${{ runner.os }}".to_string()
+    };
+    let _ = write!(
+        workflow,
+        "\n      - name: Cache unduler plugins\n        uses: actions/cache@v4\n        with:\n          \
+         path: ~/.unduler\n          key: {key}\n"
+    );
+
+    let _ = write!(
+        workflow,
+        "\n      - name: Install unduler\n        run: |\n          \
+         mkdir -p ~/.local/bin\n          \
+         curl -sSfL {UNDULER_URL} -o ~/.local/bin/unduler\n          \
+         chmod +x ~/.local/bin/unduler\n          \
+         echo ~/.local/bin >> \"$GITHUB_PATH\"\n"
+    );
+
+    if job.locked {
+        let _ = write!(
+            workflow,
+            "\n      - name: Install plugins\n        run: {}\n",
+            job.sync_command()
+        );
+    }
+
+    let _ = write!(
+        workflow,
+        "\n      - name: Release\n        run: {}\n        env:\n",
+        job.release_command()
+    );
+    for (env, secret) in &requirements.secrets {
+        let _ = writeln!(workflow, "          {env}: ${{{{ secrets.{secret} }}}}");
+    }
+
+    workflow
+}
+
+/// Generates a GitLab CI pipeline.
+fn gitlab_pipeline(job: &ReleaseJob<'_>, requirements: &Requirements) -> String {
+    let mut gitlab = String::new();
+
+    gitlab.push_str(
+        "# Generated by `unduler ci init`.\n#\n\
+         # CI/CD variables used by the release, GITLAB_TOKEN being a project access\n\
+         # token with the write_repository scope:\n",
+    );
+    for secret in requirements.secret_names(CiProvider::Gitlab) {
+        let _ = writeln!(gitlab, "#   {secret}");
+    }
+
+    let image = if requirements.cargo {
+        "rust:latest"
+    } else if requirements.npm_registry.is_some() {
+        "node:lts"
+    } else {
+        "buildpack-deps:stable-scm"
+    };
+    let _ = write!(
+        gitlab,
+        "\nrelease:\n  stage: deploy\n  image: {image}\n  resource_group: release\n  rules:\n"
+    );
+    for branch in job.branches {
+        let _ = writeln!(gitlab, "    - if: {}", branch_rule(branch));
+    }
+
+    // Releases are computed from the whole history and tags
+    gitlab.push_str("  variables:\n    GIT_DEPTH: \"0\"\n");
+    for (env, secret) in &requirements.secrets {
+        if env != secret {
+            let _ = writeln!(gitlab, "    {env}: ${secret}");
+        }
+    }
+
+    // Caches must be in the project directory, ~/.unduler links to it
+    gitlab.push_str("  cache:\n    key:");
+    if job.locked {
+        let _ = write!(gitlab, "\n      files:\n        - {LOCKFILE_NAME}\n");
+    } else {
+        gitlab.push_str(" unduler\n");
+    }
+    gitlab.push_str("    paths:\n      - .unduler-cache/\n");
+
+    let _ = write!(
+        gitlab,
+        "  before_script:\n    \
+         - echo .unduler-cache >> .git/info/exclude\n    \
+         - mkdir -p .unduler-cache && ln -sfn \"$CI_PROJECT_DIR/.unduler-cache\" ~/.unduler\n    \
+         - curl -sSfL {UNDULER_URL} -o /usr/local/bin/unduler\n    \
+         - chmod +x /usr/local/bin/unduler\n"
+    );
+    if !requirements.git_user {
+        gitlab.push_str(
+            "    - git config user.name \"$GITLAB_USER_NAME\"\n    \
+             - git config user.email \"$GITLAB_USER_EMAIL\"\n",
+        );
+    }
+    // Jobs check out a detached HEAD and cannot push with the job token
+    gitlab.push_str(
+        "    - git checkout -B \"$CI_COMMIT_BRANCH\"\n    \
+         - git remote set-url origin \"https://oauth2:${GITLAB_TOKEN}@${CI_SERVER_HOST}/${CI_PROJECT_PATH}.git\"\n",
+    );
+    if let Some(registry) = &requirements.npm_registry {
+        let host = registry
+            .split_once("://")
+            .map_or(registry.as_str(), |(_, host)| host)
+            .trim_end_matches('/');
+        let _ = writeln!(
+            gitlab,
+            "    - echo \"//{host}/:_authToken=${{NODE_AUTH_TOKEN}}\" > ~/.npmrc"
+        );
+    }
+    if job.locked {
+        let _ = writeln!(gitlab, "    - {}", job.sync_command());
+    }
+
+    let _ = writeln!(gitlab, "  script:\n    - {}", job.release_command());
+
+    gitlab
+}
+
+/// Returns the GitLab rule matching a branch pattern, converting `*` and
+/// `**` wildcards to a regex.
+fn branch_rule(pattern: &str) -> String {
+    if !pattern.contains('*') {
+        return format!("$CI_COMMIT_BRANCH == \"{pattern}\"");
+    }
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^\\/]*"),
+            '/' | '\\' | '.' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            c => regex.push(c),
+        }
+    }
+    format!("$CI_COMMIT_BRANCH =~ /{regex}$/")
+}
+
+/// Runs the ci command.
+pub fn run(args: CiArgs) -> Result<()> {
+    match args.command {
+        CiCommand::Init(args) => init(&args),
+    }
+}
+
+fn init(args: &CiInitArgs) -> Result<()> {
+    let path = args
+        .out
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(args.provider.default_path()));
+    if path.exists() && !args.force {
+        return Err(
+            Diagnostic::new(format!("{} already exists", path.display()))
+                .with_help("use --force to overwrite it")
+                .into(),
+        );
+    }
+
+    let config = super::load_config(args.profile.as_deref())?;
+    let project_dir = super::project_dir()?;
+    let mut branches = args.branches.clone();
+    if branches.is_empty() {
+        branches.clone_from(&config.version.release_branches);
+    }
+    if branches.is_empty() {
+        branches.push("main".to_string());
+    }
+    let job = ReleaseJob {
+        branches: &branches,
+        profile: args.profile.as_deref(),
+        locked: super::lockfile_path()?.exists(),
+        local_plugins: PluginStorage::has_local(&project_dir),
+    };
+    let requirements = Requirements::from_config(&config, args.provider);
+
+    let content = match args.provider {
+        CiProvider::Github => github_workflow(&job, &requirements),
+        CiProvider::Gitlab => gitlab_pipeline(&job, &requirements),
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, content).with_context(|| format!("failed to write {}", path.display()))?;
+    println!("Created {}", path.display());
+
+    print_secrets(&path, args.provider, &requirements);
+    Ok(())
+}
+
+/// Lists the secrets to configure for the release.
+fn print_secrets(path: &Path, provider: CiProvider, requirements: &Requirements) {
+    let secrets = requirements.secret_names(provider);
+    if secrets.is_empty() {
+        return;
+    }
+    let settings = match provider {
+        CiProvider::Github => "repository secrets",
+        CiProvider::Gitlab => "CI/CD variables",
+    };
+    println!("\nAdd these {settings} before running {}:", path.display());
+    for secret in secrets {
+        println!("  {secret}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_config::{NotifyChannelConfig, NotifyChannelKind};
+
+    use super::*;
+
+    fn job(branches: &[String]) -> ReleaseJob<'_> {
+        ReleaseJob {
+            branches,
+            profile: None,
+            locked: false,
+            local_plugins: false,
+        }
+    }
+
+    #[test]
+    fn test_github_workflow() {
+        let branches = vec!["main".to_string()];
+        let config = Config::default();
+        let workflow = github_workflow(
+            &job(&branches),
+            &Requirements::from_config(&config, CiProvider::Github),
+        );
+
+        assert!(workflow.contains("    branches:\n      - 'main'\n"));
+        assert!(workflow.contains("permissions:\n  contents: write\n\n"));
+        assert!(workflow.contains("fetch-depth: 0"));
+        assert!(workflow.contains("path: ~/.unduler\n          key: unduler-${{ runner.os }}\n"));
+        assert!(workflow.contains("run: unduler release --ci --push || [ $? -eq 2 ]\n"));
+        assert!(workflow.contains("GITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}\n"));
+        assert!(workflow.contains("git config user.name"));
+        assert!(!workflow.contains("setup-node"));
+        assert!(!workflow.contains("plugin sync"));
+    }
+
+    #[test]
+    fn test_github_workflow_hooks() {
+        let branches = vec!["main".to_string()];
+        let mut config = Config::default();
+        config.plugins.npm.publish = true;
+        config.plugins.milestone.repository = Some("acme/app".to_string());
+        config.plugins.notify.channels.push(NotifyChannelConfig {
+            kind: NotifyChannelKind::Slack,
+            webhook_url: None,
+            webhook_url_env: Some("SLACK_WEBHOOK_URL".to_string()),
+            template: None,
+        });
+        let locked_job = ReleaseJob {
+            locked: true,
+            profile: Some("ci"),
+            ..job(&branches)
+        };
+        let requirements = Requirements::from_config(&config, CiProvider::Github);
+        let workflow = github_workflow(&locked_job, &requirements);
+
+        assert!(workflow.contains("  contents: write\n  issues: write\n"));
+        assert!(workflow.contains("registry-url: https://registry.npmjs.org\n"));
+        assert!(workflow.contains("NODE_AUTH_TOKEN: ${{ secrets.NPM_TOKEN }}\n"));
+        assert!(workflow.contains("SLACK_WEBHOOK_URL: ${{ secrets.SLACK_WEBHOOK_URL }}\n"));
+        assert!(
+            workflow.contains("key: unduler-${{ runner.os }}-${{ hashFiles('unduler.lock') }}")
+        );
+        assert!(workflow.contains("run: unduler plugin sync\n"));
+        assert!(workflow.contains("unduler release --ci --push --locked --profile ci"));
+        assert_eq!(
+            requirements.secret_names(CiProvider::Github),
+            ["NPM_TOKEN", "SLACK_WEBHOOK_URL"]
+        );
+    }
+
+    #[test]
+    fn test_gitlab_pipeline() {
+        let branches = vec!["main".to_string(), "release/*".to_string()];
+        let mut config = Config::default();
+        config.plugins.npm.publish = true;
+        let requirements = Requirements::from_config(&config, CiProvider::Gitlab);
+        let gitlab = gitlab_pipeline(&job(&branches), &requirements);
+
+        assert!(gitlab.contains("  image: node:lts\n"));
+        assert!(gitlab.contains("    - if: $CI_COMMIT_BRANCH == \"main\"\n"));
+        assert!(gitlab.contains("    - if: $CI_COMMIT_BRANCH =~ /^release\\/[^\\/]*$/\n"));
+        assert!(gitlab.contains("    GIT_DEPTH: \"0\"\n    NODE_AUTH_TOKEN: $NPM_TOKEN\n"));
+        assert!(gitlab.contains("ln -sfn \"$CI_PROJECT_DIR/.unduler-cache\" ~/.unduler"));
+        assert!(gitlab.contains("echo \"//registry.npmjs.org/:_authToken=${NODE_AUTH_TOKEN}\""));
+        assert!(gitlab.contains("  script:\n    - unduler release --ci --push || [ $? -eq 2 ]\n"));
+        assert_eq!(
+            requirements.secret_names(CiProvider::Gitlab),
+            ["GITLAB_TOKEN", "NPM_TOKEN"]
+        );
+    }
+
+    #[test]
+    fn test_branch_rule() {
+        assert_eq!(branch_rule("main"), "$CI_COMMIT_BRANCH == \"main\"");
+        assert_eq!(
+            branch_rule("release/**"),
+            "$CI_COMMIT_BRANCH =~ /^release\\/.*$/"
+        );
+        assert_eq!(branch_rule("v*.x"), "$CI_COMMIT_BRANCH =~ /^v[^\\/]*\\.x$/");
+    }
+}
@@ -0,0 +1,53 @@
+//! Configuration commands.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+
+use unduler_config::Config;
+
+/// Configuration commands.
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the JSON Schema of unduler.toml for editor completion and validation
+    Schema(SchemaArgs),
+}
+
+/// Arguments for the `config schema` command.
+#[derive(Debug, Args)]
+pub struct SchemaArgs {
+    /// Write the schema to a file instead of stdout
+    #[arg(short = 'o', long = "out")]
+    pub out: Option<PathBuf>,
+}
+
+/// Runs the config command.
+pub fn run(args: ConfigArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Schema(args) => schema(&args),
+    }
+}
+
+fn schema(args: &SchemaArgs) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&Config::json_schema())
+        .context("failed to serialize the schema")?;
+
+    match &args.out {
+        Some(path) => {
+            fs::write(path, format!("{schema}\n"))
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+        None => println!("{schema}"),
+    }
+
+    Ok(())
+}
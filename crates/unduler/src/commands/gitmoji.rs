@@ -0,0 +1,79 @@
+//! Gitmoji commands.
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use crate::output::Output;
+
+/// Gitmoji commands.
+#[derive(Debug, Args)]
+pub struct GitmojiArgs {
+    #[command(subcommand)]
+    pub command: GitmojiCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GitmojiCommand {
+    /// List the emojis the gitmoji parser knows, with their commit types
+    List(ListArgs),
+}
+
+/// Arguments for the `gitmoji list` command.
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
+}
+
+/// Emoji mapping printed in JSON format.
+#[derive(Debug, Serialize)]
+struct Mapping {
+    emoji: String,
+    code: Option<&'static str>,
+    commit_type: String,
+    custom: bool,
+}
+
+/// Runs the gitmoji command.
+pub fn run(args: GitmojiArgs, output: Output) -> Result<()> {
+    match args.command {
+        GitmojiCommand::List(args) => list(&args, output),
+    }
+}
+
+fn list(args: &ListArgs, output: Output) -> Result<()> {
+    let config = super::load_config(args.profile.as_deref())?;
+    let mappings = unduler_lib::create_gitmoji_parser(&config).mappings();
+
+    if output.is_json() {
+        let mappings: Vec<_> = mappings
+            .into_iter()
+            .map(|mapping| Mapping {
+                emoji: mapping.emoji,
+                code: mapping.code,
+                commit_type: mapping.commit_type,
+                custom: mapping.custom,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&mappings)?);
+        return Ok(());
+    }
+
+    let width = mappings
+        .iter()
+        .filter_map(|mapping| mapping.code.map(str::len))
+        .max()
+        .unwrap_or_default();
+    for mapping in mappings {
+        let code = mapping.code.unwrap_or_default();
+        let custom = if mapping.custom { " (custom)" } else { "" };
+        println!(
+            "  {}  {code:width$}  {}{custom}",
+            mapping.emoji, mapping.commit_type
+        );
+    }
+
+    Ok(())
+}
@@ -4,11 +4,13 @@ use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use clap::{Args, ValueEnum};
 
 use unduler_config::CONFIG_FILE_NAME;
 
+use crate::diagnostic::Diagnostic;
+
 /// Parser type argument.
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum ParserType {
@@ -19,6 +21,8 @@ pub enum ParserType {
     ConventionalGitmoji,
     /// Custom regex pattern
     Regex,
+    /// JIRA ticket-prefixed subjects
+    Jira,
 }
 
 impl ParserType {
@@ -27,38 +31,64 @@ impl ParserType {
             Self::Conventional => "conventional",
             Self::ConventionalGitmoji => "conventional-gitmoji",
             Self::Regex => "regex",
+            Self::Jira => "jira",
         }
     }
 }
 
-/// Detected project type.
+/// Ecosystem of a project, detected from its manifest.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ProjectType {
+enum Ecosystem {
     Rust,
     Node,
-    RustAndNode,
-    Unknown,
+    Python,
+    /// Go modules have no version field, the version is kept in a VERSION file.
+    Go,
+    Dart,
+    Php,
+    Maven,
+    /// Gradle project, with the file holding its version.
+    Gradle(&'static str),
 }
 
-impl ProjectType {
-    fn detect() -> Self {
-        let has_cargo = Path::new("Cargo.toml").exists();
-        let has_package_json = Path::new("package.json").exists();
-
-        match (has_cargo, has_package_json) {
-            (true, true) => Self::RustAndNode,
-            (true, false) => Self::Rust,
-            (false, true) => Self::Node,
-            (false, false) => Self::Unknown,
+impl Ecosystem {
+    /// Detects an ecosystem from the files of the current directory.
+    fn detect(self) -> Option<Self> {
+        match self {
+            Self::Gradle(_) => detect_gradle(),
+            _ => Path::new(self.manifest()).exists().then_some(self),
+        }
+    }
+
+    /// Returns the file identifying the ecosystem.
+    fn manifest(self) -> &'static str {
+        match self {
+            Self::Rust => "Cargo.toml",
+            Self::Node => "package.json",
+            Self::Python => "pyproject.toml",
+            Self::Go => "go.mod",
+            Self::Dart => "pubspec.yaml",
+            Self::Php => "composer.json",
+            Self::Maven => "pom.xml",
+            Self::Gradle(file) => file,
         }
     }
 
-    fn version_files(self) -> Vec<&'static str> {
+    /// Returns the file holding the version, if updated based on its type.
+    fn version_file(self) -> Option<&'static str> {
         match self {
-            Self::Rust => vec!["Cargo.toml"],
-            Self::Node => vec!["package.json"],
-            Self::RustAndNode => vec!["Cargo.toml", "package.json"],
-            Self::Unknown => vec![],
+            Self::Go => None,
+            _ => Some(self.manifest()),
+        }
+    }
+
+    /// Returns the hook plugin suited to the ecosystem.
+    fn hook_plugin(self) -> Option<&'static str> {
+        match self {
+            Self::Rust => Some("unduler-hook-cargo"),
+            Self::Node => Some("unduler-hook-npm"),
+            Self::Maven | Self::Gradle(_) => Some("unduler-hook-jvm"),
+            _ => None,
         }
     }
 
@@ -66,12 +96,101 @@ impl ProjectType {
         match self {
             Self::Rust => "Rust",
             Self::Node => "Node.js",
-            Self::RustAndNode => "Rust + Node.js",
-            Self::Unknown => "Unknown",
+            Self::Python => "Python",
+            Self::Go => "Go",
+            Self::Dart => "Dart/Flutter",
+            Self::Php => "PHP",
+            Self::Maven => "Maven",
+            Self::Gradle(_) => "Gradle",
         }
     }
 }
 
+/// Ecosystems in detection order.
+///
+/// The Gradle entry stands for any build script, see [`detect_gradle`].
+const ECOSYSTEMS: [Ecosystem; 8] = [
+    Ecosystem::Rust,
+    Ecosystem::Node,
+    Ecosystem::Python,
+    Ecosystem::Go,
+    Ecosystem::Dart,
+    Ecosystem::Php,
+    Ecosystem::Maven,
+    Ecosystem::Gradle("build.gradle"),
+];
+
+/// Plain text file holding the version of projects without a version field.
+const VERSION_FILE: &str = "VERSION";
+
+/// Detected project type, made of every ecosystem found in the repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProjectType(Vec<Ecosystem>);
+
+impl ProjectType {
+    fn detect() -> Self {
+        Self(
+            ECOSYSTEMS
+                .into_iter()
+                .filter_map(Ecosystem::detect)
+                .collect(),
+        )
+    }
+
+    fn version_files(&self) -> Vec<&'static str> {
+        self.0
+            .iter()
+            .filter_map(|ecosystem| ecosystem.version_file())
+            .collect()
+    }
+
+    /// Returns true if the version is kept in a VERSION file.
+    fn needs_version_file(&self) -> bool {
+        self.0.contains(&Ecosystem::Go)
+    }
+
+    fn hook_plugins(&self) -> Vec<&'static str> {
+        self.0
+            .iter()
+            .filter_map(|ecosystem| ecosystem.hook_plugin())
+            .collect()
+    }
+
+    fn description(&self) -> String {
+        if self.0.is_empty() {
+            return "Unknown".to_string();
+        }
+        self.0
+            .iter()
+            .map(|ecosystem| ecosystem.description())
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+/// Detects a Gradle project and the file holding its version.
+///
+/// The version is usually kept in gradle.properties when it defines one,
+/// otherwise in the build script.
+fn detect_gradle() -> Option<Ecosystem> {
+    let properties_has_version = fs::read_to_string("gradle.properties").is_ok_and(|content| {
+        content.lines().any(|line| {
+            line.split(['=', ':'])
+                .next()
+                .is_some_and(|key| key.trim() == "version")
+        })
+    });
+
+    if properties_has_version {
+        return Some(Ecosystem::Gradle("gradle.properties"));
+    }
+
+    ["build.gradle.kts", "build.gradle"]
+        .into_iter()
+        .find(|file| Path::new(file).exists())
+        .map(Ecosystem::Gradle)
+}
+
 /// Arguments for the init command.
 #[derive(Debug, Args)]
 pub struct InitArgs {
@@ -89,7 +208,7 @@ pub struct InitArgs {
 }
 
 /// Generates the configuration file content.
-fn generate_config(parser: ParserType, project_type: ProjectType) -> String {
+fn generate_config(parser: ParserType, project_type: &ProjectType) -> String {
     let parser_name = parser.as_config_name();
     let version_files = project_type.version_files();
 
@@ -120,6 +239,16 @@ fn generate_config(parser: ParserType, project_type: ProjectType) -> String {
         );
     }
 
+    // Add JIRA placeholders if using jira
+    if matches!(parser, ParserType::Jira) {
+        config.push_str("\n[parser.jira]\n");
+        config.push_str("# projects = [\"PROJ\"]\n");
+        config.push_str("default_type = \"chore\"\n");
+        config.push_str("\n[parser.jira.types]\n");
+        config.push_str("# fix = \"fix\"\n");
+        config.push_str("# add = \"feat\"\n");
+    }
+
     // Version section
     config.push_str("\n[version]\n");
     config.push_str("tag_prefix = \"v\"\n");
@@ -133,6 +262,12 @@ fn generate_config(parser: ParserType, project_type: ProjectType) -> String {
         let _ = writeln!(config, "files = [{files_str}]");
     }
 
+    if project_type.needs_version_file() {
+        config.push_str("\n[[version.file]]\n");
+        let _ = writeln!(config, "path = \"{VERSION_FILE}\"");
+        config.push_str("pattern = \"^{version}$\"\n");
+    }
+
     // Changelog section
     config.push_str("\n[changelog]\n");
     config.push_str("output = \"CHANGELOG.md\"\n");
@@ -147,7 +282,11 @@ pub fn run(args: InitArgs) -> Result<()> {
 
     // Check if config already exists
     if config_path.exists() && !args.force {
-        bail!("{CONFIG_FILE_NAME} already exists. Use --force to overwrite.");
+        return Err(
+            Diagnostic::new(format!("{CONFIG_FILE_NAME} already exists"))
+                .with_help("use --force to overwrite it")
+                .into(),
+        );
     }
 
     // Detect project type
@@ -157,7 +296,10 @@ pub fn run(args: InitArgs) -> Result<()> {
     println!("  Project type: {}", project_type.description());
     println!("  Parser: {}", args.parser.as_config_name());
 
-    let version_files = project_type.version_files();
+    let mut version_files = project_type.version_files();
+    if project_type.needs_version_file() {
+        version_files.push(VERSION_FILE);
+    }
     if version_files.is_empty() {
         println!("  Version files: (none detected)");
     } else {
@@ -165,7 +307,7 @@ pub fn run(args: InitArgs) -> Result<()> {
     }
 
     // Generate and write config
-    let config_content = generate_config(args.parser, project_type);
+    let config_content = generate_config(args.parser, &project_type);
 
     fs::write(config_path, &config_content)
         .with_context(|| format!("failed to write {CONFIG_FILE_NAME}"))?;
@@ -177,6 +319,12 @@ pub fn run(args: InitArgs) -> Result<()> {
         println!("Edit {CONFIG_FILE_NAME} to set your custom pattern.");
     }
 
+    if project_type.needs_version_file() && !Path::new(VERSION_FILE).exists() {
+        println!("\nNote: Go modules have no version field.");
+        println!("Create a {VERSION_FILE} file holding the current version, e.g.:");
+        println!("  echo 0.1.0 > {VERSION_FILE}");
+    }
+
     if version_files.is_empty() {
         println!("\nWarning: No version files detected.");
         println!("Edit {CONFIG_FILE_NAME} to add your version files manually.");
@@ -184,14 +332,14 @@ pub fn run(args: InitArgs) -> Result<()> {
 
     // Suggest plugins based on configuration
     if !args.no_plugins {
-        suggest_plugins(args.parser, project_type);
+        suggest_plugins(args.parser, &project_type);
     }
 
     Ok(())
 }
 
 /// Suggests plugins to install based on configuration.
-fn suggest_plugins(parser: ParserType, project_type: ProjectType) {
+fn suggest_plugins(parser: ParserType, project_type: &ProjectType) {
     let mut plugins = Vec::new();
 
     // Parser plugin based on selection
@@ -199,6 +347,7 @@ fn suggest_plugins(parser: ParserType, project_type: ProjectType) {
         ParserType::Conventional => "unduler-parser-conventional",
         ParserType::ConventionalGitmoji => "unduler-parser-gitmoji",
         ParserType::Regex => "unduler-parser-regex",
+        ParserType::Jira => "unduler-parser-jira",
     };
     plugins.push(parser_plugin);
 
@@ -207,19 +356,7 @@ fn suggest_plugins(parser: ParserType, project_type: ProjectType) {
     plugins.push("unduler-formatter-keepachangelog");
 
     // Hook plugins based on project type
-    match project_type {
-        ProjectType::Rust => {
-            plugins.push("unduler-hook-cargo");
-        }
-        ProjectType::Node => {
-            plugins.push("unduler-hook-npm");
-        }
-        ProjectType::RustAndNode => {
-            plugins.push("unduler-hook-cargo");
-            plugins.push("unduler-hook-npm");
-        }
-        ProjectType::Unknown => {}
-    }
+    plugins.extend(project_type.hook_plugins());
 
     println!("\nRecommended plugins:");
     for plugin in &plugins {
@@ -252,30 +389,69 @@ mod tests {
             "conventional-gitmoji"
         );
         assert_eq!(ParserType::Regex.as_config_name(), "regex");
+        assert_eq!(ParserType::Jira.as_config_name(), "jira");
+    }
+
+    fn project(ecosystems: &[Ecosystem]) -> ProjectType {
+        ProjectType(ecosystems.to_vec())
     }
 
     #[test]
     fn test_project_type_version_files() {
-        assert_eq!(ProjectType::Rust.version_files(), vec!["Cargo.toml"]);
-        assert_eq!(ProjectType::Node.version_files(), vec!["package.json"]);
+        use Ecosystem::{Dart, Go, Gradle, Maven, Node, Php, Python, Rust};
+
+        assert_eq!(project(&[Rust]).version_files(), vec!["Cargo.toml"]);
+        assert_eq!(project(&[Node]).version_files(), vec!["package.json"]);
         assert_eq!(
-            ProjectType::RustAndNode.version_files(),
+            project(&[Rust, Node]).version_files(),
             vec!["Cargo.toml", "package.json"]
         );
-        assert!(ProjectType::Unknown.version_files().is_empty());
+        assert_eq!(project(&[Python]).version_files(), vec!["pyproject.toml"]);
+        assert_eq!(project(&[Dart]).version_files(), vec!["pubspec.yaml"]);
+        assert_eq!(project(&[Php]).version_files(), vec!["composer.json"]);
+        assert_eq!(project(&[Maven]).version_files(), vec!["pom.xml"]);
+        assert_eq!(
+            project(&[Gradle("build.gradle.kts")]).version_files(),
+            vec!["build.gradle.kts"]
+        );
+        assert!(project(&[Go]).version_files().is_empty());
+        assert!(project(&[]).version_files().is_empty());
     }
 
     #[test]
     fn test_project_type_description() {
-        assert_eq!(ProjectType::Rust.description(), "Rust");
-        assert_eq!(ProjectType::Node.description(), "Node.js");
-        assert_eq!(ProjectType::RustAndNode.description(), "Rust + Node.js");
-        assert_eq!(ProjectType::Unknown.description(), "Unknown");
+        use Ecosystem::{Dart, Go, Gradle, Maven, Node, Php, Python, Rust};
+
+        assert_eq!(project(&[Rust]).description(), "Rust");
+        assert_eq!(project(&[Node]).description(), "Node.js");
+        assert_eq!(project(&[Rust, Node]).description(), "Rust + Node.js");
+        assert_eq!(project(&[Python]).description(), "Python");
+        assert_eq!(project(&[Go]).description(), "Go");
+        assert_eq!(project(&[Dart]).description(), "Dart/Flutter");
+        assert_eq!(project(&[Php]).description(), "PHP");
+        assert_eq!(project(&[Maven]).description(), "Maven");
+        assert_eq!(
+            project(&[Gradle("gradle.properties")]).description(),
+            "Gradle"
+        );
+        assert_eq!(project(&[]).description(), "Unknown");
+    }
+
+    #[test]
+    fn test_project_type_hook_plugins() {
+        use Ecosystem::{Go, Maven, Node, Php, Python, Rust};
+
+        assert_eq!(
+            project(&[Rust, Node, Python]).hook_plugins(),
+            vec!["unduler-hook-cargo", "unduler-hook-npm"]
+        );
+        assert!(project(&[Go, Php]).hook_plugins().is_empty());
+        assert_eq!(project(&[Maven]).hook_plugins(), vec!["unduler-hook-jvm"]);
     }
 
     #[test]
     fn test_generate_config_conventional() {
-        let config = generate_config(ParserType::Conventional, ProjectType::Rust);
+        let config = generate_config(ParserType::Conventional, &project(&[Ecosystem::Rust]));
         assert!(config.contains("name = \"conventional\""));
         assert!(config.contains("files = [\"Cargo.toml\"]"));
         assert!(config.contains("tag_prefix = \"v\""));
@@ -284,7 +460,10 @@ mod tests {
 
     #[test]
     fn test_generate_config_gitmoji() {
-        let config = generate_config(ParserType::ConventionalGitmoji, ProjectType::Node);
+        let config = generate_config(
+            ParserType::ConventionalGitmoji,
+            &project(&[Ecosystem::Node]),
+        );
         assert!(config.contains("name = \"conventional-gitmoji\""));
         assert!(config.contains("[parser.conventional-gitmoji]"));
         assert!(config.contains("infer_type_from_emoji = true"));
@@ -293,16 +472,43 @@ mod tests {
 
     #[test]
     fn test_generate_config_regex() {
-        let config = generate_config(ParserType::Regex, ProjectType::Unknown);
+        let config = generate_config(ParserType::Regex, &project(&[]));
         assert!(config.contains("name = \"regex\""));
         assert!(config.contains("[parser.regex]"));
         assert!(config.contains("# pattern ="));
         assert!(!config.contains("files = ")); // No version files for unknown
     }
 
+    #[test]
+    fn test_generate_config_jira() {
+        let config = generate_config(ParserType::Jira, &project(&[]));
+        assert!(config.contains("name = \"jira\""));
+        assert!(config.contains("[parser.jira]"));
+        assert!(config.contains("default_type = \"chore\""));
+        assert!(config.contains("[parser.jira.types]"));
+    }
+
+    #[test]
+    fn test_generate_config_python() {
+        let config = generate_config(ParserType::Conventional, &project(&[Ecosystem::Python]));
+        assert!(config.contains("files = [\"pyproject.toml\"]"));
+    }
+
     #[test]
     fn test_generate_config_hybrid_project() {
-        let config = generate_config(ParserType::Conventional, ProjectType::RustAndNode);
+        let config = generate_config(
+            ParserType::Conventional,
+            &project(&[Ecosystem::Rust, Ecosystem::Node]),
+        );
         assert!(config.contains("files = [\"Cargo.toml\", \"package.json\"]"));
     }
+
+    #[test]
+    fn test_generate_config_go() {
+        let config = generate_config(ParserType::Conventional, &project(&[Ecosystem::Go]));
+        assert!(!config.contains("files = "));
+        assert!(
+            config.contains("[[version.file]]\npath = \"VERSION\"\npattern = \"^{version}$\"\n")
+        );
+    }
 }
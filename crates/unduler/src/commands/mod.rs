@@ -1,7 +1,162 @@
 //! CLI commands.
 
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use unduler_config::{
+    Config, config_key_prefix, find_config_file, load_merged_config, load_profile_config,
+    user_config_path, value_span,
+};
+use unduler_core::{FileChange, Pipeline};
+use unduler_lib::UndulerError;
+use unduler_plugin_manager::LOCKFILE_NAME;
+
+use crate::diagnostic::Diagnostic;
+use crate::output::Output;
+
 pub mod bump;
 pub mod changelog;
+pub mod check;
+pub mod ci;
+pub mod config;
+pub mod gitmoji;
 pub mod init;
+pub mod next;
 pub mod plugin;
 pub mod release;
+pub mod self_update;
+
+/// Loads the configuration of the current directory or its parents, merged
+/// over the user configuration, and applies a profile.
+///
+/// Values the plugins would otherwise ignore, such as an invalid regex
+/// pattern, are reported as errors pointing at the configuration file.
+pub fn load_config(profile: Option<&str>) -> Result<Config> {
+    let current_dir = std::env::current_dir().context("failed to load configuration")?;
+    let path = find_config_file(&current_dir).context("failed to load configuration")?;
+    let user_path = user_config_path();
+    let config = match profile {
+        Some(profile) => load_profile_config(user_path.as_deref(), &path, profile),
+        None => load_merged_config(user_path.as_deref(), &path),
+    }
+    .context("failed to load configuration")?;
+    check_regex_pattern(&config, &path)?;
+    Ok(config)
+}
+
+/// Creates the release pipeline from configuration, with the parsers,
+/// formatters and hooks it configures, built in or installed.
+pub fn create_pipeline(config: &Config) -> Result<Pipeline> {
+    Ok(create_pipeline_loading(config)?.0)
+}
+
+/// Creates the release pipeline from configuration, also returning the crate
+/// names of the installed plugins it loads.
+pub fn create_pipeline_loading(config: &Config) -> Result<(Pipeline, Vec<String>)> {
+    let plugins = plugin::InstalledPlugins::new(config, project_dir()?);
+    let pipeline = unduler_lib::create_pipeline_with(config, &plugins).map_err(plugin_error)?;
+    Ok((pipeline, plugins.loaded()))
+}
+
+/// Converts an error creating the built-in plugins, hinting at the settings
+/// the Debian formatter lacks.
+fn plugin_error(error: UndulerError) -> anyhow::Error {
+    let help = match error {
+        UndulerError::MissingDebianPackage => {
+            "set formatter.debian.package, or add a debian/control file"
+        }
+        UndulerError::MissingDebianMaintainer => {
+            "set formatter.debian.maintainer, DEBFULLNAME and DEBEMAIL, or a git identity"
+        }
+        error => return error.into(),
+    };
+    Diagnostic::from_error(error).with_help(help).into()
+}
+
+/// Returns the project directory: the directory of the configuration of the
+/// current directory or its parents, or the current directory without
+/// configuration.
+pub fn project_dir() -> Result<PathBuf> {
+    let current_dir = std::env::current_dir().context("failed to locate the project")?;
+    Ok(find_config_file(&current_dir)
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or(current_dir))
+}
+
+/// Returns the path of the plugin lockfile, in the project directory.
+pub fn lockfile_path() -> Result<PathBuf> {
+    Ok(project_dir()?.join(LOCKFILE_NAME))
+}
+
+/// Prints the unified diff of a file changed by a dry run.
+pub fn print_diff(output: Output, change: &FileChange) {
+    let diff = change.unified_diff();
+    if !diff.is_empty() {
+        output.text(diff.trim_end());
+    }
+}
+
+/// Indents each line of a text, e.g. a commit message.
+pub fn indent(text: &str, prefix: &str) -> String {
+    text.trim_end()
+        .lines()
+        .map(|line| format!("{prefix}{line}").trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rejects an invalid `parser.regex.pattern`.
+fn check_regex_pattern(config: &Config, path: &Path) -> Result<()> {
+    let Some(pattern) = &config.parser.regex.pattern else {
+        return Ok(());
+    };
+    let Err(error) = regex::Regex::new(pattern) else {
+        return Ok(());
+    };
+
+    // The last line of a syntax error describes it, the others repeat the pattern
+    let error = error.to_string();
+    let reason = error
+        .lines()
+        .last()
+        .unwrap_or_default()
+        .trim_start_matches("error: ");
+
+    let mut diagnostic = Diagnostic::new(format!("invalid regex pattern: {reason}"))
+        .with_help("see https://docs.rs/regex/latest/regex/#syntax for the supported syntax");
+    let key = match config_key_prefix(path) {
+        Some(prefix) => format!("{prefix}.parser.regex.pattern"),
+        None => "parser.regex.pattern".to_string(),
+    };
+    if let Ok(content) = fs::read_to_string(path)
+        && let Some(span) = value_span(&content, &key)
+    {
+        diagnostic = diagnostic.with_label(path, &content, span, reason);
+    }
+    Err(diagnostic.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_config::{NotifyChannelConfig, NotifyChannelKind};
+
+    use super::*;
+
+    #[test]
+    fn test_pipeline_registers_configured_hooks() {
+        let mut config = Config::default();
+        config.plugins.notify.channels.push(NotifyChannelConfig {
+            kind: NotifyChannelKind::Slack,
+            webhook_url: Some("https://hooks.test".to_string()),
+            webhook_url_env: None,
+            template: None,
+        });
+        config.plugins.sbom.enabled = true;
+        let pipeline = create_pipeline(&config).unwrap();
+
+        assert_eq!(pipeline.async_hooks()[0].name(), "notify");
+        assert_eq!(pipeline.hooks()[0].name(), "sbom");
+    }
+}
@@ -0,0 +1,63 @@
+//! Next command.
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+
+use unduler_core::ReleaseManager;
+use unduler_git::Repository;
+
+use crate::output::{Output, OutputFormat};
+
+/// Arguments for the next command.
+#[derive(Debug, Args)]
+pub struct NextArgs {
+    /// Output format: the version only as text, or a JSON object with the
+    /// version, bump type and commit counts (`--output json` implies json)
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
+}
+
+/// Next version details printed in JSON format.
+#[derive(Debug, Serialize)]
+struct NextRelease {
+    current_version: Option<String>,
+    next_version: String,
+    tag: String,
+    bump_type: String,
+    commits: usize,
+    parsed_commits: usize,
+}
+
+/// Runs the next command.
+pub fn run(args: &NextArgs, output: Output) -> Result<()> {
+    let config = super::load_config(args.profile.as_deref())?;
+    let repo = Repository::discover().context("failed to open git repository")?;
+    let manager = ReleaseManager::from_config(repo, &config).with_read_only_cache(true);
+    let pipeline = super::create_pipeline(&config)?;
+
+    let plan = manager.plan(&pipeline, None)?;
+    let next_version = &plan.new_version;
+
+    match args.format {
+        OutputFormat::Text if !output.is_json() => println!("{next_version}"),
+        OutputFormat::Text | OutputFormat::Json => {
+            let next = NextRelease {
+                current_version: (!plan.is_first_release())
+                    .then(|| plan.previous_version.to_string()),
+                tag: manager.tag_format().format(next_version),
+                next_version: next_version.to_string(),
+                bump_type: plan.bump_type.to_string(),
+                commits: plan.raw_commit_count,
+                parsed_commits: plan.commits.len(),
+            };
+            println!("{}", serde_json::to_string_pretty(&next)?);
+        }
+    }
+
+    Ok(())
+}
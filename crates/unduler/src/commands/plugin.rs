@@ -1,15 +1,80 @@
 //! Plugin management commands.
 
-use anyhow::{Context, Result};
-use clap::{Args, Subcommand};
+use std::cell::{OnceCell, RefCell};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use unduler_plugin_manager::{PluginDiscovery, PluginRegistry, PluginStorage};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::{Args, Subcommand, ValueEnum};
+
+use unduler_commit::{ParsedCommit, RawCommit};
+use unduler_config::{Config, ConfigError, find_and_load_config};
+use unduler_lib::{ExternalPlugins, UndulerError, UndulerResult};
+use unduler_plugin::{
+    BumpStrategy, BumpType, ChangelogFormatter, CommitParser, FormatterConfig, Release, ReleaseHook,
+};
+use unduler_plugin_manager::{
+    HttpOptions, LockedPlugin, Lockfile, PluginDiscovery, PluginLoader, PluginRegistry,
+    PluginSource, PluginStorage,
+};
+use unduler_wasm_runtime::{
+    PluginHost, SandboxPolicy, WasmBumpStrategy, WasmBumper, WasmChangelogFormatter,
+    WasmCommitParser, WasmEngine, WasmFormatter, WasmLimits, WasmParser, WasmReleaseHook,
+};
 
 /// Plugin management commands.
 #[derive(Debug, Args)]
 pub struct PluginArgs {
     #[command(subcommand)]
     pub command: PluginCommand,
+
+    /// Discover plugins from a private registry with the crates.io API
+    /// instead of crates.io
+    #[arg(long, global = true, env = "UNDULER_PLUGIN_REGISTRY")]
+    pub registry: Option<String>,
+
+    /// Discover plugins from a static JSON index (URL or path) instead of
+    /// crates.io
+    #[arg(
+        long,
+        global = true,
+        env = "UNDULER_PLUGIN_INDEX",
+        conflicts_with = "registry"
+    )]
+    pub index: Option<String>,
+
+    /// Proxy of plugin downloads (default: from `HTTP_PROXY` and `HTTPS_PROXY`)
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// Timeout of a plugin request, in seconds
+    #[arg(long, global = true, value_name = "SECS", default_value_t = 30)]
+    pub timeout: u64,
+
+    /// Retries of a plugin request failing on a network or server error
+    #[arg(long, global = true, default_value_t = 3)]
+    pub retries: u32,
+}
+
+impl PluginArgs {
+    /// Returns the source plugins are discovered from.
+    fn source(&self) -> PluginSource {
+        match (&self.index, &self.registry) {
+            (Some(index), _) => PluginSource::index(index),
+            (None, Some(registry)) => PluginSource::registry(registry),
+            (None, None) => PluginSource::default(),
+        }
+    }
+
+    /// Returns the HTTP settings of plugin requests.
+    fn http(&self) -> HttpOptions {
+        HttpOptions::from_env()
+            .with_proxy(self.proxy.clone())
+            .with_timeout(Duration::from_secs(self.timeout))
+            .with_retries(self.retries)
+    }
 }
 
 #[derive(Debug, Subcommand)]
@@ -31,6 +96,18 @@ pub enum PluginCommand {
 
     /// Show information about a plugin
     Info(InfoArgs),
+
+    /// Run a local plugin in isolation
+    Run(RunArgs),
+
+    /// Compile installed plugins ahead of their first use
+    Precompile(PrecompileArgs),
+
+    /// Pin installed plugins in the unduler.lock of the project
+    Lock(LockArgs),
+
+    /// Install the plugins pinned in the unduler.lock of the project
+    Sync(SyncArgs),
 }
 
 /// Arguments for the `plugin install` command.
@@ -40,8 +117,22 @@ pub struct InstallArgs {
     pub name: String,
 
     /// Specific version to install (defaults to latest)
-    #[arg(short, long)]
+    #[arg(long)]
     pub version: Option<semver::Version>,
+
+    /// Download the plugin WASM from this URL (or path), without metadata
+    #[arg(long, requires = "version")]
+    pub url: Option<String>,
+
+    /// Expected SHA-256 digest of the plugin WASM, required for HTTPS
+    /// `--url` downloads and index entries listing none
+    #[arg(long)]
+    pub sha256: Option<String>,
+
+    /// Install the plugin in the project (.unduler/plugins/), preferred
+    /// over the global installation
+    #[arg(long)]
+    pub local: bool,
 }
 
 /// Arguments for the `plugin remove` command.
@@ -49,6 +140,10 @@ pub struct InstallArgs {
 pub struct RemoveArgs {
     /// Plugin name
     pub name: String,
+
+    /// Remove the plugin installed in the project
+    #[arg(long)]
+    pub local: bool,
 }
 
 /// Arguments for the `plugin list` command.
@@ -71,6 +166,10 @@ pub struct SearchArgs {
 pub struct UpdateArgs {
     /// Plugin name (updates all if not specified)
     pub name: Option<String>,
+
+    /// Update the plugins installed in the project
+    #[arg(long)]
+    pub local: bool,
 }
 
 /// Arguments for the `plugin info` command.
@@ -80,6 +179,62 @@ pub struct InfoArgs {
     pub name: String,
 }
 
+/// Arguments for the `plugin precompile` command.
+#[derive(Debug, Args)]
+pub struct PrecompileArgs {
+    /// Plugin name (precompiles all if not specified)
+    pub name: Option<String>,
+}
+
+/// Arguments for the `plugin lock` command.
+#[derive(Debug, Args)]
+pub struct LockArgs {
+    /// Plugin names (locks all installed plugins if not specified)
+    pub names: Vec<String>,
+}
+
+/// Arguments for the `plugin sync` command.
+#[derive(Debug, Args)]
+pub struct SyncArgs {
+    /// Install the locked plugins in the project
+    #[arg(long)]
+    pub local: bool,
+}
+
+/// Arguments for the `plugin run` command.
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Plugin type
+    #[arg(value_enum)]
+    pub r#type: RunPluginType,
+
+    /// Path to the plugin `.wasm` file
+    pub path: PathBuf,
+
+    /// Commit message to parse (parser plugins)
+    #[arg(short, long)]
+    pub message: Option<String>,
+
+    /// JSON file containing parsed commits (bumper plugins)
+    #[arg(long)]
+    pub commits: Option<PathBuf>,
+
+    /// JSON file containing a release (formatter plugins)
+    #[arg(long)]
+    pub release: Option<PathBuf>,
+}
+
+/// Plugin types that can be run in isolation.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum RunPluginType {
+    /// Commit parser
+    Parser,
+    /// Version bumper
+    Bumper,
+    /// Changelog formatter
+    Formatter,
+}
+
 /// Runs the plugin command.
 pub fn run(args: PluginArgs) -> Result<()> {
     // Create a tokio runtime for async operations
@@ -89,29 +244,55 @@ pub fn run(args: PluginArgs) -> Result<()> {
 }
 
 async fn run_async(args: PluginArgs) -> Result<()> {
+    let http = args.http();
+    let discovery = PluginDiscovery::with_http(http.clone())
+        .context("invalid HTTP settings")?
+        .with_source(args.source());
     match args.command {
-        PluginCommand::Install(args) => install(args).await,
+        PluginCommand::Install(args) => install(args, &discovery).await,
         PluginCommand::Remove(ref args) => remove(args),
-        PluginCommand::Update(args) => update(args).await,
+        PluginCommand::Update(args) => update(args, &http).await,
         PluginCommand::List(ref args) => list(args),
-        PluginCommand::Search(args) => search(args).await,
-        PluginCommand::Info(args) => info(args).await,
+        PluginCommand::Search(args) => search(args, &discovery).await,
+        PluginCommand::Info(args) => info(args, &discovery).await,
+        PluginCommand::Run(ref args) => run_plugin(args),
+        PluginCommand::Precompile(ref args) => precompile(args),
+        PluginCommand::Lock(ref args) => lock(args),
+        PluginCommand::Sync(ref args) => sync(args, &discovery).await,
     }
 }
 
-async fn install(args: InstallArgs) -> Result<()> {
+async fn install(args: InstallArgs, discovery: &PluginDiscovery) -> Result<()> {
     let crate_name = normalize_plugin_name(&args.name);
 
     println!("Installing {crate_name}...");
 
-    let storage = PluginStorage::new().context("failed to initialize plugin storage")?;
-    let mut registry = PluginRegistry::new(storage).context("failed to load plugin registry")?;
-    let discovery = PluginDiscovery::new();
-
-    let plugin = discovery
-        .install(&mut registry, &crate_name, args.version.as_ref())
-        .await
-        .with_context(|| format!("failed to install {crate_name}"))?;
+    let mut registry = open_registry(args.local)?;
+
+    let plugin = match (&args.url, &args.version) {
+        (Some(url), Some(version)) => {
+            discovery
+                .install_from_url(
+                    &mut registry,
+                    &crate_name,
+                    version,
+                    url,
+                    args.sha256.as_deref(),
+                )
+                .await
+        }
+        _ => {
+            discovery
+                .install(
+                    &mut registry,
+                    &crate_name,
+                    args.version.as_ref(),
+                    args.sha256.as_deref(),
+                )
+                .await
+        }
+    }
+    .with_context(|| format!("failed to install {crate_name}"))?;
 
     println!(
         "Installed {} v{} ({})",
@@ -124,8 +305,7 @@ async fn install(args: InstallArgs) -> Result<()> {
 fn remove(args: &RemoveArgs) -> Result<()> {
     let crate_name = normalize_plugin_name(&args.name);
 
-    let storage = PluginStorage::new().context("failed to initialize plugin storage")?;
-    let mut registry = PluginRegistry::new(storage).context("failed to load plugin registry")?;
+    let mut registry = open_registry(args.local)?;
     let discovery = PluginDiscovery::new();
 
     discovery
@@ -137,10 +317,8 @@ fn remove(args: &RemoveArgs) -> Result<()> {
     Ok(())
 }
 
-async fn update(args: UpdateArgs) -> Result<()> {
-    let storage = PluginStorage::new().context("failed to initialize plugin storage")?;
-    let mut registry = PluginRegistry::new(storage).context("failed to load plugin registry")?;
-    let discovery = PluginDiscovery::new();
+async fn update(args: UpdateArgs, http: &HttpOptions) -> Result<()> {
+    let mut registry = open_registry(args.local)?;
 
     let plugins_to_update: Vec<_> = if let Some(name) = &args.name {
         let crate_name = normalize_plugin_name(name);
@@ -164,12 +342,22 @@ async fn update(args: UpdateArgs) -> Result<()> {
     for plugin in &plugins_to_update {
         print!("Checking {}... ", plugin.crate_name);
 
+        // Plugins are updated from the source they were installed from
+        if let PluginSource::Url { url } = &plugin.source {
+            println!("pinned to {url} ({})", plugin.version);
+            up_to_date += 1;
+            continue;
+        }
+        let discovery = PluginDiscovery::with_http(http.clone())
+            .context("invalid HTTP settings")?
+            .with_source(plugin.source.clone());
+
         match discovery.fetch_metadata(&plugin.crate_name).await {
             Ok(metadata) => {
                 if metadata.latest_version > plugin.version {
                     println!("updating {} -> {}", plugin.version, metadata.latest_version);
                     match discovery
-                        .install(&mut registry, &plugin.crate_name, None)
+                        .install(&mut registry, &plugin.crate_name, None, None)
                         .await
                     {
                         Ok(_) => updated += 1,
@@ -205,10 +393,10 @@ async fn update(args: UpdateArgs) -> Result<()> {
 }
 
 fn list(args: &ListArgs) -> Result<()> {
-    let storage = PluginStorage::new().context("failed to initialize plugin storage")?;
-    let registry = PluginRegistry::new(storage).context("failed to load plugin registry")?;
+    let loader = open_loader()?;
 
-    let plugins = if let Some(type_filter) = &args.r#type {
+    let mut plugins = loader.list();
+    if let Some(type_filter) = &args.r#type {
         let plugin_type = match type_filter.as_str() {
             "parser" => unduler_plugin_manager::storage::PluginType::Parser,
             "bumper" => unduler_plugin_manager::storage::PluginType::Bumper,
@@ -216,10 +404,8 @@ fn list(args: &ListArgs) -> Result<()> {
             "hook" => unduler_plugin_manager::storage::PluginType::Hook,
             _ => anyhow::bail!("unknown plugin type: {type_filter}"),
         };
-        registry.list_by_type(plugin_type)
-    } else {
-        registry.list()
-    };
+        plugins.retain(|resolved| resolved.plugin.plugin_type == plugin_type);
+    }
 
     if plugins.is_empty() {
         println!("No plugins installed.");
@@ -228,25 +414,28 @@ fn list(args: &ListArgs) -> Result<()> {
 
     println!("Installed plugins:\n");
 
-    for plugin in plugins {
-        println!("  {} v{}", plugin.crate_name, plugin.version);
+    for resolved in plugins {
+        let plugin = resolved.plugin;
+        let scope = if resolved.local { " (project)" } else { "" };
+        println!("  {} v{}{scope}", plugin.crate_name, plugin.version);
         if let Some(desc) = &plugin.description {
             println!("    {desc}");
         }
+        if plugin.source != PluginSource::default() {
+            println!("    from {}", plugin.source);
+        }
     }
 
     Ok(())
 }
 
-async fn search(args: SearchArgs) -> Result<()> {
-    let discovery = PluginDiscovery::new();
-
+async fn search(args: SearchArgs, discovery: &PluginDiscovery) -> Result<()> {
     println!("Searching for \"{}\"...\n", args.query);
 
     let results = discovery
         .search(&args.query)
         .await
-        .context("failed to search crates.io")?;
+        .with_context(|| format!("failed to search {}", discovery.source()))?;
 
     if results.is_empty() {
         println!("No plugins found.");
@@ -267,9 +456,8 @@ async fn search(args: SearchArgs) -> Result<()> {
     Ok(())
 }
 
-async fn info(args: InfoArgs) -> Result<()> {
+async fn info(args: InfoArgs, discovery: &PluginDiscovery) -> Result<()> {
     let crate_name = normalize_plugin_name(&args.name);
-    let discovery = PluginDiscovery::new();
 
     println!("Fetching info for {crate_name}...\n");
 
@@ -299,16 +487,374 @@ async fn info(args: InfoArgs) -> Result<()> {
     }
 
     // Check if installed
-    let storage = PluginStorage::new().context("failed to initialize plugin storage")?;
-    let registry = PluginRegistry::new(storage).context("failed to load plugin registry")?;
+    let loader = open_loader()?;
+
+    if let Some(resolved) = loader.resolve(&crate_name) {
+        let scope = if resolved.local { " (project)" } else { "" };
+        println!("\nInstalled:   v{}{scope}", resolved.plugin.version);
+        println!("Source:      {}", resolved.plugin.source);
+    }
+
+    Ok(())
+}
+
+fn precompile(args: &PrecompileArgs) -> Result<()> {
+    let loader = open_loader()?;
+    let engine = WasmEngine::with_cache_dir(loader.global().storage().cache_dir())
+        .context("failed to create WASM engine")?;
+
+    let plugins = if let Some(name) = &args.name {
+        let crate_name = normalize_plugin_name(name);
+        match loader.resolve(&crate_name) {
+            Some(p) => vec![p],
+            None => anyhow::bail!("plugin {crate_name} is not installed"),
+        }
+    } else {
+        loader.list()
+    };
+
+    if plugins.is_empty() {
+        println!("No plugins installed.");
+        return Ok(());
+    }
+
+    for resolved in plugins {
+        let plugin = resolved.plugin;
+        engine
+            .precompile(&resolved.path)
+            .with_context(|| format!("failed to precompile {}", plugin.crate_name))?;
+        println!("Precompiled {} v{}", plugin.crate_name, plugin.version);
+    }
+
+    Ok(())
+}
+
+fn lock(args: &LockArgs) -> Result<()> {
+    let loader = open_loader()?;
+    let path = super::lockfile_path()?;
+
+    let lockfile = if args.names.is_empty() {
+        Lockfile::from_loader(&loader).context("failed to lock installed plugins")?
+    } else {
+        // Named plugins are locked on top of the existing lockfile
+        let mut lockfile = if path.exists() {
+            Lockfile::load(&path)?
+        } else {
+            Lockfile::new()
+        };
+        for name in &args.names {
+            let crate_name = normalize_plugin_name(name);
+            let Some(resolved) = loader.resolve(&crate_name) else {
+                anyhow::bail!("plugin {crate_name} is not installed");
+            };
+            lockfile.lock(
+                LockedPlugin::from_resolved(&resolved)
+                    .with_context(|| format!("failed to lock {crate_name}"))?,
+            );
+        }
+        lockfile
+    };
+
+    lockfile.save(&path)?;
 
-    if let Some(installed) = registry.get(&crate_name) {
-        println!("\nInstalled:   v{}", installed.version);
+    for plugin in lockfile.plugins() {
+        println!("Locked {} v{}", plugin.name, plugin.version);
     }
+    println!("Wrote {}", path.display());
 
     Ok(())
 }
 
+async fn sync(args: &SyncArgs, discovery: &PluginDiscovery) -> Result<()> {
+    let path = super::lockfile_path()?;
+    let lockfile = Lockfile::load(&path)?;
+
+    let mismatches = lockfile
+        .verify(&open_loader()?)
+        .context("failed to check installed plugins")?;
+    if mismatches.is_empty() {
+        println!(
+            "All {} locked plugin(s) installed.",
+            lockfile.plugins().len()
+        );
+        return Ok(());
+    }
+
+    let mut registry = open_registry(args.local)?;
+    for mismatch in &mismatches {
+        let name = mismatch.name();
+        let Some(locked) = lockfile.get(name) else {
+            continue;
+        };
+        println!("Installing {name} v{}...", locked.version);
+        discovery
+            .install_locked(&mut registry, locked)
+            .await
+            .with_context(|| format!("failed to install {name}"))?;
+    }
+
+    println!(
+        "\nSynced {} plugin(s) from {}",
+        mismatches.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+fn run_plugin(args: &RunArgs) -> Result<()> {
+    // Plugins may be tested outside of a project, so the configuration is optional
+    let config = find_optional_config()?;
+    let limits = config.as_ref().map_or_else(WasmLimits::default, |config| {
+        WasmLimits::from_config(&config.wasm)
+    });
+    let engine = create_engine()?.with_limits(limits);
+    // The `[plugins.<name>]` table of the plugin, as JSON
+    let plugin_config = |name: &str| {
+        config
+            .as_ref()
+            .and_then(|config| config.plugins.get(name))
+            .map(ToString::to_string)
+    };
+
+    match args.r#type {
+        RunPluginType::Parser => {
+            let Some(message) = &args.message else {
+                anyhow::bail!("--message is required for parser plugins");
+            };
+
+            let mut parser = WasmParser::from_file(&engine, &args.path)
+                .with_context(|| format!("failed to load {}", args.path.display()))?;
+            if let Some(config) = plugin_config(parser.name()) {
+                parser
+                    .configure(&config)
+                    .context("failed to configure parser plugin")?;
+            }
+
+            let raw = RawCommit::new(
+                "0000000",
+                message,
+                "unduler",
+                "unduler@localhost",
+                Utc::now(),
+            );
+            let parsed = parser
+                .parse(&(&raw).into())
+                .context("failed to run parser plugin")?;
+
+            match parsed {
+                Some(commit) => {
+                    let commit: ParsedCommit = commit.into();
+                    println!("{}", serde_json::to_string_pretty(&commit)?);
+                }
+                None => println!("Commit not recognized by the parser."),
+            }
+        }
+        RunPluginType::Bumper => {
+            let Some(path) = &args.commits else {
+                anyhow::bail!("--commits is required for bumper plugins");
+            };
+            let commits: Vec<ParsedCommit> = read_json(path)?;
+
+            let mut bumper = WasmBumper::from_file(&engine, &args.path)
+                .with_context(|| format!("failed to load {}", args.path.display()))?;
+            if let Some(config) = plugin_config(bumper.name()) {
+                bumper
+                    .configure(&config)
+                    .context("failed to configure bumper plugin")?;
+            }
+
+            let commits: Vec<_> = commits.iter().map(Into::into).collect();
+            let bump: BumpType = bumper
+                .determine(&commits)
+                .context("failed to run bumper plugin")?
+                .into();
+
+            println!("{bump}");
+        }
+        RunPluginType::Formatter => {
+            let Some(path) = &args.release else {
+                anyhow::bail!("--release is required for formatter plugins");
+            };
+            let release: Release = read_json(path)?;
+
+            let mut formatter = WasmFormatter::from_file(&engine, &args.path)
+                .with_context(|| format!("failed to load {}", args.path.display()))?;
+            if let Some(config) = plugin_config(formatter.name()) {
+                formatter
+                    .configure(&config)
+                    .context("failed to configure formatter plugin")?;
+            }
+
+            let output = formatter
+                .format(&(&release).into(), &(&FormatterConfig::default()).into())
+                .context("failed to run formatter plugin")?;
+
+            print!("{output}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens the registry of the global plugins, or of the project plugins.
+fn open_registry(local: bool) -> Result<PluginRegistry> {
+    let storage = if local {
+        PluginStorage::local(&super::project_dir()?)
+    } else {
+        PluginStorage::new()
+    }
+    .context("failed to initialize plugin storage")?;
+    PluginRegistry::new(storage).context("failed to load plugin registry")
+}
+
+/// Finds and loads the configuration, if any.
+fn find_optional_config() -> Result<Option<Config>> {
+    match find_and_load_config() {
+        Ok(config) => Ok(Some(config)),
+        Err(ConfigError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e).context("failed to load configuration"),
+    }
+}
+
+/// Opens the loader of the installed plugins, preferring the plugins of the
+/// project when it has some.
+pub fn open_loader() -> Result<PluginLoader> {
+    let loader = PluginLoader::new(open_registry(false)?);
+    if PluginStorage::has_local(&super::project_dir()?) {
+        Ok(loader.with_local(open_registry(true)?))
+    } else {
+        Ok(loader)
+    }
+}
+
+/// Opens the loader of the installed plugins, delivering the
+/// `[plugins.<name>]` tables and `[sandbox.<name>]` policies of the
+/// configuration to the plugins it loads.
+fn open_configured_loader(config: &Config) -> Result<PluginLoader> {
+    let sandboxes = config
+        .sandbox
+        .iter()
+        .map(|(name, sandbox)| (name.clone(), SandboxPolicy::from_config(sandbox)))
+        .collect();
+    Ok(open_loader()?
+        .with_configs(config.plugins.other.clone())
+        .with_sandboxes(sandboxes))
+}
+
+/// Installed WASM plugins of the release pipeline, looked up by the names
+/// the configuration gives them: `parser.name = "foo"` loads
+/// `unduler-parser-foo`, `bumper.name = "foo"` loads `unduler-bumper-foo`,
+/// and a hook `foo` listed in `[hooks]` loads `unduler-hook-foo`.
+///
+/// The loader and plugin host are only opened when a plugin is looked up,
+/// and the host runs every plugin on one engine, loading each of them once.
+pub struct InstalledPlugins<'a> {
+    config: &'a Config,
+    workdir: PathBuf,
+    opened: OnceCell<(PluginLoader, PluginHost)>,
+    loaded: RefCell<Vec<String>>,
+}
+
+impl<'a> InstalledPlugins<'a> {
+    /// Creates the installed plugins of a project, whose hooks run in
+    /// `workdir`.
+    pub fn new(config: &'a Config, workdir: PathBuf) -> Self {
+        Self {
+            config,
+            workdir,
+            opened: OnceCell::new(),
+            loaded: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the crate names of the plugins loaded so far.
+    pub fn loaded(&self) -> Vec<String> {
+        self.loaded.borrow().clone()
+    }
+
+    /// Returns the loader and plugin host, opening them on first use.
+    fn open(&self) -> Result<&(PluginLoader, PluginHost)> {
+        if let Some(opened) = self.opened.get() {
+            return Ok(opened);
+        }
+        let loader = open_configured_loader(self.config)?;
+        let engine = create_engine()?.with_limits(WasmLimits::from_config(&self.config.wasm));
+        Ok(self
+            .opened
+            .get_or_init(|| (loader, PluginHost::new(engine))))
+    }
+
+    /// Loads the installed plugin `unduler-<kind>-<name>`, if there is one.
+    fn load<T>(
+        &self,
+        kind: &str,
+        name: &str,
+        load: impl FnOnce(&PluginLoader, &PluginHost, &str) -> Result<T>,
+    ) -> UndulerResult<Option<T>> {
+        let crate_name = format!("unduler-{kind}-{name}");
+        let loaded = self.open().and_then(|(loader, host)| {
+            if loader.resolve(&crate_name).is_none() {
+                return Ok(None);
+            }
+            self.loaded.borrow_mut().push(crate_name.clone());
+            load(loader, host, &crate_name).map(Some)
+        });
+        loaded.map_err(|error| UndulerError::Plugin(error.into()))
+    }
+}
+
+impl ExternalPlugins for InstalledPlugins<'_> {
+    fn parser(&self, name: &str) -> UndulerResult<Option<Box<dyn CommitParser>>> {
+        self.load("parser", name, |loader, host, crate_name| {
+            let parser = loader.load_parser(host, crate_name)?;
+            Ok(Box::new(WasmCommitParser::from_shared(name, parser)?) as Box<dyn CommitParser>)
+        })
+    }
+
+    fn bumper(&self, name: &str) -> UndulerResult<Option<Box<dyn BumpStrategy>>> {
+        self.load("bumper", name, |loader, host, crate_name| {
+            let bumper = loader.load_bumper(host, crate_name)?;
+            Ok(Box::new(WasmBumpStrategy::from_shared(name, bumper)?) as Box<dyn BumpStrategy>)
+        })
+    }
+
+    fn formatter(&self, name: &str) -> UndulerResult<Option<Box<dyn ChangelogFormatter>>> {
+        self.load("formatter", name, |loader, host, crate_name| {
+            let formatter = loader.load_formatter(host, crate_name, &self.workdir)?;
+            Ok(
+                Box::new(WasmChangelogFormatter::from_shared(name, formatter)?)
+                    as Box<dyn ChangelogFormatter>,
+            )
+        })
+    }
+
+    fn hook(&self, name: &str) -> UndulerResult<Option<Box<dyn ReleaseHook>>> {
+        self.load("hook", name, |loader, host, crate_name| {
+            let hook = loader.load_hook(host, crate_name, &self.workdir)?;
+            Ok(Box::new(WasmReleaseHook::from_shared(name, hook)?) as Box<dyn ReleaseHook>)
+        })
+    }
+}
+
+/// Creates a WASM engine caching compiled plugins in the plugin storage.
+///
+/// Falls back to an uncached engine when the storage is unavailable.
+fn create_engine() -> Result<WasmEngine> {
+    let engine = match PluginStorage::new() {
+        Ok(storage) => WasmEngine::with_cache_dir(storage.cache_dir()),
+        Err(_) => WasmEngine::new(),
+    };
+    engine.context("failed to create WASM engine")
+}
+
+/// Reads and deserializes a JSON input file.
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))
+}
+
 /// Normalizes a plugin name to its full crate name.
 ///
 /// Accepts:
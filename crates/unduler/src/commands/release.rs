@@ -1,26 +1,27 @@
 //! Release command.
 
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 
-use anyhow::{Context, Result, bail};
-use chrono::Utc;
+use anyhow::{Context, Result};
 use clap::{Args, ValueEnum};
-use semver::Version;
-use tracing::info;
-
-use unduler_bumper_semver::SemverBumper;
-use unduler_commit::{ParsedCommit, RawCommit};
-use unduler_config::{Config, find_and_load_config};
-use unduler_core::update_version_file;
-use unduler_formatter_keepachangelog::KeepAChangelogFormatter;
-use unduler_git::Repository;
-use unduler_parser_conventional::ConventionalParser;
-use unduler_parser_gitmoji::{ConventionalGitmojiParser, GitmojiParserConfig};
-use unduler_parser_regex::{FieldMapping, RegexParser, RegexParserConfig};
-use unduler_plugin::{
-    BumpStrategy, BumpType, ChangelogFormatter, CommitParser, FormatterConfig, Release,
+
+use unduler_config::Config;
+use unduler_core::{
+    CoreError, CoreResult, Pipeline, ReleaseManager, ReleaseOptions, ReleaseOutcome, ReleasePlan,
+    ReleaseProgress, ReleaseStep,
 };
+use unduler_git::{Repository, TagFormat};
+use unduler_plugin::BumpType;
+use unduler_plugin_manager::Lockfile;
+
+use crate::ci::CiProvider;
+use crate::diagnostic::{Diagnostic, Help};
+use crate::github;
+use crate::output::{Output, Summary};
+use crate::prompt;
+use crate::release_state::ReleaseState;
 
 /// Bump type argument.
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -58,6 +59,11 @@ pub struct ReleaseArgs {
     #[arg(long)]
     pub no_changelog: bool,
 
+    /// Write no changelog file, the release notes only going to the tag
+    /// message and the forge release
+    #[arg(long, conflicts_with = "no_changelog")]
+    pub no_file: bool,
+
     /// Skip git tag creation
     #[arg(long)]
     pub no_tag: bool,
@@ -65,307 +71,573 @@ pub struct ReleaseArgs {
     /// Skip git commit
     #[arg(long)]
     pub no_commit: bool,
-}
 
-/// Creates the appropriate parser based on configuration.
-fn create_parser(config: &Config) -> Box<dyn CommitParser> {
-    match config.parser.name.as_str() {
-        "gitmoji" | "conventional-gitmoji" => create_gitmoji_parser(config),
-        "regex" => create_regex_parser(config),
-        _ => Box::new(ConventionalParser::new()),
-    }
-}
+    /// Skip the pre-commit and commit-msg hooks of the release commit,
+    /// enabled by `version.verify`
+    #[arg(long, conflicts_with = "no_commit")]
+    pub no_verify: bool,
 
-fn create_gitmoji_parser(config: &Config) -> Box<dyn CommitParser> {
-    let parser_config = GitmojiParserConfig {
-        infer_type_from_emoji: config.parser.conventional_gitmoji.infer_type_from_emoji,
-        strict_emoji: config.parser.conventional_gitmoji.strict_emoji,
-    };
-    Box::new(ConventionalGitmojiParser::with_config(parser_config))
-}
+    /// Read the annotated tag message from a file instead of rendering
+    /// `version.tag_message`
+    #[arg(long, value_name = "PATH", conflicts_with = "no_tag")]
+    pub tag_message_file: Option<PathBuf>,
 
-fn create_regex_parser(config: &Config) -> Box<dyn CommitParser> {
-    let Some(ref pattern) = config.parser.regex.pattern else {
-        info!("no regex pattern configured, falling back to conventional");
-        return Box::new(ConventionalParser::new());
-    };
+    /// Allow releasing from a branch not listed in `release_branches`
+    #[arg(long)]
+    pub allow_branch: bool,
 
-    let mut metadata_mapping = std::collections::HashMap::new();
-    for (field, capture) in &config.parser.regex.mapping {
-        if !["type", "scope", "message"].contains(&field.as_str()) {
-            metadata_mapping.insert(field.clone(), capture.clone());
-        }
-    }
+    /// Allow releasing with uncommitted changes in the working tree
+    #[arg(long)]
+    pub allow_dirty: bool,
 
-    let mapping = FieldMapping {
-        r#type: config
-            .parser
-            .regex
-            .mapping
-            .get("type")
-            .cloned()
-            .unwrap_or_else(|| "type".to_string()),
-        scope: config.parser.regex.mapping.get("scope").cloned(),
-        message: config
-            .parser
-            .regex
-            .mapping
-            .get("message")
-            .cloned()
-            .unwrap_or_else(|| "message".to_string()),
-        metadata: metadata_mapping,
-    };
+    /// Allow releasing the same version as the latest tag
+    #[arg(long)]
+    pub allow_same_version: bool,
 
-    let parser_config = RegexParserConfig {
-        pattern: pattern.clone(),
-        mapping,
-        validation: config.parser.regex.validation.clone(),
-    };
+    /// Push the release commit and tag (default: from config)
+    #[arg(long)]
+    pub push: bool,
 
-    match RegexParser::new(parser_config) {
-        Ok(parser) => Box::new(parser),
-        Err(e) => {
-            info!("invalid regex pattern, falling back to conventional: {e}");
-            Box::new(ConventionalParser::new())
-        }
-    }
-}
+    /// Resume a release that failed part-way, skipping completed steps
+    #[arg(long, conflicts_with_all = ["bump_type", "dry_run"])]
+    pub resume: bool,
 
-/// Parses raw commits using the given parser.
-fn parse_commits(parser: &dyn CommitParser, raw_commits: &[RawCommit]) -> Vec<ParsedCommit> {
-    raw_commits
-        .iter()
-        .filter_map(|raw| {
-            let parsed = parser.parse(raw);
-            if parsed.is_none() {
-                info!(
-                    hash = %raw.short_hash(),
-                    subject = %raw.subject(),
-                    "skipping unparseable commit"
-                );
-            }
-            parsed
-        })
-        .collect()
-}
+    /// Non-interactive CI mode: no colors, grouped logs, exit code 2 when
+    /// there is nothing to release
+    #[arg(long)]
+    pub ci: bool,
+
+    /// Release without asking for confirmation on a terminal
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Configuration profile to apply, from a `[profile.<name>]` table
+    #[arg(long, env = "UNDULER_PROFILE")]
+    pub profile: Option<String>,
 
-/// Determines the bump type from commits.
-fn determine_bump_type(parsed_commits: &[ParsedCommit]) -> BumpType {
-    let bumper = SemverBumper::new();
-    bumper.determine(parsed_commits)
+    /// Abort unless the installed plugins match the unduler.lock of the
+    /// project
+    #[arg(long)]
+    pub locked: bool,
 }
 
-/// Calculates the next version.
-fn calculate_next_version(current: &Version, bump_type: BumpType) -> Version {
-    match bump_type {
-        BumpType::Major => Version::new(current.major + 1, 0, 0),
-        BumpType::Minor => Version::new(current.major, current.minor + 1, 0),
-        BumpType::Patch | BumpType::None => {
-            Version::new(current.major, current.minor, current.patch + 1)
-        }
+/// Error returned in CI mode when there is nothing to release.
+#[derive(Debug)]
+pub struct NothingToRelease(&'static str);
+
+impl fmt::Display for NothingToRelease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nothing to release: {}", self.0)
     }
 }
 
-/// Updates all version files.
-fn update_version_files(
-    version_files: &[String],
-    new_version: &Version,
-    dry_run: bool,
-) -> Vec<String> {
-    let mut updated = Vec::new();
+impl std::error::Error for NothingToRelease {}
 
-    for file_path in version_files {
-        let path = PathBuf::from(file_path);
+/// Fails with a reason, reported as nothing to release in CI mode.
+fn nothing_to_release(reason: &'static str, ci: bool) -> anyhow::Error {
+    if ci {
+        NothingToRelease(reason).into()
+    } else {
+        anyhow::anyhow!(reason)
+    }
+}
 
-        if dry_run {
-            println!("  Would update {file_path}");
-            updated.push(file_path.clone());
-        } else {
-            match update_version_file(&path, new_version, false) {
-                Ok(()) => {
-                    println!("  Updated {file_path}");
-                    updated.push(file_path.clone());
-                }
-                Err(e) => {
-                    eprintln!("  Failed to update {file_path}: {e}");
-                }
-            }
-        }
+/// Converts a planning error, reporting missing commits as nothing to release.
+fn plan_error(error: CoreError, ci: bool) -> anyhow::Error {
+    match error {
+        CoreError::NoCommits => nothing_to_release("no commits found since last release", ci),
+        CoreError::NoParseableCommits => nothing_to_release("no parseable commits found", ci),
+        error => anyhow::Error::new(error).context("failed to compute the next version"),
     }
+}
 
-    updated
+/// Converts a version check error, hinting at `--allow-same-version` when
+/// releasing the latest version again.
+fn version_error(error: CoreError, tag_format: &TagFormat) -> anyhow::Error {
+    if let CoreError::VersionNotGreater {
+        version,
+        latest_tag,
+    } = &error
+        && tag_format.parse(latest_tag).as_ref() == Some(version)
+    {
+        return Diagnostic::from_error(error)
+            .with_help("use --allow-same-version to release anyway")
+            .into();
+    }
+    error.into()
 }
 
-/// Writes changelog to file.
-fn write_changelog(
-    changelog: &str,
-    output_path: &PathBuf,
-    version: &Version,
-    dry_run: bool,
-) -> Result<()> {
-    if dry_run {
-        println!("  Would update {}", output_path.display());
+/// Aborts if the working tree has uncommitted changes.
+///
+/// The release commit stages every change, so uncommitted files would end
+/// up in it, unless `version.commit_release_files` restricts it to the
+/// files changed by the release, in which case they would be missing from
+/// the tagged release. Dry runs only warn since they change nothing.
+fn check_clean_working_tree(repo: &Repository, args: &ReleaseArgs) -> Result<()> {
+    if args.allow_dirty {
         return Ok(());
     }
 
-    let existing = fs::read_to_string(output_path).unwrap_or_default();
+    let status = repo
+        .status()
+        .context("failed to read working tree status")?;
+    if status.is_empty() {
+        return Ok(());
+    }
 
-    let new_content = if existing.is_empty() {
-        format!(
-            "# Changelog\n\n\
-             All notable changes to this project will be documented in this file.\n\n\
-             The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
-             and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n\
-             {changelog}"
-        )
-    } else if let Some(pos) = existing.find("\n## ") {
-        let (header, rest) = existing.split_at(pos + 1);
-        format!("{header}{changelog}{rest}")
-    } else {
-        format!("{existing}\n{changelog}")
-    };
+    let files = status
+        .iter()
+        .map(|file| format!("  {file}"))
+        .collect::<Vec<_>>()
+        .join("\n");
 
-    fs::write(output_path, new_content)
-        .with_context(|| format!("failed to write changelog to {}", output_path.display()))?;
+    if args.dry_run {
+        eprintln!("Warning: working tree has uncommitted changes:\n{files}\n");
+        return Ok(());
+    }
 
-    println!("  Updated {} for version {version}", output_path.display());
-    Ok(())
+    Err(
+        Diagnostic::new(format!("working tree has uncommitted changes:\n{files}"))
+            .with_help("commit or stash them, or use --allow-dirty to release anyway")
+            .into(),
+    )
 }
 
-/// Runs the release command.
-#[allow(clippy::needless_pass_by_value)]
-pub fn run(args: ReleaseArgs) -> Result<()> {
-    let config = find_and_load_config().context("failed to load configuration")?;
-    let repo = Repository::discover().context("failed to open git repository")?;
-    let tag_prefix = &config.version.tag_prefix;
+/// Aborts if the installed plugins the release loaded do not match the
+/// lockfile, so CI releases with the same plugins as developers.
+fn check_locked_plugins(loaded: &[String]) -> Result<()> {
+    let path = super::lockfile_path()?;
+    if !path.exists() {
+        return Err(
+            Diagnostic::new(format!("no lockfile at {}", path.display()))
+                .with_help("run `unduler plugin lock` and commit the lockfile")
+                .into(),
+        );
+    }
+    let lockfile = Lockfile::load(&path)?;
 
-    println!("Starting release process...\n");
+    let mismatches = lockfile
+        .verify_loaded(
+            &super::plugin::open_loader()?,
+            loaded.iter().map(String::as_str),
+        )
+        .context("failed to check installed plugins")?;
+    if mismatches.is_empty() {
+        return Ok(());
+    }
 
-    // Step 1: Get latest version tag
-    let latest_tag = repo
-        .latest_version_tag(tag_prefix)
-        .context("failed to get latest version tag")?;
+    let plugins = mismatches
+        .iter()
+        .map(|mismatch| format!("  {mismatch}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(Diagnostic::new(format!(
+        "installed plugins do not match {}:\n{plugins}",
+        path.display()
+    ))
+    .with_help("run `unduler plugin sync` to install the locked plugins")
+    .into())
+}
 
-    info!(tag = ?latest_tag, "found latest version tag");
+/// Answer to the release confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    /// Proceed with the release
+    Yes,
+    /// Abort the release
+    No,
+    /// Edit the changelog section, then ask again
+    Edit,
+}
 
-    // Step 2: Determine bump type
-    let bump_type = if let Some(forced) = args.bump_type {
-        info!(bump_type = ?forced, "using forced bump type");
-        forced.into()
-    } else {
-        let raw_commits = repo
-            .commits_since(latest_tag.as_deref())
-            .context("failed to get commits")?;
+/// Parses an answer to the release confirmation, defaulting to no.
+fn parse_answer(answer: &str) -> Option<Answer> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Some(Answer::Yes),
+        "" | "n" | "no" => Some(Answer::No),
+        "e" | "edit" => Some(Answer::Edit),
+        _ => None,
+    }
+}
 
-        if raw_commits.is_empty() {
-            bail!("no commits found since last release");
+/// Shows the version, files and changelog section of a release, then asks
+/// for confirmation, letting the user edit the section in their editor.
+///
+/// Returns the section to write, or fails if the user aborts.
+fn confirm_release(
+    manager: &ReleaseManager,
+    plan: &ReleasePlan,
+    mut section: Option<String>,
+    extension: &str,
+) -> Result<Option<String>> {
+    loop {
+        let files = manager
+            .planned_files(&plan.new_version, section.as_deref())
+            .context("failed to preview the changelog")?;
+        println!("Files to update:");
+        for file in &files {
+            println!("  {file}");
+        }
+        if let Some(section) = &section {
+            println!("\nChangelog:");
+            println!("{}", super::indent(section.trim_end(), "  "));
         }
 
-        info!(count = raw_commits.len(), "found commits to analyze");
+        let question = if section.is_some() {
+            format!(
+                "\nRelease {}? [y]es, [N]o, [e]dit changelog:",
+                plan.new_version
+            )
+        } else {
+            format!("\nRelease {}? [y]es, [N]o:", plan.new_version)
+        };
+        match parse_answer(&prompt::ask(&question)?) {
+            Some(Answer::Yes) => return Ok(section),
+            Some(Answer::No) => anyhow::bail!("release aborted, no changes were made"),
+            Some(Answer::Edit) if section.is_some() => {
+                section = section
+                    .as_deref()
+                    .map(|section| prompt::edit(section, extension))
+                    .transpose()?;
+                println!();
+            }
+            _ => println!("Please answer y or n, or e to edit the changelog.\n"),
+        }
+    }
+}
 
-        let parser = create_parser(&config);
-        let parsed_commits = parse_commits(parser.as_ref(), &raw_commits);
+/// Prints the steps of a release and saves its state after each one, so a
+/// failed release can be resumed.
+struct Reporter {
+    output: Output,
+    state: ReleaseState,
+    root: PathBuf,
+    remote: String,
+    fragments: Vec<PathBuf>,
+    dry_run: bool,
+    /// Number of changes of the outcome already printed.
+    printed: usize,
+    /// Step running, if any, to report its failure.
+    running: Option<ReleaseStep>,
+}
 
-        if parsed_commits.is_empty() {
-            bail!("no parseable commits found");
+impl Reporter {
+    /// Prints the files changed by the last step.
+    fn print_changes(&mut self, outcome: &ReleaseOutcome) {
+        for change in &outcome.changes[self.printed..] {
+            if self.dry_run {
+                self.output
+                    .text(format!("  Would update {}", change.path.display()));
+                super::print_diff(self.output, change);
+            } else {
+                self.output
+                    .text(format!("  Updated {}", change.path.display()));
+            }
         }
+        self.printed = outcome.changes.len();
+    }
 
-        let determined = determine_bump_type(&parsed_commits);
-        info!(bump_type = %determined, "determined bump type from commits");
-        determined
-    };
-
-    // Step 3: Calculate versions
-    let current_version = latest_tag
-        .as_ref()
-        .and_then(|tag| tag.strip_prefix(tag_prefix))
-        .and_then(|v| Version::parse(v).ok());
+    /// Prints the result of a step.
+    fn print_step(&mut self, step: ReleaseStep, outcome: &ReleaseOutcome) {
+        let output = self.output;
+        match step {
+            ReleaseStep::VersionFiles => self.print_changes(outcome),
+            ReleaseStep::Changelog => {
+                self.print_changes(outcome);
+                for fragment in &self.fragments {
+                    if self.dry_run {
+                        output.text(format!("  Would remove {}", fragment.display()));
+                    } else {
+                        output.text(format!("  Removed {}", fragment.display()));
+                    }
+                }
+            }
+            ReleaseStep::Commit => {
+                let message = outcome.commit.as_deref().unwrap_or_default();
+                if self.dry_run {
+                    output.text("  Would create commit with message:");
+                    output.text(super::indent(message, "    "));
+                } else {
+                    output.text(format!("  Created commit: {message}"));
+                }
+            }
+            ReleaseStep::Tag => {
+                let tag = outcome.tag.as_deref().unwrap_or_default();
+                match &outcome.tag_message {
+                    Some(message) if self.dry_run => {
+                        output.text(format!("  Would create tag {tag} with message:"));
+                        output.text(super::indent(message, "    "));
+                    }
+                    None if self.dry_run => {
+                        output.text(format!("  Would create lightweight tag: {tag}"));
+                    }
+                    _ => output.text(format!("  Created tag: {tag}")),
+                }
+            }
+            ReleaseStep::Push => {
+                for refspec in &outcome.refspecs {
+                    if self.dry_run {
+                        output.text(format!("  Would push {refspec} to {}", self.remote));
+                    } else {
+                        output.text(format!("  Pushed {refspec} to {}", self.remote));
+                    }
+                }
+            }
+        }
+    }
+}
 
-    let (current_version, new_version) = if let Some(current) = current_version {
-        let new = calculate_next_version(&current, bump_type);
-        (current, new)
-    } else {
-        // No tag: first release is 0.1.0
-        (Version::new(0, 0, 0), Version::new(0, 1, 0))
-    };
+impl ReleaseProgress for Reporter {
+    fn is_done(&self, step: ReleaseStep) -> bool {
+        self.state.is_done(step)
+    }
 
-    println!("Version: {current_version} -> {new_version} ({bump_type})\n");
+    fn started(&mut self, step: ReleaseStep) {
+        self.running = Some(step);
+        let (name, title) = step_group(step);
+        self.output.group_start(name, title);
+    }
 
-    // Step 4: Update version files
-    let version_files = &config.version.files;
-    if !version_files.is_empty() {
-        println!("Updating version files:");
-        let updated = update_version_files(version_files, &new_version, args.dry_run);
-        if updated.is_empty() && !args.dry_run {
-            eprintln!("Warning: no version files were updated");
+    fn completed(&mut self, step: ReleaseStep, outcome: &ReleaseOutcome) -> CoreResult<()> {
+        self.running = None;
+        self.print_step(step, outcome);
+        if !self.dry_run {
+            self.state.complete(step);
+            self.state
+                .save(&self.root)
+                .map_err(|error| CoreError::Progress(error.into()))?;
         }
-        println!();
+        self.output.group_end(step_group(step).0);
+        Ok(())
     }
+}
 
-    // Step 5: Generate and write changelog
-    if !args.no_changelog {
-        println!("Generating changelog:");
+/// Returns the output group name and title of a release step.
+fn step_group(step: ReleaseStep) -> (&'static str, &'static str) {
+    match step {
+        ReleaseStep::VersionFiles => ("version_files", "Updating version files"),
+        ReleaseStep::Changelog => ("changelog", "Generating changelog"),
+        ReleaseStep::Commit => ("commit", "Creating git commit"),
+        ReleaseStep::Tag => ("tag", "Creating git tag"),
+        ReleaseStep::Push => ("push", "Pushing release"),
+    }
+}
 
-        // Re-parse commits for changelog generation
-        let raw_commits = repo
-            .commits_since(latest_tag.as_deref())
-            .context("failed to get commits")?;
+/// Returns what a release step does, to report its failure.
+fn step_action(step: ReleaseStep) -> &'static str {
+    match step {
+        ReleaseStep::VersionFiles => "update version files",
+        ReleaseStep::Changelog => "write changelog",
+        ReleaseStep::Commit => "create commit",
+        ReleaseStep::Tag => "create tag",
+        ReleaseStep::Push => "push release",
+    }
+}
 
-        let parser = create_parser(&config);
-        let parsed_commits = parse_commits(parser.as_ref(), &raw_commits);
+/// Creates the release options from the arguments and configuration,
+/// reading the tag message file.
+fn release_options(args: &ReleaseArgs, config: &Config) -> Result<ReleaseOptions> {
+    let tag_message = args
+        .tag_message_file
+        .as_deref()
+        .map(|path| {
+            fs::read_to_string(path)
+                .with_context(|| format!("failed to read tag message from {}", path.display()))
+        })
+        .transpose()?;
+
+    Ok(ReleaseOptions::new()
+        .with_dry_run(args.dry_run)
+        .with_bump_type(args.bump_type.map(Into::into))
+        .with_changelog(!args.no_changelog)
+        .with_commit(!args.no_commit)
+        .with_tag(!args.no_tag)
+        .with_push(args.push || config.version.push)
+        .with_allow_branch(args.allow_branch)
+        .with_allow_same_version(args.allow_same_version)
+        .with_no_verify(args.no_verify)
+        .with_tag_message(tag_message))
+}
 
-        let mut release = Release::new(new_version.clone(), Utc::now(), parsed_commits);
-        if current_version != Version::new(0, 0, 0) {
-            release = release.with_previous_version(current_version.clone());
+/// Plans the release to run, or the failed release to resume, with its
+/// state.
+fn start_release(
+    args: &ReleaseArgs,
+    manager: &ReleaseManager,
+    pipeline: &Pipeline,
+    options: &ReleaseOptions,
+    output: Output,
+) -> Result<(ReleaseState, ReleasePlan)> {
+    let root = manager.repository().path();
+    let saved = ReleaseState::load(root)?;
+
+    if args.resume {
+        let state = saved.context("no failed release to resume")?;
+        output.text(format!("Resuming release {}...\n", state.new_version));
+        let plan = manager
+            .plan_version(
+                pipeline,
+                state.previous_version.clone(),
+                state.new_version.clone(),
+                state.bump_type,
+                state.commits.clone(),
+            )
+            .context("failed to get commits")?;
+        Ok((state, plan))
+    } else {
+        if let Some(saved) = saved
+            && !args.dry_run
+        {
+            return Err(
+                Diagnostic::new(format!("release {} did not complete", saved.new_version))
+                    .with_help(format!(
+                        "use --resume to continue it or remove {} to start over",
+                        ReleaseState::path(root).display()
+                    ))
+                    .into(),
+            );
         }
-
-        let formatter = KeepAChangelogFormatter::new();
-        let changelog = formatter.format(&release, &FormatterConfig::default());
-
-        let output_path = PathBuf::from(&config.changelog.output);
-        write_changelog(&changelog, &output_path, &new_version, args.dry_run)?;
-        println!();
+        check_clean_working_tree(manager.repository(), args)?;
+        output.text("Starting release process...\n");
+
+        let plan = manager
+            .plan(pipeline, options.bump_type())
+            .map_err(|e| plan_error(e, args.ci))?;
+        manager
+            .check_version(&plan.new_version, options)
+            .map_err(|e| version_error(e, manager.tag_format()))?;
+
+        let state = ReleaseState::new(
+            plan.previous_version.clone(),
+            plan.new_version.clone(),
+            plan.bump_type,
+        )
+        .with_commits(plan.commit_range());
+        Ok((state, plan))
     }
+}
 
-    // Step 6: Create git commit
-    if !args.no_commit {
-        println!("Creating git commit:");
-        let commit_message = format!("chore(release): {new_version}");
+/// Runs the release command.
+pub fn run(args: &ReleaseArgs, output: Output) -> Result<()> {
+    let output = if args.ci {
+        output.with_ci(CiProvider::detect())
+    } else {
+        output
+    };
 
-        if args.dry_run {
-            println!("  Would create commit: {commit_message}");
-        } else {
-            repo.commit(&commit_message)
-                .context("failed to create commit")?;
-            println!("  Created commit: {commit_message}");
-        }
-        println!();
+    let config = super::load_config(args.profile.as_deref())?;
+    let repo = Repository::discover().context("failed to open git repository")?;
+    let mut manager = ReleaseManager::from_config(repo, &config).with_read_only_cache(args.dry_run);
+    if args.no_file {
+        manager = manager.with_changelog_file(false);
+    }
+    let (pipeline, loaded) = super::create_pipeline_loading(&config)?;
+    if args.locked {
+        check_locked_plugins(&loaded)?;
     }
 
-    // Step 7: Create git tag
-    if !args.no_tag {
-        println!("Creating git tag:");
-        let tag_name = format!("{tag_prefix}{new_version}");
-        let tag_message = format!("Release {new_version}");
+    let options = release_options(args, &config)?;
 
-        if args.dry_run {
-            println!("  Would create tag: {tag_name}");
-        } else {
-            repo.create_tag(&tag_name, &tag_message)
-                .context("failed to create tag")?;
-            println!("  Created tag: {tag_name}");
-        }
+    if !options.allow_branch() {
+        manager
+            .check_branch()
+            .help("use --allow-branch to release anyway")?;
+    }
+    manager.check_identity(&options)?;
+
+    let root = manager.repository().path().to_path_buf();
+    let (state, mut plan) = start_release(args, &manager, &pipeline, &options, output)?;
+    if options.changelog() && !state.is_done(ReleaseStep::Changelog) {
+        github::resolve_author_logins(
+            &config,
+            manager.repository(),
+            manager.repository_url(),
+            &mut plan.commits,
+        );
+    }
+    output.text(format!(
+        "Version: {} -> {} ({})\n",
+        plan.previous_version, plan.new_version, plan.bump_type
+    ));
+
+    // Ask for confirmation on a terminal, letting the user edit the
+    // changelog section before anything is written
+    let mut section = None;
+    let interactive =
+        !args.yes && !args.ci && !args.dry_run && !output.is_json() && prompt::is_interactive();
+    if interactive {
+        let extension = match pipeline.formatter().extension() {
+            "" => "txt",
+            extension => extension,
+        };
+        let formatted = (options.changelog() && !state.is_done(ReleaseStep::Changelog))
+            .then(|| manager.format_changelog(&pipeline, &plan));
+        section = confirm_release(&manager, &plan, formatted, extension)?;
         println!();
     }
 
+    let remote = manager.remote().to_string();
+    let mut reporter = Reporter {
+        output,
+        state,
+        root: root.clone(),
+        remote: remote.clone(),
+        fragments: plan.fragments.clone(),
+        dry_run: args.dry_run,
+        printed: 0,
+        running: None,
+    };
+    let options = options.with_changelog_section(section);
+    let outcome = manager
+        .release_plan(&pipeline, plan, &options, &mut reporter)
+        .map_err(|e| match reporter.running {
+            Some(step) => anyhow::Error::new(e).context(format!("failed to {}", step_action(step))),
+            None => e.into(),
+        })?;
+    let new_version = &outcome.new_version;
+
+    let summary = Summary {
+        previous_version: Some(outcome.previous_version.to_string()),
+        new_version: Some(new_version.to_string()),
+        bump_type: Some(outcome.bump_type.to_string()),
+        files_updated: outcome.files_updated.clone(),
+        changelog: outcome
+            .changelog
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        commit: outcome.commit.clone(),
+        tag: outcome.tag.clone(),
+        pushed: outcome.pushed,
+        dry_run: args.dry_run,
+    };
+
     // Summary
     if args.dry_run {
-        println!("Dry run completed. No changes were made.");
+        output.text("Dry run completed. No changes were made.");
     } else {
-        println!("Release {new_version} completed successfully!");
-        println!("\nNext steps:");
-        println!("  git push origin main --tags");
+        ReleaseState::clear(&root)?;
+        output.text(format!("Release {new_version} completed successfully!"));
+        if !options.push() {
+            output.text("\nNext steps:");
+            output.text(format!("  git push {remote} --follow-tags"));
+        }
     }
 
-    Ok(())
+    output.summary(&summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("y"), Some(Answer::Yes));
+        assert_eq!(parse_answer("YES"), Some(Answer::Yes));
+        assert_eq!(parse_answer(""), Some(Answer::No));
+        assert_eq!(parse_answer("n"), Some(Answer::No));
+        assert_eq!(parse_answer("e"), Some(Answer::Edit));
+        assert_eq!(parse_answer("edit"), Some(Answer::Edit));
+        assert_eq!(parse_answer("maybe"), None);
+    }
 }
@@ -0,0 +1,237 @@
+//! Self-update command.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use semver::Version;
+use sha2::{Digest, Sha256};
+use unduler_plugin_manager::{HttpOptions, PluginDiscovery};
+
+use crate::diagnostic::Diagnostic;
+use crate::output::{Output, Summary};
+
+/// Owner of the repository publishing the unduler releases.
+const OWNER: &str = "jdevelop-io";
+
+/// Repository publishing the unduler releases.
+const REPO: &str = "unduler";
+
+/// Release asset listing the SHA-256 checksums of the other assets.
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Arguments for the self-update command.
+#[derive(Debug, Args)]
+pub struct SelfUpdateArgs {
+    /// Only report whether a newer version is available
+    #[arg(long)]
+    pub check: bool,
+}
+
+/// Returns the target of the running binary, if unduler releases it.
+fn target() -> Option<&'static str> {
+    if cfg!(all(
+        target_arch = "x86_64",
+        target_os = "linux",
+        target_env = "gnu"
+    )) {
+        Some("x86_64-unknown-linux-gnu")
+    } else if cfg!(all(
+        target_arch = "x86_64",
+        target_os = "linux",
+        target_env = "musl"
+    )) {
+        Some("x86_64-unknown-linux-musl")
+    } else if cfg!(all(
+        target_arch = "aarch64",
+        target_os = "linux",
+        target_env = "gnu"
+    )) {
+        Some("aarch64-unknown-linux-gnu")
+    } else if cfg!(all(target_arch = "x86_64", target_os = "macos")) {
+        Some("x86_64-apple-darwin")
+    } else if cfg!(all(target_arch = "aarch64", target_os = "macos")) {
+        Some("aarch64-apple-darwin")
+    } else if cfg!(all(target_arch = "x86_64", target_os = "windows")) {
+        Some("x86_64-pc-windows-msvc")
+    } else {
+        None
+    }
+}
+
+/// Returns the name of the binary asset of a target.
+fn asset_name(target: &str) -> String {
+    if target.contains("windows") {
+        format!("unduler-{target}.exe")
+    } else {
+        format!("unduler-{target}")
+    }
+}
+
+/// Returns the version of a release tag, e.g. `v1.2.0`.
+fn release_version(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/// Returns the checksum of an asset from a `sha256sum` listing.
+fn checksum<'a>(checksums: &'a str, asset: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (hash, name) = line.split_once(char::is_whitespace)?;
+        // Binary mode prefixes the file name with `*`
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset).then_some(hash)
+    })
+}
+
+/// Replaces the running executable, returning its path.
+///
+/// The new binary is written next to it, then renamed over it. On Windows,
+/// the running executable cannot be overwritten but can be renamed, so it
+/// is moved aside first.
+fn replace_executable(bytes: &[u8]) -> Result<PathBuf> {
+    let exe = std::env::current_exe().context("failed to locate the unduler executable")?;
+    let exe = fs::canonicalize(&exe).unwrap_or(exe);
+    let new = exe.with_extension("new");
+    fs::write(&new, bytes).with_context(|| format!("failed to write {}", new.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("failed to make {} executable", new.display()))?;
+    }
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(&exe, &old).with_context(|| format!("failed to move {}", exe.display()))?;
+    }
+
+    fs::rename(&new, &exe).with_context(|| format!("failed to replace {}", exe.display()))?;
+    Ok(exe)
+}
+
+/// Runs the self-update command.
+pub fn run(args: &SelfUpdateArgs, output: Output) -> Result<()> {
+    let rt = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+
+    rt.block_on(async { run_async(args, output).await })
+}
+
+async fn run_async(args: &SelfUpdateArgs, output: Output) -> Result<()> {
+    let current = Version::parse(env!("CARGO_PKG_VERSION")).context("invalid unduler version")?;
+    let discovery =
+        PluginDiscovery::with_http(HttpOptions::from_env()).context("invalid HTTP settings")?;
+
+    let release = discovery
+        .github_release(REPO, OWNER, REPO, None)
+        .await
+        .context("failed to fetch the latest unduler release")?;
+    let latest = release_version(&release.tag_name)
+        .with_context(|| format!("invalid release tag {}", release.tag_name))?;
+
+    let mut summary = Summary {
+        previous_version: Some(current.to_string()),
+        new_version: Some(current.to_string()),
+        dry_run: args.check,
+        ..Summary::default()
+    };
+
+    if latest <= current {
+        output.text(format!("unduler {current} is up to date"));
+        return output.summary(&summary);
+    }
+    summary.new_version = Some(latest.to_string());
+    if args.check {
+        output.text(format!(
+            "unduler {latest} is available (current: {current}), run `unduler self-update`"
+        ));
+        return output.summary(&summary);
+    }
+
+    let target = target().ok_or_else(|| {
+        Diagnostic::new("no unduler release for this platform")
+            .with_help("install it from source with `cargo install unduler`")
+    })?;
+    let asset_name = asset_name(target);
+    let (Some(asset), Some(checksums)) =
+        (release.asset(&asset_name), release.asset(CHECKSUMS_ASSET))
+    else {
+        return Err(Diagnostic::new(format!(
+            "release {} has no {asset_name} binary with checksum",
+            release.tag_name
+        ))
+        .with_help("install it from source with `cargo install unduler`")
+        .into());
+    };
+
+    output.text(format!("Downloading unduler {latest} ({target})..."));
+    let bytes = discovery
+        .fetch(REPO, &asset.browser_download_url)
+        .await
+        .context("failed to download unduler")?;
+    let checksums = discovery
+        .fetch(REPO, &checksums.browser_download_url)
+        .await
+        .context("failed to download the release checksums")?;
+    let checksums = String::from_utf8_lossy(&checksums);
+
+    let expected = checksum(&checksums, &asset_name)
+        .with_context(|| format!("no checksum for {asset_name} in {CHECKSUMS_ASSET}"))?;
+    let actual = format!("{:x}", Sha256::digest(&bytes));
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(Diagnostic::new(format!(
+            "checksum mismatch for {asset_name}: expected {expected}, got {actual}"
+        ))
+        .with_help("the download may be corrupted, retry later")
+        .into());
+    }
+
+    let path = replace_executable(&bytes)?;
+    output.text(format!(
+        "Updated unduler {current} -> {latest} ({})",
+        path.display()
+    ));
+
+    output.summary(&summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_name() {
+        assert_eq!(
+            asset_name("x86_64-unknown-linux-gnu"),
+            "unduler-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            asset_name("x86_64-pc-windows-msvc"),
+            "unduler-x86_64-pc-windows-msvc.exe"
+        );
+    }
+
+    #[test]
+    fn test_release_version() {
+        assert_eq!(release_version("v1.2.0"), Some(Version::new(1, 2, 0)));
+        assert_eq!(release_version("1.2.0"), Some(Version::new(1, 2, 0)));
+        assert_eq!(release_version("nightly"), None);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let checksums = "abc123  unduler-x86_64-unknown-linux-gnu\n\
+                         def456 *unduler-x86_64-pc-windows-msvc.exe\n";
+        assert_eq!(
+            checksum(checksums, "unduler-x86_64-unknown-linux-gnu"),
+            Some("abc123")
+        );
+        assert_eq!(
+            checksum(checksums, "unduler-x86_64-pc-windows-msvc.exe"),
+            Some("def456")
+        );
+        assert_eq!(checksum(checksums, "unduler-aarch64-apple-darwin"), None);
+    }
+}
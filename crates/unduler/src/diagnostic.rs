@@ -0,0 +1,350 @@
+//! Error reports with source snippets and hints.
+
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use unduler_config::ConfigError;
+use unduler_core::CoreError;
+use unduler_git::GitError;
+use unduler_plugin_manager::PluginManagerError;
+use unduler_wasm_runtime::WasmError;
+
+/// An error annotated with a location in a file and a hint to fix it.
+#[derive(Debug)]
+pub struct Diagnostic {
+    message: String,
+    error: Option<Box<dyn Error + Send + Sync>>,
+    label: Option<Box<Label>>,
+    help: Option<String>,
+}
+
+/// A span of a file the diagnostic points at.
+#[derive(Debug, Clone)]
+struct Label {
+    path: PathBuf,
+    content: String,
+    span: Range<usize>,
+    text: String,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic with a message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            error: None,
+            label: None,
+            help: None,
+        }
+    }
+
+    /// Annotates an error, which is displayed as is.
+    pub fn from_error(error: impl Error + Send + Sync + 'static) -> Self {
+        Self {
+            message: String::new(),
+            error: Some(Box::new(error)),
+            label: None,
+            help: None,
+        }
+    }
+
+    /// Points at a span of a file, described by a text.
+    #[must_use]
+    pub fn with_label(
+        mut self,
+        path: &Path,
+        content: &str,
+        span: Range<usize>,
+        text: impl Into<String>,
+    ) -> Self {
+        self.label = Some(Box::new(Label {
+            path: path.to_path_buf(),
+            content: content.to_string(),
+            span,
+            text: text.into(),
+        }));
+        self
+    }
+
+    /// Sets a hint to fix the error.
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.error {
+            Some(error) => fmt::Display::fmt(error, f),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.as_ref().and_then(|error| error.source())
+    }
+}
+
+/// Adds a hint to the error of a result.
+pub trait Help<T> {
+    /// Annotates the error with a hint to fix it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the annotated error.
+    fn help(self, help: impl Into<String>) -> Result<T, Diagnostic>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> Help<T> for Result<T, E> {
+    fn help(self, help: impl Into<String>) -> Result<T, Diagnostic> {
+        self.map_err(|error| Diagnostic::from_error(error).with_help(help))
+    }
+}
+
+/// Renders an error with its causes, the span it points at and a hint.
+pub fn report(error: &anyhow::Error) -> String {
+    let mut out = format!("Error: {error}\n");
+    let mut label = None;
+    let mut help = None;
+
+    for (depth, cause) in error.chain().enumerate() {
+        if depth > 0 {
+            let _ = writeln!(out, "  caused by: {cause}");
+        }
+
+        let (cause_label, cause_help) = annotate(cause);
+        help = help.or(cause_help);
+        if cause_label.is_some() {
+            // The label describes the remaining causes
+            label = cause_label;
+            break;
+        }
+    }
+
+    if let Some(label) = label {
+        out.push_str(&render_label(&label));
+    }
+    if let Some(help) = help {
+        let _ = writeln!(out, "  = help: {help}");
+    }
+    out
+}
+
+/// Returns the label and hint of a known error.
+fn annotate(error: &(dyn Error + 'static)) -> (Option<Label>, Option<String>) {
+    if let Some(diagnostic) = error.downcast_ref::<Diagnostic>() {
+        return (
+            diagnostic.label.as_deref().cloned(),
+            diagnostic.help.clone(),
+        );
+    }
+
+    if let Some(error) = error.downcast_ref::<ConfigError>() {
+        return match error {
+            ConfigError::NotFound(_) => (
+                None,
+                Some("run `unduler init` to create a configuration".to_string()),
+            ),
+            ConfigError::ProfileNotFound(profile) => (
+                None,
+                Some(format!(
+                    "define it in a [profile.{profile}] table of unduler.toml"
+                )),
+            ),
+            ConfigError::Syntax {
+                path,
+                content,
+                source,
+            } => {
+                let label = source.span().map(|span| Label {
+                    path: path.clone(),
+                    content: content.clone(),
+                    span,
+                    text: source.message().trim().to_string(),
+                });
+                (label, None)
+            }
+            _ => (None, None),
+        };
+    }
+
+    let help = if let Some(error) = error.downcast_ref::<GitError>() {
+        git_help(error)
+    } else if let Some(error) = error.downcast_ref::<CoreError>() {
+        match error {
+            CoreError::TagExists(tag) => Some(format!(
+                "delete the tag with `git tag -d {tag}` if it was created by mistake"
+            )),
+            CoreError::DetachedHead => {
+                Some("check out a branch with `git switch <branch>`".to_string())
+            }
+            _ => None,
+        }
+    } else if let Some(error) = error.downcast_ref::<PluginManagerError>() {
+        match error {
+            PluginManagerError::PluginNotFound { name } => {
+                Some(format!("install it with `unduler plugin install {name}`"))
+            }
+            PluginManagerError::CrateNotFound { .. } => {
+                Some("search for plugins with `unduler plugin search <query>`".to_string())
+            }
+            _ => None,
+        }
+    } else if let Some(error) = error.downcast_ref::<WasmError>() {
+        match error {
+            WasmError::ComponentLoad { .. } => Some(
+                "plugins must be WebAssembly components, convert a module with \
+                 `wasm-tools component new <module.wasm> -o <plugin.wasm>`"
+                    .to_string(),
+            ),
+            WasmError::Timeout { .. } => {
+                Some("raise `wasm.timeout` in unduler.toml, or set it to 0".to_string())
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+    (None, help)
+}
+
+/// Returns a command fixing a git error.
+fn git_help(error: &GitError) -> Option<String> {
+    match error {
+        GitError::RepoNotFound(_) | GitError::NotARepo(_) => {
+            Some("run unduler inside a git repository, or create one with `git init`".to_string())
+        }
+        GitError::TagExists(tag) => Some(format!(
+            "delete the tag with `git tag -d {tag}` if it was created by mistake"
+        )),
+        GitError::RemoteNotFound(remote) => Some(format!(
+            "add the remote with `git remote add {remote} <url>`"
+        )),
+        GitError::PushRejected { .. } => {
+            Some("integrate the remote changes with `git pull --rebase`, then push".to_string())
+        }
+        GitError::NoCommits => Some("create a first commit with `git commit`".to_string()),
+        GitError::MissingIdentity => Some(
+            "set it with `git config user.name` and `git config user.email`, or with \
+             version.commit_author"
+                .to_string(),
+        ),
+        GitError::HookFailed { .. } => Some(
+            "fix the reported problem, or skip the hooks with `unduler release --no-verify`"
+                .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+/// Renders the line of a label with the span underlined.
+fn render_label(label: &Label) -> String {
+    let content = &label.content;
+    let start = label.span.start.min(content.len());
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |i| start + i);
+    let line = content[line_start..line_end].trim_end_matches('\r');
+    let end = label.span.end.clamp(start, line_start + line.len());
+
+    let line_number = content[..start].matches('\n').count() + 1;
+    let column = content[line_start..start].chars().count() + 1;
+    let width = content[start..end].chars().count().max(1);
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|dir| label.path.strip_prefix(dir).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| label.path.clone());
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{gutter}--> {}:{line_number}:{column}", path.display());
+    let _ = writeln!(out, "{gutter} |");
+    let _ = writeln!(out, "{line_number} | {line}");
+    let _ = writeln!(
+        out,
+        "{gutter} | {}{} {}",
+        " ".repeat(column - 1),
+        "^".repeat(width),
+        label.text
+    );
+    let _ = writeln!(out, "{gutter} |");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[parser]\nname = \"regex\"\n\n[parser.regex]\npattern = \"(?P<type\"\n";
+
+    fn label(span: Range<usize>) -> Label {
+        Label {
+            path: PathBuf::from("unduler.toml"),
+            content: CONFIG.to_string(),
+            span,
+            text: "unclosed group".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_label() {
+        let start = CONFIG.find("\"(?P").unwrap();
+        let rendered = render_label(&label(start..start + 10));
+
+        assert_eq!(
+            rendered,
+            " --> unduler.toml:5:11\n  |\n5 | pattern = \"(?P<type\"\n  |           ^^^^^^^^^^ unclosed group\n  |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_empty_span() {
+        let rendered = render_label(&label(CONFIG.len()..CONFIG.len()));
+        assert!(rendered.contains("^ unclosed group"), "{rendered}");
+    }
+
+    #[test]
+    fn test_report_help() {
+        let error = anyhow::Error::new(
+            Err::<(), _>(CoreError::DetachedHead)
+                .help("set version.branch")
+                .unwrap_err(),
+        )
+        .context("failed to release");
+
+        let report = report(&error);
+        assert!(report.starts_with("Error: failed to release\n"), "{report}");
+        assert!(report.contains("caused by: cannot determine"), "{report}");
+        assert!(report.contains("= help: set version.branch"), "{report}");
+    }
+
+    #[test]
+    fn test_report_known_error() {
+        let error = anyhow::Error::new(GitError::RemoteNotFound("origin".to_string()));
+        let report = report(&error);
+        assert!(
+            report.contains("= help: add the remote with `git remote add origin <url>`"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn test_report_config_syntax() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("unduler.toml");
+        std::fs::write(&path, "[parser]\nname = \n").unwrap();
+        let error = anyhow::Error::new(unduler_config::load_config(&path).unwrap_err());
+
+        let report = report(&error);
+        assert!(report.contains("unduler.toml:2:"), "{report}");
+        assert!(report.contains("2 | name = "), "{report}");
+    }
+}
@@ -0,0 +1,340 @@
+//! GitHub login lookups for changelog authors.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use reqwest::blocking::Client;
+use reqwest::header::ACCEPT;
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use unduler_commit::ParsedCommit;
+use unduler_config::Config;
+use unduler_git::Repository;
+use unduler_plugin_manager::PluginStorage;
+
+/// GitHub REST API base URL.
+const API_URL: &str = "https://api.github.com";
+
+/// Cache file of the resolved logins, in the unduler cache directory.
+const CACHE_FILE: &str = "github-authors.json";
+
+/// Email domain of the private addresses GitHub gives its users.
+const NOREPLY_DOMAIN: &str = "users.noreply.github.com";
+
+/// Environment variables holding a GitHub token, in lookup order.
+const TOKEN_VARIABLES: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Records the GitHub login of each commit author when
+/// `formatter.github_authors` is enabled.
+///
+/// Authors without a known login keep their git name in the changelog.
+pub fn resolve_author_logins(
+    config: &Config,
+    repo: &Repository,
+    repository_url: Option<&str>,
+    commits: &mut [ParsedCommit],
+) {
+    if !config.formatter.include_authors || !config.formatter.github_authors {
+        return;
+    }
+
+    let Some(logins) = repository_url.and_then(AuthorLogins::new) else {
+        info!("github_authors needs a GitHub repository URL, crediting authors by name");
+        return;
+    };
+
+    let mut logins = logins.with_token(github_token(config));
+    match PluginStorage::new() {
+        Ok(storage) => logins = logins.with_cache(storage.cache_dir().join(CACHE_FILE)),
+        Err(e) => debug!("GitHub login cache unavailable: {e}"),
+    }
+    logins.resolve(repo, commits);
+}
+
+/// Returns a GitHub token from the environment, or from the output of
+/// `plugins.github-release.token_command`.
+fn github_token(config: &Config) -> Option<String> {
+    let from_env = TOKEN_VARIABLES
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|token| !token.is_empty());
+    if from_env.is_some() {
+        return from_env;
+    }
+
+    let command = config.plugins.github_release.token_command.as_deref()?;
+    let output = Command::new("sh").args(["-c", command]).output().ok()?;
+    if !output.status.success() {
+        debug!("token command `{command}` failed: {}", output.status);
+        return None;
+    }
+    let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Returns the login encoded in a GitHub noreply email, either
+/// `id+login@users.noreply.github.com` or `login@users.noreply.github.com`.
+fn noreply_login(email: &str) -> Option<&str> {
+    let local = email.strip_suffix(NOREPLY_DOMAIN)?.strip_suffix('@')?;
+    let login = local.split_once('+').map_or(local, |(_, login)| login);
+    (!login.is_empty()).then_some(login)
+}
+
+/// Outcome of looking up the author of a commit.
+enum Lookup {
+    /// The author has a GitHub account.
+    Login(String),
+    /// The author email is not linked to a GitHub account.
+    NoAccount,
+    /// GitHub does not know the commit, e.g. it was not pushed yet.
+    UnknownCommit,
+}
+
+/// Commit returned by the GitHub API.
+#[derive(Deserialize)]
+struct CommitResponse {
+    author: Option<Author>,
+}
+
+/// GitHub account of a commit author.
+#[derive(Deserialize)]
+struct Author {
+    login: String,
+}
+
+/// Resolves commit author emails to GitHub logins.
+///
+/// Results are cached by email, including emails without an account, so
+/// each author is only looked up once. The first failed request stops the
+/// lookups, leaving the remaining authors with their git name.
+struct AuthorLogins {
+    /// Repository path on GitHub, `owner/repo`.
+    repository: String,
+    token: Option<String>,
+    cache: HashMap<String, Option<String>>,
+    cache_path: Option<PathBuf>,
+    client: Option<Client>,
+    offline: bool,
+}
+
+impl AuthorLogins {
+    /// Creates a resolver for a GitHub repository web URL, `None` for other
+    /// hosts.
+    fn new(repository_url: &str) -> Option<Self> {
+        let path = repository_url
+            .trim_end_matches('/')
+            .strip_prefix("https://github.com/")?;
+        let (owner, name) = path.split_once('/')?;
+        if owner.is_empty() || name.is_empty() || name.contains('/') {
+            return None;
+        }
+
+        Some(Self {
+            repository: path.to_string(),
+            token: None,
+            cache: HashMap::new(),
+            cache_path: None,
+            client: None,
+            offline: false,
+        })
+    }
+
+    /// Sets the token authenticating API requests.
+    fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Loads the cached logins from a file, and saves them back after
+    /// resolving.
+    fn with_cache(mut self, path: PathBuf) -> Self {
+        self.cache = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        self.cache_path = Some(path);
+        self
+    }
+
+    /// Records the login of each commit author in the commit metadata.
+    fn resolve(&mut self, repo: &Repository, commits: &mut [ParsedCommit]) {
+        for commit in commits {
+            let email = match repo.find_commit(&commit.hash) {
+                Ok(raw) => raw.email,
+                Err(e) => {
+                    debug!("failed to read commit {}: {e}", commit.hash);
+                    continue;
+                }
+            };
+            if let Some(login) = self.login(&email, &commit.hash) {
+                commit
+                    .metadata
+                    .insert(ParsedCommit::AUTHOR_LOGIN_METADATA.to_string(), login);
+            }
+        }
+        self.save();
+    }
+
+    /// Returns the login of an author, from their email or the API.
+    fn login(&mut self, email: &str, hash: &str) -> Option<String> {
+        if let Some(login) = noreply_login(email) {
+            return Some(login.to_string());
+        }
+        if let Some(login) = self.cache.get(email) {
+            return login.clone();
+        }
+        if self.offline {
+            return None;
+        }
+
+        let lookup = self.client().and_then(|client| self.fetch(&client, hash));
+        match lookup {
+            Ok(Lookup::Login(login)) => {
+                self.cache.insert(email.to_string(), Some(login.clone()));
+                Some(login)
+            }
+            Ok(Lookup::NoAccount) => {
+                self.cache.insert(email.to_string(), None);
+                None
+            }
+            Ok(Lookup::UnknownCommit) => None,
+            Err(e) => {
+                info!("GitHub login lookup failed, crediting authors by name: {e}");
+                self.offline = true;
+                None
+            }
+        }
+    }
+
+    /// Returns the HTTP client, created on the first request.
+    fn client(&mut self) -> reqwest::Result<Client> {
+        if let Some(client) = &self.client {
+            return Ok(client.clone());
+        }
+        let client = Client::builder()
+            .user_agent(concat!("unduler/", env!("CARGO_PKG_VERSION")))
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        self.client = Some(client.clone());
+        Ok(client)
+    }
+
+    /// Looks up the author of a commit through the GitHub API.
+    fn fetch(&self, client: &Client, hash: &str) -> reqwest::Result<Lookup> {
+        let url = format!("{API_URL}/repos/{}/commits/{hash}", self.repository);
+        let mut request = client
+            .get(url)
+            .header(ACCEPT, "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send()?;
+        if matches!(
+            response.status(),
+            StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY
+        ) {
+            return Ok(Lookup::UnknownCommit);
+        }
+
+        let commit: CommitResponse = response.error_for_status()?.json()?;
+        Ok(commit
+            .author
+            .map_or(Lookup::NoAccount, |author| Lookup::Login(author.login)))
+    }
+
+    /// Writes the cached logins back to the cache file.
+    fn save(&self) {
+        let Some(path) = &self.cache_path else {
+            return;
+        };
+        let Ok(content) = serde_json::to_string_pretty(&self.cache) else {
+            return;
+        };
+
+        let result = match path.parent() {
+            Some(parent) => fs::create_dir_all(parent),
+            None => Ok(()),
+        }
+        .and_then(|()| fs::write(path, content));
+        if let Err(e) = result {
+            debug!("failed to write {}: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_noreply_login() {
+        assert_eq!(
+            noreply_login("1234+octocat@users.noreply.github.com"),
+            Some("octocat")
+        );
+        assert_eq!(
+            noreply_login("octocat@users.noreply.github.com"),
+            Some("octocat")
+        );
+        assert_eq!(noreply_login("octocat@example.com"), None);
+        assert_eq!(noreply_login("@users.noreply.github.com"), None);
+    }
+
+    #[test]
+    fn test_new_parses_github_repository() {
+        let logins = AuthorLogins::new("https://github.com/owner/repo/").unwrap();
+        assert_eq!(logins.repository, "owner/repo");
+
+        assert!(AuthorLogins::new("https://gitlab.com/owner/repo").is_none());
+        assert!(AuthorLogins::new("https://github.com/owner").is_none());
+        assert!(AuthorLogins::new("https://github.com/owner/repo/tree").is_none());
+    }
+
+    #[test]
+    fn test_login_uses_cache_and_noreply_emails() {
+        let mut logins = AuthorLogins::new("https://github.com/owner/repo").unwrap();
+        logins
+            .cache
+            .insert("jane@example.com".to_string(), Some("jane".to_string()));
+        logins.cache.insert("bot@example.com".to_string(), None);
+        logins.offline = true;
+
+        assert_eq!(
+            logins.login("jane@example.com", "abc"),
+            Some("jane".to_string())
+        );
+        assert_eq!(logins.login("bot@example.com", "abc"), None);
+        assert_eq!(
+            logins.login("1+octocat@users.noreply.github.com", "abc"),
+            Some("octocat".to_string())
+        );
+        assert_eq!(logins.login("unknown@example.com", "abc"), None);
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache").join(CACHE_FILE);
+
+        let mut logins = AuthorLogins::new("https://github.com/owner/repo")
+            .unwrap()
+            .with_cache(path.clone());
+        logins
+            .cache
+            .insert("jane@example.com".to_string(), Some("jane".to_string()));
+        logins.cache.insert("bot@example.com".to_string(), None);
+        logins.save();
+
+        let loaded = AuthorLogins::new("https://github.com/owner/repo")
+            .unwrap()
+            .with_cache(path);
+        assert_eq!(loaded.cache, logins.cache);
+    }
+}
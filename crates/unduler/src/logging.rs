@@ -0,0 +1,86 @@
+//! Log output.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, fmt};
+
+/// Returns the level of the logs printed on stderr set by the `-v` and `-q`
+/// flags, if any.
+fn console_level(verbose: u8, quiet: bool) -> Option<&'static str> {
+    match (quiet, verbose) {
+        (true, _) => Some("warn"),
+        (false, 0) => None,
+        (false, 1) => Some("debug"),
+        (false, _) => Some("trace"),
+    }
+}
+
+/// Returns the level of the logs written to the log file, at least debug.
+fn file_level(verbose: u8) -> &'static str {
+    if verbose > 1 { "trace" } else { "debug" }
+}
+
+/// Returns the filter of the logs printed on stderr: the level set by the
+/// flags, then `RUST_LOG` when set, and info otherwise.
+fn console_filter(level: Option<&str>) -> EnvFilter {
+    match level {
+        Some(level) => EnvFilter::new(level),
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    }
+}
+
+/// Initializes logging on stderr, keeping stdout for command output, and
+/// in a log file when set.
+///
+/// # Errors
+///
+/// Returns an error if the log file cannot be created.
+pub fn init(verbose: u8, quiet: bool, log_file: Option<&Path>, ansi: bool) -> Result<()> {
+    let console = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(ansi)
+        .with_filter(console_filter(console_level(verbose, quiet)));
+
+    let file = log_file
+        .map(|path| {
+            let file = File::create(path)
+                .with_context(|| format!("failed to create log file {}", path.display()))?;
+            anyhow::Ok(
+                fmt::layer()
+                    .with_writer(Mutex::new(file))
+                    .with_ansi(false)
+                    .with_filter(EnvFilter::new(file_level(verbose))),
+            )
+        })
+        .transpose()?;
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .init();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_level() {
+        assert_eq!(console_level(0, false), None);
+        assert_eq!(console_level(1, false), Some("debug"));
+        assert_eq!(console_level(3, false), Some("trace"));
+        assert_eq!(console_level(0, true), Some("warn"));
+    }
+
+    #[test]
+    fn test_file_level() {
+        assert_eq!(file_level(0), "debug");
+        assert_eq!(file_level(2), "trace");
+    }
+}
@@ -1,21 +1,50 @@
 //! Unduler CLI - Automate version management and changelog generation.
 
-use anyhow::Result;
+use std::process::ExitCode;
+
 use clap::Parser;
-use tracing_subscriber::EnvFilter;
 
+use commands::release::NothingToRelease;
+
+mod ci;
 mod cli;
 mod commands;
+mod diagnostic;
+mod github;
+mod logging;
+mod output;
+mod prompt;
+mod release_state;
 
-fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+/// Exit code used in CI mode when there is nothing to release.
+const EXIT_NOTHING_TO_RELEASE: u8 = 2;
 
-    // Parse CLI arguments and run
+fn main() -> ExitCode {
+    // Parse CLI arguments
     let cli = cli::Cli::parse();
-    cli.run()
+
+    // Log on stderr, keeping stdout for command output
+    if let Err(e) = logging::init(
+        cli.verbose,
+        cli.quiet,
+        cli.log_file.as_deref(),
+        !cli.is_ci(),
+    ) {
+        eprint!("{}", diagnostic::report(&e));
+        return ExitCode::FAILURE;
+    }
+
+    match cli.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.is::<NothingToRelease>() => {
+            eprintln!("{e}");
+            ExitCode::from(EXIT_NOTHING_TO_RELEASE)
+        }
+        Err(e) => {
+            // Recorded in the log file, the report is printed below
+            tracing::debug!(error = format!("{e:#}"), "command failed");
+            eprint!("{}", diagnostic::report(&e));
+            ExitCode::FAILURE
+        }
+    }
 }
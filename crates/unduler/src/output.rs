@@ -0,0 +1,177 @@
+//! Command output.
+
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::ci::CiProvider;
+
+/// Output format argument.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    #[value(alias = "plain")]
+    Text,
+    /// A JSON summary printed once the command completes
+    Json,
+}
+
+/// Value of the global `--output` flag: an output format, or the changelog
+/// file of the `changelog` command, whose `--output` predates the formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputArg {
+    /// Output format of the command.
+    Format(OutputFormat),
+    /// Changelog file, as with `changelog --file`.
+    File(String),
+}
+
+impl Default for OutputArg {
+    fn default() -> Self {
+        Self::Format(OutputFormat::default())
+    }
+}
+
+impl FromStr for OutputArg {
+    type Err = Infallible;
+
+    /// Parses an output format, taking other values as a file.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(<OutputFormat as ValueEnum>::from_str(value, true)
+            .map_or_else(|_| Self::File(value.to_string()), Self::Format))
+    }
+}
+
+/// Prints command output in the selected format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Output {
+    format: OutputFormat,
+    ci: Option<CiProvider>,
+}
+
+impl Output {
+    /// Creates an output for the given format.
+    #[must_use]
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format, ci: None }
+    }
+
+    /// Groups text output for the given CI provider.
+    #[must_use]
+    pub fn with_ci(mut self, provider: CiProvider) -> Self {
+        self.ci = Some(provider);
+        self
+    }
+
+    /// Returns true if a JSON summary is printed instead of text.
+    #[must_use]
+    pub fn is_json(self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Prints a line of human-readable text, skipped in JSON mode.
+    pub fn text(self, line: impl Display) {
+        if !self.is_json() {
+            println!("{line}");
+        }
+    }
+
+    /// Opens a group of steps, printed as a plain title outside CI.
+    pub fn group_start(self, name: &str, title: &str) {
+        match self.ci.and_then(|ci| ci.group_start(name, title)) {
+            Some(line) => self.text(line),
+            None => self.text(format!("{title}:")),
+        }
+    }
+
+    /// Closes a group of steps, printed as a blank line outside CI.
+    pub fn group_end(self, name: &str) {
+        match self.ci.and_then(|ci| ci.group_end(name)) {
+            Some(line) => self.text(line),
+            None => self.text(""),
+        }
+    }
+
+    /// Prints the summary, only in JSON mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary cannot be serialized.
+    pub fn summary(self, summary: &Summary) -> Result<()> {
+        if self.is_json() {
+            println!("{}", serde_json::to_string_pretty(summary)?);
+        }
+        Ok(())
+    }
+}
+
+/// Structured summary of a command run.
+#[derive(Debug, Default, Serialize)]
+pub struct Summary {
+    /// Version before the command ran.
+    pub previous_version: Option<String>,
+    /// Version after the command ran.
+    pub new_version: Option<String>,
+    /// Bump type applied.
+    pub bump_type: Option<String>,
+    /// Version files updated.
+    pub files_updated: Vec<String>,
+    /// Release commit message.
+    pub commit: Option<String>,
+    /// Tag created.
+    pub tag: Option<String>,
+    /// Changelog file written.
+    pub changelog: Option<String>,
+    /// Whether the release was pushed.
+    pub pushed: bool,
+    /// Whether this was a dry run.
+    pub dry_run: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_output_is_text() {
+        assert!(!Output::default().is_json());
+        assert!(Output::new(OutputFormat::Json).is_json());
+    }
+
+    #[test]
+    fn test_parse_output_arg() {
+        assert_eq!("json".parse(), Ok(OutputArg::Format(OutputFormat::Json)));
+        assert_eq!("plain".parse(), Ok(OutputArg::Format(OutputFormat::Text)));
+        assert_eq!(
+            "NEWS.md".parse(),
+            Ok(OutputArg::File("NEWS.md".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_summary_serialization() {
+        let summary = Summary {
+            previous_version: Some("1.0.0".to_string()),
+            new_version: Some("1.1.0".to_string()),
+            bump_type: Some("minor".to_string()),
+            files_updated: vec!["Cargo.toml".to_string()],
+            tag: Some("v1.1.0".to_string()),
+            ..Summary::default()
+        };
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["previous_version"], "1.0.0");
+        assert_eq!(json["new_version"], "1.1.0");
+        assert_eq!(json["bump_type"], "minor");
+        assert_eq!(json["files_updated"][0], "Cargo.toml");
+        assert_eq!(json["tag"], "v1.1.0");
+        assert!(json["changelog"].is_null());
+        assert_eq!(json["pushed"], false);
+        assert_eq!(json["dry_run"], false);
+    }
+}
@@ -0,0 +1,98 @@
+//! Interactive prompts.
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// Editor used when neither `VISUAL` nor `EDITOR` is set.
+const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// Returns true if both stdin and stdout are terminals, so the user can
+/// answer prompts.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Asks a question, returning the trimmed answer.
+///
+/// # Errors
+///
+/// Returns an error if stdin is closed or cannot be read.
+pub fn ask(question: &str) -> Result<String> {
+    print!("{question} ");
+    io::stdout().flush().context("failed to write prompt")?;
+
+    let mut answer = String::new();
+    let read = io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("failed to read answer")?;
+    if read == 0 {
+        anyhow::bail!("no answer, stdin was closed");
+    }
+    Ok(answer.trim().to_string())
+}
+
+/// Returns the editor command of the user, from `VISUAL` or `EDITOR`.
+fn editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Builds the command opening a file in an editor. The editor may include
+/// arguments, e.g. `code --wait`.
+fn editor_command(editor: &str, path: &Path) -> Command {
+    let mut parts = editor.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or(DEFAULT_EDITOR));
+    command.args(parts).arg(path);
+    command
+}
+
+/// Opens text in the editor of the user, returning the edited text.
+///
+/// The text is written to a temporary file with the given extension, so
+/// editors pick the right syntax highlighting.
+///
+/// # Errors
+///
+/// Returns an error if the editor cannot be run or exits with a failure.
+pub fn edit(text: &str, extension: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("unduler-{}.{extension}", std::process::id()));
+    fs::write(&path, text).with_context(|| format!("failed to write {}", path.display()))?;
+
+    let editor = editor();
+    let status = editor_command(&editor, &path).status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("failed to run editor `{editor}`"))?;
+    if !status.success() {
+        anyhow::bail!("editor `{editor}` exited with {status}");
+    }
+    edited.with_context(|| format!("failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command() {
+        let command = editor_command("code --wait", Path::new("/tmp/section.md"));
+        assert_eq!(command.get_program(), "code");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["--wait", "/tmp/section.md"]
+        );
+
+        let command = editor_command("vim", Path::new("/tmp/section.md"));
+        assert_eq!(command.get_program(), "vim");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["/tmp/section.md"]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Release progress, saved to resume a failed release.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use unduler_core::{CommitRange, ReleaseStep};
+use unduler_plugin::BumpType;
+
+/// Directory holding unduler state, relative to the repository root.
+const STATE_DIR: &str = ".unduler";
+
+/// Release state file name.
+const STATE_FILE: &str = "release-state.json";
+
+/// Progress of a release, saved after each completed step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseState {
+    /// Version before the release.
+    pub previous_version: Version,
+    /// Version being released.
+    pub new_version: Version,
+    /// Bump type applied.
+    pub bump_type: BumpType,
+    /// Commits the release was planned from.
+    pub commits: Option<CommitRange>,
+    /// Steps already completed.
+    pub completed: Vec<ReleaseStep>,
+}
+
+impl ReleaseState {
+    /// Creates the state of a release that has not started yet.
+    #[must_use]
+    pub fn new(previous_version: Version, new_version: Version, bump_type: BumpType) -> Self {
+        Self {
+            previous_version,
+            new_version,
+            bump_type,
+            commits: None,
+            completed: Vec::new(),
+        }
+    }
+
+    /// Sets the commits the release was planned from.
+    #[must_use]
+    pub fn with_commits(mut self, commits: CommitRange) -> Self {
+        self.commits = Some(commits);
+        self
+    }
+
+    /// Returns true if the step was completed.
+    #[must_use]
+    pub fn is_done(&self, step: ReleaseStep) -> bool {
+        self.completed.contains(&step)
+    }
+
+    /// Marks a step as completed.
+    pub fn complete(&mut self, step: ReleaseStep) {
+        if !self.is_done(step) {
+            self.completed.push(step);
+        }
+    }
+
+    /// Returns the state file path for a repository.
+    #[must_use]
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(STATE_DIR).join(STATE_FILE)
+    }
+
+    /// Loads the state of an unfinished release, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be read or parsed.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let state = serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        Ok(Some(state))
+    }
+
+    /// Saves the state.
+    ///
+    /// The state directory ignores its own content, so the file is neither
+    /// reported as a change nor included in the release commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be written.
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let dir = root.join(STATE_DIR);
+        fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(&gitignore, "*\n")
+                .with_context(|| format!("failed to write {}", gitignore.display()))?;
+        }
+
+        let path = Self::path(root);
+        fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Removes the state once the release completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be removed.
+    pub fn clear(root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn state() -> ReleaseState {
+        ReleaseState::new(
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+        )
+    }
+
+    #[test]
+    fn test_complete_step() {
+        let mut state = state();
+        assert!(!state.is_done(ReleaseStep::Commit));
+
+        state.complete(ReleaseStep::Commit);
+        state.complete(ReleaseStep::Commit);
+        assert!(state.is_done(ReleaseStep::Commit));
+        assert_eq!(state.completed, [ReleaseStep::Commit]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert!(ReleaseState::load(root).unwrap().is_none());
+
+        let mut state = state().with_commits(CommitRange {
+            latest_tag: Some("v1.0.0".to_string()),
+            head: Some("abc123".to_string()),
+        });
+        state.complete(ReleaseStep::VersionFiles);
+        state.save(root).unwrap();
+
+        assert_eq!(ReleaseState::load(root).unwrap(), Some(state));
+        let gitignore = fs::read_to_string(root.join(".unduler/.gitignore")).unwrap();
+        assert_eq!(gitignore, "*\n");
+
+        ReleaseState::clear(root).unwrap();
+        assert!(ReleaseState::load(root).unwrap().is_none());
+    }
+}
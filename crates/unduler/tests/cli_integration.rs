@@ -6,8 +6,11 @@
 //! 3. Verifying the expected outputs
 
 use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
 use std::path::Path;
 use std::process::Command;
+use std::thread;
 
 use tempfile::TempDir;
 
@@ -158,6 +161,135 @@ fn test_init_creates_config() {
     );
 }
 
+#[test]
+fn test_init_detects_python_project() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::write(
+        dir.join("pyproject.toml"),
+        "[project]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("failed to write pyproject.toml");
+
+    let output = Command::new(unduler_bin())
+        .args(["init", "--no-plugins"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler init");
+
+    assert!(output.status.success(), "init should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Python"), "should detect Python: {stdout}");
+
+    let content = fs::read_to_string(dir.join("unduler.toml")).expect("failed to read config");
+    assert!(
+        content.contains("files = [\"pyproject.toml\"]"),
+        "config should detect pyproject.toml"
+    );
+}
+
+#[test]
+fn test_init_detects_gradle_project() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::write(dir.join("build.gradle.kts"), "plugins {\n    java\n}\n")
+        .expect("failed to write build.gradle.kts");
+    fs::write(dir.join("gradle.properties"), "version=0.1.0\n")
+        .expect("failed to write gradle.properties");
+
+    let output = Command::new(unduler_bin())
+        .args(["init", "--no-plugins"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler init");
+
+    assert!(output.status.success(), "init should succeed");
+
+    let content = fs::read_to_string(dir.join("unduler.toml")).expect("failed to read config");
+    assert!(
+        content.contains("files = [\"gradle.properties\"]"),
+        "config should suggest gradle.properties: {content}"
+    );
+}
+
+#[test]
+fn test_init_detects_go_project() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::write(dir.join("go.mod"), "module example.com/app\n\ngo 1.22\n")
+        .expect("failed to write go.mod");
+
+    let output = Command::new(unduler_bin())
+        .args(["init", "--no-plugins"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler init");
+
+    assert!(output.status.success(), "init should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Project type: Go"),
+        "should detect Go: {stdout}"
+    );
+    assert!(
+        stdout.contains("echo 0.1.0 > VERSION"),
+        "should suggest a VERSION file: {stdout}"
+    );
+
+    let content = fs::read_to_string(dir.join("unduler.toml")).expect("failed to read config");
+    assert!(
+        content.contains("path = \"VERSION\""),
+        "config should use a VERSION file: {content}"
+    );
+}
+
+#[test]
+fn test_init_detects_mixed_project() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    fs::write(
+        dir.join("pyproject.toml"),
+        "[project]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("failed to write pyproject.toml");
+    fs::write(
+        dir.join("composer.json"),
+        "{\n  \"name\": \"acme/app\",\n  \"version\": \"0.1.0\"\n}\n",
+    )
+    .expect("failed to write composer.json");
+
+    let output = Command::new(unduler_bin())
+        .arg("init")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler init");
+
+    assert!(output.status.success(), "init should succeed");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Project type: Rust + Python + PHP"),
+        "should detect every ecosystem: {stdout}"
+    );
+    assert!(
+        stdout.contains("unduler-hook-cargo"),
+        "should suggest the cargo hook: {stdout}"
+    );
+
+    let content = fs::read_to_string(dir.join("unduler.toml")).expect("failed to read config");
+    assert!(
+        content.contains("files = [\"Cargo.toml\", \"pyproject.toml\", \"composer.json\"]"),
+        "config should list every version file: {content}"
+    );
+}
+
 #[test]
 fn test_init_force_overwrites() {
     let temp_dir = setup_git_repo();
@@ -275,6 +407,38 @@ output = "CHANGELOG.md"
         content.contains("resolve critical bug"),
         "should include fix commit"
     );
+    assert!(
+        dir.join(".unduler/cache/commits.json").exists(),
+        "should cache the parsed commits"
+    );
+}
+
+#[test]
+fn test_changelog_without_cache() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+    fs::write(dir.join("src.rs"), "// new feature").expect("failed to write file");
+    git_commit(dir, "feat: add new feature");
+    fs::write(dir.join("unduler.toml"), "[version]\ncache = false\n")
+        .expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+
+    assert!(
+        output.status.success(),
+        "changelog should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(dir.join("CHANGELOG.md").exists());
+    assert!(!dir.join(".unduler/cache").exists());
 }
 
 #[test]
@@ -377,6 +541,43 @@ files = ["Cargo.toml"]
     );
 }
 
+#[test]
+fn test_bump_with_missing_version_file() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: fix a bug");
+
+    let config = r#"
+[version]
+files = ["Cargo.toml", "package.json"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["bump"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("package.json"),
+        "should report the missing file: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The other file is left untouched
+    let cargo_content =
+        fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read Cargo.toml");
+    assert!(
+        cargo_content.contains("version = \"1.0.0\""),
+        "should not bump Cargo.toml"
+    );
+}
+
 #[test]
 fn test_release_dry_run() {
     let temp_dir = setup_git_repo();
@@ -431,6 +632,19 @@ output = "CHANGELOG.md"
         cargo_content.contains("0.1.0"),
         "version should not be changed in dry-run"
     );
+    assert!(!dir.join("CHANGELOG.md").exists());
+
+    // The preview shows the diffs, the commit message and the tag
+    assert!(stdout.contains("--- a/Cargo.toml\n+++ b/Cargo.toml\n"));
+    assert!(stdout.contains("-version = \"0.1.0\"\n+version = \"0.2.0\"\n"));
+    assert!(stdout.contains("--- /dev/null\n+++ b/CHANGELOG.md\n"));
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line.starts_with('+') && line.contains("add new feature"))
+    );
+    assert!(stdout.contains("Would create commit with message:\n    chore(release): 0.2.0\n"));
+    assert!(stdout.contains("Would create tag v0.2.0 with message:\n"));
 }
 
 #[test]
@@ -466,6 +680,7 @@ fn test_help_command() {
         "should show changelog command"
     );
     assert!(stdout.contains("release"), "should show release command");
+    assert!(stdout.contains("next"), "should show next command");
 }
 
 #[test]
@@ -574,3 +789,2033 @@ files = ["Cargo.toml"]
         "should indicate no bump needed: stdout={stdout}, stderr={stderr}"
     );
 }
+
+#[test]
+fn test_parser_chain_mixed_history() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    // Gitmoji commit from a human, conventional commit from a bot
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "✨ feat: add feature");
+    fs::write(dir.join("deps.txt"), "deps").expect("failed to write file");
+    git_commit(dir, "fix(deps): bump serde");
+
+    let config = r#"
+[parser]
+names = ["gitmoji", "conventional"]
+
+[version]
+tag_prefix = "v"
+files = ["Cargo.toml"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["bump", "--dry-run"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump --dry-run should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("1.1.0"),
+        "gitmoji feature should trigger minor bump: {stdout}"
+    );
+}
+
+#[test]
+fn test_bump_with_tag_format() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.2.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "app@1.2.0");
+    git_tag(dir, "v9.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let config = r#"
+[version]
+tag_format = "{package}@{version}"
+package = "app"
+files = ["Cargo.toml"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["bump", "--dry-run"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump --dry-run should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("1.2.1"),
+        "should bump from the app@ tag: {stdout}"
+    );
+}
+
+#[test]
+fn test_bump_with_version_pattern() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::create_dir(dir.join("src")).expect("failed to create src dir");
+    fs::write(dir.join("src/version.py"), "__version__ = \"1.0.0\"\n")
+        .expect("failed to write version.py");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("feature.py"), "# feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+
+    let config = r#"
+[version]
+files = []
+
+[[version.file]]
+path = "src/version.py"
+pattern = '__version__ = "{version}"'
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .arg("bump")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(dir.join("src/version.py")).expect("failed to read file");
+    assert_eq!(content, "__version__ = \"1.1.0\"\n");
+}
+
+#[test]
+fn test_bump_with_yaml_key() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::create_dir(dir.join("chart")).expect("failed to create chart dir");
+    fs::write(
+        dir.join("chart/Chart.yaml"),
+        "apiVersion: v2\nname: app\nversion: 0.1.0\nappVersion: \"1.0.0\"\n",
+    )
+    .expect("failed to write Chart.yaml");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let config = r#"
+[version]
+files = []
+
+[[version.file]]
+path = "chart/Chart.yaml"
+key = "appVersion"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .arg("bump")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content = fs::read_to_string(dir.join("chart/Chart.yaml")).expect("failed to read file");
+    assert_eq!(
+        content,
+        "apiVersion: v2\nname: app\nversion: 0.1.0\nappVersion: \"1.0.1\"\n"
+    );
+}
+
+#[test]
+fn test_bump_with_paths_filter() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    fs::create_dir_all(dir.join("crates/foo")).expect("failed to create foo dir");
+    fs::create_dir_all(dir.join("crates/bar")).expect("failed to create bar dir");
+    create_cargo_toml(&dir.join("crates/foo"), "1.0.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("crates/bar/lib.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add bar feature");
+    fs::write(dir.join("crates/foo/lib.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct foo bug");
+
+    let config = r#"
+[version]
+files = ["crates/foo/Cargo.toml"]
+paths = ["crates/foo/**"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .arg("bump")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let content =
+        fs::read_to_string(dir.join("crates/foo/Cargo.toml")).expect("failed to read file");
+    assert!(content.contains("version = \"1.0.1\""));
+}
+
+/// Creates a project tagged v1.0.0 whose version files are then changed.
+fn setup_check_project(cargo_version: &str, python_version: &str) -> TempDir {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(dir.join("version.py"), "__version__ = \"1.0.0\"\n")
+        .expect("failed to write version.py");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+
+[[version.file]]
+path = "version.py"
+pattern = '__version__ = "{version}"'
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    create_cargo_toml(dir, cargo_version);
+    fs::write(
+        dir.join("version.py"),
+        format!("__version__ = \"{python_version}\"\n"),
+    )
+    .expect("failed to write version.py");
+    temp_dir
+}
+
+#[test]
+fn test_check_versions_match() {
+    let temp_dir = setup_check_project("1.0.0", "1.0.0");
+
+    let output = Command::new(unduler_bin())
+        .arg("check")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("failed to run unduler check");
+
+    assert!(
+        output.status.success(),
+        "check should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Version files match v1.0.0"), "{stdout}");
+}
+
+#[test]
+fn test_check_versions_diverge() {
+    let temp_dir = setup_check_project("1.0.0", "1.2.0");
+
+    let output = Command::new(unduler_bin())
+        .arg("check")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("failed to run unduler check");
+
+    assert!(!output.status.success(), "check should fail");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("diverges  version.py: 1.2.0"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("1 version file(s) diverge from v1.0.0"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("unduler check --fix"), "{stderr}");
+}
+
+#[test]
+fn test_check_fix() {
+    let temp_dir = setup_check_project("0.9.0", "1.2.0");
+    let dir = temp_dir.path();
+
+    let output = Command::new(unduler_bin())
+        .args(["check", "--fix"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler check");
+
+    assert!(
+        output.status.success(),
+        "check --fix should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let cargo = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read Cargo.toml");
+    assert!(cargo.contains("version = \"1.0.0\""), "{cargo}");
+    let python = fs::read_to_string(dir.join("version.py")).expect("failed to read file");
+    assert_eq!(python, "__version__ = \"1.0.0\"\n");
+}
+
+#[test]
+fn test_check_without_tag() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+
+    let output = Command::new(unduler_bin())
+        .arg("check")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler check");
+
+    assert!(!output.status.success(), "check should fail without a tag");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no release tag found"), "{stderr}");
+}
+
+#[test]
+fn test_next_prints_version() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.3.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.3.0");
+
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "fix: add config");
+
+    let output = Command::new(unduler_bin())
+        .arg("next")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(
+        output.status.success(),
+        "next should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.4.0\n");
+
+    let content = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read file");
+    assert!(
+        content.contains("version = \"1.3.0\""),
+        "should not touch files"
+    );
+    assert!(
+        !dir.join(".unduler/cache").exists(),
+        "should not write the commit cache"
+    );
+
+    let output = Command::new(unduler_bin())
+        .args(["next", "--format", "json"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("output should be valid JSON");
+    assert_eq!(json["current_version"], "1.3.0");
+    assert_eq!(json["next_version"], "1.4.0");
+    assert_eq!(json["tag"], "v1.4.0");
+    assert_eq!(json["bump_type"], "minor");
+    assert_eq!(json["commits"], 2);
+    assert_eq!(json["parsed_commits"], 2);
+}
+
+#[test]
+fn test_bump_json_output() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["--output", "json", "bump"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler bump");
+
+    assert!(
+        output.status.success(),
+        "bump should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("output should be valid JSON");
+    assert_eq!(json["previous_version"], "1.0.0");
+    assert_eq!(json["new_version"], "1.0.1");
+    assert_eq!(json["bump_type"], "patch");
+    assert_eq!(json["files_updated"], serde_json::json!(["Cargo.toml"]));
+    assert_eq!(json["dry_run"], false);
+}
+
+#[test]
+fn test_release_ci_nothing_to_release() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--ci"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "should exit with code 2: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(unduler_bin())
+        .arg("release")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert_eq!(output.status.code(), Some(1), "should fail outside CI mode");
+}
+
+#[test]
+fn test_release_ci_groups_logs() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--ci", "--dry-run"])
+        .env("GITHUB_ACTIONS", "true")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("::group::Updating version files"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("::endgroup::"), "{stdout}");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains('\x1b'),
+        "should not print ANSI codes: {stderr}"
+    );
+}
+
+#[test]
+fn test_release_push() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    let remote_dir = TempDir::new().expect("failed to create remote dir");
+    Command::new("git")
+        .args(["init", "--bare"])
+        .current_dir(remote_dir.path())
+        .output()
+        .expect("failed to init remote");
+    Command::new("git")
+        .args(["remote", "add", "origin"])
+        .arg(remote_dir.path())
+        .current_dir(dir)
+        .output()
+        .expect("failed to add remote");
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--push", "--no-changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let tags = Command::new("git")
+        .args(["tag", "--list"])
+        .current_dir(remote_dir.path())
+        .output()
+        .expect("failed to list remote tags");
+    let tags = String::from_utf8_lossy(&tags.stdout);
+    assert!(tags.contains("v1.0.1"), "tag should be pushed: {tags}");
+
+    let log = Command::new("git")
+        .args(["log", "--all", "--format=%s"])
+        .current_dir(remote_dir.path())
+        .output()
+        .expect("failed to read remote log");
+    let log = String::from_utf8_lossy(&log.stdout);
+    assert!(
+        log.contains("chore(release): 1.0.1"),
+        "release commit should be pushed: {log}"
+    );
+}
+
+#[test]
+fn test_release_aborts_on_dirty_tree() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    fs::write(dir.join("scratch.txt"), "wip").expect("failed to write file");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert!(!output.status.success(), "release should abort");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("uncommitted changes"), "{stderr}");
+    assert!(
+        stderr.contains("scratch.txt"),
+        "should list files: {stderr}"
+    );
+
+    let content = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read file");
+    assert!(content.contains("version = \"1.0.0\""), "should not bump");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog", "--allow-dirty"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert!(
+        output.status.success(),
+        "release should succeed with --allow-dirty: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_release_branch_policy() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+release_branches = ["release/*"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let release = |args: &[&str]| {
+        Command::new(unduler_bin())
+            .arg("release")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler release")
+    };
+
+    let output = release(&["--dry-run"]);
+    assert!(!output.status.success(), "should refuse other branches");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not allowed from branch"), "{stderr}");
+
+    let output = release(&["--dry-run", "--allow-branch"]);
+    assert!(
+        output.status.success(),
+        "--allow-branch should override: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    Command::new("git")
+        .args(["checkout", "-b", "release/1.x"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to create branch");
+
+    let output = release(&["--dry-run"]);
+    assert!(
+        output.status.success(),
+        "should release from matching branch: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_prerelease_channel() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+
+[[version.channel]]
+branch = "next"
+prerelease = "rc"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run git");
+    };
+    let next = || {
+        let output = Command::new(unduler_bin())
+            .arg("next")
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler next");
+        assert!(
+            output.status.success(),
+            "next should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    git(&["checkout", "-b", "next"]);
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+    assert_eq!(next(), "1.1.0-rc.1");
+
+    git_tag(dir, "v1.1.0-rc.1");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+    assert_eq!(next(), "1.1.0-rc.2");
+
+    // The stable channel ignores prerelease tags
+    git(&["checkout", "-"]);
+    git(&["merge", "next"]);
+    assert_eq!(next(), "1.1.0");
+}
+
+#[test]
+fn test_bump_rules() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[bumper.rules]
+deps = "patch"
+"refactor(core)" = "minor"
+
+[version]
+files = ["Cargo.toml"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    let next = || {
+        let output = Command::new(unduler_bin())
+            .arg("next")
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler next");
+        assert!(
+            output.status.success(),
+            "next should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    fs::write(dir.join("Cargo.lock"), "# lock").expect("failed to write file");
+    git_commit(dir, "deps: update serde");
+    assert_eq!(next(), "1.0.1");
+
+    fs::write(dir.join("core.rs"), "// core").expect("failed to write file");
+    git_commit(dir, "refactor(core): split modules");
+    assert_eq!(next(), "1.1.0");
+}
+
+#[test]
+fn test_zero_ver_bump() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.3.2");
+    let config = r#"
+[bumper]
+zero_ver = true
+
+[version]
+files = ["Cargo.toml"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.3.2");
+
+    let next = || {
+        let output = Command::new(unduler_bin())
+            .arg("next")
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler next");
+        assert!(
+            output.status.success(),
+            "next should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+    assert_eq!(next(), "0.3.3");
+
+    fs::write(dir.join("api.rs"), "// api").expect("failed to write file");
+    git_commit(dir, "feat!: change api");
+    assert_eq!(next(), "0.4.0");
+}
+
+#[test]
+fn test_release_refuses_existing_tag() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+tag_format = "v{major}.{minor}"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0");
+
+    // 1.0.1 maps to the existing v1.0 tag
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+
+    assert!(!output.status.success(), "release should abort");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("tag v1.0 already exists"), "{stderr}");
+
+    let content = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read file");
+    assert!(content.contains("version = \"1.0.0\""), "should not bump");
+}
+
+#[test]
+fn test_release_without_git_identity() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    // No identity in the git configuration
+    Command::new("git")
+        .args(["config", "user.email", ""])
+        .current_dir(dir)
+        .output()
+        .expect("failed to configure git email");
+
+    let output = Command::new(unduler_bin())
+        .args(["release"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(!output.status.success(), "release should fail");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("version.commit_author"),
+        "should hint at commit_author: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Nothing is changed before the identity check
+    let content = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read Cargo.toml");
+    assert!(content.contains("version = \"1.0.0\""), "should not bump");
+    assert!(
+        !dir.join("CHANGELOG.md").exists(),
+        "should not write the changelog"
+    );
+}
+
+#[test]
+fn test_release_commit_author_without_git_identity() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+commit_author = "release-bot <bot@example.com>"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    // No identity in the git configuration
+    Command::new("git")
+        .args(["config", "user.email", ""])
+        .current_dir(dir)
+        .output()
+        .expect("failed to configure git email");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--format=%(taggername) %(taggeremail)",
+            "refs/tags/v1.0.1",
+        ])
+        .current_dir(dir)
+        .output()
+        .expect("failed to read tag");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "release-bot <bot@example.com>"
+    );
+}
+
+#[test]
+fn test_release_resume() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let release = |args: &[&str]| {
+        Command::new(unduler_bin())
+            .arg("release")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler release")
+    };
+
+    // The push fails: there is no remote yet
+    let output = release(&["--push", "--no-changelog"]);
+    assert!(!output.status.success(), "push should fail");
+    assert!(dir.join(".unduler/release-state.json").exists());
+
+    // A new release refuses to start over
+    let output = release(&["--push", "--no-changelog"]);
+    assert!(!output.status.success(), "release should abort");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--resume"), "{stderr}");
+
+    let remote_dir = TempDir::new().expect("failed to create remote dir");
+    Command::new("git")
+        .args(["init", "--bare"])
+        .current_dir(remote_dir.path())
+        .output()
+        .expect("failed to init remote");
+    Command::new("git")
+        .args(["remote", "add", "origin"])
+        .arg(remote_dir.path())
+        .current_dir(dir)
+        .output()
+        .expect("failed to add remote");
+
+    let output = release(&["--resume", "--push", "--no-changelog"]);
+    assert!(
+        output.status.success(),
+        "resume should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!dir.join(".unduler/release-state.json").exists());
+
+    // The release commit and tag were not created twice
+    let log = Command::new("git")
+        .args(["log", "--format=%s"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to read log");
+    let log = String::from_utf8_lossy(&log.stdout);
+    assert_eq!(log.matches("chore(release): 1.0.1").count(), 1, "{log}");
+
+    let tags = Command::new("git")
+        .args(["tag", "--list"])
+        .current_dir(remote_dir.path())
+        .output()
+        .expect("failed to list remote tags");
+    let tags = String::from_utf8_lossy(&tags.stdout);
+    assert!(tags.contains("v1.0.1"), "tag should be pushed: {tags}");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_release_resume_keeps_planned_commits() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    // The changelog fails once the version files are updated: its path is a
+    // link into a missing directory, read as a missing file but not writable
+    std::os::unix::fs::symlink("missing/CHANGELOG.md", dir.join("CHANGELOG.md"))
+        .expect("failed to link changelog");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let release = |args: &[&str]| {
+        Command::new(unduler_bin())
+            .arg("release")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler release")
+    };
+
+    let output = release(&[]);
+    assert!(!output.status.success(), "changelog should fail");
+    assert!(dir.join(".unduler/release-state.json").exists());
+
+    fs::remove_file(dir.join("CHANGELOG.md")).expect("failed to remove link");
+    fs::write(dir.join("ship.rs"), "// ship").expect("failed to write file");
+    Command::new("git")
+        .args(["add", "ship.rs", "CHANGELOG.md"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to add file");
+    Command::new("git")
+        .args(["commit", "-m", "feat: ship feature"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to commit");
+
+    let output = release(&["--resume"]);
+    assert!(
+        output.status.success(),
+        "resume should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The commit made since is not part of the release
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read file");
+    assert!(changelog.contains("correct bug"), "{changelog}");
+    assert!(!changelog.contains("ship"), "{changelog}");
+}
+
+#[test]
+fn test_release_resume_after_tag_failure() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\ncommit_message = \"fix(release): ship {version}\"\ntag_message = \"{changelog}\"\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let release = |args: &[&str]| {
+        Command::new(unduler_bin())
+            .arg("release")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler release")
+    };
+
+    // The tag fails once the release commit is created: its ref is locked
+    let lock = dir.join(".git/refs/tags/v1.0.1.lock");
+    fs::create_dir_all(lock.parent().unwrap()).expect("failed to create tags dir");
+    fs::write(&lock, "").expect("failed to lock tag");
+    let output = release(&[]);
+    assert!(!output.status.success(), "tag should fail");
+
+    fs::remove_file(&lock).expect("failed to unlock tag");
+    let output = release(&["--resume"]);
+    assert!(
+        output.status.success(),
+        "resume should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The release commit is not a commit of its own release
+    let message = Command::new("git")
+        .args(["tag", "-l", "--format=%(contents)", "v1.0.1"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to read tag message");
+    let message = String::from_utf8_lossy(&message.stdout);
+    assert!(message.contains("correct bug"), "{message}");
+    assert!(!message.contains("ship"), "{message}");
+}
+
+#[test]
+fn test_release_without_changelog_file() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\ntag_message = \"{changelog}\"\n\n[changelog]\noutput = \"none\"\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--yes"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(!dir.join("none").exists());
+    assert!(!dir.join("CHANGELOG.md").exists());
+    let message = Command::new("git")
+        .args(["tag", "-l", "--format=%(contents)", "v1.0.1"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to read tag message");
+    let message = String::from_utf8_lossy(&message.stdout);
+    assert!(message.contains("correct bug"), "{message}");
+}
+
+#[test]
+fn test_release_no_file() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--yes", "--no-file"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!dir.join("CHANGELOG.md").exists());
+    let cargo = fs::read_to_string(dir.join("Cargo.toml")).expect("failed to read Cargo.toml");
+    assert!(cargo.contains("1.0.1"), "{cargo}");
+}
+
+#[test]
+fn test_release_runs_configured_hooks() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::create_dir(dir.join("dist")).expect("failed to create dist");
+    fs::write(dir.join("dist/app"), "binary").expect("failed to write artifact");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+
+[plugins.sbom]
+enabled = true
+formats = []
+artifacts = ["dist/app"]
+output_dir = "sbom"
+
+[[hooks.post_tag]]
+name = "sbom"
+if = "!prerelease"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The post_tag hook wrote the provenance of the artifact
+    let provenance = fs::read_to_string(dir.join("sbom/provenance.intoto.jsonl"))
+        .expect("the sbom hook should run");
+    assert!(provenance.contains("\"name\":\"app\""), "{provenance}");
+}
+
+/// Builds the hook plugin of `tests/fixtures/hook-probe.wat` into a
+/// component exporting the hook and config interfaces.
+fn build_probe_hook(path: &Path) {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut resolve = wit_parser::Resolve::default();
+    resolve
+        .push_dir(manifest_dir.join("../unduler-plugin/wit"))
+        .expect("failed to parse the plugin WIT");
+    let package = resolve
+        .push_str(
+            "probe.wit",
+            "package test:probe;\n\nworld probe {\n    include unduler:plugin/unduler-hook@0.2.0;\n    include unduler:plugin/unduler-configurable@0.2.0;\n}\n",
+        )
+        .expect("failed to parse the probe world");
+    let world = resolve
+        .select_world(package, Some("probe"))
+        .expect("failed to select the probe world");
+
+    let mut module = wat::parse_file(manifest_dir.join("tests/fixtures/hook-probe.wat"))
+        .expect("failed to parse the probe hook");
+    wit_component::embed_component_metadata(
+        &mut module,
+        &resolve,
+        world,
+        wit_component::StringEncoding::UTF8,
+    )
+    .expect("failed to embed the component metadata");
+    let component = wit_component::ComponentEncoder::default()
+        .module(&module)
+        .and_then(|encoder| encoder.validate(true).encode())
+        .expect("failed to encode the probe hook");
+    fs::write(path, component).expect("failed to write the probe hook");
+}
+
+/// Answers a single HTTP request with `200 OK`, returning the request.
+fn serve_once(listener: TcpListener) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("failed to accept request");
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let read = stream.read(&mut buffer).expect("failed to read request");
+            request.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&request);
+            let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                body.len() >= length
+            });
+            if complete || read == 0 {
+                break;
+            }
+        }
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok")
+            .expect("failed to write response");
+        String::from_utf8(request).expect("request should be UTF-8")
+    })
+}
+
+#[test]
+fn test_release_runs_wasm_hook() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+    let plugin_dir = TempDir::new().expect("failed to create temp dir");
+    let plugin = plugin_dir.path().join("hook-probe.wasm");
+    build_probe_hook(&plugin);
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind server");
+    let port = listener.local_addr().expect("no server address").port();
+    let server = serve_once(listener);
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = format!(
+        r#"
+[version]
+files = ["Cargo.toml"]
+
+[[hooks.post_tag]]
+name = "probe"
+config = "http://127.0.0.1:{port}/release"
+
+[plugins.probe]
+channel = "releases"
+token_env = "UNDULER_TEST_PROBE_TOKEN"
+
+[sandbox.probe]
+allowed_hosts = ["127.0.0.1"]
+"#
+    );
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+
+    let output = Command::new(unduler_bin())
+        .args([
+            "plugin",
+            "install",
+            "hook-probe",
+            "--local",
+            "--version",
+            "1.0.0",
+        ])
+        .arg("--url")
+        .arg(&plugin)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler plugin install");
+    assert!(
+        output.status.success(),
+        "install should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["release", "--no-changelog"])
+        .env("UNDULER_TEST_PROBE_TOKEN", "s3cret")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler release");
+    assert!(
+        output.status.success(),
+        "release should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The hook sent its configuration with its secret, through the sandbox
+    let request = server.join().expect("server panicked");
+    assert!(request.starts_with("POST /release "), "{request}");
+    assert!(request.contains("x-token: s3cret"), "{request}");
+    let config = r#"{"channel":"releases","token_env":"UNDULER_TEST_PROBE_TOKEN"}"#;
+    assert!(request.ends_with(config), "{request}");
+
+    // Its write-file action ran in the repository
+    let written = fs::read_to_string(dir.join("probe.json")).expect("the hook should write");
+    assert_eq!(written, config);
+}
+
+#[test]
+fn test_changelog_is_idempotent() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+
+    fs::write(dir.join("src.rs"), "// new feature").expect("failed to write file");
+    git_commit(dir, "feat: add new feature");
+
+    let config = r#"
+[changelog]
+output = "CHANGELOG.md"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    for _ in 0..2 {
+        let output = Command::new(unduler_bin())
+            .arg("changelog")
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler changelog");
+
+        assert!(
+            output.status.success(),
+            "changelog should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read changelog");
+    assert_eq!(
+        changelog.matches("## [0.2.0]").count(),
+        1,
+        "version section should not be duplicated: {changelog}"
+    );
+    assert_eq!(changelog.matches("add new feature").count(), 1);
+}
+
+#[test]
+fn test_changelog_outputs() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+
+    fs::write(dir.join("src.rs"), "// new feature").expect("failed to write file");
+    git_commit(dir, "feat: add new feature");
+
+    let config = r#"
+[formatter.debian]
+package = "app"
+maintainer = "Jane <jane@example.com>"
+
+[[changelog.outputs]]
+formatter = "debian"
+path = "debian/changelog"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["changelog", "--dry-run"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+    assert!(
+        output.status.success(),
+        "changelog should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+++ b/debian/changelog"), "{stdout}");
+    assert!(stdout.contains("+app (0.2.0)"), "{stdout}");
+    assert!(!dir.join("debian/changelog").exists());
+
+    let output = Command::new(unduler_bin())
+        .arg("changelog")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+    assert!(output.status.success());
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read changelog");
+    assert!(changelog.contains("## [0.2.0]"), "{changelog}");
+    let debian =
+        fs::read_to_string(dir.join("debian/changelog")).expect("failed to read debian changelog");
+    assert!(debian.starts_with("app (0.2.0)"), "{debian}");
+}
+
+#[test]
+fn test_changelog_header_file() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+
+    fs::write(dir.join("src.rs"), "// new feature").expect("failed to write file");
+    git_commit(dir, "feat: add new feature");
+
+    fs::write(dir.join("header.md"), "# Release History\n").expect("failed to write header");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[changelog]\nheader_file = \"header.md\"\n",
+    )
+    .expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .arg("changelog")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+    assert!(
+        output.status.success(),
+        "changelog should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read changelog");
+    assert!(
+        changelog.starts_with("# Release History\n\n## [0.2.0]"),
+        "{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_range() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.2.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.2.0");
+
+    fs::write(dir.join("a.rs"), "// a").expect("failed to write file");
+    git_commit(dir, "feat: add feature a");
+    git_tag(dir, "v1.3.0");
+
+    fs::write(dir.join("b.rs"), "// b").expect("failed to write file");
+    git_commit(dir, "fix: correct bug b");
+    git_tag(dir, "v1.4.0");
+
+    fs::write(dir.join("c.rs"), "// c").expect("failed to write file");
+    git_commit(dir, "feat: add feature c");
+
+    fs::write(
+        dir.join("unduler.toml"),
+        "[changelog]\noutput = \"CHANGELOG.md\"\n",
+    )
+    .expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args([
+            "changelog",
+            "--from",
+            "v1.2.0",
+            "--to",
+            "v1.4.0",
+            "--dry-run",
+        ])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+
+    assert!(
+        output.status.success(),
+        "changelog should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("## [1.4.0]"), "{stdout}");
+    assert!(stdout.contains("add feature a"));
+    assert!(stdout.contains("correct bug b"));
+    assert!(!stdout.contains("add feature c"));
+
+    let changelog = |args: &[&str]| {
+        let output = Command::new(unduler_bin())
+            .arg("changelog")
+            .args(args)
+            .arg("--dry-run")
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler changelog");
+        assert!(
+            output.status.success(),
+            "changelog should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // The range ending at a tag starts at the previous tag
+    let stdout = changelog(&["--to", "v1.3.0"]);
+    assert!(stdout.contains("## [1.3.0]"), "{stdout}");
+    assert!(stdout.contains("add feature a"));
+    assert!(!stdout.contains("correct bug b"));
+
+    // A range starting at a commit follows the latest tag
+    let stdout = changelog(&["--from", "HEAD~1"]);
+    assert!(stdout.contains("## [1.5.0]"), "{stdout}");
+    assert!(stdout.contains("add feature c"));
+    assert!(!stdout.contains("correct bug b"));
+}
+
+#[test]
+fn test_changelog_unreleased() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+
+    fs::write(dir.join("src.rs"), "// new feature").expect("failed to write file");
+    git_commit(dir, "feat: add new feature");
+
+    let config = r#"
+[changelog]
+output = "CHANGELOG.md"
+repository_url = "https://github.com/owner/repo"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let run = |args: &[&str]| {
+        let output = Command::new(unduler_bin())
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler changelog");
+        assert!(
+            output.status.success(),
+            "changelog should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    run(&["changelog", "--unreleased"]);
+    run(&["changelog", "--unreleased"]);
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read changelog");
+    assert_eq!(
+        changelog.matches("## [Unreleased]").count(),
+        1,
+        "{changelog}"
+    );
+    assert!(changelog.contains("add new feature"));
+    assert!(
+        changelog.contains("[Unreleased]: https://github.com/owner/repo/compare/v0.1.0...HEAD"),
+        "should link to HEAD: {changelog}"
+    );
+
+    // Releasing replaces the Unreleased section
+    run(&["changelog"]);
+
+    let changelog = fs::read_to_string(dir.join("CHANGELOG.md")).expect("failed to read changelog");
+    assert!(!changelog.contains("[Unreleased]"), "{changelog}");
+    assert!(changelog.contains("## [0.2.0]"));
+    assert_eq!(changelog.matches("add new feature").count(), 1);
+}
+
+#[test]
+fn test_changelog_links_github_references() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "0.1.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v0.1.0");
+
+    fs::write(dir.join("src.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: handle timeouts (#7)\n\nCloses #5");
+
+    let config = r#"
+[changelog]
+repository_url = "https://github.com/owner/repo"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["changelog", "--dry-run"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler changelog");
+
+    assert!(
+        output.status.success(),
+        "changelog should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("- handle timeouts ([#7](https://github.com/owner/repo/issues/7))"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("### Closed Issues\n\n- [#5](https://github.com/owner/repo/issues/5)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_plugin_run_parser() {
+    let plugin = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("test-plugins/parser-conventional.wasm");
+    if !plugin.exists() {
+        eprintln!("Skipping test: plugin not found at {plugin:?}");
+        return;
+    }
+
+    let output = Command::new(unduler_bin())
+        .args(["plugin", "run", "parser"])
+        .arg(&plugin)
+        .args(["--message", "feat(api): add endpoint"])
+        .output()
+        .expect("failed to run unduler plugin run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "plugin run should succeed");
+    assert!(stdout.contains(r#""type": "feat""#), "stdout={stdout}");
+    assert!(stdout.contains(r#""scope": "api""#), "stdout={stdout}");
+}
+
+#[test]
+fn test_installed_parser_plugin_in_pipeline() {
+    let plugins = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("test-plugins");
+    if !plugins.join("parser-conventional.wasm").exists() {
+        eprintln!("Skipping test: plugins not found at {plugins:?}");
+        return;
+    }
+
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+    create_cargo_toml(dir, "1.3.0");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.3.0");
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+
+    // A bumper installed as a parser fails to load, unlike the parser
+    for (name, wasm) in [
+        ("parser-wasm", "parser-conventional.wasm"),
+        ("parser-broken", "bumper-semver.wasm"),
+    ] {
+        let output = Command::new(unduler_bin())
+            .args(["plugin", "install", name, "--local", "--version", "1.0.0"])
+            .arg("--url")
+            .arg(plugins.join(wasm))
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler plugin install");
+        assert!(
+            output.status.success(),
+            "install should succeed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fs::write(dir.join("unduler.toml"), "[parser]\nname = \"wasm\"\n")
+        .expect("failed to write config");
+    let output = Command::new(unduler_bin())
+        .arg("next")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+    assert!(
+        output.status.success(),
+        "next should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1.4.0\n");
+
+    fs::write(dir.join("unduler.toml"), "[parser]\nname = \"broken\"\n")
+        .expect("failed to write config");
+    let output = Command::new(unduler_bin())
+        .arg("next")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unduler-parser-broken"), "{stderr}");
+
+    // Listing the plugins does not need a valid configuration
+    fs::write(dir.join("unduler.toml"), "[parser\n").expect("failed to write config");
+    let output = Command::new(unduler_bin())
+        .args(["plugin", "list"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler plugin list");
+    assert!(
+        output.status.success(),
+        "list should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_plugin_run_requires_input() {
+    let output = Command::new(unduler_bin())
+        .args(["plugin", "run", "bumper", "missing.wasm"])
+        .output()
+        .expect("failed to run unduler plugin run");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("--commits"), "stderr={stderr}");
+}
+
+#[test]
+fn test_invalid_regex_pattern_points_at_config() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    let config = r#"
+[parser]
+name = "regex"
+
+[parser.regex]
+pattern = '^(?P<type>\w+'
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+
+    let output = Command::new(unduler_bin())
+        .arg("next")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(!output.status.success(), "should reject the pattern");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid regex pattern"), "{stderr}");
+    assert!(stderr.contains("unduler.toml:6:11"), "{stderr}");
+    assert!(
+        stderr.contains(r"6 | pattern = '^(?P<type>\w+'"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("= help:"), "{stderr}");
+}
+
+#[test]
+fn test_missing_config_hints_init() {
+    let temp_dir = setup_git_repo();
+
+    let output = Command::new(unduler_bin())
+        .arg("next")
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unduler init"), "{stderr}");
+}
+
+#[test]
+fn test_config_schema() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+
+    let output = Command::new(unduler_bin())
+        .args(["config", "schema"])
+        .current_dir(temp_dir.path())
+        .output()
+        .expect("failed to run unduler config schema");
+
+    assert!(output.status.success());
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema should be JSON");
+    assert!(schema["properties"]["version"].is_object(), "{schema}");
+}
+
+#[test]
+fn test_gitmoji_list() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("unduler.toml"),
+        "[parser.conventional-gitmoji.mappings]\n\"🧿\" = \"fix\"\n\"🎉\" = \"chore\"\n",
+    )
+    .expect("failed to write config");
+
+    let output = Command::new(unduler_bin())
+        .args(["gitmoji", "list"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler gitmoji list");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(":sparkles:"), "{stdout}");
+    assert!(stdout.contains("🧿"), "{stdout}");
+
+    let output = Command::new(unduler_bin())
+        .args(["--output", "json", "gitmoji", "list"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler gitmoji list");
+    assert!(output.status.success());
+    let mappings: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("mappings should be JSON");
+    let tada = mappings
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|mapping| mapping["emoji"] == "🎉")
+        .unwrap();
+    assert_eq!(tada["commit_type"], "chore");
+    assert_eq!(tada["custom"], true);
+}
+
+#[test]
+fn test_user_config_is_merged() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+    let config_home = TempDir::new().expect("failed to create temp dir");
+
+    fs::create_dir_all(config_home.path().join("unduler")).expect("failed to create dir");
+    fs::write(
+        config_home.path().join("unduler/config.toml"),
+        "[version]\ntag_prefix = \"release-\"\n",
+    )
+    .expect("failed to write user config");
+
+    create_cargo_toml(dir, "1.0.0");
+    fs::write(
+        dir.join("unduler.toml"),
+        "[version]\nfiles = [\"Cargo.toml\"]\n",
+    )
+    .expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "release-1.0.0");
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+
+    let output = Command::new(unduler_bin())
+        .args(["next", "--format", "json"])
+        .env("XDG_CONFIG_HOME", config_home.path())
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(
+        output.status.success(),
+        "next should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""tag": "release-1.1.0""#), "{stdout}");
+}
+
+#[test]
+fn test_profile_overrides_config() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    create_cargo_toml(dir, "1.0.0");
+    let config = r#"
+[version]
+files = ["Cargo.toml"]
+
+[profile.nightly.version]
+tag_prefix = "nightly-"
+"#;
+    fs::write(dir.join("unduler.toml"), config).expect("failed to write config");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "v1.0.0");
+    git_tag(dir, "nightly-1.0.1");
+    fs::write(dir.join("feature.rs"), "// feature").expect("failed to write file");
+    git_commit(dir, "feat: add feature");
+
+    let next = |args: &[&str]| {
+        Command::new(unduler_bin())
+            .args(["next", "--format", "json"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run unduler next")
+    };
+
+    let output = next(&[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""tag": "v1.1.0""#), "{stdout}");
+
+    let output = next(&["--profile", "nightly"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""tag": "nightly-1.1.0""#), "{stdout}");
+    assert!(stdout.contains(r#""current_version": "1.0.1""#), "{stdout}");
+
+    let output = next(&["--profile", "stable"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("profile 'stable' is not defined"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_config_in_cargo_metadata() {
+    let temp_dir = setup_git_repo();
+    let dir = temp_dir.path();
+
+    let manifest = r#"[package]
+name = "test-project"
+version = "1.0.0"
+edition = "2021"
+
+[package.metadata.unduler.version]
+files = ["Cargo.toml"]
+tag_prefix = "demo-v"
+"#;
+    fs::write(dir.join("Cargo.toml"), manifest).expect("failed to write Cargo.toml");
+    git_commit(dir, "chore: initial commit");
+    git_tag(dir, "demo-v1.0.0");
+    fs::write(dir.join("fix.rs"), "// fix").expect("failed to write file");
+    git_commit(dir, "fix: correct bug");
+
+    let output = Command::new(unduler_bin())
+        .args(["next", "--format", "json"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run unduler next");
+
+    assert!(
+        output.status.success(),
+        "next should succeed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(r#""tag": "demo-v1.0.1""#), "{stdout}");
+}
@@ -0,0 +1,126 @@
+;; Hook plugin probing the host capabilities, built into a component by the
+;; CLI integration tests.
+;;
+;; At `post-tag`, it sends its plugin configuration in a POST request to the
+;; URL given as configuration of its `[hooks]` entry, with its `token` secret
+;; in an `x-token` header, stores the response in the `response` metadata
+;; and writes its configuration to `probe.json`. Other stages succeed.
+;;
+;; Memory layout:
+;;   16..128   strings of the plugin information
+;;   128       successful hook result, without updates or actions
+;;   224       successful configuration result
+;;   256..384  strings of the post-tag stage
+;;   384       `get-secret` result
+;;   400       `x-token` header
+;;   432       `request` result
+;;   512       post-tag hook result, with its metadata update at 608 and its
+;;             action at 640
+;;   4096..    memory allocated by the host
+(module
+  (import "unduler:plugin/secrets@0.2.0" "get-secret"
+    (func $get_secret (param i32 i32 i32)))
+  (import "unduler:plugin/http@0.2.0" "request"
+    (func $request (param i32 i32 i32 i32 i32 i32 i32 i32 i32 i32)))
+
+  (memory (export "memory") 16)
+
+  (global $heap (mut i32) (i32.const 4096))
+  (global $config_ptr (mut i32) (i32.const 0))
+  (global $config_len (mut i32) (i32.const 0))
+
+  (data (i32.const 16) "probe")
+  (data (i32.const 32) "1.0.0")
+  (data (i32.const 48) "Probe hook")
+  ;; plugin-info: name, version, description and the hook plugin type
+  (data (i32.const 64)
+    "\10\00\00\00\05\00\00\00"
+    "\20\00\00\00\05\00\00\00"
+    "\30\00\00\00\0a\00\00\00"
+    "\03")
+  (data (i32.const 128) "\01")
+  (data (i32.const 256) "token")
+  (data (i32.const 272) "POST")
+  (data (i32.const 288) "x-token")
+  (data (i32.const 304) "response")
+  (data (i32.const 320) "probe.json")
+
+  ;; Bump allocator, never freeing memory
+  (func (export "cabi_realloc")
+    (param $old_ptr i32) (param $old_size i32) (param $align i32) (param $size i32)
+    (result i32)
+    (local $ptr i32)
+    (local.set $ptr
+      (i32.and
+        (i32.add (global.get $heap) (i32.sub (local.get $align) (i32.const 1)))
+        (i32.sub (i32.const 0) (local.get $align))))
+    (global.set $heap (i32.add (local.get $ptr) (local.get $size)))
+    (local.get $ptr))
+
+  (func (export "unduler:plugin/config@0.2.0#configure")
+    (param $ptr i32) (param $len i32) (result i32)
+    (global.set $config_ptr (local.get $ptr))
+    (global.set $config_len (local.get $len))
+    (i32.const 224))
+
+  (func (export "unduler:plugin/hook@0.2.0#info") (result i32)
+    (i32.const 64))
+
+  (func $succeed (param $ctx i32) (result i32)
+    (i32.const 128))
+
+  (func (export "unduler:plugin/hook@0.2.0#on-post-tag")
+    (param $ctx i32) (result i32)
+    ;; x-token header, empty without the secret
+    (call $get_secret (i32.const 256) (i32.const 5) (i32.const 384))
+    (i32.store (i32.const 400) (i32.const 288))
+    (i32.store (i32.const 404) (i32.const 7))
+    (if (i32.load8_u (i32.const 384))
+      (then
+        (i32.store (i32.const 408) (i32.load (i32.const 388)))
+        (i32.store (i32.const 412) (i32.load (i32.const 392)))))
+
+    ;; The hook configuration is a JSON string: strip its quotes
+    (call $request
+      (i32.const 272) (i32.const 4)
+      (i32.add (i32.load offset=176 (local.get $ctx)) (i32.const 1))
+      (i32.sub (i32.load offset=180 (local.get $ctx)) (i32.const 2))
+      (i32.const 400) (i32.const 1)
+      (i32.const 1) (global.get $config_ptr) (global.get $config_len)
+      (i32.const 432))
+
+    ;; A failed request fails the hook with its error
+    (if (i32.load8_u (i32.const 432))
+      (then
+        (i32.store8 (i32.const 516) (i32.const 1))
+        (i32.store (i32.const 520) (i32.load (i32.const 436)))
+        (i32.store (i32.const 524) (i32.load (i32.const 440)))
+        (return (i32.const 512))))
+
+    ;; response metadata, with the response body
+    (i32.store (i32.const 608) (i32.const 304))
+    (i32.store (i32.const 612) (i32.const 8))
+    (i32.store (i32.const 616) (i32.load (i32.const 448)))
+    (i32.store (i32.const 620) (i32.load (i32.const 452)))
+
+    ;; write-file action of the configuration
+    (i32.store8 (i32.const 640) (i32.const 1))
+    (i32.store (i32.const 644) (i32.const 320))
+    (i32.store (i32.const 648) (i32.const 10))
+    (i32.store (i32.const 652) (global.get $config_ptr))
+    (i32.store (i32.const 656) (global.get $config_len))
+
+    (i32.store8 (i32.const 512) (i32.const 1))
+    (i32.store (i32.const 528) (i32.const 608))
+    (i32.store (i32.const 532) (i32.const 1))
+    (i32.store (i32.const 536) (i32.const 640))
+    (i32.store (i32.const 540) (i32.const 1))
+    (i32.const 512))
+
+  (export "unduler:plugin/hook@0.2.0#on-pre-release" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-pre-bump" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-post-bump" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-pre-commit" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-pre-tag" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-post-release" (func $succeed))
+  (export "unduler:plugin/hook@0.2.0#on-failure" (func $succeed)))
@@ -1,7 +1,10 @@
 //! SemVer bump strategy plugin.
 
+use std::collections::HashMap;
+
+use semver::Version;
 use unduler_commit::ParsedCommit;
-use unduler_plugin::{BumpStrategy, BumpType, Plugin};
+use unduler_plugin::{BumpReason, BumpStrategy, BumpType, Plugin};
 
 /// SemVer bump strategy.
 ///
@@ -10,11 +13,22 @@ use unduler_plugin::{BumpStrategy, BumpType, Plugin};
 /// - `feat` → Minor
 /// - `fix` → Patch
 /// - Other → None (or configurable)
+///
+/// Rules set with [`with_rule`](Self::with_rule) take precedence over these
+/// types, and can target a scope with the `type(scope)` syntax.
+///
+/// With [`with_zero_ver`](Self::with_zero_ver), `0.x` versions follow the
+/// Cargo convention instead: breaking changes bump the minor and features
+/// bump the patch, so pre-1.0 projects never reach `1.0.0` by accident.
 pub struct SemverBumper {
     /// Types that trigger a patch bump.
     patch_types: Vec<String>,
     /// Types that trigger a minor bump.
     minor_types: Vec<String>,
+    /// Bump rules keyed by `type` or `type(scope)`.
+    rules: HashMap<String, BumpType>,
+    /// Whether `0.x` versions use shifted bump semantics.
+    zero_ver: bool,
 }
 
 impl SemverBumper {
@@ -24,6 +38,8 @@ impl SemverBumper {
         Self {
             patch_types: vec!["fix".to_string(), "perf".to_string()],
             minor_types: vec!["feat".to_string()],
+            rules: HashMap::new(),
+            zero_ver: false,
         }
     }
 
@@ -40,6 +56,76 @@ impl SemverBumper {
         self.minor_types = types;
         self
     }
+
+    /// Sets the bump triggered by a commit type, or a type and scope.
+    ///
+    /// The key is either a commit type (`deps`) or a type with a scope
+    /// (`refactor(core)`). Scoped rules win over type rules, which win over
+    /// the patch and minor types. Breaking changes always bump the major.
+    #[must_use]
+    pub fn with_rule(mut self, key: impl Into<String>, bump: BumpType) -> Self {
+        self.rules.insert(key.into(), bump);
+        self
+    }
+
+    /// Returns the bump triggered by a non-breaking commit.
+    fn commit_bump(&self, commit: &ParsedCommit) -> BumpType {
+        self.commit_rule(commit).0
+    }
+
+    /// Returns the bump triggered by a non-breaking commit, with the rule
+    /// that fired.
+    fn commit_rule(&self, commit: &ParsedCommit) -> (BumpType, String) {
+        let commit_type = &commit.r#type;
+        if let Some(scope) = &commit.scope {
+            let key = format!("{commit_type}({scope})");
+            if let Some(bump) = self.rules.get(&key) {
+                return (*bump, format!("rule {key}"));
+            }
+        }
+        if let Some(bump) = self.rules.get(commit_type) {
+            return (*bump, format!("rule {commit_type}"));
+        }
+
+        if self.minor_types.contains(commit_type) {
+            (BumpType::Minor, format!("{commit_type} is a minor type"))
+        } else if self.patch_types.contains(commit_type) {
+            (BumpType::Patch, format!("{commit_type} is a patch type"))
+        } else {
+            (BumpType::None, format!("{commit_type} does not bump"))
+        }
+    }
+
+    /// Enables `0.x` semantics while the major version is 0.
+    #[must_use]
+    pub fn with_zero_ver(mut self, zero_ver: bool) -> Self {
+        self.zero_ver = zero_ver;
+        self
+    }
+
+    /// Determines the bump type for commits on top of the current version.
+    ///
+    /// Same as [`determine`](BumpStrategy::determine), except that with `0.x`
+    /// semantics enabled and a major version of 0, major bumps become minor
+    /// and minor bumps become patch.
+    #[must_use]
+    pub fn determine_for(&self, current: &Version, commits: &[ParsedCommit]) -> BumpType {
+        self.downgrade(current, self.determine(commits))
+    }
+
+    /// Downgrades a bump under `0.x` semantics: major bumps become minor and
+    /// minor bumps become patch.
+    fn downgrade(&self, current: &Version, bump: BumpType) -> BumpType {
+        if !self.zero_ver || current.major > 0 {
+            return bump;
+        }
+
+        match bump {
+            BumpType::Major => BumpType::Minor,
+            BumpType::Minor => BumpType::Patch,
+            other => other,
+        }
+    }
 }
 
 impl Default for SemverBumper {
@@ -72,19 +158,46 @@ impl BumpStrategy for SemverBumper {
                 return BumpType::Major;
             }
 
-            // Check for minor bump
-            if self.minor_types.contains(&commit.r#type) {
-                bump = bump.max(BumpType::Minor);
-            }
-
-            // Check for patch bump
-            if self.patch_types.contains(&commit.r#type) {
-                bump = bump.max(BumpType::Patch);
-            }
+            bump = bump.max(self.commit_bump(commit));
         }
 
         bump
     }
+
+    fn next_version(&self, current: &Version, commits: &[ParsedCommit]) -> Option<Version> {
+        if !self.zero_ver || current.major > 0 {
+            return None;
+        }
+
+        match self.determine_for(current, commits) {
+            BumpType::Minor => Some(Version::new(0, current.minor + 1, 0)),
+            BumpType::Patch => Some(Version::new(0, current.minor, current.patch + 1)),
+            // Nothing to bump: leave the decision to the caller
+            BumpType::Major | BumpType::None => None,
+        }
+    }
+
+    fn explain(&self, current: &Version, commits: &[ParsedCommit]) -> Vec<BumpReason> {
+        commits
+            .iter()
+            .map(|commit| {
+                let (bump, mut rule) = if commit.breaking {
+                    (BumpType::Major, "breaking change".to_string())
+                } else {
+                    self.commit_rule(commit)
+                };
+                let downgraded = self.downgrade(current, bump);
+                if downgraded != bump {
+                    rule.push_str(", downgraded for 0.x");
+                }
+                BumpReason {
+                    hash: commit.hash.clone(),
+                    bump: downgraded,
+                    rule,
+                }
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -199,6 +312,170 @@ mod tests {
         assert_eq!(bumper.determine(&commits), BumpType::Major);
     }
 
+    #[test]
+    fn test_rule_for_type() {
+        let bumper = SemverBumper::new().with_rule("deps", BumpType::Patch);
+
+        assert_eq!(
+            bumper.determine(&[make_commit("deps", false)]),
+            BumpType::Patch
+        );
+    }
+
+    #[test]
+    fn test_rule_overrides_default_types() {
+        let bumper = SemverBumper::new().with_rule("feat", BumpType::Patch);
+
+        assert_eq!(
+            bumper.determine(&[make_commit("feat", false)]),
+            BumpType::Patch
+        );
+    }
+
+    #[test]
+    fn test_rule_for_scope() {
+        let bumper = SemverBumper::new()
+            .with_rule("refactor", BumpType::None)
+            .with_rule("refactor(core)", BumpType::Minor);
+
+        let core = ParsedCommit::builder("abc123", "refactor")
+            .scope("core")
+            .build();
+        let cli = ParsedCommit::builder("def456", "refactor")
+            .scope("cli")
+            .build();
+
+        assert_eq!(bumper.determine(&[core]), BumpType::Minor);
+        assert_eq!(bumper.determine(&[cli]), BumpType::None);
+    }
+
+    #[test]
+    fn test_breaking_wins_over_rules() {
+        let bumper = SemverBumper::new().with_rule("fix", BumpType::None);
+
+        assert_eq!(
+            bumper.determine(&[make_commit("fix", true)]),
+            BumpType::Major
+        );
+    }
+
+    #[test]
+    fn test_zero_ver_breaking_bumps_minor() {
+        let bumper = SemverBumper::new().with_zero_ver(true);
+        let commits = vec![make_commit("feat", true)];
+        let current = Version::new(0, 3, 2);
+
+        assert_eq!(bumper.determine_for(&current, &commits), BumpType::Minor);
+        assert_eq!(
+            bumper.next_version(&current, &commits),
+            Some(Version::new(0, 4, 0))
+        );
+    }
+
+    #[test]
+    fn test_zero_ver_feature_bumps_patch() {
+        let bumper = SemverBumper::new().with_zero_ver(true);
+        let commits = vec![make_commit("feat", false)];
+        let current = Version::new(0, 3, 2);
+
+        assert_eq!(bumper.determine_for(&current, &commits), BumpType::Patch);
+        assert_eq!(
+            bumper.next_version(&current, &commits),
+            Some(Version::new(0, 3, 3))
+        );
+    }
+
+    #[test]
+    fn test_zero_ver_ignored_after_one() {
+        let bumper = SemverBumper::new().with_zero_ver(true);
+        let commits = vec![make_commit("feat", true)];
+        let current = Version::new(1, 2, 0);
+
+        assert_eq!(bumper.determine_for(&current, &commits), BumpType::Major);
+        assert!(bumper.next_version(&current, &commits).is_none());
+    }
+
+    #[test]
+    fn test_zero_ver_disabled_by_default() {
+        let bumper = SemverBumper::new();
+        let commits = vec![make_commit("feat", true)];
+        let current = Version::new(0, 3, 2);
+
+        assert_eq!(bumper.determine_for(&current, &commits), BumpType::Major);
+        assert!(bumper.next_version(&current, &commits).is_none());
+    }
+
+    #[test]
+    fn test_explain() {
+        let scoped = ParsedCommit::builder("ccc", "refactor")
+            .scope("core")
+            .message("test")
+            .build();
+        let commits = vec![
+            ParsedCommit::builder("aaa", "fix")
+                .message("test")
+                .breaking(true)
+                .build(),
+            ParsedCommit::builder("bbb", "feat").message("test").build(),
+            scoped,
+            ParsedCommit::builder("ddd", "docs").message("test").build(),
+        ];
+        let bumper = SemverBumper::new().with_rule("refactor(core)", BumpType::Patch);
+
+        let reasons: Vec<_> = bumper
+            .explain(&Version::new(1, 0, 0), &commits)
+            .into_iter()
+            .map(|reason| (reason.hash, reason.bump, reason.rule))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (
+                    "aaa".to_string(),
+                    BumpType::Major,
+                    "breaking change".to_string()
+                ),
+                (
+                    "bbb".to_string(),
+                    BumpType::Minor,
+                    "feat is a minor type".to_string()
+                ),
+                (
+                    "ccc".to_string(),
+                    BumpType::Patch,
+                    "rule refactor(core)".to_string()
+                ),
+                (
+                    "ddd".to_string(),
+                    BumpType::None,
+                    "docs does not bump".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_zero_ver() {
+        let bumper = SemverBumper::new().with_zero_ver(true);
+        let commits = vec![make_commit("feat", true), make_commit("fix", false)];
+
+        let reasons: Vec<_> = bumper
+            .explain(&Version::new(0, 3, 2), &commits)
+            .into_iter()
+            .map(|reason| (reason.bump, reason.rule))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (
+                    BumpType::Minor,
+                    "breaking change, downgraded for 0.x".to_string()
+                ),
+                (BumpType::Patch, "fix is a patch type".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn test_plugin_name() {
         let bumper = SemverBumper::new();
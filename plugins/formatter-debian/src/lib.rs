@@ -0,0 +1,284 @@
+//! Debian changelog formatter plugin.
+
+use std::fmt::Write;
+
+use semver::Version;
+use unduler_commit::ParsedCommit;
+use unduler_plugin::{ChangelogFormatter, FormatterConfig, Plugin, Release, wrap_line};
+
+/// Width of `debian/changelog` lines, which tools expect under 80 columns.
+const DEFAULT_LINE_WIDTH: usize = 79;
+
+/// Indentation of the lines continuing an entry.
+const CONTINUATION_INDENT: &str = "    ";
+
+/// Debian changelog formatter.
+///
+/// Formats releases as [`debian/changelog`](https://www.debian.org/doc/debian-policy/ch-source.html#debian-changelog)
+/// entries:
+///
+/// ```text
+/// app (1.2.0-1) unstable; urgency=medium
+///
+///   * api: add the v2 endpoints
+///   * Fix the login redirect
+///
+///  -- Jane Doe <jane@example.com>  Thu, 15 Oct 2026 10:00:00 +0000
+/// ```
+pub struct DebianFormatter {
+    /// Name of the source package.
+    package: String,
+    /// Maintainer of the package, as `Name <email>`.
+    maintainer: String,
+    /// Distribution the package is uploaded to.
+    distribution: String,
+    /// Urgency of the upload.
+    urgency: String,
+    /// Debian revision appended to the version, e.g. `1` for `1.2.0-1`.
+    revision: Option<String>,
+}
+
+impl DebianFormatter {
+    /// Creates a formatter for a source package, uploaded to `unstable`
+    /// with a `medium` urgency.
+    #[must_use]
+    pub fn new(package: impl Into<String>, maintainer: impl Into<String>) -> Self {
+        Self {
+            package: package.into(),
+            maintainer: maintainer.into(),
+            distribution: "unstable".to_string(),
+            urgency: "medium".to_string(),
+            revision: None,
+        }
+    }
+
+    /// Sets the distribution the package is uploaded to.
+    #[must_use]
+    pub fn with_distribution(mut self, distribution: impl Into<String>) -> Self {
+        self.distribution = distribution.into();
+        self
+    }
+
+    /// Sets the urgency of the upload.
+    #[must_use]
+    pub fn with_urgency(mut self, urgency: impl Into<String>) -> Self {
+        self.urgency = urgency.into();
+        self
+    }
+
+    /// Sets the Debian revision appended to the version. Native packages
+    /// have none.
+    #[must_use]
+    pub fn with_revision(mut self, revision: Option<String>) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// Returns the Debian version of a release.
+    ///
+    /// Pre-release versions use `~` so that `1.0.0~rc.1` sorts before
+    /// `1.0.0`, as Debian versions sort.
+    #[must_use]
+    pub fn debian_version(&self, version: &Version) -> String {
+        let mut debian = format!("{}.{}.{}", version.major, version.minor, version.patch);
+        if !version.pre.is_empty() {
+            _ = write!(debian, "~{}", version.pre);
+        }
+        if !version.build.is_empty() {
+            _ = write!(debian, "+{}", version.build);
+        }
+        if let Some(revision) = &self.revision {
+            _ = write!(debian, "-{revision}");
+        }
+        debian
+    }
+
+    /// Returns the text of a commit entry, with its scope and the
+    /// `Changelog:` footer of the commit when set.
+    fn entry_text(commit: &ParsedCommit, config: &FormatterConfig) -> String {
+        let mut text = config.normalize(commit.changelog_text());
+        if let Some(scope) = commit.scope.as_ref().filter(|s| !s.is_empty()) {
+            text = format!("{scope}: {text}");
+        }
+        if commit.breaking {
+            text = format!("BREAKING: {text}");
+        }
+        text
+    }
+}
+
+impl Plugin for DebianFormatter {
+    fn name(&self) -> &'static str {
+        "debian"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Formats changelog as debian/changelog entries"
+    }
+}
+
+impl ChangelogFormatter for DebianFormatter {
+    fn format(&self, release: &Release, config: &FormatterConfig) -> String {
+        let mut output = format!(
+            "{} ({}) {}; urgency={}\n\n",
+            self.package,
+            self.debian_version(&release.version),
+            self.distribution,
+            self.urgency
+        );
+
+        let width = config.line_width.unwrap_or(DEFAULT_LINE_WIDTH);
+        let mut shown = 0;
+        for commit in &release.commits {
+            if commit.is_changelog_skipped() || config.hide_types.contains(&commit.r#type) {
+                continue;
+            }
+            for (index, line) in Self::entry_text(commit, config).lines().enumerate() {
+                let prefix = if index == 0 {
+                    "  * "
+                } else {
+                    CONTINUATION_INDENT
+                };
+                _ = writeln!(
+                    output,
+                    "{}",
+                    wrap_line(&format!("{prefix}{line}"), width, CONTINUATION_INDENT)
+                );
+            }
+            shown += 1;
+        }
+        if shown == 0 {
+            _ = writeln!(output, "  * New release.");
+        }
+
+        _ = writeln!(
+            output,
+            "\n -- {}  {}",
+            self.maintainer,
+            release.date.to_rfc2822()
+        );
+        output
+    }
+
+    fn extension(&self) -> &'static str {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use unduler_plugin::{MessageCase, TrailingPunctuation};
+
+    use super::*;
+
+    fn make_commit(commit_type: &str, message: &str) -> ParsedCommit {
+        ParsedCommit::builder("abc1234567890", commit_type)
+            .message(message)
+            .author("testuser")
+            .build()
+    }
+
+    fn make_release(version: Version, commits: Vec<ParsedCommit>) -> Release {
+        let date = Utc.with_ymd_and_hms(2026, 10, 15, 10, 0, 0).unwrap();
+        Release::new(version, date, commits)
+    }
+
+    fn formatter() -> DebianFormatter {
+        DebianFormatter::new("app", "Jane Doe <jane@example.com>")
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(formatter().name(), "debian");
+        assert_eq!(formatter().version(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_format() {
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "feat")
+                .scope("api")
+                .message("add the v2 endpoints")
+                .build(),
+            make_commit("fix", "Fix the login redirect"),
+        ];
+        let release = make_release(Version::new(1, 2, 0), commits);
+
+        assert_eq!(
+            formatter().format(&release, &FormatterConfig::default()),
+            "app (1.2.0) unstable; urgency=medium\n\
+             \n  \
+             * api: add the v2 endpoints\n  \
+             * Fix the login redirect\n\
+             \n \
+             -- Jane Doe <jane@example.com>  Thu, 15 Oct 2026 10:00:00 +0000\n"
+        );
+    }
+
+    #[test]
+    fn test_distribution_urgency_and_revision() {
+        let formatter = formatter()
+            .with_distribution("noble")
+            .with_urgency("high")
+            .with_revision(Some("1".to_string()));
+        let release = make_release(Version::new(1, 2, 0), vec![make_commit("fix", "fix")]);
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(output.starts_with("app (1.2.0-1) noble; urgency=high\n"));
+    }
+
+    #[test]
+    fn test_debian_version() {
+        let formatter = formatter().with_revision(Some("2".to_string()));
+        let version = Version::parse("1.0.0-rc.1+build.5").unwrap();
+        assert_eq!(formatter.debian_version(&version), "1.0.0~rc.1+build.5-2");
+    }
+
+    #[test]
+    fn test_hidden_and_breaking_entries() {
+        let commits = vec![
+            make_commit("chore", "bump dependencies"),
+            ParsedCommit::builder("abc1234567890", "feat")
+                .message("drop the v1 API")
+                .breaking(true)
+                .build(),
+        ];
+        let release = make_release(Version::new(2, 0, 0), commits);
+        let config = FormatterConfig {
+            hide_types: vec!["chore".to_string()],
+            message_case: MessageCase::Sentence,
+            trailing_punctuation: TrailingPunctuation::Period,
+            ..FormatterConfig::default()
+        };
+
+        let output = formatter().format(&release, &config);
+        assert!(output.contains("  * BREAKING: Drop the v1 API.\n"));
+        assert!(!output.contains("dependencies"));
+    }
+
+    #[test]
+    fn test_empty_release() {
+        let release = make_release(Version::new(1, 0, 1), vec![]);
+        let output = formatter().format(&release, &FormatterConfig::default());
+        assert!(output.contains("\n  * New release.\n"));
+    }
+
+    #[test]
+    fn test_wrap_long_entries() {
+        let message = "rework the configuration loader so that profiles and user defaults merge \
+                       before validation";
+        let release = make_release(Version::new(1, 0, 0), vec![make_commit("fix", message)]);
+
+        let output = formatter().format(&release, &FormatterConfig::default());
+        assert!(output.contains(
+            "  * rework the configuration loader so that profiles and user defaults merge\n    \
+             before validation\n"
+        ));
+        assert!(output.lines().all(|line| line.chars().count() < 80));
+    }
+}
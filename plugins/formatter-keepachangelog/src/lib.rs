@@ -4,7 +4,9 @@ use std::collections::HashMap;
 use std::fmt::Write;
 
 use unduler_commit::ParsedCommit;
-use unduler_plugin::{ChangelogFormatter, FormatterConfig, Plugin, Release};
+use unduler_plugin::{
+    ChangelogFormatter, FormatterConfig, FormatterSection, Plugin, Release, wrap_line,
+};
 
 /// Keep a Changelog formatter.
 ///
@@ -32,7 +34,8 @@ impl KeepAChangelogFormatter {
         groups
     }
 
-    /// Returns the display label for a commit type.
+    /// Returns the display label for a commit type, or for the `highlights`,
+    /// `closed_issues` and `contributors` sections.
     fn type_label(commit_type: &str, config: &FormatterConfig) -> String {
         config
             .type_labels
@@ -57,10 +60,297 @@ impl KeepAChangelogFormatter {
             "deps" => "Dependencies".to_string(),
             "security" => "Security".to_string(),
             "breaking" => "Breaking Changes".to_string(),
+            "highlights" => "Highlights".to_string(),
+            "closed_issues" => "Closed Issues".to_string(),
+            "contributors" => "Contributors".to_string(),
             _ => commit_type.to_string(),
         }
     }
 
+    /// Returns the gitmoji of a commit type, decorating its section heading.
+    fn section_emoji(commit_type: &str) -> Option<&'static str> {
+        match commit_type {
+            "feat" => Some("✨"),
+            "fix" => Some("🐛"),
+            "docs" => Some("📝"),
+            "style" => Some("🎨"),
+            "refactor" => Some("♻️"),
+            "perf" => Some("⚡️"),
+            "test" => Some("✅"),
+            "build" | "ci" => Some("👷"),
+            "chore" => Some("🔧"),
+            "revert" => Some("⏪️"),
+            "deps" => Some("⬆️"),
+            "security" => Some("🔒️"),
+            "breaking" => Some("💥"),
+            _ => None,
+        }
+    }
+
+    /// Returns the heading of a section, decorated with its emoji when
+    /// `show_emoji` is set.
+    fn heading(key: &str, config: &FormatterConfig) -> String {
+        Self::decorate(Self::type_label(key, config), key, config)
+    }
+
+    /// Decorates a section title with the emoji of a commit type when
+    /// `show_emoji` is set.
+    fn decorate(title: String, commit_type: &str, config: &FormatterConfig) -> String {
+        match Self::section_emoji(commit_type) {
+            Some(emoji) if config.show_emoji => format!("{emoji} {title}"),
+            _ => title,
+        }
+    }
+
+    /// Returns the emoji prefix of a commit entry, if it has an emoji and
+    /// `show_emoji` is set.
+    fn emoji_prefix(commit: &ParsedCommit, config: &FormatterConfig) -> String {
+        commit
+            .emoji
+            .as_ref()
+            .filter(|emoji| config.show_emoji && !emoji.is_empty())
+            .map(|emoji| format!("{emoji} "))
+            .unwrap_or_default()
+    }
+
+    /// Returns the bold scope prefix of a commit entry, if it has a scope.
+    fn scope_prefix(commit: &ParsedCommit) -> String {
+        commit
+            .scope
+            .as_ref()
+            .filter(|s| !s.is_empty())
+            .map(|s| format!("**{s}:** "))
+            .unwrap_or_default()
+    }
+
+    /// Returns the text of a commit entry, with its pull request.
+    ///
+    /// The text is the `Changelog:` footer of the commit when set. Lines
+    /// after the first, from changelog fragments, are indented under the
+    /// list item.
+    fn entry_text(
+        commit: &ParsedCommit,
+        github_url: Option<&str>,
+        config: &FormatterConfig,
+    ) -> String {
+        let message = config.normalize(commit.changelog_text());
+        let mut text = indent_continuation(&match github_url {
+            Some(url) => link_references(&message, url),
+            None => message.clone(),
+        });
+
+        // Squash merges usually mention the pull request in the message already
+        if let Some(pr) = commit.pull_request()
+            && !message.contains(&format!("#{pr}"))
+        {
+            match github_url {
+                Some(url) => _ = write!(text, " ([#{pr}]({url}/pull/{pr}))"),
+                None => _ = write!(text, " (#{pr})"),
+            }
+        }
+        text
+    }
+
+    /// Returns the text of a breaking change entry: the description of its
+    /// `BREAKING CHANGE:` footer when set, its entry text otherwise.
+    fn breaking_text(
+        commit: &ParsedCommit,
+        github_url: Option<&str>,
+        config: &FormatterConfig,
+    ) -> String {
+        let Some(description) = commit.breaking_change() else {
+            return Self::entry_text(commit, github_url, config);
+        };
+        let description = config.normalize(description);
+        match github_url {
+            Some(url) => link_references(&description, url),
+            None => description,
+        }
+    }
+
+    /// Appends the breaking changes and highlighted commits.
+    fn push_highlights(
+        output: &mut String,
+        commits: &[ParsedCommit],
+        github_url: Option<&str>,
+        config: &FormatterConfig,
+    ) {
+        let highlights: Vec<&ParsedCommit> = commits
+            .iter()
+            .filter(|commit| commit.breaking || commit.is_highlight())
+            .collect();
+        if highlights.is_empty() {
+            return;
+        }
+        let label = Self::heading("highlights", config);
+        _ = writeln!(output, "### {label}\n");
+        for commit in highlights {
+            let emoji = Self::emoji_prefix(commit, config);
+            let scope = Self::scope_prefix(commit);
+            let text = Self::entry_text(commit, github_url, config);
+            push_item(output, &format!("{emoji}{scope}{text}"), config);
+        }
+        output.push('\n');
+    }
+
+    /// Appends the breaking changes of any type, in the section replacing the
+    /// `breaking` type section.
+    fn push_breaking_changes(
+        output: &mut String,
+        commits: &[ParsedCommit],
+        github_url: Option<&str>,
+        config: &FormatterConfig,
+    ) {
+        let breaking: Vec<&ParsedCommit> =
+            commits.iter().filter(|commit| commit.breaking).collect();
+        if breaking.is_empty() {
+            return;
+        }
+        let label = Self::heading("breaking", config);
+        _ = writeln!(output, "### {label}\n");
+        for commit in breaking {
+            let emoji = Self::emoji_prefix(commit, config);
+            let scope = Self::scope_prefix(commit);
+            let text = Self::breaking_text(commit, github_url, config);
+            push_item(output, &format!("{emoji}{scope}{text}"), config);
+        }
+        output.push('\n');
+    }
+
+    /// Returns true if two commits have the same changelog entry, such as a
+    /// commit and its cherry-pick.
+    fn same_entry(a: &ParsedCommit, b: &ParsedCommit) -> bool {
+        a.r#type == b.r#type && a.scope == b.scope && a.changelog_text() == b.changelog_text()
+    }
+
+    /// Returns the commits of a release collapsed into the entry of `commit`:
+    /// all commits with the same entry when `deduplicate` is set, `commit`
+    /// alone otherwise.
+    fn copies<'a>(
+        commit: &'a ParsedCommit,
+        commits: &'a [ParsedCommit],
+        config: &FormatterConfig,
+    ) -> Vec<&'a ParsedCommit> {
+        if !config.deduplicate {
+            return vec![commit];
+        }
+        commits
+            .iter()
+            .filter(|other| Self::same_entry(other, commit))
+            .collect()
+    }
+
+    /// Returns the item of a commit in its type section, followed by the
+    /// hashes and authors of the commits collapsed into it.
+    fn section_item(
+        commit: &ParsedCommit,
+        commits: &[ParsedCommit],
+        github_url: Option<&str>,
+        config: &FormatterConfig,
+    ) -> String {
+        let emoji = Self::emoji_prefix(commit, config);
+        let scope = Self::scope_prefix(commit);
+        let copies = Self::copies(commit, commits, config);
+
+        let hashes: Vec<&str> = copies
+            .iter()
+            .filter(|copy| !copy.hash.is_empty())
+            .map(|copy| &copy.hash[..7.min(copy.hash.len())])
+            .collect();
+        // Collapsed entries always list their commits
+        let hash = if (config.include_hashes || copies.len() > 1) && !hashes.is_empty() {
+            format!(" ({})", hashes.join(", "))
+        } else {
+            String::new()
+        };
+
+        let mut authors: Vec<String> = Vec::new();
+        for author in copies.iter().copied().flat_map(Self::authors) {
+            let author = format!("@{author}");
+            if !authors.contains(&author) {
+                authors.push(author);
+            }
+        }
+        let author = if config.include_authors && !authors.is_empty() {
+            format!(" - {}", authors.join(", "))
+        } else {
+            String::new()
+        };
+
+        let text = Self::entry_text(commit, github_url, config);
+        format!("{emoji}{scope}{text}{hash}{author}")
+    }
+
+    /// Returns the issues closed by the commits of a release, without duplicates.
+    fn closed_issues(commits: &[ParsedCommit]) -> Vec<&str> {
+        let mut issues = Vec::new();
+        for issue in commits.iter().flat_map(ParsedCommit::closed_issues) {
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+        issues
+    }
+
+    /// Returns the authors of a commit, by GitHub login when known, followed
+    /// by its co-authors.
+    fn authors(commit: &ParsedCommit) -> impl Iterator<Item = &str> {
+        std::iter::once(commit.author_login().unwrap_or(&commit.author))
+            .chain(commit.co_authors())
+            .filter(|author| !author.is_empty())
+    }
+
+    /// Returns the authors of the commits of a release, without duplicates.
+    fn contributors(commits: &[ParsedCommit]) -> Vec<&str> {
+        let mut contributors = Vec::new();
+        for author in commits.iter().flat_map(Self::authors) {
+            if !contributors.contains(&author) {
+                contributors.push(author);
+            }
+        }
+        contributors
+    }
+
+    /// Returns the summary line of a release, not counting changelog
+    /// fragments as commits.
+    fn summary(commits: &[ParsedCommit]) -> String {
+        let count = commits
+            .iter()
+            .filter(|commit| commit.fragment().is_none())
+            .count();
+        let mut summary = format!(
+            "{} by {}",
+            plural(count, "commit"),
+            plural(Self::contributors(commits).len(), "contributor")
+        );
+
+        let breaking = commits.iter().filter(|commit| commit.breaking).count();
+        if breaking > 0 {
+            _ = write!(
+                summary,
+                ", with **{}**",
+                plural(breaking, "breaking change")
+            );
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Returns the sections of the configuration, or a section per type of
+    /// [`Self::section_order`].
+    fn sections(config: &FormatterConfig) -> Vec<FormatterSection> {
+        if !config.sections.is_empty() {
+            return config.sections.clone();
+        }
+        Self::section_order()
+            .into_iter()
+            .map(|commit_type| FormatterSection {
+                types: vec![commit_type.to_string()],
+                title: Self::type_label(commit_type, config),
+            })
+            .collect()
+    }
+
     /// Order for displaying sections.
     fn section_order() -> Vec<&'static str> {
         vec![
@@ -94,60 +384,111 @@ impl ChangelogFormatter for KeepAChangelogFormatter {
     fn format(&self, release: &Release, config: &FormatterConfig) -> String {
         let mut output = String::new();
 
+        // Commits left out with a `Changelog: skip` footer
+        let commits: Vec<ParsedCommit> = release
+            .commits
+            .iter()
+            .filter(|commit| !commit.is_changelog_skipped())
+            .cloned()
+            .collect();
+
         // Header
         let date = release.date.format("%Y-%m-%d");
         _ = writeln!(output, "## [{}] - {}\n", release.version, date);
 
-        // Group commits
-        let groups = Self::group_by_type(&release.commits);
+        if config.summary && !commits.is_empty() {
+            _ = writeln!(output, "{}\n", Self::summary(&commits));
+        }
+
+        // Group commits, leaving out hidden types and duplicate entries
+        let mut shown: Vec<ParsedCommit> = Vec::new();
+        for commit in &commits {
+            if config.hide_types.contains(&commit.r#type)
+                || (config.deduplicate && shown.iter().any(|c| Self::same_entry(c, commit)))
+            {
+                continue;
+            }
+            shown.push(commit.clone());
+        }
+        let github_url = github_url(release, config);
+
+        // Sections before the type sections
+        if config.highlights {
+            Self::push_highlights(&mut output, &shown, github_url, config);
+        }
+        if config.breaking_changes {
+            Self::push_breaking_changes(&mut output, &shown, github_url, config);
+            // Listed in the breaking changes section instead of their own
+            shown.retain(|commit| commit.r#type != "breaking");
+        }
+        let groups = Self::group_by_type(&shown);
 
         // Output in order
-        for commit_type in Self::section_order() {
-            if let Some(commits) = groups.get(commit_type) {
-                let label = Self::type_label(commit_type, config);
+        let sections = Self::sections(config);
+        for section in &sections {
+            let entries: Vec<&ParsedCommit> = shown
+                .iter()
+                .filter(|commit| section.types.contains(&commit.r#type))
+                .collect();
+            if !entries.is_empty() {
+                let first_type = section.types.first().map_or("", String::as_str);
+                let label = Self::decorate(section.title.clone(), first_type, config);
                 _ = writeln!(output, "### {label}\n");
 
-                for commit in commits {
-                    let scope = commit
-                        .scope
-                        .as_ref()
-                        .filter(|s| !s.is_empty())
-                        .map(|s| format!("**{s}:** "))
-                        .unwrap_or_default();
-
-                    let hash = if config.include_hashes {
-                        format!(" ({})", &commit.hash[..7.min(commit.hash.len())])
-                    } else {
-                        String::new()
-                    };
-
-                    let author = if config.include_authors {
-                        format!(" - @{}", commit.author)
-                    } else {
-                        String::new()
-                    };
-
-                    _ = writeln!(output, "- {scope}{}{hash}{author}", commit.message);
+                for commit in entries {
+                    let item = Self::section_item(commit, &commits, github_url, config);
+                    push_item(&mut output, &item, config);
                 }
 
                 output.push('\n');
             }
         }
 
-        // Handle unknown types
+        // Handle types without a section
         for (commit_type, commits) in &groups {
-            if !Self::section_order().contains(&commit_type.as_str()) {
-                let label = Self::type_label(commit_type, config);
+            if !sections
+                .iter()
+                .any(|section| section.types.contains(commit_type))
+            {
+                let label = Self::heading(commit_type, config);
                 _ = writeln!(output, "### {label}\n");
 
                 for commit in commits {
-                    _ = writeln!(output, "- {}", commit.message);
+                    let emoji = Self::emoji_prefix(commit, config);
+                    let text = Self::entry_text(commit, github_url, config);
+                    push_item(&mut output, &format!("{emoji}{text}"), config);
                 }
 
                 output.push('\n');
             }
         }
 
+        // Issues closed by the release
+        let issues = Self::closed_issues(&commits);
+        if config.closed_issues && !issues.is_empty() {
+            let label = Self::heading("closed_issues", config);
+            _ = writeln!(output, "### {label}\n");
+            for issue in issues {
+                let item = match github_url {
+                    Some(url) => format!("[#{issue}]({url}/issues/{issue})"),
+                    None => format!("#{issue}"),
+                };
+                push_item(&mut output, &item, config);
+            }
+            output.push('\n');
+        }
+
+        // Authors and co-authors of the release
+        let contributors = Self::contributors(&commits);
+        if config.contributors && !contributors.is_empty() {
+            let label = Self::heading("contributors", config);
+            _ = writeln!(output, "### {label}\n");
+            for contributor in contributors {
+                push_item(&mut output, &format!("@{contributor}"), config);
+            }
+            output.push('\n');
+        }
+
         // Comparison link
         if let (Some(prev), Some(repo_url)) = (&release.previous_version, &release.repository_url) {
             _ = writeln!(
@@ -161,11 +502,104 @@ impl ChangelogFormatter for KeepAChangelogFormatter {
     }
 }
 
+/// Indents the lines of a list item after the first one, leaving blank
+/// lines empty.
+fn indent_continuation(text: &str) -> String {
+    let mut lines = text.lines();
+    let mut indented = lines.next().unwrap_or_default().to_string();
+    for line in lines {
+        indented.push('\n');
+        if !line.is_empty() {
+            _ = write!(indented, "  {line}");
+        }
+    }
+    indented
+}
+
+/// Indentation of the lines continuing a list item.
+const CONTINUATION_INDENT: &str = "  ";
+
+/// Writes a list item with the configured bullet, wrapped to the configured
+/// line width.
+fn push_item(output: &mut String, item: &str, config: &FormatterConfig) {
+    let item = format!("{} {item}", config.bullet.as_str());
+    match config.line_width {
+        Some(width) => {
+            let lines: Vec<String> = item
+                .split('\n')
+                .map(|line| wrap_line(line, width, CONTINUATION_INDENT))
+                .collect();
+            _ = writeln!(output, "{}", lines.join("\n"));
+        }
+        None => _ = writeln!(output, "{item}"),
+    }
+}
+
+/// Returns a count followed by a noun, pluralized when needed.
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{count} {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+/// Returns the repository URL of a release when references are linked to
+/// GitHub.
+fn github_url<'a>(release: &'a Release, config: &FormatterConfig) -> Option<&'a str> {
+    if !config.link_references {
+        return None;
+    }
+
+    let url = release.repository_url.as_deref()?.trim_end_matches('/');
+    let address = url.split_once("://").map_or(url, |(_, address)| address);
+    address
+        .split('/')
+        .next()
+        .is_some_and(|host| host.eq_ignore_ascii_case("github.com"))
+        .then_some(url)
+}
+
+/// Turns the `#123` references of a text into links to a GitHub repository.
+///
+/// References of other repositories (`owner/repo#123`) are left as is.
+fn link_references(text: &str, url: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('#') {
+        output.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        let (number, tail) = after.split_at(
+            after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len()),
+        );
+
+        let standalone = output
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == '(')
+            && !tail.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if !number.is_empty() && standalone {
+            _ = write!(output, "[#{number}]({url}/issues/{number})");
+        } else {
+            output.push('#');
+            output.push_str(number);
+        }
+        rest = tail;
+    }
+
+    output.push_str(rest);
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
     use semver::Version;
+    use unduler_plugin::{ListBullet, MessageCase, TrailingPunctuation};
 
     fn make_commit(commit_type: &str, message: &str) -> ParsedCommit {
         ParsedCommit::builder("abc1234567890", commit_type)
@@ -280,6 +714,347 @@ mod tests {
         assert!(output.contains("- @testuser"));
     }
 
+    #[test]
+    fn test_format_with_author_login() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commit = ParsedCommit::builder("abc1234567890", "feat")
+            .message("add feature")
+            .author("Test User")
+            .metadata(ParsedCommit::AUTHOR_LOGIN_METADATA, "octocat")
+            .build();
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), vec![commit]);
+        let config = FormatterConfig {
+            include_authors: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("- add feature - @octocat"));
+    }
+
+    #[test]
+    fn test_format_with_co_authors() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commit = ParsedCommit::builder("abc1234567890", "feat")
+            .message("add feature")
+            .author("Test User")
+            .co_authors(&["Jane Doe".to_string()])
+            .build();
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), vec![commit]);
+        let config = FormatterConfig {
+            include_authors: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("- add feature - @Test User, @Jane Doe"));
+    }
+
+    #[test]
+    fn test_format_contributors() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "feat")
+                .message("add feature")
+                .author("Test User")
+                .metadata(ParsedCommit::AUTHOR_LOGIN_METADATA, "octocat")
+                .co_authors(&["Jane Doe".to_string()])
+                .build(),
+            ParsedCommit::builder("def1234567890", "fix")
+                .message("fix bug")
+                .author("Jane Doe")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            contributors: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("### Contributors\n\n- @octocat\n- @Jane Doe\n"));
+        assert!(!output.contains("add feature - @"));
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(!output.contains("### Contributors"));
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            make_commit("feat", "add feature"),
+            ParsedCommit::builder("def1234567890", "fix")
+                .message("drop v1 API")
+                .breaking(true)
+                .author("Jane Doe")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(2, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            summary: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(
+            output.contains("\n\n2 commits by 2 contributors, with **1 breaking change**.\n\n###")
+        );
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(!output.contains("2 commits"));
+    }
+
+    #[test]
+    fn test_format_highlights() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            make_commit("fix", "fix typo"),
+            ParsedCommit::builder("abc1234567890", "feat")
+                .scope("engine")
+                .message("twice as fast")
+                .metadata(ParsedCommit::HIGHLIGHT_METADATA, "true")
+                .build(),
+            ParsedCommit::builder("def1234567890", "feat")
+                .message("drop v1 API")
+                .breaking(true)
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(2, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            highlights: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(
+            output.contains(
+                "### Highlights\n\n- **engine:** twice as fast\n- drop v1 API\n\n### Added"
+            )
+        );
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(!output.contains("### Highlights"));
+    }
+
+    #[test]
+    fn test_format_with_custom_sections() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            make_commit("ci", "cache builds"),
+            make_commit("feat", "add feature"),
+            make_commit("build", "bump toolchain"),
+            make_commit("chore", "tidy up"),
+            make_commit("docs", "fix typo"),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            sections: vec![
+                FormatterSection {
+                    types: vec!["build".to_string(), "ci".to_string()],
+                    title: "Build System".to_string(),
+                },
+                FormatterSection {
+                    types: vec!["feat".to_string()],
+                    title: "Features".to_string(),
+                },
+            ],
+            hide_types: vec!["chore".to_string()],
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains(
+            "### Build System\n\n- cache builds\n- bump toolchain\n\n### Features\n\n- add feature\n"
+        ));
+        assert!(output.contains("### Documentation\n\n- fix typo\n"));
+        assert!(!output.contains("tidy up"));
+        assert!(!output.contains("### Added"));
+    }
+
+    #[test]
+    fn test_format_breaking_changes() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "feat")
+                .scope("config")
+                .message("new config format")
+                .breaking_change(Some("`unduler.yml` is no longer read"))
+                .build(),
+            ParsedCommit::builder("def1234567890", "fix")
+                .message("reject empty tags")
+                .breaking(true)
+                .build(),
+            ParsedCommit::builder("", "breaking")
+                .message("Remove the v1 API.")
+                .breaking(true)
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(2, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            breaking_changes: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains(
+            "### Breaking Changes\n\n- **config:** `unduler.yml` is no longer read\n- reject empty tags\n- Remove the v1 API.\n\n### Added"
+        ));
+        assert!(output.contains("### Added\n\n- **config:** new config format\n"));
+        assert_eq!(output.matches("### Breaking Changes").count(), 1);
+        assert_eq!(output.matches("Remove the v1 API.").count(), 1);
+    }
+
+    #[test]
+    fn test_format_deduplicate() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "fix")
+                .scope("auth")
+                .message("fix login redirect")
+                .author("Test User")
+                .build(),
+            make_commit("feat", "add feature"),
+            ParsedCommit::builder("def1234567890", "fix")
+                .scope("auth")
+                .message("fix login redirect")
+                .author("Jane Doe")
+                .build(),
+            ParsedCommit::builder("fed1234567890", "fix")
+                .message("fix login redirect")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 1), Utc::now(), commits);
+        let config = FormatterConfig {
+            deduplicate: true,
+            include_authors: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains(
+            "- **auth:** fix login redirect (abc1234, def1234) - @Test User, @Jane Doe\n- fix login redirect"
+        ));
+        assert_eq!(output.matches("**auth:** fix login redirect").count(), 1);
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert_eq!(output.matches("**auth:** fix login redirect").count(), 2);
+    }
+
+    #[test]
+    fn test_format_with_style() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![make_commit(
+            "feat",
+            "add a login page supporting single sign-on providers.",
+        )];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            line_width: Some(40),
+            message_case: MessageCase::Sentence,
+            trailing_punctuation: TrailingPunctuation::Strip,
+            bullet: ListBullet::Asterisk,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(
+            output.contains(
+                "### Added\n\n* Add a login page supporting single\n  sign-on providers\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_with_emoji() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "feat")
+                .scope("auth")
+                .message("add login")
+                .emoji("🎉")
+                .build(),
+            make_commit("fix", "fix typo"),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            show_emoji: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("### ✨ Added\n\n- 🎉 **auth:** add login\n"));
+        assert!(output.contains("### 🐛 Fixed\n\n- fix typo\n"));
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(output.contains("### Added\n\n- **auth:** add login\n"));
+    }
+
+    #[test]
+    fn test_summary_without_breaking_changes() {
+        let commits = vec![make_commit("feat", "add feature")];
+        assert_eq!(
+            KeepAChangelogFormatter::summary(&commits),
+            "1 commit by 1 contributor."
+        );
+    }
+
+    #[test]
+    fn test_format_with_changelog_footer() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "fix")
+                .message("retry on 503")
+                .changelog_entry(Some("Uploads no longer fail when the server is busy"))
+                .build(),
+            ParsedCommit::builder("def1234567890", "chore")
+                .message("bump deps")
+                .changelog_entry(Some("skip"))
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let output = formatter.format(&release, &FormatterConfig::default());
+
+        assert!(output.contains("- Uploads no longer fail when the server is busy\n"));
+        assert!(!output.contains("retry on 503"));
+        assert!(!output.contains("bump deps"));
+        assert!(!output.contains("### Maintenance"));
+    }
+
+    #[test]
+    fn test_format_with_fragment() {
+        let formatter = KeepAChangelogFormatter::new();
+        let fragment = ParsedCommit::builder("", "feat")
+            .message("Add single sign-on.\n\nConfigure it under `auth.sso`.")
+            .metadata(ParsedCommit::FRAGMENT_METADATA, "12.feat.md")
+            .build();
+        let commits = vec![make_commit("feat", "add feature"), fragment];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let config = FormatterConfig {
+            include_hashes: true,
+            include_authors: true,
+            summary: true,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("1 commit by 1 contributor."));
+        assert!(output.contains("- add feature (abc1234) - @testuser\n"));
+        assert!(output.contains("- Add single sign-on.\n\n  Configure it under `auth.sso`.\n"));
+    }
+
     #[test]
     fn test_format_with_comparison_link() {
         let formatter = KeepAChangelogFormatter::new();
@@ -338,6 +1113,137 @@ mod tests {
         assert!(!output.contains("### Added"));
     }
 
+    #[test]
+    fn test_format_with_custom_section_labels() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            ParsedCommit::builder("abc1234567890", "feat")
+                .message("add feature")
+                .author("Test User")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits);
+        let mut type_labels = HashMap::new();
+        type_labels.insert("feat".to_string(), "Ajouté".to_string());
+        type_labels.insert("contributors".to_string(), "Contributeurs".to_string());
+        let config = FormatterConfig {
+            contributors: true,
+            type_labels,
+            ..Default::default()
+        };
+        let output = formatter.format(&release, &config);
+
+        assert!(output.contains("### Ajouté\n\n- add feature\n"));
+        assert!(output.contains("### Contributeurs\n\n- @Test User\n"));
+    }
+
+    fn github_config() -> FormatterConfig {
+        FormatterConfig {
+            link_references: true,
+            closed_issues: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_link_references() {
+        let url = "https://github.com/user/repo";
+        assert_eq!(
+            link_references("fix crash (#12)", url),
+            "fix crash ([#12](https://github.com/user/repo/issues/12))"
+        );
+        assert_eq!(
+            link_references("#3 and #4", url),
+            "[#3](https://github.com/user/repo/issues/3) and [#4](https://github.com/user/repo/issues/4)"
+        );
+        assert_eq!(link_references("see other/repo#5", url), "see other/repo#5");
+        assert_eq!(link_references("color #fff, #12a", url), "color #fff, #12a");
+        assert_eq!(link_references("issue #", url), "issue #");
+    }
+
+    #[test]
+    fn test_github_url() {
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), vec![])
+            .with_repository_url("https://github.com/user/repo/");
+        assert_eq!(
+            github_url(&release, &github_config()),
+            Some("https://github.com/user/repo")
+        );
+        assert_eq!(github_url(&release, &FormatterConfig::default()), None);
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), vec![])
+            .with_repository_url("https://gitlab.com/user/repo");
+        assert_eq!(github_url(&release, &github_config()), None);
+    }
+
+    #[test]
+    fn test_format_links_references() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            make_commit("fix", "handle timeouts (#12)"),
+            ParsedCommit::builder("def4567", "feat")
+                .message("add export")
+                .metadata("pr", "34")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits)
+            .with_repository_url("https://github.com/user/repo");
+        let output = formatter.format(&release, &github_config());
+
+        assert!(
+            output.contains("- handle timeouts ([#12](https://github.com/user/repo/issues/12))")
+        );
+        assert!(output.contains("- add export ([#34](https://github.com/user/repo/pull/34))"));
+    }
+
+    #[test]
+    fn test_format_references_without_github() {
+        let formatter = KeepAChangelogFormatter::new();
+        let commits = vec![
+            make_commit("fix", "handle timeouts (#12)"),
+            ParsedCommit::builder("def4567", "feat")
+                .message("add export")
+                .metadata("pr", "#34")
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits)
+            .with_repository_url("https://gitlab.com/user/repo");
+        let output = formatter.format(&release, &github_config());
+
+        assert!(output.contains("- handle timeouts (#12)"));
+        assert!(output.contains("- add export (#34)"));
+    }
+
+    #[test]
+    fn test_format_closed_issues() {
+        let formatter = KeepAChangelogFormatter::new();
+        let issues = ["12".to_string(), "34".to_string()];
+        let commits = vec![
+            ParsedCommit::builder("abc1234", "fix")
+                .message("handle timeouts")
+                .closed_issues(&issues)
+                .build(),
+            ParsedCommit::builder("def4567", "fix")
+                .message("retry once")
+                .closed_issues(&issues[..1])
+                .build(),
+        ];
+
+        let release = Release::new(Version::new(1, 0, 0), Utc::now(), commits)
+            .with_repository_url("https://github.com/user/repo");
+        let output = formatter.format(&release, &github_config());
+
+        assert!(output.contains(
+            "### Closed Issues\n\n- [#12](https://github.com/user/repo/issues/12)\n- [#34](https://github.com/user/repo/issues/34)\n"
+        ));
+
+        let output = formatter.format(&release, &FormatterConfig::default());
+        assert!(!output.contains("Closed Issues"));
+    }
+
     #[test]
     fn test_default_labels_all_types() {
         assert_eq!(KeepAChangelogFormatter::default_label("feat"), "Added");
@@ -375,6 +1281,10 @@ mod tests {
             KeepAChangelogFormatter::default_label("breaking"),
             "Breaking Changes"
         );
+        assert_eq!(
+            KeepAChangelogFormatter::default_label("closed_issues"),
+            "Closed Issues"
+        );
         assert_eq!(KeepAChangelogFormatter::default_label("unknown"), "unknown");
     }
 
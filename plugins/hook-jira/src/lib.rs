@@ -0,0 +1,319 @@
+//! JIRA integration hook plugin.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::json;
+use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, Plugin, PluginError, PluginResult, ReleaseContext,
+};
+
+/// Timeout of a JIRA API request, from connection to the end of the response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Credentials of the JIRA REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JiraAuth {
+    /// Email and API token, for JIRA Cloud.
+    Basic {
+        /// Email of the account.
+        email: String,
+        /// API token of the account.
+        token: String,
+    },
+    /// Personal access token, for JIRA Server and Data Center.
+    Bearer(String),
+}
+
+/// Hook commenting on the JIRA issues of a release, and optionally
+/// transitioning them.
+pub struct JiraHook {
+    /// Base URL of the JIRA instance, e.g. `https://acme.atlassian.net`.
+    base_url: String,
+    /// Credentials of the REST API.
+    auth: JiraAuth,
+    /// Comment template, with `{version}` and `{tag}` placeholders.
+    comment: String,
+    /// Name of the transition applied to the issues, e.g. `Released`.
+    transition: Option<String>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+/// Transitions available on an issue.
+#[derive(Debug, Deserialize)]
+struct Transitions {
+    transitions: Vec<Transition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transition {
+    id: String,
+    name: String,
+}
+
+impl JiraHook {
+    /// Creates a hook using the REST API of the JIRA instance at `base_url`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, auth: JiraAuth) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            auth,
+            comment: "Released in {version}".to_string(),
+            transition: None,
+            tag_format: "v{version}".to_string(),
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("failed to build HTTP client"),
+        }
+    }
+
+    /// Sets the comment template, with `{version}` and `{tag}` placeholders.
+    #[must_use]
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = comment.into();
+        self
+    }
+
+    /// Sets the name of the transition applied to the issues.
+    #[must_use]
+    pub fn with_transition(mut self, transition: impl Into<String>) -> Self {
+        self.transition = Some(transition.into());
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Returns the base URL of the JIRA instance.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the name of the transition applied to the issues, if set.
+    #[must_use]
+    pub fn transition(&self) -> Option<&str> {
+        self.transition.as_deref()
+    }
+
+    /// Returns the keys of the issues referenced by the release commits,
+    /// sorted and deduplicated.
+    #[must_use]
+    pub fn issue_keys(ctx: &ReleaseContext) -> Vec<&str> {
+        ctx.commits
+            .iter()
+            .flat_map(|commit| commit.tickets())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Renders the comment added to the issues.
+    #[must_use]
+    pub fn comment(&self, ctx: &ReleaseContext) -> String {
+        self.comment
+            .replace("{version}", &ctx.next_version.to_string())
+            .replace("{tag}", &ctx.tag(&self.tag_format))
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/rest/api/2/{path}", self.base_url));
+        match &self.auth {
+            JiraAuth::Basic { email, token } => request.basic_auth(email, Some(token)),
+            JiraAuth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+
+    async fn send(
+        &self,
+        key: &str,
+        request: reqwest::RequestBuilder,
+    ) -> PluginResult<reqwest::Response> {
+        let failed = |reason: String| {
+            PluginError::ExecutionFailed(format!("failed to update JIRA issue {key}: {reason}"))
+        };
+        let response = request.send().await.map_err(|e| failed(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(failed(response.status().to_string()));
+        }
+        Ok(response)
+    }
+
+    async fn update_issue(&self, key: &str, comment: &str) -> PluginResult<()> {
+        let path = format!("issue/{key}/comment");
+        self.send(
+            key,
+            self.request(reqwest::Method::POST, &path)
+                .json(&json!({ "body": comment })),
+        )
+        .await?;
+
+        let Some(ref name) = self.transition else {
+            return Ok(());
+        };
+
+        let path = format!("issue/{key}/transitions");
+        let transitions: Transitions = self
+            .send(key, self.request(reqwest::Method::GET, &path))
+            .await?
+            .json()
+            .await
+            .map_err(|e| {
+                PluginError::ExecutionFailed(format!(
+                    "failed to read transitions of JIRA issue {key}: {e}"
+                ))
+            })?;
+
+        // Issues already transitioned no longer offer the transition
+        let Some(transition) = transitions
+            .transitions
+            .iter()
+            .find(|transition| transition.name.eq_ignore_ascii_case(name))
+        else {
+            return Ok(());
+        };
+
+        self.send(
+            key,
+            self.request(reqwest::Method::POST, &path)
+                .json(&json!({ "transition": { "id": transition.id } })),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl Plugin for JiraHook {
+    fn name(&self) -> &'static str {
+        "jira"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Comments on and transitions the JIRA issues of a release"
+    }
+}
+
+impl AsyncReleaseHook for JiraHook {
+    fn on_post_release<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async move {
+            if ctx.dry_run {
+                return Ok(());
+            }
+
+            let comment = self.comment(ctx);
+            for key in Self::issue_keys(ctx) {
+                self.update_issue(key, &comment).await?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_plugin::test_util::poll_once;
+
+    use semver::Version;
+    use unduler_commit::ParsedCommit;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context(commits: Vec<ParsedCommit>) -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            commits,
+        )
+    }
+
+    fn create_hook() -> JiraHook {
+        JiraHook::new(
+            "https://acme.atlassian.net/",
+            JiraAuth::Bearer("token".to_string()),
+        )
+    }
+
+    fn ticket(hash: &str, tickets: &str) -> ParsedCommit {
+        ParsedCommit::builder(hash, "feat")
+            .metadata(ParsedCommit::TICKET_METADATA, tickets)
+            .build()
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = create_hook();
+        assert_eq!(hook.base_url(), "https://acme.atlassian.net");
+        assert!(hook.transition().is_none());
+    }
+
+    #[test]
+    fn test_issue_keys() {
+        let ctx = create_test_context(vec![
+            ticket("a1", "PROJ-2"),
+            ticket("b2", "PROJ-1,PROJ-2"),
+            ParsedCommit::builder("c3", "fix").build(),
+        ]);
+        assert_eq!(JiraHook::issue_keys(&ctx), vec!["PROJ-1", "PROJ-2"]);
+    }
+
+    #[test]
+    fn test_comment() {
+        let ctx = create_test_context(vec![]);
+        assert_eq!(create_hook().comment(&ctx), "Released in 1.1.0");
+
+        let hook = create_hook()
+            .with_tag_format("release-{version}")
+            .with_comment("Shipped in {tag}");
+        assert_eq!(hook.comment(&ctx), "Shipped in release-1.1.0");
+    }
+
+    #[test]
+    fn test_with_transition() {
+        let hook = create_hook().with_transition("Released");
+        assert_eq!(hook.transition(), Some("Released"));
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(create_hook().name(), "jira");
+    }
+
+    #[test]
+    fn test_on_post_release_dry_run() {
+        let hook = create_hook();
+        let ctx = create_test_context(vec![ticket("a1", "PROJ-1")]).dry_run(true);
+        assert!(poll_once(hook.on_post_release(&ctx)).is_ok());
+    }
+
+    #[test]
+    fn test_on_post_release_without_issues() {
+        let hook = create_hook();
+        let ctx = create_test_context(vec![]);
+        assert!(poll_once(hook.on_post_release(&ctx)).is_ok());
+    }
+}
@@ -0,0 +1,365 @@
+//! Maven and Gradle publish hook plugin.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use unduler_plugin::{Plugin, PluginError, PluginResult, ReleaseContext, ReleaseHook};
+
+/// Build tool of a JVM project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildTool {
+    /// Gradle, through `./gradlew` when the wrapper exists.
+    Gradle,
+    /// Maven, through `./mvnw` when the wrapper exists.
+    Maven,
+}
+
+impl BuildTool {
+    /// Detects the build tool of the project at `root`, preferring Gradle.
+    #[must_use]
+    pub fn detect(root: &Path) -> Option<Self> {
+        let exists = |file: &str| root.join(file).exists();
+        if ["gradlew", "build.gradle", "build.gradle.kts"]
+            .into_iter()
+            .any(exists)
+        {
+            Some(Self::Gradle)
+        } else if ["mvnw", "pom.xml"].into_iter().any(exists) {
+            Some(Self::Maven)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the tasks (Gradle) or goals (Maven) publishing the artifacts.
+    #[must_use]
+    pub fn default_tasks(self) -> &'static [&'static str] {
+        match self {
+            Self::Gradle => &["publish"],
+            Self::Maven => &["deploy"],
+        }
+    }
+
+    /// Returns the program to run, the wrapper when the project has one.
+    #[must_use]
+    pub fn program(self, root: &Path) -> PathBuf {
+        let (wrapper, program) = match self {
+            Self::Gradle => ("gradlew", "gradle"),
+            Self::Maven => ("mvnw", "mvn"),
+        };
+        let wrapper = root.join(wrapper);
+        if wrapper.exists() {
+            wrapper
+        } else {
+            PathBuf::from(program)
+        }
+    }
+}
+
+/// Hook publishing JVM artifacts with Maven or Gradle.
+pub struct JvmHook {
+    /// Build tool, detected from the project files when unset.
+    tool: Option<BuildTool>,
+    /// Tasks or goals to run instead of the defaults.
+    tasks: Vec<String>,
+    /// Maven profiles to activate.
+    profiles: Vec<String>,
+    /// Additional arguments.
+    args: Vec<String>,
+    /// Environment variables of the build.
+    env: Vec<(String, String)>,
+}
+
+impl JvmHook {
+    /// Creates a new JVM hook.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tool: None,
+            tasks: Vec::new(),
+            profiles: Vec::new(),
+            args: Vec::new(),
+            env: Vec::new(),
+        }
+    }
+
+    /// Sets the build tool instead of detecting it.
+    #[must_use]
+    pub fn with_tool(mut self, tool: BuildTool) -> Self {
+        self.tool = Some(tool);
+        self
+    }
+
+    /// Sets the tasks (Gradle) or goals (Maven) to run.
+    #[must_use]
+    pub fn with_tasks(mut self, tasks: Vec<String>) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    /// Sets the Maven profiles to activate.
+    #[must_use]
+    pub fn with_profiles(mut self, profiles: Vec<String>) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    /// Sets additional arguments.
+    #[must_use]
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Adds an environment variable to the build.
+    #[must_use]
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the build tool, if set.
+    #[must_use]
+    pub fn tool(&self) -> Option<BuildTool> {
+        self.tool
+    }
+
+    /// Returns the tasks or goals to run instead of the defaults.
+    #[must_use]
+    pub fn tasks(&self) -> &[String] {
+        &self.tasks
+    }
+
+    /// Returns the Maven profiles to activate.
+    #[must_use]
+    pub fn profiles(&self) -> &[String] {
+        &self.profiles
+    }
+
+    /// Returns the program and arguments publishing the project at `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no build tool is set or detected, or if profiles
+    /// are set for Gradle.
+    pub fn invocation(&self, root: &Path) -> PluginResult<(PathBuf, Vec<String>)> {
+        let tool = self
+            .tool
+            .or_else(|| BuildTool::detect(root))
+            .ok_or_else(|| {
+                PluginError::ConfigError(
+                    "no Gradle or Maven build found, set the build tool".to_string(),
+                )
+            })?;
+
+        let mut args = Vec::new();
+        match tool {
+            BuildTool::Gradle if !self.profiles.is_empty() => {
+                return Err(PluginError::ConfigError(
+                    "profiles are only supported by Maven".to_string(),
+                ));
+            }
+            BuildTool::Gradle => {}
+            BuildTool::Maven => {
+                args.push("-B".to_string());
+                if !self.profiles.is_empty() {
+                    args.push(format!("-P{}", self.profiles.join(",")));
+                }
+            }
+        }
+        args.extend(self.args.iter().cloned());
+
+        if self.tasks.is_empty() {
+            args.extend(tool.default_tasks().iter().map(ToString::to_string));
+        } else {
+            args.extend(self.tasks.iter().cloned());
+        }
+
+        Ok((tool.program(root), args))
+    }
+}
+
+impl Default for JvmHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for JvmHook {
+    fn name(&self) -> &'static str {
+        "jvm"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Publishes JVM artifacts with Maven or Gradle"
+    }
+}
+
+impl ReleaseHook for JvmHook {
+    fn on_post_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        if ctx.dry_run {
+            return Ok(());
+        }
+
+        let (program, args) = self.invocation(&ctx.repo_path)?;
+        let output = Command::new(&program)
+            .args(&args)
+            .envs(self.env.iter().map(|(name, value)| (name, value)))
+            .current_dir(&ctx.repo_path)
+            .output()?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        tracing::debug!("{} output:\n{stdout}{stderr}", program.display());
+        if !output.status.success() {
+            return Err(PluginError::ExecutionFailed(format!(
+                "{} {} failed with {}: {}",
+                program.display(),
+                args.join(" "),
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use semver::Version;
+    use tempfile::TempDir;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn project(files: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for file in files {
+            fs::write(dir.path().join(file), "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = JvmHook::new();
+        assert!(hook.tool().is_none());
+        assert!(hook.tasks().is_empty());
+        assert!(hook.profiles().is_empty());
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            BuildTool::detect(project(&["build.gradle.kts"]).path()),
+            Some(BuildTool::Gradle)
+        );
+        assert_eq!(
+            BuildTool::detect(project(&["pom.xml"]).path()),
+            Some(BuildTool::Maven)
+        );
+        assert_eq!(BuildTool::detect(project(&[]).path()), None);
+    }
+
+    #[test]
+    fn test_gradle_invocation_uses_wrapper() {
+        let dir = project(&["gradlew", "build.gradle"]);
+        let (program, args) = JvmHook::new().invocation(dir.path()).unwrap();
+        assert_eq!(program, dir.path().join("gradlew"));
+        assert_eq!(args, ["publish"]);
+    }
+
+    #[test]
+    fn test_maven_invocation() {
+        let dir = project(&["pom.xml"]);
+        let hook = JvmHook::new()
+            .with_profiles(vec!["release".to_string(), "sign".to_string()])
+            .with_args(vec!["-DskipTests".to_string()])
+            .with_tasks(vec!["clean".to_string(), "deploy".to_string()]);
+        let (program, args) = hook.invocation(dir.path()).unwrap();
+        assert_eq!(program, PathBuf::from("mvn"));
+        assert_eq!(
+            args,
+            ["-B", "-Prelease,sign", "-DskipTests", "clean", "deploy"]
+        );
+    }
+
+    #[test]
+    fn test_explicit_tool() {
+        let dir = project(&[]);
+        let (program, args) = JvmHook::new()
+            .with_tool(BuildTool::Gradle)
+            .invocation(dir.path())
+            .unwrap();
+        assert_eq!(program, PathBuf::from("gradle"));
+        assert_eq!(args, ["publish"]);
+    }
+
+    #[test]
+    fn test_gradle_profiles_rejected() {
+        let hook = JvmHook::new()
+            .with_tool(BuildTool::Gradle)
+            .with_profiles(vec!["release".to_string()]);
+        assert!(matches!(
+            hook.invocation(Path::new("/tmp")),
+            Err(PluginError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_no_build_tool() {
+        let dir = project(&[]);
+        assert!(JvmHook::new().invocation(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_on_post_tag_dry_run() {
+        let dir = project(&[]);
+        let mut ctx = ReleaseContext::new(
+            dir.path(),
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+        .dry_run(true);
+        assert!(JvmHook::new().on_post_tag(&mut ctx).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_on_post_tag_reports_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = project(&["build.gradle"]);
+        let wrapper = dir.path().join("gradlew");
+        fs::write(
+            &wrapper,
+            "#!/bin/sh\necho building\necho 'no repository' >&2\nexit 1\n",
+        )
+        .unwrap();
+        fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755)).unwrap();
+        let mut ctx = ReleaseContext::new(
+            dir.path(),
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        );
+
+        let err = JvmHook::new().on_post_tag(&mut ctx).unwrap_err();
+        assert!(err.to_string().ends_with(": no repository"), "{err}");
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(JvmHook::new().name(), "jvm");
+    }
+}
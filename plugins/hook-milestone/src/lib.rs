@@ -0,0 +1,490 @@
+//! GitHub and GitLab milestone hook plugin.
+
+use reqwest::{Method, RequestBuilder};
+use semver::Version;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, BumpType, Plugin, PluginError, PluginResult, ReleaseContext,
+    format_tag,
+};
+
+/// Number of items per page of the listings.
+const PER_PAGE: usize = 100;
+
+/// Code forge hosting the milestones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    /// GitHub, repositories named `owner/repo`.
+    GitHub,
+    /// GitLab, projects named by their full path.
+    GitLab,
+}
+
+impl Forge {
+    /// Returns the API URL of the public instance of this forge.
+    #[must_use]
+    pub fn default_api_url(self) -> &'static str {
+        match self {
+            Self::GitHub => "https://api.github.com",
+            Self::GitLab => "https://gitlab.com/api/v4",
+        }
+    }
+}
+
+/// Milestone returned by the GitHub API.
+#[derive(Deserialize)]
+struct GithubMilestone {
+    number: u64,
+    title: String,
+}
+
+/// Issue returned by the GitHub API.
+#[derive(Deserialize)]
+struct GithubIssue {
+    number: u64,
+}
+
+/// Milestone returned by the GitLab API.
+#[derive(Deserialize)]
+struct GitlabMilestone {
+    id: u64,
+    title: String,
+}
+
+/// Issue returned by the GitLab API.
+#[derive(Deserialize)]
+struct GitlabIssue {
+    iid: u64,
+}
+
+/// Hook closing the milestone of a release and opening the next one.
+pub struct MilestoneHook {
+    /// Forge hosting the repository.
+    forge: Forge,
+    /// API URL of the forge.
+    api_url: String,
+    /// Repository (GitHub) or project path (GitLab).
+    repository: String,
+    /// API token.
+    token: String,
+    /// Milestone title template, with `{version}` and `{tag}` placeholders.
+    title: String,
+    /// Bump from the released version to the next milestone.
+    next: BumpType,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl MilestoneHook {
+    /// Creates a hook managing the milestones of `repository`, named after
+    /// the versions, and opening the next minor milestone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built.
+    #[must_use]
+    pub fn new(forge: Forge, repository: impl Into<String>, token: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .expect("failed to build HTTP client");
+
+        Self {
+            forge,
+            api_url: forge.default_api_url().to_string(),
+            repository: repository.into(),
+            token: token.into(),
+            title: "{version}".to_string(),
+            next: BumpType::Minor,
+            tag_format: "v{version}".to_string(),
+            client,
+        }
+    }
+
+    /// Sets the API URL of a self-hosted forge.
+    #[must_use]
+    pub fn with_api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the milestone title template, e.g. `Release {version}`.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the bump from the released version to the next milestone.
+    /// [`BumpType::None`] disables the next milestone.
+    #[must_use]
+    pub fn with_next(mut self, next: BumpType) -> Self {
+        self.next = next;
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Returns the forge hosting the repository.
+    #[must_use]
+    pub fn forge(&self) -> Forge {
+        self.forge
+    }
+
+    /// Returns the API URL of the forge.
+    #[must_use]
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// Returns the title of the milestone of a version.
+    #[must_use]
+    pub fn milestone_title(&self, version: &Version) -> String {
+        self.title
+            .replace("{version}", &version.to_string())
+            .replace("{tag}", &format_tag(&self.tag_format, version, None))
+    }
+
+    /// Returns the version of the next milestone, if one is opened.
+    #[must_use]
+    pub fn next_version(&self, ctx: &ReleaseContext) -> Option<Version> {
+        let current = &ctx.next_version;
+        let (major, minor, patch) = match self.next {
+            BumpType::Major => (current.major + 1, 0, 0),
+            BumpType::Minor => (current.major, current.minor + 1, 0),
+            BumpType::Patch => (current.major, current.minor, current.patch + 1),
+            BumpType::None => return None,
+        };
+        Some(Version::new(major, minor, patch))
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = match self.forge {
+            Forge::GitHub => format!("{}/repos/{}/{path}", self.api_url, self.repository),
+            Forge::GitLab => format!(
+                "{}/projects/{}/{path}",
+                self.api_url,
+                self.repository.replace('/', "%2F")
+            ),
+        };
+        let request = self.client.request(method, url);
+        match self.forge {
+            Forge::GitHub => request
+                .bearer_auth(&self.token)
+                .header("Accept", "application/vnd.github+json"),
+            Forge::GitLab => request.header("PRIVATE-TOKEN", &self.token),
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> PluginResult<T> {
+        let failed = |reason: String| {
+            PluginError::ExecutionFailed(format!(
+                "milestone update of {} failed: {reason}",
+                self.repository
+            ))
+        };
+        let response = request.send().await.map_err(|e| failed(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(failed(response.status().to_string()));
+        }
+        response.json().await.map_err(|e| failed(e.to_string()))
+    }
+
+    /// Sends a listing request, following its pages up to the first one
+    /// that is not full.
+    async fn send_all<T: DeserializeOwned>(&self, request: RequestBuilder) -> PluginResult<Vec<T>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            let request = request
+                .try_clone()
+                .expect("requests without streamed body can be cloned")
+                .query(&[("per_page", PER_PAGE), ("page", page)]);
+            let batch: Vec<T> = self.send(request).await?;
+            let last = batch.len() < PER_PAGE;
+            items.extend(batch);
+            if last {
+                return Ok(items);
+            }
+            page += 1;
+        }
+    }
+
+    /// Returns the id of the open milestone with a title.
+    async fn find(&self, title: &str) -> PluginResult<Option<u64>> {
+        Ok(match self.forge {
+            Forge::GitHub => {
+                let request = self
+                    .request(Method::GET, "milestones")
+                    .query(&[("state", "open")]);
+                self.send_all::<GithubMilestone>(request)
+                    .await?
+                    .into_iter()
+                    .find(|milestone| milestone.title == title)
+                    .map(|milestone| milestone.number)
+            }
+            Forge::GitLab => {
+                let request = self
+                    .request(Method::GET, "milestones")
+                    .query(&[("title", title), ("state", "active")]);
+                self.send_all::<GitlabMilestone>(request)
+                    .await?
+                    .into_iter()
+                    .find(|milestone| milestone.title == title)
+                    .map(|milestone| milestone.id)
+            }
+        })
+    }
+
+    /// Returns the id of the open milestone with a title, creating it if
+    /// needed.
+    async fn find_or_create(&self, title: &str) -> PluginResult<u64> {
+        if let Some(id) = self.find(title).await? {
+            return Ok(id);
+        }
+
+        let request = self
+            .request(Method::POST, "milestones")
+            .json(&json!({ "title": title }));
+        Ok(match self.forge {
+            Forge::GitHub => self.send::<GithubMilestone>(request).await?.number,
+            Forge::GitLab => self.send::<GitlabMilestone>(request).await?.id,
+        })
+    }
+
+    /// Moves the open issues of a milestone to another.
+    ///
+    /// All the issues are listed before moving any, which would shift the
+    /// pages.
+    async fn move_open_issues(&self, from: (u64, &str), to: u64) -> PluginResult<()> {
+        match self.forge {
+            Forge::GitHub => {
+                let request = self.request(Method::GET, "issues").query(&[
+                    ("milestone", from.0.to_string().as_str()),
+                    ("state", "open"),
+                ]);
+                for issue in self.send_all::<GithubIssue>(request).await? {
+                    let path = format!("issues/{}", issue.number);
+                    let request = self
+                        .request(Method::PATCH, &path)
+                        .json(&json!({ "milestone": to }));
+                    self.send::<Value>(request).await?;
+                }
+            }
+            Forge::GitLab => {
+                let request = self
+                    .request(Method::GET, "issues")
+                    .query(&[("milestone", from.1), ("state", "opened")]);
+                for issue in self.send_all::<GitlabIssue>(request).await? {
+                    let path = format!("issues/{}", issue.iid);
+                    let request = self
+                        .request(Method::PUT, &path)
+                        .json(&json!({ "milestone_id": to }));
+                    self.send::<Value>(request).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn close(&self, id: u64) -> PluginResult<()> {
+        let path = format!("milestones/{id}");
+        let request = match self.forge {
+            Forge::GitHub => self
+                .request(Method::PATCH, &path)
+                .json(&json!({ "state": "closed" })),
+            Forge::GitLab => self
+                .request(Method::PUT, &path)
+                .json(&json!({ "state_event": "close" })),
+        };
+        self.send::<Value>(request).await?;
+        Ok(())
+    }
+}
+
+impl Plugin for MilestoneHook {
+    fn name(&self) -> &'static str {
+        "milestone"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Closes the milestone of a release and opens the next one"
+    }
+}
+
+impl AsyncReleaseHook for MilestoneHook {
+    fn on_post_tag<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async move {
+            if ctx.dry_run {
+                return Ok(());
+            }
+
+            let title = self.milestone_title(&ctx.next_version);
+            let current = self.find(&title).await?;
+
+            if let Some(next) = self.next_version(ctx) {
+                let next = self.find_or_create(&self.milestone_title(&next)).await?;
+                if let Some(current) = current {
+                    self.move_open_issues((current, &title), next).await?;
+                }
+            }
+
+            if let Some(current) = current {
+                self.close(current).await?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use unduler_plugin::test_util::poll_once;
+
+    use super::*;
+
+    /// Answers one request per body, returning the request lines.
+    fn serve(bodies: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            bodies
+                .into_iter()
+                .map(|body| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request = String::new();
+                    reader.read_line(&mut request).unwrap();
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).unwrap() > 2 {
+                        line.clear();
+                    }
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+        (url, server)
+    }
+
+    fn create_test_context(version: Version) -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            version,
+            BumpType::Minor,
+            vec![],
+        )
+    }
+
+    fn create_hook() -> MilestoneHook {
+        MilestoneHook::new(Forge::GitHub, "acme/app", "token")
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = create_hook();
+        assert_eq!(hook.forge(), Forge::GitHub);
+        assert_eq!(hook.api_url(), "https://api.github.com");
+
+        let hook = MilestoneHook::new(Forge::GitLab, "acme/app", "token")
+            .with_api_url("https://gitlab.acme.com/api/v4/");
+        assert_eq!(hook.api_url(), "https://gitlab.acme.com/api/v4");
+    }
+
+    #[test]
+    fn test_milestone_title() {
+        let version = Version::new(1, 2, 0);
+        assert_eq!(create_hook().milestone_title(&version), "1.2.0");
+
+        let hook = create_hook().with_title("Release {tag}");
+        assert_eq!(hook.milestone_title(&version), "Release v1.2.0");
+
+        let hook = hook.with_tag_format("app/{major}.{minor}");
+        assert_eq!(hook.milestone_title(&version), "Release app/1.2");
+    }
+
+    #[test]
+    fn test_next_version() {
+        let ctx = create_test_context(Version::parse("1.2.3-rc.1").unwrap());
+        assert_eq!(
+            create_hook().next_version(&ctx),
+            Some(Version::new(1, 3, 0))
+        );
+        assert_eq!(
+            create_hook().with_next(BumpType::Patch).next_version(&ctx),
+            Some(Version::new(1, 2, 4))
+        );
+        assert_eq!(
+            create_hook().with_next(BumpType::Major).next_version(&ctx),
+            Some(Version::new(2, 0, 0))
+        );
+        assert_eq!(
+            create_hook().with_next(BumpType::None).next_version(&ctx),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(create_hook().name(), "milestone");
+    }
+
+    #[test]
+    fn test_find_follows_pages() {
+        let full: Vec<_> = (1..=100)
+            .map(|number| json!({ "number": number, "title": format!("0.{number}.0") }))
+            .collect();
+        let (url, server) = serve(vec![
+            Value::from(full).to_string(),
+            json!([{ "number": 101, "title": "1.2.0" }]).to_string(),
+        ]);
+        let hook = create_hook().with_api_url(url);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let id = runtime.block_on(hook.find("1.2.0")).unwrap();
+        assert_eq!(id, Some(101));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("page=1"), "{requests:?}");
+        assert!(requests[1].contains("page=2"), "{requests:?}");
+    }
+
+    #[test]
+    fn test_on_post_tag_dry_run() {
+        let hook = create_hook();
+        let ctx = create_test_context(Version::new(1, 1, 0)).dry_run(true);
+        assert!(poll_once(hook.on_post_tag(&ctx)).is_ok());
+    }
+}
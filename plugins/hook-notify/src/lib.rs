@@ -0,0 +1,462 @@
+//! Slack, Discord and Teams notification hook plugin.
+
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, Plugin, PluginError, PluginResult, ReleaseContext,
+};
+
+/// Chat service receiving a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelKind {
+    /// Slack incoming webhook.
+    Slack,
+    /// Discord webhook.
+    Discord,
+    /// Microsoft Teams incoming webhook.
+    Teams,
+}
+
+impl ChannelKind {
+    /// Returns the webhook payload posting a message to this service.
+    #[must_use]
+    pub fn payload(self, message: &str) -> Value {
+        match self {
+            Self::Slack | Self::Teams => json!({ "text": message }),
+            Self::Discord => json!({ "content": message }),
+        }
+    }
+
+    /// Returns the name of this service.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+            Self::Teams => "teams",
+        }
+    }
+}
+
+/// Timeout of a webhook request, from connection to the end of the response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A webhook notified after a release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Channel {
+    /// Chat service of the webhook.
+    kind: ChannelKind,
+    /// Webhook URL.
+    webhook_url: String,
+    /// Message template overriding the hook template.
+    template: Option<String>,
+}
+
+impl Channel {
+    /// Creates a channel posting to a webhook.
+    #[must_use]
+    pub fn new(kind: ChannelKind, webhook_url: impl Into<String>) -> Self {
+        Self {
+            kind,
+            webhook_url: webhook_url.into(),
+            template: None,
+        }
+    }
+
+    /// Sets the message template of this channel.
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Returns the chat service of the webhook.
+    #[must_use]
+    pub fn kind(&self) -> ChannelKind {
+        self.kind
+    }
+
+    /// Returns the webhook URL.
+    #[must_use]
+    pub fn webhook_url(&self) -> &str {
+        &self.webhook_url
+    }
+
+    /// Returns the message template of this channel, if set.
+    #[must_use]
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+}
+
+/// Notification hook posting release announcements to chat webhooks.
+pub struct NotifyHook {
+    /// Webhooks to notify.
+    channels: Vec<Channel>,
+    /// Default message template.
+    template: Option<String>,
+    /// Link to the changelog of the release.
+    changelog_url: Option<String>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+    /// Whether failed releases are announced.
+    failures: bool,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl NotifyHook {
+    /// Creates a new notification hook.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            channels: Vec::new(),
+            template: None,
+            changelog_url: None,
+            tag_format: "v{version}".to_string(),
+            failures: false,
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("failed to build HTTP client"),
+        }
+    }
+
+    /// Adds a webhook to notify.
+    #[must_use]
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    /// Sets the message template of channels without their own.
+    ///
+    /// Supported placeholders are `{version}`, `{previous_version}`,
+    /// `{tag}`, `{highlights}` and `{changelog_url}`.
+    #[must_use]
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Sets the link to the changelog, which may contain `{version}` and
+    /// `{tag}` placeholders.
+    #[must_use]
+    pub fn with_changelog_url(mut self, url: impl Into<String>) -> Self {
+        self.changelog_url = Some(url.into());
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Sets whether failed releases are announced, with their error.
+    #[must_use]
+    pub fn with_failures(mut self, failures: bool) -> Self {
+        self.failures = failures;
+        self
+    }
+
+    /// Returns the webhooks to notify.
+    #[must_use]
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Returns the default message template, if set.
+    #[must_use]
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+
+    /// Returns the link to the changelog, if set.
+    #[must_use]
+    pub fn changelog_url(&self) -> Option<&str> {
+        self.changelog_url.as_deref()
+    }
+
+    /// Renders the message posted to a channel.
+    #[must_use]
+    pub fn message(&self, channel: &Channel, ctx: &ReleaseContext) -> String {
+        let tag = ctx.tag(&self.tag_format);
+        let version = ctx.next_version.to_string();
+        let changelog_url = self
+            .changelog_url
+            .as_deref()
+            .map(|url| url.replace("{version}", &version).replace("{tag}", &tag));
+        let highlights = highlights(ctx);
+
+        let Some(template) = channel.template().or(self.template()) else {
+            let mut message = format!("Released {tag}");
+            if !highlights.is_empty() {
+                message.push_str("\n\nHighlights:\n");
+                message.push_str(&highlights);
+            }
+            if let Some(url) = changelog_url {
+                message.push_str("\n\n");
+                message.push_str(&url);
+            }
+            return message;
+        };
+
+        template
+            .replace("{version}", &version)
+            .replace("{previous_version}", &ctx.previous_version.to_string())
+            .replace("{tag}", &tag)
+            .replace("{highlights}", &highlights)
+            .replace(
+                "{changelog_url}",
+                changelog_url.as_deref().unwrap_or_default(),
+            )
+    }
+
+    /// Renders the message announcing a failed release.
+    #[must_use]
+    pub fn failure_message(&self, ctx: &ReleaseContext) -> String {
+        let tag = ctx.tag(&self.tag_format);
+        match &ctx.error {
+            Some(error) => format!("Release {tag} failed: {error}"),
+            None => format!("Release {tag} failed"),
+        }
+    }
+
+    async fn notify(&self, channel: &Channel, message: &str) -> PluginResult<()> {
+        let payload = channel.kind().payload(message);
+        let failed = |reason: String| {
+            PluginError::ExecutionFailed(format!(
+                "failed to notify {}: {reason}",
+                channel.kind().name()
+            ))
+        };
+
+        let response = self
+            .client
+            .post(channel.webhook_url())
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(failed(response.status().to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for NotifyHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Lists breaking changes and highlighted commits, one per line.
+fn highlights(ctx: &ReleaseContext) -> String {
+    ctx.commits
+        .iter()
+        .filter(|commit| commit.breaking || commit.is_highlight())
+        .map(|commit| format!("- {}", commit.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Plugin for NotifyHook {
+    fn name(&self) -> &'static str {
+        "notify"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Announces releases on Slack, Discord and Teams"
+    }
+}
+
+impl AsyncReleaseHook for NotifyHook {
+    fn on_post_release<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async move {
+            if ctx.dry_run {
+                return Ok(());
+            }
+
+            for channel in &self.channels {
+                self.notify(channel, &self.message(channel, ctx)).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn on_failure<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async move {
+            if ctx.dry_run || !self.failures {
+                return Ok(());
+            }
+
+            let message = self.failure_message(ctx);
+            for channel in &self.channels {
+                self.notify(channel, &message).await?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_plugin::test_util::poll_once;
+
+    use semver::Version;
+    use unduler_commit::ParsedCommit;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context(commits: Vec<ParsedCommit>) -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            commits,
+        )
+    }
+
+    fn slack() -> Channel {
+        Channel::new(
+            ChannelKind::Slack,
+            "https://hooks.slack.com/services/T0/B0/X",
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = NotifyHook::new();
+        assert!(hook.channels().is_empty());
+        assert!(hook.template().is_none());
+        assert!(hook.changelog_url().is_none());
+    }
+
+    #[test]
+    fn test_with_channel() {
+        let hook = NotifyHook::new()
+            .with_channel(slack())
+            .with_channel(Channel::new(ChannelKind::Discord, "https://discord.test"));
+        assert_eq!(hook.channels().len(), 2);
+        assert_eq!(hook.channels()[1].kind(), ChannelKind::Discord);
+        assert_eq!(hook.channels()[1].webhook_url(), "https://discord.test");
+    }
+
+    #[test]
+    fn test_payloads() {
+        assert_eq!(ChannelKind::Slack.payload("hi"), json!({ "text": "hi" }));
+        assert_eq!(
+            ChannelKind::Discord.payload("hi"),
+            json!({ "content": "hi" })
+        );
+        assert_eq!(ChannelKind::Teams.payload("hi"), json!({ "text": "hi" }));
+    }
+
+    #[test]
+    fn test_default_message() {
+        let hook = NotifyHook::new();
+        let ctx = create_test_context(vec![]);
+        assert_eq!(hook.message(&slack(), &ctx), "Released v1.1.0");
+    }
+
+    #[test]
+    fn test_default_message_with_highlights_and_link() {
+        let hook = NotifyHook::new().with_changelog_url("https://example.com/releases/{tag}");
+        let ctx = create_test_context(vec![
+            ParsedCommit::builder("a1", "feat")
+                .message("drop legacy API")
+                .breaking(true)
+                .build(),
+            ParsedCommit::builder("b2", "fix")
+                .message("minor fix")
+                .build(),
+            ParsedCommit::builder("c3", "feat")
+                .message("add dark mode")
+                .metadata(ParsedCommit::HIGHLIGHT_METADATA, "true")
+                .build(),
+        ]);
+        assert_eq!(
+            hook.message(&slack(), &ctx),
+            "Released v1.1.0\n\nHighlights:\n- drop legacy API\n- add dark mode\n\n\
+             https://example.com/releases/v1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_template() {
+        let hook = NotifyHook::new()
+            .with_tag_format("release-{version}")
+            .with_changelog_url("https://example.com/{version}")
+            .with_template("{previous_version} -> {version} ({tag}) {changelog_url}");
+        let ctx = create_test_context(vec![]);
+        assert_eq!(
+            hook.message(&slack(), &ctx),
+            "1.0.0 -> 1.1.0 (release-1.1.0) https://example.com/1.1.0"
+        );
+    }
+
+    #[test]
+    fn test_channel_template_overrides_hook_template() {
+        let hook = NotifyHook::new().with_template("hook {version}");
+        let channel = slack().with_template("channel {version}");
+        let ctx = create_test_context(vec![]);
+        assert_eq!(hook.message(&channel, &ctx), "channel 1.1.0");
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(NotifyHook::new().name(), "notify");
+    }
+
+    #[test]
+    fn test_failure_message() {
+        let hook = NotifyHook::new();
+        let mut ctx = create_test_context(vec![]);
+        assert_eq!(hook.failure_message(&ctx), "Release v1.1.0 failed");
+        ctx.error = Some("push rejected".to_string());
+        assert_eq!(
+            hook.failure_message(&ctx),
+            "Release v1.1.0 failed: push rejected"
+        );
+    }
+
+    #[test]
+    fn test_on_post_release_dry_run() {
+        let hook = NotifyHook::new().with_channel(slack());
+        let ctx = create_test_context(vec![]).dry_run(true);
+        assert!(poll_once(hook.on_post_release(&ctx)).is_ok());
+    }
+
+    #[test]
+    fn test_on_post_release_without_channels() {
+        let hook = NotifyHook::new();
+        let ctx = create_test_context(vec![]);
+        assert!(poll_once(hook.on_post_release(&ctx)).is_ok());
+    }
+
+    #[test]
+    fn test_on_failure_disabled() {
+        let hook = NotifyHook::new().with_channel(slack());
+        let ctx = create_test_context(vec![]);
+        assert!(poll_once(hook.on_failure(&ctx)).is_ok());
+    }
+}
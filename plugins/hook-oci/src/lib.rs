@@ -0,0 +1,253 @@
+//! OCI artifact and Terraform module publish hook plugin.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use unduler_plugin::{Plugin, PluginError, PluginResult, ReleaseContext, ReleaseHook};
+
+/// Hook pushing a directory of the repository to an OCI registry with
+/// [ORAS](https://oras.land).
+pub struct OciHook {
+    /// Repository the artifact is pushed to, e.g. `ghcr.io/acme/modules/vpc`.
+    repository: String,
+    /// Directory packaged into the artifact, relative to the repository root.
+    path: PathBuf,
+    /// Tags of the artifact, with `{version}` and `{tag}` placeholders.
+    tags: Vec<String>,
+    /// Artifact type of the manifest.
+    artifact_type: Option<String>,
+    /// Media type of the packaged directory.
+    media_type: Option<String>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+}
+
+impl OciHook {
+    /// Creates a hook pushing the whole repository to `repository`, tagged
+    /// with the release version.
+    #[must_use]
+    pub fn new(repository: impl Into<String>) -> Self {
+        Self {
+            repository: repository.into(),
+            path: PathBuf::from("."),
+            tags: vec!["{version}".to_string()],
+            artifact_type: None,
+            media_type: None,
+            tag_format: "v{version}".to_string(),
+        }
+    }
+
+    /// Sets the directory packaged into the artifact.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the tags of the artifact.
+    #[must_use]
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Sets the artifact type of the manifest.
+    #[must_use]
+    pub fn with_artifact_type(mut self, artifact_type: impl Into<String>) -> Self {
+        self.artifact_type = Some(artifact_type.into());
+        self
+    }
+
+    /// Sets the media type of the packaged directory.
+    #[must_use]
+    pub fn with_media_type(mut self, media_type: impl Into<String>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Returns the repository the artifact is pushed to.
+    #[must_use]
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// Returns the directory packaged into the artifact.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the `oras` arguments pushing the release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no tag is configured.
+    pub fn push_args(&self, ctx: &ReleaseContext) -> PluginResult<Vec<String>> {
+        let version = ctx.next_version.to_string();
+        let tag = ctx.tag(&self.tag_format);
+        let tags = self
+            .tags
+            .iter()
+            .map(|t| t.replace("{version}", &version).replace("{tag}", &tag))
+            .collect::<Vec<_>>();
+        if tags.is_empty() {
+            return Err(PluginError::ConfigError(
+                "at least one artifact tag is required".to_string(),
+            ));
+        }
+
+        let mut args = vec![
+            "push".to_string(),
+            format!("{}:{}", self.repository, tags.join(",")),
+        ];
+        if let Some(ref artifact_type) = self.artifact_type {
+            args.push("--artifact-type".to_string());
+            args.push(artifact_type.clone());
+        }
+        args.push("--annotation".to_string());
+        args.push(format!("org.opencontainers.image.version={version}"));
+
+        let path = self.path.display().to_string();
+        args.push(match self.media_type {
+            Some(ref media_type) => format!("{path}:{media_type}"),
+            None => path,
+        });
+
+        Ok(args)
+    }
+}
+
+impl Plugin for OciHook {
+    fn name(&self) -> &'static str {
+        "oci"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Pushes the release as an OCI artifact"
+    }
+}
+
+impl ReleaseHook for OciHook {
+    fn on_post_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        if ctx.dry_run {
+            return Ok(());
+        }
+
+        let args = self.push_args(ctx)?;
+        let output = Command::new("oras")
+            .args(&args)
+            .current_dir(&ctx.repo_path)
+            .output()?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        tracing::debug!("oras output:\n{stdout}{stderr}");
+        if !output.status.success() {
+            return Err(PluginError::ExecutionFailed(format!(
+                "oras {} failed with {}: {}",
+                args.join(" "),
+                output.status,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context(dry_run: bool) -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+        .dry_run(dry_run)
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = OciHook::new("ghcr.io/acme/app");
+        assert_eq!(hook.repository(), "ghcr.io/acme/app");
+        assert_eq!(hook.path(), Path::new("."));
+    }
+
+    #[test]
+    fn test_push_args_default() {
+        let hook = OciHook::new("ghcr.io/acme/app");
+        let args = hook.push_args(&create_test_context(false)).unwrap();
+        assert_eq!(
+            args,
+            [
+                "push",
+                "ghcr.io/acme/app:1.1.0",
+                "--annotation",
+                "org.opencontainers.image.version=1.1.0",
+                "."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_push_args_terraform_module() {
+        let hook = OciHook::new("ghcr.io/acme/modules/vpc")
+            .with_path("modules/vpc")
+            .with_tags(vec!["{tag}".to_string(), "latest".to_string()])
+            .with_artifact_type("application/vnd.acme.terraform.module")
+            .with_media_type("application/vnd.oci.image.layer.v1.tar+gzip");
+        let args = hook.push_args(&create_test_context(false)).unwrap();
+        assert_eq!(
+            args,
+            [
+                "push",
+                "ghcr.io/acme/modules/vpc:v1.1.0,latest",
+                "--artifact-type",
+                "application/vnd.acme.terraform.module",
+                "--annotation",
+                "org.opencontainers.image.version=1.1.0",
+                "modules/vpc:application/vnd.oci.image.layer.v1.tar+gzip"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_push_args_without_tags() {
+        let hook = OciHook::new("ghcr.io/acme/app").with_tags(vec![]);
+        assert!(matches!(
+            hook.push_args(&create_test_context(false)),
+            Err(PluginError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_on_post_tag_dry_run() {
+        let hook = OciHook::new("ghcr.io/acme/app");
+        let mut ctx = create_test_context(true);
+        assert!(hook.on_post_tag(&mut ctx).is_ok());
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(OciHook::new("ghcr.io/acme/app").name(), "oci");
+    }
+}
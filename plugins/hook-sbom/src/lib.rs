@@ -0,0 +1,392 @@
+//! SBOM and provenance hook plugin.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use unduler_plugin::{Plugin, PluginError, PluginResult, ReleaseContext, ReleaseHook};
+
+/// File name of the provenance statement.
+const PROVENANCE_FILE: &str = "provenance.intoto.jsonl";
+
+/// Format of a software bill of materials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SbomFormat {
+    /// `CycloneDX` JSON.
+    CycloneDx,
+    /// SPDX JSON.
+    Spdx,
+}
+
+impl SbomFormat {
+    /// Returns the `syft` output format.
+    #[must_use]
+    pub fn syft_output(self) -> &'static str {
+        match self {
+            Self::CycloneDx => "cyclonedx-json",
+            Self::Spdx => "spdx-json",
+        }
+    }
+
+    /// Returns the file name of the SBOM.
+    #[must_use]
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::CycloneDx => "sbom.cdx.json",
+            Self::Spdx => "sbom.spdx.json",
+        }
+    }
+}
+
+/// Hook generating the SBOM of a release with [Syft](https://github.com/anchore/syft)
+/// and a SLSA provenance statement of its artifacts, and attaching them to
+/// the release assets.
+pub struct SbomHook {
+    /// Source scanned by `syft`, e.g. `dir:.` or `file:target/release/app`.
+    source: String,
+    /// Formats of the generated SBOMs.
+    formats: Vec<SbomFormat>,
+    /// Artifacts of the release, relative to the repository root.
+    artifacts: Vec<PathBuf>,
+    /// Directory the files are written to, relative to the repository root.
+    output_dir: PathBuf,
+    /// Whether a provenance statement is generated.
+    provenance: bool,
+    /// Identifier of the builder recorded in the provenance.
+    builder_id: String,
+    /// URI of the source repository recorded in the provenance.
+    source_uri: Option<String>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+}
+
+impl SbomHook {
+    /// Creates a hook generating a `CycloneDX` SBOM of the repository and the
+    /// provenance of `artifacts`.
+    #[must_use]
+    pub fn new(artifacts: Vec<PathBuf>) -> Self {
+        Self {
+            source: "dir:.".to_string(),
+            formats: vec![SbomFormat::CycloneDx],
+            artifacts,
+            output_dir: PathBuf::from("target/sbom"),
+            provenance: true,
+            builder_id: env!("CARGO_PKG_REPOSITORY").to_string(),
+            source_uri: None,
+            tag_format: "v{version}".to_string(),
+        }
+    }
+
+    /// Sets the source scanned by `syft`.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = source.into();
+        self
+    }
+
+    /// Sets the formats of the generated SBOMs.
+    #[must_use]
+    pub fn with_formats(mut self, formats: Vec<SbomFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Sets the directory the files are written to.
+    #[must_use]
+    pub fn with_output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = output_dir.into();
+        self
+    }
+
+    /// Sets whether a provenance statement is generated.
+    #[must_use]
+    pub fn with_provenance(mut self, provenance: bool) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Sets the identifier of the builder recorded in the provenance.
+    #[must_use]
+    pub fn with_builder_id(mut self, builder_id: impl Into<String>) -> Self {
+        self.builder_id = builder_id.into();
+        self
+    }
+
+    /// Sets the URI of the source repository recorded in the provenance,
+    /// e.g. `git+https://github.com/acme/app`.
+    #[must_use]
+    pub fn with_source_uri(mut self, source_uri: impl Into<String>) -> Self {
+        self.source_uri = Some(source_uri.into());
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Returns the formats of the generated SBOMs.
+    #[must_use]
+    pub fn formats(&self) -> &[SbomFormat] {
+        &self.formats
+    }
+
+    /// Returns the directory the files are written to.
+    #[must_use]
+    pub fn output_dir(&self) -> &Path {
+        &self.output_dir
+    }
+
+    /// Returns whether a provenance statement is generated.
+    #[must_use]
+    pub fn provenance(&self) -> bool {
+        self.provenance
+    }
+
+    /// Returns the `syft` arguments writing the SBOMs to `output_dir`.
+    #[must_use]
+    pub fn syft_args(&self, output_dir: &Path) -> Vec<String> {
+        let mut args = vec!["scan".to_string(), self.source.clone()];
+        for format in &self.formats {
+            args.push("-o".to_string());
+            args.push(format!(
+                "{}={}",
+                format.syft_output(),
+                output_dir.join(format.file_name()).display()
+            ));
+        }
+        args
+    }
+
+    /// Returns the SLSA provenance statement of the release artifacts, built
+    /// from `commit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no artifact is configured or an artifact cannot
+    /// be read.
+    pub fn provenance_statement(&self, ctx: &ReleaseContext, commit: &str) -> PluginResult<Value> {
+        if self.artifacts.is_empty() {
+            return Err(PluginError::ConfigError(
+                "provenance needs at least one release artifact".to_string(),
+            ));
+        }
+
+        let subjects = self
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                let digest = Sha256::digest(fs::read(ctx.repo_path.join(artifact))?);
+                let name = artifact.file_name().map_or_else(
+                    || artifact.display().to_string(),
+                    |name| name.to_string_lossy().into_owned(),
+                );
+                Ok(json!({ "name": name, "digest": { "sha256": format!("{digest:x}") } }))
+            })
+            .collect::<PluginResult<Vec<_>>>()?;
+
+        let mut source = json!({ "digest": { "gitCommit": commit } });
+        if let Some(ref uri) = self.source_uri {
+            source["uri"] = json!(uri);
+        }
+
+        Ok(json!({
+            "_type": "https://in-toto.io/Statement/v1",
+            "subject": subjects,
+            "predicateType": "https://slsa.dev/provenance/v1",
+            "predicate": {
+                "buildDefinition": {
+                    "buildType": format!("{}/release/v1", env!("CARGO_PKG_REPOSITORY")),
+                    "externalParameters": {
+                        "version": ctx.next_version.to_string(),
+                        "tag": ctx.tag(&self.tag_format),
+                    },
+                    "resolvedDependencies": [source],
+                },
+                "runDetails": {
+                    "builder": { "id": self.builder_id },
+                },
+            },
+        }))
+    }
+
+    /// Returns the commit checked out in the repository.
+    fn head_commit(root: &Path) -> PluginResult<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(root)
+            .output()?;
+        if !output.status.success() {
+            return Err(PluginError::ExecutionFailed(format!(
+                "git rev-parse HEAD failed with {}",
+                output.status
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl Plugin for SbomHook {
+    fn name(&self) -> &'static str {
+        "sbom"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Generates the SBOM and provenance of a release"
+    }
+}
+
+impl ReleaseHook for SbomHook {
+    fn on_post_tag(&self, ctx: &mut ReleaseContext) -> PluginResult<()> {
+        if ctx.dry_run {
+            return Ok(());
+        }
+
+        fs::create_dir_all(ctx.repo_path.join(&self.output_dir))?;
+
+        if !self.formats.is_empty() {
+            let args = self.syft_args(&self.output_dir);
+            let output = Command::new("syft")
+                .args(&args)
+                .current_dir(&ctx.repo_path)
+                .output()?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            tracing::debug!("syft output:\n{stdout}{stderr}");
+            if !output.status.success() {
+                return Err(PluginError::ExecutionFailed(format!(
+                    "syft {} failed with {}: {}",
+                    args.join(" "),
+                    output.status,
+                    stderr.trim()
+                )));
+            }
+            for format in &self.formats {
+                let path = self.output_dir.join(format.file_name());
+                ctx.add_asset(path.display().to_string());
+            }
+        }
+
+        if self.provenance {
+            let commit = Self::head_commit(&ctx.repo_path)?;
+            let statement = self.provenance_statement(ctx, &commit)?;
+            let path = self.output_dir.join(PROVENANCE_FILE);
+            fs::write(ctx.repo_path.join(&path), format!("{statement}\n"))?;
+            ctx.add_asset(path.display().to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+    use tempfile::TempDir;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context(root: &Path) -> ReleaseContext {
+        ReleaseContext::new(
+            root,
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = SbomHook::new(vec![]);
+        assert_eq!(hook.formats(), [SbomFormat::CycloneDx]);
+        assert_eq!(hook.output_dir(), Path::new("target/sbom"));
+        assert!(hook.provenance());
+    }
+
+    #[test]
+    fn test_syft_args() {
+        let hook = SbomHook::new(vec![])
+            .with_source("file:target/release/app")
+            .with_formats(vec![SbomFormat::CycloneDx, SbomFormat::Spdx]);
+        assert_eq!(
+            hook.syft_args(Path::new("sbom")),
+            [
+                "scan",
+                "file:target/release/app",
+                "-o",
+                "cyclonedx-json=sbom/sbom.cdx.json",
+                "-o",
+                "spdx-json=sbom/sbom.spdx.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provenance_statement() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("dist")).unwrap();
+        fs::write(dir.path().join("dist/app.tar.gz"), "hello").unwrap();
+
+        let hook = SbomHook::new(vec![PathBuf::from("dist/app.tar.gz")])
+            .with_builder_id("https://github.com/acme/app/actions")
+            .with_source_uri("git+https://github.com/acme/app");
+        let ctx = create_test_context(dir.path());
+        let statement = hook.provenance_statement(&ctx, "abc123").unwrap();
+
+        assert_eq!(statement["predicateType"], "https://slsa.dev/provenance/v1");
+        assert_eq!(statement["subject"][0]["name"], "app.tar.gz");
+        assert_eq!(
+            statement["subject"][0]["digest"]["sha256"],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        let predicate = &statement["predicate"];
+        assert_eq!(
+            predicate["buildDefinition"]["externalParameters"]["tag"],
+            "v1.1.0"
+        );
+        assert_eq!(
+            predicate["buildDefinition"]["resolvedDependencies"][0],
+            json!({
+                "uri": "git+https://github.com/acme/app",
+                "digest": { "gitCommit": "abc123" }
+            })
+        );
+        assert_eq!(
+            predicate["runDetails"]["builder"]["id"],
+            "https://github.com/acme/app/actions"
+        );
+    }
+
+    #[test]
+    fn test_provenance_without_artifacts() {
+        let ctx = create_test_context(Path::new("/tmp"));
+        assert!(matches!(
+            SbomHook::new(vec![]).provenance_statement(&ctx, "abc123"),
+            Err(PluginError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_on_post_tag_dry_run() {
+        let dir = TempDir::new().unwrap();
+        let mut ctx = create_test_context(dir.path()).dry_run(true);
+        assert!(SbomHook::new(vec![]).on_post_tag(&mut ctx).is_ok());
+        assert!(ctx.assets().is_empty());
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(SbomHook::new(vec![]).name(), "sbom");
+    }
+}
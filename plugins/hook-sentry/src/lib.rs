@@ -0,0 +1,296 @@
+//! Sentry release registration hook plugin.
+
+use std::fmt::Write;
+
+use serde_json::{Value, json};
+use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, Plugin, PluginError, PluginResult, ReleaseContext,
+};
+
+/// Hook creating a Sentry release and marking its deploy.
+pub struct SentryHook {
+    /// Base URL of the Sentry instance.
+    url: String,
+    /// Organization slug.
+    org: String,
+    /// Project slugs the release belongs to.
+    projects: Vec<String>,
+    /// Authentication token.
+    token: String,
+    /// Release name template, with `{version}` and `{tag}` placeholders.
+    release: String,
+    /// Environment of the deploy, if one is marked.
+    environment: Option<String>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl SentryHook {
+    /// Creates a hook registering releases of `projects` in `org` on
+    /// sentry.io, and marking their deploy to production.
+    #[must_use]
+    pub fn new(org: impl Into<String>, projects: Vec<String>, token: impl Into<String>) -> Self {
+        Self {
+            url: "https://sentry.io".to_string(),
+            org: org.into(),
+            projects,
+            token: token.into(),
+            release: "{version}".to_string(),
+            environment: Some("production".to_string()),
+            tag_format: "v{version}".to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Sets the base URL of a self-hosted Sentry instance.
+    #[must_use]
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the release name template, e.g. `my-app@{version}`.
+    #[must_use]
+    pub fn with_release(mut self, release: impl Into<String>) -> Self {
+        self.release = release.into();
+        self
+    }
+
+    /// Sets the environment of the deploy, or disables the deploy.
+    #[must_use]
+    pub fn with_environment(mut self, environment: Option<String>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Returns the organization slug.
+    #[must_use]
+    pub fn org(&self) -> &str {
+        &self.org
+    }
+
+    /// Returns the project slugs the release belongs to.
+    #[must_use]
+    pub fn projects(&self) -> &[String] {
+        &self.projects
+    }
+
+    /// Returns the environment of the deploy, if one is marked.
+    #[must_use]
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    /// Renders the name of the Sentry release.
+    #[must_use]
+    pub fn release_name(&self, ctx: &ReleaseContext) -> String {
+        self.release
+            .replace("{version}", &ctx.next_version.to_string())
+            .replace("{tag}", &ctx.tag(&self.tag_format))
+    }
+
+    /// Returns the body creating the release, with the commits it contains.
+    #[must_use]
+    pub fn release_body(&self, ctx: &ReleaseContext) -> Value {
+        let commits = ctx
+            .commits
+            .iter()
+            .map(|commit| {
+                json!({
+                    "id": commit.hash,
+                    "message": commit.message,
+                    "author_name": commit.author,
+                    "timestamp": commit.date.to_rfc3339(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "version": self.release_name(ctx),
+            "projects": self.projects,
+            "commits": commits,
+        })
+    }
+
+    /// Returns the URL of an organization endpoint, with each path segment
+    /// percent-encoded, e.g. a `1.0.0+build.5` release name.
+    #[must_use]
+    pub fn endpoint(&self, segments: &[&str]) -> String {
+        let mut url = format!(
+            "{}/api/0/organizations/{}/",
+            self.url,
+            encode_segment(&self.org)
+        );
+        for segment in segments {
+            url.push_str(&encode_segment(segment));
+            url.push('/');
+        }
+        url
+    }
+
+    async fn post(&self, url: &str, body: &Value) -> PluginResult<()> {
+        let failed = |reason: String| PluginError::ExecutionFailed(format!("{url}: {reason}"));
+
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(failed(response.status().to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Plugin for SentryHook {
+    fn name(&self) -> &'static str {
+        "sentry"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Registers releases and deploys in Sentry"
+    }
+}
+
+impl AsyncReleaseHook for SentryHook {
+    fn on_post_tag<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(async move {
+            if ctx.dry_run {
+                return Ok(());
+            }
+
+            self.post(&self.endpoint(&["releases"]), &self.release_body(ctx))
+                .await?;
+
+            if let Some(ref environment) = self.environment {
+                let release = self.release_name(ctx);
+                let url = self.endpoint(&["releases", &release, "deploys"]);
+                self.post(&url, &json!({ "environment": environment }))
+                    .await?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Percent-encodes a URL path segment, keeping the unreserved characters.
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_plugin::test_util::poll_once;
+
+    use semver::Version;
+    use unduler_commit::ParsedCommit;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context(commits: Vec<ParsedCommit>) -> ReleaseContext {
+        ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            commits,
+        )
+    }
+
+    fn create_hook() -> SentryHook {
+        SentryHook::new("acme", vec!["web".to_string(), "api".to_string()], "token")
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = create_hook();
+        assert_eq!(hook.org(), "acme");
+        assert_eq!(hook.projects(), ["web", "api"]);
+        assert_eq!(hook.environment(), Some("production"));
+    }
+
+    #[test]
+    fn test_release_name() {
+        let ctx = create_test_context(vec![]);
+        assert_eq!(create_hook().release_name(&ctx), "1.1.0");
+
+        let hook = create_hook().with_release("web@{tag}");
+        assert_eq!(hook.release_name(&ctx), "web@v1.1.0");
+    }
+
+    #[test]
+    fn test_release_body() {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .message("add login")
+            .author("Jane Doe")
+            .build();
+        let body = create_hook().release_body(&create_test_context(vec![commit]));
+        assert_eq!(body["version"], "1.1.0");
+        assert_eq!(body["projects"], json!(["web", "api"]));
+        assert_eq!(body["commits"][0]["id"], "abc123");
+        assert_eq!(body["commits"][0]["message"], "add login");
+        assert_eq!(body["commits"][0]["author_name"], "Jane Doe");
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let hook = create_hook().with_url("https://sentry.example.com/");
+        assert_eq!(
+            hook.endpoint(&["releases"]),
+            "https://sentry.example.com/api/0/organizations/acme/releases/"
+        );
+        assert_eq!(
+            hook.endpoint(&["releases", "web@1.0.0+build.5", "deploys"]),
+            "https://sentry.example.com/api/0/organizations/acme/releases/web%401.0.0%2Bbuild.5/deploys/"
+        );
+        assert_eq!(encode_segment("a b/ü"), "a%20b%2F%C3%BC");
+    }
+
+    #[test]
+    fn test_without_deploy() {
+        let hook = create_hook().with_environment(None);
+        assert!(hook.environment().is_none());
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(create_hook().name(), "sentry");
+    }
+
+    #[test]
+    fn test_on_post_tag_dry_run() {
+        let hook = create_hook();
+        let ctx = create_test_context(vec![]).dry_run(true);
+        assert!(poll_once(hook.on_post_tag(&ctx)).is_ok());
+    }
+}
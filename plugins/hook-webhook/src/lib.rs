@@ -0,0 +1,480 @@
+//! Generic webhook hook plugin.
+
+use std::time::Duration;
+
+use serde_json::{Map, Value, json};
+use unduler_plugin::{
+    AsyncReleaseHook, BoxFuture, HookStage, Plugin, PluginError, PluginResult, ReleaseContext,
+};
+
+/// Timeout of a webhook request, from connection to the end of the response.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A URL receiving the release as JSON at some stages of the release.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Webhook {
+    /// URL the body is posted to.
+    url: String,
+    /// Stages at which the webhook is called.
+    stages: Vec<HookStage>,
+    /// Additional request headers.
+    headers: Vec<(String, String)>,
+    /// Body template, the default body when unset.
+    body: Option<Value>,
+}
+
+impl Webhook {
+    /// Creates a webhook called after the tag is created.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            stages: vec![HookStage::PostTag],
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Sets the stages at which the webhook is called.
+    #[must_use]
+    pub fn with_stages(mut self, stages: Vec<HookStage>) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    /// Adds a request header, e.g. a secret token.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body template.
+    ///
+    /// Strings equal to a placeholder are replaced by its value, so
+    /// `"{commits}"` becomes an array. Other strings have their
+    /// `{version}`, `{previous_version}`, `{tag}`, `{stage}` and `{error}`
+    /// placeholders substituted.
+    #[must_use]
+    pub fn with_body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Returns the URL the body is posted to.
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the stages at which the webhook is called.
+    #[must_use]
+    pub fn stages(&self) -> &[HookStage] {
+        &self.stages
+    }
+
+    /// Returns the additional request headers.
+    #[must_use]
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+}
+
+/// Webhook hook posting templated JSON payloads.
+pub struct WebhookHook {
+    /// Webhooks to call.
+    webhooks: Vec<Webhook>,
+    /// Template of the release tag name, e.g. `v{version}`.
+    tag_format: String,
+    /// Number of retries of a failed request.
+    retries: u32,
+    /// Delay before the first retry, doubled on each retry.
+    backoff: Duration,
+    /// HTTP client.
+    client: reqwest::Client,
+}
+
+impl WebhookHook {
+    /// Creates a new webhook hook.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be built.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            webhooks: Vec::new(),
+            tag_format: "v{version}".to_string(),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("failed to build HTTP client"),
+        }
+    }
+
+    /// Adds a webhook to call.
+    #[must_use]
+    pub fn with_webhook(mut self, webhook: Webhook) -> Self {
+        self.webhooks.push(webhook);
+        self
+    }
+
+    /// Sets the template of the release tag name, with `{version}`,
+    /// `{major}`, `{minor}` and `{patch}` placeholders.
+    #[must_use]
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Sets the number of retries of a failed request.
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled on each retry.
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Returns the webhooks to call.
+    #[must_use]
+    pub fn webhooks(&self) -> &[Webhook] {
+        &self.webhooks
+    }
+
+    /// Returns the number of retries of a failed request.
+    #[must_use]
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Returns the delay before the first retry.
+    #[must_use]
+    pub fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Renders the body posted to a webhook.
+    #[must_use]
+    pub fn body(&self, webhook: &Webhook, stage: HookStage, ctx: &ReleaseContext) -> Value {
+        let values = Placeholders::new(ctx, stage, &self.tag_format);
+        match &webhook.body {
+            Some(template) => values.render(template),
+            None => json!({
+                "version": values.version,
+                "previous_version": values.previous_version,
+                "tag": values.tag,
+                "repository_url": ctx.repository_url,
+                "stage": values.stage,
+                "changelog": values.changelog,
+                "commits": values.commits,
+                "files": ctx.files,
+                "error": ctx.error,
+            }),
+        }
+    }
+
+    async fn run(&self, stage: HookStage, ctx: &ReleaseContext) -> PluginResult<()> {
+        if ctx.dry_run {
+            return Ok(());
+        }
+
+        for webhook in &self.webhooks {
+            if webhook.stages.contains(&stage) {
+                self.post(webhook, stage, &self.body(webhook, stage, ctx))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Posts a body, retrying on network errors, rate limits and server
+    /// errors.
+    ///
+    /// At the `pre_release` stage, a client error vetoes the release with
+    /// the response body as the reason.
+    async fn post(&self, webhook: &Webhook, stage: HookStage, body: &Value) -> PluginResult<()> {
+        let mut attempt = 0;
+        loop {
+            let mut request = self.client.post(&webhook.url).json(body);
+            for (name, value) in &webhook.headers {
+                request = request.header(name, value);
+            }
+
+            let reason = match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_success() {
+                        return Ok(());
+                    }
+                    if !status.is_server_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+                    {
+                        if stage == HookStage::PreRelease && status.is_client_error() {
+                            let reason = response.text().await.unwrap_or_default();
+                            return Err(vetoed(webhook, &status.to_string(), reason.trim()));
+                        }
+                        return Err(failed(webhook, &status.to_string()));
+                    }
+                    status.to_string()
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= self.retries {
+                return Err(failed(webhook, &reason));
+            }
+            tokio::time::sleep(self.backoff.saturating_mul(2u32.saturating_pow(attempt))).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl Default for WebhookHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn failed(webhook: &Webhook, reason: &str) -> PluginError {
+    PluginError::ExecutionFailed(format!("webhook {} failed: {reason}", webhook.url))
+}
+
+fn vetoed(webhook: &Webhook, status: &str, reason: &str) -> PluginError {
+    if reason.is_empty() {
+        PluginError::Vetoed(format!("webhook {} answered {status}", webhook.url))
+    } else {
+        PluginError::Vetoed(format!("{reason} (webhook {})", webhook.url))
+    }
+}
+
+/// Values substituted in body templates.
+struct Placeholders {
+    version: String,
+    previous_version: String,
+    tag: String,
+    stage: String,
+    error: String,
+    changelog: Value,
+    commits: Value,
+}
+
+impl Placeholders {
+    fn new(ctx: &ReleaseContext, stage: HookStage, tag_format: &str) -> Self {
+        Self {
+            version: ctx.next_version.to_string(),
+            previous_version: ctx.previous_version.to_string(),
+            tag: ctx.tag(tag_format),
+            stage: stage.to_string(),
+            error: ctx.error.clone().unwrap_or_default(),
+            changelog: ctx.changelog.clone().map_or(Value::Null, Value::String),
+            commits: serde_json::to_value(&ctx.commits).unwrap_or_default(),
+        }
+    }
+
+    fn render(&self, template: &Value) -> Value {
+        match template {
+            Value::String(s) => match s.as_str() {
+                "{changelog}" => self.changelog.clone(),
+                "{commits}" => self.commits.clone(),
+                s => Value::String(
+                    s.replace("{version}", &self.version)
+                        .replace("{previous_version}", &self.previous_version)
+                        .replace("{tag}", &self.tag)
+                        .replace("{stage}", &self.stage)
+                        .replace("{error}", &self.error),
+                ),
+            },
+            Value::Array(items) => items.iter().map(|item| self.render(item)).collect(),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), self.render(value)))
+                    .collect::<Map<_, _>>(),
+            ),
+            value => value.clone(),
+        }
+    }
+}
+
+impl Plugin for WebhookHook {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Posts templated JSON payloads to webhooks"
+    }
+}
+
+impl AsyncReleaseHook for WebhookHook {
+    fn on_pre_release<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PreRelease, ctx))
+    }
+
+    fn on_pre_bump<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PreBump, ctx))
+    }
+
+    fn on_post_bump<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PostBump, ctx))
+    }
+
+    fn on_pre_commit<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PreCommit, ctx))
+    }
+
+    fn on_pre_tag<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PreTag, ctx))
+    }
+
+    fn on_post_tag<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PostTag, ctx))
+    }
+
+    fn on_post_release<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::PostRelease, ctx))
+    }
+
+    fn on_failure<'a>(&'a self, ctx: &'a ReleaseContext) -> BoxFuture<'a, PluginResult<()>> {
+        Box::pin(self.run(HookStage::Failure, ctx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unduler_plugin::test_util::poll_once;
+
+    use semver::Version;
+    use unduler_commit::ParsedCommit;
+    use unduler_plugin::BumpType;
+
+    use super::*;
+
+    fn create_test_context() -> ReleaseContext {
+        let commit = ParsedCommit::builder("abc123", "feat")
+            .message("add webhooks")
+            .build();
+        let mut ctx = ReleaseContext::new(
+            "/tmp/test",
+            Version::new(1, 0, 0),
+            Version::new(1, 1, 0),
+            BumpType::Minor,
+            vec![commit],
+        );
+        ctx.changelog = Some("## [1.1.0]".to_string());
+        ctx
+    }
+
+    #[test]
+    fn test_new() {
+        let hook = WebhookHook::new();
+        assert!(hook.webhooks().is_empty());
+        assert_eq!(hook.retries(), 3);
+        assert_eq!(hook.backoff(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_webhook_defaults_to_post_tag() {
+        let webhook = Webhook::new("https://example.com/hook");
+        assert_eq!(webhook.url(), "https://example.com/hook");
+        assert_eq!(webhook.stages(), &[HookStage::PostTag]);
+        assert!(webhook.headers().is_empty());
+    }
+
+    #[test]
+    fn test_webhook_builder() {
+        let webhook = Webhook::new("https://example.com/hook")
+            .with_stages(vec![HookStage::PreTag, HookStage::PostTag])
+            .with_header("Authorization", "Bearer secret");
+        assert_eq!(webhook.stages().len(), 2);
+        assert_eq!(
+            webhook.headers(),
+            &[("Authorization".to_string(), "Bearer secret".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_default_body() {
+        let hook = WebhookHook::new();
+        let webhook = Webhook::new("https://example.com/hook");
+        let body = hook.body(&webhook, HookStage::PostTag, &create_test_context());
+        assert_eq!(body["version"], "1.1.0");
+        assert_eq!(body["previous_version"], "1.0.0");
+        assert_eq!(body["tag"], "v1.1.0");
+        assert_eq!(body["stage"], "post_tag");
+        assert_eq!(body["changelog"], "## [1.1.0]");
+        assert_eq!(body["commits"][0]["hash"], "abc123");
+        assert_eq!(body["commits"][0]["message"], "add webhooks");
+        assert_eq!(body["files"], json!([]));
+        assert_eq!(body["error"], Value::Null);
+    }
+
+    #[test]
+    fn test_templated_body() {
+        let hook = WebhookHook::new().with_tag_format("release-{version}");
+        let webhook = Webhook::new("https://example.com/hook").with_body(json!({
+            "text": "Released {tag} ({previous_version} -> {version})",
+            "notes": "{changelog}",
+            "data": { "commits": "{commits}", "count": 1, "draft": false },
+        }));
+        let body = hook.body(&webhook, HookStage::PostTag, &create_test_context());
+        assert_eq!(body["text"], "Released release-1.1.0 (1.0.0 -> 1.1.0)");
+        assert_eq!(body["notes"], "## [1.1.0]");
+        assert_eq!(body["data"]["commits"][0]["type"], "feat");
+        assert_eq!(body["data"]["count"], 1);
+        assert_eq!(body["data"]["draft"], false);
+    }
+
+    #[test]
+    fn test_templated_body_with_error() {
+        let hook = WebhookHook::new();
+        let webhook = Webhook::new("https://example.com/hook")
+            .with_body(json!({ "text": "{tag} failed: {error}" }));
+        let mut ctx = create_test_context();
+        ctx.error = Some("push rejected".to_string());
+        let body = hook.body(&webhook, HookStage::Failure, &ctx);
+        assert_eq!(body["text"], "v1.1.0 failed: push rejected");
+    }
+
+    #[test]
+    fn test_templated_body_without_changelog() {
+        let hook = WebhookHook::new();
+        let webhook =
+            Webhook::new("https://example.com/hook").with_body(json!({ "notes": "{changelog}" }));
+        let mut ctx = create_test_context();
+        ctx.changelog = None;
+        let body = hook.body(&webhook, HookStage::PreBump, &ctx);
+        assert_eq!(body["notes"], Value::Null);
+    }
+
+    #[test]
+    fn test_plugin_name() {
+        assert_eq!(WebhookHook::new().name(), "webhook");
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let hook = WebhookHook::new().with_webhook(Webhook::new("https://example.com/hook"));
+        let ctx = create_test_context().dry_run(true);
+        assert!(poll_once(hook.on_post_tag(&ctx)).is_ok());
+    }
+
+    #[test]
+    fn test_skips_other_stages() {
+        let hook = WebhookHook::new().with_webhook(Webhook::new("https://example.com/hook"));
+        let ctx = create_test_context();
+        assert!(poll_once(hook.on_pre_bump(&ctx)).is_ok());
+    }
+}
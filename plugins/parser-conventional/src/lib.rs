@@ -56,6 +56,10 @@ impl CommitParser for ConventionalParser {
                 .scope(scope.unwrap_or_default())
                 .message(message)
                 .breaking(breaking)
+                .breaking_change(raw.breaking_change().as_deref())
+                .closed_issues(&raw.closed_issues())
+                .co_authors(&raw.co_authors())
+                .changelog_entry(raw.changelog_entry())
                 .author(&raw.author)
                 .date(raw.date)
                 .build(),
@@ -105,6 +109,47 @@ mod tests {
         assert!(parsed.breaking);
     }
 
+    #[test]
+    fn test_closed_issues() {
+        let parser = ConventionalParser::new();
+        let raw = make_raw("fix: handle timeouts\n\nCloses #12");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert_eq!(parsed.closed_issues(), vec!["12"]);
+    }
+
+    #[test]
+    fn test_co_authors() {
+        let parser = ConventionalParser::new();
+        let raw = make_raw("feat: add cache\n\nCo-authored-by: Jane Doe <jane@example.com>");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert_eq!(parsed.co_authors(), vec!["Jane Doe"]);
+    }
+
+    #[test]
+    fn test_changelog_footer() {
+        let parser = ConventionalParser::new();
+        let raw = make_raw("fix: retry on 503\n\nChangelog: Uploads survive busy servers");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert_eq!(parsed.changelog_text(), "Uploads survive busy servers");
+    }
+
+    #[test]
+    fn test_breaking_change_footer() {
+        let parser = ConventionalParser::new();
+        let raw =
+            make_raw("feat: new config format\n\nBREAKING CHANGE: `unduler.yml` is no longer read");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert!(parsed.breaking);
+        assert_eq!(
+            parsed.breaking_change(),
+            Some("`unduler.yml` is no longer read")
+        );
+    }
+
     #[test]
     fn test_invalid_commit() {
         let parser = ConventionalParser::new();
@@ -490,6 +490,22 @@ const GITMOJIS: &[Gitmoji] = &[
 static EMOJI_MAP: LazyLock<HashMap<&'static str, &'static str>> =
     LazyLock::new(|| GITMOJIS.iter().map(|g| (g.emoji, g.commit_type)).collect());
 
+/// Known emojis indexed by their first character, longest first so that
+/// an emoji followed by a variation selector or joined by a ZWJ wins over
+/// its bare prefix.
+static EMOJI_INDEX: LazyLock<HashMap<char, Vec<&'static str>>> = LazyLock::new(|| {
+    let mut index: HashMap<char, Vec<&'static str>> = HashMap::new();
+    for gitmoji in GITMOJIS {
+        if let Some(first) = gitmoji.emoji.chars().next() {
+            index.entry(first).or_default().push(gitmoji.emoji);
+        }
+    }
+    for emojis in index.values_mut() {
+        emojis.sort_by_key(|emoji| std::cmp::Reverse(emoji.len()));
+    }
+    index
+});
+
 /// Maps text codes (like :bug:) to their emoji and commit type.
 static CODE_MAP: LazyLock<HashMap<&'static str, (&'static str, &'static str)>> =
     LazyLock::new(|| {
@@ -515,6 +531,8 @@ pub struct GitmojiParserConfig {
     pub infer_type_from_emoji: bool,
     /// Reject commits with unknown emojis.
     pub strict_emoji: bool,
+    /// Commit types of emojis, overriding or extending the built-in gitmojis.
+    pub mappings: HashMap<String, String>,
 }
 
 impl Default for GitmojiParserConfig {
@@ -522,41 +540,110 @@ impl Default for GitmojiParserConfig {
         Self {
             infer_type_from_emoji: true,
             strict_emoji: false,
+            mappings: HashMap::new(),
         }
     }
 }
 
+/// An emoji known to the parser, with the commit type it maps to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitmojiMapping {
+    /// The emoji.
+    pub emoji: String,
+    /// The text code of a built-in gitmoji, like `:bug:`.
+    pub code: Option<&'static str>,
+    /// The commit type inferred from the emoji.
+    pub commit_type: String,
+    /// Whether the mapping comes from the configuration.
+    pub custom: bool,
+}
+
 /// Conventional Commits + Gitmoji parser.
 ///
 /// Supports both emoji format (✨) and text format (:sparkles:).
 pub struct ConventionalGitmojiParser {
     conventional: ConventionalParser,
     config: GitmojiParserConfig,
+    /// Custom emojis indexed by their first character, longest first.
+    custom_index: HashMap<char, Vec<String>>,
 }
 
 impl ConventionalGitmojiParser {
     /// Creates a new parser with the default configuration.
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            conventional: ConventionalParser::new(),
-            config: GitmojiParserConfig::default(),
-        }
+        Self::with_config(GitmojiParserConfig::default())
     }
 
     /// Creates a new parser with custom configuration.
     #[must_use]
     pub fn with_config(config: GitmojiParserConfig) -> Self {
+        let mut custom_index: HashMap<char, Vec<String>> = HashMap::new();
+        for emoji in config.mappings.keys() {
+            if let Some(first) = emoji.chars().next() {
+                custom_index.entry(first).or_default().push(emoji.clone());
+            }
+        }
+        for emojis in custom_index.values_mut() {
+            emojis.sort_by_key(|emoji| std::cmp::Reverse(emoji.len()));
+        }
+
         Self {
             conventional: ConventionalParser::new(),
             config,
+            custom_index,
         }
     }
 
+    /// Returns the effective emoji mappings: the built-in gitmojis with the
+    /// configured overrides, followed by the configured additions.
+    #[must_use]
+    pub fn mappings(&self) -> Vec<GitmojiMapping> {
+        let mut mappings: Vec<_> = GITMOJIS
+            .iter()
+            .map(|gitmoji| {
+                let custom = self.config.mappings.get(gitmoji.emoji);
+                GitmojiMapping {
+                    emoji: gitmoji.emoji.to_string(),
+                    code: Some(gitmoji.code),
+                    commit_type: custom
+                        .map_or(gitmoji.commit_type, String::as_str)
+                        .to_string(),
+                    custom: custom.is_some(),
+                }
+            })
+            .collect();
+
+        let mut additions: Vec<_> = self
+            .config
+            .mappings
+            .iter()
+            .filter(|(emoji, _)| !EMOJI_MAP.contains_key(emoji.as_str()))
+            .map(|(emoji, commit_type)| GitmojiMapping {
+                emoji: emoji.clone(),
+                code: None,
+                commit_type: commit_type.clone(),
+                custom: true,
+            })
+            .collect();
+        additions.sort_by(|a, b| a.emoji.cmp(&b.emoji));
+        mappings.extend(additions);
+        mappings
+    }
+
+    /// Returns the commit type of an emoji, configured or built in.
+    fn commit_type(&self, emoji: &str) -> Option<&str> {
+        self.config
+            .mappings
+            .get(emoji)
+            .map(String::as_str)
+            .or_else(|| EMOJI_MAP.get(emoji).copied())
+    }
+
     /// Extracts gitmoji from the beginning of a string.
     ///
     /// Supports both emoji format (✨) and text format (:sparkles:).
-    fn extract_gitmoji(s: &str) -> Option<ExtractedGitmoji<'_>> {
+    fn extract_gitmoji<'a>(&self, s: &'a str) -> Option<ExtractedGitmoji<'a>> {
         // Try text code format first (:code:)
         if let Some(after_colon) = s.strip_prefix(':')
             && let Some(end) = after_colon.find(':')
@@ -568,9 +655,18 @@ impl ConventionalGitmojiParser {
             }
         }
 
-        // Try known emojis
-        for emoji in EMOJI_MAP.keys() {
-            if let Some(rest) = s.strip_prefix(emoji) {
+        // Try known emojis starting with the same character, the longest
+        // configured or built-in one winning
+        if let Some(first) = s.chars().next() {
+            let custom = self.custom_index.get(&first).into_iter().flatten();
+            let builtin = EMOJI_INDEX.get(&first).into_iter().flatten();
+            let longest = custom
+                .map(String::as_str)
+                .chain(builtin.copied())
+                .filter(|emoji| s.starts_with(emoji))
+                .max_by_key(|emoji| emoji.len());
+            if let Some(emoji) = longest {
+                let (emoji, rest) = s.split_at(emoji.len());
                 return Some(ExtractedGitmoji {
                     emoji,
                     rest: rest.trim_start(),
@@ -653,16 +749,18 @@ impl CommitParser for ConventionalGitmojiParser {
         let subject = raw.subject();
 
         // Try to extract gitmoji
-        if let Some(extracted) = Self::extract_gitmoji(subject) {
+        if let Some(extracted) = self.extract_gitmoji(subject) {
             // Check if emoji is known (if strict mode)
-            let is_known = EMOJI_MAP.contains_key(extracted.emoji);
+            let is_known = self.commit_type(extracted.emoji).is_some();
             if self.config.strict_emoji && !is_known {
                 return None;
             }
 
-            // Create a modified raw commit without emoji for conventional parsing
+            // Create a modified raw commit without emoji for conventional parsing,
+            // keeping the body for its footers
+            let message = format!("{}{}", extracted.rest, &raw.message[subject.len()..]);
             let modified_raw =
-                RawCommit::new(&raw.hash, extracted.rest, &raw.author, &raw.email, raw.date);
+                RawCommit::new(&raw.hash, message, &raw.author, &raw.email, raw.date);
 
             // Try conventional parsing on the rest
             if let Some(mut parsed) = self.conventional.parse(&modified_raw) {
@@ -672,12 +770,16 @@ impl CommitParser for ConventionalGitmojiParser {
 
             // If conventional parsing fails and infer_type_from_emoji is enabled
             if self.config.infer_type_from_emoji
-                && let Some(&commit_type) = EMOJI_MAP.get(extracted.emoji)
+                && let Some(commit_type) = self.commit_type(extracted.emoji)
             {
                 return Some(
                     ParsedCommit::builder(&raw.hash, commit_type)
                         .message(extracted.rest)
                         .emoji(extracted.emoji)
+                        .closed_issues(&raw.closed_issues())
+                        .co_authors(&raw.co_authors())
+                        .changelog_entry(raw.changelog_entry())
+                        .breaking_change(raw.breaking_change().as_deref())
                         .author(&raw.author)
                         .date(raw.date)
                         .build(),
@@ -710,6 +812,31 @@ mod tests {
         assert_eq!(parsed.message, "add new endpoint");
     }
 
+    #[test]
+    fn test_closed_issues() {
+        let parser = ConventionalGitmojiParser::new();
+
+        let raw = make_raw("🐛 fix: handle timeouts\n\nCloses #12");
+        let parsed = parser.parse(&raw).unwrap();
+        assert_eq!(parsed.message, "handle timeouts");
+        assert_eq!(parsed.closed_issues(), vec!["12"]);
+
+        let raw = make_raw("🐛 handle timeouts\n\nFixes #34");
+        let parsed = parser.parse(&raw).unwrap();
+        assert_eq!(parsed.closed_issues(), vec!["34"]);
+    }
+
+    #[test]
+    fn test_co_authors() {
+        let parser = ConventionalGitmojiParser::new();
+
+        let raw = make_raw("✨ feat: add cache\n\nCo-authored-by: Jane Doe <jane@example.com>");
+        assert_eq!(parser.parse(&raw).unwrap().co_authors(), vec!["Jane Doe"]);
+
+        let raw = make_raw("✨ add cache\n\nCo-authored-by: Bob <bob@example.com>");
+        assert_eq!(parser.parse(&raw).unwrap().co_authors(), vec!["Bob"]);
+    }
+
     #[test]
     fn test_text_code_with_conventional() {
         let parser = ConventionalGitmojiParser::new();
@@ -833,6 +960,7 @@ mod tests {
         let config = GitmojiParserConfig {
             infer_type_from_emoji: true,
             strict_emoji: true,
+            mappings: HashMap::new(),
         };
         let parser = ConventionalGitmojiParser::with_config(config);
         // Unknown emoji should fail in strict mode
@@ -845,6 +973,7 @@ mod tests {
         let config = GitmojiParserConfig {
             infer_type_from_emoji: false,
             strict_emoji: false,
+            mappings: HashMap::new(),
         };
         let parser = ConventionalGitmojiParser::with_config(config);
         // Should not infer type from emoji alone
@@ -897,6 +1026,131 @@ mod tests {
         assert_eq!(parsed.r#type, "fix");
     }
 
+    #[test]
+    fn test_extract_prefers_variation_selector() {
+        let extracted = ConventionalGitmojiParser::new()
+            .extract_gitmoji("🚑️ fix: urgent bug")
+            .unwrap();
+        assert_eq!(extracted.emoji, "🚑️");
+        assert_eq!(extracted.rest, "fix: urgent bug");
+
+        let extracted = ConventionalGitmojiParser::new()
+            .extract_gitmoji("🚑 fix: urgent bug")
+            .unwrap();
+        assert_eq!(extracted.emoji, "🚑");
+        assert_eq!(extracted.rest, "fix: urgent bug");
+    }
+
+    #[test]
+    fn test_extract_zwj_sequence() {
+        let extracted = ConventionalGitmojiParser::new()
+            .extract_gitmoji("🧑‍💻 improve dx")
+            .unwrap();
+        assert_eq!(extracted.emoji, "🧑‍💻");
+        assert_eq!(extracted.rest, "improve dx");
+
+        let parser = ConventionalGitmojiParser::new();
+        let parsed = parser.parse(&make_raw("🧑‍💻 improve dx")).unwrap();
+        assert_eq!(parsed.r#type, "dx");
+    }
+
+    #[test]
+    fn test_extract_unknown_emoji_with_modifiers() {
+        let extracted = ConventionalGitmojiParser::new()
+            .extract_gitmoji("🦄\u{FE0F} add unicorn")
+            .unwrap();
+        assert_eq!(extracted.emoji, "🦄\u{FE0F}");
+        assert_eq!(extracted.rest, "add unicorn");
+    }
+
+    #[test]
+    fn test_extract_every_known_emoji() {
+        for gitmoji in GITMOJIS {
+            let subject = format!("{} message", gitmoji.emoji);
+            let extracted = ConventionalGitmojiParser::new()
+                .extract_gitmoji(&subject)
+                .unwrap();
+            assert_eq!(extracted.emoji, gitmoji.emoji);
+            assert_eq!(extracted.rest, "message");
+        }
+    }
+
+    fn custom_parser(mappings: &[(&str, &str)]) -> ConventionalGitmojiParser {
+        ConventionalGitmojiParser::with_config(GitmojiParserConfig {
+            mappings: mappings
+                .iter()
+                .map(|(emoji, commit_type)| ((*emoji).to_string(), (*commit_type).to_string()))
+                .collect(),
+            ..GitmojiParserConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_custom_mapping_extends_builtins() {
+        let parser = custom_parser(&[("🧿", "fix")]);
+        let parsed = parser.parse(&make_raw("🧿 ward off bugs")).unwrap();
+        assert_eq!(parsed.r#type, "fix");
+        assert_eq!(parsed.emoji.as_deref(), Some("🧿"));
+
+        let parsed = parser.parse(&make_raw("✨ add feature")).unwrap();
+        assert_eq!(parsed.r#type, "feat");
+    }
+
+    #[test]
+    fn test_custom_mapping_overrides_builtin() {
+        let parser = custom_parser(&[("🎉", "chore")]);
+        let parsed = parser.parse(&make_raw("🎉 begin project")).unwrap();
+        assert_eq!(parsed.r#type, "chore");
+
+        let parsed = parser.parse(&make_raw(":tada: begin project")).unwrap();
+        assert_eq!(parsed.r#type, "chore");
+    }
+
+    #[test]
+    fn test_custom_mapping_known_in_strict_mode() {
+        let parser = ConventionalGitmojiParser::with_config(GitmojiParserConfig {
+            strict_emoji: true,
+            mappings: HashMap::from([("🦄".to_string(), "feat".to_string())]),
+            ..GitmojiParserConfig::default()
+        });
+        assert!(parser.parse(&make_raw("🦄 add unicorns")).is_some());
+        assert!(parser.parse(&make_raw("🐲 add dragons")).is_none());
+    }
+
+    #[test]
+    fn test_custom_mapping_longest_match() {
+        // A custom ZWJ sequence starting with a built-in emoji
+        let parser = custom_parser(&[("🔥\u{200D}🚒", "fix")]);
+        let parsed = parser
+            .parse(&make_raw("🔥\u{200D}🚒 put out fire"))
+            .unwrap();
+        assert_eq!(parsed.r#type, "fix");
+
+        let parsed = parser.parse(&make_raw("🔥 drop code")).unwrap();
+        assert_eq!(parsed.r#type, "chore");
+    }
+
+    #[test]
+    fn test_mappings() {
+        let parser = custom_parser(&[("🎉", "chore"), ("🧿", "fix")]);
+        let mappings = parser.mappings();
+        assert_eq!(mappings.len(), GITMOJIS.len() + 1);
+
+        let tada = mappings.iter().find(|m| m.emoji == "🎉").unwrap();
+        assert_eq!(tada.code, Some(":tada:"));
+        assert_eq!(tada.commit_type, "chore");
+        assert!(tada.custom);
+
+        let sparkles = mappings.iter().find(|m| m.emoji == "✨").unwrap();
+        assert_eq!(sparkles.commit_type, "feat");
+        assert!(!sparkles.custom);
+
+        let nazar = mappings.last().unwrap();
+        assert_eq!(nazar.emoji, "🧿");
+        assert_eq!(nazar.code, None);
+        assert!(nazar.custom);
+    }
+
     #[test]
     fn test_all_feature_emojis() {
         let parser = ConventionalGitmojiParser::new();
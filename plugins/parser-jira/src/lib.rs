@@ -0,0 +1,299 @@
+//! JIRA ticket-prefixed commit parser plugin.
+//!
+//! Parses subjects starting with JIRA ticket ids, like `PROJ-123: fix login`
+//! or `[PROJ-123] feat: add SSO`, capturing the tickets into metadata.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use unduler_commit::{ParsedCommit, RawCommit};
+use unduler_parser_conventional::ConventionalParser;
+use unduler_plugin::{CommitParser, Plugin};
+
+/// A JIRA ticket id, like `PROJ-123`.
+const TICKET: &str = r"[A-Z][A-Z0-9_]+-[0-9]+";
+
+static TICKET_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(TICKET).expect("invalid regex"));
+
+/// Subjects starting with bracketed tickets: `[PROJ-1] message` or
+/// `[PROJ-1][PROJ-2]: message`.
+static BRACKETED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^(?P<tickets>(?:\[\s*{TICKET}(?:\s*,\s*{TICKET})*\s*\]\s*)+):?\s*(?P<rest>.+)$"
+    ))
+    .expect("invalid regex")
+});
+
+/// Subjects starting with tickets and a colon or a space: `PROJ-1: message`,
+/// `PROJ-1 message` or `PROJ-1, PROJ-2: message`.
+static PREFIXED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"^(?P<tickets>{TICKET}(?:\s*,\s*{TICKET})*)(?:\s*:\s*|\s+)(?P<rest>.+)$"
+    ))
+    .expect("invalid regex")
+});
+
+/// Configuration for the JIRA parser.
+#[derive(Debug, Clone)]
+pub struct JiraParserConfig {
+    /// Project keys accepted in ticket ids, any project when empty.
+    pub projects: Vec<String>,
+    /// Commit types keyed by the first word of the message, in lowercase.
+    pub types: HashMap<String, String>,
+    /// Commit type of messages without an embedded or inferred type.
+    pub default_type: String,
+}
+
+impl Default for JiraParserConfig {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            types: HashMap::new(),
+            default_type: "chore".to_string(),
+        }
+    }
+}
+
+/// JIRA ticket-prefixed commit parser.
+///
+/// The message after the tickets is parsed as a Conventional Commit when it
+/// is one, and its type is inferred from its first word otherwise. Subjects
+/// without tickets are parsed as Conventional Commits.
+pub struct JiraParser {
+    conventional: ConventionalParser,
+    config: JiraParserConfig,
+}
+
+impl JiraParser {
+    /// Creates a new parser with the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_config(JiraParserConfig::default())
+    }
+
+    /// Creates a new parser with custom configuration.
+    #[must_use]
+    pub fn with_config(config: JiraParserConfig) -> Self {
+        Self {
+            conventional: ConventionalParser::new(),
+            config,
+        }
+    }
+
+    /// Splits the leading tickets from a subject, if it has any from the
+    /// accepted projects.
+    fn split_tickets<'a>(&self, subject: &'a str) -> Option<(Vec<&'a str>, &'a str)> {
+        let captures = BRACKETED_RE
+            .captures(subject)
+            .or_else(|| PREFIXED_RE.captures(subject))?;
+        let tickets: Vec<_> = TICKET_RE
+            .find_iter(captures.name("tickets")?.as_str())
+            .map(|ticket| ticket.as_str())
+            .collect();
+
+        let accepted = tickets.iter().all(|ticket| {
+            self.config.projects.is_empty()
+                || ticket
+                    .rsplit_once('-')
+                    .is_some_and(|(project, _)| self.config.projects.iter().any(|p| p == project))
+        });
+        accepted.then(|| (tickets, captures.name("rest").map_or("", |m| m.as_str())))
+    }
+
+    /// Infers the commit type of a message from its first word.
+    fn infer_type(&self, message: &str) -> &str {
+        message
+            .split_whitespace()
+            .next()
+            .and_then(|word| self.config.types.get(&word.to_lowercase()))
+            .unwrap_or(&self.config.default_type)
+    }
+}
+
+impl Default for JiraParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for JiraParser {
+    fn name(&self) -> &'static str {
+        "jira"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn description(&self) -> &'static str {
+        "Parses commits prefixed with JIRA tickets (PROJ-123: or [PROJ-123])"
+    }
+}
+
+impl CommitParser for JiraParser {
+    fn parse(&self, raw: &RawCommit) -> Option<ParsedCommit> {
+        let subject = raw.subject();
+        let Some((tickets, rest)) = self.split_tickets(subject) else {
+            return self.conventional.parse(raw);
+        };
+        let tickets = tickets.join(",");
+
+        // Parse the rest as a Conventional Commit, keeping the body for its footers
+        let message = format!("{rest}{}", &raw.message[subject.len()..]);
+        let stripped = RawCommit::new(&raw.hash, message, &raw.author, &raw.email, raw.date);
+        if let Some(mut parsed) = self.conventional.parse(&stripped) {
+            parsed
+                .metadata
+                .insert(ParsedCommit::TICKET_METADATA.to_string(), tickets);
+            return Some(parsed);
+        }
+
+        Some(
+            ParsedCommit::builder(&raw.hash, self.infer_type(rest))
+                .message(rest)
+                .metadata(ParsedCommit::TICKET_METADATA, tickets)
+                .closed_issues(&raw.closed_issues())
+                .co_authors(&raw.co_authors())
+                .changelog_entry(raw.changelog_entry())
+                .breaking_change(raw.breaking_change().as_deref())
+                .author(&raw.author)
+                .date(raw.date)
+                .build(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn make_raw(message: &str) -> RawCommit {
+        RawCommit::new("abc123", message, "Test", "test@test.com", Utc::now())
+    }
+
+    #[test]
+    fn test_prefixed_ticket() {
+        let parser = JiraParser::new();
+        let parsed = parser.parse(&make_raw("PROJ-123: fix login")).unwrap();
+
+        assert_eq!(parsed.r#type, "chore");
+        assert_eq!(parsed.message, "fix login");
+        assert_eq!(parsed.tickets(), vec!["PROJ-123"]);
+    }
+
+    #[test]
+    fn test_bracketed_ticket_with_conventional() {
+        let parser = JiraParser::new();
+        let parsed = parser
+            .parse(&make_raw("[PROJ-123] feat(auth): add SSO"))
+            .unwrap();
+
+        assert_eq!(parsed.r#type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("auth"));
+        assert_eq!(parsed.message, "add SSO");
+        assert_eq!(parsed.tickets(), vec!["PROJ-123"]);
+    }
+
+    #[test]
+    fn test_space_separated_ticket() {
+        let parser = JiraParser::new();
+        let parsed = parser
+            .parse(&make_raw("PROJ-123 feat(api): add endpoint"))
+            .unwrap();
+
+        assert_eq!(parsed.r#type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert_eq!(parsed.tickets(), vec!["PROJ-123"]);
+    }
+
+    #[test]
+    fn test_multiple_tickets() {
+        let parser = JiraParser::new();
+        let parsed = parser
+            .parse(&make_raw("[PROJ-1][OPS-2] fix: retry uploads"))
+            .unwrap();
+        assert_eq!(parsed.tickets(), vec!["PROJ-1", "OPS-2"]);
+
+        let parsed = parser
+            .parse(&make_raw("PROJ-1, PROJ-2: update docs"))
+            .unwrap();
+        assert_eq!(parsed.tickets(), vec!["PROJ-1", "PROJ-2"]);
+        assert_eq!(parsed.message, "update docs");
+    }
+
+    #[test]
+    fn test_type_inference() {
+        let parser = JiraParser::with_config(JiraParserConfig {
+            types: HashMap::from([
+                ("fix".to_string(), "fix".to_string()),
+                ("add".to_string(), "feat".to_string()),
+            ]),
+            ..JiraParserConfig::default()
+        });
+
+        let parsed = parser.parse(&make_raw("PROJ-1: Fix login")).unwrap();
+        assert_eq!(parsed.r#type, "fix");
+        let parsed = parser.parse(&make_raw("[PROJ-2] add SSO")).unwrap();
+        assert_eq!(parsed.r#type, "feat");
+        let parsed = parser.parse(&make_raw("PROJ-3: bump deps")).unwrap();
+        assert_eq!(parsed.r#type, "chore");
+    }
+
+    #[test]
+    fn test_default_type() {
+        let parser = JiraParser::with_config(JiraParserConfig {
+            default_type: "misc".to_string(),
+            ..JiraParserConfig::default()
+        });
+        let parsed = parser.parse(&make_raw("PROJ-1: tidy up")).unwrap();
+        assert_eq!(parsed.r#type, "misc");
+    }
+
+    #[test]
+    fn test_projects_filter() {
+        let parser = JiraParser::with_config(JiraParserConfig {
+            projects: vec!["PROJ".to_string()],
+            ..JiraParserConfig::default()
+        });
+        assert!(parser.parse(&make_raw("PROJ-1: fix login")).is_some());
+        assert!(parser.parse(&make_raw("UTF-8: handle encoding")).is_none());
+    }
+
+    #[test]
+    fn test_without_ticket() {
+        let parser = JiraParser::new();
+        let parsed = parser.parse(&make_raw("feat: add cache")).unwrap();
+        assert_eq!(parsed.r#type, "feat");
+        assert!(parsed.tickets().is_empty());
+
+        assert!(parser.parse(&make_raw("random commit")).is_none());
+        assert!(parser.parse(&make_raw("proj-1: lowercase key")).is_none());
+    }
+
+    #[test]
+    fn test_body_footers() {
+        let parser = JiraParser::new();
+        let parsed = parser
+            .parse(&make_raw(
+                "PROJ-1: drop legacy api\n\nBREAKING CHANGE: v1 is gone\nCloses #12",
+            ))
+            .unwrap();
+        assert!(parsed.breaking);
+        assert_eq!(parsed.closed_issues(), vec!["12"]);
+
+        let parsed = parser
+            .parse(&make_raw("[PROJ-1] fix: retry\n\nCloses #13"))
+            .unwrap();
+        assert_eq!(parsed.closed_issues(), vec!["13"]);
+    }
+
+    #[test]
+    fn test_plugin_metadata() {
+        let parser = JiraParser::default();
+        assert_eq!(parser.name(), "jira");
+        assert!(!parser.version().is_empty());
+        assert!(!parser.description().is_empty());
+    }
+}
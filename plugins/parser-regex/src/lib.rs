@@ -117,6 +117,10 @@ impl CommitParser for RegexParser {
         // Build commit
         let mut builder = ParsedCommit::builder(&raw.hash, commit_type)
             .message(message)
+            .closed_issues(&raw.closed_issues())
+            .co_authors(&raw.co_authors())
+            .changelog_entry(raw.changelog_entry())
+            .breaking_change(raw.breaking_change().as_deref())
             .author(&raw.author)
             .date(raw.date);
 
@@ -242,6 +246,24 @@ mod tests {
         assert_eq!(parsed.message, "resolve bug");
     }
 
+    #[test]
+    fn test_parse_closed_issues() {
+        let parser = RegexParser::new(simple_config()).unwrap();
+        let raw = make_raw("fix: resolve bug\n\nCloses #12");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert_eq!(parsed.closed_issues(), vec!["12"]);
+    }
+
+    #[test]
+    fn test_parse_co_authors() {
+        let parser = RegexParser::new(simple_config()).unwrap();
+        let raw = make_raw("fix: resolve bug\n\nCo-authored-by: Jane Doe <jane@example.com>");
+        let parsed = parser.parse(&raw).unwrap();
+
+        assert_eq!(parsed.co_authors(), vec!["Jane Doe"]);
+    }
+
     #[test]
     fn test_parse_no_match() {
         let parser = RegexParser::new(simple_config()).unwrap();